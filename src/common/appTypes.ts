@@ -0,0 +1,105 @@
+export type LogLevel = 'error' | 'warn' | 'info' | 'debug';
+
+export interface LogSettings {
+  level: LogLevel;
+  filePath: string | null;
+}
+
+/**
+ * Opt-in allow/deny rules for kiosk or shared setups. An entry is a command
+ * prefix ("kubectl delete") or a regex in slashes ("/--all-namespaces/").
+ */
+export interface CommandPolicy {
+  enabled: boolean;
+  /** When non-empty, only matching commands may run */
+  allow: string[];
+  /** Matching commands are blocked even if allowed */
+  deny: string[];
+}
+
+/**
+ * Opt-in: terminals may only start in these directories or below them.
+ * Paths are compared after resolving symlinks.
+ */
+export interface TerminalCwdPolicy {
+  enabled: boolean;
+  allowedRoots: string[];
+}
+
+export type SelfTestCheckId = 'kubectl' | 'kubeconfig' | 'context' | 'exec-plugin' | 'temp-dir';
+
+export type SelfTestStatus = 'pass' | 'warn' | 'fail' | 'skipped';
+
+export interface SelfTestCheck {
+  id: SelfTestCheckId;
+  label: string;
+  status: SelfTestStatus;
+  message: string;
+  /** What to do about a warning or failure */
+  hint: string | null;
+  durationMs: number;
+}
+
+export interface SelfTestReport {
+  /** No check failed */
+  ok: boolean;
+  /** Current context the cluster checks ran against */
+  context: string | null;
+  checks: SelfTestCheck[];
+  durationMs: number;
+}
+
+export type ActionLogAction =
+  | 'switch-context'
+  | 'kubectl'
+  | 'apply'
+  | 'delete'
+  | 'scale'
+  | 'kill-pods'
+  | 'rollout-undo'
+  | 'start-port-forward'
+  | 'stop-port-forward'
+  | 'start-api-proxy'
+  | 'stop-api-proxy'
+  | 'repair-kubeconfig'
+  | 'import-kubeconfig'
+  | 'patch'
+  | 'set-labels'
+  | 'set-annotations'
+  | 'update-configmap'
+  | 'rollout-pause'
+  | 'rollout-resume'
+  | 'trigger-cronjob'
+  | 'run-plugin'
+  | 'exec-stdin-file'
+  | 'generate-sa-kubeconfig';
+
+export type ActionLogOutcome = 'success' | 'failure';
+
+export interface ActionLogEntry {
+  id: string;
+  /** ISO time the operation finished */
+  timestamp: string;
+  action: ActionLogAction;
+  context: string | null;
+  namespace: string | null;
+  /** What was acted on, e.g. "deployment/web" or "svc/api:80" */
+  target: string | null;
+  outcome: ActionLogOutcome;
+  /** Command line or summary, with secret values masked */
+  detail: string | null;
+  error: string | null;
+}
+
+export interface ActionLogFilter {
+  action?: ActionLogAction;
+  context?: string;
+  namespace?: string;
+  outcome?: ActionLogOutcome;
+  /** ISO time; only entries at or after it */
+  since?: string;
+  /** Case-insensitive match on target, detail and error */
+  search?: string;
+  /** Newest entries first, at most this many (default 200) */
+  limit?: number;
+}
@@ -4,6 +4,36 @@ export interface KubeContext {
   user?: string;
   namespace?: string;
   server?: string;
+  tags?: ContextTags;
+}
+
+export interface ContextTags {
+  tags: string[];
+  /** Guessed from the context name and server (never tagged explicitly) */
+  detected: boolean;
+}
+
+/** A context's tags as stored; the server lets them follow a renamed context */
+export interface ContextTagEntry {
+  context: string;
+  server: string | null;
+  tags: string[];
+}
+
+export interface KubeContextMatch {
+  context: KubeContext;
+  /** Fuzzy ranking, higher is better; 0 for other modes */
+  score: number;
+  /** Indexes into the context name that matched */
+  positions: number[];
+}
+
+export interface ContextSearchResult {
+  pattern: string;
+  mode: 'regex' | 'glob' | 'substring' | 'fuzzy';
+  /** Matches before the limit was applied */
+  total: number;
+  matches: KubeContextMatch[];
 }
 
 export interface KubeConfigFile {
@@ -23,4 +53,1609 @@ export interface KubectlResult {
   stdout: string;
   stderr: string;
   code: number | null;
+  /** How many times kubectl ran when a retry policy was used */
+  attempts?: number;
+}
+
+export interface RetryPolicy {
+  maxAttempts: number;
+  baseDelayMs: number;
+  maxDelayMs: number;
+  /** Randomize each delay to avoid retrying in lockstep */
+  jitter: boolean;
+}
+
+export type KubeErrorCode =
+  | 'NotFound'
+  | 'Forbidden'
+  | 'Unauthorized'
+  | 'Unreachable'
+  | 'KubectlFailed'
+  | 'Cancelled'
+  | 'NoPreviousContainer'
+  | 'WebhookTimeout'
+  | 'CommandBlocked'
+  | 'RolloutUnsupported'
+  | 'Timeout'
+  | 'Conflict'
+  | 'PoolTimeout'
+  | 'StreamLimit'
+  | 'CwdNotAllowed'
+  | 'KubectlMismatch'
+  | 'KustomizeBuildFailed';
+
+export interface KubectlPoolLimits {
+  /** kubectl calls run at once; 0 for no limit */
+  maxConcurrent: number;
+  /** How long a call may wait for a free slot before failing with PoolTimeout */
+  queueTimeoutMs: number;
+  /** Long-running processes at once; 0 for no limit. They can't wait, so one more fails with StreamLimit */
+  maxStreaming: number;
+}
+
+export interface KubectlPoolStats extends KubectlPoolLimits {
+  active: number;
+  queued: number;
+  /** Long-running processes (watches, log follows, port-forwards); counted, not queued */
+  streaming: number;
+  /** Most calls waiting at once since launch */
+  peakQueued: number;
+  /** Calls that gave up waiting since launch */
+  timeouts: number;
+  /** Long-running processes refused at maxStreaming since launch */
+  streamsRefused: number;
+}
+
+export interface PodPhaseEvent {
+  watchId: string;
+  pod: string;
+  namespace: string;
+  phase: string;
+  readyContainers: number;
+  totalContainers: number;
+  deleted: boolean;
+}
+
+/** Where a log stream's lines go */
+export type LogSink = 'ui' | 'file' | 'both';
+
+export interface LogSinkStatus {
+  sink: LogSink;
+  /** The file being appended to; null when the sink is the UI only */
+  filePath: string | null;
+}
+
+export interface LogStreamSink extends LogSinkStatus {
+  streamId: string;
+}
+
+export type LogLevel = 'trace' | 'debug' | 'info' | 'warn' | 'error' | 'fatal';
+
+/**
+ * A regular expression (case-insensitive) that marks a line as `level`.
+ * Tried before the built-in patterns, in order.
+ */
+export interface LogLevelPattern {
+  level: LogLevel;
+  pattern: string;
+}
+
+export interface LogLevelSettings {
+  patterns: LogLevelPattern[];
+  /** Also try the built-in patterns (JSON, logfmt, klog, [LEVEL], LEVEL) */
+  builtins: boolean;
+}
+
+export interface LogLinesEvent {
+  streamId: string;
+  pod: string;
+  container: string | null;
+  lines: string[];
+  /** Detected level of each line, null when none was recognized */
+  levels: Array<LogLevel | null>;
+}
+
+/**
+ * Where a JSON log line keeps its level, message and timestamp. Each entry
+ * is a field name or dotted path ("log.level"); the first present wins.
+ */
+export interface JsonLogFieldMap {
+  level: string[];
+  message: string[];
+  timestamp: string[];
+}
+
+export interface JsonLogEntry {
+  /** The line as logged */
+  raw: string;
+  /** False for lines that aren't a JSON object; the other fields are then empty */
+  parsed: boolean;
+  level: string | null;
+  /** The mapped message, or the raw line when it isn't JSON */
+  message: string;
+  /** ISO timestamp when the field was a date or epoch number, else as logged */
+  timestamp: string | null;
+  /** The parsed object */
+  fields: Record<string, any> | null;
+}
+
+export interface JsonLogEvent {
+  streamId: string;
+  pod: string;
+  container: string | null;
+  entries: JsonLogEntry[];
+}
+
+export interface ApiResource {
+  name: string;
+  shortNames: string[];
+  apiVersion: string;
+  group: string;
+  namespaced: boolean;
+  kind: string;
+  verbs: string[];
+}
+
+export interface ResourcePage {
+  kind: string;
+  /** The plural resource name the kind resolved to, e.g. "pods" */
+  resource: string;
+  namespace: string | null;
+  items: any[];
+  /** Pass back for the next page; null after the last one */
+  continueToken: string | null;
+  /** Items after this page, when the server reports it */
+  remainingItemCount: number | null;
+  /** Items in the whole list; only known on the first page */
+  total: number | null;
+  resourceVersion: string | null;
+  /** The given token had expired and this is the first page again */
+  restarted: boolean;
+}
+
+export interface DiscoveryCacheStatus {
+  context: string;
+  server: string;
+  namespace: string;
+  user: string;
+  /** Null when `kubectl version` couldn't reach the server */
+  serverVersion: string | null;
+  /** Number of API resources cached */
+  apiResources: number;
+  fetchedAt: string;
+  expiresAt: string;
+}
+
+/**
+ * What a cloud provider's exec plugin needs in the environment to run
+ */
+export interface CloudAuthProfile {
+  /** e.g. eks, gke, aks */
+  provider: string;
+  /** Exec plugin commands (basenames) that identify the provider */
+  commands: string[];
+  /** Set when not already in the environment */
+  env: Record<string, string>;
+  /** Dirs appended to PATH if they exist; `~/` is the home dir */
+  path: string[];
+}
+
+export interface CloudAuthDetection {
+  context: string;
+  /** Exec plugin command from the kubeconfig, null when the user has none */
+  execCommand: string | null;
+  provider: string | null;
+  /** Variables that are added, including PATH when it was extended */
+  env: Record<string, string>;
+  addedPath: string[];
+}
+
+export interface CrdSchema {
+  group: string;
+  version: string;
+  kind: string;
+  /** e.g. certificates.cert-manager.io */
+  crdName: string;
+  namespaced: boolean;
+  served: boolean;
+  storage: boolean;
+  /** Every version the CRD declares */
+  versions: string[];
+  /** openAPIV3Schema; null when the CRD has none */
+  schema: any | null;
+}
+
+export interface FieldChange {
+  /** e.g. spec.template.spec.containers[name=app].image */
+  path: string;
+  /** added: only in b; removed: only in a */
+  change: 'added' | 'removed' | 'changed';
+  a: any;
+  b: any;
+}
+
+export interface DiffLine {
+  op: 'same' | 'added' | 'removed';
+  text: string;
+  /** 1-based line numbers on each side; null where the line isn't present */
+  lineA: number | null;
+  lineB: number | null;
+}
+
+export interface DiffHunk {
+  lines: DiffLine[];
+}
+
+export interface ResourceComparison {
+  kind: string;
+  name: string;
+  a: { context: string; namespace?: string; exists: boolean };
+  b: { context: string; namespace?: string; exists: boolean };
+  identical: boolean;
+  /** Field-level differences; empty when the object is missing on one side */
+  changes: FieldChange[];
+  /** YAML line diff; null when the objects are too large to diff */
+  hunks: DiffHunk[] | null;
+  unified: string | null;
+}
+
+export interface ResourceDrift {
+  kind: string;
+  name: string;
+  namespace?: string;
+  /** False when the object has no last-applied-configuration (never `kubectl apply`d) */
+  hasBaseline: boolean;
+  drifted: boolean;
+  /** a is the last-applied value, b the live one */
+  changes: FieldChange[];
+  hunks: DiffHunk[] | null;
+  unified: string | null;
+}
+
+/** An object's metadata after a label or annotation change */
+export interface ResourceMetadata {
+  name: string;
+  namespace?: string;
+  labels: Record<string, string>;
+  annotations: Record<string, string>;
+  resourceVersion: string | null;
+}
+
+export type PatchType = 'strategic' | 'merge' | 'json';
+
+export interface ManifestDiffResult {
+  inSync: boolean;
+  diff: string;
+}
+
+export type MetricsTargetKind = 'pod' | 'node';
+
+export interface MetricsSample {
+  timestamp: number;
+  cpuMillicores: number | null;
+  memoryBytes: number | null;
+}
+
+export interface MetricsUpdateEvent {
+  streamId: string;
+  timestamp: number;
+  samples: Record<string, MetricsSample>;
+}
+
+/**
+ * Tag attached to background kubectl work so it can be cancelled in bulk,
+ * e.g. everything for a context after switching away from it
+ */
+export interface OperationScope {
+  context?: string;
+  namespace?: string;
+  view?: string;
+}
+
+export type OperationKind = 'kubectl' | 'logs' | 'watches' | 'metrics';
+
+export type CancelledOperationCounts = Record<OperationKind, number>;
+
+export type BackgroundSessionKind = OperationKind | 'forward';
+
+export interface BackgroundSession {
+  id: string;
+  kind: BackgroundSessionKind;
+  label: string;
+  context: string | null;
+  namespace: string | null;
+  promotedAt: number;
+  /** The view showing the session's events; null while none is */
+  attachedView: string | null;
+  /** False once the watch, stream or forward has ended */
+  active: boolean;
+  /** Events held since the session was last attached */
+  bufferedEvents: number;
+  /** Held events dropped because the buffer was full */
+  droppedEvents: number;
+}
+
+export interface SessionReattachResult {
+  session: BackgroundSession;
+  /** Held events sent again, in their original order */
+  replayed: number;
+}
+
+export interface ClusterEvent {
+  streamId: string;
+  type: string;
+  reason: string;
+  message: string;
+  object: { kind: string; name: string; namespace?: string };
+  count: number;
+  /** True when this is a repeat of an event already reported (count went up) */
+  repeat: boolean;
+  firstTimestamp: string | null;
+  lastTimestamp: string | null;
+}
+
+export interface EventRateBucket {
+  type: string;
+  reason: string;
+  count: number;
+}
+
+export interface EventRateEvent {
+  monitorId: string;
+  windowMs: number;
+  /** Event occurrences within the window, repeats included */
+  total: number;
+  warnings: number;
+  /** Largest first */
+  buckets: EventRateBucket[];
+  timestamp: number;
+}
+
+export interface RestartLeaderboardEntry {
+  namespace: string;
+  pod: string;
+  /** Restarts of all the pod's containers, as kubectl's RESTARTS column */
+  restarts: number;
+  /** Restarts since the leaderboard started watching */
+  sessionRestarts: number;
+  /** The container that restarted most */
+  container: string | null;
+  /** e.g. CrashLoopBackOff, OOMKilled, Error */
+  reason: string | null;
+  lastRestartAt: string | null;
+}
+
+export interface RestartLeaderboard {
+  leaderboardId: string;
+  trackedPods: number;
+  /** Most restarts during the session first */
+  entries: RestartLeaderboardEntry[];
+  timestamp: number;
+}
+
+export interface RawApiResponse {
+  path: string;
+  ok: boolean;
+  /** Inferred from kubectl's error; null when it didn't name a reason */
+  status: number | null;
+  body: string;
+  /** The body was cut off at its size limit */
+  truncated: boolean;
+  isJson: boolean;
+  /** The parsed body when it is JSON */
+  json: unknown;
+  error: string | null;
+}
+
+export interface RawStreamDataEvent {
+  streamId: string;
+  data: string;
+  /** Complete JSON objects in this chunk; null when the stream isn't JSON */
+  objects: unknown[] | null;
+}
+
+export interface RawStreamEndedEvent {
+  streamId: string;
+  code: number | null;
+  status: number | null;
+  error: string | null;
+  /** Ended by stopRawStream rather than by the server */
+  stopped: boolean;
+}
+
+export interface PodSpreadNode {
+  node: string;
+  /** Null when the node has no zone label or nodes couldn't be listed */
+  zone: string | null;
+  pods: string[];
+  count: number;
+  ready: number;
+}
+
+export interface PodSpreadZone {
+  zone: string;
+  /** Nodes in the zone running at least one of the pods */
+  nodes: number;
+  count: number;
+}
+
+export interface PodSpread {
+  kind: string;
+  name: string;
+  namespace: string;
+  /** Running and pending pods; completed and terminating ones are left out */
+  totalPods: number;
+  /** Most pods first */
+  nodes: PodSpreadNode[];
+  zones: PodSpreadZone[];
+  /** Pods not yet assigned to a node */
+  unscheduled: string[];
+  maxPerNode: number;
+  /** Most minus fewest pods per node, counting empty schedulable nodes; null with fewer than two */
+  nodeSkew: number | null;
+  zoneSkew: number | null;
+  /** Nodes could be listed, so skews include nodes without pods */
+  nodesListed: boolean;
+}
+
+export type ForwardKind = 'api-proxy' | 'port-forward';
+
+export type ForwardStatus = 'starting' | 'running' | 'restarting';
+
+export interface ForwardInfo {
+  id: string;
+  kind: ForwardKind;
+  context: string;
+  namespace?: string;
+  /** What is being forwarded, e.g. "api-server" or "svc/web:80" */
+  target: string;
+  localPort: number | null;
+  /** Port on the pod/service side (port-forwards only) */
+  remotePort: number | null;
+  /** Pod traffic goes to, when known (kubectl doesn't report it for svc/deployment) */
+  backingPod: string | null;
+  status: ForwardStatus;
+  autoRestart: boolean;
+  startedAt: number;
+}
+
+export interface ForwardExitedEvent {
+  id: string;
+  message: string;
+}
+
+export type PortForwardTargetKind = 'pod' | 'service' | 'deployment';
+
+export interface ForwardStatusEvent {
+  id: string;
+  status: 'no-endpoints' | 'connection-error';
+  message: string;
+}
+
+export interface AdmissionDenial {
+  /** Webhook or ValidatingAdmissionPolicy that rejected the object, when named */
+  source: string | null;
+  message: string;
+}
+
+export type ServerDryRunOutcome = 'allowed' | 'denied' | 'invalid';
+
+export interface ServerDryRunResult {
+  /** `denied` means admission rejected it; `invalid` means a schema/validation error */
+  outcome: ServerDryRunOutcome;
+  /** Objects that passed, e.g. "deployment.apps/web configured (server dry run)" */
+  accepted: string[];
+  denials: AdmissionDenial[];
+  /** Schema and validation errors */
+  errors: string[];
+  /** `Warning:` lines, e.g. policies in warn/audit mode or deprecated APIs */
+  warnings: string[];
+}
+
+export interface TemplateApplyResult {
+  dryRun: boolean;
+  /** kubectl's per-object lines, e.g. "pod/debug created (server dry run)" */
+  applied: string[];
+  warnings: string[];
+  /** The manifest after substitution; only returned for dry runs */
+  rendered: string | null;
+}
+
+export interface ResourceRef {
+  kind: string;
+  name: string;
+  namespace?: string;
+}
+
+export interface ResourceYamlItem {
+  ref: ResourceRef;
+  ok: boolean;
+  error?: string;
+  code?: KubeErrorCode;
+}
+
+export interface ResourcesYamlResult {
+  /** Successfully fetched objects as a multi-document YAML stream */
+  yaml: string;
+  items: ResourceYamlItem[];
+}
+
+export interface KubectlPlugin {
+  name: string;
+  path: string;
+}
+
+export interface ResourcePermission {
+  /** Plural resource name, e.g. "deployments" ("*" for all) */
+  resource: string;
+  /** API group ("" for the core group) */
+  group: string;
+  /** Empty when the verbs apply to every object of the resource */
+  resourceNames: string[];
+  verbs: string[];
+}
+
+export interface NonResourcePermission {
+  urls: string[];
+  verbs: string[];
+}
+
+export interface EffectivePermissions {
+  context: string;
+  namespace: string;
+  resourceRules: ResourcePermission[];
+  nonResourceRules: NonResourcePermission[];
+  /** The API server could not evaluate every rule (e.g. webhook authorizers) */
+  incomplete: boolean;
+  fetchedAt: number;
+}
+
+export interface PodFileDataEvent {
+  streamId: string;
+  data: string;
+  /** Output was dropped because the renderer fell behind */
+  truncated: boolean;
+}
+
+export type PodFileErrorCode = 'FileNotFound' | 'TailNotFound' | KubeErrorCode;
+
+export interface PodFileErrorEvent {
+  streamId: string;
+  code: PodFileErrorCode;
+  message: string;
+}
+
+export interface PodFileEndEvent {
+  streamId: string;
+  exitCode: number | null;
+}
+
+export interface ContainerImageStatus {
+  name: string;
+  init: boolean;
+  image: string;
+  /** Resolved digest, empty until the image has been pulled */
+  imageID: string;
+  imagePullPolicy: string;
+  /** Waiting reason such as ImagePullBackOff or ErrImagePull */
+  reason: string | null;
+  message: string | null;
+}
+
+export interface NodeCondition {
+  type: string;
+  status: string;
+  reason: string | null;
+  message: string | null;
+  lastHeartbeatTime: string | null;
+  lastTransitionTime: string | null;
+}
+
+export interface NodeTaint {
+  key: string;
+  value: string | null;
+  effect: string;
+}
+
+export interface NodeStatus {
+  name: string;
+  conditions: NodeCondition[];
+  taints: NodeTaint[];
+  unschedulable: boolean;
+  capacity: Record<string, string>;
+  allocatable: Record<string, string>;
+  kubeletVersion: string;
+  internalIPs: string[];
+  externalIPs: string[];
+}
+
+/**
+ * What a live nodes view shows per node. Ready is null when the kubelet
+ * stopped reporting (condition Unknown).
+ */
+export interface NodeSummary {
+  name: string;
+  ready: boolean | null;
+  readyReason: string | null;
+  kubeletVersion: string;
+  /** Pressure conditions currently True, e.g. MemoryPressure */
+  pressures: string[];
+  unschedulable: boolean;
+}
+
+export interface NodeWatchEvent {
+  watchId: string;
+  type: 'upsert' | 'remove';
+  node: NodeSummary;
+}
+
+export interface NodeNotReadyEvent {
+  watchId: string;
+  name: string;
+  /** Null when the node went Unknown rather than False */
+  ready: false | null;
+  reason: string | null;
+  message: string | null;
+  since: string | null;
+}
+
+export type AuthMethod =
+  | 'client-certificate'
+  | 'token'
+  | 'exec'
+  | 'oidc'
+  | 'auth-provider'
+  | 'basic'
+  | 'none';
+
+/**
+ * How a context's user authenticates. Never includes the credential itself.
+ */
+export interface AuthStatus {
+  context: string;
+  user: string | null;
+  method: AuthMethod;
+  /** Exec plugin command, e.g. "aws" or "kubelogin" */
+  execCommand: string | null;
+  /** Name of the legacy auth-provider (gcp, azure, oidc) */
+  authProvider: string | null;
+  /** ISO timestamp when the credential expires, when it can be determined */
+  expiresAt: string | null;
+  expiresInSeconds: number | null;
+  /** Why the status is incomplete (unreadable cert file, opaque token, ...) */
+  note: string | null;
+}
+
+export interface UserIdentity {
+  context: string;
+  /** Null when neither the server nor the kubeconfig tells */
+  username: string | null;
+  uid: string | null;
+  groups: string[];
+  extra: Record<string, string[]>;
+  /** Name of the kubeconfig users entry the context uses */
+  kubeconfigUser: string | null;
+  /** True when the API server confirmed it; false when read from the kubeconfig */
+  verified: boolean;
+  /** Why the identity is unverified */
+  note: string | null;
+}
+
+export interface ServicePort {
+  name: string | null;
+  protocol: string;
+  port: number;
+  /** Number or named container port; null when it defaults to port */
+  targetPort: number | string | null;
+  nodePort: number | null;
+}
+
+export interface EndpointPort {
+  name: string | null;
+  port: number | null;
+  protocol: string;
+}
+
+export interface EndpointAddress {
+  ip: string;
+  ready: boolean;
+  /** The pod is shutting down (EndpointSlice only) */
+  terminating: boolean;
+  /** Usually Pod */
+  targetKind: string | null;
+  targetName: string | null;
+  nodeName: string | null;
+  zone: string | null;
+  ports: EndpointPort[];
+}
+
+export interface ServiceEndpoints {
+  name: string;
+  namespace: string;
+  type: string;
+  /** Null for headless and ExternalName Services */
+  clusterIP: string | null;
+  headless: boolean;
+  externalName: string | null;
+  selector: Record<string, string> | null;
+  ports: ServicePort[];
+  source: 'EndpointSlice' | 'Endpoints';
+  endpoints: EndpointAddress[];
+  readyCount: number;
+  notReadyCount: number;
+  /** Why traffic can't reach any pod, e.g. no pods match the selector */
+  problem: string | null;
+}
+
+export interface IngressBackend {
+  /** Null for a resource backend */
+  service: string | null;
+  /** Port number or name */
+  port: number | string | null;
+  /** e.g. a StorageBucket behind a cloud load balancer */
+  resource: { apiGroup: string | null; kind: string; name: string } | null;
+}
+
+export interface IngressPath {
+  path: string;
+  /** Prefix, Exact or ImplementationSpecific; null on older APIs */
+  pathType: string | null;
+  backend: IngressBackend;
+}
+
+export interface IngressRule {
+  /** Null matches any host */
+  host: string | null;
+  paths: IngressPath[];
+}
+
+export interface IngressRouting {
+  name: string;
+  namespace: string;
+  apiVersion: string;
+  /** From spec.ingressClassName or the legacy kubernetes.io/ingress.class annotation */
+  ingressClass: string | null;
+  defaultBackend: IngressBackend | null;
+  rules: IngressRule[];
+  tls: { hosts: string[]; secretName: string | null }[];
+  /** Load balancer IPs or hostnames assigned by the controller */
+  addresses: string[];
+}
+
+export interface ResourceWatchEvent {
+  watchId: string;
+  type: 'ADDED' | 'MODIFIED' | 'DELETED';
+  name: string;
+  namespace?: string;
+  object: any;
+}
+
+/**
+ * Cluster-wide counts for a dashboard header. A count is null when it
+ * couldn't be read (e.g. the user may not list that kind).
+ */
+export interface ResourceSummary {
+  context: string;
+  pods: { total: number; byPhase: Record<string, number> } | null;
+  deployments: number | null;
+  services: number | null;
+  nodes: { ready: number; total: number } | null;
+}
+
+export interface DeletePreview {
+  /** Pass back as `confirmToken` to delete exactly these objects */
+  confirmToken: string;
+  expiresAt: number;
+  items: ResourceRef[];
+}
+
+export interface DeleteItemResult {
+  ref: ResourceRef;
+  ok: boolean;
+  error?: string;
+  code?: KubeErrorCode;
+}
+
+export interface ResourceDeletedEvent extends DeleteItemResult {
+  confirmToken: string;
+}
+
+export interface PodKilledEvent {
+  killId: string;
+  pod: string;
+  namespace: string;
+  /** already-terminating: being deleted (or gone) before we got to it */
+  outcome: 'killed' | 'already-terminating' | 'failed';
+  error?: string;
+}
+
+export interface KillPodsResult {
+  killId: string;
+  dryRun: boolean;
+  /** Pods the selector matched */
+  matched: string[];
+  killed: number;
+  skipped: number;
+  failed: number;
+}
+
+export type WorkloadKind = 'deployment' | 'statefulset' | 'daemonset';
+
+export interface WorkloadHealth {
+  kind: WorkloadKind;
+  name: string;
+  namespace: string;
+  desired: number;
+  ready: number;
+  available: number;
+  /** Replicas already running the latest pod template */
+  updated: number;
+  /** A rollout is still in progress (new template not fully rolled out) */
+  rollingOut: boolean;
+  /** Rollout paused with `kubectl rollout pause` (deployments only) */
+  paused: boolean;
+  healthy: boolean;
+  /** Reason from a failing condition, e.g. ProgressDeadlineExceeded */
+  reason: string | null;
+  message: string | null;
+}
+
+export interface WorkloadHealthReport {
+  context: string;
+  namespace: string | null;
+  workloads: WorkloadHealth[];
+  /** Kinds that couldn't be listed, with the error; the others are still reported */
+  errors: Partial<Record<WorkloadKind, string>>;
+}
+
+/**
+ * One row of the live pods table, matching `kubectl get pods -o wide`
+ */
+export interface PodTableRow {
+  name: string;
+  namespace: string;
+  /** e.g. "1/2" */
+  ready: string;
+  /** Display status as kubectl shows it (CrashLoopBackOff, Terminating, ...) */
+  status: string;
+  restarts: number;
+  /** Creation time; age is derived from it so rows aren't re-sent as they age */
+  createdAt: string | null;
+  node: string | null;
+  ip: string | null;
+}
+
+export interface PodTableUpdateEvent {
+  watchId: string;
+  upserts: PodTableRow[];
+  removed: { name: string; namespace: string }[];
+}
+
+export interface ContainerTermination {
+  reason: string | null;
+  exitCode: number | null;
+  signal: number | null;
+  startedAt: string | null;
+  finishedAt: string | null;
+}
+
+export interface ContainerRestartInfo {
+  name: string;
+  init: boolean;
+  restartCount: number;
+  state: 'running' | 'waiting' | 'terminated' | 'unknown';
+  /** Waiting/terminated reason of the current state, e.g. CrashLoopBackOff */
+  stateReason: string | null;
+  /** How the previous instance ended */
+  lastTermination: ContainerTermination | null;
+  /** When the container last restarted; null if it never has */
+  lastRestartAt: string | null;
+}
+
+export interface ProbeDefinition {
+  type: 'httpGet' | 'tcpSocket' | 'grpc' | 'exec' | 'unknown';
+  path: string | null;
+  /** HTTP or HTTPS */
+  scheme: string | null;
+  host: string | null;
+  /** Resolved from the container's ports when the probe names one */
+  port: number | null;
+  portName: string | null;
+  command: string[] | null;
+  grpcService: string | null;
+  initialDelaySeconds: number;
+  periodSeconds: number;
+  timeoutSeconds: number;
+  successThreshold: number;
+  failureThreshold: number;
+}
+
+export interface ContainerProbes {
+  name: string;
+  init: boolean;
+  ready: boolean;
+  /** Startup probe passed; null when the kubelet doesn't report it */
+  started: boolean | null;
+  liveness: ProbeDefinition | null;
+  readiness: ProbeDefinition | null;
+  startup: ProbeDefinition | null;
+}
+
+export type PodQosClass = 'Guaranteed' | 'Burstable' | 'BestEffort';
+
+/** As written in the spec (null when unset), and parsed */
+export interface ResourceValues {
+  cpu: string | null;
+  memory: string | null;
+  cpuMillicores: number | null;
+  memoryBytes: number | null;
+}
+
+export interface ContainerResources {
+  name: string;
+  init: boolean;
+  /** Init container with restartPolicy Always, running alongside the app */
+  sidecar: boolean;
+  requests: ResourceValues;
+  limits: ResourceValues;
+}
+
+export interface PodResources {
+  pod: string;
+  namespace: string;
+  qosClass: PodQosClass;
+  containers: ContainerResources[];
+}
+
+export type ApplyObjectOutcome = 'created' | 'configured' | 'unchanged' | 'error';
+
+export interface ApplyObjectEvent {
+  applyId: string;
+  /** 0-based position of the document in the manifest */
+  index: number;
+  total: number;
+  kind: string;
+  name: string;
+  namespace?: string;
+  outcome: ApplyObjectOutcome;
+  /** kubectl's line for the object, or the error */
+  message: string;
+}
+
+export interface NamespaceExportResult {
+  outputDir: string;
+  /** One file per object */
+  files: { kind: string; name: string; path: string }[];
+  /** Kind -> why it couldn't be listed */
+  errors: Record<string, string>;
+  /** Objects left out because a controller recreates them, e.g. pods of a Deployment */
+  skipped: number;
+}
+
+export interface DirectoryApplyFileResult {
+  file: string;
+  /** Kinds in the file, in document order */
+  kinds: string[];
+  outcome: ApplyObjectOutcome;
+  message: string;
+}
+
+export interface DirectoryApplyResult {
+  /** In the order applied */
+  files: DirectoryApplyFileResult[];
+  applied: number;
+  failed: number;
+}
+
+export interface ApplyDoneEvent {
+  applyId: string;
+  total: number;
+  created: number;
+  configured: number;
+  unchanged: number;
+  failed: number;
+  /** Not attempted because an earlier object failed or the apply was cancelled */
+  skipped: number;
+  cancelled: boolean;
+}
+
+export interface KustomizeApplyResult {
+  /** The overlay directory, resolved */
+  directory: string;
+  /** Id of the apply's events; null for a build-only run */
+  applyId: string | null;
+  total: number;
+  /** What the build produced, in apply order */
+  objects: Array<{ kind: string; name: string; namespace?: string }>;
+  /** The rendered manifest, for build-only runs */
+  manifest: string | null;
+}
+
+export type RolloutKind = 'deployment' | 'statefulset' | 'daemonset';
+
+export interface RolloutRevision {
+  revision: number;
+  /** `kubernetes.io/change-cause` annotation, when set */
+  changeCause: string | null;
+  createdAt: string | null;
+  images: string[];
+  /** ReplicaSet or ControllerRevision holding this revision */
+  source: string;
+}
+
+export interface RolloutHistory {
+  kind: RolloutKind;
+  name: string;
+  namespace: string;
+  currentRevision: number | null;
+  /** Oldest first */
+  revisions: RolloutRevision[];
+}
+
+export interface RevisionManifest {
+  kind: RolloutKind;
+  name: string;
+  namespace: string;
+  revision: number;
+  /** ReplicaSet or ControllerRevision holding this revision */
+  source: string;
+  sourceKind: 'ReplicaSet' | 'ControllerRevision';
+  /** The pod template this revision deployed */
+  template: any;
+  yaml: string;
+}
+
+export interface RolloutPauseState {
+  kind: RolloutKind;
+  name: string;
+  namespace: string;
+  paused: boolean;
+}
+
+export interface RolloutProgressEvent {
+  rolloutId: string;
+  /** A line from `kubectl rollout status`, or the final result */
+  message: string;
+  done: boolean;
+  /** Set when done: whether the rollout completed */
+  ok?: boolean;
+}
+
+export type LatencySampleStatus = 'ok' | 'timeout' | 'unreachable' | 'forbidden' | 'unauthorized' | 'error';
+
+export interface LatencySample {
+  probeId: string;
+  timestamp: number;
+  /** Round trip of the probe call, including kubectl start-up */
+  latencyMs: number;
+  status: LatencySampleStatus;
+  message?: string;
+}
+
+export interface ContextOverrides {
+  /** Like `kubectl --cluster` */
+  cluster?: string;
+  /** Like `kubectl --user` */
+  user?: string;
+  /** Like `kubectl -n` */
+  namespace?: string;
+}
+
+/**
+ * What kubectl actually connects to for a context, after following the
+ * context -> cluster/user references and applying overrides
+ */
+export interface ResolvedContext {
+  context: string;
+  kubeconfigPath: string;
+  isCurrent: boolean;
+  cluster: string;
+  server: string;
+  user: string;
+  namespace: string;
+  /** Where each value came from */
+  sources: {
+    cluster: 'context' | 'override';
+    user: 'context' | 'override';
+    namespace: 'context' | 'override' | 'default';
+  };
+  tlsServerName: string | null;
+  proxyUrl: string | null;
+  insecureSkipTlsVerify: boolean;
+  kubectlBinary: string;
+}
+
+export interface ServiceAccountKubeconfig {
+  path: string;
+  /** The context the file defines and selects */
+  context: string;
+  serviceAccount: string;
+  namespace: string;
+  /** "token-request" for a short-lived token, "secret" for a legacy token Secret's */
+  tokenSource: 'token-request' | 'secret';
+  /** ISO time the token stops working; null when it doesn't expire */
+  expiresAt: string | null;
+}
+
+export type ClusterCertState = 'ok' | 'expiring' | 'expired' | 'unreachable' | 'not-tls';
+
+export interface ClusterCertStatus {
+  context: string;
+  server: string;
+  state: ClusterCertState;
+  subject: string | null;
+  issuer: string | null;
+  validFrom: string | null;
+  validTo: string | null;
+  /** Negative once expired */
+  daysRemaining: number | null;
+  /** Whether the certificate verifies against the kubeconfig CA (or system roots); null if not checked */
+  trusted: boolean | null;
+  verificationError: string | null;
+  /** kubectl doesn't verify this cluster's certificate at all */
+  insecureSkipTlsVerify: boolean;
+  /** Why the state isn't "ok" */
+  message: string | null;
+}
+
+export type KubeconfigProblemType = 'missing-current-context' | 'dangling-cluster' | 'dangling-user';
+
+export interface KubeconfigProblem {
+  type: KubeconfigProblemType;
+  /** File that defines the broken entry */
+  file: string;
+  context: string | null;
+  /** The missing cluster or user name; null when none is set */
+  reference: string | null;
+  message: string;
+}
+
+export interface KubeconfigValidation {
+  /** Kubeconfig files checked, in merge order */
+  files: string[];
+  currentContext: string | null;
+  valid: boolean;
+  problems: KubeconfigProblem[];
+}
+
+export type KubeconfigRepairStrategy = 'clear-current-context' | 'prune-dangling-contexts';
+
+export interface KubeconfigRepairResult {
+  strategy: KubeconfigRepairStrategy;
+  changes: string[];
+  /** Copies of each file taken before it was changed */
+  backups: string[];
+  /** Validation after the repair */
+  report: KubeconfigValidation;
+}
+
+export interface KubeconfigImportEntry {
+  /** Name in the imported file */
+  name: string;
+  /** Name in the active kubeconfig */
+  importedAs: string;
+  /** "unchanged": the active kubeconfig already has an identical entry */
+  action: 'added' | 'renamed' | 'unchanged';
+}
+
+export interface KubeconfigImportResult {
+  source: string;
+  target: string;
+  /** False for a preview */
+  merged: boolean;
+  contexts: KubeconfigImportEntry[];
+  clusters: KubeconfigImportEntry[];
+  users: KubeconfigImportEntry[];
+  /** Set as current-context because the target had none */
+  currentContext: string | null;
+  backup: string | null;
+}
+
+export interface AllContainerLogs {
+  /** Container name -> last lines, in pod spec order (init containers first) */
+  logs: Record<string, string[]>;
+  /** Containers whose logs couldn't be read */
+  errors: Record<string, string>;
+  /** Containers that haven't started yet, so have no logs */
+  waiting: string[];
+  /** Some containers' output was cut to stay within the size limit */
+  truncated: boolean;
+}
+
+export interface LogGrepMatch {
+  pod: string;
+  container: string;
+  /** 1-based, within the fetched tail */
+  lineNumber: number;
+  line: string;
+  before: string[];
+  after: string[];
+}
+
+export interface LogGrepResult {
+  matches: LogGrepMatch[];
+  /** Pods and containers searched */
+  pods: number;
+  containers: number;
+  /** "pod/container" -> why its logs couldn't be read */
+  errors: Record<string, string>;
+  /** More matches were found than the result limits allow */
+  truncated: boolean;
+}
+
+export interface MergedLogLine {
+  container: string;
+  /** RFC3339 with the kubelet's precision; null if the line had none */
+  timestamp: string | null;
+  /** Includes any untimestamped continuation lines, newline-separated */
+  message: string;
+}
+
+export interface MergedLogs {
+  /** Oldest first across all containers */
+  lines: MergedLogLine[];
+  errors: Record<string, string>;
+  waiting: string[];
+  truncated: boolean;
+}
+
+export interface LogExportResult {
+  /** The gzip-compressed log file */
+  path: string;
+  lines: number;
+  /** Size on disk, compressed */
+  bytes: number;
+  /** Stopped at `until` rather than at the end of the container's logs */
+  reachedUntil: boolean;
+}
+
+export interface ConfigMapEntry {
+  key: string;
+  /** From binaryData; the value is base64 */
+  binary: boolean;
+  value: string;
+  /** Full size of the value, decoded for binary keys */
+  bytes: number;
+  /** Only the start of a large value is included */
+  truncated: boolean;
+}
+
+export interface ConfigMapContents {
+  name: string;
+  namespace: string;
+  resourceVersion: string | null;
+  /** Immutable ConfigMaps can't be updated, only replaced */
+  immutable: boolean;
+  entries: ConfigMapEntry[];
+}
+
+export interface FailingJobPod {
+  name: string;
+  /** e.g. Error, OOMKilled, DeadlineExceeded */
+  reason: string | null;
+  message: string | null;
+  exitCode: number | null;
+}
+
+export type JobState = 'running' | 'complete' | 'failed';
+
+export interface JobStatusEvent {
+  watchId: string;
+  name: string;
+  namespace: string;
+  state: JobState;
+  /** Pod counts */
+  active: number;
+  succeeded: number;
+  failed: number;
+  completions: number | null;
+  startTime: string | null;
+  completionTime: string | null;
+  /** From the Failed condition, e.g. BackoffLimitExceeded */
+  reason: string | null;
+  message: string | null;
+  /** Latest failed pod, once any pod has failed */
+  failingPod: FailingJobPod | null;
+  deleted: boolean;
+}
+
+export type DeploymentConditionType = 'Available' | 'Progressing' | 'ReplicaFailure';
+
+export type DeploymentConditionStatus = 'True' | 'False' | 'Unknown';
+
+export interface DeploymentReplicas {
+  desired: number;
+  updated: number;
+  ready: number;
+  available: number;
+  unavailable: number;
+}
+
+export interface DeploymentConditionEvent {
+  watchId: string;
+  name: string;
+  namespace: string;
+  /** Null for the deletion event */
+  type: DeploymentConditionType | null;
+  /** Null when the condition was removed */
+  status: DeploymentConditionStatus | null;
+  /** Null on the first event for the condition */
+  previousStatus: DeploymentConditionStatus | null;
+  /** E.g. NewReplicaSetAvailable, ProgressDeadlineExceeded, FailedCreate */
+  reason: string | null;
+  message: string | null;
+  lastTransitionTime: string | null;
+  generation: number | null;
+  observedGeneration: number | null;
+  replicas: DeploymentReplicas;
+  deleted: boolean;
+}
+
+/**
+ * A log stream that ended on its own, e.g. a Job's once it finished and
+ * its pods' logs were read to the end
+ */
+export interface LogStreamEndedEvent {
+  streamId: string;
+  reason: JobState | 'deleted';
+}
+
+export interface PvcStatusEvent {
+  watchId: string;
+  name: string;
+  namespace: string;
+  /** Pending, Bound or Lost */
+  phase: string;
+  volumeName: string | null;
+  /** Bound capacity, e.g. "10Gi" */
+  capacity: string | null;
+  storageClass: string | null;
+  /** Why a Pending claim isn't bound yet, from its events */
+  reason: string | null;
+  message: string | null;
+  deleted: boolean;
+}
+
+export interface StorageClassInfo {
+  name: string;
+  provisioner: string;
+  /** Delete or Retain */
+  reclaimPolicy: string;
+  /** Immediate or WaitForFirstConsumer */
+  volumeBindingMode: string;
+  allowVolumeExpansion: boolean;
+  /** Marked with the is-default-class annotation */
+  isDefault: boolean;
+  parameters: Record<string, string>;
+  createdAt: string | null;
+}
+
+export interface StorageClassList {
+  /** Sorted by name */
+  classes: StorageClassInfo[];
+  /** What a PVC without storageClassName gets; null when none is marked default */
+  defaultClass: string | null;
+  multipleDefaults: boolean;
+}
+
+export interface PodFileDownloadEvent {
+  downloadId: string;
+  localPath: string;
+  /** Bytes written so far */
+  bytes: number;
+  done: boolean;
+  /** Set when done */
+  ok?: boolean;
+  error?: string;
+  errorCode?: KubeErrorCode;
+}
+
+export interface ExecStdinProgressEvent {
+  execId: string;
+  /** Bytes of the file written to the program's stdin so far */
+  bytesSent: number;
+  totalBytes: number;
+  done: boolean;
+  /** The rest is set when done */
+  ok?: boolean;
+  /** The program's exit code; null if it didn't run to completion */
+  exitCode?: number | null;
+  /** Last 1M characters of each stream */
+  stdout?: string;
+  stderr?: string;
+  error?: string;
+  errorCode?: KubeErrorCode;
+}
+
+export interface WaitProgressEvent {
+  waitId: string;
+  elapsedMs: number;
+  timeoutSeconds: number;
+}
+
+/** timed-out: --timeout passed with the condition unmet; error: kubectl failed (e.g. NotFound) */
+export type WaitOutcome = 'met' | 'timed-out' | 'error' | 'cancelled';
+
+export interface WaitResultEvent {
+  waitId: string;
+  kind: string;
+  name: string;
+  condition: string;
+  outcome: WaitOutcome;
+  elapsedMs: number;
+  /** kubectl's stdout and stderr, last 64K characters */
+  output: string;
+  error?: string;
+  errorCode?: KubeErrorCode;
+}
+
+export interface PodPathEntry {
+  name: string;
+  /** As ls prints it, e.g. "-rw-r--r--" */
+  mode: string;
+  /** Bytes; null for device files */
+  size: number | null;
+  /** "2024-05-01 12:00:00.000000000 +0000" */
+  modified: string;
+  /** Symlinks only */
+  target: string | null;
+}
+
+export interface PodPathEntryChange {
+  change: 'added' | 'removed' | 'modified';
+  name: string;
+  before: PodPathEntry | null;
+  after: PodPathEntry | null;
+}
+
+export interface PodPathChangeEvent {
+  watchId: string;
+  path: string;
+  /** Empty when only the error state changed */
+  changes: PodPathEntryChange[];
+  /** Entries in the latest listing */
+  entries: number;
+  /** The listing hit the entry limit */
+  truncated: boolean;
+  /** Set while listing fails; cleared with the next successful listing */
+  error: string | null;
+  errorCode?: KubeErrorCode;
+}
+
+export interface HpaMetric {
+  /** Resource, ContainerResource, Pods, Object or External */
+  type: string;
+  /** "cpu", "memory", or the custom/external metric's name */
+  name: string;
+  valueType: 'Utilization' | 'AverageValue' | 'Value';
+  /** "80%" for utilization, else the quantity ("500m", "1k"); null when not reported */
+  target: string | null;
+  current: string | null;
+}
+
+export interface HpaScalingEvent {
+  type: string;
+  /** e.g. SuccessfulRescale, FailedGetResourceMetric */
+  reason: string;
+  message: string;
+  count: number;
+  timestamp: string | null;
+}
+
+export interface HpaStatus {
+  name: string;
+  namespace: string;
+  apiVersion: string;
+  scaleTargetRef: { kind: string; name: string };
+  minReplicas: number;
+  maxReplicas: number;
+  currentReplicas: number;
+  desiredReplicas: number;
+  lastScaleTime: string | null;
+  metrics: HpaMetric[];
+  /** AbleToScale, ScalingActive, ScalingLimited (v2 only) */
+  conditions: { type: string; status: string; reason: string | null; message: string | null }[];
+  /** Newest first */
+  events: HpaScalingEvent[];
+}
+
+export type ClusterReachability = 'unknown' | 'reachable' | 'unauthorized' | 'unreachable' | 'timeout' | 'error';
+
+export interface ClusterHealth {
+  status: ClusterReachability;
+  /** ISO timestamp of the probe; null when never probed */
+  checkedAt: string | null;
+  latencyMs: number | null;
+  message: string | null;
+}
+
+/**
+ * One context as a cluster switcher shows it. Holds no credentials, only
+ * how the context authenticates and when that expires.
+ */
+export interface ClusterDetail {
+  context: string;
+  cluster: string | null;
+  server: string | null;
+  namespace: string;
+  user: string | null;
+  isCurrent: boolean;
+  auth: AuthStatus | null;
+  /** Last known until the probe started by the listing reports back */
+  reachability: ClusterHealth;
+  /** Why the context can't be used (dangling cluster or user reference) */
+  problem: string | null;
+}
+
+export interface ClusterDetailList {
+  listId: string;
+  kubeconfigPath: string;
+  clusters: ClusterDetail[];
+}
+
+export interface ClusterDetailEvent {
+  listId: string;
+  detail: ClusterDetail;
+}
+
+/**
+ * A watch, log follow or port-forward dropped and will be reopened under
+ * the same id (`watch:reconnecting`, `logs:reconnecting`, `forward:reconnecting`)
+ */
+export interface StreamReconnectingEvent {
+  /** Watch, log stream or forward id */
+  id: string;
+  /** Pod whose log follow dropped (log streams only) */
+  pod?: string;
+  attempt: number;
+  maxAttempts: number;
+  delayMs: number;
+  /** Why the stream dropped */
+  message: string;
+}
+
+export interface StreamReconnectedEvent {
+  id: string;
+  pod?: string;
+  /** Attempts the reconnect took */
+  attempts: number;
+}
+
+export interface MergedKubeconfig {
+  /** Merged config with credentials replaced by [REDACTED] */
+  yaml: string;
+  /** Files that were merged, highest precedence first */
+  sources: string[];
+  /** Listed sources that don't exist (skipped, as kubectl does) */
+  missing: string[];
+}
+
+export interface OwnerTreeNode {
+  /** "Kind/namespace/name", referenced by edges */
+  key: string;
+  kind: string;
+  apiVersion: string | null;
+  name: string;
+  namespace: string | null;
+  uid: string | null;
+  /** An owner reference points at an object that no longer exists */
+  missing: boolean;
+  /** Why a node is missing */
+  message: string | null;
+}
+
+export interface OwnerTreeEdge {
+  owner: string;
+  owned: string;
+  /** The owner is the managing controller (ownerReference.controller) */
+  controller: boolean;
+}
+
+export interface OwnerTree {
+  /** Key of the object the tree was built for */
+  root: string;
+  nodes: OwnerTreeNode[];
+  edges: OwnerTreeEdge[];
+  /** The depth limit cut off part of the graph */
+  truncated: boolean;
 }
@@ -0,0 +1,431 @@
+export interface TerminalCreateOptions {
+  cwd?: string;
+  env?: Record<string, string>;
+  /** Terminal text area size in pixels, used by inline-image tools (0 = unknown) */
+  pixelWidth?: number;
+  pixelHeight?: number;
+  /** Forward OSC 52 clipboard writes as `terminal:clipboard` events */
+  allowClipboard?: boolean;
+  /** Context the shell targets; selects a per-context kubectl binary */
+  context?: string;
+  /** Saved profile to start from; the options above override its fields */
+  profile?: string;
+  shell?: string;
+  args?: string[];
+  /** Start the shell as a login shell (`-l`) */
+  loginShell?: boolean;
+  /** Typed into the shell once it starts */
+  initialCommand?: string;
+  /** Overrides the configured answers to capability and color queries */
+  capabilities?: Partial<TerminalCapabilities>;
+  /** TERM for the child (default xterm-256color) */
+  term?: string;
+  /** Sets LANG and LC_ALL, e.g. "C.UTF-8" for containers with a minimal locale */
+  lang?: string;
+  /** Display name, kept in layout snapshots */
+  label?: string;
+  /** Per-process limits for the shell and what it starts; unix only */
+  limits?: TerminalResourceLimits;
+  /** Close a running terminal with the same id instead of failing with DuplicateId */
+  replace?: boolean;
+}
+
+/**
+ * setrlimit-style caps on a terminal's processes. CPU time works on Linux
+ * and macOS; memory (address space) on Linux only. Limits that can't be
+ * applied are skipped with a `terminal:warning`; on Windows none are.
+ */
+export interface TerminalResourceLimits {
+  /** CPU seconds per process before it gets SIGXCPU/SIGKILL */
+  cpuSeconds?: number;
+  /** Virtual memory per process, in MiB; allocations beyond it fail */
+  memoryMb?: number;
+}
+
+export interface CwdCheck {
+  directory: string;
+  /** With symlinks resolved; null when the directory doesn't exist */
+  resolved: string | null;
+  allowed: boolean;
+  /** The allowed root it is under, when the policy is on */
+  root: string | null;
+  reason: string | null;
+}
+
+export interface TerminalPasteLimits {
+  maxBytes: number;
+  maxLines: number;
+}
+
+/**
+ * What the backend reports when a program queries the terminal: device
+ * attributes (DA1) and the colors asked for with OSC 10 / OSC 11
+ */
+export interface TerminalCapabilities {
+  /** Answer queries in the backend; off leaves them to the renderer */
+  answerQueries: boolean;
+  /** DA1 parameters, e.g. "62;22" for a VT220 with ANSI color */
+  deviceAttributes: string;
+  /** `#rrggbb` */
+  foreground: string;
+  background: string;
+}
+
+/**
+ * Named terminal preset (e.g. "prod exec"), stored in the app config
+ */
+export interface TerminalProfile {
+  name: string;
+  /** Defaults to the platform shell */
+  shell?: string;
+  args?: string[];
+  env?: Record<string, string>;
+  cwd?: string;
+  loginShell?: boolean;
+  initialCommand?: string;
+}
+
+export interface TerminalInfo {
+  id: string;
+  shell: string;
+  args: string[];
+  cwd: string;
+  env: Record<string, string>;
+  createdAt: number;
+  cols: number;
+  rows: number;
+  pixelWidth: number;
+  pixelHeight: number;
+  /** A full-screen app (k9s, vim, less) is using the alternate screen buffer */
+  altScreen: boolean;
+  label: string | null;
+  context: string | null;
+}
+
+export interface TerminalNotifyEvent {
+  /** The pattern as set */
+  pattern: string;
+  /** The text that matched and the output line it's on, escape sequences removed */
+  match: string;
+  line: string;
+}
+
+export interface TerminalDiagnostics {
+  live: number;
+  /** Still starting, possibly waiting to retry the spawn */
+  spawning: number;
+  /** Exited terminals whose last output is still held */
+  exitedRetained: number;
+  /** PTY output and input since launch, all terminals */
+  bytesRead: number;
+  bytesWritten: number;
+  largestScrollback: { id: string; bytes: number } | null;
+  outputPipes: number;
+  webSockets: number;
+  terminals: {
+    id: string;
+    isShell: boolean;
+    ageMs: number;
+    /** Since the last output */
+    idleMs: number;
+    bytesRead: number;
+    bytesWritten: number;
+    scrollbackBytes: number;
+  }[];
+}
+
+export interface PtyReaderStats {
+  terminals: number;
+  /** PTYs still being read, i.e. whose process hasn't exited */
+  readers: number;
+  /** Readers of terminals that were closed but whose process is still running */
+  lingering: {
+    id: string;
+    pid: number;
+    closedForMs: number;
+    /** SIGKILL was sent after the process ignored SIGHUP */
+    forceKilled: boolean;
+  }[];
+}
+
+export interface TerminalReconcileReport {
+  checked: number;
+  /** Terminals whose process had exited without being cleaned up */
+  reaped: { id: string; pid: number }[];
+  /** Terminals whose process is gone but whose exit may still arrive; reaped on a later run if not */
+  pendingExits: number;
+  /** Tracked PTY readers whose process was already gone */
+  staleReaders: number;
+  /** Edit-mode flags left behind for terminals that no longer exist */
+  staleEditModes: number;
+}
+
+/**
+ * Layout of the open shell terminals, enough to start equivalent fresh
+ * shells later; scrollback and running programs are not kept
+ */
+export interface TerminalSnapshot {
+  version: 1;
+  takenAt: number;
+  terminals: TerminalSnapshotEntry[];
+}
+
+export interface TerminalSnapshotEntry {
+  id: string;
+  label: string | null;
+  shell: string;
+  args: string[];
+  cwd: string;
+  context: string | null;
+  cols: number;
+  rows: number;
+}
+
+export interface TerminalRestoreResult {
+  /** Snapshot id -> id of the new terminal */
+  ids: Record<string, string>;
+  /** Restored, but without their cwd or context */
+  degraded: { id: string; reasons: string[] }[];
+  failed: { id: string; error: string }[];
+}
+
+export interface SessionBundleSummary {
+  path: string;
+  bytes: number;
+  terminals: number;
+}
+
+export interface AttachToPodOptions {
+  pod: string;
+  namespace: string;
+  container?: string;
+  /** Forward keystrokes to the container's main process */
+  stdin: boolean;
+  context: string;
+}
+
+export interface ResilientExecOptions {
+  /** Label selector for the pods to follow, e.g. "app=web" */
+  podSelector: string;
+  namespace: string;
+  container?: string;
+  /** Defaults to bash, or sh in images without bash */
+  command?: string[];
+  context: string;
+}
+
+export type ResilientExecState = 'connected' | 'reconnecting' | 'waiting';
+
+export interface ResilientExecStatusEvent {
+  id: string;
+  state: ResilientExecState;
+  /** The pod connected to, or the one just lost */
+  pod: string | null;
+  message: string;
+}
+
+export interface TerminalReconnectedEvent {
+  id: string;
+  pod: string;
+  previousPod: string;
+  /** Reconnect attempts it took */
+  attempts: number;
+}
+
+export interface LastTerminalOutput {
+  output: string;
+  exitCode: number;
+  signal?: number;
+  exitedAt: number;
+}
+
+export interface TerminalBufferRange {
+  data: string;
+  /** Offsets of `data` within the terminal's whole output stream */
+  start: number;
+  end: number;
+  /** Oldest offset still retained; anything before it was dropped */
+  available: number;
+}
+
+export interface DebugPodOptions {
+  pod: string;
+  namespace: string;
+  /** Container whose process namespace the debug container joins */
+  targetContainer?: string;
+  /** Defaults to a netshoot image with common network tools */
+  image?: string;
+  /** Command to run instead of the image's default shell */
+  command?: string[];
+  context: string;
+}
+
+export interface ScriptProgressEvent {
+  terminalId: string;
+  scriptId: string;
+  /** 1-based index of the command just sent */
+  step: number;
+  total: number;
+  command: string;
+}
+
+export interface ScriptDoneEvent {
+  terminalId: string;
+  scriptId: string;
+  stepsRun: number;
+  total: number;
+  /** Why the script stopped early, null when every command was sent */
+  stoppedReason: 'cancelled' | 'terminal-closed' | null;
+}
+
+export type ShellFamily = 'bash' | 'zsh' | 'fish' | 'pwsh' | 'sh' | 'cmd' | 'unknown';
+
+export interface ShellDetection {
+  family: ShellFamily;
+  /** "probe" when the running shell confirmed it, "command" when inferred from what was spawned */
+  source: 'command' | 'probe';
+}
+
+export type TerminalLatencyMethod = 'echo' | 'printf';
+
+export interface TerminalLatencyResult {
+  id: string;
+  /** "echo" timed the PTY echoing typed characters, "printf" a command's output */
+  method: TerminalLatencyMethod;
+  /** Null when nothing came back before the timeout */
+  roundTripMs: number | null;
+}
+
+export interface SequencedCommandResult {
+  command: string;
+  /** What the command printed, without the echoed command line */
+  output: string;
+  /** Null when it timed out or was skipped */
+  exitCode: number | null;
+  /** Interrupted with ^C after the timeout; the commands after it are skipped */
+  timedOut: boolean;
+  skipped: boolean;
+  /** The middle of a long output was dropped */
+  truncated: boolean;
+  durationMs: number;
+}
+
+export interface LocalCommandSpec {
+  command: string;
+  args?: string[];
+  cwd?: string;
+  /** Added to the app's environment */
+  env?: Record<string, string>;
+}
+
+export interface LocalCommandExitEvent {
+  commandId: string;
+  exitCode: number;
+  signal: number | null;
+  /** Ended by stopCommand rather than on its own */
+  stopped: boolean;
+  durationMs: number;
+}
+
+export interface NodeShellOptions {
+  node: string;
+  context: string;
+  /** Defaults to busybox, which ships chroot */
+  image?: string;
+  /** Run `chroot /host` so the shell sees the node's own filesystem (default true) */
+  chroot?: boolean;
+  /** kubectl debug --profile, e.g. "sysadmin" for a privileged container */
+  profile?: string;
+}
+
+export interface SshTerminalOptions {
+  host: string;
+  user?: string;
+  port?: number;
+  /** Private key to authenticate with; otherwise the agent and ssh config decide */
+  keyPath?: string;
+  /** Bastion(s) to hop through, as comma-separated [user@]host[:port] (ssh -J) */
+  jumpHost?: string;
+  /** Run on the remote host before the interactive shell, e.g. `export KUBECONFIG=...` */
+  commands?: string[];
+}
+
+/**
+ * A PTY child from an earlier run of the app that is still alive
+ */
+export interface OrphanedProcess {
+  pid: number;
+  terminalId: string;
+  command: string;
+  /** When the earlier run started it */
+  recordedAt: number;
+  /** Start time matched, so the PID hasn't been reused by another process */
+  verified: boolean;
+}
+
+export interface ReapResult {
+  pid: number;
+  ok: boolean;
+  error?: string;
+}
+
+export interface BroadcastGroup {
+  groupId: string;
+  terminalIds: string[];
+}
+
+export interface BroadcastWriteResult {
+  terminalId: string;
+  ok: boolean;
+  error?: string;
+}
+
+export interface InterruptResult {
+  /** False when the shell was idle at its prompt */
+  interrupted: boolean;
+  /** SIGINT to the foreground process group, or ^C written to the PTY */
+  method: 'signal' | 'control-char' | null;
+  /** Foreground process group that was signalled */
+  processGroup: number | null;
+}
+
+export type ForegroundProcessState = 'idle' | 'busy' | 'unknown';
+
+export interface ForegroundProcessInfo {
+  id: string;
+  /** "idle": the shell is at its prompt; "unknown" where the platform can't tell */
+  state: ForegroundProcessState;
+  /** Foreground process group (its leader's pid), or the shell's pid when idle */
+  pid: number | null;
+  name: string | null;
+  /** Full command line when busy */
+  command: string | null;
+}
+
+export interface OutputPipeStatus {
+  path: string;
+  /** A reader has the pipe open; output is dropped (not buffered) while none is */
+  connected: boolean;
+  bytesWritten: number;
+  /** Output dropped while no reader was connected or the reader fell behind */
+  droppedBytes: number;
+}
+
+export interface TerminalTextExport {
+  text: string;
+  /** Older output was dropped, either from the scrollback or by the size limit */
+  truncated: boolean;
+  /** Escape sequences were removed */
+  stripped: boolean;
+}
+
+export interface TerminalWebSocketStatus {
+  port: number;
+  /** Connect URL including the session token */
+  url: string;
+  token: string;
+  /** Viewers currently connected */
+  clients: number;
+  /** Output skipped for viewers that fell behind */
+  droppedBytes: number;
+}
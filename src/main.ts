@@ -1,7 +1,180 @@
 import { app, BrowserWindow, ipcMain } from 'electron';
-import { loadKubeConfig, runKubectlCommand, useContext, setKubeconfigPath } from './main/kube';
-import { terminalManager } from './main/terminal';
-import type { KubeConfigSummary, KubectlResult } from './common/kubeTypes';
+import {
+  loadKubeConfig,
+  runKubectlCommand,
+  runKubectlStdin,
+  useContext,
+  setKubeconfigPath,
+  KubeError,
+  getKubectlForContext,
+  setKubectlForContext,
+  getAuthStatus,
+  resolveContext,
+  resolveKubeconfigPath,
+} from './main/kube';
+import { terminalManager, TerminalIdInUseError, TerminalSpawnError, type TerminalOptions } from './main/terminal';
+import { getPasteLimits, PasteTooLargeError, setPasteLimits } from './main/pasteGuard';
+import { setEventWindow } from './main/events';
+import { watchManager } from './main/watch';
+import { watchPod, getPodImages, getContainerRestarts, getPodProbes, getPodResources } from './main/pods';
+import { exportSessionBundle } from './main/sessionBundle';
+import {
+  logStreamManager,
+  getPodLogs,
+  getPreviousLogs,
+  getAllContainerLogs,
+  getMergedLogs,
+  exportLogs,
+  grepLogs,
+  type AllContainerLogsSpec,
+  type GrepLogsSpec,
+  type JobLogStreamSpec,
+  type LogExportSpec,
+  type PodLogsSpec,
+  type SelectorLogStreamSpec,
+} from './main/logs';
+import { clearDiscoveryCache, listApiResources, warmDiscoveryCache } from './main/discovery';
+import { getCrdSchema } from './main/crdSchemas';
+import { detectCloudAuth } from './main/cloudAuth';
+import { attachToPod, debugPod, openNodeShell, openSshTerminal } from './main/execTerminals';
+import {
+  diffManifest,
+  getResourcesYaml,
+  serverDryRunApply,
+  patchResource,
+  setAnnotations,
+  setLabels,
+  type ResourcesYamlOptions,
+} from './main/manifests';
+import { installLogger, setLogLevel, getLogSettings, getLogFilePath } from './main/logger';
+import { metricsManager, type MetricsStreamSpec } from './main/metrics';
+import { operationRegistry } from './main/operations';
+import { streamEvents, type EventStreamSpec } from './main/clusterEvents';
+import { portForwardManager, type ApiProxyOptions, type PortForwardOptions } from './main/portForwards';
+import { listPlugins, runPlugin } from './main/plugins';
+import { whoAmI, whoCanI, clearPermissionsCache } from './main/rbac';
+import {
+  downloadPodFile,
+  execWithStdinFile,
+  podFileStreamManager,
+  type ExecStdinFileSpec,
+  type PodFileDownloadSpec,
+  type PodFileStreamSpec,
+} from './main/podFiles';
+import { getNodeStatus } from './main/nodes';
+import { terminalScriptRunner } from './main/terminalScripts';
+import { getResourceSummary } from './main/summary';
+import {
+  previewDeleteBySelector,
+  confirmDeleteBySelector,
+  killPods,
+  type DeleteBySelectorSpec,
+  type KillPodsSpec,
+} from './main/bulkDelete';
+import { listProfiles, saveProfile } from './main/terminalProfiles';
+import { getWorkloadHealth } from './main/workloads';
+import { watchPodsTable, type PodsTableSpec } from './main/podsTable';
+import { initOrphanTracking, listOrphanedProcesses, reapOrphans } from './main/orphans';
+import { broadcastGroupManager } from './main/broadcast';
+import {
+  applyKustomize,
+  applyManifestStreaming,
+  type KustomizeApplySpec,
+  type StreamingApplySpec,
+} from './main/applyStream';
+import { getCommandPolicy } from './main/commandPolicy';
+import {
+  getRevisionManifest,
+  getRolloutHistory,
+  rolloutPause,
+  rolloutResume,
+  rolloutUndo,
+  type RolloutUndoSpec,
+} from './main/rollouts';
+import { latencyProbeManager, type LatencyProbeSpec } from './main/latency';
+import { watchPvc } from './main/pvcs';
+import { listClustersDetailed } from './main/clusters';
+import { getMergedKubeconfig } from './main/mergedKubeconfig';
+import { getOwnerTree, type OwnerTreeOptions } from './main/ownerTree';
+import { triggerCronJob, watchJob } from './main/jobs';
+import {
+  clearInputHistory,
+  getInputHistory,
+  isInputHistoryEnabled,
+  setInputHistoryEnabled,
+} from './main/inputHistory';
+import { compareResourceAcross, getDrift } from './main/compare';
+import { filterContexts, searchContexts } from './main/contextSearch';
+import { cancelWait, waitForCondition, type WaitSpec } from './main/wait';
+import { podPathWatchManager, type PodPathWatchSpec } from './main/podPathWatch';
+import { setContextTags } from './main/contextTags';
+import { getHpaStatus } from './main/hpa';
+import { applyTemplate, type TemplateApplySpec } from './main/templates';
+import { repairKubeconfig, validateKubeconfig } from './main/kubeconfigRepair';
+import { getServiceEndpoints } from './main/services';
+import { getConfigMap, updateConfigMapKey } from './main/configMaps';
+import { eventRateMonitorManager, type EventRateMonitorSpec } from './main/eventRate';
+import { getIngress } from './main/ingresses';
+import { localCommandManager } from './main/localCommands';
+import { formatAge } from './main/age';
+import {
+  applyDirectory,
+  exportNamespace,
+  type DirectoryApplySpec,
+  type NamespaceExportSpec,
+} from './main/namespaceSnapshot';
+import { kubectlPool } from './main/kubectlPool';
+import { checkClusterCertExpiry } from './main/clusterCert';
+import { getLogLevelSettings, setLogLevelSettings } from './main/logLevels';
+import { backgroundSessionManager } from './main/backgroundSessions';
+import { listResourcesPaged, type ResourcePageSpec } from './main/pagedList';
+import {
+  generateServiceAccountKubeconfig,
+  type ServiceAccountKubeconfigSpec,
+} from './main/serviceAccountKubeconfig';
+import { restartLeaderboardManager, type RestartLeaderboardSpec } from './main/restarts';
+import { checkCwd } from './main/cwdPolicy';
+import { getRaw, rawStreamManager, type RawStreamSpec } from './main/rawApi';
+import { getWorkloadPodSpread } from './main/podSpread';
+import { runSelfTest } from './main/selfTest';
+import { watchDeployment } from './main/deployments';
+import { listStorageClasses } from './main/storageClasses';
+import { importKubeconfig } from './main/kubeconfigImport';
+import {
+  closeActionLog,
+  describeKeyChanges,
+  describeKubectlCommand,
+  getActionLog,
+  recordAction,
+  summarizeKubectlResult,
+  trackAction,
+} from './main/actionLog';
+import { resilientExecManager } from './main/resilientExec';
+import type {
+  ContextOverrides,
+  KubeconfigRepairStrategy,
+  KubeConfigSummary,
+  KubectlPoolLimits,
+  KubectlResult,
+  LogLevelSettings,
+  LogSink,
+  OperationScope,
+  PatchType,
+  ResourceRef,
+} from './common/kubeTypes';
+import type {
+  AttachToPodOptions,
+  DebugPodOptions,
+  LocalCommandSpec,
+  NodeShellOptions,
+  ResilientExecOptions,
+  SshTerminalOptions,
+  TerminalLatencyMethod,
+  TerminalPasteLimits,
+  TerminalProfile,
+  TerminalSnapshot,
+} from './common/terminalTypes';
+import type { ActionLogFilter } from './common/appTypes';
 
 declare const MAIN_WINDOW_WEBPACK_ENTRY: string;
 declare const MAIN_WINDOW_PRELOAD_WEBPACK_ENTRY: string;
@@ -14,16 +187,31 @@ type SuccessResponse<T> = {
 type ErrorResponse = {
   success: false;
   error: string;
+  code?: string;
+  details?: Record<string, unknown>;
 };
 
 function ok<T>(data: T): SuccessResponse<T> {
   return { success: true, data };
 }
 
-function err(message: string): ErrorResponse {
-  return { success: false, error: message };
+function err(message: string, code?: string): ErrorResponse {
+  return code ? { success: false, error: message, code } : { success: false, error: message };
 }
 
+function errFrom(error: unknown, fallback: string): ErrorResponse {
+  if (error instanceof PasteTooLargeError) {
+    const { bytes, lines, limits } = error;
+    return { ...err(error.message, error.code), details: { bytes, lines, limits } };
+  }
+  if (error instanceof KubeError || error instanceof TerminalSpawnError || error instanceof TerminalIdInUseError) {
+    return err(error.message, error.code);
+  }
+  return err(error instanceof Error ? error.message : fallback);
+}
+
+installLogger();
+
 function createWindow() {
   const mainWindow = new BrowserWindow({
     height: 600,
@@ -39,6 +227,8 @@ function createWindow() {
 
   // Set terminal manager window reference
   terminalManager.setWindow(mainWindow);
+  terminalManager.startReconciler();
+  setEventWindow(mainWindow);
 
   // Capture renderer console output and errors
   mainWindow.webContents.on('console-message', (event, level, message, line, sourceId) => {
@@ -79,9 +269,12 @@ function createWindow() {
 }
 
 function registerIpcHandlers() {
-  ipcMain.handle('kube:get-contexts', async () => {
+  ipcMain.handle('kube:get-contexts', async (_event, filter?: string) => {
     try {
       const summary = await loadKubeConfig();
+      if (filter) {
+        summary.contexts = filterContexts(summary.contexts, filter);
+      }
       return ok(summary);
     } catch (error) {
       const message =
@@ -96,8 +289,16 @@ function registerIpcHandlers() {
     }
 
     try {
-      await useContext(contextName);
+      await trackAction(
+        { action: 'switch-context', context: contextName, namespace: null, target: contextName, detail: null },
+        () => useContext(contextName)
+      );
+      clearPermissionsCache();
       const summary = await loadKubeConfig();
+      // Warm in the background; the switch shouldn't wait on discovery
+      warmDiscoveryCache(contextName).catch((error) => {
+        console.warn(`[Discovery ${contextName}] Warm-up failed:`, error);
+      });
 
       return ok(summary);
     } catch (error) {
@@ -111,7 +312,7 @@ function registerIpcHandlers() {
     'kube:run-command',
     async (
       _event,
-      params: { context: string; command: string }
+      params: { context: string; command: string; view?: string }
     ): Promise<SuccessResponse<KubectlResult> | ErrorResponse> => {
       if (!params?.context) {
         return err('Select a context first');
@@ -121,13 +322,16 @@ function registerIpcHandlers() {
         return err('Provide a kubectl command to run');
       }
 
+      // Reads aren't logged; only commands that change something
+      const action = describeKubectlCommand(params.command);
       try {
-        const result = await runKubectlCommand(params.context, params.command);
+        const run = () => runKubectlCommand(params.context, params.command, params.view);
+        const result = action
+          ? await trackAction({ ...action, context: action.context ?? params.context }, run, summarizeKubectlResult)
+          : await run();
         return ok(result);
       } catch (error) {
-        const message =
-          error instanceof Error ? error.message : 'Failed to run kubectl';
-        return err(message);
+        return errFrom(error, 'Failed to run kubectl');
       }
     }
   );
@@ -139,6 +343,8 @@ function registerIpcHandlers() {
 
     try {
       setKubeconfigPath(configPath);
+      clearPermissionsCache();
+      clearDiscoveryCache();
       const summary = await loadKubeConfig();
       return ok(summary);
     } catch (error) {
@@ -148,34 +354,2667 @@ function registerIpcHandlers() {
     }
   });
 
-  // Terminal IPC handlers
-  ipcMain.handle('terminal:create', async (_event, id: string, options?: { cwd?: string; env?: Record<string, string> }) => {
+  ipcMain.handle(
+    'kube:list-api-resources',
+    async (_event, params: { context: string; refresh?: boolean }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      try {
+        const resources = await listApiResources(params.context, params.refresh);
+        return ok(resources);
+      } catch (error) {
+        return errFrom(error, 'Failed to list API resources');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:warm-cache', async (_event, params: { context: string; refresh?: boolean }) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
     try {
-      terminalManager.createTerminal(id, options || {});
-      return ok({ id });
+      return ok(await warmDiscoveryCache(params.context, params.refresh));
     } catch (error) {
-      const message = error instanceof Error ? error.message : 'Failed to create terminal';
-      return err(message);
+      return errFrom(error, 'Failed to warm discovery cache');
     }
   });
 
-  ipcMain.handle('terminal:write', async (_event, id: string, data: string) => {
+  ipcMain.handle('kube:clear-cache', async (_event, context?: string) => {
+    clearDiscoveryCache(context || undefined);
+    return ok({ cleared: true });
+  });
+
+  ipcMain.handle(
+    'kube:diff-manifest',
+    async (_event, params: { content: string; context: string; namespace?: string }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.content || !params.content.trim()) {
+        return err('Provide a manifest to diff');
+      }
+
+      try {
+        const result = await diffManifest(params.content, params.context, params.namespace);
+        return ok(result);
+      } catch (error) {
+        return errFrom(error, 'Failed to diff manifest');
+      }
+    }
+  );
+
+  ipcMain.handle(
+    'kube:watch-pod',
+    async (_event, params: { pod: string; namespace: string; context: string }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.pod || !params?.namespace) {
+        return err('Pod name and namespace are required');
+      }
+
+      try {
+        const watchId = await watchPod(params.pod, params.namespace, params.context);
+        return ok({ watchId });
+      } catch (error) {
+        return errFrom(error, 'Failed to watch pod');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:stop-watch', async (_event, watchId: string) => {
     try {
-      terminalManager.writeToTerminal(id, data);
-      return ok({});
+      return ok({ stopped: watchManager.stopWatch(watchId) });
     } catch (error) {
-      const message = error instanceof Error ? error.message : 'Failed to write to terminal';
-      return err(message);
+      return errFrom(error, 'Failed to stop watch');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:stream-logs-for-selector',
+    async (_event, params: SelectorLogStreamSpec) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.labelSelector || !params?.namespace) {
+        return err('Label selector and namespace are required');
+      }
+
+      try {
+        const streamId = logStreamManager.streamLogsForSelector(params);
+        return ok({ streamId });
+      } catch (error) {
+        return errFrom(error, 'Failed to stream logs');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:stop-log-stream', async (_event, streamId: string) => {
+    try {
+      return ok({ stopped: logStreamManager.stopLogStream(streamId) });
+    } catch (error) {
+      return errFrom(error, 'Failed to stop log stream');
     }
   });
 
-  ipcMain.handle('terminal:resize', async (_event, id: string, cols: number, rows: number) => {
+  ipcMain.handle('kube:start-metrics-stream', async (_event, params: MetricsStreamSpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (params.kind !== 'pod' && params.kind !== 'node') {
+      return err('Metrics target must be "pod" or "node"');
+    }
+
     try {
-      terminalManager.resizeTerminal(id, cols, rows);
-      return ok({});
+      const streamId = metricsManager.startMetricsStream(params);
+      return ok({ streamId });
     } catch (error) {
-      const message = error instanceof Error ? error.message : 'Failed to resize terminal';
-      return err(message);
+      return errFrom(error, 'Failed to start metrics stream');
+    }
+  });
+
+  ipcMain.handle('kube:stop-metrics-stream', async (_event, streamId: string) => {
+    try {
+      return ok({ stopped: metricsManager.stopMetricsStream(streamId) });
+    } catch (error) {
+      return errFrom(error, 'Failed to stop metrics stream');
+    }
+  });
+
+  ipcMain.handle('kube:get-metrics-history', async (_event, streamId: string) => {
+    try {
+      return ok(metricsManager.getMetricsHistory(streamId));
+    } catch (error) {
+      return errFrom(error, 'Failed to read metrics history');
+    }
+  });
+
+  ipcMain.handle('kube:cancel-operations', async (_event, filter?: OperationScope) => {
+    try {
+      if (filter?.view !== undefined) {
+        backgroundSessionManager.detachView(filter.view);
+      }
+      return ok(operationRegistry.cancel(filter ?? {}));
+    } catch (error) {
+      return errFrom(error, 'Failed to cancel operations');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:set-kubectl-for-context',
+    async (_event, params: { context: string; path: string }) => {
+      if (!params?.context) {
+        return err('Context name is required');
+      }
+
+      try {
+        await setKubectlForContext(params.context, params.path ?? '');
+        return ok({ context: params.context, path: getKubectlForContext(params.context) });
+      } catch (error) {
+        return errFrom(error, 'Failed to set kubectl binary');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:get-kubectl-for-context', async (_event, context: string) => {
+    if (!context) {
+      return err('Context name is required');
+    }
+
+    return ok({ context, path: getKubectlForContext(context) });
+  });
+
+  ipcMain.handle('kube:stream-events', async (_event, params: EventStreamSpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    try {
+      const streamId = streamEvents({ ...params, warningsOnly: params.warningsOnly === true });
+      return ok({ streamId });
+    } catch (error) {
+      return errFrom(error, 'Failed to stream events');
+    }
+  });
+
+  ipcMain.handle('kube:start-api-proxy', async (_event, params: ApiProxyOptions) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (params.port !== undefined && (!Number.isInteger(params.port) || params.port < 0 || params.port > 65535)) {
+      return err('Proxy port must be between 0 and 65535');
+    }
+
+    try {
+      const proxy = await trackAction(
+        { action: 'start-api-proxy', context: params.context, namespace: null, target: 'api-server', detail: null },
+        () => portForwardManager.startApiProxy(params),
+        (info) => ({ detail: `listening on port ${info.localPort}` })
+      );
+      return ok(proxy);
+    } catch (error) {
+      return errFrom(error, 'Failed to start API proxy');
+    }
+  });
+
+  ipcMain.handle('kube:stop-api-proxy', async (_event, id: string) => {
+    try {
+      const forward = portForwardManager.listForwards().find((entry) => entry.id === id);
+      const stopped = portForwardManager.stopForward(id);
+      if (forward) {
+        recordAction({
+          action: 'stop-api-proxy',
+          context: forward.context,
+          namespace: forward.namespace ?? null,
+          target: forward.target,
+          detail: null,
+        });
+      }
+      return ok({ stopped });
+    } catch (error) {
+      return errFrom(error, 'Failed to stop API proxy');
+    }
+  });
+
+  ipcMain.handle('kube:list-forwards', async () => {
+    try {
+      return ok(portForwardManager.listForwards());
+    } catch (error) {
+      return errFrom(error, 'Failed to list forwards');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:get-resources-yaml',
+    async (
+      _event,
+      params: { refs: ResourceRef[]; context: string } & ResourcesYamlOptions
+    ) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!Array.isArray(params.refs) || params.refs.length === 0) {
+        return err('Select at least one resource');
+      }
+
+      try {
+        const { refs, context, ...options } = params;
+        return ok(await getResourcesYaml(refs, context, options));
+      } catch (error) {
+        return errFrom(error, 'Failed to fetch resources');
+      }
+    }
+  );
+
+  ipcMain.handle(
+    'kube:run-plugin',
+    async (
+      _event,
+      params: { name: string; args?: string[]; context: string; namespace?: string }
+    ) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.name) {
+        return err('Plugin name is required');
+      }
+
+      const args = params.args ?? [];
+      try {
+        const result = await trackAction(
+          {
+            action: 'run-plugin',
+            context: params.context,
+            namespace: params.namespace ?? null,
+            target: params.name,
+            detail: `kubectl ${[params.name, ...args].join(' ')}`,
+          },
+          () => runPlugin(params.name, args, params.context, params.namespace),
+          summarizeKubectlResult
+        );
+        return ok(result);
+      } catch (error) {
+        return errFrom(error, 'Failed to run plugin');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:list-plugins', async () => {
+    try {
+      return ok(await listPlugins());
+    } catch (error) {
+      return errFrom(error, 'Failed to list plugins');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:who-can-i',
+    async (_event, params: { namespace: string; context: string; refresh?: boolean }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.namespace) {
+        return err('Namespace is required');
+      }
+
+      try {
+        return ok(await whoCanI(params.namespace, params.context, params.refresh));
+      } catch (error) {
+        return errFrom(error, 'Failed to list permissions');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:stream-pod-file', async (_event, params: PodFileStreamSpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.pod || !params?.namespace || !params?.path) {
+      return err('Pod, namespace and file path are required');
+    }
+
+    try {
+      const streamId = podFileStreamManager.streamPodFile(params);
+      return ok({ streamId });
+    } catch (error) {
+      return errFrom(error, 'Failed to stream file');
+    }
+  });
+
+  ipcMain.handle('kube:stop-pod-file-stream', async (_event, streamId: string) => {
+    try {
+      return ok({ stopped: podFileStreamManager.stopPodFileStream(streamId) });
+    } catch (error) {
+      return errFrom(error, 'Failed to stop file stream');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:get-pod-images',
+    async (_event, params: { pod: string; namespace: string; context: string }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.pod || !params?.namespace) {
+        return err('Pod name and namespace are required');
+      }
+
+      try {
+        return ok(await getPodImages(params.pod, params.namespace, params.context));
+      } catch (error) {
+        return errFrom(error, 'Failed to read pod images');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:start-port-forward', async (_event, params: PortForwardOptions) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.name || !params?.namespace) {
+      return err('Target name and namespace are required');
+    }
+
+    if (!['pod', 'service', 'deployment'].includes(params.targetKind)) {
+      return err('Target kind must be "pod", "service" or "deployment"');
+    }
+
+    if (!Number.isInteger(params.remotePort) || params.remotePort < 1 || params.remotePort > 65535) {
+      return err('Remote port must be between 1 and 65535');
+    }
+
+    try {
+      const forward = await trackAction(
+        {
+          action: 'start-port-forward',
+          context: params.context,
+          namespace: params.namespace,
+          target: `${params.targetKind}/${params.name}:${params.remotePort}`,
+          detail: null,
+        },
+        () => portForwardManager.startPortForward(params),
+        (info) => ({ detail: `local port ${info.localPort}` })
+      );
+      return ok(forward);
+    } catch (error) {
+      return errFrom(error, 'Failed to start port-forward');
+    }
+  });
+
+  ipcMain.handle('kube:stop-port-forward', async (_event, id: string) => {
+    try {
+      const forward = portForwardManager.listForwards().find((entry) => entry.id === id);
+      const stopped = portForwardManager.stopForward(id);
+      if (forward) {
+        recordAction({
+          action: 'stop-port-forward',
+          context: forward.context,
+          namespace: forward.namespace ?? null,
+          target: forward.target,
+          detail: null,
+        });
+      }
+      return ok({ stopped });
+    } catch (error) {
+      return errFrom(error, 'Failed to stop port-forward');
+    }
+  });
+
+  ipcMain.handle('kube:get-node-status', async (_event, params: { name: string; context: string }) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.name) {
+      return err('Node name is required');
+    }
+
+    try {
+      return ok(await getNodeStatus(params.name, params.context));
+    } catch (error) {
+      return errFrom(error, 'Failed to read node status');
+    }
+  });
+
+  ipcMain.handle('kube:get-auth-status', async (_event, contextName: string) => {
+    if (!contextName) {
+      return err('Context name is required');
+    }
+
+    try {
+      return ok(await getAuthStatus(contextName));
+    } catch (error) {
+      return errFrom(error, 'Failed to read auth status');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:watch-selected',
+    async (
+      _event,
+      params: {
+        kind: string;
+        labelSelector?: string;
+        fieldSelector?: string;
+        namespace?: string;
+        context: string;
+        coalesceMs?: number;
+      }
+    ) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.kind) {
+        return err('Resource kind is required');
+      }
+
+      try {
+        const watchId = watchManager.watchSelected({
+          context: params.context,
+          kind: params.kind,
+          namespace: params.namespace,
+          allNamespaces: !params.namespace,
+          labelSelector: params.labelSelector,
+          fieldSelector: params.fieldSelector,
+          coalesceMs: params.coalesceMs,
+        });
+        return ok({ watchId });
+      } catch (error) {
+        return errFrom(error, 'Failed to start watch');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:get-pod-logs', async (_event, params: PodLogsSpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.pod || !params?.namespace) {
+      return err('Pod name and namespace are required');
+    }
+
+    try {
+      return ok({ logs: await getPodLogs(params) });
+    } catch (error) {
+      return errFrom(error, 'Failed to read logs');
+    }
+  });
+
+  ipcMain.handle('kube:get-previous-logs', async (_event, params: Omit<PodLogsSpec, 'previous'>) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.pod || !params?.namespace) {
+      return err('Pod name and namespace are required');
+    }
+
+    try {
+      return ok({ logs: await getPreviousLogs(params) });
+    } catch (error) {
+      return errFrom(error, 'Failed to read previous logs');
+    }
+  });
+
+  ipcMain.handle('kube:get-resource-summary', async (_event, contextName: string) => {
+    if (!contextName) {
+      return err('Select a context first');
+    }
+
+    try {
+      return ok(await getResourceSummary(contextName));
+    } catch (error) {
+      return errFrom(error, 'Failed to summarize resources');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:pause-log-stream',
+    async (_event, params: { streamId: string; paused: boolean }) => {
+      try {
+        const flushedLines = logStreamManager.pauseLogStream(params.streamId, params.paused === true);
+        return ok({ paused: params.paused === true, flushedLines });
+      } catch (error) {
+        return errFrom(error, 'Failed to pause log stream');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:delete-by-selector', async (_event, params: DeleteBySelectorSpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.kind || !params?.labelSelector?.trim()) {
+      return err('Resource kind and a label selector are required');
+    }
+
+    if (
+      params.gracePeriodSeconds !== undefined &&
+      (!Number.isInteger(params.gracePeriodSeconds) || params.gracePeriodSeconds < 0)
+    ) {
+      return err('Grace period must be a non-negative number of seconds');
+    }
+
+    try {
+      // Without a confirm token this only previews what would be deleted
+      if (!params.confirmToken) {
+        return ok({ preview: await previewDeleteBySelector(params) });
+      }
+      const results = await trackAction(
+        {
+          action: 'delete',
+          context: params.context,
+          namespace: params.namespace ?? null,
+          target: `${params.kind} -l ${params.labelSelector}`,
+          detail: null,
+        },
+        () => confirmDeleteBySelector(params),
+        (items) => {
+          const failed = items.filter((item) => !item.ok).length;
+          return {
+            detail: `${items.length - failed} deleted`,
+            error: failed > 0 ? `${failed} of ${items.length} could not be deleted` : null,
+          };
+        }
+      );
+      return ok({ results });
+    } catch (error) {
+      return errFrom(error, 'Failed to delete resources');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:get-workload-health',
+    async (_event, params: { namespace?: string; context: string }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      try {
+        return ok(await getWorkloadHealth(params.namespace || undefined, params.context));
+      } catch (error) {
+        return errFrom(error, 'Failed to get workload health');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:watch-pods-table', async (_event, params: PodsTableSpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    try {
+      const watchId = watchPodsTable({
+        context: params.context,
+        namespace: params.namespace || undefined,
+        node: params.node || undefined,
+        view: params.view,
+      });
+      return ok({ watchId });
+    } catch (error) {
+      return errFrom(error, 'Failed to watch pods');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:server-dry-run-apply',
+    async (_event, params: { content: string; context: string; namespace?: string }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.content || !params.content.trim()) {
+        return err('Provide a manifest to test');
+      }
+
+      try {
+        return ok(await serverDryRunApply(params.content, params.context, params.namespace));
+      } catch (error) {
+        return errFrom(error, 'Server dry-run failed');
+      }
+    }
+  );
+
+  ipcMain.handle(
+    'kube:get-container-restarts',
+    async (_event, params: { pod: string; namespace: string; context: string }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.pod || !params?.namespace) {
+        return err('Pod name and namespace are required');
+      }
+
+      try {
+        return ok(await getContainerRestarts(params.pod, params.namespace, params.context));
+      } catch (error) {
+        return errFrom(error, 'Failed to read container restarts');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:apply-manifest-streaming', async (_event, params: StreamingApplySpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.content || !params.content.trim()) {
+      return err('Provide a manifest to apply');
+    }
+
+    try {
+      return ok(applyManifestStreaming(params));
+    } catch (error) {
+      return errFrom(error, 'Failed to apply manifest');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:get-rollout-history',
+    async (_event, params: { kind: string; name: string; namespace: string; context: string }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.kind || !params?.name || !params?.namespace) {
+        return err('Kind, name and namespace are required');
+      }
+
+      try {
+        return ok(await getRolloutHistory(params.kind, params.name, params.namespace, params.context));
+      } catch (error) {
+        return errFrom(error, 'Failed to get rollout history');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:rollout-undo', async (_event, params: RolloutUndoSpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.kind || !params?.name || !params?.namespace) {
+      return err('Kind, name and namespace are required');
+    }
+
+    if (params.toRevision !== undefined && (!Number.isInteger(params.toRevision) || params.toRevision < 1)) {
+      return err('Revision must be a positive number');
+    }
+
+    try {
+      const rollout = await trackAction(
+        {
+          action: 'rollout-undo',
+          context: params.context,
+          namespace: params.namespace,
+          target: `${params.kind}/${params.name}`,
+          detail: params.toRevision !== undefined ? `to revision ${params.toRevision}` : null,
+        },
+        () => rolloutUndo(params)
+      );
+      return ok(rollout);
+    } catch (error) {
+      return errFrom(error, 'Failed to roll back');
+    }
+  });
+
+  ipcMain.handle('kube:start-latency-probe', async (_event, params: LatencyProbeSpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    try {
+      return ok({ probeId: latencyProbeManager.startLatencyProbe(params) });
+    } catch (error) {
+      return errFrom(error, 'Failed to start latency probe');
+    }
+  });
+
+  ipcMain.handle('kube:stop-latency-probe', async (_event, probeId: string) => {
+    try {
+      return ok({ stopped: latencyProbeManager.stopLatencyProbe(probeId) });
+    } catch (error) {
+      return errFrom(error, 'Failed to stop latency probe');
+    }
+  });
+
+  ipcMain.handle('kube:get-latency-history', async (_event, probeId: string) => {
+    try {
+      return ok(latencyProbeManager.getLatencyHistory(probeId));
+    } catch (error) {
+      return errFrom(error, 'Failed to get latency history');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:resolve-context',
+    async (_event, params: { name: string; kubeconfigPath?: string; overrides?: ContextOverrides }) => {
+      if (!params?.name) {
+        return err('Context name is required');
+      }
+
+      try {
+        return ok(await resolveContext(params.name, params.kubeconfigPath || undefined, params.overrides ?? {}));
+      } catch (error) {
+        return errFrom(error, 'Failed to resolve context');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:get-all-container-logs', async (_event, params: AllContainerLogsSpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.pod || !params?.namespace) {
+      return err('Pod name and namespace are required');
+    }
+
+    if (params.tailLines !== undefined && (!Number.isInteger(params.tailLines) || params.tailLines < 1)) {
+      return err('Tail lines must be a positive number');
+    }
+
+    try {
+      return ok(await getAllContainerLogs(params));
+    } catch (error) {
+      return errFrom(error, 'Failed to read container logs');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:watch-pvc',
+    async (_event, params: { name: string; namespace: string; context: string }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.name || !params?.namespace) {
+        return err('Claim name and namespace are required');
+      }
+
+      try {
+        const watchId = await watchPvc(params.name, params.namespace, params.context);
+        return ok({ watchId });
+      } catch (error) {
+        return errFrom(error, 'Failed to watch persistent volume claim');
+      }
+    }
+  );
+
+  ipcMain.handle(
+    'kube:get-revision-manifest',
+    async (
+      _event,
+      params: { kind: string; name: string; namespace: string; revision: number; context: string }
+    ) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.kind || !params?.name || !params?.namespace) {
+        return err('Kind, name and namespace are required');
+      }
+
+      if (!Number.isInteger(params.revision) || params.revision < 1) {
+        return err('Revision must be a positive integer');
+      }
+
+      try {
+        return ok(
+          await getRevisionManifest(params.kind, params.name, params.namespace, params.revision, params.context)
+        );
+      } catch (error) {
+        return errFrom(error, 'Failed to get revision manifest');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:download-pod-file', async (_event, params: PodFileDownloadSpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.pod || !params?.namespace || !params?.remotePath || !params?.localPath) {
+      return err('Pod, namespace, remote path and local path are required');
+    }
+
+    try {
+      return ok(downloadPodFile(params));
+    } catch (error) {
+      return errFrom(error, 'Failed to download file');
+    }
+  });
+
+  ipcMain.handle('kube:list-clusters-detailed', async (_event, kubeconfigPath?: string) => {
+    try {
+      return ok(await listClustersDetailed(kubeconfigPath || undefined));
+    } catch (error) {
+      return errFrom(error, 'Failed to list clusters');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:run-kubectl-stdin',
+    async (
+      _event,
+      params: { args: string[]; stdin: string; context: string; namespace?: string; timeoutMs?: number }
+    ): Promise<SuccessResponse<KubectlResult> | ErrorResponse> => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!Array.isArray(params?.args) || params.args.some((arg) => typeof arg !== 'string')) {
+        return err('kubectl arguments must be a list of strings');
+      }
+
+      if (typeof params.stdin !== 'string') {
+        return err('Input for kubectl is required');
+      }
+
+      // Logged like a typed command; the input itself isn't, it can hold Secrets
+      const action = describeKubectlCommand(params.args.join(' '));
+      try {
+        const run = () =>
+          runKubectlStdin(params.args, params.stdin, params.context, params.namespace, params.timeoutMs);
+        const result = action
+          ? await trackAction(
+              {
+                ...action,
+                context: action.context ?? params.context,
+                namespace: action.namespace ?? params.namespace ?? null,
+              },
+              run,
+              summarizeKubectlResult
+            )
+          : await run();
+        return ok(result);
+      } catch (error) {
+        return errFrom(error, 'Failed to run kubectl');
+      }
+    }
+  );
+
+  ipcMain.handle(
+    'kube:rollout-pause',
+    async (_event, params: { kind: string; name: string; namespace: string; context: string }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.kind || !params?.name || !params?.namespace) {
+        return err('Kind, name and namespace are required');
+      }
+
+      try {
+        return ok(
+          await trackAction(
+            {
+              action: 'rollout-pause',
+              context: params.context,
+              namespace: params.namespace,
+              target: `${params.kind}/${params.name}`,
+              detail: null,
+            },
+            () => rolloutPause(params.kind, params.name, params.namespace, params.context)
+          )
+        );
+      } catch (error) {
+        return errFrom(error, 'Failed to pause rollout');
+      }
+    }
+  );
+
+  ipcMain.handle(
+    'kube:rollout-resume',
+    async (_event, params: { kind: string; name: string; namespace: string; context: string }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.kind || !params?.name || !params?.namespace) {
+        return err('Kind, name and namespace are required');
+      }
+
+      try {
+        return ok(
+          await trackAction(
+            {
+              action: 'rollout-resume',
+              context: params.context,
+              namespace: params.namespace,
+              target: `${params.kind}/${params.name}`,
+              detail: null,
+            },
+            () => rolloutResume(params.kind, params.name, params.namespace, params.context)
+          )
+        );
+      } catch (error) {
+        return errFrom(error, 'Failed to resume rollout');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:get-merged-kubeconfig', async () => {
+    try {
+      return ok(await getMergedKubeconfig());
+    } catch (error) {
+      return errFrom(error, 'Failed to merge kubeconfig');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:get-owner-tree',
+    async (
+      _event,
+      params: { kind: string; name: string; namespace: string; context: string } & OwnerTreeOptions
+    ) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.kind || !params?.name || !params?.namespace) {
+        return err('Kind, name and namespace are required');
+      }
+
+      try {
+        return ok(
+          await getOwnerTree(params.kind, params.name, params.namespace, params.context, {
+            includeDescendants: params.includeDescendants === true,
+            maxDepth: params.maxDepth,
+          })
+        );
+      } catch (error) {
+        return errFrom(error, 'Failed to get owner tree');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:kill-pods', async (_event, params: KillPodsSpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.namespace || !params?.labelSelector?.trim()) {
+      return err('Namespace and a label selector are required');
+    }
+
+    if (
+      params.gracePeriodSeconds !== undefined &&
+      (!Number.isInteger(params.gracePeriodSeconds) || params.gracePeriodSeconds < 0)
+    ) {
+      return err('Grace period must be a non-negative number of seconds');
+    }
+
+    if (params.dryRun) {
+      try {
+        return ok(await killPods(params));
+      } catch (error) {
+        return errFrom(error, 'Failed to kill pods');
+      }
+    }
+
+    try {
+      const result = await trackAction(
+        {
+          action: 'kill-pods',
+          context: params.context,
+          namespace: params.namespace,
+          target: `pods -l ${params.labelSelector}`,
+          detail: null,
+        },
+        () => killPods(params),
+        (killed) => ({
+          detail: `${killed.killed} killed, ${killed.skipped} skipped`,
+          error: killed.failed > 0 ? `${killed.failed} of ${killed.matched.length} could not be killed` : null,
+        })
+      );
+      return ok(result);
+    } catch (error) {
+      return errFrom(error, 'Failed to kill pods');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:get-crd-schema',
+    async (_event, params: { group: string; version: string; kind: string; context: string; refresh?: boolean }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.group || !params?.version || !params?.kind) {
+        return err('Group, version and kind are required');
+      }
+
+      try {
+        return ok(await getCrdSchema(params.group, params.version, params.kind, params.context, params.refresh));
+      } catch (error) {
+        return errFrom(error, 'Failed to get CRD schema');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:get-merged-logs', async (_event, params: AllContainerLogsSpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.pod || !params?.namespace) {
+      return err('Pod name and namespace are required');
+    }
+
+    if (params.tailLines !== undefined && (!Number.isInteger(params.tailLines) || params.tailLines < 1)) {
+      return err('Tail lines must be a positive number');
+    }
+
+    try {
+      return ok(await getMergedLogs(params));
+    } catch (error) {
+      return errFrom(error, 'Failed to read container logs');
+    }
+  });
+
+  ipcMain.handle('kube:detect-cloud-auth', async (_event, contextName: string) => {
+    if (!contextName) {
+      return err('Select a context first');
+    }
+
+    try {
+      return ok(detectCloudAuth(contextName, resolveKubeconfigPath()));
+    } catch (error) {
+      return errFrom(error, 'Failed to detect cloud auth');
+    }
+  });
+
+  ipcMain.handle('kube:grep-logs', async (_event, params: GrepLogsSpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.namespace || !params?.labelSelector?.trim()) {
+      return err('Namespace and a label selector are required');
+    }
+
+    if (!params?.pattern) {
+      return err('Search pattern is required');
+    }
+
+    if (params.tailLines !== undefined && (!Number.isInteger(params.tailLines) || params.tailLines < 1)) {
+      return err('Tail lines must be a positive number');
+    }
+
+    if (
+      params.contextLines !== undefined &&
+      (!Number.isInteger(params.contextLines) || params.contextLines < 0 || params.contextLines > 50)
+    ) {
+      return err('Context lines must be between 0 and 50');
+    }
+
+    try {
+      return ok(await grepLogs(params));
+    } catch (error) {
+      return errFrom(error, 'Failed to search logs');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:patch-resource',
+    async (
+      _event,
+      params: {
+        kind: string;
+        name: string;
+        namespace?: string;
+        patch: string | object;
+        patchType?: PatchType;
+        context: string;
+      }
+    ) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.kind || !params?.name) {
+        return err('Resource kind and name are required');
+      }
+
+      if (params.patch === undefined || params.patch === null) {
+        return err('Patch is required');
+      }
+
+      const patchType = params.patchType ?? 'strategic';
+      try {
+        return ok(
+          await trackAction(
+            {
+              action: 'patch',
+              context: params.context,
+              namespace: params.namespace ?? null,
+              target: `${params.kind}/${params.name}`,
+              // The patch body can carry Secret data, so only its type is kept
+              detail: `${patchType} patch`,
+            },
+            () =>
+              patchResource(params.kind, params.name, params.namespace, params.patch, patchType, params.context)
+          )
+        );
+      } catch (error) {
+        return errFrom(error, 'Failed to patch resource');
+      }
+    }
+  );
+
+  ipcMain.handle(
+    'kube:watch-job',
+    async (_event, params: { name: string; namespace: string; context: string }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.name || !params?.namespace) {
+        return err('Job name and namespace are required');
+      }
+
+      try {
+        return ok({ watchId: await watchJob(params.name, params.namespace, params.context) });
+      } catch (error) {
+        return errFrom(error, 'Failed to watch job');
+      }
+    }
+  );
+
+  ipcMain.handle(
+    'kube:trigger-cronjob',
+    async (_event, params: { name: string; namespace: string; context: string }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.name || !params?.namespace) {
+        return err('CronJob name and namespace are required');
+      }
+
+      try {
+        return ok(
+          await trackAction(
+            {
+              action: 'trigger-cronjob',
+              context: params.context,
+              namespace: params.namespace,
+              target: `cronjob/${params.name}`,
+              detail: null,
+            },
+            () => triggerCronJob(params.name, params.namespace, params.context),
+            (result) => ({ detail: `created job/${result.jobName}` })
+          )
+        );
+      } catch (error) {
+        return errFrom(error, 'Failed to trigger cronjob');
+      }
+    }
+  );
+
+  ipcMain.handle(
+    'kube:get-pod-probes',
+    async (_event, params: { pod: string; namespace: string; context: string }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.pod || !params?.namespace) {
+        return err('Pod name and namespace are required');
+      }
+
+      try {
+        return ok(await getPodProbes(params.pod, params.namespace, params.context));
+      } catch (error) {
+        return errFrom(error, 'Failed to read pod probes');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:exec-with-stdin-file', async (_event, params: ExecStdinFileSpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.pod || !params?.namespace || !params?.stdinPath) {
+      return err('Pod, namespace and a local file are required');
+    }
+
+    if (!Array.isArray(params.command) || params.command.length === 0 || !params.command[0]) {
+      return err('Command to run in the container is required');
+    }
+
+    try {
+      // Logged when it starts; the program's own result arrives later as exec:progress
+      return ok(
+        await trackAction(
+          {
+            action: 'exec-stdin-file',
+            context: params.context,
+            namespace: params.namespace,
+            target: `pod/${params.pod}`,
+            detail: `${params.command.join(' ')} < ${params.stdinPath}`,
+          },
+          () => execWithStdinFile(params)
+        )
+      );
+    } catch (error) {
+      return errFrom(error, 'Failed to start exec');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:compare-resource-across',
+    async (
+      _event,
+      params: {
+        kind: string;
+        name: string;
+        a: { context: string; namespace?: string };
+        b: { context: string; namespace?: string };
+      }
+    ) => {
+      if (!params?.a?.context || !params?.b?.context) {
+        return err('Select a context for both sides');
+      }
+
+      if (!params?.kind || !params?.name) {
+        return err('Resource kind and name are required');
+      }
+
+      try {
+        return ok(await compareResourceAcross(params.kind, params.name, params.a, params.b));
+      } catch (error) {
+        return errFrom(error, 'Failed to compare resources');
+      }
+    }
+  );
+
+  ipcMain.handle(
+    'kube:search-contexts',
+    async (_event, params: { pattern: string; fuzzy?: boolean; limit?: number }) => {
+      if (!params?.pattern) {
+        return err('Search pattern is required');
+      }
+
+      if (params.limit !== undefined && (!Number.isInteger(params.limit) || params.limit < 1)) {
+        return err('Limit must be a positive integer');
+      }
+
+      try {
+        return ok(await searchContexts(params.pattern, params.fuzzy === true, params.limit));
+      } catch (error) {
+        return errFrom(error, 'Failed to search contexts');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:wait-for-condition', async (_event, params: WaitSpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.kind || !params?.name || !params?.condition) {
+      return err('Resource kind, name and condition are required');
+    }
+
+    try {
+      return ok({ waitId: waitForCondition(params) });
+    } catch (error) {
+      return errFrom(error, 'Failed to start wait');
+    }
+  });
+
+  ipcMain.handle('kube:cancel-wait', async (_event, waitId: string) => {
+    try {
+      return ok({ cancelled: cancelWait(waitId) });
+    } catch (error) {
+      return errFrom(error, 'Failed to cancel wait');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:set-labels',
+    async (
+      _event,
+      params: {
+        kind: string;
+        name: string;
+        namespace?: string;
+        labels: Record<string, string | null>;
+        overwrite?: boolean;
+        context: string;
+      }
+    ) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.kind || !params?.name) {
+        return err('Resource kind and name are required');
+      }
+
+      const labels = params.labels ?? {};
+      try {
+        return ok(
+          await trackAction(
+            {
+              action: 'set-labels',
+              context: params.context,
+              namespace: params.namespace ?? null,
+              target: `${params.kind}/${params.name}`,
+              detail: describeKeyChanges(labels),
+            },
+            () =>
+              setLabels(params.kind, params.name, params.namespace, labels, params.overwrite === true, params.context)
+          )
+        );
+      } catch (error) {
+        return errFrom(error, 'Failed to update labels');
+      }
+    }
+  );
+
+  ipcMain.handle(
+    'kube:set-annotations',
+    async (
+      _event,
+      params: {
+        kind: string;
+        name: string;
+        namespace?: string;
+        annotations: Record<string, string | null>;
+        overwrite?: boolean;
+        context: string;
+      }
+    ) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.kind || !params?.name) {
+        return err('Resource kind and name are required');
+      }
+
+      const annotations = params.annotations ?? {};
+      try {
+        return ok(
+          await trackAction(
+            {
+              action: 'set-annotations',
+              context: params.context,
+              namespace: params.namespace ?? null,
+              target: `${params.kind}/${params.name}`,
+              detail: describeKeyChanges(annotations),
+            },
+            () =>
+              setAnnotations(
+                params.kind,
+                params.name,
+                params.namespace,
+                annotations,
+                params.overwrite === true,
+                params.context
+              )
+          )
+        );
+      } catch (error) {
+        return errFrom(error, 'Failed to update annotations');
+      }
+    }
+  );
+
+  ipcMain.handle(
+    'kube:get-pod-resources',
+    async (_event, params: { pod: string; namespace: string; context: string }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.pod || !params?.namespace) {
+        return err('Pod name and namespace are required');
+      }
+
+      try {
+        return ok(await getPodResources(params.pod, params.namespace, params.context));
+      } catch (error) {
+        return errFrom(error, 'Failed to read pod resources');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:watch-pod-path', async (_event, params: PodPathWatchSpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.pod || !params?.namespace || !params?.path) {
+      return err('Pod name, namespace and path are required');
+    }
+
+    try {
+      return ok({ watchId: podPathWatchManager.startPodPathWatch(params) });
+    } catch (error) {
+      return errFrom(error, 'Failed to watch pod path');
+    }
+  });
+
+  ipcMain.handle('kube:stop-pod-path-watch', async (_event, watchId: string) => {
+    try {
+      return ok({ stopped: podPathWatchManager.stopPodPathWatch(watchId) });
+    } catch (error) {
+      return errFrom(error, 'Failed to stop pod path watch');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:set-log-sink',
+    async (_event, params: { streamId: string; sink: LogSink; filePath?: string }) => {
+      if (!params?.streamId) {
+        return err('Log stream id is required');
+      }
+
+      if (params.sink !== 'ui' && params.sink !== 'file' && params.sink !== 'both') {
+        return err('Sink must be ui, file or both');
+      }
+
+      try {
+        return ok(logStreamManager.setLogSink(params.streamId, params.sink, params.filePath));
+      } catch (error) {
+        return errFrom(error, 'Failed to set log sink');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:list-log-sinks', async () => {
+    try {
+      return ok(logStreamManager.listLogSinks());
+    } catch (error) {
+      return errFrom(error, 'Failed to list log sinks');
+    }
+  });
+
+  ipcMain.handle('kube:set-context-tags', async (_event, params: { name: string; tags: string[] | null }) => {
+    if (!params?.name) {
+      return err('Context name is required');
+    }
+
+    if (params.tags !== null && !Array.isArray(params.tags)) {
+      return err('Tags must be a list, or null to reset');
+    }
+
+    try {
+      const { contexts } = await loadKubeConfig();
+      const context = contexts.find((candidate) => candidate.name === params.name);
+      if (!context) {
+        return err(`Context ${params.name} not found`, 'NotFound');
+      }
+      return ok(setContextTags(params.name, context.server ?? null, params.tags));
+    } catch (error) {
+      return errFrom(error, 'Failed to set context tags');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:get-hpa-status',
+    async (_event, params: { name: string; namespace: string; context: string }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.name || !params?.namespace) {
+        return err('HPA name and namespace are required');
+      }
+
+      try {
+        return ok(await getHpaStatus(params.name, params.namespace, params.context));
+      } catch (error) {
+        return errFrom(error, 'Failed to read autoscaler status');
+      }
+    }
+  );
+
+  ipcMain.handle(
+    'kube:watch-nodes',
+    async (_event, params: { context: string; labelSelector?: string; fieldSelector?: string; view?: string }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      try {
+        return ok({ watchId: watchManager.watchNodes(params) });
+      } catch (error) {
+        return errFrom(error, 'Failed to watch nodes');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:apply-template', async (_event, params: TemplateApplySpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.template || !params.template.trim()) {
+      return err('Provide a template to apply');
+    }
+
+    if (params.dryRun) {
+      try {
+        return ok(await applyTemplate(params));
+      } catch (error) {
+        return errFrom(error, 'Failed to apply template');
+      }
+    }
+
+    try {
+      const result = await trackAction(
+        {
+          action: 'apply',
+          context: params.context,
+          namespace: params.namespace ?? null,
+          target: 'template',
+          detail: null,
+        },
+        () => applyTemplate(params),
+        (applied) => ({ detail: applied.applied.join(', ') || null })
+      );
+      return ok(result);
+    } catch (error) {
+      return errFrom(error, 'Failed to apply template');
+    }
+  });
+
+  ipcMain.handle('kube:validate-kubeconfig', async () => {
+    try {
+      return ok(await validateKubeconfig());
+    } catch (error) {
+      return errFrom(error, 'Failed to validate kubeconfig');
+    }
+  });
+
+  ipcMain.handle('kube:repair-kubeconfig', async (_event, strategy: KubeconfigRepairStrategy) => {
+    if (!strategy) {
+      return err('Repair strategy is required');
+    }
+
+    try {
+      const result = await trackAction(
+        { action: 'repair-kubeconfig', context: null, namespace: null, target: null, detail: strategy },
+        () => repairKubeconfig(strategy),
+        (repair) => ({ detail: `${repair.changes.length} change(s)` })
+      );
+      clearPermissionsCache();
+      return ok(result);
+    } catch (error) {
+      return errFrom(error, 'Failed to repair kubeconfig');
+    }
+  });
+
+  ipcMain.handle('kube:stream-job-logs', async (_event, params: JobLogStreamSpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.job || !params?.namespace) {
+      return err('Job name and namespace are required');
+    }
+
+    try {
+      return ok({ streamId: await logStreamManager.streamJobLogs(params) });
+    } catch (error) {
+      return errFrom(error, 'Failed to stream job logs');
+    }
+  });
+
+  ipcMain.handle('kube:whoami', async (_event, context: string) => {
+    if (!context) {
+      return err('Select a context first');
+    }
+
+    try {
+      return ok(await whoAmI(context));
+    } catch (error) {
+      return errFrom(error, 'Failed to read user identity');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:get-service-endpoints',
+    async (_event, params: { name: string; namespace: string; context: string }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.name || !params?.namespace) {
+        return err('Service name and namespace are required');
+      }
+
+      try {
+        return ok(await getServiceEndpoints(params.name, params.namespace, params.context));
+      } catch (error) {
+        return errFrom(error, 'Failed to read service endpoints');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:export-logs', async (_event, params: LogExportSpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.pod || !params?.namespace) {
+      return err('Pod name and namespace are required');
+    }
+
+    if (!params.since) {
+      return err('A start time is required');
+    }
+
+    try {
+      return ok(await exportLogs(params));
+    } catch (error) {
+      return errFrom(error, 'Failed to export logs');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:get-configmap',
+    async (_event, params: { name: string; namespace: string; context: string }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.name || !params?.namespace) {
+        return err('ConfigMap name and namespace are required');
+      }
+
+      try {
+        return ok(await getConfigMap(params.name, params.namespace, params.context));
+      } catch (error) {
+        return errFrom(error, 'Failed to get ConfigMap');
+      }
+    }
+  );
+
+  ipcMain.handle(
+    'kube:update-configmap-key',
+    async (_event, params: { name: string; namespace: string; key: string; value: string; context: string }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.name || !params?.namespace || !params?.key) {
+        return err('ConfigMap name, namespace and key are required');
+      }
+
+      if (typeof params.value !== 'string') {
+        return err('Value must be a string');
+      }
+
+      try {
+        return ok(
+          await trackAction(
+            {
+              action: 'update-configmap',
+              context: params.context,
+              namespace: params.namespace,
+              target: `configmap/${params.name}`,
+              detail: `key ${params.key}`,
+            },
+            () => updateConfigMapKey(params.name, params.namespace, params.key, params.value, params.context)
+          )
+        );
+      } catch (error) {
+        return errFrom(error, 'Failed to update ConfigMap');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:start-event-rate-monitor', async (_event, params: EventRateMonitorSpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    try {
+      return ok({ monitorId: eventRateMonitorManager.startEventRateMonitor(params) });
+    } catch (error) {
+      return errFrom(error, 'Failed to start event rate monitor');
+    }
+  });
+
+  ipcMain.handle('kube:stop-event-rate-monitor', async (_event, monitorId: string) => {
+    try {
+      return ok({ stopped: eventRateMonitorManager.stopEventRateMonitor(monitorId) });
+    } catch (error) {
+      return errFrom(error, 'Failed to stop event rate monitor');
+    }
+  });
+
+  ipcMain.handle('kube:get-ingress', async (_event, params: { name: string; namespace: string; context: string }) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.name || !params?.namespace) {
+      return err('Ingress name and namespace are required');
+    }
+
+    try {
+      return ok(await getIngress(params.name, params.namespace, params.context));
+    } catch (error) {
+      return errFrom(error, 'Failed to get Ingress');
+    }
+  });
+
+  ipcMain.handle('kube:format-ages', async (_event, timestamps: (string | null)[]) => {
+    if (!Array.isArray(timestamps)) {
+      return err('Timestamps must be a list');
+    }
+
+    try {
+      // One clock reading so ages in the same view agree
+      const now = Date.now();
+      return ok(timestamps.map((timestamp) => formatAge(timestamp, now)));
+    } catch (error) {
+      return errFrom(error, 'Failed to format ages');
+    }
+  });
+
+  ipcMain.handle('kube:export-namespace', async (_event, params: NamespaceExportSpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.namespace || !params?.outputDir) {
+      return err('Namespace and output directory are required');
+    }
+
+    try {
+      return ok(await exportNamespace(params));
+    } catch (error) {
+      return errFrom(error, 'Failed to export namespace');
+    }
+  });
+
+  ipcMain.handle('kube:apply-directory', async (_event, params: DirectoryApplySpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.dir) {
+      return err('Directory is required');
+    }
+
+    try {
+      const result = await trackAction(
+        {
+          action: 'apply',
+          context: params.context,
+          namespace: params.namespace ?? null,
+          target: params.dir,
+          detail: null,
+        },
+        () => applyDirectory(params),
+        (applied) => ({
+          detail: `${applied.applied} file(s) applied`,
+          error: applied.failed > 0 ? `${applied.failed} file(s) failed` : null,
+        })
+      );
+      return ok(result);
+    } catch (error) {
+      return errFrom(error, 'Failed to apply directory');
+    }
+  });
+
+  ipcMain.handle('kube:get-kubectl-pool-stats', async () => {
+    try {
+      return ok(kubectlPool.getStats());
+    } catch (error) {
+      return errFrom(error, 'Failed to read kubectl pool stats');
+    }
+  });
+
+  ipcMain.handle('kube:set-kubectl-pool-limits', async (_event, limits: Partial<KubectlPoolLimits>) => {
+    if (!limits || typeof limits !== 'object') {
+      return err('Limits are required');
+    }
+
+    try {
+      return ok(kubectlPool.setLimits(limits));
+    } catch (error) {
+      return errFrom(error, 'Failed to set kubectl pool limits');
+    }
+  });
+
+  ipcMain.handle('kube:check-cluster-cert', async (_event, contextName: string) => {
+    if (!contextName) {
+      return err('Select a context first');
+    }
+
+    try {
+      return ok(await checkClusterCertExpiry(contextName));
+    } catch (error) {
+      return errFrom(error, 'Failed to check cluster certificate');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:get-drift',
+    async (_event, params: { kind: string; name: string; namespace?: string; context: string }) => {
+      if (!params?.context) {
+        return err('No context selected');
+      }
+
+      if (!params?.kind || !params?.name) {
+        return err('Resource kind and name are required');
+      }
+
+      try {
+        return ok(await getDrift(params.kind, params.name, params.namespace, params.context));
+      } catch (error) {
+        return errFrom(error, 'Failed to check drift');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:get-log-level-settings', async () => {
+    try {
+      return ok(getLogLevelSettings());
+    } catch (error) {
+      return errFrom(error, 'Failed to read log level settings');
+    }
+  });
+
+  ipcMain.handle('kube:set-log-level-settings', async (_event, settings: Partial<LogLevelSettings>) => {
+    try {
+      return ok(setLogLevelSettings(settings ?? {}));
+    } catch (error) {
+      return errFrom(error, 'Failed to set log level settings');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:promote-to-background',
+    async (_event, params: { id: string; label?: string; view?: string }) => {
+      if (!params?.id) {
+        return err('Operation id is required');
+      }
+
+      try {
+        return ok(backgroundSessionManager.promote(params.id, params.label, params.view));
+      } catch (error) {
+        return errFrom(error, 'Failed to move operation to the background');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:list-background-sessions', async () => {
+    try {
+      return ok(backgroundSessionManager.listSessions());
+    } catch (error) {
+      return errFrom(error, 'Failed to list background sessions');
+    }
+  });
+
+  ipcMain.handle('kube:reattach-session', async (_event, id: string, view?: string) => {
+    try {
+      return ok(backgroundSessionManager.reattach(id, view));
+    } catch (error) {
+      return errFrom(error, 'Failed to reattach session');
+    }
+  });
+
+  ipcMain.handle('kube:close-background-session', async (_event, id: string) => {
+    try {
+      return ok({ closed: backgroundSessionManager.closeSession(id) });
+    } catch (error) {
+      return errFrom(error, 'Failed to close background session');
+    }
+  });
+
+  ipcMain.handle('kube:list-resources-paged', async (_event, spec: ResourcePageSpec) => {
+    if (!spec?.context) {
+      return err('No context selected');
+    }
+
+    if (!spec?.kind) {
+      return err('Resource kind is required');
+    }
+
+    try {
+      return ok(await listResourcesPaged(spec));
+    } catch (error) {
+      return errFrom(error, 'Failed to list resources');
+    }
+  });
+
+  ipcMain.handle('kube:generate-sa-kubeconfig', async (_event, spec: ServiceAccountKubeconfigSpec) => {
+    if (!spec?.context) {
+      return err('No context selected');
+    }
+
+    if (!spec?.serviceAccount || !spec?.namespace) {
+      return err('Service account and namespace are required');
+    }
+
+    try {
+      return ok(
+        await trackAction(
+          {
+            action: 'generate-sa-kubeconfig',
+            context: spec.context,
+            namespace: spec.namespace,
+            target: `serviceaccount/${spec.serviceAccount}`,
+            detail: null,
+          },
+          () => generateServiceAccountKubeconfig(spec),
+          (result) => ({ detail: `${result.tokenSource} token written to ${result.path}` })
+        )
+      );
+    } catch (error) {
+      return errFrom(error, 'Failed to generate kubeconfig');
+    }
+  });
+
+  ipcMain.handle('kube:start-restart-leaderboard', async (_event, params: RestartLeaderboardSpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    try {
+      return ok({ leaderboardId: restartLeaderboardManager.startRestartLeaderboard(params) });
+    } catch (error) {
+      return errFrom(error, 'Failed to start restart leaderboard');
+    }
+  });
+
+  ipcMain.handle('kube:get-restart-leaderboard', async (_event, leaderboardId: string) => {
+    try {
+      return ok(restartLeaderboardManager.getRestartLeaderboard(leaderboardId));
+    } catch (error) {
+      return errFrom(error, 'Failed to read restart leaderboard');
+    }
+  });
+
+  ipcMain.handle('kube:stop-restart-leaderboard', async (_event, leaderboardId: string) => {
+    try {
+      return ok({ stopped: restartLeaderboardManager.stopRestartLeaderboard(leaderboardId) });
+    } catch (error) {
+      return errFrom(error, 'Failed to stop restart leaderboard');
+    }
+  });
+
+  ipcMain.handle('kube:get-raw', async (_event, params: { path: string; context: string; view?: string }) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.path) {
+      return err('API path is required');
+    }
+
+    try {
+      return ok(await getRaw(params.path, params.context, params.view));
+    } catch (error) {
+      return errFrom(error, 'Failed to request API path');
+    }
+  });
+
+  ipcMain.handle('kube:stream-raw', async (_event, spec: RawStreamSpec) => {
+    if (!spec?.context) {
+      return err('Select a context first');
+    }
+
+    if (!spec?.path) {
+      return err('API path is required');
+    }
+
+    try {
+      return ok({ streamId: rawStreamManager.startRawStream(spec) });
+    } catch (error) {
+      return errFrom(error, 'Failed to stream API path');
+    }
+  });
+
+  ipcMain.handle('kube:stop-raw-stream', async (_event, streamId: string) => {
+    try {
+      return ok({ stopped: rawStreamManager.stopRawStream(streamId) });
+    } catch (error) {
+      return errFrom(error, 'Failed to stop raw stream');
+    }
+  });
+
+  ipcMain.handle(
+    'kube:get-workload-pod-spread',
+    async (_event, params: { kind: string; name: string; namespace: string; context: string }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.kind || !params?.name || !params?.namespace) {
+        return err('Kind, name and namespace are required');
+      }
+
+      try {
+        return ok(await getWorkloadPodSpread(params.kind, params.name, params.namespace, params.context));
+      } catch (error) {
+        return errFrom(error, 'Failed to get pod spread');
+      }
+    }
+  );
+
+  ipcMain.handle(
+    'kube:watch-deployment',
+    async (_event, params: { name: string; namespace: string; context: string }) => {
+      if (!params?.context) {
+        return err('Select a context first');
+      }
+
+      if (!params?.name || !params?.namespace) {
+        return err('Deployment name and namespace are required');
+      }
+
+      try {
+        return ok({ watchId: await watchDeployment(params.name, params.namespace, params.context) });
+      } catch (error) {
+        return errFrom(error, 'Failed to watch deployment');
+      }
+    }
+  );
+
+  ipcMain.handle('kube:list-storage-classes', async (_event, context: string) => {
+    if (!context) {
+      return err('Select a context first');
+    }
+
+    try {
+      return ok(await listStorageClasses(context));
+    } catch (error) {
+      return errFrom(error, 'Failed to list storage classes');
+    }
+  });
+
+  ipcMain.handle('kube:apply-kustomize', async (_event, params: KustomizeApplySpec) => {
+    if (!params?.context) {
+      return err('Select a context first');
+    }
+
+    if (!params?.directory) {
+      return err('Kustomization directory is required');
+    }
+
+    try {
+      return ok(await applyKustomize(params));
+    } catch (error) {
+      return errFrom(error, 'Failed to apply kustomization');
+    }
+  });
+
+  ipcMain.handle('kube:import-kubeconfig', async (_event, params: { sourcePath: string; merge?: boolean }) => {
+    if (!params?.sourcePath) {
+      return err('Kubeconfig file is required');
+    }
+
+    try {
+      // A preview changes nothing and isn't logged
+      const result = params.merge === true
+        ? await trackAction(
+            { action: 'import-kubeconfig', context: null, namespace: null, target: params.sourcePath, detail: null },
+            () => importKubeconfig(params.sourcePath, true),
+            (imported) => ({
+              detail: `${imported.contexts.filter((entry) => entry.action !== 'unchanged').length} context(s) imported`,
+            })
+          )
+        : await importKubeconfig(params.sourcePath, false);
+      if (result.merged) {
+        clearPermissionsCache();
+      }
+      return ok(result);
+    } catch (error) {
+      return errFrom(error, 'Failed to import kubeconfig');
+    }
+  });
+
+  // App IPC handlers
+  ipcMain.handle('app:set-log-level', async (_event, level: string) => {
+    try {
+      return ok({ level: setLogLevel(level) });
+    } catch (error) {
+      return errFrom(error, 'Failed to set log level');
+    }
+  });
+
+  ipcMain.handle('app:get-log-settings', async () => {
+    return ok(getLogSettings());
+  });
+
+  ipcMain.handle('app:get-log-file-path', async () => {
+    return ok({ path: getLogFilePath() });
+  });
+
+  ipcMain.handle('app:get-command-policy', async () => {
+    return ok(getCommandPolicy());
+  });
+
+  ipcMain.handle('app:self-test', async () => {
+    try {
+      return ok(await runSelfTest());
+    } catch (error) {
+      return errFrom(error, 'Failed to run self-test');
+    }
+  });
+
+  ipcMain.handle('app:get-action-log', async (_event, filter?: ActionLogFilter) => {
+    try {
+      return ok(getActionLog(filter));
+    } catch (error) {
+      return errFrom(error, 'Failed to read the action log');
+    }
+  });
+
+  // Terminal IPC handlers
+  ipcMain.handle('terminal:create', async (_event, id: string, options?: TerminalOptions) => {
+    try {
+      await terminalManager.createTerminal(id, options || {});
+      return ok({ id });
+    } catch (error) {
+      return errFrom(error, 'Failed to create terminal');
+    }
+  });
+
+  ipcMain.handle('terminal:attach-to-pod', async (_event, id: string, options: AttachToPodOptions) => {
+    if (!options?.context) {
+      return err('Select a context first');
+    }
+
+    if (!options?.pod || !options?.namespace) {
+      return err('Pod name and namespace are required');
+    }
+
+    try {
+      await attachToPod(id, { ...options, stdin: options.stdin === true });
+      return ok({ id });
+    } catch (error) {
+      return errFrom(error, 'Failed to attach to pod');
+    }
+  });
+
+  ipcMain.handle('terminal:write', async (_event, id: string, data: string) => {
+    try {
+      terminalManager.writeToTerminal(id, data);
+      return ok({});
+    } catch (error) {
+      return errFrom(error, 'Failed to write to terminal');
+    }
+  });
+
+  ipcMain.handle(
+    'terminal:resize',
+    async (_event, id: string, cols: number, rows: number, pixelWidth?: number, pixelHeight?: number) => {
+      try {
+        terminalManager.resizeTerminal(id, cols, rows, pixelWidth, pixelHeight);
+        return ok({});
+      } catch (error) {
+        const message = error instanceof Error ? error.message : 'Failed to resize terminal';
+        return err(message);
+      }
+    }
+  );
+
+  ipcMain.handle('terminal:export-session-bundle', async (_event, outputPath: string) => {
+    if (!outputPath) {
+      return err('Output path is required');
+    }
+
+    try {
+      const summary = await exportSessionBundle(outputPath);
+      return ok(summary);
+    } catch (error) {
+      return errFrom(error, 'Failed to export session bundle');
+    }
+  });
+
+  ipcMain.handle('terminal:set-clipboard-access', async (_event, id: string, enabled: boolean) => {
+    try {
+      terminalManager.setClipboardAccess(id, enabled === true);
+      return ok({});
+    } catch (error) {
+      return errFrom(error, 'Failed to update clipboard access');
+    }
+  });
+
+  ipcMain.handle('terminal:set-notify-pattern', async (_event, id: string, pattern: string) => {
+    try {
+      terminalManager.setNotifyPattern(id, pattern ?? '');
+      return ok({});
+    } catch (error) {
+      return errFrom(error, 'Failed to set notify pattern');
+    }
+  });
+
+  ipcMain.handle('terminal:paste', async (_event, id: string, data: string, confirmed?: boolean) => {
+    try {
+      terminalManager.pasteToTerminal(id, data, confirmed === true);
+      return ok({});
+    } catch (error) {
+      return errFrom(error, 'Failed to paste into terminal');
+    }
+  });
+
+  ipcMain.handle('terminal:get-paste-limits', async () => {
+    try {
+      return ok(getPasteLimits());
+    } catch (error) {
+      return errFrom(error, 'Failed to read paste limits');
+    }
+  });
+
+  ipcMain.handle('terminal:set-paste-limits', async (_event, limits: Partial<TerminalPasteLimits>) => {
+    try {
+      return ok(setPasteLimits(limits ?? {}));
+    } catch (error) {
+      return errFrom(error, 'Failed to set paste limits');
+    }
+  });
+
+  ipcMain.handle('terminal:get-last-output', async (_event, id: string) => {
+    try {
+      return ok(terminalManager.getLastOutput(id));
+    } catch (error) {
+      return errFrom(error, 'Failed to read terminal output');
+    }
+  });
+
+  ipcMain.handle('terminal:list', async () => {
+    try {
+      return ok(terminalManager.listTerminals());
+    } catch (error) {
+      return errFrom(error, 'Failed to list terminals');
+    }
+  });
+
+  ipcMain.handle(
+    'terminal:get-buffer',
+    async (_event, id: string, range?: { start?: number; length?: number }) => {
+      try {
+        return ok(terminalManager.getTerminalBuffer(id, range?.start, range?.length));
+      } catch (error) {
+        return errFrom(error, 'Failed to read terminal buffer');
+      }
+    }
+  );
+
+  ipcMain.handle(
+    'terminal:export-env',
+    async (_event, id: string, vars: Record<string, string | null>) => {
+      try {
+        terminalManager.exportToTerminal(id, vars ?? {});
+        return ok({});
+      } catch (error) {
+        return errFrom(error, 'Failed to export environment');
+      }
+    }
+  );
+
+  ipcMain.handle('terminal:debug-pod', async (_event, id: string, options: DebugPodOptions) => {
+    if (!options?.context) {
+      return err('Select a context first');
+    }
+
+    if (!options?.pod || !options?.namespace) {
+      return err('Pod name and namespace are required');
+    }
+
+    try {
+      await debugPod(id, options);
+      return ok({ id });
+    } catch (error) {
+      return errFrom(error, 'Failed to start debug container');
+    }
+  });
+
+  ipcMain.handle('terminal:run-script', async (_event, id: string, scriptPath: string) => {
+    if (!scriptPath) {
+      return err('Script path is required');
+    }
+
+    try {
+      return ok(await terminalScriptRunner.runScriptInTerminal(id, scriptPath));
+    } catch (error) {
+      return errFrom(error, 'Failed to run script');
+    }
+  });
+
+  ipcMain.handle('terminal:cancel-script', async (_event, id: string) => {
+    try {
+      return ok({ cancelled: terminalScriptRunner.cancelScript(id) });
+    } catch (error) {
+      return errFrom(error, 'Failed to cancel script');
+    }
+  });
+
+  ipcMain.handle('terminal:open-node-shell', async (_event, id: string, options: NodeShellOptions) => {
+    if (!options?.context) {
+      return err('Select a context first');
+    }
+
+    if (!options?.node) {
+      return err('Node name is required');
+    }
+
+    try {
+      await openNodeShell(id, options);
+      return ok({ id });
+    } catch (error) {
+      return errFrom(error, 'Failed to open node shell');
+    }
+  });
+
+  ipcMain.handle('terminal:open-ssh', async (_event, id: string, options: SshTerminalOptions) => {
+    if (!options?.host) {
+      return err('Host is required');
+    }
+
+    try {
+      await openSshTerminal(id, options);
+      return ok({ id });
+    } catch (error) {
+      return errFrom(error, 'Failed to open SSH terminal');
+    }
+  });
+
+  ipcMain.handle('terminal:list-profiles', async () => {
+    try {
+      return ok(listProfiles());
+    } catch (error) {
+      return errFrom(error, 'Failed to list terminal profiles');
+    }
+  });
+
+  ipcMain.handle('terminal:save-profile', async (_event, profile: TerminalProfile) => {
+    if (!profile?.name?.trim()) {
+      return err('Profile name is required');
+    }
+
+    try {
+      return ok(saveProfile(profile));
+    } catch (error) {
+      return errFrom(error, 'Failed to save terminal profile');
+    }
+  });
+
+  ipcMain.handle('terminal:list-orphaned-processes', async () => {
+    try {
+      return ok(await listOrphanedProcesses());
+    } catch (error) {
+      return errFrom(error, 'Failed to list orphaned processes');
+    }
+  });
+
+  ipcMain.handle('terminal:reap-orphans', async () => {
+    try {
+      return ok(await reapOrphans());
+    } catch (error) {
+      return errFrom(error, 'Failed to reap orphaned processes');
+    }
+  });
+
+  ipcMain.handle('terminal:create-broadcast-group', async (_event, terminalIds: string[]) => {
+    if (!Array.isArray(terminalIds)) {
+      return err('Select the terminals to broadcast to');
+    }
+
+    try {
+      return ok(broadcastGroupManager.createBroadcastGroup(terminalIds));
+    } catch (error) {
+      return errFrom(error, 'Failed to create broadcast group');
+    }
+  });
+
+  ipcMain.handle('terminal:broadcast-write', async (_event, groupId: string, data: string) => {
+    try {
+      return ok(broadcastGroupManager.broadcastWrite(groupId, data));
+    } catch (error) {
+      return errFrom(error, 'Failed to broadcast input');
+    }
+  });
+
+  ipcMain.handle('terminal:delete-broadcast-group', async (_event, groupId: string) => {
+    try {
+      return ok({ deleted: broadcastGroupManager.deleteBroadcastGroup(groupId) });
+    } catch (error) {
+      return errFrom(error, 'Failed to delete broadcast group');
+    }
+  });
+
+  ipcMain.handle('terminal:interrupt', async (_event, id: string) => {
+    try {
+      return ok(await terminalManager.interruptTerminal(id));
+    } catch (error) {
+      return errFrom(error, 'Failed to interrupt terminal');
+    }
+  });
+
+  ipcMain.handle('terminal:attach-output-pipe', async (_event, id: string, pipePath: string) => {
+    if (!pipePath) {
+      return err('Pipe path is required');
+    }
+
+    try {
+      return ok(await terminalManager.attachOutputPipe(id, pipePath));
+    } catch (error) {
+      return errFrom(error, 'Failed to attach output pipe');
+    }
+  });
+
+  ipcMain.handle('terminal:detach-output-pipe', async (_event, id: string) => {
+    try {
+      return ok(terminalManager.detachOutputPipe(id));
+    } catch (error) {
+      return errFrom(error, 'Failed to detach output pipe');
+    }
+  });
+
+  ipcMain.handle('terminal:export-text', async (_event, id: string, stripAnsi?: boolean) => {
+    try {
+      return ok(terminalManager.exportTerminalText(id, stripAnsi !== false));
+    } catch (error) {
+      return errFrom(error, 'Failed to export terminal text');
+    }
+  });
+
+  ipcMain.handle('terminal:start-web-socket', async (_event, id: string, port?: number) => {
+    if (port !== undefined && (!Number.isInteger(port) || port < 1 || port > 65535)) {
+      return err('Port must be between 1 and 65535');
+    }
+
+    try {
+      return ok(await terminalManager.startTerminalWebSocket(id, port));
+    } catch (error) {
+      return errFrom(error, 'Failed to start WebSocket server');
+    }
+  });
+
+  ipcMain.handle('terminal:stop-web-socket', async (_event, id: string) => {
+    try {
+      return ok({ stopped: terminalManager.stopTerminalWebSocket(id) });
+    } catch (error) {
+      return errFrom(error, 'Failed to stop WebSocket server');
+    }
+  });
+
+  ipcMain.handle('terminal:snapshot', async () => {
+    try {
+      return ok(terminalManager.snapshotTerminals());
+    } catch (error) {
+      return errFrom(error, 'Failed to snapshot terminals');
+    }
+  });
+
+  ipcMain.handle('terminal:restore-snapshot', async (_event, snapshot: TerminalSnapshot) => {
+    try {
+      return ok(await terminalManager.restoreFromSnapshot(snapshot));
+    } catch (error) {
+      return errFrom(error, 'Failed to restore terminals');
+    }
+  });
+
+  ipcMain.handle('terminal:get-diagnostics', async () => {
+    try {
+      return ok(terminalManager.getTerminalDiagnostics());
+    } catch (error) {
+      return errFrom(error, 'Failed to read terminal diagnostics');
+    }
+  });
+
+  ipcMain.handle('terminal:get-input-history', async (_event, profile: string | null) => {
+    try {
+      return ok({ enabled: isInputHistoryEnabled(), lines: getInputHistory(profile ?? null) });
+    } catch (error) {
+      return errFrom(error, 'Failed to read input history');
+    }
+  });
+
+  ipcMain.handle('terminal:set-input-history-enabled', async (_event, enabled: boolean) => {
+    try {
+      setInputHistoryEnabled(enabled === true);
+      return ok({ enabled: enabled === true });
+    } catch (error) {
+      return errFrom(error, 'Failed to update input history setting');
+    }
+  });
+
+  ipcMain.handle('terminal:clear-input-history', async (_event, profile?: string | null) => {
+    try {
+      clearInputHistory(profile);
+      return ok({ cleared: true });
+    } catch (error) {
+      return errFrom(error, 'Failed to clear input history');
+    }
+  });
+
+  ipcMain.handle('terminal:get-reader-stats', async () => {
+    try {
+      return ok(terminalManager.getReaderStats());
+    } catch (error) {
+      return errFrom(error, 'Failed to read terminal reader stats');
+    }
+  });
+
+  ipcMain.handle('terminal:reconcile', async () => {
+    try {
+      return ok(terminalManager.reconcileTerminals());
+    } catch (error) {
+      return errFrom(error, 'Failed to reconcile terminals');
+    }
+  });
+
+  ipcMain.handle('terminal:run-command', async (_event, spec: LocalCommandSpec) => {
+    if (!spec?.command) {
+      return err('Command is required');
+    }
+
+    try {
+      return ok({ commandId: await localCommandManager.runCommand(spec) });
+    } catch (error) {
+      return errFrom(error, 'Failed to run command');
+    }
+  });
+
+  ipcMain.handle('terminal:stop-command', async (_event, commandId: string) => {
+    try {
+      return ok({ stopped: localCommandManager.stopCommand(commandId) });
+    } catch (error) {
+      return errFrom(error, 'Failed to stop command');
+    }
+  });
+
+  ipcMain.handle('terminal:detect-shell', async (_event, id: string, probe?: boolean) => {
+    try {
+      return ok(await terminalManager.detectTerminalShell(id, probe === true));
+    } catch (error) {
+      return errFrom(error, 'Failed to detect shell');
+    }
+  });
+
+  ipcMain.handle(
+    'terminal:measure-latency',
+    async (_event, id: string, params?: { method?: TerminalLatencyMethod; timeoutMs?: number }) => {
+      try {
+        return ok(await terminalManager.measureTerminalLatency(id, params?.method, params?.timeoutMs));
+      } catch (error) {
+        return errFrom(error, 'Failed to measure terminal latency');
+      }
+    }
+  );
+
+  ipcMain.handle('terminal:check-cwd', async (_event, directory: string) => {
+    if (!directory) {
+      return err('Directory is required');
+    }
+
+    try {
+      return ok(await checkCwd(directory));
+    } catch (error) {
+      return errFrom(error, 'Failed to check directory');
+    }
+  });
+
+  ipcMain.handle(
+    'terminal:run-commands-sequenced',
+    async (_event, id: string, commands: string[], params?: { timeoutMs?: number }) => {
+      if (!Array.isArray(commands)) {
+        return err('Commands are required');
+      }
+
+      try {
+        return ok(await terminalManager.runCommandsSequenced(id, commands, params?.timeoutMs));
+      } catch (error) {
+        return errFrom(error, 'Failed to run commands');
+      }
+    }
+  );
+
+  ipcMain.handle('terminal:get-foreground-process', async (_event, id: string) => {
+    try {
+      return ok(await terminalManager.getForegroundProcess(id));
+    } catch (error) {
+      return errFrom(error, 'Failed to read foreground process');
+    }
+  });
+
+  ipcMain.handle('terminal:create-resilient-exec', async (_event, id: string, options: ResilientExecOptions) => {
+    if (!options?.context) {
+      return err('Select a context first');
+    }
+
+    if (!options?.podSelector?.trim() || !options?.namespace) {
+      return err('Pod selector and namespace are required');
+    }
+
+    try {
+      return ok(await resilientExecManager.createResilientExec(id, options));
+    } catch (error) {
+      return errFrom(error, 'Failed to exec into pod');
     }
   });
 
@@ -191,11 +3030,28 @@ function registerIpcHandlers() {
 }
 
 app.on('ready', createWindow);
+// Before any terminal exists, so only the previous run's PTYs are loaded
+app.whenReady().then(initOrphanTracking);
 app.whenReady().then(registerIpcHandlers);
 
 app.on('window-all-closed', () => {
-  // Clean up all terminals
+  // Clean up all terminals and background kubectl processes
+  terminalManager.stopReconciler();
   terminalManager.closeAllTerminals();
+  logStreamManager.stopAllLogStreams();
+  metricsManager.stopAllMetricsStreams();
+  watchManager.stopAllWatches();
+  podFileStreamManager.stopAllPodFileStreams();
+  portForwardManager.stopAllForwards();
+  latencyProbeManager.stopAllLatencyProbes();
+  eventRateMonitorManager.stopAllEventRateMonitors();
+  restartLeaderboardManager.stopAllRestartLeaderboards();
+  rawStreamManager.stopAllRawStreams();
+  localCommandManager.stopAllCommands();
+  podPathWatchManager.stopAllPodPathWatches();
+  backgroundSessionManager.closeAllSessions();
+  resilientExecManager.stopAllResilientExecs();
+  closeActionLog();
   
   if (process.platform !== 'darwin') {
     app.quit();
@@ -0,0 +1,95 @@
+import './testSupport';
+import assert from 'node:assert/strict';
+import { after, describe, it } from 'node:test';
+import {
+  closeActionLog,
+  describeKeyChanges,
+  describeKubectlCommand,
+  getActionLog,
+  recordAction,
+  summarizeKubectlResult,
+  trackAction,
+} from './actionLog';
+
+after(() => closeActionLog());
+
+describe('describeKubectlCommand', () => {
+  it('skips reads', () => {
+    assert.equal(describeKubectlCommand('kubectl get pods -A'), null);
+    assert.equal(describeKubectlCommand('kubectl rollout status deploy/web'), null);
+  });
+
+  it('finds the verb after global flags that take a value', () => {
+    const action = describeKubectlCommand('kubectl --context prod --kubeconfig /tmp/k delete pod web-1');
+    assert.equal(action?.action, 'delete');
+    assert.equal(action?.target, 'pod web-1');
+    assert.equal(action?.context, 'prod');
+
+    const impersonated = describeKubectlCommand(
+      'kubectl --as admin --cluster c1 -n shop scale deploy/web --replicas=0'
+    );
+    assert.equal(impersonated?.action, 'scale');
+    assert.equal(impersonated?.target, 'deploy/web');
+    assert.equal(impersonated?.namespace, 'shop');
+  });
+
+  it('skips a container name given with -c', () => {
+    const action = describeKubectlCommand('kubectl -c sidecar annotate pod web-1 note=x');
+    assert.equal(action?.action, 'kubectl');
+    assert.equal(action?.target, 'pod web-1');
+  });
+
+  it('leaves out dry runs', () => {
+    assert.equal(describeKubectlCommand('kubectl delete pod web-1 --dry-run=server'), null);
+    assert.equal(describeKubectlCommand('kubectl apply -f app.yaml --dry-run'), null);
+    assert.equal(describeKubectlCommand('kubectl delete pod web-1 --dry-run=none')?.action, 'delete');
+  });
+
+  it('reports mutating rollout subcommands', () => {
+    const action = describeKubectlCommand('kubectl rollout restart deploy/web');
+    assert.equal(action?.action, 'kubectl');
+    assert.equal(action?.target, 'deploy/web');
+  });
+});
+
+describe('action summaries', () => {
+  it('marks a non-zero kubectl exit as an error', () => {
+    assert.deepEqual(summarizeKubectlResult({ stdout: '', stderr: '', code: 0 }), { error: null });
+    assert.deepEqual(summarizeKubectlResult({ stdout: '', stderr: 'Forbidden\n', code: 1 }), {
+      error: 'Forbidden',
+    });
+  });
+
+  it('names changed keys without their values', () => {
+    assert.equal(describeKeyChanges({ team: 'payments', old: null }), 'set team; removed old');
+    assert.equal(describeKeyChanges({}), null);
+  });
+});
+
+describe('recording', () => {
+  it('records outcomes newest first with secrets masked', async () => {
+    recordAction({
+      action: 'apply',
+      context: 'dev',
+      namespace: 'default',
+      target: null,
+      detail: 'kubectl create secret generic db --from-literal=password=hunter2',
+    });
+    await assert.rejects(
+      trackAction({ action: 'delete', context: 'dev', namespace: null, target: 'pod x', detail: null }, async () => {
+        throw new Error('not found');
+      })
+    );
+
+    const [failed, applied] = getActionLog({ context: 'dev' });
+    assert.equal(failed.action, 'delete');
+    assert.equal(failed.outcome, 'failure');
+    assert.equal(failed.error, 'not found');
+    assert.equal(applied.outcome, 'success');
+    assert.ok(!applied.detail?.includes('hunter2'));
+    assert.deepEqual(
+      getActionLog({ outcome: 'failure' }).map((entry) => entry.id),
+      [failed.id]
+    );
+  });
+});
@@ -0,0 +1,302 @@
+import { randomUUID } from 'crypto';
+import { app } from 'electron';
+import { existsSync, readFileSync } from 'fs';
+import path from 'path';
+import type { ActionLogAction, ActionLogEntry, ActionLogFilter } from '../common/appTypes';
+import type { KubectlResult } from '../common/kubeTypes';
+import { emitToRenderer } from './events';
+import { REDACTED, redactSecrets } from './redact';
+import { RotatingFile } from './rotatingFile';
+
+const MAX_LOG_FILE_BYTES = 5 * 1024 * 1024;
+const MAX_ROTATED_FILES = 3;
+// Entries kept in memory for queries; older ones are only on disk
+const MAX_MEMORY_ENTRIES = 2000;
+const DEFAULT_QUERY_LIMIT = 200;
+
+// kubectl verbs that change the cluster; `rollout` only for the subcommands below
+const MUTATING_VERBS = new Set([
+  'apply',
+  'create',
+  'replace',
+  'patch',
+  'edit',
+  'delete',
+  'scale',
+  'autoscale',
+  'label',
+  'annotate',
+  'set',
+  'taint',
+  'cordon',
+  'uncordon',
+  'drain',
+  'expose',
+  'run',
+]);
+const MUTATING_ROLLOUT_SUBCOMMANDS = new Set(['restart', 'undo', 'pause', 'resume']);
+// Flags whose value is a separate argument, so it isn't mistaken for the verb or target
+const VALUE_FLAGS = new Set([
+  '-n',
+  '--namespace',
+  '-f',
+  '--filename',
+  '-l',
+  '--selector',
+  '-o',
+  '--output',
+  '-c',
+  '--container',
+  '--context',
+  '--kubeconfig',
+  '--cluster',
+  '--user',
+  '--as',
+  '--as-group',
+]);
+// --from-literal=key=value for `create secret`; the key is fine to keep
+const FROM_LITERAL_PATTERN = /(--from-literal[= ]['"]?[^=\s]+=)\S+/g;
+
+export type ActionInput = Omit<ActionLogEntry, 'id' | 'timestamp' | 'outcome' | 'error'>;
+
+export interface ActionSummary {
+  detail?: string | null;
+  /** Set when the operation returned but part of it failed */
+  error?: string | null;
+}
+
+let logFile: RotatingFile | null = null;
+let entries: ActionLogEntry[] | null = null;
+
+function logFilePath(): string {
+  return path.join(app.getPath('userData'), 'action-log.jsonl');
+}
+
+function parseLines(filePath: string): ActionLogEntry[] {
+  if (!existsSync(filePath)) {
+    return [];
+  }
+  const parsed: ActionLogEntry[] = [];
+  for (const line of readFileSync(filePath, 'utf8').split('\n')) {
+    if (!line.trim()) {
+      continue;
+    }
+    try {
+      parsed.push(JSON.parse(line));
+    } catch {
+      // A line cut short by a crash mid-write
+    }
+  }
+  return parsed;
+}
+
+// Loaded on first use: the current file, topped up from the newest rotated one
+function loadedEntries(): ActionLogEntry[] {
+  if (!entries) {
+    const filePath = logFilePath();
+    try {
+      let loaded = parseLines(filePath);
+      if (loaded.length < MAX_MEMORY_ENTRIES) {
+        loaded = [...parseLines(`${filePath}.1`), ...loaded];
+      }
+      entries = loaded.slice(-MAX_MEMORY_ENTRIES);
+    } catch (error) {
+      console.warn('[ActionLog] Could not read the action log:', error);
+      entries = [];
+    }
+  }
+  return entries;
+}
+
+function openLogFile(): RotatingFile | null {
+  if (!logFile) {
+    try {
+      logFile = new RotatingFile(logFilePath(), MAX_LOG_FILE_BYTES, MAX_ROTATED_FILES, (message, error) =>
+        console.error(`[ActionLog] ${message}:`, error)
+      );
+    } catch (error) {
+      console.error('[ActionLog] Unable to open the action log:', error);
+      return null;
+    }
+  }
+  return logFile.isOpen ? logFile : null;
+}
+
+function redact(text: string | null): string | null {
+  return text === null ? null : redactSecrets(text.replace(FROM_LITERAL_PATTERN, `$1${REDACTED}`));
+}
+
+function messageOf(error: unknown): string {
+  return error instanceof Error ? error.message : String(error);
+}
+
+/**
+ * Record a finished operation: kept in memory for getActionLog, appended to
+ * userData/action-log.jsonl and emitted as `action-log:entry`. Secret-looking
+ * values in the target, detail and error are masked before anything is
+ * stored. Failing to write the file never fails the operation itself.
+ */
+export function recordAction(input: ActionInput, error?: unknown): ActionLogEntry {
+  const entry: ActionLogEntry = {
+    id: randomUUID(),
+    timestamp: new Date().toISOString(),
+    action: input.action,
+    context: input.context || null,
+    namespace: input.namespace || null,
+    target: redact(input.target),
+    outcome: error === undefined ? 'success' : 'failure',
+    detail: redact(input.detail),
+    error: error === undefined ? null : redact(messageOf(error)),
+  };
+
+  const loaded = loadedEntries();
+  loaded.push(entry);
+  if (loaded.length > MAX_MEMORY_ENTRIES) {
+    loaded.splice(0, loaded.length - MAX_MEMORY_ENTRIES);
+  }
+  openLogFile()?.write(`${JSON.stringify(entry)}\n`);
+  emitToRenderer('action-log:entry', entry);
+  return entry;
+}
+
+/**
+ * Run an operation and record its outcome. `summarize` can add a summary of
+ * the result to the entry's detail (e.g. how many objects were deleted) and
+ * mark a partly failed result as a failure. The operation's result or error
+ * is passed through unchanged.
+ */
+export async function trackAction<T>(
+  input: ActionInput,
+  run: () => Promise<T>,
+  summarize?: (result: T) => ActionSummary
+): Promise<T> {
+  let result: T;
+  try {
+    result = await run();
+  } catch (error) {
+    recordAction(input, error);
+    throw error;
+  }
+  const { detail = null, error = null } = summarize?.(result) ?? {};
+  recordAction(
+    { ...input, detail: [input.detail, detail].filter(Boolean).join(': ') || null },
+    error === null ? undefined : new Error(error)
+  );
+  return result;
+}
+
+/**
+ * A kubectl run that exited non-zero is a failed action even though
+ * running it didn't throw
+ */
+export function summarizeKubectlResult(result: KubectlResult): ActionSummary {
+  return { error: result.code === 0 ? null : result.stderr.trim() || `exit code ${result.code}` };
+}
+
+/**
+ * Which label or annotation keys a change sets and removes (null values);
+ * the values are left out
+ */
+export function describeKeyChanges(changes: Record<string, string | null>): string | null {
+  const keys = Object.keys(changes);
+  const set = keys.filter((key) => changes[key] !== null);
+  const removed = keys.filter((key) => changes[key] === null);
+  const parts: string[] = [];
+  if (set.length > 0) {
+    parts.push(`set ${set.join(', ')}`);
+  }
+  if (removed.length > 0) {
+    parts.push(`removed ${removed.join(', ')}`);
+  }
+  return parts.length > 0 ? parts.join('; ') : null;
+}
+
+/**
+ * What a free-form kubectl command does to the cluster, for the action log,
+ * or null when it only reads (get, describe, logs, rollout status...) or is
+ * a dry run. The context is only set when the command names one itself.
+ */
+export function describeKubectlCommand(command: string): ActionInput | null {
+  const tokens = command.trim().split(/\s+/);
+  const args = tokens[0]?.toLowerCase() === 'kubectl' ? tokens.slice(1) : tokens;
+
+  let namespace: string | null = null;
+  let context: string | null = null;
+  const positional: string[] = [];
+  for (let index = 0; index < args.length; index++) {
+    const arg = args[index];
+    if (arg === '-n' || arg === '--namespace') {
+      namespace = args[index + 1] ?? null;
+    } else if (arg.startsWith('--namespace=')) {
+      namespace = arg.slice('--namespace='.length);
+    } else if (arg === '--context') {
+      context = args[index + 1] ?? null;
+    } else if (arg.startsWith('--context=')) {
+      context = arg.slice('--context='.length);
+    } else if (arg === '--dry-run' || (arg.startsWith('--dry-run=') && arg !== '--dry-run=none')) {
+      // Nothing changes; a bare --dry-run is the old spelling of --dry-run=client
+      return null;
+    }
+    if (VALUE_FLAGS.has(arg)) {
+      index++;
+    } else if (!arg.startsWith('-')) {
+      positional.push(arg);
+    }
+  }
+
+  const [verb, ...rest] = positional.map((arg) => arg.toLowerCase());
+  const isRollout = verb === 'rollout' && MUTATING_ROLLOUT_SUBCOMMANDS.has(rest[0]);
+  if (!verb || (!MUTATING_VERBS.has(verb) && !isRollout)) {
+    return null;
+  }
+
+  let action: ActionLogAction = 'kubectl';
+  if (verb === 'scale' || verb === 'delete') {
+    action = verb;
+  } else if (verb === 'apply' || verb === 'create' || verb === 'replace') {
+    action = 'apply';
+  }
+  const targetArgs = positional.slice(isRollout ? 2 : 1, isRollout ? 4 : 3);
+  return {
+    action,
+    context,
+    namespace,
+    target: targetArgs.length > 0 ? targetArgs.join(' ') : null,
+    detail: `kubectl ${args.join(' ')}`,
+  };
+}
+
+/**
+ * Recorded operations, newest first. Only the most recent entries (about
+ * 2000) are searched; older ones remain in the rotated files on disk.
+ */
+export function getActionLog(filter: ActionLogFilter = {}): ActionLogEntry[] {
+  const since = filter.since ? Date.parse(filter.since) : NaN;
+  const search = filter.search?.trim().toLowerCase();
+  const limit = filter.limit && filter.limit > 0 ? Math.floor(filter.limit) : DEFAULT_QUERY_LIMIT;
+
+  const matches: ActionLogEntry[] = [];
+  const loaded = loadedEntries();
+  for (let index = loaded.length - 1; index >= 0 && matches.length < limit; index--) {
+    const entry = loaded[index];
+    if (
+      (filter.action && entry.action !== filter.action) ||
+      (filter.context && entry.context !== filter.context) ||
+      (filter.namespace && entry.namespace !== filter.namespace) ||
+      (filter.outcome && entry.outcome !== filter.outcome) ||
+      (!Number.isNaN(since) && Date.parse(entry.timestamp) < since)
+    ) {
+      continue;
+    }
+    if (search && ![entry.target, entry.detail, entry.error].some((text) => text?.toLowerCase().includes(search))) {
+      continue;
+    }
+    matches.push(entry);
+  }
+  return matches;
+}
+
+export function closeActionLog(): void {
+  logFile?.close();
+  logFile = null;
+}
@@ -0,0 +1,64 @@
+import './testSupport';
+import assert from 'node:assert/strict';
+import { describe, it } from 'node:test';
+import { formatAge, formatDuration } from './age';
+
+const s = 1000;
+const m = 60 * s;
+const h = 60 * m;
+const d = 24 * h;
+
+describe('formatDuration', () => {
+  it('matches kubectl at each boundary', () => {
+    const cases: [number, string][] = [
+      [0, '0s'],
+      [999, '0s'],
+      [119 * s, '119s'],
+      [2 * m, '2m'],
+      [2 * m + 30 * s, '2m30s'],
+      [9 * m + 59 * s, '9m59s'],
+      [10 * m + 30 * s, '10m'],
+      [179 * m, '179m'],
+      [3 * h, '3h'],
+      [7 * h + 59 * m, '7h59m'],
+      [8 * h + 30 * m, '8h'],
+      [47 * h, '47h'],
+      [48 * h, '2d'],
+      [2 * d + 5 * h, '2d5h'],
+      [7 * d + 23 * h, '7d23h'],
+      [8 * d + 5 * h, '8d'],
+      [729 * d, '729d'],
+      [730 * d, '2y'],
+      [2 * 365 * d + 10 * d, '2y10d'],
+      [8 * 365 * d - h, '7y364d'],
+      [8 * 365 * d, '8y'],
+    ];
+    for (const [ms, expected] of cases) {
+      assert.equal(formatDuration(ms), expected, `${ms}ms`);
+    }
+  });
+
+  it('shows a negative duration as 0s', () => {
+    assert.equal(formatDuration(-5 * s), '0s');
+  });
+});
+
+describe('formatAge', () => {
+  const now = Date.parse('2026-03-10T12:00:00Z');
+
+  it('formats the time since a timestamp', () => {
+    assert.equal(formatAge('2026-03-07T08:00:00Z', now), '3d4h');
+    assert.equal(formatAge('2026-03-10T11:45:00Z', now), '15m');
+  });
+
+  it('treats a slightly future timestamp as clock skew', () => {
+    assert.equal(formatAge('2026-03-10T12:02:00Z', now), '0s');
+    assert.equal(formatAge('2026-03-10T12:30:00Z', now), '<invalid>');
+  });
+
+  it('reports missing and unparseable timestamps as unknown', () => {
+    assert.equal(formatAge(undefined, now), '<unknown>');
+    assert.equal(formatAge(null, now), '<unknown>');
+    assert.equal(formatAge('yesterday-ish', now), '<unknown>');
+  });
+});
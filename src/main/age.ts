@@ -0,0 +1,70 @@
+// Future timestamps up to this far ahead are clock skew between us and the API server
+const CLOCK_SKEW_TOLERANCE_MS = 5 * 60 * 1000;
+
+const MINUTE = 60;
+const HOUR = 60 * MINUTE;
+const DAY = 24 * HOUR;
+const YEAR = 365 * DAY;
+
+function pair(first: number, firstUnit: string, second: number, secondUnit: string): string {
+  return second === 0 ? `${first}${firstUnit}` : `${first}${firstUnit}${second}${secondUnit}`;
+}
+
+/**
+ * A duration in kubectl's compact form (duration.HumanDuration): two
+ * units while the larger one is small ("5m30s", "3h5m", "2d4h", "2y10d"),
+ * one after that ("45m", "20h", "300d", "9y").
+ */
+export function formatDuration(ms: number): string {
+  const seconds = Math.floor(ms / 1000);
+  if (seconds < 0) {
+    return '0s';
+  }
+  if (seconds < 2 * MINUTE) {
+    return `${seconds}s`;
+  }
+
+  const minutes = Math.floor(seconds / MINUTE);
+  if (minutes < 10) {
+    return pair(minutes, 'm', seconds % MINUTE, 's');
+  }
+  if (minutes < 3 * 60) {
+    return `${minutes}m`;
+  }
+
+  const hours = Math.floor(seconds / HOUR);
+  if (hours < 8) {
+    return pair(hours, 'h', minutes % 60, 'm');
+  }
+  if (hours < 48) {
+    return `${hours}h`;
+  }
+  if (hours < 8 * 24) {
+    return pair(Math.floor(seconds / DAY), 'd', hours % 24, 'h');
+  }
+  if (hours < 2 * 365 * 24) {
+    return `${Math.floor(seconds / DAY)}d`;
+  }
+  if (hours < 8 * 365 * 24) {
+    return pair(Math.floor(seconds / YEAR), 'y', Math.floor(seconds / DAY) % 365, 'd');
+  }
+  return `${Math.floor(seconds / YEAR)}y`;
+}
+
+/**
+ * kubectl's AGE column for a timestamp such as creationTimestamp. Missing
+ * or unparseable times are "<unknown>"; times slightly in the future (a
+ * skewed clock) are "0s", further ahead "<invalid>", as kubectl shows.
+ */
+export function formatAge(timestamp: string | null | undefined, now = Date.now()): string {
+  const time = timestamp ? Date.parse(timestamp) : NaN;
+  if (Number.isNaN(time)) {
+    return '<unknown>';
+  }
+
+  const elapsed = now - time;
+  if (elapsed < -CLOCK_SKEW_TOLERANCE_MS) {
+    return '<invalid>';
+  }
+  return formatDuration(Math.max(elapsed, 0));
+}
@@ -0,0 +1,17 @@
+import { userDataDir } from './testSupport';
+import assert from 'node:assert/strict';
+import { describe, it } from 'node:test';
+import { existsSync, readFileSync, statSync, writeFileSync } from 'fs';
+import path from 'path';
+import { writeFileAtomically } from './appConfig';
+
+describe('writeFileAtomically', () => {
+  it('replaces the contents with the given permissions and leaves no temp file', () => {
+    const filePath = path.join(userDataDir, 'atomic-kubeconfig');
+    writeFileSync(filePath, 'old', { mode: 0o600 });
+    writeFileAtomically(filePath, 'new', statSync(filePath).mode);
+    assert.equal(readFileSync(filePath, 'utf8'), 'new');
+    assert.equal(statSync(filePath).mode & 0o777, 0o600);
+    assert.equal(existsSync(`${filePath}.tmp`), false);
+  });
+});
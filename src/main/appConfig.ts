@@ -0,0 +1,392 @@
+import { app } from 'electron';
+import { mkdirSync, readFileSync, renameSync, writeFileSync } from 'fs';
+import path from 'path';
+import type { CommandPolicy, TerminalCwdPolicy } from '../common/appTypes';
+import type {
+  CloudAuthProfile,
+  ContextTagEntry,
+  KubectlPoolLimits,
+  LogLevel,
+  LogLevelPattern,
+  LogLevelSettings,
+  RetryPolicy,
+} from '../common/kubeTypes';
+import type { TerminalCapabilities, TerminalPasteLimits, TerminalProfile } from '../common/terminalTypes';
+
+/**
+ * Persistent app settings stored as JSON under the Electron userData dir
+ */
+export interface AppConfig {
+  /** Context name -> kubectl binary to use for that context */
+  kubectlPaths: Record<string, string>;
+  /** Bound on concurrent one-shot kubectl processes */
+  kubectlPool: KubectlPoolLimits;
+  /** Retries for transient PTY spawn failures (forkpty/openpty) */
+  terminalSpawn: {
+    maxAttempts: number;
+    timeoutMs: number;
+  };
+  terminalProfiles: TerminalProfile[];
+  /** Pastes above either limit need confirmation; 0 turns a limit off */
+  terminalPaste: TerminalPasteLimits;
+  /** Answers to device-attribute and color queries from terminal programs */
+  terminalCapabilities: TerminalCapabilities;
+  /** Off by default; only editable in the config file, not from the UI */
+  commandPolicy: CommandPolicy;
+  /** Off by default; only editable in the config file, not from the UI */
+  terminalCwdPolicy: TerminalCwdPolicy;
+  /** Backoff for reopening dropped watches, log follows and port-forwards */
+  reconnect: RetryPolicy;
+  /** Env and PATH for cloud exec plugins; profiles replace built-ins by provider */
+  cloudAuth: {
+    enabled: boolean;
+    profiles: CloudAuthProfile[];
+  };
+  /** Record commands typed into shells, per profile; opt-in */
+  inputHistory: {
+    enabled: boolean;
+  };
+  /** Tags like "production" per context; untagged contexts get guessed ones */
+  contextTags: {
+    autoDetect: boolean;
+    entries: ContextTagEntry[];
+  };
+  /** How log stream lines are assigned a level for coloring */
+  logLevels: LogLevelSettings;
+}
+
+const DEFAULT_CONFIG: AppConfig = {
+  kubectlPaths: {},
+  kubectlPool: {
+    maxConcurrent: 16,
+    queueTimeoutMs: 30 * 1000,
+    maxStreaming: 64,
+  },
+  terminalSpawn: {
+    maxAttempts: 3,
+    timeoutMs: 5000,
+  },
+  terminalProfiles: [],
+  terminalPaste: {
+    maxBytes: 64 * 1024,
+    maxLines: 200,
+  },
+  terminalCapabilities: {
+    answerQueries: true,
+    deviceAttributes: '62;22',
+    // Matches the renderer's xterm theme
+    foreground: '#d4d4d4',
+    background: '#1e1e1e',
+  },
+  commandPolicy: {
+    enabled: false,
+    allow: [],
+    deny: [],
+  },
+  terminalCwdPolicy: {
+    enabled: false,
+    allowedRoots: [],
+  },
+  reconnect: {
+    maxAttempts: 5,
+    baseDelayMs: 1000,
+    maxDelayMs: 30000,
+    jitter: true,
+  },
+  cloudAuth: {
+    enabled: true,
+    profiles: [],
+  },
+  inputHistory: {
+    enabled: false,
+  },
+  contextTags: {
+    autoDetect: true,
+    entries: [],
+  },
+  logLevels: {
+    patterns: [],
+    builtins: true,
+  },
+};
+
+let cachedConfig: AppConfig | null = null;
+
+function getConfigPath(): string {
+  return path.join(app.getPath('userData'), 'config.json');
+}
+
+function isStringRecord(value: any): value is Record<string, string> {
+  return (
+    !!value &&
+    typeof value === 'object' &&
+    !Array.isArray(value) &&
+    Object.values(value).every((entry) => typeof entry === 'string')
+  );
+}
+
+const HEX_COLOR_PATTERN = /^#[0-9a-f]{6}$/i;
+const DEVICE_ATTRIBUTES_PATTERN = /^\d+(;\d+)*$/;
+
+/**
+ * Valid fields of `raw` laid over `base`; anything malformed keeps the base value
+ */
+export function normalizeCapabilities(raw: any, base: TerminalCapabilities): TerminalCapabilities {
+  const capabilities = { ...base };
+  if (!raw || typeof raw !== 'object') {
+    return capabilities;
+  }
+
+  if (typeof raw.answerQueries === 'boolean') {
+    capabilities.answerQueries = raw.answerQueries;
+  }
+  if (typeof raw.deviceAttributes === 'string' && DEVICE_ATTRIBUTES_PATTERN.test(raw.deviceAttributes)) {
+    capabilities.deviceAttributes = raw.deviceAttributes;
+  }
+  for (const color of ['foreground', 'background'] as const) {
+    if (typeof raw[color] === 'string' && HEX_COLOR_PATTERN.test(raw[color])) {
+      capabilities[color] = raw[color];
+    }
+  }
+  return capabilities;
+}
+
+export function normalizeProfile(raw: any): TerminalProfile | null {
+  if (!raw || typeof raw !== 'object' || typeof raw.name !== 'string' || !raw.name) {
+    return null;
+  }
+
+  const profile: TerminalProfile = { name: raw.name };
+  if (typeof raw.shell === 'string' && raw.shell) {
+    profile.shell = raw.shell;
+  }
+  if (Array.isArray(raw.args) && raw.args.every((arg: any) => typeof arg === 'string')) {
+    profile.args = raw.args;
+  }
+  if (isStringRecord(raw.env)) {
+    profile.env = raw.env;
+  }
+  if (typeof raw.cwd === 'string' && raw.cwd) {
+    profile.cwd = raw.cwd;
+  }
+  if (typeof raw.loginShell === 'boolean') {
+    profile.loginShell = raw.loginShell;
+  }
+  if (typeof raw.initialCommand === 'string' && raw.initialCommand) {
+    profile.initialCommand = raw.initialCommand;
+  }
+  return profile;
+}
+
+function normalizeCloudAuthProfile(raw: any): CloudAuthProfile | null {
+  if (!raw || typeof raw !== 'object' || typeof raw.provider !== 'string' || !raw.provider) {
+    return null;
+  }
+
+  const isStringList = (value: any) => Array.isArray(value) && value.every((entry) => typeof entry === 'string');
+  return {
+    provider: raw.provider,
+    commands: isStringList(raw.commands) ? raw.commands : [],
+    env: isStringRecord(raw.env) ? raw.env : {},
+    path: isStringList(raw.path) ? raw.path : [],
+  };
+}
+
+function normalizeContextTagEntry(raw: any): ContextTagEntry | null {
+  if (!raw || typeof raw !== 'object' || typeof raw.context !== 'string' || !Array.isArray(raw.tags)) {
+    return null;
+  }
+  return {
+    context: raw.context,
+    server: typeof raw.server === 'string' ? raw.server : null,
+    tags: raw.tags.filter((tag: any) => typeof tag === 'string' && tag),
+  };
+}
+
+const LOG_LEVELS: LogLevel[] = ['trace', 'debug', 'info', 'warn', 'error', 'fatal'];
+
+export function normalizeLogLevelPattern(raw: any): LogLevelPattern | null {
+  if (!raw || typeof raw !== 'object' || !LOG_LEVELS.includes(raw.level)) {
+    return null;
+  }
+  if (typeof raw.pattern !== 'string' || !raw.pattern) {
+    return null;
+  }
+  try {
+    new RegExp(raw.pattern, 'i');
+  } catch {
+    return null;
+  }
+  return { level: raw.level, pattern: raw.pattern };
+}
+
+function normalize(raw: any): AppConfig {
+  const config: AppConfig = JSON.parse(JSON.stringify(DEFAULT_CONFIG));
+  if (!raw || typeof raw !== 'object') {
+    return config;
+  }
+
+  if (raw.kubectlPaths && typeof raw.kubectlPaths === 'object') {
+    for (const [context, binary] of Object.entries(raw.kubectlPaths)) {
+      if (typeof binary === 'string' && binary) {
+        config.kubectlPaths[context] = binary;
+      }
+    }
+  }
+
+  const spawn = raw.terminalSpawn;
+  if (spawn && typeof spawn === 'object') {
+    if (Number.isInteger(spawn.maxAttempts) && spawn.maxAttempts >= 1) {
+      config.terminalSpawn.maxAttempts = spawn.maxAttempts;
+    }
+    if (typeof spawn.timeoutMs === 'number' && spawn.timeoutMs > 0) {
+      config.terminalSpawn.timeoutMs = spawn.timeoutMs;
+    }
+  }
+
+  const pool = raw.kubectlPool;
+  if (pool && typeof pool === 'object') {
+    if (Number.isInteger(pool.maxConcurrent) && pool.maxConcurrent >= 0) {
+      config.kubectlPool.maxConcurrent = pool.maxConcurrent;
+    }
+    if (Number.isInteger(pool.queueTimeoutMs) && pool.queueTimeoutMs > 0) {
+      config.kubectlPool.queueTimeoutMs = pool.queueTimeoutMs;
+    }
+    if (Number.isInteger(pool.maxStreaming) && pool.maxStreaming >= 0) {
+      config.kubectlPool.maxStreaming = pool.maxStreaming;
+    }
+  }
+
+  const paste = raw.terminalPaste;
+  if (paste && typeof paste === 'object') {
+    for (const field of ['maxBytes', 'maxLines'] as const) {
+      if (Number.isInteger(paste[field]) && paste[field] >= 0) {
+        config.terminalPaste[field] = paste[field];
+      }
+    }
+  }
+
+  config.terminalCapabilities = normalizeCapabilities(raw.terminalCapabilities, config.terminalCapabilities);
+
+  const reconnect = raw.reconnect;
+  if (reconnect && typeof reconnect === 'object') {
+    if (Number.isInteger(reconnect.maxAttempts) && reconnect.maxAttempts >= 0) {
+      config.reconnect.maxAttempts = reconnect.maxAttempts;
+    }
+    for (const field of ['baseDelayMs', 'maxDelayMs'] as const) {
+      if (typeof reconnect[field] === 'number' && reconnect[field] > 0) {
+        config.reconnect[field] = reconnect[field];
+      }
+    }
+    if (typeof reconnect.jitter === 'boolean') {
+      config.reconnect.jitter = reconnect.jitter;
+    }
+  }
+
+  const cloudAuth = raw.cloudAuth;
+  if (cloudAuth && typeof cloudAuth === 'object') {
+    if (typeof cloudAuth.enabled === 'boolean') {
+      config.cloudAuth.enabled = cloudAuth.enabled;
+    }
+    if (Array.isArray(cloudAuth.profiles)) {
+      config.cloudAuth.profiles = cloudAuth.profiles
+        .map(normalizeCloudAuthProfile)
+        .filter((profile: CloudAuthProfile | null): profile is CloudAuthProfile => profile !== null);
+    }
+  }
+
+  config.inputHistory.enabled = raw.inputHistory?.enabled === true;
+
+  const contextTags = raw.contextTags;
+  if (contextTags && typeof contextTags === 'object') {
+    if (typeof contextTags.autoDetect === 'boolean') {
+      config.contextTags.autoDetect = contextTags.autoDetect;
+    }
+    if (Array.isArray(contextTags.entries)) {
+      config.contextTags.entries = contextTags.entries
+        .map(normalizeContextTagEntry)
+        .filter((entry: ContextTagEntry | null): entry is ContextTagEntry => entry !== null);
+    }
+  }
+
+  const logLevels = raw.logLevels;
+  if (logLevels && typeof logLevels === 'object') {
+    if (typeof logLevels.builtins === 'boolean') {
+      config.logLevels.builtins = logLevels.builtins;
+    }
+    if (Array.isArray(logLevels.patterns)) {
+      config.logLevels.patterns = logLevels.patterns
+        .map(normalizeLogLevelPattern)
+        .filter((pattern: LogLevelPattern | null): pattern is LogLevelPattern => pattern !== null);
+    }
+  }
+
+  const policy = raw.commandPolicy;
+  if (policy && typeof policy === 'object') {
+    config.commandPolicy.enabled = policy.enabled === true;
+    for (const list of ['allow', 'deny'] as const) {
+      if (Array.isArray(policy[list])) {
+        config.commandPolicy[list] = policy[list].filter((entry: any) => typeof entry === 'string' && entry.trim());
+      }
+    }
+  }
+
+  const cwdPolicy = raw.terminalCwdPolicy;
+  if (cwdPolicy && typeof cwdPolicy === 'object') {
+    config.terminalCwdPolicy.enabled = cwdPolicy.enabled === true;
+    if (Array.isArray(cwdPolicy.allowedRoots)) {
+      config.terminalCwdPolicy.allowedRoots = cwdPolicy.allowedRoots.filter(
+        (root: any) => typeof root === 'string' && root.trim()
+      );
+    }
+  }
+
+  if (Array.isArray(raw.terminalProfiles)) {
+    for (const entry of raw.terminalProfiles) {
+      const profile = normalizeProfile(entry);
+      if (profile && !config.terminalProfiles.some((existing) => existing.name === profile.name)) {
+        config.terminalProfiles.push(profile);
+      }
+    }
+  }
+
+  return config;
+}
+
+export function getAppConfig(): AppConfig {
+  if (cachedConfig) {
+    return cachedConfig;
+  }
+
+  try {
+    cachedConfig = normalize(JSON.parse(readFileSync(getConfigPath(), 'utf8')));
+  } catch {
+    // Missing or corrupt config: start from defaults
+    cachedConfig = normalize(null);
+  }
+  return cachedConfig;
+}
+
+/**
+ * Replace a file's contents atomically (write a temp file, then rename it
+ * over the original). `mode` sets the permissions of the new file.
+ */
+export function writeFileAtomically(filePath: string, contents: string, mode?: number): void {
+  const tempPath = `${filePath}.tmp`;
+  writeFileSync(tempPath, contents, { encoding: 'utf8', mode });
+  renameSync(tempPath, filePath);
+}
+
+/**
+ * Apply a change to the config and persist it atomically (write + rename)
+ */
+export function updateAppConfig(mutate: (config: AppConfig) => void): AppConfig {
+  const config = getAppConfig();
+  mutate(config);
+
+  const configPath = getConfigPath();
+  mkdirSync(path.dirname(configPath), { recursive: true });
+  writeFileAtomically(configPath, JSON.stringify(config, null, 2));
+
+  return config;
+}
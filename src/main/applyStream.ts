@@ -0,0 +1,258 @@
+import { randomUUID } from 'crypto';
+import { readdir, stat } from 'fs/promises';
+import path from 'path';
+import YAML from 'yaml';
+import type { ApplyDoneEvent, ApplyObjectEvent, ApplyObjectOutcome, KustomizeApplyResult } from '../common/kubeTypes';
+import { recordAction } from './actionLog';
+import { runKubectl, kubectlFailure, KubeError } from './kube';
+import { emitToRenderer } from './events';
+import { operationRegistry } from './operations';
+
+export interface StreamingApplySpec {
+  content: string;
+  context: string;
+  namespace?: string;
+  /** Keep applying after an object fails (default: stop) */
+  continueOnError?: boolean;
+  /** Objects applied at once; 1 keeps manifest order (namespaces before their contents) */
+  parallelism?: number;
+  /** Server-side dry run: validated and admitted, but nothing is persisted */
+  dryRun?: boolean;
+  view?: string;
+}
+
+export interface KustomizeApplySpec extends Omit<StreamingApplySpec, 'content'> {
+  directory: string;
+  /** Return the rendered manifest without applying it */
+  buildOnly?: boolean;
+}
+
+interface ManifestObject {
+  kind: string;
+  name: string;
+  namespace?: string;
+  yaml: string;
+}
+
+const MAX_PARALLELISM = 4;
+// The names kustomize looks for, in its order
+const KUSTOMIZATION_FILES = ['kustomization.yaml', 'kustomization.yml', 'Kustomization'];
+// Remote bases are fetched during the build
+const KUSTOMIZE_BUILD_TIMEOUT_MS = 2 * 60 * 1000;
+
+// "deployment.apps/web configured", optionally followed by "(dry run)" etc.
+export const APPLY_OUTCOME_PATTERN = /\b(created|configured|unchanged)\b/;
+
+/**
+ * Split a multi-document manifest, failing on the first document that
+ * doesn't parse so nothing is applied from a broken file
+ */
+function splitManifest(content: string): ManifestObject[] {
+  const objects: ManifestObject[] = [];
+
+  YAML.parseAllDocuments(content).forEach((document, index) => {
+    if (document.errors.length > 0) {
+      throw new Error(`Document ${index + 1} is not valid YAML: ${document.errors[0].message}`);
+    }
+
+    const object = document.toJS();
+    if (object === null || object === undefined) {
+      // Empty document, e.g. a trailing ---
+      return;
+    }
+    if (typeof object !== 'object' || !object.kind || !object.metadata?.name) {
+      throw new Error(`Document ${index + 1} is missing kind or metadata.name`);
+    }
+
+    objects.push({
+      kind: String(object.kind),
+      name: String(object.metadata.name),
+      namespace: object.metadata.namespace,
+      yaml: YAML.stringify(object),
+    });
+  });
+
+  return objects;
+}
+
+/**
+ * Apply a manifest one object at a time, emitting `apply:object` as each
+ * finishes and `apply:done` with totals at the end. All documents are
+ * parsed before anything is applied. Returns immediately with the apply id.
+ * Unless it is a dry run, the outcome goes to the action log, with `source`
+ * (a file or directory) as the target when given.
+ */
+export function applyManifestStreaming(
+  spec: StreamingApplySpec,
+  source?: string
+): { applyId: string; total: number } {
+  const objects = splitManifest(spec.content);
+  if (objects.length === 0) {
+    throw new Error('Manifest contains no objects');
+  }
+
+  const applyId = randomUUID();
+  const parallelism = Math.max(1, Math.min(MAX_PARALLELISM, Math.floor(spec.parallelism ?? 1)));
+  const counts: Record<ApplyObjectOutcome, number> = { created: 0, configured: 0, unchanged: 0, error: 0 };
+  let cancelled = false;
+  let stopped = false;
+  let firstError: string | null = null;
+  let next = 0;
+
+  operationRegistry.register(
+    applyId,
+    'kubectl',
+    { context: spec.context, namespace: spec.namespace, view: spec.view },
+    () => {
+      cancelled = true;
+    }
+  );
+
+  const applyOne = async (index: number) => {
+    const object = objects[index];
+    let outcome: ApplyObjectOutcome = 'error';
+    let message: string;
+
+    try {
+      const args = spec.dryRun ? ['apply', '-f', '-', '--dry-run=server'] : ['apply', '-f', '-'];
+      const result = await runKubectl(spec.context, args, spec.namespace, {
+        stdin: object.yaml,
+        view: spec.view,
+      });
+      if (result.code === 0) {
+        message = result.stdout.trim();
+        const match = message.match(APPLY_OUTCOME_PATTERN);
+        outcome = match ? (match[1] as ApplyObjectOutcome) : 'configured';
+      } else {
+        message = kubectlFailure(result, `Failed to apply ${object.kind}/${object.name}`).message;
+      }
+    } catch (error) {
+      message = error instanceof Error ? error.message : String(error);
+    }
+
+    counts[outcome] += 1;
+    if (outcome === 'error') {
+      firstError = firstError ?? `${object.kind}/${object.name}: ${message}`;
+      if (!spec.continueOnError) {
+        stopped = true;
+      }
+    }
+
+    const event: ApplyObjectEvent = {
+      applyId,
+      index,
+      total: objects.length,
+      kind: object.kind,
+      name: object.name,
+      namespace: object.namespace ?? spec.namespace,
+      outcome,
+      message,
+    };
+    emitToRenderer('apply:object', event);
+  };
+
+  const worker = async () => {
+    while (next < objects.length && !stopped && !cancelled) {
+      await applyOne(next++);
+    }
+  };
+
+  Promise.all(Array.from({ length: Math.min(parallelism, objects.length) }, () => worker())).finally(() => {
+    operationRegistry.unregister(applyId);
+
+    const attempted = counts.created + counts.configured + counts.unchanged + counts.error;
+    const done: ApplyDoneEvent = {
+      applyId,
+      total: objects.length,
+      created: counts.created,
+      configured: counts.configured,
+      unchanged: counts.unchanged,
+      failed: counts.error,
+      skipped: objects.length - attempted,
+      cancelled,
+    };
+    console.log(
+      `[Apply ${applyId}] ${attempted}/${objects.length} applied (${counts.error} failed, ${done.skipped} skipped)`
+    );
+    emitToRenderer('apply:done', done);
+
+    if (!spec.dryRun) {
+      const failure = cancelled ? 'Apply was cancelled' : firstError;
+      recordAction(
+        {
+          action: 'apply',
+          context: spec.context,
+          namespace: spec.namespace ?? null,
+          target: source ?? `${objects.length} object(s)`,
+          detail:
+            `${done.created} created, ${done.configured} configured, ${done.unchanged} unchanged, ` +
+            `${done.failed} failed, ${done.skipped} skipped`,
+        },
+        failure === null ? undefined : new Error(failure)
+      );
+    }
+  });
+
+  return { applyId, total: objects.length };
+}
+
+async function kustomizationDirectory(directory: string): Promise<string> {
+  const resolved = path.resolve(directory);
+  let info;
+  try {
+    info = await stat(resolved);
+  } catch {
+    throw new Error(`Directory not found: ${resolved}`);
+  }
+  if (!info.isDirectory()) {
+    throw new Error(`${resolved} is not a directory`);
+  }
+
+  const entries = await readdir(resolved);
+  if (!KUSTOMIZATION_FILES.some((name) => entries.includes(name))) {
+    throw new Error(`${resolved} has no ${KUSTOMIZATION_FILES.join(', ')} file`);
+  }
+  return resolved;
+}
+
+/**
+ * Render a kustomize overlay with `kubectl kustomize` and apply the result
+ * through applyManifestStreaming, so each object gets an `apply:object`
+ * event and the totals come in `apply:done`. A build failure (bad
+ * kustomization, missing base) throws KustomizeBuildFailed before anything
+ * is applied; apply failures arrive as events like for any manifest. With
+ * `buildOnly` the rendered manifest is returned for a preview instead.
+ */
+export async function applyKustomize(spec: KustomizeApplySpec): Promise<KustomizeApplyResult> {
+  const directory = await kustomizationDirectory(spec.directory);
+  const result = await runKubectl(spec.context, ['kustomize', directory], undefined, {
+    view: spec.view,
+    timeoutMs: KUSTOMIZE_BUILD_TIMEOUT_MS,
+  });
+  if (result.code !== 0) {
+    const message = (result.stderr || result.stdout || 'kustomize build failed').trim();
+    throw new KubeError('KustomizeBuildFailed', `Failed to build ${directory}: ${message}`);
+  }
+
+  let objects: ManifestObject[];
+  try {
+    objects = splitManifest(result.stdout);
+  } catch (error) {
+    throw new KubeError(
+      'KustomizeBuildFailed',
+      `${directory} built an invalid manifest: ${error instanceof Error ? error.message : String(error)}`
+    );
+  }
+  const summary = objects.map((object) => ({ kind: object.kind, name: object.name, namespace: object.namespace }));
+
+  if (spec.buildOnly) {
+    return { directory, applyId: null, total: objects.length, objects: summary, manifest: result.stdout };
+  }
+  if (objects.length === 0) {
+    throw new KubeError('KustomizeBuildFailed', `${directory} built no objects`);
+  }
+
+  const { applyId, total } = applyManifestStreaming({ ...spec, content: result.stdout }, directory);
+  console.log(`[Apply ${applyId}] Applying ${total} objects from ${directory}${spec.dryRun ? ' (dry run)' : ''}`);
+  return { directory, applyId, total, objects: summary, manifest: null };
+}
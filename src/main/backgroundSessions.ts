@@ -0,0 +1,221 @@
+import type { BackgroundSession, BackgroundSessionKind, SessionReattachResult } from '../common/kubeTypes';
+import { emitToRenderer, tapRendererEvents } from './events';
+import { operationRegistry } from './operations';
+import { portForwardManager } from './portForwards';
+
+interface HeldEvent {
+  channel: string;
+  args: unknown[];
+}
+
+interface Session {
+  id: string;
+  kind: BackgroundSessionKind;
+  label: string;
+  context: string | null;
+  namespace: string | null;
+  promotedAt: number;
+  attachedView: string | null;
+  held: HeldEvent[];
+  dropped: number;
+}
+
+// Per session; the oldest events are dropped first
+const MAX_HELD_EVENTS = 1000;
+// Fields the managers' event payloads carry their stream's id in
+const ID_FIELDS = ['streamId', 'watchId', 'id'] as const;
+
+/**
+ * The stream an event belongs to: the first argument when it's an id
+ * (`logs:error`, `watch:error`, ...), otherwise the payload's id field
+ */
+function sessionIdOf(args: unknown[]): string | null {
+  const first = args[0];
+  if (typeof first === 'string') {
+    return first;
+  }
+  if (first && typeof first === 'object') {
+    for (const field of ID_FIELDS) {
+      const value = (first as Record<string, unknown>)[field];
+      if (typeof value === 'string') {
+        return value;
+      }
+    }
+  }
+  return null;
+}
+
+/**
+ * Watches, log and metrics streams and port-forwards that were promoted to
+ * the background, so closing the view that started them doesn't stop
+ * them. The owning managers keep the processes; a session only holds the
+ * id, keeps the operation out of view-scoped cancels, and holds its events
+ * while no view is attached so they can be replayed on reattach.
+ */
+export class BackgroundSessionManager {
+  private sessions: Map<string, Session> = new Map();
+  private removeTap: (() => void) | null = null;
+
+  /**
+   * Promote a running operation (by its watch, stream or forward id),
+   * attached to `view` until that view closes
+   */
+  promote(id: string, label?: string, view?: string): BackgroundSession {
+    const existing = this.sessions.get(id);
+    if (existing) {
+      existing.label = label || existing.label;
+      return this.describe(existing);
+    }
+
+    const operation = operationRegistry.describe(id);
+    const forward = operation ? null : portForwardManager.listForwards().find((entry) => entry.id === id);
+    if (!operation && !forward) {
+      throw new Error(`No running watch, stream or forward with id ${id}`);
+    }
+
+    const kind: BackgroundSessionKind = operation ? operation.kind : 'forward';
+    const session: Session = {
+      id,
+      kind,
+      label: label || (forward ? `${forward.kind} ${forward.target}` : `${kind} ${id.slice(0, 8)}`),
+      context: operation?.scope.context ?? forward?.context ?? null,
+      namespace: operation?.scope.namespace ?? forward?.namespace ?? null,
+      promotedAt: Date.now(),
+      attachedView: view ?? operation?.scope.view ?? null,
+      held: [],
+      dropped: 0,
+    };
+
+    if (operation) {
+      operationRegistry.setBackground(id, true);
+    }
+    this.sessions.set(id, session);
+    if (!this.removeTap) {
+      this.removeTap = tapRendererEvents((channel, args) => this.capture(channel, args));
+    }
+    console.log(`[Background ${id}] Promoted ${session.kind} "${session.label}"`);
+    return this.describe(session);
+  }
+
+  listSessions(): BackgroundSession[] {
+    // Ended sessions are kept only while they hold events nobody has seen
+    for (const session of Array.from(this.sessions.values())) {
+      if (session.held.length === 0 && !this.isActive(session)) {
+        this.remove(session.id);
+      }
+    }
+    return Array.from(this.sessions.values()).map((session) => this.describe(session));
+  }
+
+  /**
+   * Attach a session to a view and replay the events held since it was
+   * detached. A session whose operation has ended is removed once its
+   * last events are delivered.
+   */
+  reattach(id: string, view?: string): SessionReattachResult {
+    const session = this.sessions.get(id);
+    if (!session) {
+      throw new Error(`Background session ${id} not found`);
+    }
+
+    session.attachedView = view ?? null;
+    const held = session.held;
+    session.held = [];
+    for (const event of held) {
+      emitToRenderer(event.channel, ...event.args);
+    }
+
+    const result: SessionReattachResult = { session: this.describe(session), replayed: held.length };
+    session.dropped = 0;
+    if (!result.session.active) {
+      this.remove(id);
+    }
+    return result;
+  }
+
+  /**
+   * Detach the sessions shown in a view that is closing; they keep running
+   * and hold their events until reattached
+   */
+  detachView(view: string): number {
+    let detached = 0;
+    for (const session of this.sessions.values()) {
+      if (session.attachedView === view) {
+        session.attachedView = null;
+        detached += 1;
+      }
+    }
+    return detached;
+  }
+
+  /**
+   * Stop a background session's operation and forget the session
+   */
+  closeSession(id: string): boolean {
+    const session = this.sessions.get(id);
+    if (!session) {
+      return false;
+    }
+
+    if (session.kind === 'forward') {
+      portForwardManager.stopForward(id);
+    } else {
+      operationRegistry.cancelOne(id);
+    }
+    this.remove(id);
+    console.log(`[Background ${id}] Closed`);
+    return true;
+  }
+
+  closeAllSessions(): void {
+    for (const id of Array.from(this.sessions.keys())) {
+      this.remove(id);
+    }
+  }
+
+  private capture(channel: string, args: unknown[]): void {
+    const id = sessionIdOf(args);
+    const session = id ? this.sessions.get(id) : undefined;
+    if (!session || session.attachedView !== null) {
+      return;
+    }
+
+    session.held.push({ channel, args });
+    if (session.held.length > MAX_HELD_EVENTS) {
+      session.held.shift();
+      session.dropped += 1;
+    }
+  }
+
+  private isActive(session: Session): boolean {
+    return session.kind === 'forward'
+      ? portForwardManager.listForwards().some((entry) => entry.id === session.id)
+      : operationRegistry.has(session.id);
+  }
+
+  private remove(id: string): void {
+    this.sessions.delete(id);
+    if (this.sessions.size === 0 && this.removeTap) {
+      this.removeTap();
+      this.removeTap = null;
+    }
+  }
+
+  private describe(session: Session): BackgroundSession {
+    return {
+      id: session.id,
+      kind: session.kind,
+      label: session.label,
+      context: session.context,
+      namespace: session.namespace,
+      promotedAt: session.promotedAt,
+      attachedView: session.attachedView,
+      active: this.isActive(session),
+      bufferedEvents: session.held.length,
+      droppedEvents: session.dropped,
+    };
+  }
+}
+
+// Singleton instance
+export const backgroundSessionManager = new BackgroundSessionManager();
@@ -0,0 +1,47 @@
+import './testSupport';
+import assert from 'node:assert/strict';
+import { beforeEach, describe, it } from 'node:test';
+import { BroadcastGroupManager } from './broadcast';
+import { terminalManager } from './terminal';
+
+describe('broadcast groups', () => {
+  let manager: BroadcastGroupManager;
+  let live: Set<string>;
+  let written: [string, string][];
+
+  beforeEach(() => {
+    manager = new BroadcastGroupManager();
+    live = new Set(['a', 'b', 'c']);
+    written = [];
+    (terminalManager as any).hasTerminal = (id: string) => live.has(id);
+    (terminalManager as any).writeToTerminal = (id: string, data: string) => {
+      if (id === 'c') {
+        throw new Error('Input blocked by command policy');
+      }
+      written.push([id, data]);
+    };
+  });
+
+  it('needs at least two distinct, running terminals', () => {
+    assert.throws(() => manager.createBroadcastGroup(['a', 'a']), /at least two terminals/);
+    assert.throws(() => manager.createBroadcastGroup(['a', 'zz']), /not found: zz/);
+    assert.deepEqual(manager.listBroadcastGroups(), []);
+  });
+
+  it('writes the same input to every member and reports each one', () => {
+    const { groupId } = manager.createBroadcastGroup(['a', 'b', 'c']);
+    live.delete('b');
+    assert.deepEqual(manager.broadcastWrite(groupId, 'uptime\r'), [
+      { terminalId: 'a', ok: true },
+      { terminalId: 'b', ok: false, error: 'Terminal has exited' },
+      { terminalId: 'c', ok: false, error: 'Input blocked by command policy' },
+    ]);
+    assert.deepEqual(written, [['a', 'uptime\r']]);
+  });
+
+  it('forgets a deleted group', () => {
+    const { groupId } = manager.createBroadcastGroup(['a', 'b']);
+    assert.equal(manager.deleteBroadcastGroup(groupId), true);
+    assert.throws(() => manager.broadcastWrite(groupId, 'x'), /not found/);
+  });
+});
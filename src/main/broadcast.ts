@@ -0,0 +1,65 @@
+import { randomUUID } from 'crypto';
+import type { BroadcastGroup, BroadcastWriteResult } from '../common/terminalTypes';
+import { terminalManager } from './terminal';
+
+/**
+ * Groups of terminals that receive the same input, like tmux's
+ * synchronize-panes. Writes go through the normal terminal write path.
+ */
+export class BroadcastGroupManager {
+  private groups: Map<string, string[]> = new Map();
+
+  createBroadcastGroup(terminalIds: string[]): BroadcastGroup {
+    const unique = Array.from(new Set(terminalIds));
+    if (unique.length < 2) {
+      throw new Error('A broadcast group needs at least two terminals');
+    }
+
+    const missing = unique.filter((id) => !terminalManager.hasTerminal(id));
+    if (missing.length > 0) {
+      throw new Error(`Terminal(s) not found: ${missing.join(', ')}`);
+    }
+
+    const groupId = randomUUID();
+    this.groups.set(groupId, unique);
+    console.log(`[Broadcast ${groupId}] Created for ${unique.length} terminals`);
+    return { groupId, terminalIds: [...unique] };
+  }
+
+  /**
+   * Write the same data to every terminal in the group. Terminals that have
+   * exited are skipped and reported; the rest still get the input.
+   */
+  broadcastWrite(groupId: string, data: string): BroadcastWriteResult[] {
+    const terminalIds = this.groups.get(groupId);
+    if (!terminalIds) {
+      throw new Error(`Broadcast group ${groupId} not found`);
+    }
+
+    return terminalIds.map((terminalId) => {
+      if (!terminalManager.hasTerminal(terminalId)) {
+        return { terminalId, ok: false, error: 'Terminal has exited' };
+      }
+      try {
+        terminalManager.writeToTerminal(terminalId, data);
+        return { terminalId, ok: true };
+      } catch (error) {
+        return { terminalId, ok: false, error: error instanceof Error ? error.message : String(error) };
+      }
+    });
+  }
+
+  deleteBroadcastGroup(groupId: string): boolean {
+    return this.groups.delete(groupId);
+  }
+
+  listBroadcastGroups(): BroadcastGroup[] {
+    return Array.from(this.groups.entries()).map(([groupId, terminalIds]) => ({
+      groupId,
+      terminalIds: [...terminalIds],
+    }));
+  }
+}
+
+// Singleton instance
+export const broadcastGroupManager = new BroadcastGroupManager();
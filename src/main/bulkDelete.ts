@@ -0,0 +1,209 @@
+import { randomUUID } from 'crypto';
+import type {
+  DeleteItemResult,
+  DeletePreview,
+  KillPodsResult,
+  PodKilledEvent,
+  ResourceDeletedEvent,
+  ResourceRef,
+} from '../common/kubeTypes';
+import { getKubectlJson, kubectlFailure, runKubectl } from './kube';
+import { emitToRenderer } from './events';
+
+export interface DeleteBySelectorSpec {
+  kind: string;
+  labelSelector: string;
+  namespace?: string;
+  context: string;
+  /** Token from the preview call; without it nothing is deleted */
+  confirmToken?: string;
+  gracePeriodSeconds?: number;
+}
+
+export interface KillPodsSpec {
+  labelSelector: string;
+  namespace: string;
+  context: string;
+  gracePeriodSeconds?: number;
+  /** Required unless dryRun is set */
+  confirm?: boolean;
+  /** Only list the pods that would be killed */
+  dryRun?: boolean;
+}
+
+interface PendingDelete {
+  spec: DeleteBySelectorSpec;
+  items: ResourceRef[];
+  expiresAt: number;
+}
+
+// A preview has to be confirmed soon, while it still reflects the cluster
+const CONFIRM_TTL_MS = 2 * 60 * 1000;
+const MAX_CONCURRENT_DELETES = 5;
+
+const pendingDeletes: Map<string, PendingDelete> = new Map();
+
+function sameTarget(a: DeleteBySelectorSpec, b: DeleteBySelectorSpec): boolean {
+  return (
+    a.kind === b.kind &&
+    a.labelSelector === b.labelSelector &&
+    (a.namespace ?? '') === (b.namespace ?? '') &&
+    a.context === b.context
+  );
+}
+
+function pruneExpired(): void {
+  const now = Date.now();
+  for (const [token, pending] of Array.from(pendingDeletes)) {
+    if (pending.expiresAt <= now) {
+      pendingDeletes.delete(token);
+    }
+  }
+}
+
+/**
+ * List what a selector matches and hand out a confirm token for deleting
+ * exactly those objects
+ */
+export async function previewDeleteBySelector(spec: DeleteBySelectorSpec): Promise<DeletePreview> {
+  pruneExpired();
+
+  const args = ['get', spec.kind, '-l', spec.labelSelector];
+  if (!spec.namespace) {
+    args.push('--all-namespaces');
+  }
+  const list = await getKubectlJson(spec.context, args, spec.namespace);
+
+  const items: ResourceRef[] = (list?.items ?? []).map((item: any) => ({
+    kind: spec.kind,
+    name: item.metadata?.name,
+    namespace: item.metadata?.namespace,
+  }));
+
+  const confirmToken = randomUUID();
+  const expiresAt = Date.now() + CONFIRM_TTL_MS;
+  pendingDeletes.set(confirmToken, { spec, items, expiresAt });
+
+  return { confirmToken, expiresAt, items };
+}
+
+async function deleteOne(ref: ResourceRef, context: string, gracePeriodSeconds?: number): Promise<DeleteItemResult> {
+  const args = ['delete', ref.kind, ref.name, '--wait=false'];
+  if (gracePeriodSeconds !== undefined) {
+    args.push(`--grace-period=${gracePeriodSeconds}`);
+  }
+
+  try {
+    const result = await runKubectl(context, args, ref.namespace);
+    if (result.code !== 0) {
+      const failure = kubectlFailure(result, `Failed to delete ${ref.name}`);
+      return { ref, ok: false, error: failure.message, code: failure.code };
+    }
+    return { ref, ok: true };
+  } catch (error) {
+    return { ref, ok: false, error: error instanceof Error ? error.message : String(error) };
+  }
+}
+
+/**
+ * Delete the objects from a confirmed preview, emitting `resource:deleted`
+ * as each one completes. Objects created after the preview are left alone.
+ */
+export async function confirmDeleteBySelector(spec: DeleteBySelectorSpec): Promise<DeleteItemResult[]> {
+  pruneExpired();
+
+  const token = spec.confirmToken ?? '';
+  const pending = pendingDeletes.get(token);
+  if (!pending) {
+    throw new Error('Delete confirmation expired or is invalid. Preview the deletion again.');
+  }
+  if (!sameTarget(pending.spec, spec)) {
+    throw new Error('Delete confirmation does not match this selector. Preview the deletion again.');
+  }
+  // Tokens are single-use
+  pendingDeletes.delete(token);
+
+  const results: DeleteItemResult[] = new Array(pending.items.length);
+  let next = 0;
+
+  const worker = async () => {
+    while (next < pending.items.length) {
+      const index = next++;
+      const result = await deleteOne(pending.items[index], spec.context, spec.gracePeriodSeconds);
+      results[index] = result;
+
+      const event: ResourceDeletedEvent = { ...result, confirmToken: token };
+      emitToRenderer('resource:deleted', event);
+    }
+  };
+
+  await Promise.all(
+    Array.from({ length: Math.min(MAX_CONCURRENT_DELETES, pending.items.length) }, () => worker())
+  );
+
+  const failed = results.filter((result) => !result.ok).length;
+  console.log(
+    `[Delete] ${spec.kind} -l ${spec.labelSelector}: ${results.length - failed} deleted, ${failed} failed`
+  );
+  return results;
+}
+
+/**
+ * Delete the pods matching a selector one at a time, e.g. to force a
+ * rolling restart, emitting `pod:killed` for each. Pods already
+ * terminating are skipped. Requires `confirm`; `dryRun` only lists.
+ */
+export async function killPods(spec: KillPodsSpec): Promise<KillPodsResult> {
+  if (!spec.dryRun && spec.confirm !== true) {
+    throw new Error('Killing pods needs confirmation; pass confirm: true, or use dryRun to preview');
+  }
+
+  const list = await getKubectlJson(spec.context, ['get', 'pods', '-l', spec.labelSelector], spec.namespace);
+  const pods: any[] = list?.items ?? [];
+  const killId = randomUUID();
+  const result: KillPodsResult = {
+    killId,
+    dryRun: spec.dryRun === true,
+    matched: pods.map((pod) => pod.metadata?.name),
+    killed: 0,
+    skipped: 0,
+    failed: 0,
+  };
+
+  if (spec.dryRun) {
+    return result;
+  }
+
+  for (const pod of pods) {
+    const name: string = pod.metadata?.name;
+    const event: PodKilledEvent = { killId, pod: name, namespace: spec.namespace, outcome: 'killed' };
+
+    if (pod.metadata?.deletionTimestamp) {
+      event.outcome = 'already-terminating';
+    } else {
+      const ref: ResourceRef = { kind: 'pod', name, namespace: spec.namespace };
+      const deleted = await deleteOne(ref, spec.context, spec.gracePeriodSeconds);
+      if (deleted.code === 'NotFound') {
+        // Gone between listing and deleting: nothing left to kill
+        event.outcome = 'already-terminating';
+      } else if (!deleted.ok) {
+        event.outcome = 'failed';
+        event.error = deleted.error;
+      }
+    }
+
+    if (event.outcome === 'killed') {
+      result.killed += 1;
+    } else if (event.outcome === 'already-terminating') {
+      result.skipped += 1;
+    } else {
+      result.failed += 1;
+    }
+    emitToRenderer('pod:killed', event);
+  }
+
+  console.log(
+    `[Delete] pods -l ${spec.labelSelector}: ${result.killed} killed, ${result.skipped} skipped, ${result.failed} failed`
+  );
+  return result;
+}
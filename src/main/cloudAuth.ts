@@ -0,0 +1,156 @@
+import { existsSync, readFileSync, statSync } from 'fs';
+import os from 'os';
+import path from 'path';
+import YAML from 'yaml';
+import type { CloudAuthDetection, CloudAuthProfile } from '../common/kubeTypes';
+import { getAppConfig } from './appConfig';
+
+/**
+ * Exec plugins of the managed-cluster providers, and what they need that an
+ * app launched from the dock or start menu (rather than a shell) often lacks:
+ * the install dirs on PATH, and a few provider switches.
+ */
+export const DEFAULT_CLOUD_AUTH_PROFILES: CloudAuthProfile[] = [
+  {
+    provider: 'eks',
+    commands: ['aws', 'aws-iam-authenticator'],
+    env: {},
+    path: ['/usr/local/bin', '/opt/homebrew/bin', '~/.local/bin'],
+  },
+  {
+    provider: 'gke',
+    commands: ['gke-gcloud-auth-plugin', 'gcloud'],
+    env: { USE_GKE_GCLOUD_AUTH_PLUGIN: 'True' },
+    path: [
+      '~/google-cloud-sdk/bin',
+      '/usr/local/bin',
+      '/opt/homebrew/bin',
+      '/opt/homebrew/share/google-cloud-sdk/bin',
+      '/usr/lib/google-cloud-sdk/bin',
+      '/snap/bin',
+    ],
+  },
+  {
+    provider: 'aks',
+    commands: ['kubelogin', 'az'],
+    env: {},
+    path: ['/usr/local/bin', '/opt/homebrew/bin', '~/.azure-kubelogin'],
+  },
+];
+
+interface KubeconfigEntry {
+  mtimeMs: number;
+  // Context name -> exec plugin command
+  execCommands: Map<string, string | null>;
+}
+
+// Parsed on every kubectl call otherwise; re-read only when the file changes
+const kubeconfigCache: Map<string, KubeconfigEntry> = new Map();
+
+function readExecCommands(kubeconfigPath: string): Map<string, string | null> {
+  let mtimeMs: number;
+  try {
+    mtimeMs = statSync(kubeconfigPath).mtimeMs;
+  } catch {
+    return new Map();
+  }
+
+  const cached = kubeconfigCache.get(kubeconfigPath);
+  if (cached && cached.mtimeMs === mtimeMs) {
+    return cached.execCommands;
+  }
+
+  const execCommands: Map<string, string | null> = new Map();
+  try {
+    const config = YAML.parse(readFileSync(kubeconfigPath, 'utf8')) ?? {};
+    const users: any[] = Array.isArray(config.users) ? config.users : [];
+    for (const entry of Array.isArray(config.contexts) ? config.contexts : []) {
+      const user = users.find((candidate) => candidate?.name === entry?.context?.user)?.user;
+      const command = user?.exec?.command;
+      execCommands.set(entry?.name, typeof command === 'string' ? command : null);
+    }
+  } catch {
+    // Unreadable config: kubectl will report it; inject nothing
+  }
+
+  kubeconfigCache.set(kubeconfigPath, { mtimeMs, execCommands });
+  return execCommands;
+}
+
+/**
+ * Built-in profiles with the configured ones laid over them by provider name
+ */
+export function getCloudAuthProfiles(): CloudAuthProfile[] {
+  const profiles = [...DEFAULT_CLOUD_AUTH_PROFILES];
+  for (const override of getAppConfig().cloudAuth.profiles) {
+    const index = profiles.findIndex((profile) => profile.provider === override.provider);
+    if (index >= 0) {
+      profiles[index] = override;
+    } else {
+      profiles.push(override);
+    }
+  }
+  return profiles;
+}
+
+function expandHome(dir: string): string {
+  return dir === '~' || dir.startsWith('~/') ? path.join(os.homedir(), dir.slice(1)) : dir;
+}
+
+/**
+ * Which provider a context's exec plugin belongs to, and the env that
+ * would be added for it on top of `baseEnv`
+ */
+export function detectCloudAuth(
+  context: string,
+  kubeconfigPath: string,
+  baseEnv: NodeJS.ProcessEnv = process.env
+): CloudAuthDetection {
+  const command = readExecCommands(kubeconfigPath).get(context) ?? null;
+  const detection: CloudAuthDetection = { context, execCommand: command, provider: null, env: {}, addedPath: [] };
+  if (!command || !getAppConfig().cloudAuth.enabled) {
+    return detection;
+  }
+
+  const name = path.basename(command, path.extname(command));
+  const profile = getCloudAuthProfiles().find((candidate) => candidate.commands.includes(name));
+  if (!profile) {
+    return detection;
+  }
+  detection.provider = profile.provider;
+
+  // Only fill gaps: whatever the user's environment already sets wins
+  for (const [key, value] of Object.entries(profile.env)) {
+    if (baseEnv[key] === undefined) {
+      detection.env[key] = value;
+    }
+  }
+
+  const basePath = baseEnv.PATH ?? '';
+  const current = basePath.split(path.delimiter).filter(Boolean);
+  // An absolute plugin path in the kubeconfig needs no help
+  if (!path.isAbsolute(command)) {
+    for (const dir of profile.path.map(expandHome)) {
+      if (!current.includes(dir) && !detection.addedPath.includes(dir) && existsSync(dir)) {
+        detection.addedPath.push(dir);
+      }
+    }
+  }
+  if (detection.addedPath.length > 0) {
+    detection.env.PATH = [...current, ...detection.addedPath].join(path.delimiter);
+  }
+
+  return detection;
+}
+
+/**
+ * Env to add to kubectl and terminals for a context so its cloud exec
+ * plugin can run; empty when the context doesn't use one
+ */
+export function getCloudAuthEnv(
+  context: string,
+  kubeconfigPath: string,
+  baseEnv: NodeJS.ProcessEnv = process.env
+): Record<string, string> {
+  return detectCloudAuth(context, kubeconfigPath, baseEnv).env;
+}
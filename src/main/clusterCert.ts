@@ -0,0 +1,116 @@
+import { isIP } from 'net';
+import tls from 'tls';
+import type { ClusterCertStatus } from '../common/kubeTypes';
+import { getClusterCa, resolveContext } from './kube';
+
+// Serving certificates expiring within this many days are flagged
+const EXPIRY_WARNING_DAYS = 30;
+const CONNECT_TIMEOUT_MS = 10 * 1000;
+const DAY_MS = 24 * 60 * 60 * 1000;
+
+interface PeerCertificate {
+  cert: tls.PeerCertificate;
+  authorized: boolean;
+  authorizationError: string | null;
+}
+
+function readPeerCertificate(options: tls.ConnectionOptions): Promise<PeerCertificate> {
+  return new Promise((resolve, reject) => {
+    const socket = tls.connect({ ...options, timeout: CONNECT_TIMEOUT_MS });
+    socket.once('secureConnect', () => {
+      const cert = socket.getPeerCertificate();
+      const authorizationError = socket.authorizationError ? String(socket.authorizationError) : null;
+      resolve({ cert, authorized: socket.authorized, authorizationError });
+      socket.destroy();
+    });
+    socket.once('timeout', () => {
+      socket.destroy();
+      reject(new Error(`No TLS handshake within ${CONNECT_TIMEOUT_MS / 1000}s`));
+    });
+    socket.once('error', reject);
+  });
+}
+
+// "CN=kube-apiserver, O=..." from the parsed subject or issuer
+function formatName(name: object | undefined): string | null {
+  if (!name) {
+    return null;
+  }
+  const parts = Object.entries(name).map(([key, value]) => `${key}=${Array.isArray(value) ? value.join('+') : value}`);
+  return parts.length > 0 ? parts.join(', ') : null;
+}
+
+/**
+ * Connect to a context's API server and report when its serving
+ * certificate expires, warning within 30 days. The handshake itself
+ * never fails on trust; whether the certificate verifies against the
+ * kubeconfig's CA is reported separately. Plain-HTTP servers and servers
+ * that can't be reached are results, not errors.
+ */
+export async function checkClusterCertExpiry(context: string): Promise<ClusterCertStatus> {
+  const resolved = await resolveContext(context);
+  const status: ClusterCertStatus = {
+    context,
+    server: resolved.server,
+    state: 'ok',
+    subject: null,
+    issuer: null,
+    validFrom: null,
+    validTo: null,
+    daysRemaining: null,
+    trusted: null,
+    verificationError: null,
+    insecureSkipTlsVerify: resolved.insecureSkipTlsVerify,
+    message: null,
+  };
+
+  let url: URL;
+  try {
+    url = new URL(resolved.server);
+  } catch {
+    throw new Error(`Server URL "${resolved.server}" of context ${context} is not valid`);
+  }
+  if (url.protocol !== 'https:') {
+    return { ...status, state: 'not-tls', message: 'The server is not using TLS' };
+  }
+
+  const host = url.hostname.replace(/^\[|\]$/g, '');
+  const serverName = resolved.tlsServerName ?? (isIP(host) ? undefined : host);
+  let peer: PeerCertificate;
+  try {
+    peer = await readPeerCertificate({
+      host,
+      port: url.port ? Number(url.port) : 443,
+      servername: serverName,
+      ca: (await getClusterCa(context)) ?? undefined,
+      // Inspect whatever is served; trust is reported, not enforced
+      rejectUnauthorized: false,
+    });
+  } catch (error) {
+    const reason = error instanceof Error ? error.message : String(error);
+    const proxy = resolved.proxyUrl
+      ? ` (kubectl reaches it through ${resolved.proxyUrl}, which this check doesn't use)`
+      : '';
+    return { ...status, state: 'unreachable', message: `Could not connect to ${url.host}: ${reason}${proxy}` };
+  }
+
+  const { cert } = peer;
+  const validTo = Date.parse(cert.valid_to);
+  const daysRemaining = Number.isNaN(validTo) ? null : Math.floor((validTo - Date.now()) / DAY_MS);
+  status.subject = formatName(cert.subject);
+  status.issuer = formatName(cert.issuer);
+  status.validFrom = cert.valid_from ? new Date(cert.valid_from).toISOString() : null;
+  status.validTo = Number.isNaN(validTo) ? null : new Date(validTo).toISOString();
+  status.daysRemaining = daysRemaining;
+  status.trusted = resolved.insecureSkipTlsVerify ? null : peer.authorized;
+  status.verificationError = resolved.insecureSkipTlsVerify ? null : peer.authorizationError;
+
+  if (daysRemaining !== null && validTo <= Date.now()) {
+    status.state = 'expired';
+    status.message = `The API server certificate expired on ${status.validTo}`;
+  } else if (daysRemaining !== null && daysRemaining < EXPIRY_WARNING_DAYS) {
+    status.state = 'expiring';
+    status.message = `The API server certificate expires in ${daysRemaining} day(s)`;
+  }
+  return status;
+}
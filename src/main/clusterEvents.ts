@@ -0,0 +1,97 @@
+import type { ClusterEvent } from '../common/kubeTypes';
+import { emitToRenderer } from './events';
+import { watchManager } from './watch';
+
+export interface EventStreamSpec {
+  context: string;
+  namespace?: string;
+  warningsOnly?: boolean;
+  view?: string;
+}
+
+// Remember counts for this many distinct events per stream
+const MAX_TRACKED_EVENTS = 5000;
+
+export function toClusterEvent(streamId: string, object: any, repeat: boolean): ClusterEvent {
+  const involved = object?.involvedObject ?? object?.regarding ?? {};
+
+  return {
+    streamId,
+    type: object?.type ?? 'Normal',
+    reason: object?.reason ?? '',
+    message: (object?.message ?? object?.note ?? '').trim(),
+    object: {
+      kind: involved.kind ?? '',
+      name: involved.name ?? '',
+      namespace: involved.namespace,
+    },
+    count: eventCount(object),
+    repeat,
+    firstTimestamp: object?.firstTimestamp ?? object?.eventTime ?? null,
+    lastTimestamp: object?.lastTimestamp ?? object?.series?.lastObservedTime ?? object?.eventTime ?? null,
+  };
+}
+
+export function eventCount(object: any): number {
+  return object?.count ?? object?.series?.count ?? 1;
+}
+
+/**
+ * Remember an event's count, bounded to the most recent events. Returns
+ * the count seen before (undefined for a new event), or null when the
+ * count hasn't gone up: reopened watches replay existing events.
+ */
+export function recordEventCount(seenCounts: Map<string, number>, object: any): number | undefined | null {
+  const key: string = object?.metadata?.uid ?? `${object?.metadata?.namespace}/${object?.metadata?.name}`;
+  const count = eventCount(object);
+  const previous = seenCounts.get(key);
+  if (previous !== undefined && count <= previous) {
+    return null;
+  }
+
+  seenCounts.delete(key);
+  seenCounts.set(key, count);
+  if (seenCounts.size > MAX_TRACKED_EVENTS) {
+    const oldest = seenCounts.keys().next().value;
+    if (oldest !== undefined) {
+      seenCounts.delete(oldest);
+    }
+  }
+  return previous;
+}
+
+/**
+ * Watch events (cluster-wide when no namespace is given) and emit
+ * `event:new`. Repeats of the same event are collapsed using its count,
+ * so the UI can show "x5" instead of five rows.
+ */
+export function streamEvents(spec: EventStreamSpec): string {
+  const seenCounts: Map<string, number> = new Map();
+
+  const streamId = watchManager.startWatch(
+    {
+      context: spec.context,
+      kind: 'events',
+      namespace: spec.namespace,
+      allNamespaces: !spec.namespace,
+      fieldSelector: spec.warningsOnly ? 'type=Warning' : undefined,
+      view: spec.view,
+    },
+    (event) => {
+      if (event.type !== 'ADDED' && event.type !== 'MODIFIED') {
+        return;
+      }
+
+      const object = event.object;
+      const previous = recordEventCount(seenCounts, object);
+      if (previous === null) {
+        return;
+      }
+
+      emitToRenderer('event:new', toClusterEvent(streamId, object, previous !== undefined));
+    },
+    (message) => emitToRenderer('watch:error', streamId, message)
+  );
+
+  return streamId;
+}
@@ -0,0 +1,138 @@
+import { randomUUID } from 'crypto';
+import { readFile } from 'fs/promises';
+import YAML from 'yaml';
+import type { ClusterDetail, ClusterDetailList, ClusterHealth } from '../common/kubeTypes';
+import {
+  classifyKubectlError,
+  getAuthStatus,
+  KubeError,
+  resolveContext,
+  resolveKubeconfigPath,
+  runKubectl,
+} from './kube';
+import { emitToRenderer } from './events';
+import { operationRegistry } from './operations';
+
+const PROBE_TIMEOUT_MS = 5000;
+const PROBE_CONCURRENCY = 8;
+
+// Last probe result per kubeconfig + context, shown until a new one arrives
+const lastKnownHealth = new Map<string, ClusterHealth>();
+
+const UNKNOWN_HEALTH: ClusterHealth = { status: 'unknown', checkedAt: null, latencyMs: null, message: null };
+
+function healthKey(kubeconfigPath: string, context: string): string {
+  return `${kubeconfigPath}\u0000${context}`;
+}
+
+async function describeContext(name: string, kubeconfigPath: string, isCurrent: boolean): Promise<ClusterDetail> {
+  const detail: ClusterDetail = {
+    context: name,
+    cluster: null,
+    server: null,
+    namespace: 'default',
+    user: null,
+    isCurrent,
+    auth: null,
+    reachability: lastKnownHealth.get(healthKey(kubeconfigPath, name)) ?? UNKNOWN_HEALTH,
+    problem: null,
+  };
+
+  try {
+    const resolved = await resolveContext(name, kubeconfigPath);
+    detail.cluster = resolved.cluster;
+    detail.server = resolved.server;
+    detail.namespace = resolved.namespace;
+    detail.user = resolved.user;
+    detail.auth = await getAuthStatus(name, kubeconfigPath);
+  } catch (error) {
+    detail.problem = error instanceof Error ? error.message : String(error);
+  }
+
+  return detail;
+}
+
+/**
+ * Time `/healthz` against a context. A hung exec plugin or server is
+ * cancelled after PROBE_TIMEOUT_MS.
+ */
+async function probeContext(context: string, kubeconfigPath: string, listId: string): Promise<ClusterHealth> {
+  const view = `cluster-probe:${listId}`;
+  const started = Date.now();
+  let timedOut = false;
+  const timer = setTimeout(() => {
+    timedOut = true;
+    operationRegistry.cancel({ context, view });
+  }, PROBE_TIMEOUT_MS);
+
+  const health = (status: ClusterHealth['status'], message: string | null): ClusterHealth => ({
+    status,
+    checkedAt: new Date(started).toISOString(),
+    latencyMs: status === 'reachable' ? Date.now() - started : null,
+    message,
+  });
+
+  try {
+    const result = await runKubectl(
+      context,
+      ['get', '--raw', '/healthz', `--request-timeout=${PROBE_TIMEOUT_MS / 1000}s`],
+      undefined,
+      { kubeconfig: kubeconfigPath, view }
+    );
+    if (result.code === 0) {
+      return health('reachable', null);
+    }
+
+    const output = (result.stderr || result.stdout).trim();
+    switch (classifyKubectlError(output)) {
+      case 'Unauthorized':
+        return health('unauthorized', output);
+      case 'Unreachable':
+        return health('unreachable', output);
+      case 'Forbidden':
+        // The server answered and knows who we are
+        return health('reachable', output);
+      default:
+        return health('error', output);
+    }
+  } catch (error) {
+    if (timedOut || (error instanceof KubeError && error.code === 'Cancelled')) {
+      return health('timeout', `No answer within ${PROBE_TIMEOUT_MS / 1000}s`);
+    }
+    return health('error', error instanceof Error ? error.message : String(error));
+  } finally {
+    clearTimeout(timer);
+  }
+}
+
+/**
+ * Every context in a kubeconfig with its cluster, server, namespace, auth
+ * method and credential expiry, for a cluster switcher. Returns at once
+ * with the last known reachability; fresh probes run concurrently and each
+ * result is emitted as a `cluster:detail` event.
+ */
+export async function listClustersDetailed(
+  kubeconfigPath: string = resolveKubeconfigPath()
+): Promise<ClusterDetailList> {
+  const config = YAML.parse(await readFile(kubeconfigPath, 'utf8')) ?? {};
+  const names: string[] = (Array.isArray(config.contexts) ? config.contexts : [])
+    .map((entry: any) => entry?.name)
+    .filter((name: any): name is string => typeof name === 'string' && name.length > 0);
+
+  const listId = randomUUID();
+  const clusters = await Promise.all(
+    names.map((name) => describeContext(name, kubeconfigPath, config['current-context'] === name))
+  );
+
+  const queue = clusters.filter((detail) => !detail.problem);
+  const worker = async () => {
+    for (let detail = queue.shift(); detail; detail = queue.shift()) {
+      const reachability = await probeContext(detail.context, kubeconfigPath, listId);
+      lastKnownHealth.set(healthKey(kubeconfigPath, detail.context), reachability);
+      emitToRenderer('cluster:detail', { listId, detail: { ...detail, reachability } });
+    }
+  };
+  void Promise.all(Array.from({ length: Math.min(PROBE_CONCURRENCY, queue.length) }, worker));
+
+  return { listId, kubeconfigPath, clusters };
+}
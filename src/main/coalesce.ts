@@ -0,0 +1,126 @@
+export interface CoalescerOptions {
+  /** Delay before pending output is flushed */
+  intervalMs?: number;
+  /** Pending output beyond this is dropped from the front */
+  maxPendingChars?: number;
+}
+
+const DEFAULT_INTERVAL_MS = 50;
+const DEFAULT_MAX_PENDING_CHARS = 1024 * 1024;
+
+/**
+ * Batches high-rate output into fewer IPC messages and bounds how much can
+ * pile up between flushes. `onFlush` reports whether anything was dropped.
+ */
+export class OutputCoalescer {
+  private pending = '';
+  private truncated = false;
+  private timer: NodeJS.Timeout | null = null;
+  private readonly intervalMs: number;
+  private readonly maxPendingChars: number;
+
+  constructor(
+    private readonly onFlush: (data: string, truncated: boolean) => void,
+    options: CoalescerOptions = {}
+  ) {
+    this.intervalMs = options.intervalMs ?? DEFAULT_INTERVAL_MS;
+    this.maxPendingChars = options.maxPendingChars ?? DEFAULT_MAX_PENDING_CHARS;
+  }
+
+  push(data: string): void {
+    this.pending += data;
+    if (this.pending.length > this.maxPendingChars) {
+      this.pending = this.pending.slice(-this.maxPendingChars);
+      this.truncated = true;
+    }
+
+    if (!this.timer) {
+      this.timer = setTimeout(() => this.flush(), this.intervalMs);
+    }
+  }
+
+  flush(): void {
+    if (this.timer) {
+      clearTimeout(this.timer);
+      this.timer = null;
+    }
+    if (!this.pending) {
+      return;
+    }
+
+    const data = this.pending;
+    const truncated = this.truncated;
+    this.pending = '';
+    this.truncated = false;
+    this.onFlush(data, truncated);
+  }
+
+  /**
+   * Drop pending output without flushing it
+   */
+  dispose(): void {
+    if (this.timer) {
+      clearTimeout(this.timer);
+      this.timer = null;
+    }
+    this.pending = '';
+  }
+}
+
+/**
+ * Collapses updates that share a key within a window into the latest one,
+ * e.g. rapid status changes of one object. Values are emitted in the order
+ * their key first became pending.
+ */
+export class KeyedCoalescer<T> {
+  private pending: Map<string, T> = new Map();
+  private timer: NodeJS.Timeout | null = null;
+
+  constructor(
+    private readonly onFlush: (key: string, value: T) => void,
+    private readonly windowMs: number
+  ) {}
+
+  push(key: string, value: T): void {
+    this.pending.set(key, value);
+    if (!this.timer) {
+      this.timer = setTimeout(() => this.flush(), this.windowMs);
+    }
+  }
+
+  /**
+   * Emit the pending value for one key now, so an event that must not be
+   * reordered (a deletion) can follow it
+   */
+  flushKey(key: string): void {
+    const value = this.pending.get(key);
+    if (value === undefined) {
+      return;
+    }
+    this.pending.delete(key);
+    this.onFlush(key, value);
+  }
+
+  flush(): void {
+    if (this.timer) {
+      clearTimeout(this.timer);
+      this.timer = null;
+    }
+    const pending = this.pending;
+    this.pending = new Map();
+    for (const [key, value] of pending) {
+      this.onFlush(key, value);
+    }
+  }
+
+  /**
+   * Drop pending values without flushing them
+   */
+  dispose(): void {
+    if (this.timer) {
+      clearTimeout(this.timer);
+      this.timer = null;
+    }
+    this.pending.clear();
+  }
+}
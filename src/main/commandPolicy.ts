@@ -0,0 +1,147 @@
+import { app } from 'electron';
+import { appendFileSync, mkdirSync } from 'fs';
+import path from 'path';
+import type { CommandPolicy } from '../common/appTypes';
+import { getAppConfig } from './appConfig';
+import { KubeError } from './kube';
+
+export type CommandSource = 'kubectl' | 'terminal';
+
+const REGEX_ENTRY_PATTERN = /^\/(.+)\/([a-z]*)$/;
+// CSI sequences (arrows, function keys) and other two-byte escapes
+const ESCAPE_SEQUENCE_PATTERN = /^\x1b(?:\[[0-?]*[ -/]*[@-~]|O.|.)/;
+
+const compiledPatterns: Map<string, RegExp | null> = new Map();
+
+export function getCommandPolicy(): CommandPolicy {
+  return getAppConfig().commandPolicy;
+}
+
+function normalizeCommand(command: string): string {
+  return command.trim().replace(/\s+/g, ' ');
+}
+
+function compile(entry: string): RegExp | null {
+  if (!compiledPatterns.has(entry)) {
+    const match = entry.match(REGEX_ENTRY_PATTERN);
+    let pattern: RegExp | null = null;
+    if (match) {
+      try {
+        pattern = new RegExp(match[1], match[2]);
+      } catch (error) {
+        console.warn(`[Policy] Ignoring invalid pattern ${entry}:`, error);
+      }
+    }
+    compiledPatterns.set(entry, pattern);
+  }
+  return compiledPatterns.get(entry) ?? null;
+}
+
+function matches(command: string, entry: string): boolean {
+  if (REGEX_ENTRY_PATTERN.test(entry)) {
+    return compile(entry)?.test(command) ?? false;
+  }
+  // Whole-word prefix, so "kubectl delete" doesn't match "kubectl deletes"
+  const prefix = normalizeCommand(entry);
+  return command === prefix || command.startsWith(`${prefix} `);
+}
+
+/**
+ * Why a command is blocked by the policy, or null if it may run. A command
+ * that can be written more than one way (a plugin is both `kubectl foo`
+ * and `kubectl-foo`) is given as a list of its forms: it is blocked if any
+ * form matches a blocked pattern, and allowed if any is on the allowed list.
+ */
+export function checkCommand(command: string | string[], policy: CommandPolicy = getCommandPolicy()): string | null {
+  if (!policy.enabled) {
+    return null;
+  }
+
+  const forms = (Array.isArray(command) ? command : [command]).map(normalizeCommand);
+  const denied = policy.deny.find((entry) => forms.some((form) => matches(form, entry)));
+  if (denied) {
+    return `matches blocked pattern "${denied}"`;
+  }
+  if (policy.allow.length > 0 && !policy.allow.some((entry) => forms.some((form) => matches(form, entry)))) {
+    return 'is not on the allowed list';
+  }
+  return null;
+}
+
+/**
+ * Append a blocked command to audit.log (one JSON line per entry). Written
+ * directly rather than through the logger so the log level can't drop it.
+ */
+function audit(source: CommandSource, command: string, reason: string): void {
+  console.warn(`[Policy] Blocked ${source} command (${reason}): ${command}`);
+  try {
+    const auditPath = path.join(app.getPath('userData'), 'audit.log');
+    mkdirSync(path.dirname(auditPath), { recursive: true });
+    appendFileSync(
+      auditPath,
+      `${JSON.stringify({ time: new Date().toISOString(), source, command, reason })}\n`,
+      'utf8'
+    );
+  } catch (error) {
+    console.error('[Policy] Could not write audit log entry:', error);
+  }
+}
+
+export function assertCommandAllowed(source: CommandSource, command: string | string[]): void {
+  const reason = checkCommand(command);
+  if (reason) {
+    const shown = normalizeCommand(Array.isArray(command) ? command[0] : command);
+    audit(source, shown, reason);
+    throw new KubeError('CommandBlocked', `Command blocked by policy: ${shown} ${reason}`);
+  }
+}
+
+/**
+ * Reconstructs the line being typed into a terminal so it can be checked
+ * when Enter is pressed. Best-effort: tab completion, history recall and
+ * cursor movement happen in the shell and aren't visible here.
+ */
+export class TerminalInputFilter {
+  private line = '';
+
+  /**
+   * Returns the data that may be written to the PTY. If a completed line is
+   * blocked, the typed line is cleared (^U) instead of submitted, anything
+   * after it is dropped, and CommandBlocked is thrown after `write` is given
+   * the safe prefix.
+   */
+  filter(data: string, write: (safe: string) => void): void {
+    let index = 0;
+
+    while (index < data.length) {
+      const char = data[index];
+
+      if (char === '\x1b') {
+        const sequence = data.slice(index).match(ESCAPE_SEQUENCE_PATTERN);
+        index += sequence ? sequence[0].length : 1;
+        continue;
+      }
+
+      if (char === '\r' || char === '\n') {
+        const line = this.line;
+        this.line = '';
+        const reason = checkCommand(line);
+        if (reason) {
+          write(`${data.slice(0, index)}\x15`);
+          audit('terminal', normalizeCommand(line), reason);
+          throw new KubeError('CommandBlocked', `Command blocked by policy: ${normalizeCommand(line)} ${reason}`);
+        }
+      } else if (char === '\x7f' || char === '\b') {
+        this.line = this.line.slice(0, -1);
+      } else if (char === '\x03' || char === '\x15') {
+        // ^C / ^U discard the line
+        this.line = '';
+      } else if (char >= ' ') {
+        this.line += char;
+      }
+      index += 1;
+    }
+
+    write(data);
+  }
+}
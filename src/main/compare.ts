@@ -0,0 +1,280 @@
+import YAML from 'yaml';
+import type { DiffHunk, DiffLine, FieldChange, ResourceComparison, ResourceDrift } from '../common/kubeTypes';
+import { getKubectlJson, KubeError } from './kube';
+import { neatObject } from './neatYaml';
+
+export interface CompareLocation {
+  context: string;
+  namespace?: string;
+}
+
+const DIFF_CONTEXT_LINES = 3;
+// LCS table cells; beyond this the YAML is too large to diff line by line
+const MAX_DIFF_CELLS = 2000 * 2000;
+
+function isPlainObject(value: any): value is Record<string, any> {
+  return !!value && typeof value === 'object' && !Array.isArray(value);
+}
+
+async function fetchNormalized(kind: string, name: string, location: CompareLocation): Promise<any | null> {
+  let object: any;
+  try {
+    object = await getKubectlJson(location.context, ['get', kind, name], location.namespace);
+  } catch (error) {
+    if (error instanceof KubeError && error.code === 'NotFound') {
+      return null;
+    }
+    throw error;
+  }
+
+  const neat = neatObject(object);
+  delete neat.status;
+  // Differs by definition when comparing namespaces
+  delete neat.metadata?.namespace;
+  return neat;
+}
+
+/**
+ * Arrays of named items (containers, env, ports, volumes) are matched by
+ * name so an insertion doesn't show everything after it as changed
+ */
+function namedItems(list: any[]): Map<string, any> | null {
+  if (list.length === 0 || !list.every((item) => isPlainObject(item) && typeof item.name === 'string')) {
+    return null;
+  }
+  const items = new Map(list.map((item) => [item.name as string, item]));
+  return items.size === list.length ? items : null;
+}
+
+function diffValues(a: any, b: any, path: string, changes: FieldChange[]): void {
+  if (JSON.stringify(a) === JSON.stringify(b)) {
+    return;
+  }
+  if (a === undefined) {
+    changes.push({ path, change: 'added', a: null, b });
+    return;
+  }
+  if (b === undefined) {
+    changes.push({ path, change: 'removed', a, b: null });
+    return;
+  }
+
+  if (isPlainObject(a) && isPlainObject(b)) {
+    const keys = [...Object.keys(a), ...Object.keys(b).filter((key) => !(key in a))];
+    for (const key of keys) {
+      diffValues(a[key], b[key], path ? `${path}.${key}` : key, changes);
+    }
+    return;
+  }
+
+  if (Array.isArray(a) && Array.isArray(b)) {
+    const namedA = namedItems(a);
+    const namedB = namedItems(b);
+    if (namedA && namedB) {
+      const names = [...namedA.keys(), ...[...namedB.keys()].filter((name) => !namedA.has(name))];
+      for (const name of names) {
+        diffValues(namedA.get(name), namedB.get(name), `${path}[name=${name}]`, changes);
+      }
+      return;
+    }
+    for (let index = 0; index < Math.max(a.length, b.length); index++) {
+      diffValues(a[index], b[index], `${path}[${index}]`, changes);
+    }
+    return;
+  }
+
+  changes.push({ path, change: 'changed', a, b });
+}
+
+/**
+ * Line diff (longest common subsequence) grouped into hunks with a few
+ * lines of context, like `diff -u`
+ */
+function diffLines(a: string[], b: string[]): DiffHunk[] | null {
+  if (a.length * b.length > MAX_DIFF_CELLS) {
+    return null;
+  }
+
+  // lengths[i][j]: LCS of a[i..] and b[j..]
+  const lengths: Uint32Array[] = Array.from({ length: a.length + 1 }, () => new Uint32Array(b.length + 1));
+  for (let i = a.length - 1; i >= 0; i--) {
+    for (let j = b.length - 1; j >= 0; j--) {
+      lengths[i][j] = a[i] === b[j] ? lengths[i + 1][j + 1] + 1 : Math.max(lengths[i + 1][j], lengths[i][j + 1]);
+    }
+  }
+
+  const lines: DiffLine[] = [];
+  let i = 0;
+  let j = 0;
+  while (i < a.length || j < b.length) {
+    if (i < a.length && j < b.length && a[i] === b[j]) {
+      lines.push({ op: 'same', text: a[i], lineA: i + 1, lineB: j + 1 });
+      i++;
+      j++;
+    } else if (j < b.length && (i >= a.length || lengths[i][j + 1] >= lengths[i + 1][j])) {
+      lines.push({ op: 'added', text: b[j], lineA: null, lineB: j + 1 });
+      j++;
+    } else {
+      lines.push({ op: 'removed', text: a[i], lineA: i + 1, lineB: null });
+      i++;
+    }
+  }
+
+  // Each change with its context; overlapping windows merge into one hunk
+  const ranges: [number, number][] = [];
+  lines.forEach((line, index) => {
+    if (line.op === 'same') {
+      return;
+    }
+    const from = Math.max(0, index - DIFF_CONTEXT_LINES);
+    const to = Math.min(lines.length - 1, index + DIFF_CONTEXT_LINES);
+    const last = ranges[ranges.length - 1];
+    if (last && from <= last[1] + 1) {
+      last[1] = to;
+    } else {
+      ranges.push([from, to]);
+    }
+  });
+
+  const hunks: DiffHunk[] = ranges.map(([from, to]) => ({ lines: lines.slice(from, to + 1) }));
+  return hunks;
+}
+
+function formatUnified(hunks: DiffHunk[], labelA: string, labelB: string): string {
+  const output = [`--- ${labelA}`, `+++ ${labelB}`];
+  for (const hunk of hunks) {
+    const startA = hunk.lines.find((line) => line.lineA !== null)?.lineA ?? 0;
+    const startB = hunk.lines.find((line) => line.lineB !== null)?.lineB ?? 0;
+    const countA = hunk.lines.filter((line) => line.op !== 'added').length;
+    const countB = hunk.lines.filter((line) => line.op !== 'removed').length;
+    output.push(`@@ -${startA},${countA} +${startB},${countB} @@`);
+    for (const line of hunk.lines) {
+      output.push(`${line.op === 'added' ? '+' : line.op === 'removed' ? '-' : ' '}${line.text}`);
+    }
+  }
+  return `${output.join('\n')}\n`;
+}
+
+function describe(location: CompareLocation): string {
+  return `${location.context}/${location.namespace ?? '-'}`;
+}
+
+/**
+ * Compare one object in two places (contexts and/or namespaces), e.g.
+ * staging against prod. Both sides are normalized (server fields, status
+ * and namespace dropped) and compared field by field and as YAML lines.
+ */
+export async function compareResourceAcross(
+  kind: string,
+  name: string,
+  a: CompareLocation,
+  b: CompareLocation
+): Promise<ResourceComparison> {
+  const [objectA, objectB] = await Promise.all([fetchNormalized(kind, name, a), fetchNormalized(kind, name, b)]);
+  if (!objectA && !objectB) {
+    throw new KubeError('NotFound', `${kind}/${name} exists in neither ${describe(a)} nor ${describe(b)}`);
+  }
+
+  const changes: FieldChange[] = [];
+  if (objectA && objectB) {
+    diffValues(objectA, objectB, '', changes);
+  }
+
+  const yamlA = objectA ? YAML.stringify(objectA) : '';
+  const yamlB = objectB ? YAML.stringify(objectB) : '';
+  const split = (yaml: string) => (yaml ? yaml.replace(/\n$/, '').split('\n') : []);
+  const hunks = diffLines(split(yamlA), split(yamlB));
+
+  return {
+    kind,
+    name,
+    a: { ...a, exists: objectA !== null },
+    b: { ...b, exists: objectB !== null },
+    identical: objectA !== null && objectB !== null && changes.length === 0,
+    changes,
+    hunks,
+    unified: hunks ? formatUnified(hunks, describe(a), describe(b)) : null,
+  };
+}
+
+const LAST_APPLIED_ANNOTATION = 'kubectl.kubernetes.io/last-applied-configuration';
+
+/**
+ * The parts of the live object that the applied manifest set. Fields only
+ * the server filled in (defaults, other controllers' additions) would
+ * otherwise all look like drift. Named list items that exist only live
+ * are kept, since adding one is a change to the list.
+ */
+function projectOnto(baseline: any, live: any): any {
+  if (isPlainObject(baseline) && isPlainObject(live)) {
+    const projected: Record<string, any> = {};
+    for (const key of Object.keys(baseline)) {
+      if (key in live) {
+        projected[key] = projectOnto(baseline[key], live[key]);
+      }
+    }
+    return projected;
+  }
+
+  if (Array.isArray(baseline) && Array.isArray(live)) {
+    const named = namedItems(baseline);
+    const liveNamed = namedItems(live);
+    if (named && liveNamed) {
+      return live.map((item) => (named.has(item.name) ? projectOnto(named.get(item.name), item) : item));
+    }
+    return live.map((item, index) => (index < baseline.length ? projectOnto(baseline[index], item) : item));
+  }
+
+  return live;
+}
+
+/**
+ * Compare a live object with the manifest it was last applied from (its
+ * last-applied-configuration annotation) to find out-of-band changes such
+ * as `kubectl edit` or `kubectl scale`. Only fields the manifest set are
+ * compared. Objects never applied declaratively have no baseline.
+ */
+export async function getDrift(
+  kind: string,
+  name: string,
+  namespace: string | undefined,
+  context: string
+): Promise<ResourceDrift> {
+  const live = await getKubectlJson(context, ['get', kind, name], namespace);
+  const result: ResourceDrift = {
+    kind,
+    name,
+    namespace,
+    hasBaseline: false,
+    drifted: false,
+    changes: [],
+    hunks: null,
+    unified: null,
+  };
+
+  const annotation = live?.metadata?.annotations?.[LAST_APPLIED_ANNOTATION];
+  if (typeof annotation !== 'string' || !annotation.trim()) {
+    return result;
+  }
+  let applied: any;
+  try {
+    applied = JSON.parse(annotation);
+  } catch {
+    throw new Error(`The ${LAST_APPLIED_ANNOTATION} annotation of ${kind}/${name} is not valid JSON`);
+  }
+
+  const baseline = neatObject(applied);
+  delete baseline.status;
+  const projected = projectOnto(baseline, neatObject(live));
+  diffValues(baseline, projected, '', result.changes);
+
+  const split = (object: any) => YAML.stringify(object).replace(/\n$/, '').split('\n');
+  const hunks = diffLines(split(baseline), split(projected));
+  return {
+    ...result,
+    hasBaseline: true,
+    drifted: result.changes.length > 0,
+    hunks,
+    unified: hunks && hunks.length > 0 ? formatUnified(hunks, 'last-applied', 'live') : null,
+  };
+}
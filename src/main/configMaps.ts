@@ -0,0 +1,90 @@
+import type { ConfigMapContents, ConfigMapEntry } from '../common/kubeTypes';
+import { getKubectlJson, KubeError } from './kube';
+import { patchResource } from './manifests';
+
+// Larger values are cut to a preview; the full text is a kubectl get away
+const MAX_VALUE_PREVIEW_BYTES = 256 * 1024;
+
+function previewOf(value: string): { value: string; truncated: boolean } {
+  if (Buffer.byteLength(value, 'utf8') <= MAX_VALUE_PREVIEW_BYTES) {
+    return { value, truncated: false };
+  }
+  // Cut on a character boundary
+  const preview = Buffer.from(value, 'utf8').subarray(0, MAX_VALUE_PREVIEW_BYTES).toString('utf8');
+  return { value: preview.replace(/\uFFFD$/, ''), truncated: true };
+}
+
+function toContents(configMap: any, name: string, namespace: string): ConfigMapContents {
+  const text: ConfigMapEntry[] = Object.entries<string>(configMap?.data ?? {}).map(([key, value]) => ({
+    key,
+    binary: false,
+    bytes: Buffer.byteLength(value ?? '', 'utf8'),
+    ...previewOf(value ?? ''),
+  }));
+  const binary: ConfigMapEntry[] = Object.entries<string>(configMap?.binaryData ?? {}).map(([key, value]) => ({
+    key,
+    binary: true,
+    bytes: Buffer.from(value ?? '', 'base64').length,
+    ...previewOf(value ?? ''),
+  }));
+
+  return {
+    name,
+    namespace,
+    resourceVersion: configMap?.metadata?.resourceVersion ?? null,
+    immutable: configMap?.immutable === true,
+    entries: [...text, ...binary].sort((a, b) => a.key.localeCompare(b.key)),
+  };
+}
+
+/**
+ * A ConfigMap's keys and values, for viewing and editing config without a
+ * terminal. binaryData values stay base64. Throws NotFound or Forbidden.
+ */
+export async function getConfigMap(name: string, namespace: string, context: string): Promise<ConfigMapContents> {
+  return toContents(await getKubectlJson(context, ['get', 'configmap', name], namespace), name, namespace);
+}
+
+// JSON Pointer escaping for a key used as a path segment
+function pointerSegment(key: string): string {
+  return key.replace(/~/g, '~0').replace(/\//g, '~1');
+}
+
+/**
+ * Replace the value of one existing key and return the ConfigMap as
+ * stored. Other keys are untouched even if they changed meanwhile, since
+ * only this key is patched. Values of binaryData keys are base64.
+ */
+export async function updateConfigMapKey(
+  name: string,
+  namespace: string,
+  key: string,
+  value: string,
+  context: string
+): Promise<ConfigMapContents> {
+  const current = await getKubectlJson(context, ['get', 'configmap', name], namespace);
+  if (current?.immutable === true) {
+    throw new Error(`ConfigMap ${namespace}/${name} is immutable; it has to be recreated to change it`);
+  }
+
+  const inData = Object.prototype.hasOwnProperty.call(current?.data ?? {}, key);
+  const inBinary = Object.prototype.hasOwnProperty.call(current?.binaryData ?? {}, key);
+  if (!inData && !inBinary) {
+    throw new KubeError('NotFound', `ConfigMap ${namespace}/${name} has no key "${key}"`);
+  }
+  if (inBinary && !/^[A-Za-z0-9+/]*={0,2}$/.test(value)) {
+    throw new Error(`"${key}" is binary data; the value must be base64`);
+  }
+
+  // replace fails if the key was removed since it was read
+  const updated = await patchResource(
+    'configmap',
+    name,
+    namespace,
+    [{ op: 'replace', path: `/${inData ? 'data' : 'binaryData'}/${pointerSegment(key)}`, value }],
+    'json',
+    context
+  );
+  console.log(`[ConfigMap ${namespace}/${name}] Updated key "${key}"`);
+  return toContents(updated, name, namespace);
+}
@@ -0,0 +1,141 @@
+import type { ContextSearchResult, KubeContext, KubeContextMatch } from '../common/kubeTypes';
+import { loadKubeConfig } from './kube';
+
+// Same convention as the command policy: "/prod-.*/i" is a regex
+const REGEX_FILTER_PATTERN = /^\/(.+)\/([a-z]*)$/;
+const GLOB_CHARS = /[*?]/;
+// Fuzzy matches that start a word ("p" in "gke_proj_prod") rank higher
+const WORD_BOUNDARY_CHARS = new Set(['-', '_', '.', '/', ':', '@']);
+
+export type ContextFilterMode = ContextSearchResult['mode'];
+
+interface NameMatch {
+  score: number;
+  positions: number[];
+}
+
+interface CompiledFilter {
+  mode: ContextFilterMode;
+  match: (name: string) => NameMatch | null;
+}
+
+function globToRegExp(glob: string): RegExp {
+  const source = glob
+    .split('')
+    .map((char) => (char === '*' ? '.*' : char === '?' ? '.' : char.replace(/[\\^$.+()[\]{}|]/g, '\\$&')))
+    .join('');
+  return new RegExp(`^${source}$`, 'i');
+}
+
+function regexMatch(pattern: RegExp): (name: string) => NameMatch | null {
+  return (name) => {
+    // Fresh lastIndex for /g and /y patterns
+    pattern.lastIndex = 0;
+    const match = pattern.exec(name);
+    if (!match) {
+      return null;
+    }
+    const positions = Array.from({ length: match[0].length }, (_, offset) => match.index + offset);
+    return { score: 0, positions };
+  };
+}
+
+/**
+ * Characters of the pattern in order, not necessarily adjacent ("gkpr"
+ * finds "gke-prod"). Runs of adjacent characters and matches at word
+ * starts score higher; gaps cost a little.
+ */
+function fuzzyMatch(pattern: string): (name: string) => NameMatch | null {
+  const needle = pattern.toLowerCase();
+  return (name) => {
+    const haystack = name.toLowerCase();
+    const positions: number[] = [];
+    let score = 0;
+    let from = 0;
+
+    for (const char of needle) {
+      const index = haystack.indexOf(char, from);
+      if (index < 0) {
+        return null;
+      }
+      const previous = positions[positions.length - 1];
+      if (previous !== undefined && index === previous + 1) {
+        score += 5;
+      } else if (previous !== undefined) {
+        score -= Math.min(index - previous - 1, 5);
+      }
+      if (index === 0 || WORD_BOUNDARY_CHARS.has(haystack[index - 1])) {
+        score += 8;
+      }
+      score += 1;
+      positions.push(index);
+      from = index + 1;
+    }
+
+    return { score, positions };
+  };
+}
+
+/**
+ * Compile a context filter. `/…/flags` is a regular expression, a pattern
+ * with `*` or `?` a glob over the whole name, and plain text a
+ * case-insensitive substring (or, with fuzzy, a subsequence).
+ */
+export function compileContextFilter(pattern: string, fuzzy = false): CompiledFilter {
+  const regex = pattern.match(REGEX_FILTER_PATTERN);
+  if (regex) {
+    let compiled: RegExp;
+    try {
+      compiled = new RegExp(regex[1], regex[2]);
+    } catch (error) {
+      throw new Error(`Invalid pattern: ${error instanceof Error ? error.message : String(error)}`);
+    }
+    return { mode: 'regex', match: regexMatch(compiled) };
+  }
+
+  if (GLOB_CHARS.test(pattern)) {
+    return { mode: 'glob', match: regexMatch(globToRegExp(pattern)) };
+  }
+  if (fuzzy) {
+    return { mode: 'fuzzy', match: fuzzyMatch(pattern) };
+  }
+
+  const escaped = pattern.replace(/[\\^$.*+?()[\]{}|]/g, '\\$&');
+  return { mode: 'substring', match: regexMatch(new RegExp(escaped, 'i')) };
+}
+
+/**
+ * Contexts whose name matches the filter, in kubeconfig order
+ */
+export function filterContexts(contexts: KubeContext[], filter: string): KubeContext[] {
+  const { match } = compileContextFilter(filter);
+  return contexts.filter((context) => match(context.name) !== null);
+}
+
+/**
+ * Search the current kubeconfig's contexts by name. Fuzzy results are
+ * ranked best first; other modes keep kubeconfig order. Positions let the
+ * context switcher highlight what matched.
+ */
+export async function searchContexts(pattern: string, fuzzy = false, limit?: number): Promise<ContextSearchResult> {
+  const filter = compileContextFilter(pattern, fuzzy);
+  const { contexts } = await loadKubeConfig();
+
+  const matches: KubeContextMatch[] = [];
+  for (const context of contexts) {
+    const match = filter.match(context.name);
+    if (match) {
+      matches.push({ context, ...match });
+    }
+  }
+  if (filter.mode === 'fuzzy') {
+    matches.sort((a, b) => b.score - a.score || a.context.name.length - b.context.name.length);
+  }
+
+  return {
+    pattern,
+    mode: filter.mode,
+    total: matches.length,
+    matches: limit !== undefined ? matches.slice(0, limit) : matches,
+  };
+}
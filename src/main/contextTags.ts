@@ -0,0 +1,92 @@
+import type { ContextTagEntry, ContextTags } from '../common/kubeTypes';
+import { getAppConfig, updateAppConfig } from './appConfig';
+
+const TAG_PATTERN = /^[a-z0-9][a-z0-9-]*$/;
+const MAX_TAG_LENGTH = 32;
+const MAX_TAGS = 10;
+
+// "prod", "production", "prd" as a word of the context name or server host
+const PRODUCTION_NAME_PATTERN = /(?:^|[-_.:/@])(?:prod|production|prd)(?:$|[-_.:/@]|\d)/i;
+
+/**
+ * Heuristic tags for a context nobody tagged: "production" when its name
+ * or API server host looks like prod. Deliberately loose; a wrong guess is
+ * corrected by tagging the context explicitly.
+ */
+function detectTags(context: string, server: string | null): string[] {
+  let host = '';
+  if (server) {
+    try {
+      host = new URL(server).hostname;
+    } catch {
+      host = server;
+    }
+  }
+  return PRODUCTION_NAME_PATTERN.test(context) || PRODUCTION_NAME_PATTERN.test(host) ? ['production'] : [];
+}
+
+/**
+ * The stored entry for a context: by name, else by API server so tags
+ * follow a context that was renamed (e.g. by re-running a cloud CLI's
+ * get-credentials)
+ */
+function findEntry(entries: ContextTagEntry[], context: string, server: string | null): ContextTagEntry | undefined {
+  return (
+    entries.find((entry) => entry.context === context) ??
+    (server ? entries.find((entry) => entry.server === server) : undefined)
+  );
+}
+
+export function normalizeTags(tags: string[]): string[] {
+  const normalized: string[] = [];
+  for (const raw of tags) {
+    const tag = String(raw).trim().toLowerCase();
+    if (!tag) {
+      continue;
+    }
+    if (tag.length > MAX_TAG_LENGTH || !TAG_PATTERN.test(tag)) {
+      throw new Error(`Invalid tag "${raw}": use up to ${MAX_TAG_LENGTH} lowercase letters, digits and "-"`);
+    }
+    if (!normalized.includes(tag)) {
+      normalized.push(tag);
+    }
+  }
+  if (normalized.length > MAX_TAGS) {
+    throw new Error(`A context can have at most ${MAX_TAGS} tags`);
+  }
+  return normalized;
+}
+
+/**
+ * Tags set for the context, or detected ones when it was never tagged
+ */
+export function getContextTags(context: string, server: string | null): ContextTags {
+  const { autoDetect, entries } = getAppConfig().contextTags;
+  const entry = findEntry(entries, context, server);
+  if (entry) {
+    return { tags: [...entry.tags], detected: false };
+  }
+  return { tags: autoDetect ? detectTags(context, server) : [], detected: autoDetect };
+}
+
+/**
+ * Tag a context (e.g. "production", "danger") so the UI can warn before
+ * destructive commands. An empty list marks the context as untagged,
+ * overriding detection; null forgets the entry and detection applies again.
+ */
+export function setContextTags(context: string, server: string | null, tags: string[] | null): ContextTags {
+  const normalized = tags === null ? null : normalizeTags(tags);
+
+  updateAppConfig((config) => {
+    const { entries } = config.contextTags;
+    const existing = findEntry(entries, context, server);
+    if (existing) {
+      entries.splice(entries.indexOf(existing), 1);
+    }
+    if (normalized !== null) {
+      entries.push({ context, server, tags: normalized });
+    }
+  });
+
+  return getContextTags(context, server);
+}
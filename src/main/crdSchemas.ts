@@ -0,0 +1,74 @@
+import type { CrdSchema } from '../common/kubeTypes';
+import { getKubectlJson, KubeError } from './kube';
+
+interface CacheEntry {
+  crds: any[];
+  fetchedAt: number;
+}
+
+// CRDs change rarely and the list can be megabytes on big clusters
+const CRD_TTL_MS = 10 * 60 * 1000;
+
+const crdCache: Map<string, CacheEntry> = new Map();
+
+async function listCrds(context: string, refresh: boolean): Promise<any[]> {
+  const cached = crdCache.get(context);
+  if (!refresh && cached && Date.now() - cached.fetchedAt < CRD_TTL_MS) {
+    return cached.crds;
+  }
+
+  const list = await getKubectlJson(context, ['get', 'customresourcedefinitions']);
+  const crds: any[] = list?.items ?? [];
+  crdCache.set(context, { crds, fetchedAt: Date.now() });
+  return crds;
+}
+
+/**
+ * OpenAPI v3 schema of one version of a custom resource, for schema-aware
+ * editing. `schema` is null when the CRD doesn't publish one. Throws
+ * NotFound if no CRD serves that group and kind, or not that version.
+ */
+export async function getCrdSchema(
+  group: string,
+  version: string,
+  kind: string,
+  context: string,
+  refresh = false
+): Promise<CrdSchema> {
+  const crds = await listCrds(context, refresh);
+  const crd = crds.find(
+    (candidate) =>
+      candidate.spec?.group === group && candidate.spec?.names?.kind?.toLowerCase() === kind.toLowerCase()
+  );
+  if (!crd) {
+    throw new KubeError('NotFound', `No CustomResourceDefinition for ${kind} in ${group} is installed`);
+  }
+
+  const versions: any[] = crd.spec?.versions ?? [];
+  const match = versions.find((candidate) => candidate.name === version);
+  if (!match) {
+    const available = versions.map((candidate) => candidate.name).join(', ') || 'none';
+    throw new KubeError('NotFound', `${crd.metadata?.name} has no version ${version} (available: ${available})`);
+  }
+
+  return {
+    group,
+    version,
+    kind: crd.spec.names.kind,
+    crdName: crd.metadata?.name ?? '',
+    namespaced: crd.spec?.scope === 'Namespaced',
+    served: match.served === true,
+    storage: match.storage === true,
+    versions: versions.map((candidate) => candidate.name),
+    // apiextensions/v1beta1 CRDs may declare one schema for all versions
+    schema: match.schema?.openAPIV3Schema ?? crd.spec?.validation?.openAPIV3Schema ?? null,
+  };
+}
+
+export function clearCrdSchemaCache(context?: string): void {
+  if (context) {
+    crdCache.delete(context);
+  } else {
+    crdCache.clear();
+  }
+}
@@ -0,0 +1,83 @@
+import './testSupport';
+import assert from 'node:assert/strict';
+import { afterEach, before, describe, it } from 'node:test';
+import { mkdirSync, mkdtempSync, realpathSync, symlinkSync } from 'fs';
+import os from 'os';
+import path from 'path';
+import { getAppConfig } from './appConfig';
+import { checkCwd, resolveTerminalCwd } from './cwdPolicy';
+import { LocalCommandManager } from './localCommands';
+
+// base/root is allowed; base/rootx and base/outside are not
+let base: string;
+let root: string;
+const policy = () => ({ enabled: true, allowedRoots: [root] });
+
+before(() => {
+  base = realpathSync(mkdtempSync(path.join(os.tmpdir(), 'cwd-policy-')));
+  root = path.join(base, 'root');
+  mkdirSync(path.join(root, 'project'), { recursive: true });
+  mkdirSync(path.join(base, 'rootx'));
+  mkdirSync(path.join(base, 'outside'));
+  symlinkSync(path.join(base, 'outside'), path.join(root, 'escape'));
+});
+
+afterEach(() => {
+  getAppConfig().terminalCwdPolicy = { enabled: false, allowedRoots: [] };
+});
+
+describe('checkCwd', () => {
+  it('allows the root and directories below it', async () => {
+    assert.equal((await checkCwd(root, policy())).allowed, true);
+    const check = await checkCwd(path.join(root, 'project'), policy());
+    assert.equal(check.allowed, true);
+    assert.equal(check.root, root);
+  });
+
+  it('refuses a sibling that only shares the prefix', async () => {
+    const check = await checkCwd(path.join(base, 'rootx'), policy());
+    assert.equal(check.allowed, false);
+    assert.match(check.reason ?? '', /outside the allowed directories/);
+  });
+
+  it('refuses a symlink inside the root that points outside it', async () => {
+    const check = await checkCwd(path.join(root, 'escape'), policy());
+    assert.equal(check.allowed, false);
+    assert.equal(check.resolved, path.join(base, 'outside'));
+  });
+
+  it('refuses climbing out with ..', async () => {
+    assert.equal((await checkCwd(path.join(root, 'project', '..', '..', 'outside'), policy())).allowed, false);
+  });
+
+  it('allows anything that exists when the policy is off', async () => {
+    assert.equal((await checkCwd(path.join(base, 'outside'), { enabled: false, allowedRoots: [] })).allowed, true);
+    assert.equal((await checkCwd(path.join(base, 'missing'), { enabled: false, allowedRoots: [] })).allowed, false);
+  });
+});
+
+describe('resolveTerminalCwd', () => {
+  it('falls back to the first root when the home directory is outside', async () => {
+    getAppConfig().terminalCwdPolicy = policy();
+    assert.equal(await resolveTerminalCwd(undefined, path.join(base, 'outside')), root);
+  });
+
+  it('throws CwdNotAllowed for a requested directory outside the roots', async () => {
+    getAppConfig().terminalCwdPolicy = policy();
+    await assert.rejects(resolveTerminalCwd(path.join(base, 'rootx'), root), { code: 'CwdNotAllowed' });
+  });
+});
+
+describe('LocalCommandManager', () => {
+  it('applies the directory policy before starting anything', async () => {
+    getAppConfig().terminalCwdPolicy = policy();
+    const manager = new LocalCommandManager();
+    await assert.rejects(manager.runCommand({ command: 'make', cwd: path.join(root, 'escape') }), {
+      code: 'CwdNotAllowed',
+    });
+    // An allowed directory gets as far as spawning, which the test stub refuses
+    await assert.rejects(manager.runCommand({ command: 'make', cwd: path.join(root, 'project') }), {
+      name: 'TerminalSpawnError',
+    });
+  });
+});
@@ -0,0 +1,93 @@
+import { realpath } from 'fs/promises';
+import os from 'os';
+import path from 'path';
+import type { TerminalCwdPolicy } from '../common/appTypes';
+import type { CwdCheck } from '../common/terminalTypes';
+import { getAppConfig } from './appConfig';
+import { KubeError } from './kube';
+
+export function getCwdPolicy(): TerminalCwdPolicy {
+  return getAppConfig().terminalCwdPolicy;
+}
+
+function expandHome(directory: string): string {
+  return directory === '~' || directory.startsWith('~/') ? path.join(os.homedir(), directory.slice(1)) : directory;
+}
+
+// Case-insensitive file systems are the default on Windows and macOS
+function comparable(directory: string): string {
+  return process.platform === 'win32' || process.platform === 'darwin' ? directory.toLowerCase() : directory;
+}
+
+function isWithin(directory: string, root: string): boolean {
+  const relative = path.relative(comparable(root), comparable(directory));
+  // A sibling such as "..data" is outside only if the first segment is exactly ".."
+  return relative === '' || (relative.split(path.sep)[0] !== '..' && !path.isAbsolute(relative));
+}
+
+/**
+ * The allowed roots with symlinks resolved; roots that don't exist are skipped
+ */
+async function canonicalRoots(policy: TerminalCwdPolicy): Promise<string[]> {
+  const roots: string[] = [];
+  for (const root of policy.allowedRoots) {
+    try {
+      roots.push(await realpath(path.resolve(expandHome(root))));
+    } catch {
+      console.warn(`[CwdPolicy] Ignoring allowed root ${root}: it does not exist`);
+    }
+  }
+  return roots;
+}
+
+/**
+ * Whether a terminal may start in `directory`. The directory is resolved
+ * to its real path first, so neither "../" nor a symlink inside an
+ * allowed root that points outside it gets through. Always allowed when
+ * the policy is off.
+ */
+export async function checkCwd(directory: string, policy: TerminalCwdPolicy = getCwdPolicy()): Promise<CwdCheck> {
+  let resolved: string;
+  try {
+    resolved = await realpath(path.resolve(expandHome(directory)));
+  } catch {
+    return { directory, resolved: null, allowed: false, root: null, reason: `${directory} does not exist` };
+  }
+  if (!policy.enabled) {
+    return { directory, resolved, allowed: true, root: null, reason: null };
+  }
+
+  const root = (await canonicalRoots(policy)).find((candidate) => isWithin(resolved, candidate)) ?? null;
+  return {
+    directory,
+    resolved,
+    allowed: root !== null,
+    root,
+    reason: root === null ? `${resolved} is outside the allowed directories` : null,
+  };
+}
+
+/**
+ * The directory a terminal starts in: the requested one (or the home
+ * directory) after checking it against the policy. With the policy on and
+ * no directory requested, a home directory outside the allowed roots falls
+ * back to the first allowed root. Throws KubeError CwdNotAllowed.
+ */
+export async function resolveTerminalCwd(requested: string | undefined, fallback: string): Promise<string> {
+  const policy = getCwdPolicy();
+  if (!policy.enabled) {
+    return requested || fallback;
+  }
+
+  const check = await checkCwd(requested || fallback, policy);
+  if (check.allowed && check.resolved) {
+    return check.resolved;
+  }
+  if (!requested) {
+    const [root] = await canonicalRoots(policy);
+    if (root) {
+      return root;
+    }
+  }
+  throw new KubeError('CwdNotAllowed', `Terminal not started: ${check.reason ?? 'directory is not allowed'}`);
+}
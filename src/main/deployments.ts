@@ -0,0 +1,120 @@
+import type {
+  DeploymentConditionEvent,
+  DeploymentConditionStatus,
+  DeploymentConditionType,
+  DeploymentReplicas,
+} from '../common/kubeTypes';
+import { getKubectlJson } from './kube';
+import { emitToRenderer } from './events';
+import { watchManager } from './watch';
+
+const CONDITION_TYPES: DeploymentConditionType[] = ['Available', 'Progressing', 'ReplicaFailure'];
+
+interface ConditionState {
+  status: DeploymentConditionStatus;
+  reason: string | null;
+}
+
+function replicasOf(deployment: any): DeploymentReplicas {
+  const status = deployment?.status ?? {};
+  return {
+    desired: deployment?.spec?.replicas ?? 1,
+    updated: status.updatedReplicas ?? 0,
+    ready: status.readyReplicas ?? 0,
+    available: status.availableReplicas ?? 0,
+    unavailable: status.unavailableReplicas ?? 0,
+  };
+}
+
+function toStatus(value: unknown): DeploymentConditionStatus {
+  return value === 'True' || value === 'False' ? value : 'Unknown';
+}
+
+/**
+ * Watch a deployment and emit `deployment:condition` when its Available,
+ * Progressing or ReplicaFailure condition changes status or reason, e.g.
+ * Progressing going False with ProgressDeadlineExceeded. The first event
+ * for each condition has no previous status; a condition that disappears
+ * (ReplicaFailure once the quota frees up) is reported with a null status.
+ * Only transitions are emitted, so the watch reopening after a timeout
+ * doesn't repeat them. Returns the watch id.
+ */
+export async function watchDeployment(name: string, namespace: string, context: string): Promise<string> {
+  // Fail fast with NotFound rather than watching nothing
+  await getKubectlJson(context, ['get', 'deployment', name], namespace);
+
+  const states = new Map<DeploymentConditionType, ConditionState>();
+
+  const watchId = watchManager.startWatch(
+    { context, kind: 'deployment', name, namespace },
+    (event) => {
+      if (event.type !== 'ADDED' && event.type !== 'MODIFIED' && event.type !== 'DELETED') {
+        return;
+      }
+
+      const deployment = event.object;
+      const base = {
+        watchId,
+        name,
+        namespace,
+        generation: deployment?.metadata?.generation ?? null,
+        observedGeneration: deployment?.status?.observedGeneration ?? null,
+        replicas: replicasOf(deployment),
+      };
+
+      if (event.type === 'DELETED') {
+        const payload: DeploymentConditionEvent = {
+          ...base,
+          type: null,
+          status: null,
+          previousStatus: null,
+          reason: null,
+          message: 'Deployment was deleted',
+          lastTransitionTime: null,
+          deleted: true,
+        };
+        emitToRenderer('deployment:condition', payload);
+        watchManager.stopWatch(watchId);
+        return;
+      }
+
+      const conditions: any[] = deployment?.status?.conditions ?? [];
+      for (const type of CONDITION_TYPES) {
+        const current = conditions.find((entry) => entry?.type === type);
+        const previous = states.get(type) ?? null;
+        if (!current && !previous) {
+          continue;
+        }
+
+        const next: ConditionState | null = current
+          ? { status: toStatus(current.status), reason: current.reason ?? null }
+          : null;
+        // Progressing's message changes with every ReplicaSet update; the reason marks real transitions
+        if (next && previous && next.status === previous.status && next.reason === previous.reason) {
+          continue;
+        }
+
+        if (next) {
+          states.set(type, next);
+        } else {
+          states.delete(type);
+        }
+        const payload: DeploymentConditionEvent = {
+          ...base,
+          type,
+          status: next?.status ?? null,
+          previousStatus: previous?.status ?? null,
+          reason: next?.reason ?? null,
+          message: current?.message ?? null,
+          lastTransitionTime: current?.lastTransitionTime ?? null,
+          deleted: false,
+        };
+        emitToRenderer('deployment:condition', payload);
+      }
+    },
+    (message) => emitToRenderer('watch:error', watchId, message)
+  );
+
+  console.log(`[Deployment ${namespace}/${name}] Watching conditions`);
+  return watchId;
+}
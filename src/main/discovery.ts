@@ -0,0 +1,182 @@
+import type { ApiResource, DiscoveryCacheStatus, ResolvedContext } from '../common/kubeTypes';
+import { runKubectl, kubectlFailure, resolveContext } from './kube';
+import { clearCrdSchemaCache } from './crdSchemas';
+import { parseKubectlTable } from './table';
+
+interface CacheEntry {
+  resources: ApiResource[];
+  fetchedAt: number;
+}
+
+// Discovery is slow on clusters with many CRDs; keep it around for a while
+const API_RESOURCES_TTL_MS = 10 * 60 * 1000;
+
+// Contexts kept; the least recently fetched is dropped past this
+const MAX_CACHED_CONTEXTS = 16;
+
+const apiResourceCache: Map<string, CacheEntry> = new Map();
+// Concurrent callers share one kubectl call instead of each starting their own
+const apiResourceRequests: Map<string, Promise<ApiResource[]>> = new Map();
+
+interface WarmEntry {
+  serverVersion: string | null;
+  scope: ResolvedContext;
+  fetchedAt: number;
+}
+
+const warmCache: Map<string, WarmEntry> = new Map();
+const warmRequests: Map<string, Promise<DiscoveryCacheStatus>> = new Map();
+
+function setBounded<T>(cache: Map<string, T>, context: string, entry: T): void {
+  // Re-inserting moves the context to the end, so the first key is the oldest
+  cache.delete(context);
+  cache.set(context, entry);
+  while (cache.size > MAX_CACHED_CONTEXTS) {
+    cache.delete(cache.keys().next().value as string);
+  }
+}
+
+function parseVerbs(value: string): string[] {
+  // Newer kubectl prints "[create delete get]", older prints "create,delete,get"
+  return value
+    .replace(/^\[|\]$/g, '')
+    .split(/[\s,]+/)
+    .filter(Boolean);
+}
+
+function parseApiResources(output: string): ApiResource[] {
+  return parseKubectlTable(output)
+    .filter((row) => row.NAME)
+    .map((row) => {
+      const apiVersion = row.APIVERSION ?? row.APIGROUP ?? '';
+      const group = apiVersion.includes('/') ? apiVersion.split('/')[0] : '';
+
+      return {
+        name: row.NAME,
+        shortNames: row.SHORTNAMES ? row.SHORTNAMES.split(',').filter(Boolean) : [],
+        apiVersion,
+        group,
+        namespaced: row.NAMESPACED === 'true',
+        kind: row.KIND ?? '',
+        verbs: parseVerbs(row.VERBS ?? ''),
+      };
+    });
+}
+
+async function fetchApiResources(context: string): Promise<ApiResource[]> {
+  const result = await runKubectl(context, ['api-resources', '-o', 'wide']);
+
+  // Aggregated APIs that are down make kubectl exit non-zero while still
+  // printing everything else; only fail when nothing came back
+  if (result.code !== 0 && !result.stdout.trim()) {
+    throw kubectlFailure(result, 'Failed to list API resources');
+  }
+
+  const resources = parseApiResources(result.stdout);
+  setBounded(apiResourceCache, context, { resources, fetchedAt: Date.now() });
+  return resources;
+}
+
+/**
+ * List the API resources served by the cluster along with their verbs
+ */
+export async function listApiResources(context: string, refresh = false): Promise<ApiResource[]> {
+  const cached = apiResourceCache.get(context);
+  if (!refresh && cached && Date.now() - cached.fetchedAt < API_RESOURCES_TTL_MS) {
+    return cached.resources;
+  }
+
+  const pending = apiResourceRequests.get(context);
+  if (pending) {
+    return pending;
+  }
+
+  const request = fetchApiResources(context).finally(() => apiResourceRequests.delete(context));
+  apiResourceRequests.set(context, request);
+  return request;
+}
+
+export function clearApiResourceCache(context?: string): void {
+  if (context) {
+    apiResourceCache.delete(context);
+  } else {
+    apiResourceCache.clear();
+  }
+}
+
+async function fetchServerVersion(context: string): Promise<string | null> {
+  try {
+    const result = await runKubectl(context, ['version', '-o', 'json']);
+    const parsed = JSON.parse(result.stdout || '{}');
+    return parsed?.serverVersion?.gitVersion ?? null;
+  } catch (error) {
+    console.warn(`[Discovery ${context}] Could not read server version:`, error);
+    return null;
+  }
+}
+
+function cacheStatus(context: string, entry: WarmEntry, resources: ApiResource[]): DiscoveryCacheStatus {
+  return {
+    context,
+    server: entry.scope.server,
+    namespace: entry.scope.namespace,
+    user: entry.scope.user,
+    serverVersion: entry.serverVersion,
+    apiResources: resources.length,
+    fetchedAt: new Date(entry.fetchedAt).toISOString(),
+    expiresAt: new Date(entry.fetchedAt + API_RESOURCES_TTL_MS).toISOString(),
+  };
+}
+
+async function warm(context: string, refresh: boolean): Promise<DiscoveryCacheStatus> {
+  const [scope, resources, serverVersion] = await Promise.all([
+    resolveContext(context),
+    listApiResources(context, refresh),
+    fetchServerVersion(context),
+  ]);
+  const entry: WarmEntry = { serverVersion, scope, fetchedAt: Date.now() };
+  setBounded(warmCache, context, entry);
+  return cacheStatus(context, entry, resources);
+}
+
+/**
+ * Fetch what most commands need to know about a context up front: the API
+ * resources, server version and the resolved server, user and namespace.
+ * Meant to run on context switch so the first resource view doesn't wait
+ * on discovery. Entries expire with the API resource TTL; refresh forces a
+ * fetch.
+ */
+export async function warmDiscoveryCache(context: string, refresh = false): Promise<DiscoveryCacheStatus> {
+  const cached = warmCache.get(context);
+  const resources = apiResourceCache.get(context);
+  if (!refresh && cached && resources && Date.now() - cached.fetchedAt < API_RESOURCES_TTL_MS) {
+    return cacheStatus(context, cached, resources.resources);
+  }
+
+  const pending = warmRequests.get(context);
+  if (pending && !refresh) {
+    return pending;
+  }
+
+  const request = warm(context, refresh).finally(() => {
+    if (warmRequests.get(context) === request) {
+      warmRequests.delete(context);
+    }
+  });
+  warmRequests.set(context, request);
+  return request;
+}
+
+/**
+ * Drop everything cached about a context's API (or every context's):
+ * API resources, the warm-up data and CRD schemas
+ */
+export function clearDiscoveryCache(context?: string): void {
+  clearApiResourceCache(context);
+  clearCrdSchemaCache(context);
+  if (context) {
+    warmCache.delete(context);
+  } else {
+    warmCache.clear();
+  }
+}
@@ -0,0 +1,178 @@
+import { randomUUID } from 'crypto';
+import type { EventRateBucket, EventRateEvent } from '../common/kubeTypes';
+import { eventCount, recordEventCount } from './clusterEvents';
+import { emitToRenderer } from './events';
+import { watchManager } from './watch';
+
+export interface EventRateMonitorSpec {
+  context: string;
+  namespace?: string;
+  /** Length of the sliding window counted over */
+  windowMs?: number;
+  view?: string;
+}
+
+interface EventRateMonitor {
+  watchId: string;
+  windowMs: number;
+  slotMs: number;
+  // Ring of per-slot counts by "type\0reason"; slot i covers one slotMs
+  slots: Map<string, number>[];
+  slot: number;
+  slotStartedAt: number;
+  totals: Map<string, number>;
+  timer: NodeJS.Timeout;
+}
+
+const DEFAULT_WINDOW_MS = 5 * 60 * 1000;
+const MIN_WINDOW_MS = 10 * 1000;
+const MAX_WINDOW_MS = 60 * 60 * 1000;
+// The window moves in steps of windowMs / WINDOW_SLOTS
+const WINDOW_SLOTS = 30;
+const EMIT_INTERVAL_MS = 2000;
+
+function bucketKey(type: string, reason: string): string {
+  return `${type}\0${reason}`;
+}
+
+/**
+ * Counts cluster events by type and reason over a sliding window and emits
+ * `event:rate` every couple of seconds, so the UI can flag an event storm
+ * (a burst of Warnings usually means something is wrong). Counts are kept
+ * per time slot, so moving the window only subtracts the expired slot.
+ */
+export class EventRateMonitorManager {
+  private monitors: Map<string, EventRateMonitor> = new Map();
+
+  startEventRateMonitor(spec: EventRateMonitorSpec): string {
+    const windowMs = spec.windowMs ?? DEFAULT_WINDOW_MS;
+    if (!Number.isFinite(windowMs) || windowMs < MIN_WINDOW_MS || windowMs > MAX_WINDOW_MS) {
+      throw new Error(`Window must be between ${MIN_WINDOW_MS / 1000}s and ${MAX_WINDOW_MS / 60000} minutes`);
+    }
+
+    const id = randomUUID();
+    const seenCounts: Map<string, number> = new Map();
+
+    const watchId = watchManager.startWatch(
+      {
+        context: spec.context,
+        kind: 'events',
+        namespace: spec.namespace,
+        allNamespaces: !spec.namespace,
+        view: spec.view,
+      },
+      (event) => {
+        const monitor = this.monitors.get(id);
+        if (!monitor || (event.type !== 'ADDED' && event.type !== 'MODIFIED')) {
+          return;
+        }
+
+        const object = event.object;
+        const previous = recordEventCount(seenCounts, object);
+        if (previous === null) {
+          return;
+        }
+        // The initial list replays history; only count what happened within the window
+        const last = Date.parse(object?.lastTimestamp ?? object?.series?.lastObservedTime ?? object?.eventTime ?? '');
+        if (previous === undefined && !Number.isNaN(last) && last < Date.now() - monitor.windowMs) {
+          return;
+        }
+
+        const occurrences = previous === undefined ? 1 : eventCount(object) - previous;
+        this.add(monitor, bucketKey(object?.type ?? 'Normal', object?.reason ?? ''), occurrences);
+      },
+      (message) => emitToRenderer('watch:error', id, message)
+    );
+
+    const monitor: EventRateMonitor = {
+      watchId,
+      windowMs,
+      slotMs: Math.ceil(windowMs / WINDOW_SLOTS),
+      slots: Array.from({ length: WINDOW_SLOTS }, () => new Map()),
+      slot: 0,
+      slotStartedAt: Date.now(),
+      totals: new Map(),
+      timer: setInterval(() => this.emit(id), EMIT_INTERVAL_MS),
+    };
+    this.monitors.set(id, monitor);
+
+    console.log(`[EventRate ${id}] Counting events in ${spec.namespace ?? 'all namespaces'} over ${windowMs}ms`);
+    return id;
+  }
+
+  stopEventRateMonitor(id: string): boolean {
+    const monitor = this.monitors.get(id);
+    if (!monitor) {
+      return false;
+    }
+
+    clearInterval(monitor.timer);
+    watchManager.stopWatch(monitor.watchId);
+    this.monitors.delete(id);
+    console.log(`[EventRate ${id}] Stopped`);
+    return true;
+  }
+
+  stopAllEventRateMonitors(): void {
+    for (const id of Array.from(this.monitors.keys())) {
+      this.stopEventRateMonitor(id);
+    }
+  }
+
+  private add(monitor: EventRateMonitor, key: string, count: number): void {
+    this.advance(monitor);
+    const slot = monitor.slots[monitor.slot];
+    slot.set(key, (slot.get(key) ?? 0) + count);
+    monitor.totals.set(key, (monitor.totals.get(key) ?? 0) + count);
+  }
+
+  // Move to the slot for now, dropping every slot that fell out of the window
+  private advance(monitor: EventRateMonitor): void {
+    const elapsed = Math.floor((Date.now() - monitor.slotStartedAt) / monitor.slotMs);
+    for (let step = 0; step < Math.min(elapsed, WINDOW_SLOTS); step++) {
+      monitor.slot = (monitor.slot + 1) % WINDOW_SLOTS;
+      const expired = monitor.slots[monitor.slot];
+      for (const [key, count] of expired) {
+        const remaining = (monitor.totals.get(key) ?? 0) - count;
+        if (remaining > 0) {
+          monitor.totals.set(key, remaining);
+        } else {
+          monitor.totals.delete(key);
+        }
+      }
+      expired.clear();
+    }
+    monitor.slotStartedAt += elapsed * monitor.slotMs;
+  }
+
+  private emit(id: string): void {
+    const monitor = this.monitors.get(id);
+    if (!monitor) {
+      return;
+    }
+    // The watch was cancelled elsewhere, e.g. by a context switch
+    if (!watchManager.hasWatch(monitor.watchId)) {
+      this.stopEventRateMonitor(id);
+      return;
+    }
+
+    this.advance(monitor);
+    const buckets: EventRateBucket[] = Array.from(monitor.totals, ([key, count]) => {
+      const [type, reason] = key.split('\0');
+      return { type, reason, count };
+    }).sort((a, b) => b.count - a.count);
+
+    const event: EventRateEvent = {
+      monitorId: id,
+      windowMs: monitor.windowMs,
+      total: buckets.reduce((sum, bucket) => sum + bucket.count, 0),
+      warnings: buckets.filter((bucket) => bucket.type === 'Warning').reduce((sum, bucket) => sum + bucket.count, 0),
+      buckets,
+      timestamp: Date.now(),
+    };
+    emitToRenderer('event:rate', event);
+  }
+}
+
+// Singleton instance
+export const eventRateMonitorManager = new EventRateMonitorManager();
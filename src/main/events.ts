@@ -0,0 +1,32 @@
+import { BrowserWindow } from 'electron';
+
+let targetWindow: BrowserWindow | null = null;
+const taps: Set<(channel: string, args: unknown[]) => void> = new Set();
+
+/**
+ * Set the window that receives backend push events (watches, streams, ...)
+ */
+export function setEventWindow(window: BrowserWindow): void {
+  targetWindow = window;
+}
+
+/**
+ * Send an event to the renderer, ignoring it if the window is gone
+ */
+export function emitToRenderer(channel: string, ...args: unknown[]): void {
+  for (const tap of taps) {
+    tap(channel, args);
+  }
+  if (targetWindow && !targetWindow.isDestroyed()) {
+    targetWindow.webContents.send(channel, ...args);
+  }
+}
+
+/**
+ * Observe every event sent to the renderer (whether or not a window is
+ * open). Returns a function that removes the tap.
+ */
+export function tapRendererEvents(tap: (channel: string, args: unknown[]) => void): () => void {
+  taps.add(tap);
+  return () => taps.delete(tap);
+}
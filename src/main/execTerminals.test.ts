@@ -0,0 +1,81 @@
+import './testSupport';
+import assert from 'node:assert/strict';
+import { before, beforeEach, describe, it } from 'node:test';
+import { writeFileSync } from 'fs';
+import path from 'path';
+import { attachToPod, debugPod, openNodeShell, openSshTerminal } from './execTerminals';
+import { setKubeconfigPath } from './kube';
+import { terminalManager } from './terminal';
+import { userDataDir } from './testSupport';
+
+describe('kubectl process terminals', () => {
+  const kubeconfig = path.join(userDataDir, 'exec-terminals-kubeconfig');
+  let spawned: { file: string; args: string[]; options: any }[];
+
+  before(() => {
+    // JSON is YAML; a GKE context whose exec plugin needs USE_GKE_GCLOUD_AUTH_PLUGIN
+    const config = {
+      contexts: [{ name: 'gke', context: { cluster: 'c', user: 'gke-user' } }],
+      users: [{ name: 'gke-user', user: { exec: { command: 'gke-gcloud-auth-plugin' } } }],
+    };
+    writeFileSync(kubeconfig, JSON.stringify(config));
+    setKubeconfigPath(kubeconfig);
+  });
+
+  beforeEach(() => {
+    spawned = [];
+    const createProcessTerminal = async (_id: string, file: string, args: string[], options: any) => {
+      spawned.push({ file, args, options });
+    };
+    (terminalManager as any).createProcessTerminal = createProcessTerminal;
+    (terminalManager as any).writeNotice = () => undefined;
+  });
+
+  const assertCloudAuthEnv = () => {
+    const [{ options }] = spawned;
+    assert.equal(options.env.KUBECONFIG, kubeconfig);
+    assert.equal(options.env.USE_GKE_GCLOUD_AUTH_PLUGIN, 'True');
+  };
+
+  it('gives attach the context\'s cloud auth env', async () => {
+    await attachToPod('t1', { context: 'gke', namespace: 'shop', pod: 'web-0', stdin: false });
+    assert.ok(spawned[0].args.includes('attach'));
+    assertCloudAuthEnv();
+  });
+
+  it('gives debug the context\'s cloud auth env', async () => {
+    await debugPod('t1', { context: 'gke', namespace: 'shop', pod: 'web-0' });
+    assertCloudAuthEnv();
+  });
+
+  it('gives node shells the context\'s cloud auth env', async () => {
+    await openNodeShell('t1', { context: 'gke', node: 'node-1' });
+    assertCloudAuthEnv();
+  });
+});
+
+describe('ssh terminals', () => {
+  let spawned: string[][];
+
+  beforeEach(() => {
+    spawned = [];
+    (terminalManager as any).createProcessTerminal = async (_id: string, _file: string, args: string[]) => {
+      spawned.push(args);
+    };
+    (terminalManager as any).writeNotice = () => undefined;
+  });
+
+  it('passes a chain of jump hosts to -J', async () => {
+    const jumpHost = 'ops@bastion.example.com,10.0.0.5:2222,[fd00::1]';
+    await openSshTerminal('t1', { host: 'node-1', jumpHost });
+    const [args] = spawned;
+    assert.equal(args[args.indexOf('-J') + 1], jumpHost);
+  });
+
+  it('checks every hop, not just the first', async () => {
+    for (const jumpHost of ['bastion,-oProxyCommand=id', 'bastion,', 'bastion,ops@', 'bastion,a b', '@bastion']) {
+      await assert.rejects(openSshTerminal('t1', { host: 'node-1', jumpHost }), /Invalid jump host/, jumpHost);
+    }
+    assert.equal(spawned.length, 0);
+  });
+});
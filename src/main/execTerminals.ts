@@ -0,0 +1,236 @@
+import { stat } from 'fs/promises';
+import os from 'os';
+import path from 'path';
+import type {
+  AttachToPodOptions,
+  DebugPodOptions,
+  NodeShellOptions,
+  SshTerminalOptions,
+} from '../common/terminalTypes';
+import { getCloudAuthEnv } from './cloudAuth';
+import { buildKubectlArgs, getKubectlBinary, getKubectlEnv, resolveKubeconfigPath } from './kube';
+import { terminalManager, type OutputHint } from './terminal';
+
+const ATTACH_HINTS: OutputHint[] = [
+  {
+    pattern: /Unable to use a TTY|did not allocate one/i,
+    message:
+      'The container was started without a TTY, so output is shown line-buffered and interactive programs may not render. Use exec for an interactive shell.',
+  },
+  {
+    pattern: /cannot attach to a container in a completed pod|container not found/i,
+    message: 'The container is not running, so there is no process to attach to.',
+  },
+];
+
+export const DEFAULT_DEBUG_IMAGE = 'nicolaka/netshoot';
+
+const DEBUG_HINTS: OutputHint[] = [
+  {
+    pattern: /ephemeral containers are disabled|could not find the requested resource|EphemeralContainers/i,
+    message:
+      'This cluster does not support ephemeral debug containers (they need Kubernetes 1.23+ with the feature enabled). Try exec into an existing container instead.',
+  },
+  {
+    pattern: /ErrImagePull|ImagePullBackOff|Back-off pulling image/i,
+    message: 'The debug image could not be pulled. Pick an image your cluster can reach.',
+  },
+];
+
+export const DEFAULT_NODE_SHELL_IMAGE = 'busybox';
+
+// Prefer bash on the host, fall back to sh (minimal node OSes)
+const HOST_SHELL_SCRIPT = 'if command -v bash >/dev/null 2>&1; then exec bash -l; else exec sh -l; fi';
+
+const NODE_SHELL_HINTS: OutputHint[] = [
+  {
+    pattern: /forbidden|cannot create resource "pods"|violates PodSecurity/i,
+    message:
+      'Not permitted: a node shell needs rights to create pods with host namespaces in the context\'s namespace, and that namespace\'s Pod Security level must allow privileged pods.',
+  },
+  {
+    pattern: /nodes? "[^"]+" not found/i,
+    message: 'That node no longer exists.',
+  },
+];
+
+// Host names, IPv4/IPv6 addresses and ssh config aliases; a leading "-" would be read as an option
+const SSH_HOST_PATTERN = /^[A-Za-z0-9_.:[\]%][A-Za-z0-9_.:[\]%-]*$/;
+const SSH_USER_PATTERN = /^[A-Za-z0-9_.][A-Za-z0-9_.@-]*$/;
+
+const SSH_HINTS: OutputHint[] = [
+  {
+    pattern: /Are you sure you want to continue connecting/i,
+    message:
+      'First connection to this host: compare the fingerprint above with one from the host\'s owner, then type "yes" to trust it.',
+  },
+  {
+    pattern: /REMOTE HOST IDENTIFICATION HAS CHANGED/i,
+    message:
+      'The host key differs from the one in known_hosts. This is expected after a rebuild, but can mean the connection is being intercepted; confirm before removing the old key with ssh-keygen -R.',
+  },
+  {
+    pattern: /UNPROTECTED PRIVATE KEY FILE/i,
+    message: 'ssh refused the key because others can read it. Restrict it with chmod 600.',
+  },
+  {
+    pattern: /Permission denied \((?:publickey|password|keyboard-interactive)/i,
+    message:
+      'Authentication failed: check the user name and that the key is authorized on the host (and on the bastion).',
+  },
+  {
+    pattern: /Could not resolve hostname|Connection timed out|Connection refused|No route to host/i,
+    message: 'The host could not be reached. Check the host name, port and whether a bastion is needed.',
+  },
+];
+
+// What the app's own kubectl calls get, so a context's cloud exec plugin works here too
+function kubectlEnv(context: string): Record<string, string> {
+  return { ...getKubectlEnv(), ...getCloudAuthEnv(context, resolveKubeconfigPath()) };
+}
+
+/**
+ * Open a PTY running `kubectl attach` to watch (and optionally drive)
+ * a container's main process
+ */
+export async function attachToPod(terminalId: string, options: AttachToPodOptions): Promise<void> {
+  const args = ['attach', options.pod];
+
+  if (options.container) {
+    args.push('-c', options.container);
+  }
+  // Without -i kubectl ignores keystrokes, keeping the attach read-only
+  if (options.stdin) {
+    args.push('-i', '-t');
+  }
+
+  await terminalManager.createProcessTerminal(
+    terminalId,
+    getKubectlBinary(options.context),
+    buildKubectlArgs(options.context, args, options.namespace),
+    { env: kubectlEnv(options.context), outputHints: ATTACH_HINTS }
+  );
+
+  if (options.stdin) {
+    terminalManager.writeNotice(
+      terminalId,
+      'Attached with input enabled: keystrokes (including Ctrl-C) go to the container\'s main process.'
+    );
+  }
+}
+
+/**
+ * Open a PTY running `kubectl debug` with an ephemeral container that shares
+ * the target container's process namespace, for distroless images without a shell
+ */
+export async function debugPod(terminalId: string, options: DebugPodOptions): Promise<void> {
+  const args = ['debug', options.pod, '-it', `--image=${options.image || DEFAULT_DEBUG_IMAGE}`];
+
+  if (options.targetContainer) {
+    args.push(`--target=${options.targetContainer}`);
+  }
+  if (options.command && options.command.length > 0) {
+    args.push('--', ...options.command);
+  }
+
+  await terminalManager.createProcessTerminal(
+    terminalId,
+    getKubectlBinary(options.context),
+    buildKubectlArgs(options.context, args, options.namespace),
+    { env: kubectlEnv(options.context), outputHints: DEBUG_HINTS }
+  );
+}
+
+/**
+ * Open a host shell on a node via `kubectl debug node/NAME`. kubectl runs a
+ * pod with the node's namespaces and its root filesystem mounted at /host;
+ * `chroot /host` makes the shell behave like an SSH session on the node.
+ * See docs/NODE_SHELL.md for the required permissions.
+ */
+export async function openNodeShell(terminalId: string, options: NodeShellOptions): Promise<void> {
+  const args = ['debug', `node/${options.node}`, '-it', `--image=${options.image || DEFAULT_NODE_SHELL_IMAGE}`];
+
+  if (options.profile) {
+    args.push(`--profile=${options.profile}`);
+  }
+  if (options.chroot !== false) {
+    args.push('--', 'chroot', '/host', 'sh', '-c', HOST_SHELL_SCRIPT);
+  }
+
+  await terminalManager.createProcessTerminal(
+    terminalId,
+    getKubectlBinary(options.context),
+    buildKubectlArgs(options.context, args),
+    { env: kubectlEnv(options.context), outputHints: NODE_SHELL_HINTS }
+  );
+
+  terminalManager.writeNotice(
+    terminalId,
+    `Opening a host shell on ${options.node}. The debug pod keeps running after you exit; delete it with kubectl delete pod.`
+  );
+}
+
+/** `-J` takes comma-separated hops, each `[user@]host`; ssh splits a hop at its last "@" */
+function isValidJumpHost(jumpHost: string): boolean {
+  return jumpHost.split(',').every((hop) => {
+    const at = hop.lastIndexOf('@');
+    const userValid = at === -1 || SSH_USER_PATTERN.test(hop.slice(0, at));
+    return userValid && SSH_HOST_PATTERN.test(hop.slice(at + 1));
+  });
+}
+
+function expandHome(filePath: string): string {
+  return filePath === '~' || filePath.startsWith(`~${path.sep}`) || filePath.startsWith('~/')
+    ? path.join(os.homedir(), filePath.slice(2))
+    : filePath;
+}
+
+/**
+ * Open a PTY running `ssh` to a host, optionally through a bastion, for
+ * clusters whose API is only reachable from inside their network. Setup
+ * commands run on the host before its login shell starts. Host key
+ * prompts and password prompts are answered in the terminal as usual.
+ */
+export async function openSshTerminal(terminalId: string, options: SshTerminalOptions): Promise<void> {
+  if (!SSH_HOST_PATTERN.test(options.host ?? '')) {
+    throw new Error(`Invalid host "${options.host}"`);
+  }
+  if (options.user !== undefined && !SSH_USER_PATTERN.test(options.user)) {
+    throw new Error(`Invalid user "${options.user}"`);
+  }
+  if (options.jumpHost !== undefined && !isValidJumpHost(options.jumpHost)) {
+    throw new Error(`Invalid jump host "${options.jumpHost}"`);
+  }
+  if (options.port !== undefined && (!Number.isInteger(options.port) || options.port < 1 || options.port > 65535)) {
+    throw new Error(`Invalid port ${options.port}`);
+  }
+
+  // Keep ssh from dying on idle bastions that drop quiet connections
+  const args = ['-t', '-o', 'ServerAliveInterval=30'];
+  if (options.port) {
+    args.push('-p', String(options.port));
+  }
+  if (options.keyPath) {
+    const keyPath = path.resolve(expandHome(options.keyPath));
+    const info = await stat(keyPath).catch(() => null);
+    if (!info?.isFile()) {
+      throw new Error(`Key file ${keyPath} does not exist`);
+    }
+    // Only offer this key, not every key in the agent
+    args.push('-i', keyPath, '-o', 'IdentitiesOnly=yes');
+  }
+  if (options.jumpHost) {
+    args.push('-J', options.jumpHost);
+  }
+  args.push(options.user ? `${options.user}@${options.host}` : options.host);
+
+  const commands = (options.commands ?? []).map((command) => command.trim()).filter(Boolean);
+  if (commands.length > 0) {
+    args.push(`${commands.join(' && ')}; exec "\${SHELL:-/bin/sh}" -l`);
+  }
+
+  await terminalManager.createProcessTerminal(terminalId, 'ssh', args, { outputHints: SSH_HINTS });
+
+  const via = options.jumpHost ? ` via ${options.jumpHost}` : '';
+  terminalManager.writeNotice(terminalId, `Connecting to ${options.host}${via} over SSH.`);
+}
@@ -0,0 +1,144 @@
+import type { HpaMetric, HpaScalingEvent, HpaStatus } from '../common/kubeTypes';
+import { getKubectlJson } from './kube';
+
+const MAX_SCALING_EVENTS = 10;
+
+// Metric type -> the field holding its source, e.g. Resource -> resource
+function sourceOf(metric: any): any {
+  const type: string = metric?.type ?? '';
+  return metric?.[type.charAt(0).toLowerCase() + type.slice(1)] ?? {};
+}
+
+function metricName(metric: any): string {
+  const source = sourceOf(metric);
+  const name: string = source.name ?? source.metric?.name ?? source.metricName ?? 'unknown';
+  return source.container ? `${name} (container ${source.container})` : name;
+}
+
+function formatUtilization(value: any): string | null {
+  return typeof value === 'number' ? `${value}%` : null;
+}
+
+function formatQuantity(value: any): string | null {
+  return typeof value === 'string' || typeof value === 'number' ? String(value) : null;
+}
+
+/**
+ * Target or current value in the units kubectl shows: "80%" for
+ * utilization, the raw quantity otherwise. Handles autoscaling/v2
+ * (`target`/`current` objects) and v2beta1 (flat fields such as
+ * targetAverageUtilization).
+ */
+function metricValue(
+  source: any,
+  side: 'target' | 'current'
+): { valueType: HpaMetric['valueType']; value: string | null } {
+  const nested = source?.[side];
+  if (nested && typeof nested === 'object') {
+    if (nested.averageUtilization !== undefined || nested.type === 'Utilization') {
+      return { valueType: 'Utilization', value: formatUtilization(nested.averageUtilization) };
+    }
+    if (nested.averageValue !== undefined || nested.type === 'AverageValue') {
+      return { valueType: 'AverageValue', value: formatQuantity(nested.averageValue) };
+    }
+    return { valueType: 'Value', value: formatQuantity(nested.value) };
+  }
+
+  const utilization = source?.[`${side}AverageUtilization`];
+  if (utilization !== undefined) {
+    return { valueType: 'Utilization', value: formatUtilization(utilization) };
+  }
+  const average = source?.[`${side}AverageValue`];
+  if (average !== undefined) {
+    return { valueType: 'AverageValue', value: formatQuantity(average) };
+  }
+  return { valueType: 'Value', value: formatQuantity(source?.[`${side}Value`]) };
+}
+
+function v2Metrics(hpa: any): HpaMetric[] {
+  const specs: any[] = hpa?.spec?.metrics ?? [];
+  const current: any[] = hpa?.status?.currentMetrics ?? [];
+
+  return specs.map((metric) => {
+    const name = metricName(metric);
+    const status = current.find((candidate) => candidate?.type === metric.type && metricName(candidate) === name);
+    const target = metricValue(sourceOf(metric), 'target');
+    return {
+      type: metric.type ?? 'Unknown',
+      name,
+      valueType: target.valueType,
+      target: target.value,
+      current: status ? metricValue(sourceOf(status), 'current').value : null,
+    };
+  });
+}
+
+// autoscaling/v1 only knows average CPU utilization
+function v1Metrics(hpa: any): HpaMetric[] {
+  const target = hpa?.spec?.targetCPUUtilizationPercentage;
+  return [
+    {
+      type: 'Resource',
+      name: 'cpu',
+      valueType: 'Utilization',
+      // The API server's default when unset
+      target: formatUtilization(typeof target === 'number' ? target : 80),
+      current: formatUtilization(hpa?.status?.currentCPUUtilizationPercentage),
+    },
+  ];
+}
+
+async function scalingEvents(name: string, namespace: string, context: string): Promise<HpaScalingEvent[]> {
+  try {
+    const list = await getKubectlJson(
+      context,
+      ['get', 'events', '--field-selector', `involvedObject.kind=HorizontalPodAutoscaler,involvedObject.name=${name}`],
+      namespace
+    );
+    const events: HpaScalingEvent[] = (list?.items ?? []).map((event: any) => ({
+      type: event.type ?? 'Normal',
+      reason: event.reason ?? '',
+      message: event.message ?? '',
+      count: typeof event.count === 'number' ? event.count : 1,
+      timestamp: event.lastTimestamp ?? event.eventTime ?? event.firstTimestamp ?? null,
+    }));
+    const time = (event: HpaScalingEvent) => Date.parse(event.timestamp ?? '') || 0;
+    return events.sort((a, b) => time(b) - time(a)).slice(0, MAX_SCALING_EVENTS);
+  } catch (error) {
+    console.warn(`[HPA ${namespace}/${name}] Could not read events:`, error);
+    return [];
+  }
+}
+
+/**
+ * Replica counts, each metric's current value against its target, the
+ * conditions (AbleToScale, ScalingActive, ScalingLimited) and the most
+ * recent events of a HorizontalPodAutoscaler, for "why isn't this
+ * scaling". Works with autoscaling/v1 and v2 objects.
+ */
+export async function getHpaStatus(name: string, namespace: string, context: string): Promise<HpaStatus> {
+  const hpa = await getKubectlJson(context, ['get', 'hpa', name], namespace);
+  const apiVersion: string = hpa?.apiVersion ?? 'autoscaling/v1';
+  const spec = hpa?.spec ?? {};
+  const status = hpa?.status ?? {};
+
+  return {
+    name,
+    namespace,
+    apiVersion,
+    scaleTargetRef: { kind: spec.scaleTargetRef?.kind ?? '', name: spec.scaleTargetRef?.name ?? '' },
+    minReplicas: typeof spec.minReplicas === 'number' ? spec.minReplicas : 1,
+    maxReplicas: typeof spec.maxReplicas === 'number' ? spec.maxReplicas : 0,
+    currentReplicas: typeof status.currentReplicas === 'number' ? status.currentReplicas : 0,
+    desiredReplicas: typeof status.desiredReplicas === 'number' ? status.desiredReplicas : 0,
+    lastScaleTime: status.lastScaleTime ?? null,
+    metrics: apiVersion === 'autoscaling/v1' ? v1Metrics(hpa) : v2Metrics(hpa),
+    conditions: (status.conditions ?? []).map((condition: any) => ({
+      type: condition.type ?? '',
+      status: condition.status ?? 'Unknown',
+      reason: condition.reason ?? null,
+      message: condition.message ?? null,
+    })),
+    events: await scalingEvents(name, namespace, context),
+  };
+}
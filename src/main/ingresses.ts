@@ -0,0 +1,60 @@
+import type { IngressBackend, IngressPath, IngressRouting } from '../common/kubeTypes';
+import { getKubectlJson } from './kube';
+
+// networking.k8s.io/v1 nests service name and port; v1beta1 and extensions/v1beta1 are flat
+function toBackend(backend: any): IngressBackend | null {
+  if (!backend || typeof backend !== 'object') {
+    return null;
+  }
+
+  if (backend.resource) {
+    return {
+      service: null,
+      port: null,
+      resource: {
+        apiGroup: backend.resource.apiGroup ?? null,
+        kind: backend.resource.kind ?? '',
+        name: backend.resource.name ?? '',
+      },
+    };
+  }
+  if (backend.service) {
+    const port = backend.service.port ?? {};
+    return { service: backend.service.name ?? null, port: port.number ?? port.name ?? null, resource: null };
+  }
+  return { service: backend.serviceName ?? null, port: backend.servicePort ?? null, resource: null };
+}
+
+/**
+ * An Ingress's routing as data: which host and path go to which Service
+ * port, the TLS secrets per host, the ingress class and the addresses the
+ * controller assigned. Reads v1 and the older v1beta1 schemas. Throws
+ * NotFound for a missing Ingress.
+ */
+export async function getIngress(name: string, namespace: string, context: string): Promise<IngressRouting> {
+  const ingress = await getKubectlJson(context, ['get', 'ingress', name], namespace);
+  const spec = ingress?.spec ?? {};
+
+  return {
+    name,
+    namespace,
+    apiVersion: ingress?.apiVersion ?? 'networking.k8s.io/v1',
+    ingressClass:
+      spec.ingressClassName ?? ingress?.metadata?.annotations?.['kubernetes.io/ingress.class'] ?? null,
+    defaultBackend: toBackend(spec.defaultBackend ?? spec.backend),
+    rules: (spec.rules ?? []).map((rule: any) => ({
+      host: rule?.host || null,
+      paths: (rule?.http?.paths ?? []).map(
+        (path: any): IngressPath => ({
+          path: path?.path || '/',
+          pathType: path?.pathType ?? null,
+          backend: toBackend(path?.backend) ?? { service: null, port: null, resource: null },
+        })
+      ),
+    })),
+    tls: (spec.tls ?? []).map((tls: any) => ({ hosts: tls?.hosts ?? [], secretName: tls?.secretName ?? null })),
+    addresses: (ingress?.status?.loadBalancer?.ingress ?? [])
+      .map((entry: any) => entry?.ip ?? entry?.hostname)
+      .filter((address: any): address is string => typeof address === 'string'),
+  };
+}
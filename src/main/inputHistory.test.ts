@@ -0,0 +1,117 @@
+import './testSupport';
+import assert from 'node:assert/strict';
+import { beforeEach, describe, it } from 'node:test';
+import { getAppConfig } from './appConfig';
+import { TerminalInputFilter } from './commandPolicy';
+import { clearInputHistory, getInputHistory, LineAssembler } from './inputHistory';
+import { TerminalManager } from './terminal';
+
+describe('LineAssembler', () => {
+  it('returns a line whose typing was echoed', () => {
+    const lines = new LineAssembler();
+    assert.deepEqual(lines.feed('l'), []);
+    lines.observeOutput('l');
+    assert.deepEqual(lines.feed('s'), []);
+    lines.observeOutput('s');
+    assert.deepEqual(lines.feed('\r'), ['ls']);
+  });
+
+  it('drops a line typed with echo off', () => {
+    const lines = new LineAssembler();
+    lines.feed('hunter2');
+    lines.observeOutput('\r\n');
+    assert.deepEqual(lines.feed('\r'), []);
+
+    // The next line starts over
+    lines.feed('pwd');
+    lines.observeOutput('pwd');
+    assert.deepEqual(lines.feed('\r'), ['pwd']);
+  });
+
+  it('sees echoes through colors and line redraws', () => {
+    const lines = new LineAssembler();
+    lines.feed('ls');
+    lines.observeOutput('l\b\x1b[32ml\x1b[0ms');
+    assert.deepEqual(lines.feed('\r'), ['ls']);
+  });
+
+  it('keeps waiting while typing runs ahead of the echo', () => {
+    const lines = new LineAssembler();
+    lines.feed('k');
+    lines.feed('g');
+    lines.observeOutput('k');
+    lines.observeOutput('g');
+    assert.deepEqual(lines.feed('\r'), ['kg']);
+  });
+
+  it('takes a line pasted together with its Enter', () => {
+    const lines = new LineAssembler();
+    assert.deepEqual(lines.feed('kubectl get pods\r\nkubectl get svc\r'), ['kubectl get pods', 'kubectl get svc']);
+  });
+
+  it('applies backspace, ^W and ^U', () => {
+    const lines = new LineAssembler();
+    assert.deepEqual(lines.feed('lx\x7fs -la foo\x17\r'), ['ls -la ']);
+    assert.deepEqual(lines.feed('rm -rf /\x15echo ok\r'), ['echo ok']);
+  });
+});
+
+describe('terminal input history', () => {
+  const profile = 'history-test';
+  let manager: TerminalManager;
+  let written: string;
+  let atPrompt: boolean | null;
+
+  beforeEach(() => {
+    const config = getAppConfig();
+    config.inputHistory.enabled = true;
+    config.commandPolicy = { enabled: true, allow: [], deny: ['kubectl delete'] };
+    clearInputHistory();
+
+    manager = new TerminalManager();
+    written = '';
+    atPrompt = true;
+    (manager as any).isAtShellPrompt = () => atPrompt;
+    (manager as any).terminals.set('t1', {
+      pty: {
+        pid: 1,
+        write: (data: string) => {
+          written += data;
+        },
+      },
+      awaitingRespawn: false,
+      altScreen: false,
+      profile,
+      inputFilter: new TerminalInputFilter(),
+      inputLines: new LineAssembler(),
+      bytesWritten: 0,
+    });
+  });
+
+  it('records commands typed at the prompt', () => {
+    manager.writeToTerminal('t1', 'kubectl get pods\r');
+    assert.deepEqual(getInputHistory(profile), ['kubectl get pods']);
+    assert.equal(written, 'kubectl get pods\r');
+  });
+
+  it('skips lines blocked by the command policy', () => {
+    assert.throws(() => manager.writeToTerminal('t1', 'kubectl delete ns prod\r'), { code: 'CommandBlocked' });
+    manager.writeToTerminal('t1', 'ls\r');
+    assert.deepEqual(getInputHistory(profile), ['ls']);
+  });
+
+  it('skips a blocked line typed key by key', () => {
+    for (const char of 'kubectl delete ns prod') {
+      manager.writeToTerminal('t1', char);
+    }
+    assert.throws(() => manager.writeToTerminal('t1', '\r'), { code: 'CommandBlocked' });
+    manager.writeToTerminal('t1', 'ls\r');
+    assert.deepEqual(getInputHistory(profile), ['ls']);
+  });
+
+  it('skips lines typed while another program owns the terminal', () => {
+    atPrompt = false;
+    manager.writeToTerminal('t1', 'secret-password\r');
+    assert.deepEqual(getInputHistory(profile), []);
+  });
+});
@@ -0,0 +1,220 @@
+import { app } from 'electron';
+import { mkdirSync, readFileSync, renameSync, writeFileSync } from 'fs';
+import path from 'path';
+import { getAppConfig, updateAppConfig } from './appConfig';
+import { stripAnsi } from './terminalSequences';
+
+// CSI/SS3 sequences (arrows, bracketed paste markers) and two-byte escapes
+const ESCAPE_SEQUENCE_PATTERN = /^\x1b(?:\[[0-?]*[ -/]*[@-~]|O.|.)/;
+// Per profile, oldest dropped first
+const MAX_HISTORY_ENTRIES = 1000;
+const MAX_LINE_LENGTH = 4096;
+// Output kept while waiting for typed text to be echoed
+const MAX_ECHO_CHARS = 2 * MAX_LINE_LENGTH;
+// Typed text counts as echoed once its last few characters show up in the output
+const ECHO_MATCH_CHARS = 8;
+const PERSIST_DELAY_MS = 1000;
+// Key for terminals not started from a profile
+const NO_PROFILE = '';
+
+/**
+ * Rebuilds typed lines from raw keystrokes. Handles backspace, ^W and
+ * ^U/^C; cursor movement, tab completion and history recall happen in the
+ * shell and can't be seen here, so lines edited that way come out as typed.
+ *
+ * A line is only returned if the text typed before Enter was echoed back
+ * (see observeOutput): with echo off, as at sudo's or ssh's password
+ * prompt, it never is. Text typed in the same chunk as its Enter (a paste,
+ * a snippet) can't be checked and counts as echoed.
+ */
+export class LineAssembler {
+  private line = '';
+  /** Typed text the terminal hasn't echoed yet */
+  private awaitingEcho = '';
+  /** Output seen since awaitingEcho started filling */
+  private echoed = '';
+
+  /** Lines completed by Enter in this chunk of input */
+  feed(data: string): string[] {
+    const lines: string[] = [];
+    let index = 0;
+    let verified = this.awaitingEcho === '';
+    let typed = '';
+
+    while (index < data.length) {
+      const char = data[index];
+
+      if (char === '\x1b') {
+        const sequence = data.slice(index).match(ESCAPE_SEQUENCE_PATTERN);
+        index += sequence ? sequence[0].length : 1;
+        continue;
+      }
+
+      if (char === '\r' || char === '\n') {
+        // "\r\n" from a paste is one Enter
+        if (!(char === '\n' && data[index - 1] === '\r') && verified) {
+          lines.push(this.line);
+        }
+        this.startLine();
+        verified = true;
+        typed = '';
+      } else if (char === '\x7f' || char === '\b') {
+        this.line = Array.from(this.line).slice(0, -1).join('');
+        typed = Array.from(typed).slice(0, -1).join('');
+      } else if (char === '\x17') {
+        // ^W: the word before the cursor and the spaces after it
+        this.line = this.line.replace(/\S*\s*$/, '');
+        typed = typed.replace(/\S*\s*$/, '');
+      } else if (char === '\x03' || char === '\x15') {
+        this.startLine();
+        verified = true;
+        typed = '';
+      } else if (char >= ' ' && this.line.length < MAX_LINE_LENGTH) {
+        this.line += char;
+        typed += char;
+      }
+      index += 1;
+    }
+
+    if (!verified) {
+      // Still unconfirmed from an earlier chunk; keep waiting for all of it
+      this.awaitingEcho += typed;
+    } else if (typed) {
+      this.awaitingEcho = typed;
+      this.echoed = '';
+    }
+    return lines;
+  }
+
+  /** Output from the terminal, to confirm typed text was echoed */
+  observeOutput(data: string): void {
+    if (!this.awaitingEcho) {
+      return;
+    }
+    // Control characters (backspaces from line editing) would split the echoed text
+    const text = stripAnsi(data).replace(/[\x00-\x1f\x7f]/g, '');
+    this.echoed = (this.echoed + text).slice(-MAX_ECHO_CHARS);
+    if (this.echoed.includes(this.awaitingEcho.slice(-ECHO_MATCH_CHARS))) {
+      this.awaitingEcho = '';
+      this.echoed = '';
+    }
+  }
+
+  reset(): void {
+    this.startLine();
+  }
+
+  private startLine(): void {
+    this.line = '';
+    this.awaitingEcho = '';
+    this.echoed = '';
+  }
+}
+
+let history: Record<string, string[]> | null = null;
+let persistTimer: NodeJS.Timeout | null = null;
+
+function getHistoryPath(): string {
+  return path.join(app.getPath('userData'), 'input-history.json');
+}
+
+function load(): Record<string, string[]> {
+  if (history) {
+    return history;
+  }
+
+  history = {};
+  try {
+    const raw = JSON.parse(readFileSync(getHistoryPath(), 'utf8'));
+    for (const [profile, lines] of Object.entries(raw ?? {})) {
+      if (Array.isArray(lines)) {
+        history[profile] = lines.filter((line): line is string => typeof line === 'string');
+      }
+    }
+  } catch {
+    // Missing or corrupt: start empty
+  }
+  return history;
+}
+
+function persist(): void {
+  persistTimer = null;
+  try {
+    const historyPath = getHistoryPath();
+    const tempPath = `${historyPath}.tmp`;
+    mkdirSync(path.dirname(historyPath), { recursive: true });
+    writeFileSync(tempPath, JSON.stringify(load()), { encoding: 'utf8', mode: 0o600 });
+    renameSync(tempPath, historyPath);
+  } catch (error) {
+    console.warn('[History] Could not save terminal input history:', error);
+  }
+}
+
+function schedulePersist(): void {
+  if (!persistTimer) {
+    persistTimer = setTimeout(persist, PERSIST_DELAY_MS);
+  }
+}
+
+export function isInputHistoryEnabled(): boolean {
+  return getAppConfig().inputHistory.enabled;
+}
+
+/**
+ * Turn recording on or off. Turning it off keeps what was recorded until
+ * clearInputHistory is called.
+ */
+export function setInputHistoryEnabled(enabled: boolean): void {
+  updateAppConfig((config) => {
+    config.inputHistory.enabled = enabled;
+  });
+}
+
+/**
+ * Record a command typed into a terminal. Blank lines, repeats of the
+ * previous command and lines starting with a space (the HISTCONTROL
+ * ignorespace convention) are skipped.
+ */
+export function recordInput(profile: string | null, line: string): void {
+  if (!isInputHistoryEnabled() || !line.trim() || line.startsWith(' ')) {
+    return;
+  }
+
+  const all = load();
+  const key = profile ?? NO_PROFILE;
+  const lines = all[key] ?? [];
+  all[key] = lines;
+  if (lines[lines.length - 1] === line) {
+    return;
+  }
+  lines.push(line);
+  if (lines.length > MAX_HISTORY_ENTRIES) {
+    lines.splice(0, lines.length - MAX_HISTORY_ENTRIES);
+  }
+  schedulePersist();
+}
+
+/**
+ * Recorded commands for a profile, oldest first; null for terminals that
+ * weren't started from a profile
+ */
+export function getInputHistory(profile: string | null): string[] {
+  return [...(load()[profile ?? NO_PROFILE] ?? [])];
+}
+
+/**
+ * Forget one profile's history (null: terminals without a profile), or
+ * everything when no profile is given
+ */
+export function clearInputHistory(profile?: string | null): void {
+  const all = load();
+  if (profile === undefined) {
+    history = {};
+  } else {
+    delete all[profile ?? NO_PROFILE];
+  }
+  if (persistTimer) {
+    clearTimeout(persistTimer);
+  }
+  persist();
+}
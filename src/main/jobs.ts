@@ -0,0 +1,146 @@
+import type { FailingJobPod, JobState, JobStatusEvent } from '../common/kubeTypes';
+import { getKubectlJson, kubectlFailure, runKubectl } from './kube';
+import { emitToRenderer } from './events';
+import { watchManager } from './watch';
+
+// Object names are DNS labels; leave room for the pod suffix the Job adds
+const MAX_JOB_NAME_LENGTH = 52;
+
+function condition(job: any, type: string): any {
+  const conditions: any[] = job?.status?.conditions ?? [];
+  return conditions.find((entry) => entry?.type === type && entry?.status === 'True');
+}
+
+export function jobState(job: any): JobState {
+  return condition(job, 'Complete') ? 'complete' : condition(job, 'Failed') ? 'failed' : 'running';
+}
+
+/**
+ * Label selector for a Job's pods. The Job's own selector matches on its
+ * controller uid, so pods of an older Job with the same name aren't
+ * picked up; `job-name` is the fallback.
+ */
+export async function getJobPodSelector(name: string, namespace: string, context: string): Promise<string> {
+  const job = await getKubectlJson(context, ['get', 'job', name], namespace);
+  const matchLabels: Record<string, string> = job?.spec?.selector?.matchLabels ?? {};
+  const labels = Object.entries(matchLabels).map(([key, value]) => `${key}=${value}`);
+  return labels.length > 0 ? labels.join(',') : `job-name=${name}`;
+}
+
+/**
+ * The most recently failed pod of a Job and why its container stopped
+ */
+async function failingPod(name: string, namespace: string, context: string): Promise<FailingJobPod | null> {
+  const list = await getKubectlJson(context, ['get', 'pods', '-l', `job-name=${name}`], namespace);
+  const failed: any[] = (list?.items ?? []).filter((pod: any) => pod.status?.phase === 'Failed');
+  if (failed.length === 0) {
+    return null;
+  }
+
+  const created = (pod: any) => Date.parse(pod.metadata?.creationTimestamp ?? '') || 0;
+  const pod = failed.reduce((newest, candidate) => (created(candidate) >= created(newest) ? candidate : newest));
+  const statuses: any[] = pod.status?.containerStatuses ?? [];
+  const terminated = statuses.map((status) => status.state?.terminated).find((state) => state && state.exitCode !== 0);
+
+  return {
+    name: pod.metadata?.name ?? '',
+    reason: terminated?.reason ?? pod.status?.reason ?? null,
+    message: terminated?.message ?? pod.status?.message ?? null,
+    exitCode: typeof terminated?.exitCode === 'number' ? terminated.exitCode : null,
+  };
+}
+
+/**
+ * Watch a Job and emit `job:status` with its pod counts until it
+ * completes, fails or is deleted. When pods fail, the latest failed pod
+ * is looked up so the UI can point at it. Returns the watch id.
+ */
+export async function watchJob(name: string, namespace: string, context: string): Promise<string> {
+  // Fail fast with NotFound rather than watching nothing
+  await getKubectlJson(context, ['get', 'job', name], namespace);
+
+  let lastState = '';
+  let lastFailed = 0;
+  let failing: FailingJobPod | null = null;
+  let latest: JobStatusEvent | null = null;
+
+  const emit = (event: JobStatusEvent) => {
+    const state = JSON.stringify({ ...event, watchId: undefined });
+    if (state !== lastState) {
+      lastState = state;
+      emitToRenderer('job:status', event);
+    }
+  };
+
+  const watchId = watchManager.startWatch(
+    { context, kind: 'job', name, namespace },
+    (event) => {
+      if (event.type !== 'ADDED' && event.type !== 'MODIFIED' && event.type !== 'DELETED') {
+        return;
+      }
+
+      const job = event.object;
+      const failedCondition = condition(job, 'Failed');
+      const next: JobStatusEvent = {
+        watchId,
+        name,
+        namespace,
+        state: jobState(job),
+        active: job?.status?.active ?? 0,
+        succeeded: job?.status?.succeeded ?? 0,
+        failed: job?.status?.failed ?? 0,
+        completions: job?.spec?.completions ?? null,
+        startTime: job?.status?.startTime ?? null,
+        completionTime: job?.status?.completionTime ?? null,
+        reason: failedCondition?.reason ?? null,
+        message: failedCondition?.message ?? null,
+        failingPod: failing,
+        deleted: event.type === 'DELETED',
+      };
+      latest = next;
+      const finished = next.deleted || next.state !== 'running';
+      if (finished) {
+        watchManager.stopWatch(watchId);
+      }
+
+      if (next.failed <= lastFailed || next.deleted) {
+        emit(next);
+        return;
+      }
+
+      // A new pod failed: find it before reporting, then report the newest state
+      lastFailed = next.failed;
+      failingPod(name, namespace, context)
+        .then((pod) => {
+          failing = pod;
+        })
+        .catch((error) => console.warn(`[Job ${namespace}/${name}] Could not read failed pods:`, error))
+        .finally(() => emit({ ...(latest ?? next), failingPod: failing }));
+    },
+    (message) => emitToRenderer('watch:error', watchId, message)
+  );
+
+  return watchId;
+}
+
+/**
+ * Run a CronJob now by creating a Job from its template, the way
+ * `kubectl create job --from=cronjob/NAME` does, and start watching it
+ */
+export async function triggerCronJob(
+  name: string,
+  namespace: string,
+  context: string
+): Promise<{ jobName: string; watchId: string }> {
+  const suffix = `-manual-${Math.floor(Date.now() / 1000).toString(36)}`;
+  const jobName = `${name.slice(0, MAX_JOB_NAME_LENGTH - suffix.length).replace(/-+$/, '')}${suffix}`;
+
+  const result = await runKubectl(context, ['create', 'job', jobName, `--from=cronjob/${name}`], namespace);
+  if (result.code !== 0) {
+    throw kubectlFailure(result, `Failed to trigger cronjob/${name}`);
+  }
+
+  const watchId = await watchJob(jobName, namespace, context);
+  console.log(`[Job ${namespace}/${jobName}] Created from cronjob/${name}`);
+  return { jobName, watchId };
+}
@@ -0,0 +1,90 @@
+import type { JsonLogEntry, JsonLogFieldMap } from '../common/kubeTypes';
+
+// Field names used by common loggers (zap, logrus, slog, bunyan, ECS)
+export const DEFAULT_JSON_LOG_FIELDS: JsonLogFieldMap = {
+  level: ['level', 'lvl', 'severity', 'log.level'],
+  message: ['msg', 'message', 'log'],
+  timestamp: ['time', 'ts', 'timestamp', '@timestamp'],
+};
+
+/**
+ * Field map for a stream: each given field replaces the default list,
+ * a single name is accepted too
+ */
+export function resolveJsonLogFields(
+  fields?: Partial<Record<keyof JsonLogFieldMap, string | string[]>>
+): JsonLogFieldMap {
+  const resolved: JsonLogFieldMap = { ...DEFAULT_JSON_LOG_FIELDS };
+  for (const key of Object.keys(DEFAULT_JSON_LOG_FIELDS) as Array<keyof JsonLogFieldMap>) {
+    const value = fields?.[key];
+    const names = (typeof value === 'string' ? [value] : Array.isArray(value) ? value : []).filter(
+      (name) => typeof name === 'string' && name
+    );
+    if (names.length > 0) {
+      resolved[key] = names;
+    }
+  }
+  return resolved;
+}
+
+function lookup(object: Record<string, any>, names: string[]): any {
+  for (const name of names) {
+    // A literal key with dots ("log.level") takes precedence over a path
+    if (object[name] !== undefined && object[name] !== null) {
+      return object[name];
+    }
+    const value = name.split('.').reduce<any>((current, part) => current?.[part], object);
+    if (value !== undefined && value !== null) {
+      return value;
+    }
+  }
+  return undefined;
+}
+
+function toTimestamp(value: any): string | null {
+  if (typeof value === 'number' && Number.isFinite(value)) {
+    // Epoch seconds (zap's float "ts") or milliseconds
+    const date = new Date(value < 1e12 ? value * 1000 : value);
+    return Number.isNaN(date.getTime()) ? null : date.toISOString();
+  }
+  if (typeof value === 'string' && value) {
+    const time = Date.parse(value);
+    return Number.isNaN(time) ? value : new Date(time).toISOString();
+  }
+  return null;
+}
+
+function toText(value: any): string | null {
+  if (value === undefined || value === null) {
+    return null;
+  }
+  return typeof value === 'string' ? value : JSON.stringify(value);
+}
+
+/**
+ * Parse one log line as a JSON object, falling back to a raw-text entry
+ */
+export function parseJsonLogLine(line: string, fields: JsonLogFieldMap): JsonLogEntry {
+  const trimmed = line.trim();
+  let object: any = null;
+  if (trimmed.startsWith('{')) {
+    try {
+      object = JSON.parse(trimmed);
+    } catch {
+      object = null;
+    }
+  }
+
+  if (!object || typeof object !== 'object' || Array.isArray(object)) {
+    return { raw: line, parsed: false, level: null, message: line, timestamp: null, fields: null };
+  }
+
+  return {
+    raw: line,
+    parsed: true,
+    level: toText(lookup(object, fields.level))?.toLowerCase() ?? null,
+    message: toText(lookup(object, fields.message)) ?? '',
+    timestamp: toTimestamp(lookup(object, fields.timestamp)),
+    fields: object,
+  };
+}
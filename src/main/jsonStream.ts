@@ -0,0 +1,64 @@
+/**
+ * Splits a stream of concatenated JSON objects (as printed by
+ * `kubectl get --watch -o json`) into individually parsed values.
+ * Chunks may end anywhere, including in the middle of a string.
+ */
+export class JsonObjectStream {
+  private buffer = '';
+  private scanned = 0;
+  private depth = 0;
+  private start = -1;
+  private inString = false;
+  private escaped = false;
+
+  push(chunk: string): unknown[] {
+    this.buffer += chunk;
+    const values: unknown[] = [];
+
+    for (let i = this.scanned; i < this.buffer.length; i += 1) {
+      const char = this.buffer[i];
+
+      if (this.inString) {
+        if (this.escaped) {
+          this.escaped = false;
+        } else if (char === '\\') {
+          this.escaped = true;
+        } else if (char === '"') {
+          this.inString = false;
+        }
+        continue;
+      }
+
+      if (char === '"') {
+        this.inString = true;
+      } else if (char === '{') {
+        if (this.depth === 0) {
+          this.start = i;
+        }
+        this.depth += 1;
+      } else if (char === '}' && this.depth > 0) {
+        this.depth -= 1;
+        if (this.depth === 0) {
+          try {
+            values.push(JSON.parse(this.buffer.slice(this.start, i + 1)));
+          } catch {
+            // Skip malformed documents rather than stalling the stream
+          }
+          this.start = -1;
+        }
+      }
+    }
+
+    // Keep only the unfinished document around
+    if (this.depth === 0) {
+      this.buffer = '';
+      this.scanned = 0;
+    } else {
+      this.buffer = this.buffer.slice(this.start);
+      this.start = 0;
+      this.scanned = this.buffer.length;
+    }
+
+    return values;
+  }
+}
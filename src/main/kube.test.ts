@@ -0,0 +1,125 @@
+import { userDataDir } from './testSupport';
+import assert from 'node:assert/strict';
+import { afterEach, describe, it } from 'node:test';
+import { chmodSync, mkdtempSync, writeFileSync } from 'fs';
+import path from 'path';
+import { getAppConfig } from './appConfig';
+import { isTransientKubectlError, kubectlFailure, runKubectl, runKubectlStdin, setKubectlForContext } from './kube';
+
+const fast = { baseDelayMs: 1, maxDelayMs: 1, jitter: false };
+
+/** A kubectl that prints `stderr` and fails for its first `failures` runs, then succeeds */
+function flakyKubectl(failures: number, stderr: string): string {
+  const directory = mkdtempSync(path.join(userDataDir, 'kubectl-'));
+  const binary = path.join(directory, 'kubectl');
+  writeFileSync(
+    binary,
+    [
+      '#!/bin/sh',
+      `count=$(cat "${directory}/runs" 2>/dev/null || echo 0)`,
+      'count=$((count + 1))',
+      `echo $count > "${directory}/runs"`,
+      `if [ $count -le ${failures} ]; then echo '${stderr}' >&2; exit 1; fi`,
+      'echo "run $count"',
+    ].join('\n')
+  );
+  chmodSync(binary, 0o755);
+  return binary;
+}
+
+/** A kubectl running `script` in place of the real thing */
+function scriptedKubectl(script: string): string {
+  const binary = path.join(mkdtempSync(path.join(userDataDir, 'kubectl-')), 'kubectl');
+  writeFileSync(binary, `#!/bin/sh\n${script}\n`);
+  chmodSync(binary, 0o755);
+  return binary;
+}
+
+describe('kubectl retries', () => {
+  it('tells transient failures from permanent ones', () => {
+    assert.equal(isTransientKubectlError('Unable to connect to the server: net/http: TLS handshake timeout'), true);
+    const unavailable = 'Error from server (ServiceUnavailable): the server is currently unable to handle the request';
+    assert.equal(isTransientKubectlError(unavailable), true);
+    assert.equal(isTransientKubectlError('Error from server (Forbidden): pods is forbidden: i/o timeout'), false);
+    assert.equal(isTransientKubectlError('Error from server (NotFound): pods "web-0" not found'), false);
+  });
+
+  it('retries a transient failure until it succeeds', async () => {
+    const binary = flakyKubectl(2, 'dial tcp 10.0.0.1:443: connect: connection refused');
+    const result = await runKubectl('dev', ['get', 'pods'], undefined, { binary, retry: { maxAttempts: 3, ...fast } });
+    assert.equal(result.code, 0);
+    assert.equal(result.stdout.trim(), 'run 3');
+    assert.equal(result.attempts, 3);
+  });
+
+  it('gives up after the last attempt and says how many it took', async () => {
+    const binary = flakyKubectl(5, 'unexpected EOF');
+    const result = await runKubectl('dev', ['get', 'pods'], undefined, { binary, retry: { maxAttempts: 2, ...fast } });
+    assert.equal(result.attempts, 2);
+    assert.match(kubectlFailure(result).message, /unexpected EOF \(failed after 2 attempts\)$/);
+  });
+
+  it('does not retry a permanent failure or a call without a retry policy', async () => {
+    const notFound = flakyKubectl(5, 'Error from server (NotFound): pods "web-0" not found');
+    const result = await runKubectl('dev', ['get', 'pod', 'web-0'], undefined, { binary: notFound, retry: true });
+    assert.equal(result.attempts, 1);
+
+    const flaky = flakyKubectl(1, 'unexpected EOF');
+    assert.equal((await runKubectl('dev', ['get', 'pods'], undefined, { binary: flaky })).code, 1);
+  });
+});
+
+describe('runKubectlStdin', () => {
+  afterEach(() => {
+    delete getAppConfig().kubectlPaths.dev;
+  });
+
+  it('pipes the manifest to kubectl and closes its stdin', async () => {
+    getAppConfig().kubectlPaths.dev = scriptedKubectl('echo "$*"; cat');
+    const manifest = 'apiVersion: v1\nkind: ConfigMap\nmetadata:\n  name: settings\n';
+    const result = await runKubectlStdin(['apply', '-f', '-'], manifest, 'dev', 'shop');
+    assert.equal(result.code, 0);
+    const [args, ...echoed] = result.stdout.split('\n');
+    assert.match(args, /apply -f -/);
+    assert.match(args, /-n shop/);
+    assert.equal(echoed.join('\n'), manifest);
+  });
+
+  it('does not deadlock when kubectl writes while the input is still going in', async () => {
+    getAppConfig().kubectlPaths.dev = scriptedKubectl('cat');
+    const manifest = `data: ${'x'.repeat(4 * 1024 * 1024)}\n`;
+    const result = await runKubectlStdin(['apply', '-f', '-'], manifest, 'dev');
+    assert.equal(result.stdout.length, manifest.length);
+  });
+
+  it('kills kubectl and fails with Timeout when it runs too long', async () => {
+    getAppConfig().kubectlPaths.dev = scriptedKubectl('exec sleep 5');
+    await assert.rejects(runKubectlStdin(['apply', '-f', '-'], 'kind: Pod\n', 'dev', undefined, 50), {
+      code: 'Timeout',
+    });
+  });
+
+  it('needs arguments', async () => {
+    await assert.rejects(runKubectlStdin([], 'kind: Pod\n', 'dev'), /Provide kubectl arguments/);
+  });
+});
+
+describe('setKubectlForContext', () => {
+  afterEach(() => {
+    getAppConfig().kubectlPaths = {};
+  });
+
+  it('pins a binary named kubectl', async () => {
+    const binary = scriptedKubectl('echo pinned');
+    await setKubectlForContext('old', binary);
+    assert.equal(getAppConfig().kubectlPaths.old, binary);
+  });
+
+  it('rejects a binary terminals could not run as kubectl', async () => {
+    const binary = path.join(mkdtempSync(path.join(userDataDir, 'kubectl-')), 'kubectl-1.24');
+    writeFileSync(binary, '#!/bin/sh\n');
+    chmodSync(binary, 0o755);
+    await assert.rejects(setKubectlForContext('old', binary), { code: 'KubectlMismatch' });
+    assert.equal(getAppConfig().kubectlPaths.old, undefined);
+  });
+});
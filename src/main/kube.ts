@@ -1,16 +1,102 @@
-import { spawn } from 'child_process';
-import { access, constants, readFile, readdir } from 'fs/promises';
+import { spawn, type ChildProcessWithoutNullStreams } from 'child_process';
+import { access, constants, readFile, readdir, stat } from 'fs/promises';
 import os from 'os';
 import path from 'path';
+import { randomUUID, X509Certificate } from 'crypto';
 import YAML from 'yaml';
-import type { KubeConfigSummary, KubectlResult, KubeConfigFile } from '../common/kubeTypes';
+import type {
+  AuthStatus,
+  ContextOverrides,
+  KubeConfigSummary,
+  KubectlResult,
+  KubeConfigFile,
+  KubeErrorCode,
+  OperationScope,
+  ResolvedContext,
+  RetryPolicy,
+} from '../common/kubeTypes';
 import { type KubeContext } from '../common/kubeTypes';
+import { operationRegistry } from './operations';
+import { getAppConfig, updateAppConfig } from './appConfig';
+import { assertCommandAllowed } from './commandPolicy';
+import { backoffDelay } from './reconnect';
+import { getCloudAuthEnv } from './cloudAuth';
+import { getContextTags } from './contextTags';
+import { kubectlPool } from './kubectlPool';
 
 const DEFAULT_RELATIVE_CONFIG = path.join('.kube', 'config');
 
 let currentKubeconfigPath: string | null = null;
 
-function resolveKubeconfigPath(): string {
+/**
+ * Error raised by the kubectl helpers, tagged with a coarse category
+ * so the renderer can react to e.g. NotFound without parsing messages
+ */
+export class KubeError extends Error {
+  readonly code: KubeErrorCode;
+
+  constructor(code: KubeErrorCode, message: string) {
+    super(message);
+    this.name = 'KubeError';
+    this.code = code;
+  }
+}
+
+export function classifyKubectlError(output: string): KubeErrorCode {
+  if (/Unable to connect to the server|connection refused|no such host|i\/o timeout|dial tcp/i.test(output)) {
+    return 'Unreachable';
+  }
+  if (/\(Forbidden\)|forbidden/i.test(output)) {
+    return 'Forbidden';
+  }
+  if (/Unauthorized|must be logged in/i.test(output)) {
+    return 'Unauthorized';
+  }
+  if (/\(NotFound\)|not found/i.test(output)) {
+    return 'NotFound';
+  }
+  // Optimistic-concurrency failure: "the object has been modified; please apply your changes to the latest version"
+  if (/\(Conflict\)|the object has been modified/i.test(output)) {
+    return 'Conflict';
+  }
+  return 'KubectlFailed';
+}
+
+/**
+ * Build a KubeError from a failed kubectl invocation
+ */
+export function kubectlFailure(result: KubectlResult, fallback = 'kubectl command failed'): KubeError {
+  const message = (result.stderr || result.stdout || fallback).trim();
+  const suffix = result.attempts && result.attempts > 1 ? ` (failed after ${result.attempts} attempts)` : '';
+  return new KubeError(classifyKubectlError(message), message + suffix);
+}
+
+// Errors seen while the API server restarts or the network blips
+const TRANSIENT_ERROR_PATTERN =
+  /TLS handshake timeout|unexpected EOF|connection refused|connection reset by peer|i\/o timeout|http2: server sent GOAWAY|server is currently unable to handle the request|etcdserver: request timed out|Too Many Requests/i;
+
+/**
+ * Whether a failed kubectl call is worth retrying. Auth and NotFound
+ * errors never are, even if the output also mentions a network error.
+ */
+export function isTransientKubectlError(output: string): boolean {
+  if (/\(Forbidden\)|forbidden|Unauthorized|must be logged in|\(NotFound\)|not found/i.test(output)) {
+    return false;
+  }
+  return TRANSIENT_ERROR_PATTERN.test(output);
+}
+
+export const DEFAULT_RETRY_POLICY: RetryPolicy = {
+  maxAttempts: 3,
+  baseDelayMs: 500,
+  maxDelayMs: 5000,
+  jitter: true,
+};
+
+/**
+ * Kubeconfig in use: the one picked in the app, else $KUBECONFIG, else ~/.kube/config
+ */
+export function resolveKubeconfigPath(): string {
   // If a specific config was set, use it
   if (currentKubeconfigPath) {
     return currentKubeconfigPath;
@@ -27,6 +113,23 @@ function resolveKubeconfigPath(): string {
   return path.join(os.homedir(), DEFAULT_RELATIVE_CONFIG);
 }
 
+/**
+ * Files kubectl merges, highest precedence first: the config picked in the
+ * app, else every $KUBECONFIG entry, else ~/.kube/config
+ */
+export function getKubeconfigSources(): string[] {
+  if (currentKubeconfigPath) {
+    return [currentKubeconfigPath];
+  }
+
+  const envPaths = (process.env.KUBECONFIG ?? '').split(path.delimiter).filter((entry) => entry.trim());
+  if (envPaths.length > 0) {
+    return Array.from(new Set(envPaths));
+  }
+
+  return [path.join(os.homedir(), DEFAULT_RELATIVE_CONFIG)];
+}
+
 export async function discoverKubeconfigs(): Promise<KubeConfigFile[]> {
   const configs: KubeConfigFile[] = [];
   const homeDir = os.homedir();
@@ -148,6 +251,7 @@ export async function loadKubeConfig(): Promise<KubeConfigSummary> {
       user: contextData.user,
       namespace: contextData.namespace,
       server: cluster?.server,
+      tags: getContextTags(ctx.name, cluster?.server ?? null),
     };
   });
 
@@ -165,6 +269,281 @@ export async function loadKubeConfig(): Promise<KubeConfigSummary> {
   };
 }
 
+async function readContextUser(
+  contextName: string,
+  kubeconfigPath: string
+): Promise<{ userName: string | null; user: Record<string, any> }> {
+  const config = YAML.parse(await readFile(kubeconfigPath, 'utf8')) ?? {};
+
+  const rawContexts: any[] = Array.isArray(config.contexts) ? config.contexts : [];
+  const rawUsers: any[] = Array.isArray(config.users) ? config.users : [];
+  const context = rawContexts.find((ctx) => ctx?.name === contextName);
+  if (!context) {
+    throw new KubeError('NotFound', `Context ${contextName} not found in kubeconfig`);
+  }
+
+  const userName: string | null = context.context?.user ?? null;
+  const user: Record<string, any> = rawUsers.find((entry) => entry?.name === userName)?.user ?? {};
+  return { userName, user };
+}
+
+/**
+ * The CA a context's cluster is verified against, from
+ * certificate-authority-data or the certificate-authority file; null when
+ * neither is set (system roots are used)
+ */
+export async function getClusterCa(
+  contextName: string,
+  kubeconfigPath: string = resolveKubeconfigPath()
+): Promise<Buffer | null> {
+  const config = YAML.parse(await readFile(kubeconfigPath, 'utf8')) ?? {};
+  const context = findNamed(config.contexts, contextName);
+  if (!context) {
+    throw new KubeError('NotFound', `Context ${contextName} not found in kubeconfig`);
+  }
+
+  const cluster: Record<string, any> = findNamed(config.clusters, context.context?.cluster)?.cluster ?? {};
+  if (typeof cluster['certificate-authority-data'] === 'string') {
+    return Buffer.from(cluster['certificate-authority-data'], 'base64');
+  }
+  if (typeof cluster['certificate-authority'] === 'string') {
+    return readFile(path.resolve(path.dirname(kubeconfigPath), cluster['certificate-authority']));
+  }
+  return null;
+}
+
+function jwtClaims(token: string): Record<string, any> | null {
+  const parts = token.split('.');
+  if (parts.length !== 3) {
+    return null;
+  }
+
+  try {
+    return JSON.parse(Buffer.from(parts[1], 'base64url').toString('utf8'));
+  } catch {
+    return null;
+  }
+}
+
+export function jwtExpiry(token: string): Date | null {
+  const payload = jwtClaims(token);
+  return typeof payload?.exp === 'number' ? new Date(payload.exp * 1000) : null;
+}
+
+async function certificateExpiry(user: Record<string, any>, baseDir: string): Promise<Date | null> {
+  let pem: Buffer;
+  if (typeof user['client-certificate-data'] === 'string') {
+    pem = Buffer.from(user['client-certificate-data'], 'base64');
+  } else {
+    pem = await readFile(path.resolve(baseDir, user['client-certificate']));
+  }
+  return new Date(new X509Certificate(pem).validTo);
+}
+
+/**
+ * Describe how a context authenticates and, when it can be read without
+ * running anything, when the credential expires. Exec plugins are only
+ * inspected, never executed.
+ */
+export async function getAuthStatus(
+  contextName: string,
+  kubeconfigPath: string = resolveKubeconfigPath()
+): Promise<AuthStatus> {
+  const { userName, user } = await readContextUser(contextName, kubeconfigPath);
+  const baseDir = path.dirname(kubeconfigPath);
+  const status: AuthStatus = {
+    context: contextName,
+    user: userName,
+    method: 'none',
+    execCommand: null,
+    authProvider: null,
+    expiresAt: null,
+    expiresInSeconds: null,
+    note: null,
+  };
+
+  let expiry: Date | null = null;
+
+  if (user.exec && typeof user.exec === 'object') {
+    status.method = 'exec';
+    status.execCommand = typeof user.exec.command === 'string' ? path.basename(user.exec.command) : null;
+    status.note = 'Exec plugins issue short-lived credentials on demand';
+  } else if (user['auth-provider'] && typeof user['auth-provider'] === 'object') {
+    const provider = user['auth-provider'];
+    status.authProvider = typeof provider.name === 'string' ? provider.name : null;
+    status.method = status.authProvider === 'oidc' ? 'oidc' : 'auth-provider';
+    const idToken = provider.config?.['id-token'];
+    if (typeof idToken === 'string') {
+      expiry = jwtExpiry(idToken);
+    }
+    if (typeof provider.config?.expiry === 'string') {
+      expiry = new Date(provider.config.expiry);
+    }
+  } else if (user['client-certificate-data'] || user['client-certificate']) {
+    status.method = 'client-certificate';
+    try {
+      expiry = await certificateExpiry(user, baseDir);
+    } catch {
+      status.note = 'The client certificate could not be read or parsed';
+    }
+  } else if (user.token || user.tokenFile) {
+    status.method = 'token';
+    let token: string | null = typeof user.token === 'string' ? user.token : null;
+    if (!token && typeof user.tokenFile === 'string') {
+      try {
+        token = (await readFile(path.resolve(baseDir, user.tokenFile), 'utf8')).trim();
+      } catch {
+        status.note = 'The token file could not be read';
+      }
+    }
+    if (token) {
+      expiry = jwtExpiry(token);
+      if (!expiry) {
+        status.note = 'The token has no readable expiry';
+      }
+    }
+  } else if (user.username || user.password) {
+    status.method = 'basic';
+  }
+
+  if (expiry && !Number.isNaN(expiry.getTime())) {
+    status.expiresAt = expiry.toISOString();
+    status.expiresInSeconds = Math.round((expiry.getTime() - Date.now()) / 1000);
+  }
+
+  return status;
+}
+
+function findNamed(entries: any, name: string): Record<string, any> | undefined {
+  const list: any[] = Array.isArray(entries) ? entries : [];
+  return list.find((entry) => entry?.name === name);
+}
+
+/**
+ * Who the kubeconfig says the user of a context is, without asking the
+ * server: the subject of a client certificate (CN is the username, O the
+ * groups), the claims of a bearer token, or a basic-auth username. Exec
+ * plugins and auth providers only reveal the kubeconfig user entry's name.
+ */
+export async function getKubeconfigIdentity(
+  contextName: string,
+  kubeconfigPath: string = resolveKubeconfigPath()
+): Promise<{ kubeconfigUser: string | null; username: string | null; groups: string[] }> {
+  const { userName, user } = await readContextUser(contextName, kubeconfigPath);
+  const baseDir = path.dirname(kubeconfigPath);
+  const identity = { kubeconfigUser: userName, username: null as string | null, groups: [] as string[] };
+
+  if (user['client-certificate-data'] || user['client-certificate']) {
+    try {
+      const pem =
+        typeof user['client-certificate-data'] === 'string'
+          ? Buffer.from(user['client-certificate-data'], 'base64')
+          : await readFile(path.resolve(baseDir, user['client-certificate']));
+      // One "KEY=value" per line
+      for (const line of new X509Certificate(pem).subject.split('\n')) {
+        const [key, ...rest] = line.split('=');
+        if (key === 'CN') {
+          identity.username = rest.join('=');
+        } else if (key === 'O') {
+          identity.groups.push(rest.join('='));
+        }
+      }
+    } catch {
+      // Unreadable certificate: nothing more to tell
+    }
+    return identity;
+  }
+
+  let token: string | null = typeof user.token === 'string' ? user.token : null;
+  if (!token && typeof user.tokenFile === 'string') {
+    token = await readFile(path.resolve(baseDir, user.tokenFile), 'utf8')
+      .then((contents) => contents.trim())
+      .catch(() => null);
+  }
+  const claims = token ? jwtClaims(token) : null;
+  if (claims) {
+    // Service account tokens carry the Kubernetes username as sub; OIDC ones usually an email
+    const sub = typeof claims.sub === 'string' ? claims.sub : null;
+    identity.username = sub?.startsWith('system:serviceaccount:')
+      ? sub
+      : claims.email ?? claims.preferred_username ?? sub;
+    if (Array.isArray(claims.groups)) {
+      identity.groups = claims.groups.filter((group: unknown) => typeof group === 'string');
+    }
+  } else if (typeof user.username === 'string') {
+    identity.username = user.username;
+  }
+
+  return identity;
+}
+
+/**
+ * Follow a context's cluster and user references the way kubectl does and
+ * report the effective server, user and namespace. Dangling references are
+ * errors that name the missing entry.
+ */
+export async function resolveContext(
+  contextName: string,
+  kubeconfigPath: string = resolveKubeconfigPath(),
+  overrides: ContextOverrides = {}
+): Promise<ResolvedContext> {
+  const config = YAML.parse(await readFile(kubeconfigPath, 'utf8')) ?? {};
+
+  const context = findNamed(config.contexts, contextName);
+  if (!context) {
+    throw new KubeError('NotFound', `Context "${contextName}" is not defined in ${kubeconfigPath}`);
+  }
+  const contextData: Record<string, any> = context.context ?? {};
+
+  const clusterName: string | undefined = overrides.cluster || contextData.cluster;
+  if (!clusterName) {
+    throw new KubeError('NotFound', `Context "${contextName}" does not set a cluster`);
+  }
+  const cluster = findNamed(config.clusters, clusterName);
+  if (!cluster) {
+    throw new KubeError(
+      'NotFound',
+      `Context "${contextName}" references cluster "${clusterName}", which is not defined under clusters in ${kubeconfigPath}`
+    );
+  }
+  const clusterData: Record<string, any> = cluster.cluster ?? {};
+  if (!clusterData.server) {
+    throw new KubeError('NotFound', `Cluster "${clusterName}" has no server URL`);
+  }
+
+  const userName: string | undefined = overrides.user || contextData.user;
+  if (!userName) {
+    throw new KubeError('NotFound', `Context "${contextName}" does not set a user`);
+  }
+  if (!findNamed(config.users, userName)) {
+    throw new KubeError(
+      'NotFound',
+      `Context "${contextName}" references user "${userName}", which is not defined under users in ${kubeconfigPath}`
+    );
+  }
+
+  const namespace: string = overrides.namespace || contextData.namespace || 'default';
+
+  return {
+    context: contextName,
+    kubeconfigPath,
+    isCurrent: config['current-context'] === contextName,
+    cluster: clusterName,
+    server: clusterData.server,
+    user: userName,
+    namespace,
+    sources: {
+      cluster: overrides.cluster ? 'override' : 'context',
+      user: overrides.user ? 'override' : 'context',
+      namespace: overrides.namespace ? 'override' : contextData.namespace ? 'context' : 'default',
+    },
+    tlsServerName: clusterData['tls-server-name'] ?? null,
+    proxyUrl: clusterData['proxy-url'] ?? null,
+    insecureSkipTlsVerify: clusterData['insecure-skip-tls-verify'] === true,
+    kubectlBinary: getKubectlBinary(contextName),
+  };
+}
+
 function tokenize(command: string): string[] {
   const tokens: string[] = [];
   let current = '';
@@ -216,7 +595,8 @@ function tokenize(command: string): string[] {
 
 export async function runKubectlCommand(
   contextName: string,
-  commandInput: string
+  commandInput: string,
+  view?: string
 ): Promise<KubectlResult> {
   if (!commandInput.trim()) {
     throw new Error('Command cannot be empty');
@@ -230,25 +610,288 @@ export async function runKubectlCommand(
     throw new Error('Provide kubectl arguments, for example: get pods');
   }
 
-  const finalArgs = ['--context', contextName, ...args];
-  const kubeconfigPath = resolveKubeconfigPath();
+  return runKubectl(contextName, args, undefined, { view });
+}
+
+/**
+ * Run kubectl with `stdin` piped in and closed (for `apply -f -`,
+ * `create -f -`, `--patch-file=/dev/stdin`). Output is read while the
+ * input is written, so large inputs can't deadlock.
+ */
+export async function runKubectlStdin(
+  args: string[],
+  stdin: string,
+  contextName: string,
+  namespace?: string,
+  timeoutMs?: number
+): Promise<KubectlResult> {
+  if (args.length === 0) {
+    throw new Error('Provide kubectl arguments, for example: apply -f -');
+  }
+
+  return runKubectl(contextName, args, namespace, { stdin, timeoutMs });
+}
+
+/**
+ * kubectl binary for a context: the per-context override when configured,
+ * otherwise whatever `kubectl` resolves to on PATH
+ */
+export function getKubectlBinary(contextName?: string): string {
+  if (contextName) {
+    const configured = getAppConfig().kubectlPaths[contextName];
+    if (configured) {
+      return configured;
+    }
+  }
+  return 'kubectl';
+}
+
+export function getKubectlForContext(contextName: string): string | null {
+  return getAppConfig().kubectlPaths[contextName] ?? null;
+}
 
-  return executeKubectl(finalArgs, kubeconfigPath);
+/**
+ * Pin a kubectl binary for a context (e.g. an older client for an old
+ * cluster). An empty path clears the override. The binary must be named
+ * kubectl (a symlink will do) so terminals for the context can put it on
+ * PATH as `kubectl`.
+ */
+export async function setKubectlForContext(contextName: string, binaryPath: string): Promise<void> {
+  if (!contextName) {
+    throw new Error('Context name is required');
+  }
+
+  const trimmed = binaryPath?.trim() ?? '';
+  if (trimmed) {
+    try {
+      const info = await stat(trimmed);
+      if (!info.isFile()) {
+        throw new Error('not a file');
+      }
+      await access(trimmed, constants.X_OK);
+    } catch {
+      throw new Error(`${trimmed} is not an executable file`);
+    }
+    if (path.basename(trimmed, path.extname(trimmed)) !== 'kubectl') {
+      throw new KubeError(
+        'KubectlMismatch',
+        `${trimmed} is not named kubectl, so terminals for ${contextName} couldn't run it as "kubectl"; ` +
+          'pin a symlink named kubectl instead'
+      );
+    }
+  }
+
+  updateAppConfig((config) => {
+    if (trimmed) {
+      config.kubectlPaths[contextName] = trimmed;
+    } else {
+      delete config.kubectlPaths[contextName];
+    }
+  });
 }
 
-async function executeKubectl(args: string[], kubeconfigPath?: string): Promise<KubectlResult> {
+export function buildKubectlArgs(
+  contextName: string,
+  args: string[],
+  namespace?: string,
+  policyCommand: string | string[] = ['kubectl', ...args].join(' ')
+): string[] {
+  // Every kubectl invocation (run, spawn, PTY) passes through here
+  assertCommandAllowed('kubectl', policyCommand);
+
+  const finalArgs = ['--context', contextName];
+  if (namespace) {
+    finalArgs.push('-n', namespace);
+  }
+  return [...finalArgs, ...args];
+}
+
+/**
+ * Run kubectl with pre-split arguments against a context
+ */
+export interface KubectlRunOptions {
+  /** Written to kubectl's stdin, which is then closed (for `-f -`) */
+  stdin?: string;
+  /** UI view that requested the call, for scoped cancellation */
+  view?: string;
+  /** Program to run instead of the context's kubectl (e.g. a kubectl plugin) */
+  binary?: string;
+  /** What the command policy checks instead of `kubectl <args>`, for a binary that isn't kubectl */
+  policyCommand?: string | string[];
+  /** Extra environment variables for the child process */
+  env?: Record<string, string>;
+  /** Retry transient failures; `true` uses DEFAULT_RETRY_POLICY */
+  retry?: boolean | Partial<RetryPolicy>;
+  /** Kubeconfig to use instead of the active one */
+  kubeconfig?: string;
+  /** Kill kubectl and fail with Timeout after this long */
+  timeoutMs?: number;
+}
+
+export async function runKubectl(
+  contextName: string,
+  args: string[],
+  namespace?: string,
+  options: KubectlRunOptions = {}
+): Promise<KubectlResult> {
+  const kubeconfigPath = options.kubeconfig ?? resolveKubeconfigPath();
+  const runOptions: KubectlRunOptions = {
+    ...options,
+    env: { ...options.env, ...getCloudAuthEnv(contextName, kubeconfigPath, { ...process.env, ...options.env }) },
+  };
+  const run = () =>
+    executeKubectl(
+      buildKubectlArgs(contextName, args, namespace, options.policyCommand),
+      kubeconfigPath,
+      runOptions,
+      { context: contextName, namespace, view: options.view },
+      options.binary ?? getKubectlBinary(contextName)
+    );
+
+  if (!options.retry) {
+    return run();
+  }
+
+  const policy: RetryPolicy = {
+    ...DEFAULT_RETRY_POLICY,
+    ...(options.retry === true ? {} : options.retry),
+  };
+
+  for (let attempt = 1; ; attempt++) {
+    const result = await run();
+    if (
+      result.code === 0 ||
+      attempt >= policy.maxAttempts ||
+      !isTransientKubectlError(result.stderr || result.stdout)
+    ) {
+      return { ...result, attempts: attempt };
+    }
+
+    const delay = backoffDelay(policy, attempt);
+    console.warn(
+      `[kubectl] Transient failure (attempt ${attempt}/${policy.maxAttempts}), retrying in ${Math.round(delay)}ms`
+    );
+    await new Promise((resolve) => setTimeout(resolve, delay));
+  }
+}
+
+/**
+ * Run a kubectl read command with `-o json` and return the parsed output.
+ * Reads are idempotent, so transient failures are retried.
+ */
+export async function getKubectlJson<T = any>(
+  contextName: string,
+  args: string[],
+  namespace?: string
+): Promise<T> {
+  const result = await runKubectl(contextName, [...args, '-o', 'json'], namespace, { retry: true });
+
+  if (result.code !== 0) {
+    throw kubectlFailure(result);
+  }
+
+  try {
+    return JSON.parse(result.stdout) as T;
+  } catch {
+    throw new KubeError('KubectlFailed', 'kubectl returned invalid JSON');
+  }
+}
+
+/**
+ * Environment for kubectl child processes, pointing at the active kubeconfig
+ */
+export function getKubectlEnv(): Record<string, string> {
+  return { KUBECONFIG: resolveKubeconfigPath() };
+}
+
+/**
+ * Spawn a long-running kubectl process (watch, logs -f, port-forward, ...).
+ * Callers own the child and must handle its 'error' event. Throws a
+ * StreamLimit KubeError when the pool's maxStreaming processes are
+ * already running.
+ */
+export function spawnKubectl(
+  contextName: string,
+  args: string[],
+  namespace?: string
+): ChildProcessWithoutNullStreams {
+  if (!kubectlPool.tryStartStreaming()) {
+    const { maxStreaming } = kubectlPool.getStats();
+    throw new KubeError(
+      'StreamLimit',
+      `Too many long-running kubectl processes (${maxStreaming}); stop a watch, log follow or port-forward first`
+    );
+  }
+  const child = spawn(getKubectlBinary(contextName), buildKubectlArgs(contextName, args, namespace), {
+    env: { ...process.env, ...getCloudAuthEnv(contextName, resolveKubeconfigPath()), ...getKubectlEnv() },
+  });
+  kubectlPool.trackStreaming(child);
+  return child;
+}
+
+/**
+ * Run kubectl once a pool slot is free, so bursts of calls queue instead
+ * of all spawning at once
+ */
+async function executeKubectl(
+  args: string[],
+  kubeconfigPath?: string,
+  options: KubectlRunOptions = {},
+  scope?: OperationScope,
+  binary = 'kubectl'
+): Promise<KubectlResult> {
+  const release = await kubectlPool.acquire();
+  if (!release) {
+    const { queueTimeoutMs } = kubectlPool.getStats();
+    throw new KubeError(
+      'PoolTimeout',
+      `Too many kubectl commands are running; gave up after waiting ${queueTimeoutMs}ms for a free slot`
+    );
+  }
+
+  try {
+    return await runKubectlProcess(args, kubeconfigPath, options, scope, binary);
+  } finally {
+    release();
+  }
+}
+
+function runKubectlProcess(
+  args: string[],
+  kubeconfigPath: string | undefined,
+  options: KubectlRunOptions,
+  scope: OperationScope | undefined,
+  binary: string
+): Promise<KubectlResult> {
   return new Promise<KubectlResult>((resolve, reject) => {
-    const env = { ...process.env };
+    const env = { ...process.env, ...options.env };
     if (kubeconfigPath) {
       env.KUBECONFIG = kubeconfigPath;
     }
 
-    const child = spawn('kubectl', args, {
+    const child = spawn(binary, args, {
       env,
     });
 
     let stdout = '';
     let stderr = '';
+    let cancelled = false;
+    let timedOut = false;
+    const operationId = randomUUID();
+
+    const timer = options.timeoutMs
+      ? setTimeout(() => {
+          timedOut = true;
+          child.kill();
+        }, options.timeoutMs)
+      : null;
+
+    if (scope) {
+      operationRegistry.register(operationId, 'kubectl', scope, () => {
+        cancelled = true;
+        child.kill();
+      });
+    }
 
     child.stdout.on('data', (chunk) => {
       stdout += chunk.toString();
@@ -259,6 +902,10 @@ async function executeKubectl(args: string[], kubeconfigPath?: string): Promise<
     });
 
     child.once('error', (error: NodeJS.ErrnoException) => {
+      operationRegistry.unregister(operationId);
+      if (timer) {
+        clearTimeout(timer);
+      }
       if (error?.code === 'ENOENT') {
         reject(
           new Error(
@@ -271,12 +918,32 @@ async function executeKubectl(args: string[], kubeconfigPath?: string): Promise<
     });
 
     child.once('close', (code) => {
+      operationRegistry.unregister(operationId);
+      if (timer) {
+        clearTimeout(timer);
+      }
+      if (timedOut) {
+        reject(new KubeError('Timeout', `kubectl did not finish within ${options.timeoutMs}ms`));
+        return;
+      }
+      if (cancelled) {
+        reject(new KubeError('Cancelled', 'kubectl command was cancelled'));
+        return;
+      }
       resolve({
         stdout,
         stderr,
         code,
       });
     });
+
+    // stdout/stderr are already being drained, so large stdin can't deadlock
+    if (options.stdin !== undefined) {
+      child.stdin.on('error', () => {
+        // kubectl may exit before consuming all input; the exit code reports why
+      });
+      child.stdin.end(options.stdin);
+    }
   });
 }
 
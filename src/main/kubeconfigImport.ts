@@ -0,0 +1,232 @@
+import { copyFile, mkdir, readFile, stat, writeFile } from 'fs/promises';
+import path from 'path';
+import YAML from 'yaml';
+import type { KubeconfigImportEntry, KubeconfigImportResult } from '../common/kubeTypes';
+import { writeFileAtomically } from './appConfig';
+import { resolveKubeconfigPath } from './kube';
+import { resolvePaths } from './mergedKubeconfig';
+
+type NamedList = 'clusters' | 'contexts' | 'users';
+
+interface PlannedList {
+  entries: KubeconfigImportEntry[];
+  // Entries to append to the target, already renamed
+  additions: any[];
+}
+
+function namedEntries(config: any, list: NamedList): any[] {
+  return Array.isArray(config?.[list]) ? config[list].filter((entry: any) => entry?.name) : [];
+}
+
+// Key order doesn't make two entries different
+function canonical(value: any): string {
+  if (Array.isArray(value)) {
+    return `[${value.map(canonical).join(',')}]`;
+  }
+  if (value && typeof value === 'object') {
+    return `{${Object.keys(value)
+      .sort()
+      .map((key) => `${JSON.stringify(key)}:${canonical(value[key])}`)
+      .join(',')}}`;
+  }
+  return JSON.stringify(value ?? null);
+}
+
+function parseSource(sourcePath: string, contents: string): any {
+  const document = YAML.parseDocument(contents);
+  if (document.errors.length > 0) {
+    throw new Error(`${sourcePath} is not valid YAML: ${document.errors[0].message}`);
+  }
+  const config = document.toJS();
+  if (!config || typeof config !== 'object' || Array.isArray(config)) {
+    throw new Error(`${sourcePath} is not a kubeconfig`);
+  }
+  if (config.kind !== undefined && config.kind !== 'Config') {
+    throw new Error(`${sourcePath} is a ${config.kind}, not a kubeconfig`);
+  }
+
+  const contexts = namedEntries(config, 'contexts');
+  if (contexts.length === 0) {
+    throw new Error(`${sourcePath} defines no contexts`);
+  }
+  const clusters = new Set(namedEntries(config, 'clusters').map((entry) => entry.name));
+  const users = new Set(namedEntries(config, 'users').map((entry) => entry.name));
+  for (const entry of contexts) {
+    const { cluster, user } = entry.context ?? {};
+    if (!cluster || !clusters.has(cluster)) {
+      throw new Error(`Context "${entry.name}" in ${sourcePath} references an undefined cluster "${cluster ?? ''}"`);
+    }
+    if (!user || !users.has(user)) {
+      throw new Error(`Context "${entry.name}" in ${sourcePath} references an undefined user "${user ?? ''}"`);
+    }
+  }
+  return config;
+}
+
+/**
+ * Work out what each source entry becomes in the target: kept as is when
+ * the target has nothing by that name, skipped when the target already has
+ * an identical entry, and otherwise renamed with a numeric suffix so the
+ * existing entry stays untouched
+ */
+function planList(
+  list: NamedList,
+  sourceEntries: any[],
+  targetEntries: any[],
+  prepare: (entry: any) => any
+): PlannedList {
+  const existing = new Map<string, string>(
+    targetEntries.map((entry) => [entry.name, canonical(entry[list.slice(0, -1)])])
+  );
+  const taken = new Set(existing.keys());
+  const planned: PlannedList = { entries: [], additions: [] };
+
+  for (const source of sourceEntries) {
+    const entry = prepare(source);
+    const body = canonical(entry[list.slice(0, -1)]);
+    if (existing.get(entry.name) === body) {
+      planned.entries.push({ name: source.name, importedAs: source.name, action: 'unchanged' });
+      continue;
+    }
+
+    let importedAs = entry.name;
+    for (let suffix = 2; taken.has(importedAs); suffix++) {
+      importedAs = `${entry.name}-${suffix}`;
+    }
+    taken.add(importedAs);
+    planned.entries.push({
+      name: source.name,
+      importedAs,
+      action: importedAs === source.name ? 'added' : 'renamed',
+    });
+    planned.additions.push({ ...entry, name: importedAs });
+  }
+  return planned;
+}
+
+function appendEntries(document: YAML.Document, list: NamedList, additions: any[]): void {
+  if (additions.length === 0) {
+    return;
+  }
+  let sequence = document.get(list);
+  if (!YAML.isSeq(sequence)) {
+    document.set(list, document.createNode([]));
+    sequence = document.get(list);
+  }
+  for (const entry of additions) {
+    (sequence as YAML.YAMLSeq).add(document.createNode(entry));
+  }
+}
+
+/**
+ * Import the clusters, users and contexts of a downloaded kubeconfig into
+ * the active one. The source is validated first (every context's cluster
+ * and user must be defined in it). Entries the target already has
+ * identically are skipped; a name that is taken by something different
+ * gets a "-2" style suffix, with the imported contexts pointing at the
+ * renamed cluster and user. Relative certificate and exec paths are made
+ * absolute so they still resolve from the target's directory.
+ * current-context is only set when the target has none. Without `merge`
+ * nothing is written and the result is a preview. The target is backed up
+ * next to itself and written atomically; comments are kept.
+ */
+export async function importKubeconfig(sourcePath: string, merge: boolean): Promise<KubeconfigImportResult> {
+  const source = path.resolve(sourcePath);
+  const target = resolveKubeconfigPath();
+  if (source === path.resolve(target)) {
+    throw new Error(`${source} is the kubeconfig in use`);
+  }
+
+  let sourceContents: string;
+  try {
+    sourceContents = await readFile(source, 'utf8');
+  } catch (error) {
+    throw new Error(`Failed to read ${source}: ${error instanceof Error ? error.message : error}`);
+  }
+  const config = parseSource(source, sourceContents);
+  const baseDir = path.dirname(source);
+
+  let targetContents: string | null = null;
+  try {
+    targetContents = await readFile(target, 'utf8');
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException)?.code !== 'ENOENT') {
+      throw new Error(`Failed to read ${target}: ${error instanceof Error ? error.message : error}`);
+    }
+  }
+  const document = YAML.parseDocument(targetContents ?? '');
+  if (document.errors.length > 0) {
+    throw new Error(`${target} is not valid YAML: ${document.errors[0].message}`);
+  }
+  if (!YAML.isMap(document.contents)) {
+    if (document.contents !== null && targetContents?.trim()) {
+      throw new Error(`${target} is not a kubeconfig`);
+    }
+    document.contents = document.createNode({ apiVersion: 'v1', kind: 'Config' }) as any;
+  }
+  const existing = document.toJS() ?? {};
+
+  // The source was parsed just for this, so its entries can be changed in place
+  const clusters = planList('clusters', namedEntries(config, 'clusters'), namedEntries(existing, 'clusters'), (entry) =>
+    resolvePaths('clusters', entry, baseDir)
+  );
+  const users = planList('users', namedEntries(config, 'users'), namedEntries(existing, 'users'), (entry) =>
+    resolvePaths('users', entry, baseDir)
+  );
+  const renamed = (planned: PlannedList, name: string) =>
+    planned.entries.find((entry) => entry.name === name)?.importedAs ?? name;
+  const contexts = planList(
+    'contexts',
+    namedEntries(config, 'contexts'),
+    namedEntries(existing, 'contexts'),
+    (entry) => ({
+      ...entry,
+      context: {
+        ...entry.context,
+        cluster: renamed(clusters, entry.context.cluster),
+        user: renamed(users, entry.context.user),
+      },
+    })
+  );
+
+  const hasCurrent = typeof existing['current-context'] === 'string' && existing['current-context'] !== '';
+  const sourceCurrent = contexts.entries.find((entry) => entry.name === config['current-context']);
+  const currentContext = hasCurrent ? null : (sourceCurrent ?? contexts.entries[0]).importedAs;
+
+  const result: KubeconfigImportResult = {
+    source,
+    target,
+    merged: false,
+    contexts: contexts.entries,
+    clusters: clusters.entries,
+    users: users.entries,
+    currentContext,
+    backup: null,
+  };
+  const additions = contexts.additions.length + clusters.additions.length + users.additions.length;
+  if (!merge || (additions === 0 && !currentContext)) {
+    return result;
+  }
+
+  appendEntries(document, 'clusters', clusters.additions);
+  appendEntries(document, 'users', users.additions);
+  appendEntries(document, 'contexts', contexts.additions);
+  if (currentContext) {
+    document.set('current-context', currentContext);
+  }
+
+  if (targetContents === null) {
+    await mkdir(path.dirname(target), { recursive: true });
+    // Holds credentials
+    await writeFile(target, document.toString(), { encoding: 'utf8', mode: 0o600 });
+  } else {
+    const stamp = new Date().toISOString().replace(/[:.]/g, '-');
+    result.backup = `${target}.backup-${stamp}`;
+    await copyFile(target, result.backup);
+    writeFileAtomically(target, document.toString(), (await stat(target)).mode);
+  }
+  result.merged = true;
+
+  console.log(`[Kubeconfig] Imported ${contexts.additions.length} context(s) from ${source} into ${target}`);
+  return result;
+}
@@ -0,0 +1,192 @@
+import { copyFile, readFile, stat } from 'fs/promises';
+import YAML from 'yaml';
+import type {
+  KubeconfigProblem,
+  KubeconfigRepairResult,
+  KubeconfigRepairStrategy,
+  KubeconfigValidation,
+} from '../common/kubeTypes';
+import { writeFileAtomically } from './appConfig';
+import { getKubeconfigSources } from './kube';
+
+interface SourceFile {
+  path: string;
+  config: any;
+}
+
+const REPAIR_STRATEGIES: KubeconfigRepairStrategy[] = ['clear-current-context', 'prune-dangling-contexts'];
+
+function namedEntries(config: any, list: string): any[] {
+  return Array.isArray(config?.[list]) ? config[list] : [];
+}
+
+async function readSources(): Promise<SourceFile[]> {
+  const files: SourceFile[] = [];
+  for (const source of getKubeconfigSources()) {
+    try {
+      files.push({ path: source, config: YAML.parse(await readFile(source, 'utf8')) ?? {} });
+    } catch (error) {
+      // Missing files are skipped, as kubectl does
+      if ((error as NodeJS.ErrnoException)?.code !== 'ENOENT') {
+        throw new Error(`Failed to read ${source}: ${error instanceof Error ? error.message : error}`);
+      }
+    }
+  }
+  return files;
+}
+
+function validate(files: SourceFile[]): KubeconfigValidation {
+  // References resolve across every merged file, like kubectl
+  const names = (list: string) =>
+    new Set(files.flatMap((file) => namedEntries(file.config, list).map((entry) => entry?.name)));
+  const clusters = names('clusters');
+  const users = names('users');
+  const contexts = names('contexts');
+  const problems: KubeconfigProblem[] = [];
+
+  // The first file with a non-empty current-context decides it
+  const currentFile = files.find(
+    (file) => typeof file.config['current-context'] === 'string' && file.config['current-context']
+  );
+  const currentContext: string | null = currentFile?.config['current-context'] ?? null;
+  if (currentFile && currentContext && !contexts.has(currentContext)) {
+    problems.push({
+      type: 'missing-current-context',
+      file: currentFile.path,
+      context: currentContext,
+      reference: null,
+      message: `current-context "${currentContext}" is not defined in any kubeconfig file`,
+    });
+  }
+
+  const seen: Set<string> = new Set();
+  for (const file of files) {
+    for (const entry of namedEntries(file.config, 'contexts')) {
+      const name: string | undefined = entry?.name;
+      // Only the first definition of a name is used
+      if (!name || seen.has(name)) {
+        continue;
+      }
+      seen.add(name);
+
+      const cluster: string | undefined = entry.context?.cluster;
+      const user: string | undefined = entry.context?.user;
+      if (!cluster || !clusters.has(cluster)) {
+        problems.push({
+          type: 'dangling-cluster',
+          file: file.path,
+          context: name,
+          reference: cluster ?? null,
+          message: cluster
+            ? `Context "${name}" references cluster "${cluster}", which is not defined`
+            : `Context "${name}" does not set a cluster`,
+        });
+      }
+      if (!user || !users.has(user)) {
+        problems.push({
+          type: 'dangling-user',
+          file: file.path,
+          context: name,
+          reference: user ?? null,
+          message: user
+            ? `Context "${name}" references user "${user}", which is not defined`
+            : `Context "${name}" does not set a user`,
+        });
+      }
+    }
+  }
+
+  return {
+    files: files.map((file) => file.path),
+    currentContext,
+    valid: problems.length === 0,
+    problems,
+  };
+}
+
+/**
+ * Check the kubeconfig kubectl will use: that current-context names a
+ * defined context and that every context's cluster and user exist. These
+ * otherwise surface as cryptic failures on the first kubectl call.
+ */
+export async function validateKubeconfig(): Promise<KubeconfigValidation> {
+  return validate(await readSources());
+}
+
+/**
+ * Fix what validation reported. `clear-current-context` empties a
+ * current-context that names a missing context; `prune-dangling-contexts`
+ * also removes contexts whose cluster or user is missing (clearing
+ * current-context if it pointed at one). Each changed file is backed up
+ * next to itself first. Comments and formatting are kept.
+ */
+export async function repairKubeconfig(strategy: KubeconfigRepairStrategy): Promise<KubeconfigRepairResult> {
+  if (!REPAIR_STRATEGIES.includes(strategy)) {
+    throw new Error(`Unknown repair strategy "${strategy}"`);
+  }
+
+  const sources = await readSources();
+  const before = validate(sources);
+  const clearContextIn: Map<string, string> = new Map();
+  const pruneIn: Map<string, Set<string>> = new Map();
+
+  for (const problem of before.problems) {
+    if (problem.type === 'missing-current-context') {
+      clearContextIn.set(problem.file, problem.context ?? '');
+    } else if (strategy === 'prune-dangling-contexts' && problem.context) {
+      const names = pruneIn.get(problem.file) ?? new Set();
+      names.add(problem.context);
+      pruneIn.set(problem.file, names);
+    }
+  }
+  // Pruning the current context leaves current-context dangling as well
+  const pruned = new Set(Array.from(pruneIn.values()).flatMap((names) => Array.from(names)));
+  if (before.currentContext && pruned.has(before.currentContext)) {
+    const currentFile = sources.find((file) => file.config['current-context'] === before.currentContext);
+    if (currentFile) {
+      clearContextIn.set(currentFile.path, before.currentContext);
+    }
+  }
+
+  const changes: string[] = [];
+  const backups: string[] = [];
+  const files = new Set([...clearContextIn.keys(), ...pruneIn.keys()]);
+  const stamp = new Date().toISOString().replace(/[:.]/g, '-');
+
+  for (const filePath of files) {
+    const document = YAML.parseDocument(await readFile(filePath, 'utf8'));
+    if (document.errors.length > 0) {
+      throw new Error(`${filePath} is not valid YAML: ${document.errors[0].message}`);
+    }
+
+    const names = pruneIn.get(filePath);
+    if (names) {
+      const contexts = document.get('contexts');
+      if (YAML.isSeq(contexts)) {
+        contexts.items = contexts.items.filter((item) => {
+          const name = YAML.isMap(item) ? item.get('name') : undefined;
+          if (typeof name === 'string' && names.has(name)) {
+            changes.push(`Removed context "${name}" from ${filePath}`);
+            return false;
+          }
+          return true;
+        });
+      }
+    }
+
+    const cleared = clearContextIn.get(filePath);
+    if (cleared !== undefined) {
+      document.set('current-context', '');
+      changes.push(`Cleared current-context "${cleared}" in ${filePath}`);
+    }
+
+    const backupPath = `${filePath}.backup-${stamp}`;
+    await copyFile(filePath, backupPath);
+    backups.push(backupPath);
+    // Kubeconfigs hold credentials, so the rewrite keeps the file's permissions
+    writeFileAtomically(filePath, document.toString(), (await stat(filePath)).mode);
+    console.log(`[Kubeconfig] Repaired ${filePath} (backup at ${backupPath})`);
+  }
+
+  return { strategy, changes, backups, report: await validateKubeconfig() };
+}
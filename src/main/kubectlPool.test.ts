@@ -0,0 +1,103 @@
+import { FakeChild } from './testSupport';
+import assert from 'node:assert/strict';
+import { afterEach, describe, it } from 'node:test';
+import { getAppConfig } from './appConfig';
+import { spawnKubectl } from './kube';
+import { kubectlPool } from './kubectlPool';
+import { watchManager } from './watch';
+
+const defaults = { ...getAppConfig().kubectlPool };
+
+afterEach(() => {
+  getAppConfig().kubectlPool = { ...defaults };
+});
+
+describe('kubectl pool', () => {
+  it('queues calls past the limit and starts them in order as slots free up', async () => {
+    getAppConfig().kubectlPool = { ...defaults, maxConcurrent: 2, queueTimeoutMs: 1000 };
+    const first = await kubectlPool.acquire();
+    const second = await kubectlPool.acquire();
+    const order: string[] = [];
+    const third = kubectlPool.acquire().then((release) => {
+      order.push('third');
+      return release;
+    });
+    const fourth = kubectlPool.acquire().then((release) => {
+      order.push('fourth');
+      return release;
+    });
+    await new Promise((resolve) => setImmediate(resolve));
+    assert.deepEqual([kubectlPool.getStats().active, kubectlPool.getStats().queued], [2, 2]);
+    assert.deepEqual(order, []);
+
+    first?.();
+    const thirdRelease = await third;
+    assert.deepEqual(order, ['third']);
+    assert.equal(kubectlPool.getStats().active, 2);
+
+    second?.();
+    const fourthRelease = await fourth;
+    assert.deepEqual(order, ['third', 'fourth']);
+    thirdRelease?.();
+    fourthRelease?.();
+    assert.deepEqual([kubectlPool.getStats().active, kubectlPool.getStats().queued], [0, 0]);
+  });
+
+  it('gives up on a call that waits past the queue timeout', async () => {
+    getAppConfig().kubectlPool = { ...defaults, maxConcurrent: 1, queueTimeoutMs: 20 };
+    const timeouts = kubectlPool.getStats().timeouts;
+    const held = await kubectlPool.acquire();
+    assert.equal(await kubectlPool.acquire(), null);
+    assert.equal(kubectlPool.getStats().timeouts, timeouts + 1);
+    assert.equal(kubectlPool.getStats().queued, 0);
+    held?.();
+  });
+
+  it('refuses long-running processes past their limit', async () => {
+    getAppConfig().kubectlPool = { ...defaults, maxStreaming: 2 };
+    const children = [new FakeChild(), new FakeChild()];
+    for (const child of children) {
+      assert.equal(kubectlPool.tryStartStreaming(), true);
+      kubectlPool.trackStreaming(child as any);
+    }
+
+    const refused = kubectlPool.getStats().streamsRefused;
+    assert.throws(() => spawnKubectl('dev', ['logs', '-f', 'web-0']), { code: 'StreamLimit' });
+    assert.equal(kubectlPool.getStats().streamsRefused, refused + 1);
+
+    children[0].exit(0);
+    await new Promise((resolve) => children[0].once('close', resolve));
+    assert.equal(kubectlPool.tryStartStreaming(), true);
+    children[1].exit(0);
+    await new Promise((resolve) => children[1].once('close', resolve));
+    assert.equal(kubectlPool.getStats().streaming, 0);
+  });
+
+  it('fails a watch started at the limit through its error handler', async () => {
+    getAppConfig().kubectlPool = { ...defaults, maxStreaming: 1 };
+    const held = new FakeChild();
+    kubectlPool.trackStreaming(held as any);
+
+    const failed = new Promise<string>((resolve) => {
+      const id = watchManager.startWatch({ context: 'dev', kind: 'pods' }, () => undefined, (message) => {
+        assert.equal(watchManager.hasWatch(id), false);
+        resolve(message);
+      });
+    });
+    assert.match(await failed, /Too many long-running kubectl processes/);
+    held.exit(0);
+  });
+
+  it('only takes known, valid limits', () => {
+    assert.throws(() => kubectlPool.setLimits({ maxConcurrent: 4, extra: 1 } as any), /Unknown kubectl pool limit/);
+    assert.throws(() => kubectlPool.setLimits({ maxStreaming: -1 }), /maxStreaming must be a whole number/);
+    assert.throws(() => kubectlPool.setLimits({ queueTimeoutMs: '10' as any }), /positive whole number/);
+    assert.equal(getAppConfig().kubectlPool.maxConcurrent, defaults.maxConcurrent);
+
+    const stats = kubectlPool.setLimits({ maxConcurrent: 4 });
+    assert.equal(stats.maxConcurrent, 4);
+    assert.equal(stats.queueTimeoutMs, defaults.queueTimeoutMs);
+    const keys = Object.keys(getAppConfig().kubectlPool).sort();
+    assert.deepEqual(keys, ['maxConcurrent', 'maxStreaming', 'queueTimeoutMs']);
+  });
+});
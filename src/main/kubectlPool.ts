@@ -0,0 +1,145 @@
+import type { ChildProcess } from 'child_process';
+import type { KubectlPoolLimits, KubectlPoolStats } from '../common/kubeTypes';
+import { getAppConfig, updateAppConfig } from './appConfig';
+
+interface Waiter {
+  grant: (release: () => void) => void;
+  timer: NodeJS.Timeout;
+}
+
+/**
+ * Bounds how many one-shot kubectl processes run at once; the rest wait in
+ * order of arrival, so a burst of fetches can't fork hundreds of
+ * processes. Long-running processes have their own limit: they hold a
+ * slot for their whole life, so queueing them behind one-shot calls (or
+ * the other way round) could starve everything else, and one past the
+ * limit is refused rather than queued.
+ */
+class KubectlPool {
+  private active = 0;
+  private queue: Waiter[] = [];
+  private streaming = 0;
+  private peakQueued = 0;
+  private timeouts = 0;
+  private streamsRefused = 0;
+
+  /**
+   * Wait for a slot. Resolves with the function that frees it, or with
+   * null if none came free within the queue timeout.
+   */
+  acquire(): Promise<(() => void) | null> {
+    const { maxConcurrent, queueTimeoutMs } = getAppConfig().kubectlPool;
+    if (maxConcurrent === 0 || (this.active < maxConcurrent && this.queue.length === 0)) {
+      this.active++;
+      return Promise.resolve(this.releaser());
+    }
+
+    return new Promise((resolve) => {
+      const waiter: Waiter = {
+        grant: (release) => {
+          clearTimeout(waiter.timer);
+          resolve(release);
+        },
+        timer: setTimeout(() => {
+          this.queue = this.queue.filter((queued) => queued !== waiter);
+          this.timeouts++;
+          resolve(null);
+        }, queueTimeoutMs),
+      };
+      this.queue.push(waiter);
+      this.peakQueued = Math.max(this.peakQueued, this.queue.length);
+    });
+  }
+
+  /** Whether another long-running process may start; counts a refusal when not */
+  tryStartStreaming(): boolean {
+    const { maxStreaming } = getAppConfig().kubectlPool;
+    if (maxStreaming === 0 || this.streaming < maxStreaming) {
+      return true;
+    }
+    this.streamsRefused++;
+    return false;
+  }
+
+  trackStreaming(child: ChildProcess): void {
+    this.streaming++;
+    child.once('close', () => {
+      this.streaming--;
+    });
+  }
+
+  getStats(): KubectlPoolStats {
+    return {
+      ...getAppConfig().kubectlPool,
+      active: this.active,
+      queued: this.queue.length,
+      streaming: this.streaming,
+      peakQueued: this.peakQueued,
+      timeouts: this.timeouts,
+      streamsRefused: this.streamsRefused,
+    };
+  }
+
+  /**
+   * Change some of the limits; the rest keep their values. Only the known
+   * keys are taken, each checked, so the saved config can't pick up
+   * anything else.
+   */
+  setLimits(limits: Partial<KubectlPoolLimits>): KubectlPoolStats {
+    const next: KubectlPoolLimits = { ...getAppConfig().kubectlPool };
+    for (const [key, value] of Object.entries(limits)) {
+      if (value === undefined) {
+        continue;
+      }
+      switch (key) {
+        case 'maxConcurrent':
+        case 'maxStreaming':
+          if (!Number.isInteger(value) || value < 0) {
+            throw new Error(`${key} must be a whole number, or 0 for no limit`);
+          }
+          break;
+        case 'queueTimeoutMs':
+          if (!Number.isInteger(value) || value <= 0) {
+            throw new Error('queueTimeoutMs must be a positive whole number');
+          }
+          break;
+        default:
+          throw new Error(`Unknown kubectl pool limit "${key}"`);
+      }
+      next[key as keyof KubectlPoolLimits] = value;
+    }
+
+    updateAppConfig((config) => {
+      config.kubectlPool = next;
+    });
+    // A higher limit lets waiting calls start now
+    this.drain();
+    return this.getStats();
+  }
+
+  private releaser(): () => void {
+    let released = false;
+    return () => {
+      if (released) {
+        return;
+      }
+      released = true;
+      this.active--;
+      this.drain();
+    };
+  }
+
+  private drain(): void {
+    const { maxConcurrent } = getAppConfig().kubectlPool;
+    while (this.queue.length > 0 && (maxConcurrent === 0 || this.active < maxConcurrent)) {
+      const waiter = this.queue.shift();
+      if (waiter) {
+        this.active++;
+        waiter.grant(this.releaser());
+      }
+    }
+  }
+}
+
+// Singleton instance
+export const kubectlPool = new KubectlPool();
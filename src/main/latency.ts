@@ -0,0 +1,147 @@
+import { randomUUID } from 'crypto';
+import type { LatencySample, LatencySampleStatus } from '../common/kubeTypes';
+import { classifyKubectlError, runKubectl } from './kube';
+import { emitToRenderer } from './events';
+import { operationRegistry } from './operations';
+
+export interface LatencyProbeSpec {
+  context: string;
+  intervalMs?: number;
+  view?: string;
+}
+
+interface LatencyProbe {
+  spec: LatencyProbeSpec;
+  timer: NodeJS.Timeout | null;
+  inFlight: boolean;
+  history: LatencySample[];
+}
+
+const DEFAULT_INTERVAL_MS = 5000;
+const MIN_INTERVAL_MS = 1000;
+const MAX_HISTORY_SAMPLES = 720;
+// A probe slower than this counts as a timeout sample
+const PROBE_TIMEOUT = '5s';
+
+const TIMEOUT_PATTERN = /Client\.Timeout exceeded|context deadline exceeded|i\/o timeout|timed out|Timeout:/i;
+
+function classifySample(output: string): LatencySampleStatus {
+  // Checked first: kubectl reports most timeouts as connection errors too
+  if (TIMEOUT_PATTERN.test(output)) {
+    return 'timeout';
+  }
+  switch (classifyKubectlError(output)) {
+    case 'Unreachable':
+      return 'unreachable';
+    case 'Forbidden':
+      return 'forbidden';
+    case 'Unauthorized':
+      return 'unauthorized';
+    default:
+      return 'error';
+  }
+}
+
+/**
+ * Periodically times `kubectl get --raw /healthz` against a context,
+ * emitting `latency:sample` for a live latency indicator. Failures are
+ * samples too, so an outage shows up as such rather than as a gap.
+ */
+export class LatencyProbeManager {
+  private probes: Map<string, LatencyProbe> = new Map();
+
+  startLatencyProbe(spec: LatencyProbeSpec): string {
+    const id = randomUUID();
+    const probe: LatencyProbe = { spec, timer: null, inFlight: false, history: [] };
+    const interval = Math.max(spec.intervalMs ?? DEFAULT_INTERVAL_MS, MIN_INTERVAL_MS);
+
+    this.probes.set(id, probe);
+    operationRegistry.register(
+      id,
+      'metrics',
+      { context: spec.context, view: spec.view },
+      () => this.stopLatencyProbe(id)
+    );
+    probe.timer = setInterval(() => this.sample(id, probe), interval);
+    this.sample(id, probe);
+
+    console.log(`[Latency ${id}] Probing ${spec.context} every ${interval}ms`);
+    return id;
+  }
+
+  stopLatencyProbe(id: string): boolean {
+    const probe = this.probes.get(id);
+    if (!probe) {
+      return false;
+    }
+
+    if (probe.timer) {
+      clearInterval(probe.timer);
+      probe.timer = null;
+    }
+    this.probes.delete(id);
+    operationRegistry.unregister(id);
+    console.log(`[Latency ${id}] Stopped`);
+    return true;
+  }
+
+  stopAllLatencyProbes(): void {
+    for (const id of Array.from(this.probes.keys())) {
+      this.stopLatencyProbe(id);
+    }
+  }
+
+  getLatencyHistory(id: string): LatencySample[] {
+    const probe = this.probes.get(id);
+    if (!probe) {
+      throw new Error(`Latency probe with id ${id} not found`);
+    }
+    return probe.history.slice();
+  }
+
+  private async sample(id: string, probe: LatencyProbe): Promise<void> {
+    // A slow cluster shouldn't stack up probes
+    if (probe.inFlight) {
+      return;
+    }
+    probe.inFlight = true;
+
+    const started = Date.now();
+    let sample: LatencySample;
+
+    try {
+      const result = await runKubectl(
+        probe.spec.context,
+        ['get', '--raw', '/healthz', `--request-timeout=${PROBE_TIMEOUT}`],
+        undefined,
+        { view: probe.spec.view }
+      );
+      const latencyMs = Date.now() - started;
+
+      if (result.code === 0) {
+        sample = { probeId: id, timestamp: started, latencyMs, status: 'ok' };
+      } else {
+        const output = (result.stderr || result.stdout).trim();
+        sample = { probeId: id, timestamp: started, latencyMs, status: classifySample(output), message: output };
+      }
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      sample = { probeId: id, timestamp: started, latencyMs: Date.now() - started, status: 'error', message };
+    } finally {
+      probe.inFlight = false;
+    }
+
+    if (!this.probes.has(id)) {
+      return;
+    }
+
+    probe.history.push(sample);
+    if (probe.history.length > MAX_HISTORY_SAMPLES) {
+      probe.history.splice(0, probe.history.length - MAX_HISTORY_SAMPLES);
+    }
+    emitToRenderer('latency:sample', sample);
+  }
+}
+
+// Singleton instance
+export const latencyProbeManager = new LatencyProbeManager();
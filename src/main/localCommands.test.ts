@@ -0,0 +1,75 @@
+import { processPty, userDataDir } from './testSupport';
+import assert from 'node:assert/strict';
+import { after, afterEach, beforeEach, describe, it } from 'node:test';
+import type { LocalCommandExitEvent } from '../common/terminalTypes';
+import { getAppConfig } from './appConfig';
+import { tapRendererEvents } from './events';
+import { LocalCommandManager } from './localCommands';
+
+describe('local commands', () => {
+  const nodePty = require('node-pty');
+  const { spawn } = nodePty;
+  const output: Map<string, string> = new Map();
+  const exits: LocalCommandExitEvent[] = [];
+  const untap = tapRendererEvents((channel, args) => {
+    if (channel === 'command:data') {
+      const [id, data] = args as [string, string];
+      output.set(id, `${output.get(id) ?? ''}${data}`);
+    } else if (channel === 'command:exit') {
+      exits.push(args[0] as LocalCommandExitEvent);
+    }
+  });
+  after(untap);
+
+  beforeEach(() => {
+    nodePty.spawn = processPty;
+    exits.length = 0;
+  });
+
+  afterEach(() => {
+    nodePty.spawn = spawn;
+    getAppConfig().commandPolicy = { enabled: false, allow: [], deny: [] };
+  });
+
+  const exitOf = async (id: string) => {
+    for (let turn = 0; turn < 1000; turn++) {
+      const exit = exits.find((event) => event.commandId === id);
+      if (exit) {
+        return exit;
+      }
+      await new Promise((resolve) => setTimeout(resolve, 5));
+    }
+    throw new Error('command did not exit');
+  };
+
+  it('streams the output and reports the exit code', async () => {
+    const id = await new LocalCommandManager().runCommand({
+      command: '/bin/sh',
+      args: ['-c', 'echo "deploying to $TARGET from $(pwd)"; echo done; exit 3'],
+      cwd: userDataDir,
+      env: { TARGET: 'staging' },
+    });
+
+    const exit = await exitOf(id);
+    assert.equal(output.get(id), `deploying to staging from ${userDataDir}\ndone\n`);
+    assert.deepEqual([exit.exitCode, exit.stopped], [3, false]);
+  });
+
+  it('stops a running command', async () => {
+    const manager = new LocalCommandManager();
+    const id = await manager.runCommand({ command: '/bin/sh', args: ['-c', 'exec sleep 30'], cwd: userDataDir });
+    assert.equal(manager.stopCommand(id), true);
+    assert.equal((await exitOf(id)).stopped, true);
+    assert.equal(manager.stopCommand(id), false);
+  });
+
+  it('checks the command before starting it', async () => {
+    const manager = new LocalCommandManager();
+    await assert.rejects(manager.runCommand({ command: ' ' }), /Command is required/);
+    await assert.rejects(manager.runCommand({ command: 'make', cwd: `${userDataDir}/missing` }), /not found/);
+    getAppConfig().commandPolicy = { enabled: true, allow: [], deny: ['make deploy'] };
+    await assert.rejects(manager.runCommand({ command: 'make', args: ['deploy'], cwd: userDataDir }), {
+      code: 'CommandBlocked',
+    });
+  });
+});
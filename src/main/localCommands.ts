@@ -0,0 +1,116 @@
+import * as pty from 'node-pty';
+import { randomUUID } from 'crypto';
+import { stat } from 'fs/promises';
+import path from 'path';
+import type { LocalCommandExitEvent, LocalCommandSpec } from '../common/terminalTypes';
+import { assertCommandAllowed } from './commandPolicy';
+import { resolveTerminalCwd } from './cwdPolicy';
+import { emitToRenderer } from './events';
+import { trackPtyProcess, untrackPtyProcess } from './orphans';
+import { TerminalSpawnError } from './terminal';
+
+interface LocalCommand {
+  process: pty.IPty;
+  startedAt: number;
+  stopped: boolean;
+}
+
+const MAX_RUNNING_COMMANDS = 16;
+// Wide enough that build tools don't wrap their progress lines
+const COMMAND_COLS = 200;
+const COMMAND_ROWS = 50;
+
+/**
+ * Runs local programs the user wires up (`make deploy`, a deploy script)
+ * on a PTY, so they colour and buffer their output as in a terminal, but
+ * non-interactively: output arrives as `command:data` and the exit status
+ * as `command:exit`. Commands are subject to the command policy.
+ */
+export class LocalCommandManager {
+  private commands: Map<string, LocalCommand> = new Map();
+
+  async runCommand(spec: LocalCommandSpec): Promise<string> {
+    const args = spec.args ?? [];
+    if (!spec.command?.trim()) {
+      throw new Error('Command is required');
+    }
+    if (!args.every((arg) => typeof arg === 'string')) {
+      throw new Error('Arguments must be strings');
+    }
+    if (this.commands.size >= MAX_RUNNING_COMMANDS) {
+      throw new Error(`Too many commands running (limit ${MAX_RUNNING_COMMANDS}); stop one first`);
+    }
+    assertCommandAllowed('terminal', [spec.command, ...args].join(' '));
+
+    // Same directory policy as terminals; a command is no way around it
+    const cwd = path.resolve(await resolveTerminalCwd(spec.cwd, process.env.HOME || process.cwd()));
+    const info = await stat(cwd).catch(() => null);
+    if (!info?.isDirectory()) {
+      throw new Error(`Working directory not found: ${cwd}`);
+    }
+
+    const id = randomUUID();
+    let child: pty.IPty;
+    try {
+      child = pty.spawn(spec.command, args, {
+        name: 'xterm-256color',
+        cols: COMMAND_COLS,
+        rows: COMMAND_ROWS,
+        cwd,
+        env: { ...process.env, ...spec.env } as Record<string, string>,
+      });
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      throw new TerminalSpawnError(`Failed to start ${spec.command}: ${message}`, 1);
+    }
+
+    const command: LocalCommand = { process: child, startedAt: Date.now(), stopped: false };
+    this.commands.set(id, command);
+    trackPtyProcess(child.pid, id, [spec.command, ...args].join(' '));
+
+    child.onData((data) => emitToRenderer('command:data', id, data));
+    child.onExit(({ exitCode, signal }) => {
+      untrackPtyProcess(child.pid);
+      this.commands.delete(id);
+      const event: LocalCommandExitEvent = {
+        commandId: id,
+        exitCode,
+        signal: signal || null,
+        stopped: command.stopped,
+        durationMs: Date.now() - command.startedAt,
+      };
+      console.log(`[Command ${id}] Exited with code ${exitCode}${signal ? `, signal ${signal}` : ''}`);
+      emitToRenderer('command:exit', event);
+    });
+
+    console.log(`[Command ${id}] Running ${spec.command} ${args.join(' ')} in ${cwd}`);
+    return id;
+  }
+
+  /**
+   * Ask a running command to stop; `command:exit` follows once it has
+   */
+  stopCommand(id: string): boolean {
+    const command = this.commands.get(id);
+    if (!command) {
+      return false;
+    }
+
+    command.stopped = true;
+    try {
+      command.process.kill();
+    } catch (error) {
+      console.error(`[Command ${id}] Error stopping command:`, error);
+    }
+    return true;
+  }
+
+  stopAllCommands(): void {
+    for (const id of Array.from(this.commands.keys())) {
+      this.stopCommand(id);
+    }
+  }
+}
+
+// Singleton instance
+export const localCommandManager = new LocalCommandManager();
@@ -0,0 +1,65 @@
+import './testSupport';
+import assert from 'node:assert/strict';
+import { afterEach, describe, it } from 'node:test';
+import { getAppConfig } from './appConfig';
+import { createLogLevelDetector, setLogLevelSettings } from './logLevels';
+
+describe('log level detection', () => {
+  const detect = createLogLevelDetector({ patterns: [], builtins: true });
+
+  it('reads logrus text and JSON lines', () => {
+    assert.equal(detect('time="2026-03-10T12:00:00Z" level=warning msg="cache miss" key=users'), 'warn');
+    assert.equal(detect('{"level":"error","msg":"failed to sync","time":"2026-03-10T12:00:00Z"}'), 'error');
+  });
+
+  it('reads zap JSON and console lines', () => {
+    assert.equal(detect('{"level":"dpanic","ts":1741608000.1,"caller":"main.go:42","msg":"bad state"}'), 'error');
+    assert.equal(detect('2026-03-10T12:00:00.000Z\tDEBUG\tserver/handler.go:88\thandling request'), 'debug');
+  });
+
+  it('reads klog, pino and slog lines', () => {
+    assert.equal(detect('E0310 12:00:00.123456       1 reflector.go:138] failed to list *v1.Pod'), 'error');
+    assert.equal(detect('W0310 12:00:00.123456       1 warnings.go:70] v1 Endpoints is deprecated'), 'warn');
+    assert.equal(detect('{"level":30,"time":1741608000000,"msg":"listening"}'), 'info');
+    assert.equal(detect('time=2026-03-10T12:00:00Z level=INFO msg="server started" port=8080'), 'info');
+  });
+
+  it('reads plain bracketed and upper-case levels', () => {
+    assert.equal(detect('[ERROR] connection refused'), 'error');
+    assert.equal(detect('2026-03-10 12:00:00 [ warn ] disk 91% full'), 'warn');
+    assert.equal(detect('12:00:00.000 [main] FATAL com.example.App - out of memory'), 'fatal');
+  });
+
+  it('does not take prose for a level', () => {
+    assert.equal(detect('GET /healthz 200 no error'), null);
+    assert.equal(detect('Processed 42 records'), null);
+    assert.equal(detect('{"message":"not really a level"}'), null);
+  });
+
+  it('tries configured patterns first and can turn the built-in ones off', () => {
+    const custom = createLogLevelDetector({ patterns: [{ level: 'fatal', pattern: 'OOMKilled' }], builtins: true });
+    assert.equal(custom('[INFO] container OOMKilled'), 'fatal');
+    assert.equal(custom('[INFO] container started'), 'info');
+
+    const only = createLogLevelDetector({ patterns: [{ level: 'warn', pattern: '^W ' }], builtins: false });
+    assert.equal(only('W slow query'), 'warn');
+    assert.equal(only('[ERROR] connection refused'), null);
+  });
+});
+
+describe('log level settings', () => {
+  const { logLevels } = getAppConfig();
+
+  afterEach(() => {
+    getAppConfig().logLevels = logLevels;
+  });
+
+  it('checks patterns before saving them', () => {
+    assert.throws(() => setLogLevelSettings({ patterns: [{ level: 'loud' as any, pattern: 'x' }] }), /Pattern 1/);
+    assert.throws(() => setLogLevelSettings({ patterns: [{ level: 'warn', pattern: '(' }] }), /valid regular/);
+    assert.throws(() => setLogLevelSettings({ builtins: 'yes' as any }), /true or false/);
+
+    const saved = setLogLevelSettings({ patterns: [{ level: 'error', pattern: 'Traceback' }], builtins: false });
+    assert.deepEqual(saved, { patterns: [{ level: 'error', pattern: 'Traceback' }], builtins: false });
+  });
+});
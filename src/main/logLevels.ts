@@ -0,0 +1,133 @@
+import type { LogLevel, LogLevelPattern, LogLevelSettings } from '../common/kubeTypes';
+import { getAppConfig, normalizeLogLevelPattern, updateAppConfig } from './appConfig';
+import { DEFAULT_JSON_LOG_FIELDS, parseJsonLogLine } from './jsonLogs';
+
+// Level names and abbreviations used by common loggers, and pino/bunyan's numbers
+const LEVEL_ALIASES: Record<string, LogLevel> = {
+  trace: 'trace',
+  trc: 'trace',
+  '10': 'trace',
+  debug: 'debug',
+  dbg: 'debug',
+  verbose: 'debug',
+  '20': 'debug',
+  info: 'info',
+  inf: 'info',
+  notice: 'info',
+  '30': 'info',
+  warn: 'warn',
+  warning: 'warn',
+  wrn: 'warn',
+  '40': 'warn',
+  error: 'error',
+  err: 'error',
+  eror: 'error',
+  // zap's "panic in development"
+  dpanic: 'error',
+  '50': 'error',
+  fatal: 'fatal',
+  ftl: 'fatal',
+  crit: 'fatal',
+  critical: 'fatal',
+  panic: 'fatal',
+  emerg: 'fatal',
+  alert: 'fatal',
+  '60': 'fatal',
+};
+
+const KLOG_LEVELS: Record<string, LogLevel> = { I: 'info', W: 'warn', E: 'error', F: 'fatal' };
+
+// logfmt, as written by logrus' text formatter, slog and go-kit: level=warn
+const LOGFMT_PATTERN = /(?:^|\s)(?:level|lvl|severity)=["']?([A-Za-z]+|\d+)/i;
+// klog (Kubernetes components): "E0501 10:00:00.123456    1 file.go:12] ..."
+const KLOG_PATTERN = /^([IWEF])\d{4} \d{2}:\d{2}:\d{2}/;
+const BRACKET_PATTERN = /\[\s*(trace|debug|dbg|info|inf|warn(?:ing)?|wrn|err(?:or)?|fatal|crit(?:ical)?|panic)\s*\]/i;
+// A bare upper-case level, e.g. zap's console encoder or log4j. Upper case
+// only, so prose such as "no error" doesn't count.
+const WORD_PATTERN = /(?:^|[\s|])(TRACE|DEBUG|INFO|WARN(?:ING)?|ERROR|FATAL|CRIT(?:ICAL)?|PANIC)(?=[\s:|\]]|$)/;
+
+// Only the start of a long line is searched for a level
+const MAX_SCANNED_CHARS = 256;
+
+function toLevel(name: string | null | undefined): LogLevel | null {
+  return name ? LEVEL_ALIASES[name.toLowerCase()] ?? null : null;
+}
+
+function detectBuiltin(line: string): LogLevel | null {
+  if (line.trimStart().startsWith('{')) {
+    const entry = parseJsonLogLine(line, DEFAULT_JSON_LOG_FIELDS);
+    if (entry.parsed) {
+      return toLevel(entry.level);
+    }
+  }
+
+  const head = line.slice(0, MAX_SCANNED_CHARS);
+  const klog = KLOG_PATTERN.exec(head);
+  if (klog) {
+    return KLOG_LEVELS[klog[1]];
+  }
+  return (
+    toLevel(LOGFMT_PATTERN.exec(head)?.[1]) ??
+    toLevel(BRACKET_PATTERN.exec(head)?.[1]) ??
+    toLevel(WORD_PATTERN.exec(head)?.[1])
+  );
+}
+
+/**
+ * A level detector for the configured patterns, compiled once. Configured
+ * patterns are tried first, then (unless turned off) the built-in ones:
+ * JSON level fields (zap, logrus, slog, pino), logfmt `level=`, klog's
+ * severity letter, `[LEVEL]` and a bare upper-case LEVEL.
+ */
+export function createLogLevelDetector(
+  settings: LogLevelSettings = getLogLevelSettings()
+): (line: string) => LogLevel | null {
+  const custom = settings.patterns.map((entry) => ({ level: entry.level, pattern: new RegExp(entry.pattern, 'i') }));
+
+  return (line: string) => {
+    for (const entry of custom) {
+      if (entry.pattern.test(line)) {
+        return entry.level;
+      }
+    }
+    return settings.builtins ? detectBuiltin(line) : null;
+  };
+}
+
+export function getLogLevelSettings(): LogLevelSettings {
+  const { patterns, builtins } = getAppConfig().logLevels;
+  return { patterns: patterns.map((pattern) => ({ ...pattern })), builtins };
+}
+
+/**
+ * Replace the configured patterns and/or turn the built-in ones on or off.
+ * Applies to log streams started afterwards.
+ */
+export function setLogLevelSettings(settings: Partial<LogLevelSettings>): LogLevelSettings {
+  let patterns: LogLevelPattern[] | undefined;
+  if (settings.patterns !== undefined) {
+    if (!Array.isArray(settings.patterns)) {
+      throw new Error('patterns must be a list');
+    }
+    patterns = settings.patterns.map((entry, index) => {
+      const pattern = normalizeLogLevelPattern(entry);
+      if (!pattern) {
+        throw new Error(`Pattern ${index + 1} needs a known level and a valid regular expression`);
+      }
+      return pattern;
+    });
+  }
+  if (settings.builtins !== undefined && typeof settings.builtins !== 'boolean') {
+    throw new Error('builtins must be true or false');
+  }
+
+  updateAppConfig((config) => {
+    if (patterns) {
+      config.logLevels.patterns = patterns;
+    }
+    if (settings.builtins !== undefined) {
+      config.logLevels.builtins = settings.builtins;
+    }
+  });
+  return getLogLevelSettings();
+}
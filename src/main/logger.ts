@@ -0,0 +1,99 @@
+import { app } from 'electron';
+import path from 'path';
+import { format } from 'util';
+import type { LogLevel, LogSettings } from '../common/appTypes';
+import { RotatingFile } from './rotatingFile';
+
+const LEVEL_ORDER: Record<LogLevel, number> = { error: 0, warn: 1, info: 2, debug: 3 };
+const DEFAULT_LEVEL: LogLevel = 'info';
+const MAX_LOG_FILE_BYTES = 5 * 1024 * 1024;
+const MAX_ROTATED_FILES = 3;
+
+let currentLevel: LogLevel = DEFAULT_LEVEL;
+let logFilePath: string | null = null;
+let logFile: RotatingFile | null = null;
+let installed = false;
+
+const original = {
+  error: console.error.bind(console),
+  warn: console.warn.bind(console),
+  log: console.log.bind(console),
+  debug: console.debug.bind(console),
+};
+
+export function isLogLevel(value: unknown): value is LogLevel {
+  return typeof value === 'string' && Object.prototype.hasOwnProperty.call(LEVEL_ORDER, value);
+}
+
+function writeToFile(level: LogLevel, args: unknown[]): void {
+  if (!logFile?.isOpen) {
+    return;
+  }
+
+  logFile.write(`${new Date().toISOString()} [${level.toUpperCase()}] ${format(...args)}\n`);
+}
+
+function wrap(level: LogLevel, target: (...args: unknown[]) => void) {
+  return (...args: unknown[]) => {
+    if (LEVEL_ORDER[level] > LEVEL_ORDER[currentLevel]) {
+      return;
+    }
+    target(...args);
+    writeToFile(level, args);
+  };
+}
+
+/**
+ * Route main-process console output through a level filter and, in
+ * packaged builds (or when forced), a rotating file under userData/logs
+ */
+export function installLogger(options: { fileLogging?: boolean } = {}): void {
+  if (installed) {
+    return;
+  }
+  installed = true;
+
+  const envLevel = process.env.KUBECLI_LOG_LEVEL;
+  currentLevel = isLogLevel(envLevel) ? envLevel : DEFAULT_LEVEL;
+
+  if (options.fileLogging ?? app.isPackaged) {
+    try {
+      logFilePath = path.join(app.getPath('userData'), 'logs', 'main.log');
+      // Logged with the original console: the wrapped one would write to the failing file
+      logFile = new RotatingFile(logFilePath, MAX_LOG_FILE_BYTES, MAX_ROTATED_FILES, (message, error) =>
+        original.error(`[Logger] ${message}:`, error)
+      );
+    } catch (error) {
+      original.error('[Logger] Unable to open log file:', error);
+      logFilePath = null;
+    }
+  }
+
+  console.error = wrap('error', original.error);
+  console.warn = wrap('warn', original.warn);
+  console.log = wrap('info', original.log);
+  console.info = wrap('info', original.log);
+  console.debug = wrap('debug', original.debug);
+}
+
+/**
+ * Change verbosity at runtime. Unknown levels fall back to the default.
+ */
+export function setLogLevel(level: string): LogLevel {
+  const normalized = level?.trim().toLowerCase();
+  if (isLogLevel(normalized)) {
+    currentLevel = normalized;
+  } else {
+    original.warn(`[Logger] Unknown log level "${level}", using "${DEFAULT_LEVEL}"`);
+    currentLevel = DEFAULT_LEVEL;
+  }
+  return currentLevel;
+}
+
+export function getLogSettings(): LogSettings {
+  return { level: currentLevel, filePath: logFilePath };
+}
+
+export function getLogFilePath(): string | null {
+  return logFilePath;
+}
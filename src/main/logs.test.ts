@@ -0,0 +1,118 @@
+import { stubSpawnKubectl, userDataDir, waitForSpawn } from './testSupport';
+import assert from 'node:assert/strict';
+import { describe, it } from 'node:test';
+import { readFileSync } from 'fs';
+import path from 'path';
+import { gunzipSync } from 'zlib';
+import { getAppConfig } from './appConfig';
+import { tapRendererEvents } from './events';
+import { exportLogs, logStreamManager } from './logs';
+
+const spawned = stubSpawnKubectl();
+
+describe('exportLogs', () => {
+  it('keeps multi-byte characters split across chunks whole', async () => {
+    const outputPath = path.join(userDataDir, 'export-utf8.log.gz');
+    const exported = exportLogs({
+      context: 'dev',
+      namespace: 'shop',
+      pod: 'web-0',
+      since: '2026-01-01T00:00:00Z',
+      outputPath,
+    });
+    const [{ child }] = await waitForSpawn(spawned, 1);
+    const text = Buffer.from('2026-01-01T00:00:01Z grüße\n2026-01-01T00:00:02Z 日本\n', 'utf8');
+    for (let offset = 0; offset < text.length; offset += 5) {
+      child.stdout.write(text.subarray(offset, offset + 5));
+    }
+    child.exit(0);
+
+    const result = await exported;
+    assert.equal(result.lines, 2);
+    assert.equal(gunzipSync(readFileSync(outputPath)).toString('utf8'), text.toString('utf8'));
+  });
+});
+
+describe('following a selector\'s pods', () => {
+  it('backs off and gives up on a pod whose logs keep ending', async () => {
+    const { reconnect } = getAppConfig();
+    getAppConfig().reconnect = { maxAttempts: 2, baseDelayMs: 1, maxDelayMs: 1, jitter: false };
+    spawned.length = 0;
+    const events: [string, unknown[]][] = [];
+    const removeTap = tapRendererEvents((channel, args) => events.push([channel, args]));
+    const id = logStreamManager.streamLogsForSelector({ context: 'dev', namespace: 'shop', labelSelector: 'app=web' });
+
+    await waitForSpawn(spawned, 1);
+    const pod = { metadata: { name: 'web-0' }, status: { phase: 'Running' } };
+    spawned[0].child.stdout.write(JSON.stringify({ type: 'ADDED', object: pod }));
+
+    // A clean end, then a drop whose stderr is too long to keep whole
+    await waitForSpawn(spawned, 2);
+    spawned[1].child.exit(0);
+    await waitForSpawn(spawned, 3);
+    spawned[2].child.stderr.write('x'.repeat(100 * 1024));
+    spawned[2].child.exit(1);
+    await waitForSpawn(spawned, 4);
+    spawned[3].child.exit(0);
+    for (let turn = 0; turn < 1000 && !events.some(([channel]) => channel === 'logs:error'); turn++) {
+      await new Promise((resolve) => setImmediate(resolve));
+    }
+
+    const reconnecting = events.filter(([channel]) => channel === 'logs:reconnecting').map(([, [event]]) => event);
+    assert.deepEqual(reconnecting.map((event: any) => event.attempt), [1, 2]);
+    assert.ok((reconnecting[1] as any).message.length <= 4096);
+    const [, error] = events.find(([channel]) => channel === 'logs:error') ?? [];
+    assert.deepEqual(error, [id, 'Lost logs for web-0: kubectl logs ended']);
+    assert.equal(spawned.length, 4);
+    removeTap();
+    logStreamManager.stopLogStream(id);
+    getAppConfig().reconnect = reconnect;
+  });
+});
+
+describe('log levels', () => {
+  it('tags each streamed line with its level and keeps the line as it was', async () => {
+    spawned.length = 0;
+    const batches: any[] = [];
+    const removeTap = tapRendererEvents((channel, [event]) => channel === 'logs:data' && batches.push(event));
+    const id = logStreamManager.streamLogsForSelector({ context: 'dev', namespace: 'shop', labelSelector: 'app=api' });
+
+    await waitForSpawn(spawned, 1);
+    const pod = { metadata: { name: 'api-0' }, status: { phase: 'Running' } };
+    spawned[0].child.stdout.write(JSON.stringify({ type: 'ADDED', object: pod }));
+    await waitForSpawn(spawned, 2);
+    const lines = [
+      'time="2026-03-10T12:00:00Z" level=error msg="db timeout"',
+      '{"level":"warn","msg":"slow query"}',
+      'listening on :8080',
+    ];
+    spawned[1].child.stdout.write(lines.map((line) => `[pod/api-0/api] ${line}\n`).join(''));
+    for (let turn = 0; turn < 1000 && batches.length === 0; turn++) {
+      await new Promise((resolve) => setTimeout(resolve, 5));
+    }
+
+    assert.equal(batches.length, 1);
+    assert.equal(batches[0].container, 'api');
+    assert.deepEqual(batches[0].lines, lines);
+    assert.deepEqual(batches[0].levels, ['error', 'warn', null]);
+    removeTap();
+    logStreamManager.stopLogStream(id);
+  });
+});
+
+describe('log sinks', () => {
+  it('lists the sink of each running stream', () => {
+    const uiOnly = logStreamManager.streamLogsForSelector({ context: 'dev', namespace: 'shop', labelSelector: 'a=1' });
+    const saved = logStreamManager.streamLogsForSelector({ context: 'dev', namespace: 'shop', labelSelector: 'a=2' });
+    const filePath = path.join(userDataDir, 'sink-db.log');
+    logStreamManager.setLogSink(saved, 'both', filePath);
+
+    assert.deepEqual(logStreamManager.listLogSinks(), [
+      { streamId: uiOnly, sink: 'ui', filePath: null },
+      { streamId: saved, sink: 'both', filePath },
+    ]);
+    logStreamManager.stopLogStream(uiOnly);
+    logStreamManager.stopLogStream(saved);
+    assert.deepEqual(logStreamManager.listLogSinks(), []);
+  });
+});
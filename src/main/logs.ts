@@ -0,0 +1,1099 @@
+import { randomUUID } from 'crypto';
+import type { ChildProcessWithoutNullStreams } from 'child_process';
+import { app } from 'electron';
+import { createWriteStream } from 'fs';
+import { mkdir, stat, unlink } from 'fs/promises';
+import path from 'path';
+import { Transform } from 'stream';
+import { pipeline } from 'stream/promises';
+import { StringDecoder } from 'string_decoder';
+import { createGzip } from 'zlib';
+import type {
+  AllContainerLogs,
+  JsonLogEvent,
+  JsonLogFieldMap,
+  LogGrepMatch,
+  LogGrepResult,
+  LogExportResult,
+  LogLevel,
+  LogLinesEvent,
+  LogSink,
+  LogSinkStatus,
+  LogStreamEndedEvent,
+  LogStreamSink,
+  MergedLogLine,
+  MergedLogs,
+  StreamReconnectedEvent,
+  StreamReconnectingEvent,
+} from '../common/kubeTypes';
+import { spawnKubectl, runKubectl, kubectlFailure, getKubectlJson, KubeError } from './kube';
+import { emitToRenderer } from './events';
+import { watchManager, type WatchEvent } from './watch';
+import { operationRegistry } from './operations';
+import { Reconnector } from './reconnect';
+import { parseJsonLogLine, resolveJsonLogFields } from './jsonLogs';
+import { createLogLevelDetector } from './logLevels';
+import { RotatingFile } from './rotatingFile';
+import { getJobPodSelector, jobState } from './jobs';
+
+export interface SelectorLogStreamSpec {
+  context: string;
+  namespace: string;
+  labelSelector: string;
+  container?: string;
+  tailLines?: number;
+  view?: string;
+  /** Parse lines as JSON and emit `logs:json` instead of `logs:data` */
+  json?: boolean;
+  /** Field names for level, message and timestamp; defaults cover common loggers */
+  jsonFields?: Partial<Record<keyof JsonLogFieldMap, string | string[]>>;
+}
+
+export interface JobLogStreamSpec extends Omit<SelectorLogStreamSpec, 'labelSelector'> {
+  job: string;
+}
+
+export interface PodLogsSpec {
+  context: string;
+  pod: string;
+  namespace: string;
+  container?: string;
+  /** Logs of the previous (crashed) instance of the container */
+  previous?: boolean;
+  tailLines?: number;
+  /** Prefix each line with its RFC3339 timestamp */
+  timestamps?: boolean;
+}
+
+export interface AllContainerLogsSpec {
+  context: string;
+  pod: string;
+  namespace: string;
+  tailLines?: number;
+  includeInit?: boolean;
+  timestamps?: boolean;
+}
+
+export interface GrepLogsSpec {
+  context: string;
+  namespace: string;
+  labelSelector: string;
+  pattern: string;
+  ignoreCase?: boolean;
+  /** Only search this container of each pod */
+  container?: string;
+  tailLines?: number;
+  /** Lines of context kept before and after each match */
+  contextLines?: number;
+}
+
+export interface LogExportSpec {
+  context: string;
+  pod: string;
+  namespace: string;
+  container?: string;
+  /** RFC3339 start of the window */
+  since: string;
+  /** RFC3339 end of the window; the end of the logs if unset */
+  until?: string;
+  /** Where to write; defaults to a file under the app's data directory */
+  outputPath?: string;
+}
+
+interface PodFollower {
+  process: ChildProcessWithoutNullStreams | null;
+  reconnector: Reconnector;
+  partialLine: string;
+  phase: string;
+}
+
+interface LogStream {
+  id: string;
+  spec: SelectorLogStreamSpec;
+  watchId: string | null;
+  followers: Map<string, PodFollower>;
+  pending: string[];
+  // Pods that finished and whose logs were read to the end, or whose
+  // follow was given up after repeated drops
+  completed: Set<string>;
+  stopped: boolean;
+  paused: boolean;
+  // Batches held back while paused, flushed on resume
+  held: LogLinesEvent[];
+  heldLines: number;
+  // Set when lines are emitted as structured `logs:json` events
+  jsonFields: JsonLogFieldMap | null;
+  detectLevel: (line: string) => LogLevel | null;
+  sink: LogSink;
+  // Open while the sink includes a file
+  file: RotatingFile | null;
+  // Phase of each pod seen, so a pod that already finished isn't followed as running
+  phases: Map<string, string>;
+  // Watch on the Job whose pods these are, until it finishes
+  jobWatchId: string | null;
+  // Set once the stream should end as soon as every pod's logs are read
+  endReason: LogStreamEndedEvent['reason'] | null;
+  endTimer: NodeJS.Timeout | null;
+}
+
+// Bound the number of concurrent `kubectl logs -f` processes per stream
+const MAX_FOLLOWED_PODS = 20;
+// Tail of a follower's stderr kept for the error message; the rest is read and dropped
+const MAX_FOLLOW_STDERR_CHARS = 4096;
+const DEFAULT_TAIL_LINES = 50;
+// Lines kept while a stream is paused; older ones are dropped first
+const MAX_HELD_LINES = 10000;
+const DEFAULT_BATCH_TAIL_LINES = 200;
+const MAX_CONCURRENT_LOG_FETCHES = 4;
+// Shared by all containers of one batch fetch
+const MAX_BATCH_LOG_BYTES = 4 * 1024 * 1024;
+const DEFAULT_GREP_TAIL_LINES = 1000;
+const MAX_CONCURRENT_GREP_FETCHES = 8;
+const MAX_GREP_MATCHES = 1000;
+// Matched lines plus their context, across all pods
+const MAX_GREP_RESULT_BYTES = 2 * 1024 * 1024;
+// Log files roll over at this size, keeping a few older parts next to them
+const MAX_SINK_FILE_BYTES = 50 * 1024 * 1024;
+const MAX_SINK_ROTATED_FILES = 5;
+// A finished Job's stream ends even if a pod's logs never finish reading
+const MAX_DRAIN_MS = 30 * 1000;
+const DRAIN_SETTLE_MS = 2000;
+const NOT_STARTED_PATTERN = /is waiting to start|ContainerCreating|PodInitializing/i;
+// `--previous` for a container that never restarted fails with
+// "previous terminated container "app" in pod "web-1" not found"
+const NO_PREVIOUS_PATTERN = /previous terminated container .* not found/i;
+// `--prefix` output looks like "[pod/NAME/CONTAINER] line"
+const PREFIX_PATTERN = /^\[pod\/([^/\]]+)\/([^\]]+)\] ?(.*)$/;
+// `--timestamps` output looks like "2024-05-01T10:00:00.123456789Z line"
+const TIMESTAMP_PATTERN = /^(\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2})(?:\.(\d{1,9}))?(Z|[+-]\d{2}:\d{2}) ?(.*)$/;
+
+/**
+ * Follows logs for a changing set of pods, similar to stern: pods matching
+ * a selector are picked up as they appear and dropped when deleted
+ */
+export class LogStreamManager {
+  private streams: Map<string, LogStream> = new Map();
+
+  streamLogsForSelector(spec: SelectorLogStreamSpec): string {
+    const id = randomUUID();
+    const stream: LogStream = {
+      id,
+      spec,
+      watchId: null,
+      followers: new Map(),
+      pending: [],
+      completed: new Set(),
+      stopped: false,
+      paused: false,
+      held: [],
+      heldLines: 0,
+      jsonFields: spec.json ? resolveJsonLogFields(spec.jsonFields) : null,
+      detectLevel: createLogLevelDetector(),
+      sink: 'ui',
+      file: null,
+      phases: new Map(),
+      jobWatchId: null,
+      endReason: null,
+      endTimer: null,
+    };
+
+    this.streams.set(id, stream);
+    operationRegistry.register(
+      id,
+      'logs',
+      { context: spec.context, namespace: spec.namespace, view: spec.view },
+      () => this.stopLogStream(id)
+    );
+    stream.watchId = watchManager.startWatch(
+      {
+        context: spec.context,
+        kind: 'pod',
+        namespace: spec.namespace,
+        labelSelector: spec.labelSelector,
+        view: spec.view,
+      },
+      (event) => this.handlePodEvent(stream, event),
+      (message) => {
+        emitToRenderer('logs:error', id, message);
+        this.stopLogStream(id);
+      }
+    );
+    operationRegistry.setParent(stream.watchId, id);
+
+    console.log(`[Logs ${id}] Streaming pods matching ${spec.labelSelector}`);
+    return id;
+  }
+
+  /**
+   * Follow the logs of every pod of a Job, including pods it creates later
+   * (parallel Jobs, retries), as `logs:data` attributed to each pod. Once
+   * the Job completes, fails or is deleted and the remaining logs are read,
+   * `logs:ended` is emitted and the stream stops.
+   */
+  async streamJobLogs(spec: JobLogStreamSpec): Promise<string> {
+    const { job, ...rest } = spec;
+    const labelSelector = await getJobPodSelector(job, spec.namespace, spec.context);
+    const id = this.streamLogsForSelector({ ...rest, labelSelector });
+    const stream = this.streams.get(id) as LogStream;
+
+    const jobWatchId = watchManager.startWatch(
+      { context: spec.context, kind: 'job', name: job, namespace: spec.namespace, view: spec.view },
+      (event) => {
+        if (event.type !== 'ADDED' && event.type !== 'MODIFIED' && event.type !== 'DELETED') {
+          return;
+        }
+        const state = event.type === 'DELETED' ? 'deleted' : jobState(event.object);
+        if (state !== 'running') {
+          watchManager.stopWatch(jobWatchId);
+          stream.jobWatchId = null;
+          this.endWhenDrained(stream, state);
+        }
+      },
+      (message) => emitToRenderer('logs:error', id, `Stopped tracking job/${job}: ${message}`)
+    );
+    stream.jobWatchId = jobWatchId;
+    operationRegistry.setParent(jobWatchId, id);
+
+    console.log(`[Logs ${id}] Streaming job/${job}`);
+    return id;
+  }
+
+  stopLogStream(id: string): boolean {
+    const stream = this.streams.get(id);
+    if (!stream) {
+      return false;
+    }
+
+    stream.stopped = true;
+    if (stream.watchId) {
+      watchManager.stopWatch(stream.watchId);
+    }
+    if (stream.jobWatchId) {
+      watchManager.stopWatch(stream.jobWatchId);
+    }
+    if (stream.endTimer) {
+      clearTimeout(stream.endTimer);
+      stream.endTimer = null;
+    }
+    for (const pod of Array.from(stream.followers.keys())) {
+      this.unfollow(stream, pod);
+    }
+    stream.file?.close();
+
+    this.streams.delete(id);
+    operationRegistry.unregister(id);
+    console.log(`[Logs ${id}] Stopped`);
+    return true;
+  }
+
+  stopAllLogStreams(): void {
+    for (const id of Array.from(this.streams.keys())) {
+      this.stopLogStream(id);
+    }
+  }
+
+  /**
+   * Stop emitting `logs:data` while the user reads, without stopping the
+   * kubectl processes. Lines keep buffering (bounded) and are flushed on
+   * resume. Returns how many lines were flushed.
+   */
+  pauseLogStream(id: string, paused: boolean): number {
+    const stream = this.streams.get(id);
+    if (!stream) {
+      throw new Error(`Log stream ${id} not found`);
+    }
+
+    stream.paused = paused;
+    if (paused) {
+      return 0;
+    }
+
+    const flushed = stream.heldLines;
+    for (const batch of stream.held) {
+      this.emitBatch(stream, batch);
+    }
+    stream.held = [];
+    stream.heldLines = 0;
+    return flushed;
+  }
+
+  /**
+   * Route a stream's lines to the UI (`logs:data`), to a file, or both.
+   * The file is appended to as "[pod/container] line" and rolls over at
+   * 50MB; it's written even while the stream is paused. Without a path,
+   * a new file under userData/logs/streams is used. If writing fails the
+   * stream falls back to the UI and reports it with `logs:error`.
+   */
+  setLogSink(id: string, sink: LogSink, filePath?: string): LogSinkStatus {
+    const stream = this.streams.get(id);
+    if (!stream) {
+      throw new Error(`Log stream ${id} not found`);
+    }
+
+    const wantsFile = sink === 'file' || sink === 'both';
+    const target = filePath ? path.resolve(filePath) : stream.file?.filePath ?? this.defaultSinkPath(stream);
+    if (stream.file && (!wantsFile || stream.file.filePath !== target)) {
+      stream.file.close();
+      stream.file = null;
+    }
+    if (wantsFile && !stream.file) {
+      try {
+        stream.file = new RotatingFile(target, MAX_SINK_FILE_BYTES, MAX_SINK_ROTATED_FILES, (message, error) =>
+          this.failSink(stream, message, error)
+        );
+      } catch (error) {
+        throw new Error(`Cannot write logs to ${target}: ${error instanceof Error ? error.message : String(error)}`);
+      }
+    }
+
+    stream.sink = sink;
+    console.log(`[Logs ${id}] Sink: ${sink}${stream.file ? ` (${stream.file.filePath})` : ''}`);
+    return { sink, filePath: stream.file?.filePath ?? null };
+  }
+
+  /** The sink of every running log stream */
+  listLogSinks(): LogStreamSink[] {
+    return Array.from(this.streams.values()).map((stream) => ({
+      streamId: stream.id,
+      sink: stream.sink,
+      filePath: stream.file?.filePath ?? null,
+    }));
+  }
+
+  hasLogStream(id: string): boolean {
+    return this.streams.has(id);
+  }
+
+  private handlePodEvent(stream: LogStream, event: WatchEvent): void {
+    const pod: string | undefined = event.object?.metadata?.name;
+    if (!pod || stream.stopped) {
+      return;
+    }
+
+    if (event.type === 'DELETED') {
+      stream.pending = stream.pending.filter((name) => name !== pod);
+      stream.completed.delete(pod);
+      stream.phases.delete(pod);
+      this.unfollow(stream, pod);
+      this.fillSlots(stream);
+      return;
+    }
+
+    const phase: string = event.object?.status?.phase ?? 'Unknown';
+    stream.phases.set(pod, phase);
+    const follower = stream.followers.get(pod);
+    if (follower) {
+      follower.phase = phase;
+      return;
+    }
+
+    // Logs aren't available until containers start
+    if (phase === 'Pending' || stream.completed.has(pod) || stream.pending.includes(pod)) {
+      return;
+    }
+
+    stream.pending.push(pod);
+    this.fillSlots(stream);
+  }
+
+  private fillSlots(stream: LogStream): void {
+    while (stream.followers.size < MAX_FOLLOWED_PODS && stream.pending.length > 0) {
+      const pod = stream.pending.shift() as string;
+      stream.followers.set(pod, {
+        process: null,
+        reconnector: new Reconnector(),
+        partialLine: '',
+        phase: stream.phases.get(pod) ?? 'Running',
+      });
+      this.follow(stream, pod);
+    }
+
+    if (stream.endReason && stream.followers.size === 0 && stream.pending.length === 0) {
+      this.endLogStream(stream);
+    }
+  }
+
+  /**
+   * End the stream once its followed pods have been read to the end,
+   * or after MAX_DRAIN_MS at the latest
+   */
+  private endWhenDrained(stream: LogStream, reason: LogStreamEndedEvent['reason']): void {
+    if (stream.stopped || stream.endTimer || stream.endReason) {
+      return;
+    }
+    // Give the pod watch a moment to list the pods of a Job that finished quickly
+    stream.endTimer = setTimeout(() => {
+      stream.endReason = reason;
+      stream.endTimer = setTimeout(() => {
+        stream.endTimer = null;
+        this.endLogStream(stream);
+      }, MAX_DRAIN_MS);
+      this.fillSlots(stream);
+    }, DRAIN_SETTLE_MS);
+  }
+
+  private endLogStream(stream: LogStream): void {
+    if (stream.stopped || !stream.endReason) {
+      return;
+    }
+    // Lines still held by a pause are delivered before the end
+    if (stream.paused) {
+      this.pauseLogStream(stream.id, false);
+    }
+    const event: LogStreamEndedEvent = { streamId: stream.id, reason: stream.endReason };
+    emitToRenderer('logs:ended', event);
+    this.stopLogStream(stream.id);
+  }
+
+  private follow(stream: LogStream, pod: string, sinceTime?: string): void {
+    const follower = stream.followers.get(pod);
+    if (!follower || stream.stopped) {
+      return;
+    }
+
+    const { spec } = stream;
+    const args = ['logs', '-f', pod];
+
+    if (spec.container) {
+      args.push('-c', spec.container);
+    } else {
+      args.push('--all-containers', '--prefix');
+    }
+
+    if (sinceTime) {
+      args.push(`--since-time=${sinceTime}`);
+    } else {
+      args.push(`--tail=${spec.tailLines ?? DEFAULT_TAIL_LINES}`);
+    }
+
+    let child: ChildProcessWithoutNullStreams;
+    try {
+      child = spawnKubectl(spec.context, args, spec.namespace);
+    } catch (error) {
+      // At the pool's limit for long-running processes; a slot may free up
+      this.refollowLater(stream, pod, follower, error instanceof Error ? error.message : String(error), sinceTime);
+      return;
+    }
+    // A multi-byte character can be split across chunks
+    const decoder = new StringDecoder('utf8');
+    let stderr = '';
+    follower.process = child;
+    follower.reconnector.connected();
+
+    child.stdout.on('data', (chunk) => {
+      const attempts = follower.reconnector.reset();
+      if (attempts > 0) {
+        const reconnected: StreamReconnectedEvent = { id: stream.id, pod, attempts };
+        emitToRenderer('logs:reconnected', reconnected);
+      }
+
+      const text = follower.partialLine + decoder.write(chunk);
+      const lines = text.split('\n');
+      follower.partialLine = lines.pop() ?? '';
+      this.emitLines(stream, pod, lines);
+    });
+
+    child.stderr.on('data', (chunk) => {
+      stderr = (stderr + chunk.toString()).slice(-MAX_FOLLOW_STDERR_CHARS);
+    });
+
+    child.once('error', (error) => {
+      console.error(`[Logs ${stream.id}] Failed to follow ${pod}:`, error);
+    });
+
+    child.once('close', (code) => {
+      follower.process = null;
+      follower.partialLine += decoder.end();
+      if (follower.partialLine) {
+        this.emitLines(stream, pod, [follower.partialLine]);
+        follower.partialLine = '';
+      }
+
+      if (stream.stopped || stream.followers.get(pod) !== follower) {
+        return;
+      }
+
+      // Finished pods won't produce more output; free the slot
+      if (follower.phase === 'Succeeded' || follower.phase === 'Failed') {
+        stream.followers.delete(pod);
+        stream.completed.add(pod);
+        this.fillSlots(stream);
+        return;
+      }
+
+      // A container restart ends `logs -f` cleanly, a dropped connection doesn't; either way pick
+      // up where we left off, backing off so a container that keeps exiting isn't refollowed forever
+      const resumeFrom = new Date().toISOString();
+      const message =
+        code === 0 ? 'kubectl logs ended' : stderr.trim() || `kubectl logs exited with code ${code}`;
+      this.refollowLater(stream, pod, follower, message, resumeFrom);
+    });
+  }
+
+  /** Follow the pod again after the reconnect backoff, or give up on it once the attempts are used up */
+  private refollowLater(
+    stream: LogStream,
+    pod: string,
+    follower: PodFollower,
+    message: string,
+    resumeFrom?: string
+  ): void {
+    const delayMs = follower.reconnector.schedule(() => this.follow(stream, pod, resumeFrom));
+    if (delayMs === null) {
+      console.error(`[Logs ${stream.id}] Giving up on ${pod}: ${message}`);
+      stream.followers.delete(pod);
+      stream.completed.add(pod);
+      emitToRenderer('logs:error', stream.id, `Lost logs for ${pod}: ${message}`);
+      this.fillSlots(stream);
+      return;
+    }
+
+    const event: StreamReconnectingEvent = {
+      id: stream.id,
+      pod,
+      attempt: follower.reconnector.attempts,
+      maxAttempts: follower.reconnector.policy.maxAttempts,
+      delayMs,
+      message,
+    };
+    console.warn(`[Logs ${stream.id}] Lost ${pod} (${message}), reconnecting in ${delayMs}ms`);
+    emitToRenderer('logs:reconnecting', event);
+  }
+
+  private unfollow(stream: LogStream, pod: string): void {
+    const follower = stream.followers.get(pod);
+    if (!follower) {
+      return;
+    }
+
+    stream.followers.delete(pod);
+    follower.reconnector.cancel();
+    if (follower.process) {
+      try {
+        follower.process.kill();
+      } catch (error) {
+        console.error(`[Logs ${stream.id}] Error killing kubectl for ${pod}:`, error);
+      }
+    }
+  }
+
+  private emitLines(stream: LogStream, pod: string, rawLines: string[]): void {
+    // Group consecutive lines per container so each event has one attribution
+    const batches: LogLinesEvent[] = [];
+
+    for (const rawLine of rawLines) {
+      let container: string | null = stream.spec.container ?? null;
+      let line = rawLine;
+
+      if (!stream.spec.container) {
+        const match = PREFIX_PATTERN.exec(rawLine);
+        if (match) {
+          container = match[2];
+          line = match[3];
+        }
+      }
+
+      // kubectl merges stdout and stderr, so lines can't be told apart by stream
+      const level = stream.detectLevel(line);
+      const last = batches[batches.length - 1];
+      if (last && last.container === container) {
+        last.lines.push(line);
+        last.levels.push(level);
+      } else {
+        batches.push({ streamId: stream.id, pod, container, lines: [line], levels: [level] });
+      }
+    }
+
+    if (stream.file) {
+      this.writeToSink(stream.file, batches);
+    }
+    if (stream.sink === 'file') {
+      return;
+    }
+
+    if (stream.paused) {
+      this.hold(stream, batches);
+      return;
+    }
+
+    for (const batch of batches) {
+      this.emitBatch(stream, batch);
+    }
+  }
+
+  private writeToSink(file: RotatingFile, batches: LogLinesEvent[]): void {
+    const text = batches
+      .map((batch) => {
+        const prefix = `[pod/${batch.pod}${batch.container ? `/${batch.container}` : ''}] `;
+        return batch.lines.map((line) => `${prefix}${line}\n`).join('');
+      })
+      .join('');
+    file.write(text);
+  }
+
+  private failSink(stream: LogStream, message: string, error: unknown): void {
+    console.error(`[Logs ${stream.id}] ${message}:`, error);
+    const reason = error instanceof Error ? error.message : String(error);
+    stream.file?.close();
+    stream.file = null;
+    // Keep the lines visible rather than dropping them
+    stream.sink = 'ui';
+    emitToRenderer('logs:error', stream.id, `${message}: ${reason}. Showing logs here only.`);
+  }
+
+  private defaultSinkPath(stream: LogStream): string {
+    const selector = stream.spec.labelSelector.replace(/[^\w.-]+/g, '_').slice(0, 60);
+    const stamp = new Date().toISOString().replace(/[:.]/g, '-');
+    return path.join(app.getPath('userData'), 'logs', 'streams', `${stream.spec.namespace}-${selector}-${stamp}.log`);
+  }
+
+  private emitBatch(stream: LogStream, batch: LogLinesEvent): void {
+    if (!stream.jsonFields) {
+      emitToRenderer('logs:data', batch);
+      return;
+    }
+
+    const fields = stream.jsonFields;
+    const event: JsonLogEvent = {
+      streamId: batch.streamId,
+      pod: batch.pod,
+      container: batch.container,
+      entries: batch.lines.map((line) => parseJsonLogLine(line, fields)),
+    };
+    emitToRenderer('logs:json', event);
+  }
+
+  private hold(stream: LogStream, batches: LogLinesEvent[]): void {
+    for (const batch of batches) {
+      stream.held.push(batch);
+      stream.heldLines += batch.lines.length;
+    }
+
+    while (stream.heldLines > MAX_HELD_LINES && stream.held.length > 0) {
+      const oldest = stream.held[0];
+      const excess = stream.heldLines - MAX_HELD_LINES;
+      if (oldest.lines.length <= excess) {
+        stream.held.shift();
+        stream.heldLines -= oldest.lines.length;
+      } else {
+        oldest.lines = oldest.lines.slice(excess);
+        oldest.levels = oldest.levels.slice(excess);
+        stream.heldLines -= excess;
+      }
+    }
+  }
+}
+
+/**
+ * Fetch a container's logs once (no follow). With `previous`, returns the
+ * output of the last crashed instance, which is what CrashLoopBackOff
+ * debugging needs.
+ */
+export async function getPodLogs(spec: PodLogsSpec): Promise<string> {
+  const args = ['logs', spec.pod];
+  if (spec.container) {
+    args.push('-c', spec.container);
+  }
+  if (spec.previous) {
+    args.push('--previous');
+  }
+  if (spec.tailLines !== undefined) {
+    args.push(`--tail=${spec.tailLines}`);
+  }
+  if (spec.timestamps) {
+    args.push('--timestamps');
+  }
+
+  const result = await runKubectl(spec.context, args, spec.namespace);
+  if (result.code !== 0) {
+    if (spec.previous && NO_PREVIOUS_PATTERN.test(result.stderr)) {
+      throw new KubeError(
+        'NoPreviousContainer',
+        `${spec.container ?? 'The container'} has not restarted, so there are no logs from a previous run`
+      );
+    }
+    throw kubectlFailure(result, 'Failed to read logs');
+  }
+
+  return result.stdout;
+}
+
+export function getPreviousLogs(spec: Omit<PodLogsSpec, 'previous'>): Promise<string> {
+  return getPodLogs({ ...spec, previous: true });
+}
+
+/**
+ * Keep the newest lines that fit in a byte budget
+ */
+function lastLinesWithin(output: string, maxBytes: number): { lines: string[]; truncated: boolean } {
+  const lines = output.split('\n');
+  if (lines[lines.length - 1] === '') {
+    lines.pop();
+  }
+
+  let bytes = 0;
+  let start = lines.length;
+  while (start > 0) {
+    const size = Buffer.byteLength(lines[start - 1], 'utf8') + 1;
+    if (bytes + size > maxBytes) {
+      break;
+    }
+    bytes += size;
+    start--;
+  }
+  return { lines: lines.slice(start), truncated: start > 0 };
+}
+
+/**
+ * Last N lines of every container in a pod, fetched concurrently, so a
+ * multi-container pod can be shown side by side. Containers that haven't
+ * started are listed in `waiting` instead of failing the batch.
+ */
+export async function getAllContainerLogs(spec: AllContainerLogsSpec): Promise<AllContainerLogs> {
+  const pod = await getKubectlJson(spec.context, ['get', 'pod', spec.pod], spec.namespace);
+
+  const specs: any[] = [
+    ...(spec.includeInit ? pod?.spec?.initContainers ?? [] : []),
+    ...(pod?.spec?.containers ?? []),
+  ];
+  const statuses: any[] = [
+    ...(pod?.status?.initContainerStatuses ?? []),
+    ...(pod?.status?.containerStatuses ?? []),
+  ];
+  const names: string[] = specs.map((container) => container.name);
+
+  const result: AllContainerLogs = { logs: {}, errors: {}, waiting: [], truncated: false };
+  const fetched: Map<string, string> = new Map();
+  const perContainerBytes = Math.floor(MAX_BATCH_LOG_BYTES / Math.max(names.length, 1));
+  let next = 0;
+
+  const worker = async () => {
+    while (next < names.length) {
+      const name = names[next++];
+      const status = statuses.find((candidate) => candidate?.name === name);
+
+      // Never ran: nothing to fetch (a restarted container still has logs)
+      if (!status || (status.state?.waiting && !status.lastState?.terminated && !status.restartCount)) {
+        result.waiting.push(name);
+        continue;
+      }
+
+      try {
+        fetched.set(
+          name,
+          await getPodLogs({
+            context: spec.context,
+            pod: spec.pod,
+            namespace: spec.namespace,
+            container: name,
+            tailLines: spec.tailLines ?? DEFAULT_BATCH_TAIL_LINES,
+            timestamps: spec.timestamps,
+          })
+        );
+      } catch (error) {
+        const message = error instanceof Error ? error.message : String(error);
+        if (NOT_STARTED_PATTERN.test(message)) {
+          result.waiting.push(name);
+        } else {
+          result.errors[name] = message;
+        }
+      }
+    }
+  };
+
+  await Promise.all(
+    Array.from({ length: Math.min(MAX_CONCURRENT_LOG_FETCHES, names.length) }, () => worker())
+  );
+
+  // Fill in spec order, independent of which fetch finished first
+  for (const name of names) {
+    const output = fetched.get(name);
+    if (output === undefined) {
+      continue;
+    }
+    const { lines, truncated } = lastLinesWithin(output, perContainerBytes);
+    result.logs[name] = lines;
+    result.truncated = result.truncated || truncated;
+  }
+  result.waiting.sort((a, b) => names.indexOf(a) - names.indexOf(b));
+
+  return result;
+}
+
+/**
+ * Search the recent logs of every pod matching a selector at once, e.g. to
+ * find which replica logged an error. Matches come back in pod and
+ * container order with surrounding lines, up to a count and size limit.
+ */
+export async function grepLogs(spec: GrepLogsSpec): Promise<LogGrepResult> {
+  let pattern: RegExp;
+  try {
+    pattern = new RegExp(spec.pattern, spec.ignoreCase ? 'i' : '');
+  } catch (error) {
+    throw new Error(`Invalid pattern: ${error instanceof Error ? error.message : String(error)}`);
+  }
+
+  const list = await getKubectlJson(spec.context, ['get', 'pods', '-l', spec.labelSelector], spec.namespace);
+  const targets: { pod: string; container: string }[] = [];
+  for (const pod of list?.items ?? []) {
+    const containers: string[] = (pod.spec?.containers ?? []).map((container: any) => container.name);
+    for (const container of containers) {
+      if (!spec.container || container === spec.container) {
+        targets.push({ pod: pod.metadata?.name, container });
+      }
+    }
+  }
+
+  const contextLines = spec.contextLines ?? 0;
+  const found: LogGrepMatch[][] = targets.map(() => []);
+  const result: LogGrepResult = {
+    matches: [],
+    pods: new Set(targets.map((target) => target.pod)).size,
+    containers: targets.length,
+    errors: {},
+    truncated: false,
+  };
+  let next = 0;
+
+  const worker = async () => {
+    while (next < targets.length) {
+      const index = next++;
+      const { pod, container } = targets[index];
+
+      let output: string;
+      try {
+        output = await getPodLogs({
+          context: spec.context,
+          pod,
+          namespace: spec.namespace,
+          container,
+          tailLines: spec.tailLines ?? DEFAULT_GREP_TAIL_LINES,
+        });
+      } catch (error) {
+        const message = error instanceof Error ? error.message : String(error);
+        // A container that hasn't started has nothing to search
+        if (!NOT_STARTED_PATTERN.test(message)) {
+          result.errors[`${pod}/${container}`] = message;
+        }
+        continue;
+      }
+
+      const lines = output.split('\n');
+      if (lines[lines.length - 1] === '') {
+        lines.pop();
+      }
+      for (let lineNumber = 0; lineNumber < lines.length; lineNumber++) {
+        if (!pattern.test(lines[lineNumber])) {
+          continue;
+        }
+        // No one container can contribute more than the overall limit
+        if (found[index].length >= MAX_GREP_MATCHES) {
+          result.truncated = true;
+          break;
+        }
+        found[index].push({
+          pod,
+          container,
+          lineNumber: lineNumber + 1,
+          line: lines[lineNumber],
+          before: lines.slice(Math.max(0, lineNumber - contextLines), lineNumber),
+          after: lines.slice(lineNumber + 1, lineNumber + 1 + contextLines),
+        });
+      }
+    }
+  };
+
+  await Promise.all(
+    Array.from({ length: Math.min(MAX_CONCURRENT_GREP_FETCHES, targets.length) }, () => worker())
+  );
+
+  let bytes = 0;
+  for (const match of found.flat()) {
+    const size = [match.line, ...match.before, ...match.after].reduce(
+      (total, line) => total + Buffer.byteLength(line, 'utf8') + 1,
+      0
+    );
+    if (result.matches.length >= MAX_GREP_MATCHES || bytes + size > MAX_GREP_RESULT_BYTES) {
+      result.truncated = true;
+      break;
+    }
+    bytes += size;
+    result.matches.push(match);
+  }
+
+  return result;
+}
+
+interface TimedLine {
+  line: MergedLogLine;
+  // Whole seconds (as epoch ms) and the fraction in ns: a double can't hold
+  // nanoseconds since the epoch exactly
+  seconds: number;
+  nanos: number;
+}
+
+function parseTimestampedLines(container: string, lines: string[]): TimedLine[] {
+  const parsed: TimedLine[] = [];
+  for (const line of lines) {
+    const match = TIMESTAMP_PATTERN.exec(line);
+    const seconds = match ? Date.parse(`${match[1]}${match[3]}`) : NaN;
+
+    if (match && !Number.isNaN(seconds)) {
+      parsed.push({
+        line: {
+          container,
+          timestamp: `${match[1]}${match[2] ? `.${match[2]}` : ''}${match[3]}`,
+          message: match[4],
+        },
+        seconds,
+        nanos: Number((match[2] ?? '').padEnd(9, '0')),
+      });
+    } else if (parsed.length > 0) {
+      // e.g. a stack trace the runtime didn't split into separate entries
+      parsed[parsed.length - 1].line.message += `\n${line}`;
+    } else {
+      // Untimed lines cut loose by the tail: sort them before everything
+      parsed.push({ line: { container, timestamp: null, message: line }, seconds: -Infinity, nanos: 0 });
+    }
+  }
+  return parsed;
+}
+
+/**
+ * Logs of every container in a pod as one timeline ordered by the
+ * kubelet's timestamps, each line tagged with its container. Lines
+ * without a timestamp stay with the line before them.
+ */
+export async function getMergedLogs(spec: Omit<AllContainerLogsSpec, 'timestamps'>): Promise<MergedLogs> {
+  const batch = await getAllContainerLogs({ ...spec, timestamps: true });
+
+  // Concatenated in container order, so the stable sort breaks ties by container
+  const timed = Object.entries(batch.logs).flatMap(([container, lines]) =>
+    parseTimestampedLines(container, lines)
+  );
+  timed.sort((a, b) => (a.seconds === b.seconds ? a.nanos - b.nanos : a.seconds < b.seconds ? -1 : 1));
+
+  return {
+    lines: timed.map((entry) => entry.line),
+    errors: batch.errors,
+    waiting: batch.waiting,
+    truncated: batch.truncated,
+  };
+}
+
+function parseWindowTime(value: string, field: string): number {
+  const time = Date.parse(value);
+  if (Number.isNaN(time)) {
+    throw new Error(`${field} must be an RFC3339 time, e.g. 2024-05-01T10:00:00Z`);
+  }
+  return time;
+}
+
+// Epoch ms of a `--timestamps` line, or null for a line without one
+function lineTime(line: string): number | null {
+  const match = TIMESTAMP_PATTERN.exec(line);
+  const seconds = match ? Date.parse(`${match[1]}${match[3]}`) : NaN;
+  if (!match || Number.isNaN(seconds)) {
+    return null;
+  }
+  return seconds + Number((match[2] ?? '').padEnd(3, '0').slice(0, 3));
+}
+
+/**
+ * Save a container's logs for a time window to a gzip file, for sharing an
+ * incident's logs. Lines keep their kubelet timestamps. kubectl only
+ * supports the start of a window (`--since-time`), so the end is applied
+ * here: reading stops at the first line after `until`. Output is streamed
+ * through the compressor, so memory use doesn't grow with the logs.
+ */
+export async function exportLogs(spec: LogExportSpec): Promise<LogExportResult> {
+  const since = parseWindowTime(spec.since, 'since');
+  const until = spec.until ? parseWindowTime(spec.until, 'until') : null;
+  if (until !== null && until <= since) {
+    throw new Error('until must be after since');
+  }
+
+  const stamp = new Date().toISOString().replace(/[:.]/g, '-');
+  const outputPath =
+    spec.outputPath ||
+    path.join(app.getPath('userData'), 'logs', 'exports', `${spec.namespace}-${spec.pod}-${stamp}.log.gz`);
+  await mkdir(path.dirname(outputPath), { recursive: true });
+
+  const args = ['logs', spec.pod, '--timestamps', `--since-time=${new Date(since).toISOString()}`];
+  if (spec.container) {
+    args.push('-c', spec.container);
+  }
+
+  const child = spawnKubectl(spec.context, args, spec.namespace);
+  let stderr = '';
+  child.stderr.on('data', (chunk) => {
+    stderr += chunk.toString();
+  });
+  const exited = new Promise<number | null>((resolve, reject) => {
+    child.once('error', reject);
+    child.once('close', resolve);
+  });
+
+  let lines = 0;
+  let partialLine = '';
+  const decoder = new StringDecoder('utf8');
+  let reachedUntil = false;
+  const keep = (line: string, output: string[]) => {
+    const time = until === null ? null : lineTime(line);
+    if (time !== null && until !== null && time > until) {
+      reachedUntil = true;
+      // Logs are in time order, so nothing after this is wanted
+      child.kill();
+      return;
+    }
+    output.push(`${line}\n`);
+    lines++;
+  };
+
+  const filter = new Transform({
+    transform(chunk: Buffer, _encoding, callback) {
+      if (reachedUntil) {
+        callback();
+        return;
+      }
+      const split = (partialLine + decoder.write(chunk)).split('\n');
+      partialLine = split.pop() ?? '';
+      const output: string[] = [];
+      for (const line of split) {
+        keep(line, output);
+        if (reachedUntil) {
+          break;
+        }
+      }
+      callback(null, output.join(''));
+    },
+    flush(callback) {
+      const output: string[] = [];
+      partialLine += decoder.end();
+      if (partialLine && !reachedUntil) {
+        keep(partialLine, output);
+      }
+      callback(null, output.join(''));
+    },
+  });
+
+  try {
+    await pipeline(child.stdout, filter, createGzip(), createWriteStream(outputPath));
+    const code = await exited;
+    // Killed at `until` is a success; any other failure leaves no partial file
+    if (!reachedUntil && code !== 0) {
+      throw kubectlFailure({ code, stdout: '', stderr }, 'Failed to export logs');
+    }
+  } catch (error) {
+    child.kill();
+    await unlink(outputPath).catch(() => undefined);
+    throw error;
+  }
+
+  const { size } = await stat(outputPath);
+  console.log(`[Logs] Exported ${lines} line(s) of ${spec.namespace}/${spec.pod} to ${outputPath}`);
+  return { path: outputPath, lines, bytes: size, reachedUntil };
+}
+
+// Singleton instance
+export const logStreamManager = new LogStreamManager();
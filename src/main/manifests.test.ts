@@ -0,0 +1,130 @@
+import './testSupport';
+import assert from 'node:assert/strict';
+import { beforeEach, describe, it } from 'node:test';
+import * as kube from './kube';
+import { patchResource, setAnnotations, setLabels } from './manifests';
+
+describe('patchResource', () => {
+  let calls: { args: string[]; options: any }[];
+  let reply: { code: number; stdout: string; stderr: string };
+
+  beforeEach(() => {
+    calls = [];
+    reply = { code: 0, stdout: '{"kind":"Deployment","metadata":{"name":"web"}}', stderr: '' };
+    (kube as any).runKubectl = async (_context: string, args: string[], _namespace: string, options: any) => {
+      calls.push({ args, options });
+      return reply;
+    };
+  });
+
+  const patchInput = () => {
+    const [{ args, options }] = calls;
+    return args.includes('-p') ? args[args.indexOf('-p') + 1] : options.stdin;
+  };
+
+  it('sends a strategic merge patch and returns the patched object', async () => {
+    const patched = await patchResource('deployment', 'web', 'shop', { metadata: { labels: { a: 'b' } } },
+      'strategic', 'dev');
+    assert.equal(patched.metadata.name, 'web');
+    assert.ok(calls[0].args.includes('--type=strategic'));
+    assert.deepEqual(JSON.parse(patchInput()), { metadata: { labels: { a: 'b' } } });
+  });
+
+  it('sends a merge patch given as a string', async () => {
+    await patchResource('configmap', 'settings', 'shop', '{"data":{"mode":"on"}}', 'merge', 'dev');
+    assert.ok(calls[0].args.includes('--type=merge'));
+    assert.deepEqual(JSON.parse(patchInput()), { data: { mode: 'on' } });
+  });
+
+  it('sends a JSON patch array', async () => {
+    const operations = [{ op: 'replace', path: '/spec/replicas', value: 3 }];
+    await patchResource('deployment', 'web', 'shop', operations, 'json', 'dev');
+    assert.ok(calls[0].args.includes('--type=json'));
+    assert.deepEqual(JSON.parse(patchInput()), operations);
+  });
+
+  it('keeps the patch off the command line', { skip: process.platform === 'win32' }, async () => {
+    await patchResource('deployment', 'web', 'shop', { metadata: { annotations: { secret: 'x' } } }, 'merge', 'dev');
+    assert.ok(calls[0].args.includes('--patch-file=/dev/stdin'));
+    assert.ok(!calls[0].args.some((arg) => arg.includes('secret')));
+  });
+
+  it('rejects patches that do not match their type', async () => {
+    await assert.rejects(patchResource('deployment', 'web', 'shop', '{', 'merge', 'dev'), /not valid JSON/);
+    await assert.rejects(patchResource('deployment', 'web', 'shop', {}, 'json', 'dev'), /array of operations/);
+    await assert.rejects(patchResource('deployment', 'web', 'shop', [], 'merge', 'dev'), /JSON object/);
+    await assert.rejects(patchResource('deployment', 'web', 'shop', {}, 'apply' as any, 'dev'), /Unsupported/);
+    assert.equal(calls.length, 0);
+  });
+
+  it('reports a missing object as NotFound', async () => {
+    reply = { code: 1, stdout: '', stderr: 'Error from server (NotFound): deployments.apps "web" not found' };
+    await assert.rejects(patchResource('deployment', 'web', 'shop', {}, 'merge', 'dev'), { code: 'NotFound' });
+  });
+});
+
+describe('labels and annotations', () => {
+  let calls: string[][];
+  let reply: { code: number; stdout: string; stderr: string };
+
+  beforeEach(() => {
+    calls = [];
+    const metadata = {
+      name: 'web-0',
+      namespace: 'shop',
+      labels: { app: 'web', tier: 'frontend' },
+      annotations: { 'example.com/owner': 'team-a' },
+      resourceVersion: '812',
+    };
+    reply = { code: 0, stdout: JSON.stringify({ kind: 'Pod', metadata }), stderr: '' };
+    (kube as any).runKubectl = async (_context: string, args: string[]) => {
+      calls.push(args);
+      return reply;
+    };
+  });
+
+  it('adds labels and returns the updated metadata', async () => {
+    const metadata = await setLabels('pod', 'web-0', 'shop', { tier: 'frontend' }, false, 'dev');
+    assert.deepEqual(calls, [['label', 'pod', 'web-0', 'tier=frontend', '-o', 'json']]);
+    assert.deepEqual(metadata.labels, { app: 'web', tier: 'frontend' });
+    assert.equal(metadata.resourceVersion, '812');
+  });
+
+  it('passes overwrite on to kubectl', async () => {
+    await setAnnotations('pod', 'web-0', 'shop', { 'example.com/owner': 'team a, on call' }, true, 'dev');
+    assert.deepEqual(calls[0], [
+      'annotate', 'pod', 'web-0', 'example.com/owner=team a, on call', '--overwrite', '-o', 'json',
+    ]);
+  });
+
+  it('removes keys given a null value or a trailing "-"', async () => {
+    await setLabels('pod', 'web-0', 'shop', { tier: null, 'example.com/canary-': '' }, false, 'dev');
+    assert.deepEqual(calls[0].slice(3, 5), ['tier-', 'example.com/canary-']);
+  });
+
+  it('refuses invalid keys and label values without running kubectl', async () => {
+    const invalid: Record<string, string>[] = [
+      { '-tier': 'x' },
+      { 'Example.com/tier': 'x' },
+      { ['a'.repeat(64)]: 'x' },
+      { 'a/b/': 'x' },
+      { tier: 'front end' },
+    ];
+    for (const labels of invalid) {
+      await assert.rejects(setLabels('pod', 'web-0', 'shop', labels, false, 'dev'), /Invalid (label key|value)/);
+    }
+    await assert.rejects(setAnnotations('pod', 'web-0', 'shop', {}, false, 'dev'), /No annotations to change/);
+    assert.equal(calls.length, 0);
+  });
+
+  it('keeps kubectl\'s error types', async () => {
+    reply = { code: 1, stdout: '', stderr: 'Error from server (NotFound): pods "web-0" not found' };
+    await assert.rejects(setLabels('pod', 'web-0', 'shop', { a: 'b' }, false, 'dev'), { code: 'NotFound' });
+    reply = {
+      code: 1,
+      stdout: '',
+      stderr: 'Error from server (Forbidden): pods "web-0" is forbidden: User "dev" cannot patch resource "pods"',
+    };
+    await assert.rejects(setAnnotations('pod', 'web-0', 'shop', { a: 'b' }, false, 'dev'), { code: 'Forbidden' });
+  });
+});
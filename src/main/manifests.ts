@@ -0,0 +1,393 @@
+import YAML from 'yaml';
+import type {
+  AdmissionDenial,
+  ManifestDiffResult,
+  PatchType,
+  ResourceMetadata,
+  ResourceRef,
+  ResourceYamlItem,
+  ResourcesYamlResult,
+  ServerDryRunResult,
+} from '../common/kubeTypes';
+import { runKubectl, kubectlFailure, classifyKubectlError, getKubectlJson, KubeError } from './kube';
+import { neatObject } from './neatYaml';
+
+export interface ResourcesYamlOptions {
+  stripManagedFields?: boolean;
+  stripStatus?: boolean;
+  /** Drop server-populated fields and order keys canonically for stable diffs */
+  neat?: boolean;
+}
+
+const MAX_CONCURRENT_FETCHES = 6;
+const MAX_EXPORT_BYTES = 8 * 1024 * 1024;
+
+const PATCH_TYPES: PatchType[] = ['strategic', 'merge', 'json'];
+const JSON_PATCH_OPS = ['add', 'remove', 'replace', 'move', 'copy', 'test'];
+
+/**
+ * Parse and check a patch before sending it: JSON Patch is an array of
+ * operations, strategic and merge patches are a single object
+ */
+function parsePatch(patch: string | object, patchType: PatchType): string {
+  if (!PATCH_TYPES.includes(patchType)) {
+    throw new Error(`Unsupported patch type ${patchType}; use strategic, merge or json`);
+  }
+
+  let parsed: any = patch;
+  if (typeof patch === 'string') {
+    try {
+      parsed = JSON.parse(patch);
+    } catch (error) {
+      throw new Error(`Patch is not valid JSON: ${error instanceof Error ? error.message : String(error)}`);
+    }
+  }
+
+  if (patchType === 'json') {
+    const valid =
+      Array.isArray(parsed) &&
+      parsed.every(
+        (operation) => JSON_PATCH_OPS.includes(operation?.op) && typeof operation?.path === 'string'
+      );
+    if (!valid) {
+      throw new Error('A JSON patch must be an array of operations, each with an op and a path');
+    }
+  } else if (!parsed || typeof parsed !== 'object' || Array.isArray(parsed)) {
+    throw new Error(`A ${patchType} patch must be a JSON object`);
+  }
+
+  return JSON.stringify(parsed);
+}
+
+/**
+ * Change part of a live object with `kubectl patch` and return the object
+ * as stored afterwards. Fails with NotFound or Conflict like other writes.
+ * The patch goes in on stdin, so it stays out of the process list and
+ * isn't bounded by argv limits.
+ */
+export async function patchResource(
+  kind: string,
+  name: string,
+  namespace: string | undefined,
+  patch: string | object,
+  patchType: PatchType,
+  context: string
+): Promise<any> {
+  const body = parsePatch(patch, patchType);
+  // kubectl reads --patch-file as a path, and Windows has no /dev/stdin
+  const viaStdin = process.platform !== 'win32';
+  const patchArgs = viaStdin ? ['--patch-file=/dev/stdin'] : ['-p', body];
+  const result = await runKubectl(
+    context,
+    ['patch', kind, name, `--type=${patchType}`, ...patchArgs, '-o', 'json'],
+    namespace,
+    viaStdin ? { stdin: body } : {}
+  );
+  if (result.code !== 0) {
+    throw kubectlFailure(result, `Failed to patch ${kind}/${name}`);
+  }
+
+  try {
+    return JSON.parse(result.stdout);
+  } catch {
+    throw new KubeError('KubectlFailed', 'kubectl returned invalid JSON');
+  }
+}
+
+// Qualified name: [prefix/]name, the prefix a DNS subdomain
+const QUALIFIED_NAME_PATTERN = /^[A-Za-z0-9](?:[-A-Za-z0-9_.]*[A-Za-z0-9])?$/;
+const DNS_SUBDOMAIN_PATTERN = /^[a-z0-9](?:[-a-z0-9]*[a-z0-9])?(?:\.[a-z0-9](?:[-a-z0-9]*[a-z0-9])?)*$/;
+const MAX_NAME_LENGTH = 63;
+const MAX_PREFIX_LENGTH = 253;
+
+type MetadataField = 'label' | 'annotation';
+
+function metadataKeyProblem(key: string): string | null {
+  const slash = key.lastIndexOf('/');
+  const prefix = slash >= 0 ? key.slice(0, slash) : null;
+  const name = slash >= 0 ? key.slice(slash + 1) : key;
+
+  if (prefix !== null && (prefix.length > MAX_PREFIX_LENGTH || !DNS_SUBDOMAIN_PATTERN.test(prefix))) {
+    return 'the prefix must be a lowercase DNS subdomain (e.g. example.com)';
+  }
+  if (name.length === 0 || name.length > MAX_NAME_LENGTH) {
+    return `the name must be 1-${MAX_NAME_LENGTH} characters`;
+  }
+  if (!QUALIFIED_NAME_PATTERN.test(name)) {
+    return 'the name must be alphanumeric, "-", "_" or ".", starting and ending with an alphanumeric character';
+  }
+  return null;
+}
+
+/**
+ * kubectl label/annotate arguments: `key=value` to set, `key-` to remove.
+ * A null value or a key ending in "-" removes.
+ */
+function metadataArgs(field: MetadataField, changes: Record<string, string | null>): string[] {
+  const args: string[] = [];
+  for (const [rawKey, value] of Object.entries(changes)) {
+    const remove = value === null || rawKey.endsWith('-');
+    const key = rawKey.endsWith('-') ? rawKey.slice(0, -1) : rawKey;
+
+    const problem = metadataKeyProblem(key);
+    if (problem) {
+      throw new Error(`Invalid ${field} key "${key}": ${problem}`);
+    }
+    if (remove) {
+      args.push(`${key}-`);
+      continue;
+    }
+    if (field === 'label' && value !== '' && (value.length > MAX_NAME_LENGTH || !QUALIFIED_NAME_PATTERN.test(value))) {
+      throw new Error(
+        `Invalid value for label "${key}": must be empty or up to ${MAX_NAME_LENGTH} alphanumeric, "-", "_" or "." ` +
+          'characters, starting and ending with an alphanumeric character'
+      );
+    }
+    args.push(`${key}=${value}`);
+  }
+
+  if (args.length === 0) {
+    throw new Error(`No ${field}s to change`);
+  }
+  return args;
+}
+
+async function updateMetadata(
+  field: MetadataField,
+  kind: string,
+  name: string,
+  namespace: string | undefined,
+  changes: Record<string, string | null>,
+  overwrite: boolean,
+  context: string
+): Promise<ResourceMetadata> {
+  const args = [field === 'label' ? 'label' : 'annotate', kind, name, ...metadataArgs(field, changes)];
+  if (overwrite) {
+    args.push('--overwrite');
+  }
+
+  const result = await runKubectl(context, [...args, '-o', 'json'], namespace);
+  if (result.code !== 0) {
+    throw kubectlFailure(result, `Failed to update ${field}s of ${kind}/${name}`);
+  }
+
+  let object: any;
+  try {
+    object = JSON.parse(result.stdout);
+  } catch {
+    throw new KubeError('KubectlFailed', 'kubectl returned invalid JSON');
+  }
+  const metadata = object?.metadata ?? {};
+  return {
+    name: metadata.name ?? name,
+    namespace: metadata.namespace,
+    labels: metadata.labels ?? {},
+    annotations: metadata.annotations ?? {},
+    resourceVersion: metadata.resourceVersion ?? null,
+  };
+}
+
+/**
+ * Add, change or remove (null value, or "key-") labels with `kubectl
+ * label`. Changing an existing label needs overwrite, as in kubectl.
+ * Keys and values are validated before anything is sent.
+ */
+export async function setLabels(
+  kind: string,
+  name: string,
+  namespace: string | undefined,
+  labels: Record<string, string | null>,
+  overwrite: boolean,
+  context: string
+): Promise<ResourceMetadata> {
+  return updateMetadata('label', kind, name, namespace, labels, overwrite, context);
+}
+
+/**
+ * Same as setLabels for annotations (`kubectl annotate`); values may be
+ * any text
+ */
+export async function setAnnotations(
+  kind: string,
+  name: string,
+  namespace: string | undefined,
+  annotations: Record<string, string | null>,
+  overwrite: boolean,
+  context: string
+): Promise<ResourceMetadata> {
+  return updateMetadata('annotation', kind, name, namespace, annotations, overwrite, context);
+}
+
+/**
+ * Diff a manifest against the live cluster with `kubectl diff -f -`.
+ * kubectl exits 1 when there are differences, so only >1 is an error.
+ */
+export async function diffManifest(
+  content: string,
+  context: string,
+  namespace?: string
+): Promise<ManifestDiffResult> {
+  if (!content.trim()) {
+    throw new Error('Manifest content is empty');
+  }
+
+  const result = await runKubectl(context, ['diff', '-f', '-'], namespace, { stdin: content });
+
+  if (result.code === 0) {
+    return { inSync: true, diff: '' };
+  }
+
+  if (result.code === 1) {
+    return { inSync: false, diff: result.stdout };
+  }
+
+  throw kubectlFailure(result, 'Failed to diff manifest');
+}
+
+// admission webhook "validation.gatekeeper.sh" denied the request: ...
+const WEBHOOK_DENIAL_PATTERN = /admission webhook "([^"]+)" denied the request:?\s*(.*)/i;
+// ValidatingAdmissionPolicy 'name' with binding 'binding' denied request: ...
+const POLICY_DENIAL_PATTERN = /ValidatingAdmissionPolicy '([^']+)'(?: with binding '[^']+')? denied request:?\s*(.*)/i;
+const WEBHOOK_TIMEOUT_PATTERN = /failed calling webhook "([^"]+)".*(?:context deadline exceeded|timeout|timed out)/i;
+const SERVER_ERROR_PATTERN = /^(?:Error from server|error:|error when|error validating)/i;
+
+/**
+ * Run a manifest through admission (webhooks such as Gatekeeper or Kyverno,
+ * and ValidatingAdmissionPolicies) with `kubectl apply --dry-run=server`.
+ * Nothing is persisted. Denials are reported separately from schema errors;
+ * a webhook that times out is an error rather than a decision.
+ */
+export async function serverDryRunApply(
+  content: string,
+  context: string,
+  namespace?: string
+): Promise<ServerDryRunResult> {
+  if (!content.trim()) {
+    throw new Error('Manifest content is empty');
+  }
+
+  const result = await runKubectl(context, ['apply', '--dry-run=server', '-f', '-'], namespace, { stdin: content });
+
+  const accepted = result.stdout
+    .split('\n')
+    .map((line) => line.trim())
+    .filter((line) => line.length > 0);
+  const denials: AdmissionDenial[] = [];
+  const errors: string[] = [];
+  const warnings: string[] = [];
+
+  for (const rawLine of result.stderr.split('\n')) {
+    const line = rawLine.trim();
+    if (!line) {
+      continue;
+    }
+
+    if (/^Warning:/i.test(line)) {
+      warnings.push(line.replace(/^Warning:\s*/i, ''));
+      continue;
+    }
+
+    const timeout = line.match(WEBHOOK_TIMEOUT_PATTERN);
+    if (timeout) {
+      throw new KubeError(
+        'WebhookTimeout',
+        `Admission webhook "${timeout[1]}" did not respond in time, so no admission decision was made. ${line}`
+      );
+    }
+
+    const denial = line.match(WEBHOOK_DENIAL_PATTERN) ?? line.match(POLICY_DENIAL_PATTERN);
+    if (denial) {
+      denials.push({ source: denial[1], message: denial[2] || line });
+      continue;
+    }
+
+    if (SERVER_ERROR_PATTERN.test(line)) {
+      errors.push(line);
+    } else if (errors.length > 0) {
+      // Continuation of a multi-line validation error
+      errors[errors.length - 1] += `\n${line}`;
+    }
+  }
+
+  if (result.code !== 0 && denials.length === 0 && errors.length === 0) {
+    throw kubectlFailure(result, 'Server dry-run failed');
+  }
+
+  // Connection, auth and RBAC problems aren't about the manifest
+  const code = classifyKubectlError(errors.join('\n'));
+  if (denials.length === 0 && (code === 'Unreachable' || code === 'Unauthorized' || code === 'Forbidden')) {
+    throw kubectlFailure(result, 'Server dry-run failed');
+  }
+
+  return {
+    outcome: denials.length > 0 ? 'denied' : errors.length > 0 ? 'invalid' : 'allowed',
+    accepted,
+    denials,
+    errors,
+    warnings,
+  };
+}
+
+function cleanObject(object: any, options: ResourcesYamlOptions): any {
+  if (options.stripStatus) {
+    delete object.status;
+  }
+  if (options.stripManagedFields && object.metadata) {
+    delete object.metadata.managedFields;
+  }
+  return options.neat ? neatObject(object) : object;
+}
+
+/**
+ * Fetch several objects concurrently and join them into one multi-document
+ * YAML stream. Failures are reported per item instead of failing the batch.
+ */
+export async function getResourcesYaml(
+  refs: ResourceRef[],
+  context: string,
+  options: ResourcesYamlOptions = {}
+): Promise<ResourcesYamlResult> {
+  const documents: (string | null)[] = new Array(refs.length).fill(null);
+  const items: ResourceYamlItem[] = refs.map((ref) => ({ ref, ok: false }));
+  let totalBytes = 0;
+  let next = 0;
+
+  const worker = async () => {
+    while (next < refs.length) {
+      const index = next++;
+      const ref = refs[index];
+
+      if (totalBytes > MAX_EXPORT_BYTES) {
+        items[index].error = `Export size limit of ${MAX_EXPORT_BYTES} bytes reached`;
+        continue;
+      }
+
+      try {
+        const object = await getKubectlJson(context, ['get', `${ref.kind}/${ref.name}`], ref.namespace);
+        const document = YAML.stringify(cleanObject(object, options));
+        totalBytes += Buffer.byteLength(document, 'utf8');
+
+        if (totalBytes > MAX_EXPORT_BYTES) {
+          items[index].error = `Export size limit of ${MAX_EXPORT_BYTES} bytes reached`;
+          continue;
+        }
+
+        documents[index] = document;
+        items[index].ok = true;
+      } catch (error) {
+        items[index].error = error instanceof Error ? error.message : String(error);
+        if (error instanceof KubeError) {
+          items[index].code = error.code;
+        }
+      }
+    }
+  };
+
+  await Promise.all(
+    Array.from({ length: Math.min(MAX_CONCURRENT_FETCHES, refs.length) }, () => worker())
+  );
+
+  // Keep the documents in the order they were requested
+  const yaml = documents.filter((document): document is string => document !== null).join('---\n');
+  return { yaml, items };
+}
@@ -0,0 +1,85 @@
+import { readFile } from 'fs/promises';
+import path from 'path';
+import YAML from 'yaml';
+import type { MergedKubeconfig } from '../common/kubeTypes';
+import { getKubeconfigSources } from './kube';
+import { redactKubeconfig } from './redact';
+
+// Named lists merged by entry name; the first file to define a name wins
+const NAMED_LISTS = ['clusters', 'contexts', 'users'] as const;
+
+// Path fields kubectl resolves relative to the file that set them
+const PATH_FIELDS: Record<(typeof NAMED_LISTS)[number], string[]> = {
+  clusters: ['certificate-authority'],
+  contexts: [],
+  users: ['client-certificate', 'client-key', 'tokenFile'],
+};
+
+export function resolvePaths(list: string, entry: any, baseDir: string): any {
+  const body = entry?.[list.slice(0, -1)];
+  if (!body || typeof body !== 'object') {
+    return entry;
+  }
+
+  for (const field of PATH_FIELDS[list as keyof typeof PATH_FIELDS]) {
+    if (typeof body[field] === 'string' && body[field] && !path.isAbsolute(body[field])) {
+      body[field] = path.resolve(baseDir, body[field]);
+    }
+  }
+  // Only commands given as a relative path, not ones looked up on PATH
+  const command = body.exec?.command;
+  if (typeof command === 'string' && command.includes('/') && !path.isAbsolute(command)) {
+    body.exec.command = path.resolve(baseDir, command);
+  }
+  return entry;
+}
+
+/**
+ * Merge every kubeconfig source the way kubectl does (first file wins for
+ * each named entry, `current-context` and other top-level keys) and return
+ * it as one YAML document with credentials redacted, for a "show my
+ * effective config" view. Missing files are skipped, as kubectl does.
+ */
+export async function getMergedKubeconfig(): Promise<MergedKubeconfig> {
+  const sources = getKubeconfigSources();
+  const merged: Record<string, any> = { apiVersion: 'v1', kind: 'Config' };
+  const lists: Record<string, any[]> = { clusters: [], contexts: [], users: [] };
+  const used: string[] = [];
+  const missing: string[] = [];
+
+  for (const source of sources) {
+    let config: any;
+    try {
+      config = YAML.parse(await readFile(source, 'utf8')) ?? {};
+    } catch (error) {
+      if ((error as NodeJS.ErrnoException)?.code === 'ENOENT') {
+        missing.push(source);
+        continue;
+      }
+      throw new Error(`Failed to read ${source}: ${error instanceof Error ? error.message : error}`);
+    }
+    used.push(source);
+
+    const baseDir = path.dirname(source);
+    for (const list of NAMED_LISTS) {
+      for (const entry of Array.isArray(config[list]) ? config[list] : []) {
+        if (entry?.name && !lists[list].some((existing) => existing.name === entry.name)) {
+          lists[list].push(resolvePaths(list, entry, baseDir));
+        }
+      }
+    }
+
+    for (const [key, value] of Object.entries(config)) {
+      if (NAMED_LISTS.includes(key as (typeof NAMED_LISTS)[number]) || key === 'apiVersion' || key === 'kind') {
+        continue;
+      }
+      // An empty current-context doesn't shadow a later file's
+      if (merged[key] === undefined || (key === 'current-context' && !merged[key])) {
+        merged[key] = value;
+      }
+    }
+  }
+
+  const redacted = redactKubeconfig({ ...merged, ...lists });
+  return { yaml: YAML.stringify(redacted), sources: used, missing };
+}
@@ -0,0 +1,161 @@
+import { randomUUID } from 'crypto';
+import type { MetricsSample, MetricsTargetKind, MetricsUpdateEvent } from '../common/kubeTypes';
+import { runKubectl, kubectlFailure } from './kube';
+import { emitToRenderer } from './events';
+import { parseKubectlTable } from './table';
+import { parseCpuMillicores, parseMemoryBytes } from './quantity';
+import { operationRegistry } from './operations';
+
+export interface MetricsStreamSpec {
+  context: string;
+  kind: MetricsTargetKind;
+  namespace?: string;
+  intervalMs?: number;
+  view?: string;
+}
+
+interface MetricsStream {
+  spec: MetricsStreamSpec;
+  timer: NodeJS.Timeout | null;
+  inFlight: boolean;
+  history: Map<string, MetricsSample[]>;
+}
+
+const DEFAULT_INTERVAL_MS = 15000;
+// metrics-server only refreshes every ~15s, polling faster is wasted work
+const MIN_INTERVAL_MS = 5000;
+// History is capped both by sample count and by age
+const MAX_SAMPLES_PER_TARGET = 120;
+const MAX_SAMPLE_AGE_MS = 30 * 60 * 1000;
+
+/**
+ * Polls `kubectl top` on an interval, emitting `metrics:update` and keeping
+ * a short per-pod/node history for sparkline charts
+ */
+export class MetricsManager {
+  private streams: Map<string, MetricsStream> = new Map();
+
+  startMetricsStream(spec: MetricsStreamSpec): string {
+    const id = randomUUID();
+    const stream: MetricsStream = { spec, timer: null, inFlight: false, history: new Map() };
+    const interval = Math.max(spec.intervalMs ?? DEFAULT_INTERVAL_MS, MIN_INTERVAL_MS);
+
+    this.streams.set(id, stream);
+    operationRegistry.register(
+      id,
+      'metrics',
+      { context: spec.context, namespace: spec.namespace, view: spec.view },
+      () => this.stopMetricsStream(id)
+    );
+    stream.timer = setInterval(() => this.poll(id, stream), interval);
+    this.poll(id, stream);
+
+    console.log(`[Metrics ${id}] Started for ${spec.kind}s every ${interval}ms`);
+    return id;
+  }
+
+  stopMetricsStream(id: string): boolean {
+    const stream = this.streams.get(id);
+    if (!stream) {
+      return false;
+    }
+
+    if (stream.timer) {
+      clearInterval(stream.timer);
+      stream.timer = null;
+    }
+    // Drop the history with the stream so stale data can't be read back
+    stream.history.clear();
+    this.streams.delete(id);
+    operationRegistry.unregister(id);
+    console.log(`[Metrics ${id}] Stopped`);
+    return true;
+  }
+
+  stopAllMetricsStreams(): void {
+    for (const id of Array.from(this.streams.keys())) {
+      this.stopMetricsStream(id);
+    }
+  }
+
+  getMetricsHistory(id: string): Record<string, MetricsSample[]> {
+    const stream = this.streams.get(id);
+    if (!stream) {
+      throw new Error(`Metrics stream with id ${id} not found`);
+    }
+
+    this.pruneHistory(stream, Date.now());
+    const result: Record<string, MetricsSample[]> = {};
+    for (const [name, samples] of stream.history) {
+      result[name] = samples.slice();
+    }
+    return result;
+  }
+
+  private async poll(id: string, stream: MetricsStream): Promise<void> {
+    // Skip a tick rather than piling up slow `kubectl top` calls
+    if (stream.inFlight) {
+      return;
+    }
+    stream.inFlight = true;
+
+    try {
+      const { spec } = stream;
+      const args = spec.kind === 'node' ? ['top', 'node'] : ['top', 'pod'];
+      const result = await runKubectl(spec.context, args, spec.kind === 'pod' ? spec.namespace : undefined);
+
+      if (!this.streams.has(id)) {
+        return;
+      }
+      if (result.code !== 0) {
+        const error = kubectlFailure(result, 'kubectl top failed');
+        emitToRenderer('metrics:error', id, error.message);
+        return;
+      }
+
+      const timestamp = Date.now();
+      const samples: Record<string, MetricsSample> = {};
+
+      for (const row of parseKubectlTable(result.stdout)) {
+        if (!row.NAME) {
+          continue;
+        }
+        const sample: MetricsSample = {
+          timestamp,
+          cpuMillicores: parseCpuMillicores(row['CPU(cores)']),
+          memoryBytes: parseMemoryBytes(row['MEMORY(bytes)']),
+        };
+        samples[row.NAME] = sample;
+
+        const history = stream.history.get(row.NAME) ?? [];
+        history.push(sample);
+        stream.history.set(row.NAME, history);
+      }
+
+      this.pruneHistory(stream, timestamp);
+
+      const event: MetricsUpdateEvent = { streamId: id, timestamp, samples };
+      emitToRenderer('metrics:update', event);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : 'kubectl top failed';
+      emitToRenderer('metrics:error', id, message);
+    } finally {
+      stream.inFlight = false;
+    }
+  }
+
+  private pruneHistory(stream: MetricsStream, now: number): void {
+    for (const [name, samples] of stream.history) {
+      const fresh = samples.filter((sample) => now - sample.timestamp <= MAX_SAMPLE_AGE_MS);
+      const capped = fresh.slice(-MAX_SAMPLES_PER_TARGET);
+      if (capped.length === 0) {
+        stream.history.delete(name);
+      } else {
+        stream.history.set(name, capped);
+      }
+    }
+  }
+}
+
+// Singleton instance
+export const metricsManager = new MetricsManager();
@@ -0,0 +1,248 @@
+import { mkdir, readdir, readFile, writeFile } from 'fs/promises';
+import path from 'path';
+import YAML from 'yaml';
+import type {
+  ApplyObjectOutcome,
+  DirectoryApplyFileResult,
+  DirectoryApplyResult,
+  NamespaceExportResult,
+} from '../common/kubeTypes';
+import { getKubectlJson, kubectlFailure, runKubectl } from './kube';
+import { neatObject } from './neatYaml';
+import { APPLY_OUTCOME_PATTERN } from './applyStream';
+
+export interface NamespaceExportSpec {
+  namespace: string;
+  context: string;
+  outputDir: string;
+  /** Resource kinds to export; Secrets are only included when listed */
+  kinds?: string[];
+}
+
+export interface DirectoryApplySpec {
+  dir: string;
+  context: string;
+  /** Applied into this namespace; exported files carry none of their own */
+  namespace?: string;
+}
+
+const DEFAULT_EXPORT_KINDS = [
+  'serviceaccounts',
+  'roles',
+  'rolebindings',
+  'configmaps',
+  'persistentvolumeclaims',
+  'services',
+  'deployments',
+  'statefulsets',
+  'daemonsets',
+  'cronjobs',
+  'ingresses',
+  'networkpolicies',
+  'horizontalpodautoscalers',
+  'poddisruptionbudgets',
+];
+const MAX_CONCURRENT_FETCHES = 4;
+const MANIFEST_FILE_PATTERN = /\.(?:ya?ml|json)$/i;
+
+// Objects every namespace gets automatically; applying them elsewhere would clash
+const GENERATED_NAMES: Record<string, string[]> = {
+  ConfigMap: ['kube-root-ca.crt'],
+  ServiceAccount: ['default'],
+};
+
+// Kinds that others depend on go first; anything unlisted is applied after these
+const APPLY_ORDER = [
+  'Namespace',
+  'CustomResourceDefinition',
+  'PriorityClass',
+  'StorageClass',
+  'ServiceAccount',
+  'ClusterRole',
+  'Role',
+  'ClusterRoleBinding',
+  'RoleBinding',
+  'ResourceQuota',
+  'LimitRange',
+  'ConfigMap',
+  'Secret',
+  'PersistentVolume',
+  'PersistentVolumeClaim',
+  'Service',
+];
+
+function applyRank(kind: string): number {
+  const index = APPLY_ORDER.indexOf(kind);
+  return index >= 0 ? index : APPLY_ORDER.length;
+}
+
+function isControlled(object: any): boolean {
+  return (object?.metadata?.ownerReferences ?? []).some((owner: any) => owner?.controller === true);
+}
+
+function isGenerated(object: any): boolean {
+  const kind: string = object?.kind ?? '';
+  const name: string = object?.metadata?.name ?? '';
+  if (GENERATED_NAMES[kind]?.includes(name)) {
+    return true;
+  }
+  // Token secrets are created by the token controller for their service account
+  return kind === 'Secret' && object?.type === 'kubernetes.io/service-account-token';
+}
+
+/**
+ * An object as it would be written by hand: no status or server-set
+ * metadata, and no namespace so the snapshot can be applied to another
+ * one. Service IPs are dropped because they are allocated per cluster.
+ */
+function cleanForSnapshot(object: any): any {
+  const clean = neatObject(object);
+  delete clean.status;
+  if (clean.metadata) {
+    delete clean.metadata.namespace;
+    delete clean.metadata.ownerReferences;
+  }
+  if (clean.kind === 'Service' && clean.spec && clean.spec.clusterIP !== 'None') {
+    delete clean.spec.clusterIP;
+    delete clean.spec.clusterIPs;
+  }
+  if (clean.kind === 'PersistentVolumeClaim' && clean.spec) {
+    // Bound to a volume of this cluster; let the new claim be provisioned
+    delete clean.spec.volumeName;
+  }
+  return clean;
+}
+
+function fileNameFor(kind: string, name: string): string {
+  return `${kind.toLowerCase()}-${name.replace(/[^\w.-]+/g, '_')}.yaml`;
+}
+
+/**
+ * Write the namespace's objects of the given kinds to one YAML file each,
+ * cleaned of server state, for a backup or to copy the namespace
+ * elsewhere with applyDirectory. Objects a controller owns (ReplicaSets,
+ * pods) and per-namespace defaults are left out. Kinds that can't be
+ * listed are reported without failing the export.
+ */
+export async function exportNamespace(spec: NamespaceExportSpec): Promise<NamespaceExportResult> {
+  const kinds = spec.kinds && spec.kinds.length > 0 ? spec.kinds : DEFAULT_EXPORT_KINDS;
+  const outputDir = path.resolve(spec.outputDir);
+  await mkdir(outputDir, { recursive: true });
+
+  const result: NamespaceExportResult = { outputDir, files: [], errors: {}, skipped: 0 };
+  let next = 0;
+
+  const worker = async () => {
+    while (next < kinds.length) {
+      const kind = kinds[next++];
+      let items: any[];
+      try {
+        items = (await getKubectlJson(spec.context, ['get', kind], spec.namespace))?.items ?? [];
+      } catch (error) {
+        result.errors[kind] = error instanceof Error ? error.message : String(error);
+        continue;
+      }
+
+      for (const object of items) {
+        if (isControlled(object) || isGenerated(object)) {
+          result.skipped++;
+          continue;
+        }
+
+        const clean = cleanForSnapshot(object);
+        const name: string = clean.metadata?.name ?? 'unnamed';
+        const filePath = path.join(outputDir, fileNameFor(clean.kind ?? kind, name));
+        // Secrets hold credentials; keep them private to the user
+        await writeFile(filePath, YAML.stringify(clean), { mode: clean.kind === 'Secret' ? 0o600 : 0o644 });
+        result.files.push({ kind: clean.kind ?? kind, name, path: filePath });
+      }
+    }
+  };
+
+  await Promise.all(Array.from({ length: Math.min(MAX_CONCURRENT_FETCHES, kinds.length) }, () => worker()));
+
+  result.files.sort((a, b) => a.path.localeCompare(b.path));
+  console.log(`[Snapshot] Exported ${result.files.length} object(s) from ${spec.namespace} to ${outputDir}`);
+  return result;
+}
+
+function kindsIn(content: string, file: string): { kinds: string[]; crds: string[] } {
+  const kinds: string[] = [];
+  const crds: string[] = [];
+  YAML.parseAllDocuments(content).forEach((document, index) => {
+    if (document.errors.length > 0) {
+      throw new Error(`${file}: document ${index + 1} is not valid YAML: ${document.errors[0].message}`);
+    }
+    const object = document.toJS();
+    if (object?.kind) {
+      kinds.push(String(object.kind));
+      if (object.kind === 'CustomResourceDefinition' && object.metadata?.name) {
+        crds.push(String(object.metadata.name));
+      }
+    }
+  });
+  return { kinds, crds };
+}
+
+/**
+ * Apply every manifest file in a directory, as written by exportNamespace,
+ * one file at a time: namespaces and CRDs first, then RBAC, config and
+ * storage, then workloads, so objects exist before what references them.
+ * All files are parsed before anything is applied. Returns a result per
+ * file; a failed file doesn't stop the others.
+ */
+export async function applyDirectory(spec: DirectoryApplySpec): Promise<DirectoryApplyResult> {
+  const dir = path.resolve(spec.dir);
+  const entries = await readdir(dir, { withFileTypes: true });
+  const files = await Promise.all(
+    entries
+      .filter((entry) => entry.isFile() && MANIFEST_FILE_PATTERN.test(entry.name))
+      .map(async (entry) => {
+        const file = path.join(dir, entry.name);
+        const content = await readFile(file, 'utf8');
+        return { file, content, ...kindsIn(content, entry.name) };
+      })
+  );
+  if (files.length === 0) {
+    throw new Error(`No .yaml, .yml or .json files in ${dir}`);
+  }
+
+  const rank = (kinds: string[]) => Math.min(...kinds.map(applyRank), APPLY_ORDER.length);
+  files.sort((a, b) => rank(a.kinds) - rank(b.kinds) || a.file.localeCompare(b.file));
+
+  const results: DirectoryApplyFileResult[] = [];
+  const crds = files.flatMap((entry) => entry.crds);
+  let waitedForCrds = false;
+  for (const entry of files) {
+    // Custom resources can't be applied until their CRDs are served
+    if (!waitedForCrds && crds.length > 0 && rank(entry.kinds) > applyRank('CustomResourceDefinition')) {
+      waitedForCrds = true;
+      await runKubectl(spec.context, [
+        'wait',
+        '--for=condition=Established',
+        '--timeout=30s',
+        ...crds.map((name) => `crd/${name}`),
+      ]);
+    }
+
+    let outcome: ApplyObjectOutcome = 'error';
+    let message: string;
+    try {
+      const result = await runKubectl(spec.context, ['apply', '-f', '-'], spec.namespace, { stdin: entry.content });
+      if (result.code === 0) {
+        message = result.stdout.trim();
+        const match = message.match(APPLY_OUTCOME_PATTERN);
+        outcome = match ? (match[1] as ApplyObjectOutcome) : 'configured';
+      } else {
+        message = kubectlFailure(result, `Failed to apply ${path.basename(entry.file)}`).message;
+      }
+    } catch (error) {
+      message = error instanceof Error ? error.message : String(error);
+    }
+    results.push({ file: entry.file, kinds: entry.kinds, outcome, message });
+  }
+
+  const failed = results.filter((result) => result.outcome === 'error').length;
+  console.log(`[Snapshot] Applied ${results.length - failed}/${results.length} file(s) from ${dir}`);
+  return { files: results, applied: results.length - failed, failed };
+}
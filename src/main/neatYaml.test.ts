@@ -0,0 +1,45 @@
+import './testSupport';
+import assert from 'node:assert/strict';
+import { describe, it } from 'node:test';
+import { neatObject } from './neatYaml';
+
+const pod = {
+  status: { phase: 'Running' },
+  spec: { containers: [{ name: 'web', image: 'nginx' }, { image: 'envoy', name: 'proxy' }] },
+  metadata: {
+    uid: 'abc',
+    resourceVersion: '42',
+    managedFields: [{ manager: 'kubectl' }],
+    creationTimestamp: '2026-01-01T00:00:00Z',
+    labels: { app: 'web' },
+    namespace: 'shop',
+    name: 'web-0',
+    annotations: { 'kubectl.kubernetes.io/last-applied-configuration': '{}' },
+  },
+  kind: 'Pod',
+  apiVersion: 'v1',
+};
+
+describe('neatObject', () => {
+  it('drops server-populated metadata', () => {
+    const neat = neatObject(pod);
+    assert.deepEqual(neat.metadata, { name: 'web-0', namespace: 'shop', labels: { app: 'web' } });
+  });
+
+  it('orders fields the way manifests are read, status last', () => {
+    const neat = neatObject({ ...pod, extra: true });
+    assert.deepEqual(Object.keys(neat), ['apiVersion', 'kind', 'metadata', 'spec', 'extra', 'status']);
+    assert.deepEqual(Object.keys(neat.spec.containers[1]), ['name', 'image']);
+  });
+
+  it('keeps array order and leaves the input alone', () => {
+    const neat = neatObject(pod);
+    assert.deepEqual(neat.spec.containers.map((container: any) => container.name), ['web', 'proxy']);
+    assert.equal(pod.metadata.uid, 'abc');
+  });
+
+  it('gives the same result for the same object fetched twice', () => {
+    const refetched = { ...pod, metadata: { ...pod.metadata, resourceVersion: '43', uid: 'def' } };
+    assert.equal(JSON.stringify(neatObject(refetched)), JSON.stringify(neatObject(pod)));
+  });
+});
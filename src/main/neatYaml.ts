@@ -0,0 +1,73 @@
+// Root keys in the order people read a manifest; anything else sorts between spec and status
+const ROOT_KEY_ORDER = ['apiVersion', 'kind', 'metadata', 'spec', 'data', 'stringData', 'type'];
+const METADATA_KEY_ORDER = ['name', 'generateName', 'namespace', 'labels', 'annotations', 'ownerReferences', 'finalizers'];
+
+// Server-populated metadata that differs between otherwise identical objects
+const NOISY_METADATA_FIELDS = ['managedFields', 'resourceVersion', 'uid', 'generation', 'creationTimestamp', 'selfLink'];
+const NOISY_ANNOTATIONS = ['kubectl.kubernetes.io/last-applied-configuration'];
+
+function isPlainObject(value: any): value is Record<string, any> {
+  return !!value && typeof value === 'object' && !Array.isArray(value);
+}
+
+function orderKeys(keys: string[], priority: string[], last: string[] = []): string[] {
+  const rank = (key: string) => {
+    if (last.includes(key)) {
+      return priority.length + 1 + last.indexOf(key);
+    }
+    const index = priority.indexOf(key);
+    return index >= 0 ? index : priority.length;
+  };
+  return [...keys].sort((a, b) => rank(a) - rank(b) || (a < b ? -1 : a > b ? 1 : 0));
+}
+
+/**
+ * Rebuild a value with map keys in a fixed order. Array order is kept
+ * as-is since it is meaningful (containers, ports, env).
+ */
+function canonicalize(value: any, priority: string[] = ['name']): any {
+  if (Array.isArray(value)) {
+    return value.map((item) => canonicalize(item));
+  }
+  if (!isPlainObject(value)) {
+    return value;
+  }
+
+  const result: Record<string, any> = {};
+  for (const key of orderKeys(Object.keys(value), priority)) {
+    result[key] = canonicalize(value[key]);
+  }
+  return result;
+}
+
+/**
+ * Strip server noise from an object and put its fields in a stable order
+ * (metadata, spec, ..., status), so two fetches of the same or similar
+ * objects diff cleanly. The input is not modified.
+ */
+export function neatObject(object: any): any {
+  if (!isPlainObject(object)) {
+    return object;
+  }
+
+  const copy = JSON.parse(JSON.stringify(object));
+  if (isPlainObject(copy.metadata)) {
+    for (const field of NOISY_METADATA_FIELDS) {
+      delete copy.metadata[field];
+    }
+    if (isPlainObject(copy.metadata.annotations)) {
+      for (const annotation of NOISY_ANNOTATIONS) {
+        delete copy.metadata.annotations[annotation];
+      }
+      if (Object.keys(copy.metadata.annotations).length === 0) {
+        delete copy.metadata.annotations;
+      }
+    }
+  }
+
+  const result: Record<string, any> = {};
+  for (const key of orderKeys(Object.keys(copy), ROOT_KEY_ORDER, ['status'])) {
+    result[key] = key === 'metadata' ? canonicalize(copy.metadata, METADATA_KEY_ORDER) : canonicalize(copy[key]);
+  }
+  return result;
+}
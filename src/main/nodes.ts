@@ -0,0 +1,68 @@
+import type { NodeCondition, NodeStatus, NodeSummary, NodeTaint } from '../common/kubeTypes';
+import { getKubectlJson } from './kube';
+
+function addressesOfType(node: any, type: string): string[] {
+  const addresses: any[] = node?.status?.addresses ?? [];
+  return addresses.filter((address) => address?.type === type).map((address) => address.address);
+}
+
+/**
+ * Conditions, taints and resources of a node from `kubectl get node -o json`.
+ * Heartbeat times are included so the UI can flag stale conditions.
+ */
+export async function getNodeStatus(name: string, context: string): Promise<NodeStatus> {
+  const node = await getKubectlJson(context, ['get', 'node', name]);
+
+  const conditions: NodeCondition[] = (node?.status?.conditions ?? []).map((condition: any) => ({
+    type: condition.type,
+    status: condition.status,
+    reason: condition.reason ?? null,
+    message: condition.message ?? null,
+    lastHeartbeatTime: condition.lastHeartbeatTime ?? null,
+    lastTransitionTime: condition.lastTransitionTime ?? null,
+  }));
+
+  const taints: NodeTaint[] = (node?.spec?.taints ?? []).map((taint: any) => ({
+    key: taint.key,
+    value: taint.value ?? null,
+    effect: taint.effect,
+  }));
+
+  return {
+    name: node?.metadata?.name ?? name,
+    conditions,
+    taints,
+    unschedulable: node?.spec?.unschedulable === true,
+    capacity: node?.status?.capacity ?? {},
+    allocatable: node?.status?.allocatable ?? {},
+    kubeletVersion: node?.status?.nodeInfo?.kubeletVersion ?? '',
+    internalIPs: addressesOfType(node, 'InternalIP'),
+    externalIPs: addressesOfType(node, 'ExternalIP'),
+  };
+}
+
+const PRESSURE_CONDITIONS = ['MemoryPressure', 'DiskPressure', 'PIDPressure', 'NetworkUnavailable'];
+
+export function readyCondition(node: any): any {
+  return (node?.status?.conditions ?? []).find((condition: any) => condition?.type === 'Ready');
+}
+
+/**
+ * The fields of a node a live view cares about. Leaves out heartbeat times
+ * so a status update that only refreshes them compares equal.
+ */
+export function summarizeNode(node: any): NodeSummary {
+  const ready = readyCondition(node);
+  const conditions: any[] = node?.status?.conditions ?? [];
+
+  return {
+    name: node?.metadata?.name ?? '',
+    ready: ready?.status === 'True' ? true : ready?.status === 'False' ? false : null,
+    readyReason: ready?.reason ?? null,
+    kubeletVersion: node?.status?.nodeInfo?.kubeletVersion ?? '',
+    pressures: PRESSURE_CONDITIONS.filter((type) =>
+      conditions.some((condition) => condition?.type === type && condition.status === 'True')
+    ),
+    unschedulable: node?.spec?.unschedulable === true,
+  };
+}
@@ -0,0 +1,114 @@
+import type { CancelledOperationCounts, OperationKind, OperationScope } from '../common/kubeTypes';
+
+interface Operation {
+  kind: OperationKind;
+  scope: OperationScope;
+  cancel: () => void;
+  // Promoted to a background session: outlives the view that started it
+  background: boolean;
+  // The stream this is part of, e.g. the pod watch behind a log stream
+  parent: string | null;
+}
+
+// Streams own watches/processes of their own, so cancel them first
+const CANCEL_ORDER: OperationKind[] = ['logs', 'metrics', 'watches', 'kubectl'];
+
+function matches(scope: OperationScope, filter: OperationScope): boolean {
+  return (
+    (filter.context === undefined || scope.context === filter.context) &&
+    (filter.namespace === undefined || scope.namespace === filter.namespace) &&
+    (filter.view === undefined || scope.view === filter.view)
+  );
+}
+
+/**
+ * Tracks cancellable kubectl work across all managers
+ */
+export class OperationRegistry {
+  private operations: Map<string, Operation> = new Map();
+
+  register(id: string, kind: OperationKind, scope: OperationScope, cancel: () => void): void {
+    this.operations.set(id, { kind, scope, cancel, background: false, parent: null });
+  }
+
+  has(id: string): boolean {
+    return this.operations.has(id);
+  }
+
+  describe(id: string): { kind: OperationKind; scope: OperationScope } | null {
+    const operation = this.operations.get(id);
+    return operation ? { kind: operation.kind, scope: { ...operation.scope } } : null;
+  }
+
+  setBackground(id: string, background: boolean): boolean {
+    const operation = this.operations.get(id);
+    if (!operation) {
+      return false;
+    }
+    operation.background = background;
+    return true;
+  }
+
+  unregister(id: string): void {
+    this.operations.delete(id);
+  }
+
+  /**
+   * Mark an operation as part of another so it shares the parent's
+   * background state
+   */
+  setParent(id: string, parent: string): void {
+    const operation = this.operations.get(id);
+    if (operation) {
+      operation.parent = parent;
+    }
+  }
+
+  private isBackground(operation: Operation): boolean {
+    return operation.background || (operation.parent !== null && !!this.operations.get(operation.parent)?.background);
+  }
+
+  cancelOne(id: string): boolean {
+    const operation = this.operations.get(id);
+    if (!operation) {
+      return false;
+    }
+    this.operations.delete(id);
+    operation.cancel();
+    return true;
+  }
+
+  /**
+   * Cancel every operation matching the filter (all of them when omitted).
+   * Closing a view (a filter with `view`) leaves background operations running.
+   */
+  cancel(filter: OperationScope = {}): CancelledOperationCounts {
+    const counts: CancelledOperationCounts = { kubectl: 0, logs: 0, watches: 0, metrics: 0 };
+
+    for (const kind of CANCEL_ORDER) {
+      for (const [id, operation] of Array.from(this.operations)) {
+        // Cancelling a stream may already have removed its child watch
+        if (operation.kind !== kind || !this.operations.has(id) || !matches(operation.scope, filter)) {
+          continue;
+        }
+        if (filter.view !== undefined && this.isBackground(operation)) {
+          continue;
+        }
+
+        this.operations.delete(id);
+        try {
+          operation.cancel();
+          counts[kind] += 1;
+        } catch (error) {
+          console.error(`[Operations] Failed to cancel ${kind} operation ${id}:`, error);
+        }
+      }
+    }
+
+    console.log('[Operations] Cancelled:', counts);
+    return counts;
+  }
+}
+
+// Singleton instance
+export const operationRegistry = new OperationRegistry();
@@ -0,0 +1,190 @@
+import { app } from 'electron';
+import { execFile } from 'child_process';
+import { mkdirSync, readFileSync, renameSync, writeFileSync } from 'fs';
+import { readFile } from 'fs/promises';
+import path from 'path';
+import { promisify } from 'util';
+import type { OrphanedProcess, ReapResult } from '../common/terminalTypes';
+
+const execFileAsync = promisify(execFile);
+
+interface TrackedProcess {
+  pid: number;
+  /** Opaque start time used to tell a live child from a reused PID */
+  startTime: string | null;
+  terminalId: string;
+  command: string;
+  recordedAt: number;
+}
+
+// PTY children of this run
+const current: Map<number, TrackedProcess> = new Map();
+// Children recorded by an earlier run that never cleaned up after itself
+let previous: TrackedProcess[] = [];
+
+function getStatePath(): string {
+  return path.join(app.getPath('userData'), 'pty-processes.json');
+}
+
+function persist(): void {
+  try {
+    const statePath = getStatePath();
+    const tempPath = `${statePath}.tmp`;
+    mkdirSync(path.dirname(statePath), { recursive: true });
+    writeFileSync(tempPath, JSON.stringify([...previous, ...current.values()], null, 2), 'utf8');
+    renameSync(tempPath, statePath);
+  } catch (error) {
+    console.warn('[Orphans] Could not save PTY process state:', error);
+  }
+}
+
+/**
+ * Process start time: jiffies since boot on Linux, `ps` lstart elsewhere.
+ * Null when it can't be read (e.g. Windows).
+ */
+async function readStartTime(pid: number): Promise<string | null> {
+  try {
+    if (process.platform === 'linux') {
+      const stat = await readFile(`/proc/${pid}/stat`, 'utf8');
+      // Fields after the parenthesized command; starttime is field 22 overall
+      const fields = stat.slice(stat.lastIndexOf(')') + 2).split(' ');
+      return fields[19] ?? null;
+    }
+    if (process.platform !== 'win32') {
+      const { stdout } = await execFileAsync('ps', ['-o', 'lstart=', '-p', String(pid)]);
+      return stdout.trim() || null;
+    }
+  } catch {
+    // Process is gone or ps is unavailable
+  }
+  return null;
+}
+
+export function isAlive(pid: number): boolean {
+  try {
+    process.kill(pid, 0);
+    return true;
+  } catch (error) {
+    // EPERM: alive but owned by someone else
+    return (error as NodeJS.ErrnoException)?.code === 'EPERM';
+  }
+}
+
+function isTrackedProcess(entry: any): entry is TrackedProcess {
+  return (
+    !!entry &&
+    Number.isInteger(entry.pid) &&
+    entry.pid > 0 &&
+    typeof entry.terminalId === 'string' &&
+    typeof entry.command === 'string'
+  );
+}
+
+/**
+ * Load PTY children an earlier run left behind. Call once at startup,
+ * before any terminal is created.
+ */
+export function initOrphanTracking(): void {
+  try {
+    const raw = JSON.parse(readFileSync(getStatePath(), 'utf8'));
+    previous = Array.isArray(raw) ? raw.filter(isTrackedProcess).filter((entry) => isAlive(entry.pid)) : [];
+  } catch {
+    // No state file: the last run shut down cleanly (or never ran)
+    previous = [];
+  }
+
+  persist();
+  if (previous.length > 0) {
+    console.warn(`[Orphans] ${previous.length} PTY process(es) from a previous run may still be running`);
+  }
+}
+
+export function trackPtyProcess(pid: number, terminalId: string, command: string): void {
+  const entry: TrackedProcess = { pid, startTime: null, terminalId, command, recordedAt: Date.now() };
+  current.set(pid, entry);
+  persist();
+
+  readStartTime(pid).then((startTime) => {
+    if (current.get(pid) === entry && startTime) {
+      entry.startTime = startTime;
+      persist();
+    }
+  });
+}
+
+export function untrackPtyProcess(pid: number): void {
+  if (current.delete(pid)) {
+    persist();
+  }
+}
+
+/**
+ * Earlier-run PTY children that are still alive. Entries whose PID is gone
+ * or now belongs to a different process are dropped.
+ */
+export async function listOrphanedProcesses(): Promise<OrphanedProcess[]> {
+  const orphans: OrphanedProcess[] = [];
+  const stillTracked: TrackedProcess[] = [];
+
+  for (const entry of previous) {
+    if (!isAlive(entry.pid)) {
+      continue;
+    }
+
+    const startTime = await readStartTime(entry.pid);
+    if (entry.startTime && startTime && startTime !== entry.startTime) {
+      // PID was reused by an unrelated process
+      continue;
+    }
+
+    stillTracked.push(entry);
+    orphans.push({
+      pid: entry.pid,
+      terminalId: entry.terminalId,
+      command: entry.command,
+      recordedAt: entry.recordedAt,
+      verified: !!entry.startTime && startTime === entry.startTime,
+    });
+  }
+
+  if (stillTracked.length !== previous.length) {
+    previous = stillTracked;
+    persist();
+  }
+  return orphans;
+}
+
+/**
+ * Send SIGTERM to each verified orphan (and its process group, since a PTY
+ * child leads its own session). Unverified PIDs are never signalled.
+ */
+export async function reapOrphans(): Promise<ReapResult[]> {
+  const results: ReapResult[] = [];
+
+  for (const orphan of await listOrphanedProcesses()) {
+    if (!orphan.verified) {
+      results.push({
+        pid: orphan.pid,
+        ok: false,
+        error: 'Could not verify the process is the original terminal; not killing it',
+      });
+      continue;
+    }
+
+    try {
+      try {
+        process.kill(-orphan.pid, 'SIGTERM');
+      } catch {
+        process.kill(orphan.pid, 'SIGTERM');
+      }
+      previous = previous.filter((entry) => entry.pid !== orphan.pid);
+      results.push({ pid: orphan.pid, ok: true });
+      console.log(`[Orphans] Sent SIGTERM to ${orphan.pid} (${orphan.command})`);
+    } catch (error) {
+      results.push({ pid: orphan.pid, ok: false, error: error instanceof Error ? error.message : String(error) });
+    }
+  }
+
+  persist();
+  return results;
+}
@@ -0,0 +1,45 @@
+import './testSupport';
+import assert from 'node:assert/strict';
+import { describe, it } from 'node:test';
+import { OutputNotifier } from './outputNotifier';
+
+describe('OutputNotifier', () => {
+  const start = Date.now();
+  it('finds a match split across chunks and reports its line', () => {
+    const notifier = OutputNotifier.compile('BUILD (SUCCESS|FAILURE)');
+    assert.equal(notifier.push('[INFO] ----\r\n[INFO] BUILD SUC', start), null);
+    assert.deepEqual(notifier.push('CESS\r\n[INFO] Total time: 42 s\r\n', start), {
+      match: 'BUILD SUCCESS',
+      line: '[INFO] BUILD SUCCESS',
+    });
+  });
+
+  it('matches colored output', () => {
+    const notifier = OutputNotifier.compile('deployment "\\w+" successfully rolled out');
+    const output = '\x1b[32mdeployment "web" successfully rolled out\x1b[0m\r\n';
+    assert.equal(notifier.push(output, start)?.match, 'deployment "web" successfully rolled out');
+  });
+
+  it('notifies once for a burst of matches, and again later', () => {
+    const notifier = OutputNotifier.compile('FAIL');
+    assert.ok(notifier.push('FAIL a.test.ts\r\n', start));
+    assert.equal(notifier.push('FAIL b.test.ts\r\n', start + 1000), null);
+    assert.equal(notifier.push('FAIL c.test.ts\r\n', start + 4999), null);
+    assert.ok(notifier.push('FAIL d.test.ts\r\n', start + 6000));
+  });
+
+  it('does not report the same text twice', () => {
+    const notifier = OutputNotifier.compile('Error');
+    assert.ok(notifier.push('Error: connection refused', start));
+    assert.equal(notifier.push('\r\n$ ', start + 10 * 1000), null);
+  });
+
+  it('only scans the end of a large burst', () => {
+    const notifier = OutputNotifier.compile('needle');
+    assert.equal(notifier.push(`needle${'x'.repeat(64 * 1024)}`, start), null);
+  });
+
+  it('refuses invalid patterns', () => {
+    assert.throws(() => OutputNotifier.compile('(unclosed'), /Invalid pattern/);
+  });
+});
@@ -0,0 +1,51 @@
+import type { TerminalNotifyEvent } from '../common/terminalTypes';
+import { stripAnsi } from './terminalSequences';
+
+// Kept from the previous chunk so a match split across chunks is still found
+const MAX_TAIL_CHARS = 1024;
+// Only the end of a large burst of output is searched, bounding the cost per chunk
+const MAX_SCAN_CHARS = 16 * 1024;
+const MAX_LINE_CHARS = 500;
+// A burst of matching lines (a failing test suite) notifies once
+const NOTIFY_DEBOUNCE_MS = 5000;
+
+/**
+ * Watches a terminal's output for a pattern, for "ping me when this
+ * finishes" notifications. Escape sequences are stripped first so colored
+ * output matches; matched text is consumed so it can't notify twice.
+ */
+export class OutputNotifier {
+  private tail = '';
+  private lastNotifiedAt = 0;
+
+  private constructor(readonly pattern: RegExp) {}
+
+  static compile(source: string): OutputNotifier {
+    try {
+      return new OutputNotifier(new RegExp(source));
+    } catch (error) {
+      throw new Error(`Invalid pattern: ${error instanceof Error ? error.message : String(error)}`);
+    }
+  }
+
+  /** The match, when it should be reported */
+  push(data: string, now = Date.now()): Omit<TerminalNotifyEvent, 'pattern'> | null {
+    const window = `${this.tail}${stripAnsi(data).slice(-MAX_SCAN_CHARS)}`;
+    const match = this.pattern.exec(window);
+    if (!match) {
+      this.tail = window.slice(-MAX_TAIL_CHARS);
+      return null;
+    }
+
+    const end = match.index + match[0].length;
+    this.tail = window.slice(end).slice(-MAX_TAIL_CHARS);
+    if (now - this.lastNotifiedAt < NOTIFY_DEBOUNCE_MS) {
+      return null;
+    }
+    this.lastNotifiedAt = now;
+
+    const lineEnd = window.indexOf('\n', end);
+    const line = window.slice(window.lastIndexOf('\n', match.index) + 1, lineEnd < 0 ? undefined : lineEnd);
+    return { match: match[0], line: line.slice(0, MAX_LINE_CHARS) };
+  }
+}
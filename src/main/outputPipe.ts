@@ -0,0 +1,155 @@
+import { constants, open } from 'fs';
+import { stat } from 'fs/promises';
+import net from 'net';
+import { promisify } from 'util';
+import type { OutputPipeStatus } from '../common/terminalTypes';
+
+const openFd = promisify(open);
+
+// How often to look for a reader while none is connected
+const RECONNECT_INTERVAL_MS = 1000;
+// Stop queueing when a connected reader falls this far behind
+const MAX_QUEUED_BYTES = 1024 * 1024;
+
+/**
+ * Mirrors terminal output into a named pipe (a FIFO, or `\\.\pipe\...` on
+ * Windows) for external tools. A missing or vanished reader never affects
+ * the terminal: output is dropped until a reader shows up again.
+ */
+export class OutputPipe {
+  private socket: net.Socket | null = null;
+  private reconnectTimer: NodeJS.Timeout | null = null;
+  private connecting = false;
+  private closed = false;
+  private bytesWritten = 0;
+  private droppedBytes = 0;
+
+  private constructor(readonly path: string) {}
+
+  static async create(pipePath: string): Promise<OutputPipe> {
+    if (process.platform !== 'win32') {
+      let info;
+      try {
+        info = await stat(pipePath);
+      } catch {
+        throw new Error(`Named pipe not found: ${pipePath} (create it with mkfifo)`);
+      }
+      if (!info.isFIFO()) {
+        throw new Error(`${pipePath} is not a named pipe`);
+      }
+    }
+
+    const pipe = new OutputPipe(pipePath);
+    await pipe.connect();
+    return pipe;
+  }
+
+  write(data: string): void {
+    const bytes = Buffer.byteLength(data, 'utf8');
+    if (!this.socket || this.socket.writableLength > MAX_QUEUED_BYTES) {
+      this.droppedBytes += bytes;
+      return;
+    }
+    this.socket.write(data);
+    this.bytesWritten += bytes;
+  }
+
+  status(): OutputPipeStatus {
+    return {
+      path: this.path,
+      connected: this.socket !== null,
+      bytesWritten: this.bytesWritten,
+      droppedBytes: this.droppedBytes,
+    };
+  }
+
+  close(): void {
+    this.closed = true;
+    if (this.reconnectTimer) {
+      clearTimeout(this.reconnectTimer);
+      this.reconnectTimer = null;
+    }
+    this.socket?.destroy();
+    this.socket = null;
+  }
+
+  private async connect(): Promise<void> {
+    if (this.closed || this.connecting) {
+      return;
+    }
+    this.connecting = true;
+
+    try {
+      const socket = await this.openSocket();
+      if (this.closed) {
+        socket?.destroy();
+        return;
+      }
+      if (!socket) {
+        this.scheduleReconnect();
+        return;
+      }
+
+      socket.on('error', (error: NodeJS.ErrnoException) => {
+        // EPIPE: the reader went away; wait for the next one
+        if (error.code !== 'EPIPE') {
+          console.warn(`[OutputPipe] ${this.path}: ${error.message}`);
+        }
+      });
+      socket.once('close', () => {
+        if (this.socket === socket) {
+          this.socket = null;
+          this.scheduleReconnect();
+        }
+      });
+      this.socket = socket;
+    } finally {
+      this.connecting = false;
+    }
+  }
+
+  /**
+   * Open the pipe for writing without blocking. Resolves null when there
+   * is no reader yet (ENXIO for a FIFO, ENOENT/ECONNREFUSED on Windows).
+   */
+  private async openSocket(): Promise<net.Socket | null> {
+    if (process.platform === 'win32') {
+      return new Promise((resolve) => {
+        const socket = net.connect(this.path);
+        socket.once('connect', () => {
+          socket.removeAllListeners('error');
+          resolve(socket);
+        });
+        socket.once('error', () => {
+          socket.destroy();
+          resolve(null);
+        });
+      });
+    }
+
+    try {
+      const fd = await openFd(this.path, constants.O_WRONLY | constants.O_NONBLOCK);
+      // A net.Socket over the fd handles EAGAIN and backpressure for us
+      return new net.Socket({ fd, readable: false, writable: true });
+    } catch (error) {
+      if ((error as NodeJS.ErrnoException)?.code === 'ENXIO') {
+        return null;
+      }
+      throw error;
+    }
+  }
+
+  private scheduleReconnect(): void {
+    if (this.closed || this.reconnectTimer) {
+      return;
+    }
+    this.reconnectTimer = setTimeout(() => {
+      this.reconnectTimer = null;
+      this.connect().catch((error) => {
+        console.warn(`[OutputPipe] Reopening ${this.path} failed:`, error);
+        this.scheduleReconnect();
+      });
+    }, RECONNECT_INTERVAL_MS);
+    this.reconnectTimer.unref();
+  }
+}
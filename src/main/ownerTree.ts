@@ -0,0 +1,209 @@
+import type { OwnerTree, OwnerTreeEdge, OwnerTreeNode } from '../common/kubeTypes';
+import { getKubectlJson, KubeError } from './kube';
+
+export interface OwnerTreeOptions {
+  /** Also walk down to owned objects (deployment -> replicasets -> pods) */
+  includeDescendants?: boolean;
+  /** Levels walked in each direction */
+  maxDepth?: number;
+}
+
+const DEFAULT_MAX_DEPTH = 5;
+const MAX_DEPTH_LIMIT = 10;
+
+// Kinds whose controllers create other objects, and what they create
+const OWNED_KINDS: Record<string, string[]> = {
+  Deployment: ['replicasets.apps'],
+  ReplicaSet: ['pods'],
+  StatefulSet: ['pods', 'controllerrevisions.apps'],
+  DaemonSet: ['pods', 'controllerrevisions.apps'],
+  ReplicationController: ['pods'],
+  CronJob: ['jobs.batch'],
+  Job: ['pods'],
+};
+
+/**
+ * kubectl resource name for a kind in an API group version,
+ * e.g. ("apps/v1", "ReplicaSet") -> "replicaset.v1.apps"
+ */
+function resourceFor(apiVersion: string | undefined, kind: string): string {
+  const [group, version] = (apiVersion ?? '').includes('/') ? (apiVersion as string).split('/') : ['', apiVersion];
+  const name = kind.toLowerCase();
+  return group ? `${name}.${version}.${group}` : name;
+}
+
+function nodeKey(kind: string, namespace: string | null, name: string): string {
+  return `${kind}/${namespace ?? ''}/${name}`;
+}
+
+function toNode(object: any, fallbackKind: string): OwnerTreeNode {
+  const kind: string = object?.kind ?? fallbackKind;
+  const namespace: string | null = object?.metadata?.namespace ?? null;
+  const name: string = object?.metadata?.name ?? '';
+  return {
+    key: nodeKey(kind, namespace, name),
+    kind,
+    apiVersion: object?.apiVersion ?? null,
+    name,
+    namespace,
+    uid: object?.metadata?.uid ?? null,
+    missing: false,
+    message: null,
+  };
+}
+
+/**
+ * How one object relates to its owners and, optionally, what it owns:
+ * ownerReferences are followed upward (pod -> replicaset -> deployment)
+ * and controller-created kinds are listed downward. Lookups are memoized
+ * per call, owners that no longer exist (or were recreated with a new uid)
+ * become `missing` nodes, and both directions stop at `maxDepth`.
+ */
+export async function getOwnerTree(
+  kind: string,
+  name: string,
+  namespace: string,
+  context: string,
+  options: OwnerTreeOptions = {}
+): Promise<OwnerTree> {
+  const maxDepth = Math.max(1, Math.min(MAX_DEPTH_LIMIT, Math.floor(options.maxDepth ?? DEFAULT_MAX_DEPTH)));
+  const objects = new Map<string, Promise<any>>();
+  const lists = new Map<string, Promise<any[]>>();
+  const nodes = new Map<string, OwnerTreeNode>();
+  const edges: OwnerTreeEdge[] = [];
+  let truncated = false;
+
+  // Owner references are namespace-local, or point at cluster-scoped objects
+  const fetchObject = (resource: string, objectName: string): Promise<any> => {
+    const cacheKey = `${resource}/${objectName}`;
+    if (!objects.has(cacheKey)) {
+      objects.set(cacheKey, getKubectlJson(context, ['get', resource, objectName], namespace));
+    }
+    return objects.get(cacheKey) as Promise<any>;
+  };
+
+  const listObjects = (resource: string): Promise<any[]> => {
+    if (!lists.has(resource)) {
+      lists.set(
+        resource,
+        getKubectlJson(context, ['get', resource], namespace).then((list) => list?.items ?? [])
+      );
+    }
+    return lists.get(resource) as Promise<any[]>;
+  };
+
+  const addEdge = (owner: string, owned: string, controller: boolean) => {
+    if (!edges.some((edge) => edge.owner === owner && edge.owned === owned)) {
+      edges.push({ owner, owned, controller });
+    }
+  };
+
+  const walkUp = async (object: any, node: OwnerTreeNode, depth: number): Promise<void> => {
+    const references: any[] = object?.metadata?.ownerReferences ?? [];
+    if (references.length > 0 && depth >= maxDepth) {
+      truncated = true;
+      return;
+    }
+
+    await Promise.all(
+      references.map(async (reference) => {
+        if (!reference?.kind || !reference?.name) {
+          return;
+        }
+
+        let owner: any = null;
+        let message: string | null = null;
+        try {
+          owner = await fetchObject(resourceFor(reference.apiVersion, reference.kind), reference.name);
+          if (reference.uid && owner?.metadata?.uid !== reference.uid) {
+            message = `${reference.kind} ${reference.name} was deleted and recreated; this object is orphaned`;
+            owner = null;
+          }
+        } catch (error) {
+          message =
+            error instanceof KubeError && error.code === 'NotFound'
+              ? `${reference.kind} ${reference.name} no longer exists`
+              : error instanceof Error
+                ? error.message
+                : String(error);
+        }
+
+        if (!owner) {
+          const missing: OwnerTreeNode = {
+            key: nodeKey(reference.kind, node.namespace, reference.name),
+            kind: reference.kind,
+            apiVersion: reference.apiVersion ?? null,
+            name: reference.name,
+            namespace: node.namespace,
+            uid: reference.uid ?? null,
+            missing: true,
+            message,
+          };
+          nodes.set(missing.key, missing);
+          addEdge(missing.key, node.key, reference.controller === true);
+          return;
+        }
+
+        const ownerNode = toNode(owner, reference.kind);
+        addEdge(ownerNode.key, node.key, reference.controller === true);
+        if (nodes.has(ownerNode.key)) {
+          return;
+        }
+        nodes.set(ownerNode.key, ownerNode);
+        await walkUp(owner, ownerNode, depth + 1);
+      })
+    );
+  };
+
+  const walkDown = async (node: OwnerTreeNode, depth: number): Promise<void> => {
+    const resources = OWNED_KINDS[node.kind] ?? [];
+    if (resources.length === 0 || !node.uid) {
+      return;
+    }
+
+    const children = (
+      await Promise.all(
+        resources.map((resource) =>
+          listObjects(resource).catch((error) => {
+            // A forbidden child kind shouldn't hide the rest of the tree
+            console.warn(`[OwnerTree] Could not list ${resource}:`, error instanceof Error ? error.message : error);
+            return [];
+          })
+        )
+      )
+    )
+      .flat()
+      .filter((child) =>
+        (child?.metadata?.ownerReferences ?? []).some((reference: any) => reference?.uid === node.uid)
+      );
+
+    if (children.length > 0 && depth >= maxDepth) {
+      truncated = true;
+      return;
+    }
+
+    await Promise.all(
+      children.map(async (child) => {
+        const childNode = toNode(child, '');
+        const reference = child.metadata.ownerReferences.find((entry: any) => entry?.uid === node.uid);
+        addEdge(node.key, childNode.key, reference?.controller === true);
+        if (nodes.has(childNode.key)) {
+          return;
+        }
+        nodes.set(childNode.key, childNode);
+        await walkDown(childNode, depth + 1);
+      })
+    );
+  };
+
+  const object = await getKubectlJson(context, ['get', kind, name], namespace);
+  const root = toNode(object, kind);
+  nodes.set(root.key, root);
+
+  await Promise.all([
+    walkUp(object, root, 0),
+    options.includeDescendants ? walkDown(root, 0) : Promise.resolve(),
+  ]);
+
+  return { root: root.key, nodes: Array.from(nodes.values()), edges, truncated };
+}
@@ -0,0 +1,134 @@
+import type { ApiResource, ResourcePage } from '../common/kubeTypes';
+import { listApiResources } from './discovery';
+import { runKubectl, kubectlFailure, KubeError } from './kube';
+
+export interface ResourcePageSpec {
+  context: string;
+  /** Kind, resource name, short name or "resource.group", as kubectl accepts */
+  kind: string;
+  /** Omit for all namespaces */
+  namespace?: string;
+  limit?: number;
+  /** From the previous page; omit for the first page */
+  continueToken?: string;
+  labelSelector?: string;
+  fieldSelector?: string;
+  view?: string;
+}
+
+const DEFAULT_PAGE_SIZE = 500;
+const MAX_PAGE_SIZE = 5000;
+// 410 Gone: the continue token outlived etcd's compaction window
+const EXPIRED_PATTERN = /\(Expired\)|continue parameter is too old|\b410\b/i;
+
+function matchesKind(resource: ApiResource, kind: string): boolean {
+  const [name, ...group] = kind.toLowerCase().split('.');
+  if (group.length > 0 && resource.group !== group.join('.')) {
+    return false;
+  }
+  return (
+    resource.name === name ||
+    resource.kind.toLowerCase() === name ||
+    resource.shortNames.some((shortName) => shortName === name)
+  );
+}
+
+async function resolveResource(context: string, kind: string): Promise<ApiResource> {
+  const candidates = (await listApiResources(context)).filter(
+    (resource) => matchesKind(resource, kind) && resource.verbs.includes('list')
+  );
+  // Core and built-in groups win over a CRD that reuses a short name, as in kubectl
+  const builtIn = (group: string) => !group.includes('.') || group.endsWith('.k8s.io');
+  const resource = candidates.find((candidate) => builtIn(candidate.group)) ?? candidates[0];
+  if (!resource) {
+    throw new KubeError('NotFound', `The server doesn't have a resource type "${kind}" that can be listed`);
+  }
+  return resource;
+}
+
+function listPath(resource: ApiResource, spec: ResourcePageSpec, limit: number, continueToken?: string): string {
+  const base = resource.group ? `/apis/${resource.apiVersion}` : `/api/${resource.apiVersion}`;
+  const scope = resource.namespaced && spec.namespace ? `/namespaces/${encodeURIComponent(spec.namespace)}` : '';
+  const query = new URLSearchParams({ limit: String(limit) });
+  if (continueToken) {
+    query.set('continue', continueToken);
+  }
+  if (spec.labelSelector) {
+    query.set('labelSelector', spec.labelSelector);
+  }
+  if (spec.fieldSelector) {
+    query.set('fieldSelector', spec.fieldSelector);
+  }
+  return `${base}${scope}/${resource.name}?${query.toString()}`;
+}
+
+/**
+ * One page of a list, using the API's limit/continue paging so a huge
+ * namespace can be loaded as the user scrolls instead of all at once. Pass
+ * the returned token to get the next page; it is null after the last. A
+ * token that expired (the list is older than etcd keeps history) restarts
+ * from the first page, flagged with `restarted`. The total is only known
+ * on the first page, and only when the server reports remainingItemCount.
+ */
+export async function listResourcesPaged(spec: ResourcePageSpec): Promise<ResourcePage> {
+  if (spec.limit !== undefined && (!Number.isInteger(spec.limit) || spec.limit < 1)) {
+    throw new Error('limit must be a positive whole number');
+  }
+  const limit = Math.min(spec.limit ?? DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE);
+  const resource = await resolveResource(spec.context, spec.kind);
+
+  const fetchPage = (continueToken?: string) =>
+    runKubectl(spec.context, ['get', '--raw', listPath(resource, spec, limit, continueToken)], undefined, {
+      view: spec.view,
+      retry: true,
+    });
+
+  let restarted = false;
+  let result = await fetchPage(spec.continueToken);
+  if (result.code !== 0 && spec.continueToken && EXPIRED_PATTERN.test(result.stderr)) {
+    console.warn(`[List ${resource.name}] Continue token expired, restarting from the first page`);
+    restarted = true;
+    result = await fetchPage();
+  }
+  if (result.code !== 0) {
+    throw kubectlFailure(result, `Failed to list ${resource.name}`);
+  }
+
+  let list: any;
+  try {
+    list = JSON.parse(result.stdout);
+  } catch {
+    throw new KubeError('KubectlFailed', 'kubectl returned invalid JSON');
+  }
+
+  // List items come without their own apiVersion and kind
+  const items: any[] = (Array.isArray(list?.items) ? list.items : []).map((item: any) => ({
+    apiVersion: resource.apiVersion,
+    kind: resource.kind,
+    ...item,
+  }));
+  const remaining = list?.metadata?.remainingItemCount;
+  const remainingItemCount = typeof remaining === 'number' ? remaining : null;
+  const continueToken: string | null = list?.metadata?.continue || null;
+
+  let total: number | null = null;
+  if (!spec.continueToken || restarted) {
+    if (!continueToken) {
+      total = items.length;
+    } else if (remainingItemCount !== null) {
+      total = items.length + remainingItemCount;
+    }
+  }
+
+  return {
+    kind: resource.kind,
+    resource: resource.name,
+    namespace: resource.namespaced ? spec.namespace ?? null : null,
+    items,
+    continueToken,
+    remainingItemCount,
+    total,
+    resourceVersion: list?.metadata?.resourceVersion ?? null,
+    restarted,
+  };
+}
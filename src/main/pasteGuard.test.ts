@@ -0,0 +1,56 @@
+import './testSupport';
+import assert from 'node:assert/strict';
+import { afterEach, describe, it } from 'node:test';
+import { getAppConfig } from './appConfig';
+import { checkPasteSize, countPastedLines, PasteTooLargeError, setPasteLimits } from './pasteGuard';
+
+describe('paste size limits', () => {
+  const defaults = { ...getAppConfig().terminalPaste };
+
+  afterEach(() => {
+    getAppConfig().terminalPaste = { ...defaults };
+  });
+
+  it('counts pasted lines the way the shell will see them', () => {
+    assert.equal(countPastedLines(''), 0);
+    assert.equal(countPastedLines('kubectl get pods'), 1);
+    assert.equal(countPastedLines('a\rb\r'), 2);
+    assert.equal(countPastedLines('a\r\nb\nc'), 3);
+  });
+
+  it('allows a paste right at the line limit and refuses one line more', () => {
+    const limits = { maxBytes: 0, maxLines: 3 };
+    checkPasteSize('a\rb\rc\r', limits);
+    assert.throws(
+      () => checkPasteSize('a\rb\rc\rd', limits),
+      (error: unknown) => {
+        assert.ok(error instanceof PasteTooLargeError);
+        assert.deepEqual([error.code, error.lines, error.bytes], ['PasteTooLarge', 4, 7]);
+        assert.match(error.message, /over the 3 lines limit/);
+        return true;
+      }
+    );
+  });
+
+  it('measures the byte limit in UTF-8', () => {
+    const limits = { maxBytes: 8, maxLines: 0 };
+    checkPasteSize('€€', limits);
+    checkPasteSize('12345678', limits);
+    assert.throws(() => checkPasteSize('€€€', limits), /9 bytes, is over the 8 bytes limit/);
+  });
+
+  it('treats a limit of 0 as off', () => {
+    checkPasteSize('x\r'.repeat(10 * 1000), { maxBytes: 0, maxLines: 0 });
+  });
+
+  it('uses the configured limits by default', () => {
+    getAppConfig().terminalPaste = { maxBytes: 0, maxLines: 1 };
+    assert.throws(() => checkPasteSize('a\rb'), { code: 'PasteTooLarge' });
+  });
+
+  it('takes whole, non-negative limits', () => {
+    assert.throws(() => setPasteLimits({ maxLines: -1 }), /maxLines must be a whole number/);
+    assert.throws(() => setPasteLimits({ maxBytes: 1.5 }), /maxBytes must be a whole number/);
+    assert.deepEqual(setPasteLimits({ maxLines: 500 }), { maxBytes: defaults.maxBytes, maxLines: 500 });
+  });
+});
@@ -0,0 +1,67 @@
+import type { TerminalPasteLimits } from '../common/terminalTypes';
+import { getAppConfig, updateAppConfig } from './appConfig';
+
+/**
+ * A paste over the configured size that wasn't confirmed. Carries the
+ * size so the UI can ask "paste 5000 lines?" and retry with confirmation.
+ */
+export class PasteTooLargeError extends Error {
+  readonly code = 'PasteTooLarge';
+
+  constructor(
+    message: string,
+    readonly bytes: number,
+    readonly lines: number,
+    readonly limits: TerminalPasteLimits
+  ) {
+    super(message);
+    this.name = 'PasteTooLargeError';
+  }
+}
+
+export function countPastedLines(data: string): number {
+  // xterm sends pasted line breaks as \r; files pasted verbatim may use \n or \r\n
+  const breaks = data.match(/\r\n|\r|\n/g)?.length ?? 0;
+  // A trailing line break doesn't start another line
+  return /[\r\n]$/.test(data) || data.length === 0 ? breaks : breaks + 1;
+}
+
+/**
+ * Throws PasteTooLargeError when a paste exceeds either limit. Sizes equal
+ * to a limit are allowed; a limit of 0 is off.
+ */
+export function checkPasteSize(data: string, limits: TerminalPasteLimits = getPasteLimits()): void {
+  const bytes = Buffer.byteLength(data, 'utf8');
+  const lines = countPastedLines(data);
+  const tooManyBytes = limits.maxBytes > 0 && bytes > limits.maxBytes;
+  const tooManyLines = limits.maxLines > 0 && lines > limits.maxLines;
+  if (!tooManyBytes && !tooManyLines) {
+    return;
+  }
+
+  const limit = tooManyLines ? `${limits.maxLines} lines` : `${limits.maxBytes} bytes`;
+  throw new PasteTooLargeError(
+    `Paste of ${lines} line(s), ${bytes} bytes, is over the ${limit} limit; confirm to paste anyway`,
+    bytes,
+    lines,
+    { ...limits }
+  );
+}
+
+export function getPasteLimits(): TerminalPasteLimits {
+  return { ...getAppConfig().terminalPaste };
+}
+
+export function setPasteLimits(limits: Partial<TerminalPasteLimits>): TerminalPasteLimits {
+  for (const field of ['maxBytes', 'maxLines'] as const) {
+    const value = limits[field];
+    if (value !== undefined && (!Number.isInteger(value) || value < 0)) {
+      throw new Error(`${field} must be a whole number, or 0 for no limit`);
+    }
+  }
+
+  updateAppConfig((config) => {
+    config.terminalPaste = { ...config.terminalPaste, ...limits };
+  });
+  return getPasteLimits();
+}
@@ -0,0 +1,40 @@
+import { userDataDir } from './testSupport';
+import assert from 'node:assert/strict';
+import { afterEach, before, describe, it } from 'node:test';
+import { chmodSync, mkdirSync, writeFileSync } from 'fs';
+import path from 'path';
+import { getAppConfig } from './appConfig';
+import { runPlugin } from './plugins';
+
+describe('runPlugin', () => {
+  before(() => {
+    process.env.KREW_ROOT = path.join(userDataDir, 'krew');
+    const bin = path.join(process.env.KREW_ROOT, 'bin');
+    mkdirSync(bin, { recursive: true });
+    writeFileSync(path.join(bin, 'kubectl-hello'), '#!/bin/sh\necho "hello $*"\n');
+    chmodSync(path.join(bin, 'kubectl-hello'), 0o755);
+  });
+
+  afterEach(() => {
+    getAppConfig().commandPolicy = { enabled: false, allow: [], deny: [] };
+  });
+
+  it('is blocked by a rule naming the plugin either way', async () => {
+    for (const rule of ['kubectl hello', 'kubectl-hello world']) {
+      getAppConfig().commandPolicy = { enabled: true, allow: [], deny: [rule] };
+      await assert.rejects(runPlugin('hello', ['world'], 'dev'), { code: 'CommandBlocked' });
+    }
+  });
+
+  it('is not blocked by a rule for the plugin\'s arguments alone', async () => {
+    getAppConfig().commandPolicy = { enabled: true, allow: [], deny: ['kubectl world'] };
+    const result = await runPlugin('hello', ['world'], 'dev');
+    assert.equal(result.code, 0);
+    assert.match(result.stdout, /hello --context dev world/);
+  });
+
+  it('runs when the allowed list names the plugin', async () => {
+    getAppConfig().commandPolicy = { enabled: true, allow: ['kubectl hello'], deny: [] };
+    assert.equal((await runPlugin('hello', [], 'dev')).code, 0);
+  });
+});
@@ -0,0 +1,115 @@
+import { access, constants, readdir } from 'fs/promises';
+import os from 'os';
+import path from 'path';
+import type { KubectlPlugin, KubectlResult } from '../common/kubeTypes';
+import { runKubectl } from './kube';
+
+const PLUGIN_PREFIX = 'kubectl-';
+const PLUGIN_NAME_PATTERN = /^[a-z0-9][a-z0-9_-]*$/i;
+
+/**
+ * Directory krew links installed plugins into. Packaged apps don't inherit
+ * the shell profile that usually adds it to PATH.
+ */
+export function getKrewBinDir(): string {
+  const root = process.env.KREW_ROOT || path.join(os.homedir(), '.krew');
+  return path.join(root, 'bin');
+}
+
+function pluginPath(): string {
+  return [getKrewBinDir(), process.env.PATH].filter(Boolean).join(path.delimiter);
+}
+
+function pluginFileName(name: string): string {
+  // `kubectl foo-bar` runs `kubectl-foo_bar`
+  const fileName = `${PLUGIN_PREFIX}${name.replace(/-/g, '_')}`;
+  return process.platform === 'win32' ? `${fileName}.exe` : fileName;
+}
+
+async function isExecutable(filePath: string): Promise<boolean> {
+  try {
+    await access(filePath, process.platform === 'win32' ? constants.F_OK : constants.X_OK);
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+async function findPlugin(name: string): Promise<string | null> {
+  const fileName = pluginFileName(name);
+
+  for (const directory of pluginPath().split(path.delimiter)) {
+    if (!directory) {
+      continue;
+    }
+    const candidate = path.join(directory, fileName);
+    if (await isExecutable(candidate)) {
+      return candidate;
+    }
+  }
+
+  return null;
+}
+
+/**
+ * Plugins installed through krew, sorted by name
+ */
+export async function listPlugins(): Promise<KubectlPlugin[]> {
+  const binDir = getKrewBinDir();
+  let entries: string[];
+
+  try {
+    entries = await readdir(binDir);
+  } catch {
+    // krew isn't installed
+    return [];
+  }
+
+  const plugins: KubectlPlugin[] = [];
+  for (const entry of entries) {
+    if (!entry.startsWith(PLUGIN_PREFIX)) {
+      continue;
+    }
+
+    const filePath = path.join(binDir, entry);
+    if (!(await isExecutable(filePath))) {
+      continue;
+    }
+
+    const name = entry
+      .slice(PLUGIN_PREFIX.length)
+      .replace(/\.exe$/i, '')
+      .replace(/_/g, '-');
+    plugins.push({ name, path: filePath });
+  }
+
+  return plugins.sort((a, b) => a.name.localeCompare(b.name));
+}
+
+/**
+ * Run a kubectl plugin by name with captured output. The plugin binary is
+ * invoked directly so `--context`/`-n` reach it (kubectl only dispatches to
+ * plugins when no global flags precede the plugin name).
+ */
+export async function runPlugin(
+  name: string,
+  args: string[],
+  context: string,
+  namespace?: string
+): Promise<KubectlResult> {
+  if (!PLUGIN_NAME_PATTERN.test(name)) {
+    throw new Error(`Invalid plugin name: ${name}`);
+  }
+
+  const binary = await findPlugin(name);
+  if (!binary) {
+    throw new Error(`Plugin "${name}" is not installed. Install it with: kubectl krew install ${name}`);
+  }
+
+  return runKubectl(context, args, namespace, {
+    binary,
+    env: { PATH: pluginPath() },
+    // Blocked and allowed whether the policy names it `kubectl foo` or `kubectl-foo`
+    policyCommand: [['kubectl', name, ...args].join(' '), [`${PLUGIN_PREFIX}${name}`, ...args].join(' ')],
+  });
+}
@@ -0,0 +1,94 @@
+import { stubSpawnKubectl, userDataDir, waitForSpawn } from './testSupport';
+import assert from 'node:assert/strict';
+import { after, beforeEach, describe, it } from 'node:test';
+import { writeFileSync } from 'fs';
+import path from 'path';
+import type { ExecStdinProgressEvent } from '../common/kubeTypes';
+import { tapRendererEvents } from './events';
+import { execWithStdinFile } from './podFiles';
+
+describe('execWithStdinFile', () => {
+  const spawned = stubSpawnKubectl();
+  const dump = path.join(userDataDir, 'dump.sql');
+  // Several pipe buffers' worth, so the file has to be streamed in pieces
+  const contents = 'INSERT INTO orders VALUES (1);\n'.repeat(100 * 1024);
+  const spec = { context: 'dev', pod: 'db-0', namespace: 'shop', command: ['psql'], stdinPath: dump };
+  const events: ExecStdinProgressEvent[] = [];
+  const untap = tapRendererEvents((channel, [payload]) => {
+    if (channel === 'exec:progress') {
+      events.push(payload as ExecStdinProgressEvent);
+    }
+  });
+  after(untap);
+
+  beforeEach(() => {
+    writeFileSync(dump, contents);
+    spawned.length = 0;
+    events.length = 0;
+  });
+
+  /** Resolves with the final event once the exec `execId` is done */
+  const finished = async (execId: string) => {
+    for (let turn = 0; turn < 1000; turn++) {
+      const final = events.find((event) => event.execId === execId && event.done);
+      if (final) {
+        return final;
+      }
+      await new Promise((resolve) => setTimeout(resolve, 5));
+    }
+    throw new Error('exec did not finish');
+  };
+
+  it('streams the file to the program and reports its output', async () => {
+    const { execId, totalBytes } = await execWithStdinFile({ ...spec, container: 'postgres', command: ['psql', '-q'] });
+    assert.equal(totalBytes, contents.length);
+
+    await waitForSpawn(spawned, 1);
+    const { args, child } = spawned[0];
+    assert.deepEqual(args, ['exec', '-i', 'db-0', '-c', 'postgres', '--', 'psql', '-q']);
+    // The stubbed program reads stdin to the end, then answers
+    const received: Buffer[] = [];
+    child.stdin.on('data', (chunk: Buffer) => received.push(chunk));
+    child.stdin.on('end', () => {
+      child.stdout.write(`INSERT ${Buffer.concat(received).length}\n`);
+      child.exit(0);
+    });
+
+    const final = await finished(execId);
+    assert.equal(Buffer.concat(received).toString(), contents);
+    assert.deepEqual([final.ok, final.exitCode, final.bytesSent], [true, 0, contents.length]);
+    assert.equal(final.stdout, `INSERT ${contents.length}\n`);
+    assert.ok(events.some((event) => !event.done && event.bytesSent > 0));
+  });
+
+  it('surfaces the program\'s own exit code', async () => {
+    const { execId } = await execWithStdinFile(spec);
+    await waitForSpawn(spawned, 1);
+    const { child } = spawned[0];
+    child.stdin.resume();
+    child.stderr.write('ERROR: relation "orders" does not exist\ncommand terminated with exit code 3\n');
+    child.exit(1);
+
+    const final = await finished(execId);
+    assert.deepEqual([final.ok, final.exitCode, final.error], [false, 3, undefined]);
+    assert.match(final.stderr ?? '', /relation "orders" does not exist/);
+  });
+
+  it('reports kubectl failures separately from the program\'s', async () => {
+    const { execId } = await execWithStdinFile({ ...spec, pod: 'db-9' });
+    await waitForSpawn(spawned, 1);
+    const { child } = spawned[0];
+    child.stderr.write('Error from server (NotFound): pods "db-9" not found\n');
+    child.exit(1);
+
+    const final = await finished(execId);
+    assert.deepEqual([final.ok, final.exitCode, final.errorCode], [false, null, 'NotFound']);
+  });
+
+  it('checks the file before starting kubectl', async () => {
+    const missing = path.join(userDataDir, 'nope.sql');
+    await assert.rejects(execWithStdinFile({ ...spec, stdinPath: missing }), /readable file/);
+    await assert.rejects(execWithStdinFile({ ...spec, stdinPath: userDataDir }), /readable file/);
+    assert.equal(spawned.length, 0);
+  });
+});
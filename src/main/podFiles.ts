@@ -0,0 +1,440 @@
+import { randomUUID } from 'crypto';
+import type { ChildProcessWithoutNullStreams } from 'child_process';
+import { createReadStream, createWriteStream } from 'fs';
+import { rename, stat, unlink } from 'fs/promises';
+import type {
+  ExecStdinProgressEvent,
+  KubeErrorCode,
+  PodFileDownloadEvent,
+  PodFileErrorCode,
+} from '../common/kubeTypes';
+import { classifyKubectlError, spawnKubectl } from './kube';
+import { emitToRenderer } from './events';
+import { OutputCoalescer } from './coalesce';
+import { operationRegistry } from './operations';
+
+export interface PodFileStreamSpec {
+  pod: string;
+  namespace: string;
+  container?: string;
+  path: string;
+  context: string;
+  /** Keep reading as the file grows (`tail -f`); false reads it once */
+  follow?: boolean;
+  tailLines?: number;
+  view?: string;
+}
+
+interface PodFileStream {
+  spec: PodFileStreamSpec;
+  process: ChildProcessWithoutNullStreams;
+  coalescer: OutputCoalescer;
+  stopped: boolean;
+}
+
+export interface PodFileDownloadSpec {
+  context: string;
+  pod: string;
+  namespace: string;
+  container?: string;
+  remotePath: string;
+  localPath: string;
+  view?: string;
+}
+
+export interface ExecStdinFileSpec {
+  context: string;
+  pod: string;
+  namespace: string;
+  container?: string;
+  /** Program and arguments run in the container */
+  command: string[];
+  /** Local file streamed to the program's stdin */
+  stdinPath: string;
+  view?: string;
+}
+
+const DEFAULT_TAIL_LINES = 200;
+// Progress events are throttled to this interval
+const PROGRESS_INTERVAL_MS = 200;
+// Output kept from an exec fed from a file; the newest output wins
+const MAX_CAPTURED_OUTPUT_CHARS = 1024 * 1024;
+
+const MISSING_FILE_PATTERN = /No such file or directory/i;
+// kubectl's stderr when the remote program exits non-zero
+const REMOTE_EXIT_PATTERN = /command terminated with exit code (\d+)/;
+
+function classifyPodFileError(output: string): PodFileErrorCode {
+  if (/executable file not found|tail: not found|tail: command not found|no such file or directory: unknown/i.test(output)) {
+    return 'TailNotFound';
+  }
+  if (/tail: cannot open|tail: can't open|No such file or directory/i.test(output)) {
+    return 'FileNotFound';
+  }
+  return classifyKubectlError(output);
+}
+
+function describeError(code: PodFileErrorCode, spec: PodFileStreamSpec, output: string): string {
+  switch (code) {
+    case 'TailNotFound':
+      return `The container has no "tail" binary (distroless or scratch image), so ${spec.path} can't be streamed`;
+    case 'FileNotFound':
+      return `${spec.path} does not exist in the container`;
+    default:
+      return output;
+  }
+}
+
+/**
+ * Streams a file from inside a container via `kubectl exec -- tail`, for
+ * app logs that aren't written to stdout
+ */
+export class PodFileStreamManager {
+  private streams: Map<string, PodFileStream> = new Map();
+
+  streamPodFile(spec: PodFileStreamSpec): string {
+    const id = randomUUID();
+    const tailArgs = ['tail', '-n', String(spec.tailLines ?? DEFAULT_TAIL_LINES)];
+    if (spec.follow !== false) {
+      tailArgs.push('-f');
+    }
+    tailArgs.push(spec.path);
+
+    const args = ['exec', spec.pod];
+    if (spec.container) {
+      args.push('-c', spec.container);
+    }
+    args.push('--', ...tailArgs);
+
+    const child = spawnKubectl(spec.context, args, spec.namespace);
+    const coalescer = new OutputCoalescer((data, truncated) => {
+      emitToRenderer('file:data', { streamId: id, data, truncated });
+    });
+    const stream: PodFileStream = { spec, process: child, coalescer, stopped: false };
+    let stderr = '';
+
+    this.streams.set(id, stream);
+    operationRegistry.register(
+      id,
+      'logs',
+      { context: spec.context, namespace: spec.namespace, view: spec.view },
+      () => this.stopPodFileStream(id)
+    );
+
+    child.stdout.on('data', (chunk) => coalescer.push(chunk.toString()));
+    child.stderr.on('data', (chunk) => {
+      stderr += chunk.toString();
+    });
+
+    child.once('error', (error: NodeJS.ErrnoException) => {
+      const message =
+        error?.code === 'ENOENT'
+          ? 'kubectl executable not found. Install kubectl or add it to your PATH.'
+          : error.message;
+      stream.stopped = true;
+      this.finish(id, stream);
+      emitToRenderer('file:error', { streamId: id, code: 'KubectlFailed', message });
+    });
+
+    child.once('close', (code) => {
+      if (stream.stopped) {
+        return;
+      }
+      coalescer.flush();
+      this.finish(id, stream);
+
+      if (code !== 0) {
+        const output = stderr.trim() || `kubectl exec exited with code ${code}`;
+        const errorCode = classifyPodFileError(output);
+        emitToRenderer('file:error', {
+          streamId: id,
+          code: errorCode,
+          message: describeError(errorCode, spec, output),
+        });
+        return;
+      }
+
+      emitToRenderer('file:end', { streamId: id, exitCode: code });
+    });
+
+    console.log(`[PodFile ${id}] Streaming ${spec.path} from ${spec.namespace}/${spec.pod}`);
+    return id;
+  }
+
+  stopPodFileStream(id: string): boolean {
+    const stream = this.streams.get(id);
+    if (!stream) {
+      return false;
+    }
+
+    stream.stopped = true;
+    try {
+      stream.process.kill();
+    } catch (error) {
+      console.error(`[PodFile ${id}] Error killing kubectl:`, error);
+    }
+    this.finish(id, stream);
+    console.log(`[PodFile ${id}] Stopped`);
+    return true;
+  }
+
+  stopAllPodFileStreams(): void {
+    for (const id of Array.from(this.streams.keys())) {
+      this.stopPodFileStream(id);
+    }
+  }
+
+  private finish(id: string, stream: PodFileStream): void {
+    stream.coalescer.dispose();
+    this.streams.delete(id);
+    operationRegistry.unregister(id);
+  }
+}
+
+function emitProgress(event: PodFileDownloadEvent): void {
+  emitToRenderer('download:progress', event);
+}
+
+/**
+ * Copy one file out of a container with `kubectl exec -- cat`, writing the
+ * raw bytes straight to disk (no tar, no decoding). Output goes to
+ * `<localPath>.part` and is renamed once complete, so a failed download
+ * never leaves a truncated file behind. Emits `download:progress` while
+ * running and once when done. Returns immediately with the download id.
+ */
+export function downloadPodFile(spec: PodFileDownloadSpec): { downloadId: string } {
+  const downloadId = randomUUID();
+  const partPath = `${spec.localPath}.part`;
+
+  const args = ['exec', spec.pod];
+  if (spec.container) {
+    args.push('-c', spec.container);
+  }
+  args.push('--', 'cat', spec.remotePath);
+
+  const child = spawnKubectl(spec.context, args, spec.namespace);
+  const file = createWriteStream(partPath);
+  let bytes = 0;
+  let stderr = '';
+  let lastProgressAt = 0;
+  let finished = false;
+  let cancelled = false;
+  let exitCode: number | null = null;
+  let fileClosed = false;
+  let childExited = false;
+
+  const finish = async (error?: string, errorCode?: KubeErrorCode) => {
+    if (finished) {
+      return;
+    }
+    finished = true;
+    operationRegistry.unregister(downloadId);
+
+    if (!error) {
+      try {
+        await rename(partPath, spec.localPath);
+      } catch (renameError) {
+        error = `Failed to save ${spec.localPath}: ${renameError instanceof Error ? renameError.message : renameError}`;
+      }
+    }
+    if (error) {
+      file.destroy();
+      await unlink(partPath).catch(() => undefined);
+      console.warn(`[Download ${downloadId}] ${spec.pod}:${spec.remotePath} failed: ${error}`);
+    }
+
+    emitProgress({ downloadId, localPath: spec.localPath, bytes, done: true, ok: !error, error, errorCode });
+  };
+
+  // Both the process and the file must be done before the result is known
+  const settle = () => {
+    if (!childExited || !fileClosed) {
+      return;
+    }
+    if (cancelled) {
+      finish('Download cancelled', 'Cancelled');
+    } else if (exitCode !== 0) {
+      const message = stderr.trim() || `kubectl exited with code ${exitCode}`;
+      if (MISSING_FILE_PATTERN.test(message)) {
+        finish(`${spec.remotePath} does not exist in the container`, 'NotFound');
+      } else {
+        finish(message, classifyKubectlError(message));
+      }
+    } else {
+      finish();
+    }
+  };
+
+  child.stdout.on('data', (chunk: Buffer) => {
+    bytes += chunk.length;
+    const now = Date.now();
+    if (now - lastProgressAt >= PROGRESS_INTERVAL_MS) {
+      lastProgressAt = now;
+      emitProgress({ downloadId, localPath: spec.localPath, bytes, done: false });
+    }
+  });
+  // pipe() pauses kubectl's stdout while the disk catches up
+  child.stdout.pipe(file);
+
+  child.stderr.on('data', (chunk: Buffer) => {
+    stderr += chunk.toString();
+  });
+
+  file.on('close', () => {
+    fileClosed = true;
+    settle();
+  });
+
+  file.on('error', (error) => {
+    child.kill();
+    finish(`Failed to write ${partPath}: ${error.message}`);
+  });
+
+  child.on('error', (error) => {
+    file.end();
+    finish(`Failed to start kubectl: ${error.message}`);
+  });
+
+  child.on('close', (code) => {
+    childExited = true;
+    exitCode = code;
+    settle();
+  });
+
+  operationRegistry.register(
+    downloadId,
+    'kubectl',
+    { context: spec.context, namespace: spec.namespace, view: spec.view },
+    () => {
+      cancelled = true;
+      child.kill();
+    }
+  );
+
+  console.log(`[Download ${downloadId}] ${spec.namespace}/${spec.pod}:${spec.remotePath} -> ${spec.localPath}`);
+  return { downloadId };
+}
+
+function appendCapped(buffer: string, chunk: string): string {
+  const next = buffer + chunk;
+  return next.length > MAX_CAPTURED_OUTPUT_CHARS ? next.slice(-MAX_CAPTURED_OUTPUT_CHARS) : next;
+}
+
+/**
+ * Run a program in a container with a local file as its stdin, like
+ * `kubectl exec -i POD -- psql < dump.sql`. The file is streamed, never
+ * read into memory, and `exec:progress` reports bytes sent. The final
+ * event carries the program's exit code and the tail of its output.
+ * Returns the exec id once the file is known to be readable.
+ */
+export async function execWithStdinFile(spec: ExecStdinFileSpec): Promise<{ execId: string; totalBytes: number }> {
+  let totalBytes: number;
+  try {
+    const info = await stat(spec.stdinPath);
+    if (!info.isFile()) {
+      throw new Error('not a file');
+    }
+    totalBytes = info.size;
+  } catch {
+    throw new Error(`${spec.stdinPath} is not a readable file`);
+  }
+
+  const execId = randomUUID();
+  const args = ['exec', '-i', spec.pod];
+  if (spec.container) {
+    args.push('-c', spec.container);
+  }
+  args.push('--', ...spec.command);
+
+  const child = spawnKubectl(spec.context, args, spec.namespace);
+  const input = createReadStream(spec.stdinPath);
+  let bytesSent = 0;
+  let stdout = '';
+  let stderr = '';
+  let lastProgressAt = 0;
+  let finished = false;
+  let cancelled = false;
+
+  const finish = (event: Partial<ExecStdinProgressEvent>) => {
+    if (finished) {
+      return;
+    }
+    finished = true;
+    operationRegistry.unregister(execId);
+    input.destroy();
+    const final: ExecStdinProgressEvent = {
+      execId,
+      bytesSent,
+      totalBytes,
+      done: true,
+      exitCode: null,
+      stdout,
+      stderr,
+      ...event,
+    };
+    emitToRenderer('exec:progress', final);
+  };
+
+  input.on('data', (chunk: Buffer | string) => {
+    bytesSent += chunk.length;
+    const now = Date.now();
+    if (now - lastProgressAt >= PROGRESS_INTERVAL_MS) {
+      lastProgressAt = now;
+      const progress: ExecStdinProgressEvent = { execId, bytesSent, totalBytes, done: false };
+      emitToRenderer('exec:progress', progress);
+    }
+  });
+  // pipe() holds back the file while the exec stream is busy, and ends stdin at EOF
+  input.pipe(child.stdin);
+  input.on('error', (error) => {
+    child.kill();
+    finish({ ok: false, error: `Failed to read ${spec.stdinPath}: ${error.message}` });
+  });
+  // The program may exit before reading everything (EPIPE); its exit code says why
+  child.stdin.on('error', () => input.unpipe(child.stdin));
+
+  child.stdout.on('data', (chunk: Buffer) => {
+    stdout = appendCapped(stdout, chunk.toString());
+  });
+  child.stderr.on('data', (chunk: Buffer) => {
+    stderr = appendCapped(stderr, chunk.toString());
+  });
+
+  child.on('error', (error) => finish({ ok: false, error: `Failed to start kubectl: ${error.message}` }));
+
+  child.on('close', (code) => {
+    if (cancelled) {
+      finish({ ok: false, error: 'Exec cancelled', errorCode: 'Cancelled' });
+      return;
+    }
+    if (code === 0) {
+      finish({ ok: true, exitCode: 0 });
+      return;
+    }
+
+    const remoteExit = stderr.match(REMOTE_EXIT_PATTERN);
+    if (remoteExit) {
+      // The program ran and failed; that's its result, not a kubectl error
+      finish({ ok: false, exitCode: Number(remoteExit[1]) });
+    } else {
+      const message = stderr.trim() || `kubectl exited with code ${code}`;
+      finish({ ok: false, error: message, errorCode: classifyKubectlError(message) });
+    }
+  });
+
+  operationRegistry.register(
+    execId,
+    'kubectl',
+    { context: spec.context, namespace: spec.namespace, view: spec.view },
+    () => {
+      cancelled = true;
+      child.kill();
+    }
+  );
+
+  console.log(`[Exec ${execId}] ${spec.stdinPath} -> ${spec.namespace}/${spec.pod}: ${spec.command.join(' ')}`);
+  return { execId, totalBytes };
+}
+
+// Singleton instance
+export const podFileStreamManager = new PodFileStreamManager();
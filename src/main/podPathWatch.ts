@@ -0,0 +1,209 @@
+import { randomUUID } from 'crypto';
+import type { KubeErrorCode, PodPathChangeEvent, PodPathEntry, PodPathEntryChange } from '../common/kubeTypes';
+import { classifyKubectlError, runKubectl } from './kube';
+import { emitToRenderer } from './events';
+import { operationRegistry } from './operations';
+
+export interface PodPathWatchSpec {
+  context: string;
+  pod: string;
+  namespace: string;
+  container?: string;
+  /** A directory (its entries are compared) or a single file */
+  path: string;
+  intervalMs?: number;
+  view?: string;
+}
+
+interface PodPathWatch {
+  spec: PodPathWatchSpec;
+  timer: NodeJS.Timeout | null;
+  inFlight: boolean;
+  // Null until the first listing succeeds
+  entries: Map<string, PodPathEntry> | null;
+  error: string | null;
+}
+
+const DEFAULT_INTERVAL_MS = 2000;
+const MIN_INTERVAL_MS = 500;
+// Entries compared per listing; the rest of a huge directory is ignored
+const MAX_ENTRIES = 5000;
+
+// `ls -la --full-time` (GNU and busybox): mode, links, owner, group, size, date, time, zone, name
+const LS_LINE_PATTERN =
+  /^([-bcdlps]\S{9,10})\s+\d+\s+\S+\s+\S+\s+(\d+|\d+,\s*\d+)\s+(\d{4}-\d\d-\d\d [\d:.]+ [+-]\d{4}) (.+)$/;
+const MISSING_PATH_PATTERN = /No such file or directory/i;
+const REMOTE_EXIT_PATTERN = /command terminated with exit code \d+/;
+
+function parseListing(output: string): { entries: Map<string, PodPathEntry>; truncated: boolean } {
+  const entries: Map<string, PodPathEntry> = new Map();
+  let truncated = false;
+
+  for (const line of output.split('\n')) {
+    const match = line.match(LS_LINE_PATTERN);
+    if (!match) {
+      // "total 12" and anything unexpected
+      continue;
+    }
+    const [, mode, size, modified, rest] = match;
+    const symlink = mode.startsWith('l') ? rest.indexOf(' -> ') : -1;
+    const name = symlink >= 0 ? rest.slice(0, symlink) : rest;
+    if (name === '.' || name === '..') {
+      continue;
+    }
+    if (entries.size >= MAX_ENTRIES) {
+      truncated = true;
+      break;
+    }
+    entries.set(name, {
+      name,
+      mode,
+      size: /^\d+$/.test(size) ? Number(size) : null,
+      modified,
+      target: symlink >= 0 ? rest.slice(symlink + 4) : null,
+    });
+  }
+
+  return { entries, truncated };
+}
+
+function diffEntries(before: Map<string, PodPathEntry>, after: Map<string, PodPathEntry>): PodPathEntryChange[] {
+  const changes: PodPathEntryChange[] = [];
+  for (const [name, entry] of before) {
+    const next = after.get(name);
+    if (!next) {
+      changes.push({ change: 'removed', name, before: entry, after: null });
+    } else if (
+      next.mode !== entry.mode ||
+      next.size !== entry.size ||
+      next.modified !== entry.modified ||
+      next.target !== entry.target
+    ) {
+      changes.push({ change: 'modified', name, before: entry, after: next });
+    }
+  }
+  for (const [name, entry] of after) {
+    if (!before.has(name)) {
+      changes.push({ change: 'added', name, before: null, after: entry });
+    }
+  }
+  return changes;
+}
+
+/**
+ * Polls a path inside a container with `ls` and emits `fs:change` when
+ * entries are added, removed or modified (mode, size, mtime or symlink
+ * target), e.g. to see a ConfigMap volume update land. Works in any image
+ * with an `ls`, no inotify tooling needed. The first listing is the
+ * baseline; a path that disappears reports its entries as removed.
+ */
+export class PodPathWatchManager {
+  private watches: Map<string, PodPathWatch> = new Map();
+
+  startPodPathWatch(spec: PodPathWatchSpec): string {
+    const id = randomUUID();
+    const watch: PodPathWatch = { spec, timer: null, inFlight: false, entries: null, error: null };
+    const interval = Math.max(spec.intervalMs ?? DEFAULT_INTERVAL_MS, MIN_INTERVAL_MS);
+
+    this.watches.set(id, watch);
+    operationRegistry.register(
+      id,
+      'watches',
+      { context: spec.context, namespace: spec.namespace, view: spec.view },
+      () => this.stopPodPathWatch(id)
+    );
+    watch.timer = setInterval(() => this.poll(id, watch), interval);
+    this.poll(id, watch);
+
+    console.log(`[PathWatch ${id}] Watching ${spec.namespace}/${spec.pod}:${spec.path} every ${interval}ms`);
+    return id;
+  }
+
+  stopPodPathWatch(id: string): boolean {
+    const watch = this.watches.get(id);
+    if (!watch) {
+      return false;
+    }
+
+    if (watch.timer) {
+      clearInterval(watch.timer);
+      watch.timer = null;
+    }
+    this.watches.delete(id);
+    operationRegistry.unregister(id);
+    console.log(`[PathWatch ${id}] Stopped`);
+    return true;
+  }
+
+  stopAllPodPathWatches(): void {
+    for (const id of Array.from(this.watches.keys())) {
+      this.stopPodPathWatch(id);
+    }
+  }
+
+  private async poll(id: string, watch: PodPathWatch): Promise<void> {
+    // A slow exec shouldn't stack up listings
+    if (watch.inFlight) {
+      return;
+    }
+    watch.inFlight = true;
+
+    const { spec } = watch;
+    const args = ['exec', spec.pod];
+    if (spec.container) {
+      args.push('-c', spec.container);
+    }
+    args.push('--', 'ls', '-la', '--full-time', '--', spec.path);
+
+    let listing: { entries: Map<string, PodPathEntry>; truncated: boolean } | null = null;
+    let error: string | null = null;
+    let errorCode: KubeErrorCode | undefined;
+    try {
+      const result = await runKubectl(spec.context, args, spec.namespace, { view: spec.view });
+      const stderr = result.stderr.trim();
+      if (result.code === 0) {
+        listing = parseListing(result.stdout);
+      } else if (REMOTE_EXIT_PATTERN.test(stderr) && MISSING_PATH_PATTERN.test(stderr)) {
+        // The path is gone (or not there yet): everything in it counts as removed
+        listing = { entries: new Map(), truncated: false };
+        error = `${spec.path} does not exist`;
+        errorCode = 'NotFound';
+      } else {
+        error = stderr || `kubectl exited with code ${result.code}`;
+        errorCode = classifyKubectlError(error);
+      }
+    } catch (caught) {
+      error = caught instanceof Error ? caught.message : String(caught);
+    } finally {
+      watch.inFlight = false;
+    }
+
+    if (!this.watches.has(id)) {
+      return;
+    }
+
+    const changes = listing && watch.entries ? diffEntries(watch.entries, listing.entries) : [];
+    const errorChanged = error !== watch.error;
+    if (listing) {
+      watch.entries = listing.entries;
+    }
+    watch.error = error;
+    if (changes.length === 0 && !errorChanged) {
+      return;
+    }
+
+    const event: PodPathChangeEvent = {
+      watchId: id,
+      path: spec.path,
+      changes,
+      entries: watch.entries?.size ?? 0,
+      truncated: listing?.truncated ?? false,
+      error,
+      errorCode,
+    };
+    emitToRenderer('fs:change', event);
+  }
+}
+
+// Singleton instance
+export const podPathWatchManager = new PodPathWatchManager();
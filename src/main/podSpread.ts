@@ -0,0 +1,141 @@
+import type { PodSpread, PodSpreadNode, PodSpreadZone } from '../common/kubeTypes';
+import { getKubectlJson } from './kube';
+import { readyCondition } from './nodes';
+import { getOwnerTree } from './ownerTree';
+
+const ZONE_LABELS = ['topology.kubernetes.io/zone', 'failure-domain.beta.kubernetes.io/zone'];
+// Deployment -> ReplicaSet -> Pod and CronJob -> Job -> Pod are the deepest chains
+const OWNER_DEPTH = 3;
+
+function zoneOf(node: any): string | null {
+  const labels: Record<string, string> = node?.metadata?.labels ?? {};
+  const label = ZONE_LABELS.find((key) => labels[key]);
+  return label ? labels[label] : null;
+}
+
+function isPodReady(pod: any): boolean {
+  const conditions: any[] = pod?.status?.conditions ?? [];
+  return conditions.some((condition) => condition?.type === 'Ready' && condition?.status === 'True');
+}
+
+// Completed and terminating pods don't count towards the spread
+function isActive(pod: any): boolean {
+  const phase = pod?.status?.phase;
+  return phase !== 'Succeeded' && phase !== 'Failed' && !pod?.metadata?.deletionTimestamp;
+}
+
+function isSchedulable(node: any): boolean {
+  return node?.spec?.unschedulable !== true && readyCondition(node)?.status === 'True';
+}
+
+/**
+ * Difference between the most and least loaded domains, counting
+ * schedulable domains without pods as zero, as topologySpreadConstraints'
+ * maxSkew does. Null with fewer than two domains.
+ */
+function skewOf(counts: Map<string, number>): number | null {
+  if (counts.size < 2) {
+    return null;
+  }
+  const values = Array.from(counts.values());
+  return Math.max(...values) - Math.min(...values);
+}
+
+/**
+ * Where a workload's pods run: counts per node and per zone, to see
+ * whether replicas are spread out or piled onto one node (a missing
+ * anti-affinity or topology spread constraint). Pods are found through
+ * owner references, so a Deployment's pods are those of its ReplicaSets.
+ * Node and zone skew take the empty schedulable nodes into account when
+ * nodes can be listed; without that permission they cover only the nodes
+ * the pods are on, and zones are unknown.
+ */
+export async function getWorkloadPodSpread(
+  kind: string,
+  name: string,
+  namespace: string,
+  context: string
+): Promise<PodSpread> {
+  const tree = await getOwnerTree(kind, name, namespace, context, {
+    includeDescendants: true,
+    maxDepth: OWNER_DEPTH,
+  });
+  const root = tree.nodes.find((node) => node.key === tree.root);
+  const podNames = new Set(tree.nodes.filter((node) => node.kind === 'Pod').map((node) => node.name));
+
+  const [podList, nodeList] = await Promise.all([
+    podNames.size > 0 ? getKubectlJson(context, ['get', 'pods'], namespace) : Promise.resolve({ items: [] }),
+    getKubectlJson(context, ['get', 'nodes']).catch((error) => {
+      console.warn('[PodSpread] Could not list nodes:', error instanceof Error ? error.message : error);
+      return null;
+    }),
+  ]);
+  const pods: any[] = (podList?.items ?? []).filter(
+    (pod: any) => podNames.has(pod?.metadata?.name) && isActive(pod)
+  );
+  const clusterNodes: any[] | null = nodeList ? nodeList.items ?? [] : null;
+  const nodesByName = new Map<string, any>((clusterNodes ?? []).map((node) => [node?.metadata?.name, node]));
+
+  const byNode = new Map<string, PodSpreadNode>();
+  const unscheduled: string[] = [];
+  for (const pod of pods) {
+    const nodeName: string | undefined = pod?.spec?.nodeName;
+    if (!nodeName) {
+      unscheduled.push(pod.metadata.name);
+      continue;
+    }
+    let entry = byNode.get(nodeName);
+    if (!entry) {
+      entry = { node: nodeName, zone: zoneOf(nodesByName.get(nodeName)), pods: [], count: 0, ready: 0 };
+      byNode.set(nodeName, entry);
+    }
+    entry.pods.push(pod.metadata.name);
+    entry.count++;
+    if (isPodReady(pod)) {
+      entry.ready++;
+    }
+  }
+
+  const nodeCounts = new Map<string, number>();
+  const zoneCounts = new Map<string, number>();
+  for (const node of clusterNodes ?? []) {
+    if (isSchedulable(node)) {
+      nodeCounts.set(node.metadata.name, 0);
+      const zone = zoneOf(node);
+      if (zone) {
+        zoneCounts.set(zone, 0);
+      }
+    }
+  }
+
+  const zones = new Map<string, PodSpreadZone>();
+  for (const entry of byNode.values()) {
+    nodeCounts.set(entry.node, entry.count);
+    if (!entry.zone) {
+      continue;
+    }
+    let zone = zones.get(entry.zone);
+    if (!zone) {
+      zone = { zone: entry.zone, nodes: 0, count: 0 };
+      zones.set(entry.zone, zone);
+    }
+    zone.nodes++;
+    zone.count += entry.count;
+    zoneCounts.set(entry.zone, zone.count);
+  }
+
+  const nodes = Array.from(byNode.values()).sort((a, b) => b.count - a.count || a.node.localeCompare(b.node));
+  return {
+    kind: root?.kind ?? kind,
+    name,
+    namespace,
+    totalPods: pods.length,
+    nodes,
+    zones: Array.from(zones.values()).sort((a, b) => b.count - a.count || a.zone.localeCompare(b.zone)),
+    unscheduled: unscheduled.sort(),
+    maxPerNode: nodes[0]?.count ?? 0,
+    nodeSkew: skewOf(nodeCounts),
+    zoneSkew: skewOf(zoneCounts),
+    nodesListed: clusterNodes !== null,
+  };
+}
@@ -0,0 +1,293 @@
+import type {
+  ContainerImageStatus,
+  ContainerProbes,
+  ContainerResources,
+  ContainerRestartInfo,
+  ContainerTermination,
+  PodPhaseEvent,
+  PodQosClass,
+  PodResources,
+  ProbeDefinition,
+  ResourceValues,
+} from '../common/kubeTypes';
+import { getKubectlJson } from './kube';
+import { parseCpuMillicores, parseMemoryBytes } from './quantity';
+import { emitToRenderer } from './events';
+import { watchManager } from './watch';
+
+function countReadyContainers(pod: any): { ready: number; total: number } {
+  const statuses: any[] = Array.isArray(pod?.status?.containerStatuses)
+    ? pod.status.containerStatuses
+    : [];
+  const total = Array.isArray(pod?.spec?.containers) ? pod.spec.containers.length : statuses.length;
+  const ready = statuses.filter((status) => status?.ready === true).length;
+
+  return { ready, total };
+}
+
+/**
+ * Watch a single pod and emit `pod:phase` whenever its phase or
+ * ready container count changes. Returns the watch id.
+ */
+export async function watchPod(pod: string, namespace: string, context: string): Promise<string> {
+  // Fail fast: a watch on a missing pod would just sit idle
+  await getKubectlJson(context, ['get', 'pod', pod], namespace);
+
+  let lastState = '';
+
+  const watchId = watchManager.startWatch(
+    { context, kind: 'pod', name: pod, namespace },
+    (event) => {
+      const containers = countReadyContainers(event.object);
+      const payload: PodPhaseEvent = {
+        watchId,
+        pod,
+        namespace,
+        phase: event.object?.status?.phase ?? 'Unknown',
+        readyContainers: containers.ready,
+        totalContainers: containers.total,
+        deleted: event.type === 'DELETED',
+      };
+
+      if (payload.deleted) {
+        emitToRenderer('pod:phase', payload);
+        watchManager.stopWatch(watchId);
+        return;
+      }
+
+      if (event.type !== 'ADDED' && event.type !== 'MODIFIED') {
+        return;
+      }
+
+      // Reopened watches replay the current state; only report transitions
+      const state = `${payload.phase}:${payload.readyContainers}/${payload.totalContainers}`;
+      if (state === lastState) {
+        return;
+      }
+      lastState = state;
+      emitToRenderer('pod:phase', payload);
+    },
+    (message) => emitToRenderer('watch:error', watchId, message)
+  );
+
+  return watchId;
+}
+
+function containerImages(pod: any, init: boolean): ContainerImageStatus[] {
+  const specs: any[] = (init ? pod?.spec?.initContainers : pod?.spec?.containers) ?? [];
+  const statuses: any[] = (init ? pod?.status?.initContainerStatuses : pod?.status?.containerStatuses) ?? [];
+
+  return specs.map((container) => {
+    const status = statuses.find((candidate) => candidate?.name === container.name);
+    const waiting = status?.state?.waiting;
+
+    return {
+      name: container.name,
+      init,
+      image: container.image ?? status?.image ?? '',
+      imageID: status?.imageID ?? '',
+      imagePullPolicy: container.imagePullPolicy ?? '',
+      reason: waiting?.reason ?? null,
+      message: waiting?.message ?? null,
+    };
+  });
+}
+
+/**
+ * Images of every container (init containers first) with pull status,
+ * for a quick "why isn't my pod starting" view
+ */
+export async function getPodImages(pod: string, namespace: string, context: string): Promise<ContainerImageStatus[]> {
+  const object = await getKubectlJson(context, ['get', 'pod', pod], namespace);
+  return [...containerImages(object, true), ...containerImages(object, false)];
+}
+
+function toTermination(terminated: any): ContainerTermination | null {
+  if (!terminated) {
+    return null;
+  }
+  return {
+    reason: terminated.reason ?? null,
+    exitCode: typeof terminated.exitCode === 'number' ? terminated.exitCode : null,
+    signal: typeof terminated.signal === 'number' ? terminated.signal : null,
+    startedAt: terminated.startedAt ?? null,
+    finishedAt: terminated.finishedAt ?? null,
+  };
+}
+
+function containerRestarts(pod: any, init: boolean): ContainerRestartInfo[] {
+  const specs: any[] = (init ? pod?.spec?.initContainers : pod?.spec?.containers) ?? [];
+  const statuses: any[] = (init ? pod?.status?.initContainerStatuses : pod?.status?.containerStatuses) ?? [];
+
+  return specs.map((container) => {
+    const status = statuses.find((candidate) => candidate?.name === container.name);
+    // `state` (and `lastState`) hold exactly one of running/waiting/terminated
+    const current = status?.state ?? {};
+    const lastTermination = toTermination(status?.lastState?.terminated);
+    const restartCount: number = status?.restartCount ?? 0;
+
+    let state: ContainerRestartInfo['state'] = 'unknown';
+    let stateReason: string | null = null;
+    if (current.running) {
+      state = 'running';
+    } else if (current.waiting) {
+      state = 'waiting';
+      stateReason = current.waiting.reason ?? null;
+    } else if (current.terminated) {
+      state = 'terminated';
+      stateReason = current.terminated.reason ?? null;
+    }
+
+    // The current instance started at the restart; while it waits (e.g.
+    // CrashLoopBackOff) the best we have is when the previous one ended
+    let lastRestartAt: string | null = null;
+    if (restartCount > 0) {
+      lastRestartAt =
+        current.running?.startedAt ?? current.terminated?.startedAt ?? lastTermination?.finishedAt ?? null;
+    }
+
+    return {
+      name: container.name,
+      init,
+      restartCount,
+      state,
+      stateReason,
+      lastTermination,
+      lastRestartAt,
+    };
+  });
+}
+
+/**
+ * Restart counts and the last termination of every container (init
+ * containers first), for a restart-history tooltip
+ */
+export async function getContainerRestarts(
+  pod: string,
+  namespace: string,
+  context: string
+): Promise<ContainerRestartInfo[]> {
+  const object = await getKubectlJson(context, ['get', 'pod', pod], namespace);
+  return [...containerRestarts(object, true), ...containerRestarts(object, false)];
+}
+
+const PROBE_TYPES = ['httpGet', 'tcpSocket', 'grpc', 'exec'] as const;
+
+function toProbe(probe: any, container: any): ProbeDefinition | null {
+  if (!probe || typeof probe !== 'object') {
+    return null;
+  }
+
+  const handler = probe.httpGet ?? probe.tcpSocket ?? probe.grpc ?? null;
+  // Named ports refer to the container's ports list
+  let port: number | null = typeof handler?.port === 'number' ? handler.port : null;
+  const portName: string | null = typeof handler?.port === 'string' ? handler.port : null;
+  if (portName) {
+    const named = (container?.ports ?? []).find((entry: any) => entry?.name === portName);
+    port = typeof named?.containerPort === 'number' ? named.containerPort : Number(portName) || null;
+  }
+
+  return {
+    type: PROBE_TYPES.find((type) => probe[type]) ?? 'unknown',
+    path: probe.httpGet?.path ?? null,
+    scheme: probe.httpGet?.scheme ?? null,
+    host: handler?.host ?? null,
+    port,
+    portName,
+    command: Array.isArray(probe.exec?.command) ? probe.exec.command : null,
+    grpcService: probe.grpc?.service ?? null,
+    // The API server fills these in; the fallbacks are its defaults
+    initialDelaySeconds: probe.initialDelaySeconds ?? 0,
+    periodSeconds: probe.periodSeconds ?? 10,
+    timeoutSeconds: probe.timeoutSeconds ?? 1,
+    successThreshold: probe.successThreshold ?? 1,
+    failureThreshold: probe.failureThreshold ?? 3,
+  };
+}
+
+function containerProbes(pod: any, init: boolean): ContainerProbes[] {
+  const specs: any[] = (init ? pod?.spec?.initContainers : pod?.spec?.containers) ?? [];
+  const statuses: any[] = (init ? pod?.status?.initContainerStatuses : pod?.status?.containerStatuses) ?? [];
+
+  return specs.map((container) => {
+    const status = statuses.find((candidate) => candidate?.name === container.name);
+    return {
+      name: container.name,
+      init,
+      ready: status?.ready === true,
+      started: typeof status?.started === 'boolean' ? status.started : null,
+      liveness: toProbe(container.livenessProbe, container),
+      readiness: toProbe(container.readinessProbe, container),
+      startup: toProbe(container.startupProbe, container),
+    };
+  });
+}
+
+/**
+ * Liveness, readiness and startup probes of every container (init
+ * containers first) next to its ready state, for "why isn't this pod
+ * ready". Only sidecar-style init containers can have probes.
+ */
+export async function getPodProbes(pod: string, namespace: string, context: string): Promise<ContainerProbes[]> {
+  const object = await getKubectlJson(context, ['get', 'pod', pod], namespace);
+  return [...containerProbes(object, true), ...containerProbes(object, false)];
+}
+
+const QOS_CLASSES: PodQosClass[] = ['Guaranteed', 'Burstable', 'BestEffort'];
+
+function resourceValues(values: any): ResourceValues {
+  const cpu = typeof values?.cpu === 'string' || typeof values?.cpu === 'number' ? String(values.cpu) : null;
+  const memory =
+    typeof values?.memory === 'string' || typeof values?.memory === 'number' ? String(values.memory) : null;
+  return { cpu, memory, cpuMillicores: parseCpuMillicores(cpu), memoryBytes: parseMemoryBytes(memory) };
+}
+
+function containerResources(pod: any, init: boolean): ContainerResources[] {
+  const specs: any[] = (init ? pod?.spec?.initContainers : pod?.spec?.containers) ?? [];
+  return specs.map((container) => ({
+    name: container.name,
+    init,
+    sidecar: init && container.restartPolicy === 'Always',
+    requests: resourceValues(container.resources?.requests),
+    limits: resourceValues(container.resources?.limits),
+  }));
+}
+
+/**
+ * The kubelet's rules, for pods whose status doesn't carry qosClass yet.
+ * Requests left unset default to the limits when the pod is created, so
+ * they're already filled in here.
+ */
+function computeQosClass(containers: ContainerResources[]): PodQosClass {
+  const values = containers.flatMap((container) => [container.requests, container.limits]);
+  if (values.every((value) => value.cpu === null && value.memory === null)) {
+    return 'BestEffort';
+  }
+
+  const guaranteed = containers.every(
+    ({ requests, limits }) =>
+      limits.cpuMillicores !== null &&
+      limits.memoryBytes !== null &&
+      requests.cpuMillicores === limits.cpuMillicores &&
+      requests.memoryBytes === limits.memoryBytes
+  );
+  return guaranteed ? 'Guaranteed' : 'Burstable';
+}
+
+/**
+ * QoS class and the CPU/memory requests and limits of every container
+ * (init containers first), for a resources tab and for telling why a pod
+ * is throttled or OOM-killed. Quantities come back as written and parsed.
+ */
+export async function getPodResources(pod: string, namespace: string, context: string): Promise<PodResources> {
+  const object = await getKubectlJson(context, ['get', 'pod', pod], namespace);
+  const containers = [...containerResources(object, true), ...containerResources(object, false)];
+  const reported = object?.status?.qosClass;
+
+  return {
+    pod,
+    namespace,
+    qosClass: QOS_CLASSES.includes(reported) ? reported : computeQosClass(containers),
+    containers,
+  };
+}
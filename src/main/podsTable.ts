@@ -0,0 +1,149 @@
+import type { PodTableRow, PodTableUpdateEvent } from '../common/kubeTypes';
+import { emitToRenderer } from './events';
+import { watchManager } from './watch';
+
+export interface PodsTableSpec {
+  context: string;
+  /** Omit to watch every namespace */
+  namespace?: string;
+  /** Only pods scheduled on this node */
+  node?: string;
+  view?: string;
+}
+
+// Several updates to one row within this window are sent once
+const COALESCE_INTERVAL_MS = 250;
+
+/**
+ * Status column the way kubectl computes it: a waiting/terminated container
+ * reason wins over the phase, and deletion shows as Terminating
+ */
+function podDisplayStatus(pod: any): string {
+  const status = pod?.status ?? {};
+  let reason: string = status.reason || status.phase || 'Unknown';
+
+  const initStatuses: any[] = status.initContainerStatuses ?? [];
+  for (let index = 0; index < initStatuses.length; index++) {
+    const state = initStatuses[index]?.state ?? {};
+    if (state.terminated && state.terminated.exitCode === 0) {
+      continue;
+    }
+    if (state.terminated) {
+      reason = `Init:${state.terminated.reason || `ExitCode:${state.terminated.exitCode}`}`;
+    } else if (state.waiting?.reason && state.waiting.reason !== 'PodInitializing') {
+      reason = `Init:${state.waiting.reason}`;
+    } else {
+      reason = `Init:${index}/${initStatuses.length}`;
+    }
+    return pod?.metadata?.deletionTimestamp ? 'Terminating' : reason;
+  }
+
+  for (const container of status.containerStatuses ?? []) {
+    const state = container?.state ?? {};
+    if (state.waiting?.reason) {
+      reason = state.waiting.reason;
+    } else if (state.terminated) {
+      reason = state.terminated.reason || `ExitCode:${state.terminated.exitCode}`;
+    }
+  }
+
+  if (pod?.metadata?.deletionTimestamp) {
+    return status.reason === 'NodeLost' ? 'Unknown' : 'Terminating';
+  }
+  return reason;
+}
+
+function toPodTableRow(pod: any): PodTableRow {
+  const statuses: any[] = pod?.status?.containerStatuses ?? [];
+  const total = Array.isArray(pod?.spec?.containers) ? pod.spec.containers.length : statuses.length;
+  const ready = statuses.filter((status) => status?.ready === true).length;
+
+  return {
+    name: pod?.metadata?.name ?? '',
+    namespace: pod?.metadata?.namespace ?? '',
+    ready: `${ready}/${total}`,
+    status: podDisplayStatus(pod),
+    restarts: statuses.reduce((sum, status) => sum + (status?.restartCount ?? 0), 0),
+    createdAt: pod?.metadata?.creationTimestamp ?? null,
+    node: pod?.spec?.nodeName || null,
+    ip: pod?.status?.podIP || null,
+  };
+}
+
+function rowKey(namespace: string, name: string): string {
+  return `${namespace}/${name}`;
+}
+
+/**
+ * Watch pods and emit `pods-table:update` with ready-to-render rows instead
+ * of raw objects. Changes are coalesced per row, and rows that didn't
+ * change (e.g. replayed after a watch reopens) aren't sent again. Returns
+ * the watch id; stop it with the usual stopWatch.
+ */
+export function watchPodsTable(spec: PodsTableSpec): string {
+  // Last row sent per pod, to skip no-op updates
+  const sent: Map<string, string> = new Map();
+  // Row to send, or a `{ removed: true }` marker when the pod was deleted
+  const pending: Map<string, PodTableRow | { name: string; namespace: string; removed: true }> = new Map();
+  let flushTimer: NodeJS.Timeout | null = null;
+
+  const flush = () => {
+    flushTimer = null;
+    if (!watchManager.hasWatch(watchId)) {
+      pending.clear();
+      return;
+    }
+
+    const event: PodTableUpdateEvent = { watchId, upserts: [], removed: [] };
+    for (const [key, entry] of pending) {
+      if ('removed' in entry) {
+        if (sent.delete(key)) {
+          event.removed.push({ name: entry.name, namespace: entry.namespace });
+        }
+        continue;
+      }
+
+      const serialized = JSON.stringify(entry);
+      if (sent.get(key) !== serialized) {
+        sent.set(key, serialized);
+        event.upserts.push(entry);
+      }
+    }
+    pending.clear();
+
+    if (event.upserts.length > 0 || event.removed.length > 0) {
+      emitToRenderer('pods-table:update', event);
+    }
+  };
+
+  const watchId = watchManager.startWatch(
+    {
+      context: spec.context,
+      kind: 'pods',
+      namespace: spec.namespace,
+      allNamespaces: !spec.namespace,
+      fieldSelector: spec.node ? `spec.nodeName=${spec.node}` : undefined,
+      view: spec.view,
+    },
+    (event) => {
+      if (event.type !== 'ADDED' && event.type !== 'MODIFIED' && event.type !== 'DELETED') {
+        return;
+      }
+
+      const name: string | undefined = event.object?.metadata?.name;
+      if (!name) {
+        return;
+      }
+      const namespace: string = event.object?.metadata?.namespace ?? '';
+      const key = rowKey(namespace, name);
+
+      pending.set(key, event.type === 'DELETED' ? { name, namespace, removed: true } : toPodTableRow(event.object));
+      if (!flushTimer) {
+        flushTimer = setTimeout(flush, COALESCE_INTERVAL_MS);
+      }
+    },
+    (message) => emitToRenderer('watch:error', watchId, message)
+  );
+
+  return watchId;
+}
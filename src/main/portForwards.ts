@@ -0,0 +1,356 @@
+import { randomUUID } from 'crypto';
+import type { ChildProcessWithoutNullStreams } from 'child_process';
+import type {
+  ForwardInfo,
+  ForwardStatusEvent,
+  PortForwardTargetKind,
+  StreamReconnectedEvent,
+  StreamReconnectingEvent,
+} from '../common/kubeTypes';
+import { spawnKubectl, getKubectlJson, KubeError } from './kube';
+import { emitToRenderer } from './events';
+import { Reconnector } from './reconnect';
+
+export interface ApiProxyOptions {
+  context: string;
+  port?: number;
+  autoRestart?: boolean;
+}
+
+export interface PortForwardOptions {
+  context: string;
+  namespace: string;
+  targetKind: PortForwardTargetKind;
+  name: string;
+  remotePort: number;
+  /** Omit (or 0) for an ephemeral local port */
+  localPort?: number;
+  autoRestart?: boolean;
+}
+
+interface ForwardHandle {
+  info: ForwardInfo;
+  /** kubectl arguments for a given local port */
+  buildArgs: (port: number) => string[];
+  /** Matches the line announcing the bound port, port in group 1 */
+  readyPattern: RegExp;
+  /** Port requested for the next spawn; 0 asks kubectl for an ephemeral one */
+  requestedPort: number;
+  process: ChildProcessWithoutNullStreams | null;
+  reconnector: Reconnector;
+  /** When the current process started forwarding */
+  readyAt: number;
+  stopped: boolean;
+}
+
+// kubectl proxy's own default
+const DEFAULT_PROXY_PORT = 8001;
+const READY_TIMEOUT_MS = 15000;
+// A forward that ran this long before dropping starts its backoff afresh;
+// one that keeps crashing right after starting runs out of attempts
+const STABLE_FORWARD_MS = 60000;
+
+const PROXY_READY_PATTERN = /Starting to serve on [^\s:]+:(\d+)/;
+// "Forwarding from 127.0.0.1:8080 -> 80"
+const PORT_FORWARD_READY_PATTERN = /Forwarding from \S+:(\d+) ->/;
+const PORT_IN_USE_PATTERN = /address already in use/i;
+const FORWARD_CONNECTION_ERROR_PATTERN = /an error occurred forwarding[^\n]*/i;
+
+const TARGET_RESOURCES: Record<PortForwardTargetKind, string> = {
+  pod: 'pod',
+  service: 'svc',
+  deployment: 'deployment',
+};
+
+function processError(error: NodeJS.ErrnoException): string {
+  return error?.code === 'ENOENT'
+    ? 'kubectl executable not found. Install kubectl or add it to your PATH.'
+    : error.message;
+}
+
+function hasReadyEndpoints(endpoints: any): boolean {
+  const subsets: any[] = endpoints?.subsets ?? [];
+  return subsets.some((subset) => Array.isArray(subset?.addresses) && subset.addresses.length > 0);
+}
+
+/**
+ * Owns long-running forwarding processes (`kubectl proxy` and
+ * `kubectl port-forward`) so they can be listed, stopped, and restarted
+ * when they crash
+ */
+export class PortForwardManager {
+  private forwards: Map<string, ForwardHandle> = new Map();
+
+  /**
+   * Start `kubectl proxy` and resolve once it reports the port it bound.
+   * A busy port is retried once with an ephemeral port.
+   */
+  async startApiProxy(options: ApiProxyOptions): Promise<ForwardInfo> {
+    const handle = this.createHandle(
+      {
+        kind: 'api-proxy',
+        context: options.context,
+        target: 'api-server',
+        remotePort: null,
+        backingPod: null,
+        autoRestart: options.autoRestart === true,
+      },
+      (port) => ['proxy', `--port=${port}`],
+      PROXY_READY_PATTERN,
+      options.port ?? DEFAULT_PROXY_PORT
+    );
+
+    await this.start(handle);
+    console.log(`[Forward ${handle.info.id}] API proxy for ${options.context} serving on port ${handle.info.localPort}`);
+    return { ...handle.info };
+  }
+
+  /**
+   * Start `kubectl port-forward` to a pod, service or deployment. Services
+   * and deployments are checked for ready backends first so a forward with
+   * nothing behind it fails fast instead of hanging.
+   */
+  async startPortForward(options: PortForwardOptions): Promise<ForwardInfo> {
+    const resource = `${TARGET_RESOURCES[options.targetKind]}/${options.name}`;
+    const id = randomUUID();
+
+    await this.checkTarget(id, options);
+
+    const handle = this.createHandle(
+      {
+        kind: 'port-forward',
+        context: options.context,
+        namespace: options.namespace,
+        target: `${resource}:${options.remotePort}`,
+        remotePort: options.remotePort,
+        backingPod: options.targetKind === 'pod' ? options.name : null,
+        autoRestart: options.autoRestart === true,
+      },
+      (port) => ['port-forward', resource, `${port}:${options.remotePort}`],
+      PORT_FORWARD_READY_PATTERN,
+      options.localPort ?? 0,
+      id
+    );
+
+    await this.start(handle);
+    console.log(`[Forward ${id}] ${resource}:${options.remotePort} forwarded to port ${handle.info.localPort}`);
+    return { ...handle.info };
+  }
+
+  stopForward(id: string): boolean {
+    const handle = this.forwards.get(id);
+    if (!handle) {
+      return false;
+    }
+
+    handle.stopped = true;
+    handle.reconnector.cancel();
+    if (handle.process) {
+      try {
+        handle.process.kill();
+      } catch (error) {
+        console.error(`[Forward ${id}] Error killing kubectl:`, error);
+      }
+      handle.process = null;
+    }
+
+    this.forwards.delete(id);
+    console.log(`[Forward ${id}] Stopped`);
+    return true;
+  }
+
+  stopAllForwards(): void {
+    for (const id of Array.from(this.forwards.keys())) {
+      this.stopForward(id);
+    }
+  }
+
+  listForwards(): ForwardInfo[] {
+    return Array.from(this.forwards.values()).map((handle) => ({ ...handle.info }));
+  }
+
+  private createHandle(
+    info: Omit<ForwardInfo, 'id' | 'localPort' | 'status' | 'startedAt'>,
+    buildArgs: (port: number) => string[],
+    readyPattern: RegExp,
+    requestedPort: number,
+    id: string = randomUUID()
+  ): ForwardHandle {
+    const handle: ForwardHandle = {
+      info: { ...info, id, localPort: null, status: 'starting', startedAt: Date.now() },
+      buildArgs,
+      readyPattern,
+      requestedPort,
+      process: null,
+      reconnector: new Reconnector(),
+      readyAt: 0,
+      stopped: false,
+    };
+
+    this.forwards.set(id, handle);
+    return handle;
+  }
+
+  private async checkTarget(id: string, options: PortForwardOptions): Promise<void> {
+    let ready = true;
+
+    if (options.targetKind === 'service') {
+      const endpoints = await getKubectlJson(options.context, ['get', 'endpoints', options.name], options.namespace);
+      ready = hasReadyEndpoints(endpoints);
+    } else if (options.targetKind === 'deployment') {
+      const deployment = await getKubectlJson(options.context, ['get', 'deployment', options.name], options.namespace);
+      ready = (deployment?.status?.readyReplicas ?? 0) > 0;
+    }
+
+    if (!ready) {
+      const message = `No endpoints available: ${options.targetKind} ${options.name} has no ready pods`;
+      const event: ForwardStatusEvent = { id, status: 'no-endpoints', message };
+      emitToRenderer('forward:status', event);
+      throw new KubeError('KubectlFailed', message);
+    }
+  }
+
+  private async start(handle: ForwardHandle): Promise<void> {
+    const { id } = handle.info;
+
+    try {
+      await this.spawnForward(handle);
+    } catch (error) {
+      if (!(error instanceof Error) || !PORT_IN_USE_PATTERN.test(error.message) || handle.requestedPort === 0) {
+        this.stopForward(id);
+        throw error;
+      }
+
+      console.warn(`[Forward ${id}] Port ${handle.requestedPort} is in use, retrying with an ephemeral port`);
+      handle.requestedPort = 0;
+      try {
+        await this.spawnForward(handle);
+      } catch (retryError) {
+        this.stopForward(id);
+        throw retryError;
+      }
+    }
+  }
+
+  private spawnForward(handle: ForwardHandle): Promise<void> {
+    const { id } = handle.info;
+
+    return new Promise<void>((resolve, reject) => {
+      const child = spawnKubectl(handle.info.context, handle.buildArgs(handle.requestedPort), handle.info.namespace);
+      let output = '';
+      let ready = false;
+
+      handle.process = child;
+
+      const timeout = setTimeout(() => {
+        if (!ready) {
+          child.kill();
+          reject(new Error(`Timed out waiting for kubectl to start forwarding ${handle.info.target}`));
+        }
+      }, READY_TIMEOUT_MS);
+
+      const onOutput = (chunk: Buffer) => {
+        const text = chunk.toString();
+
+        if (ready) {
+          // Per-connection failures don't end the forward, but the UI should know
+          const connectionError = text.match(FORWARD_CONNECTION_ERROR_PATTERN);
+          if (connectionError) {
+            const event: ForwardStatusEvent = { id, status: 'connection-error', message: connectionError[0] };
+            emitToRenderer('forward:status', event);
+          }
+          return;
+        }
+        output += text;
+
+        const match = output.match(handle.readyPattern);
+        if (match) {
+          ready = true;
+          handle.readyAt = Date.now();
+          clearTimeout(timeout);
+          handle.info.localPort = Number(match[1]);
+          handle.info.status = 'running';
+          // Keep the bound port so restarts don't move the forward around
+          handle.requestedPort = handle.info.localPort;
+          resolve();
+        }
+      };
+
+      child.stdout.on('data', onOutput);
+      child.stderr.on('data', onOutput);
+
+      child.once('error', (error: NodeJS.ErrnoException) => {
+        clearTimeout(timeout);
+        if (!ready) {
+          reject(new Error(processError(error)));
+          return;
+        }
+        this.handleExit(handle, processError(error));
+      });
+
+      child.once('close', (code) => {
+        clearTimeout(timeout);
+        if (handle.process === child) {
+          handle.process = null;
+        }
+        if (!ready) {
+          reject(new Error(output.trim() || `kubectl exited with code ${code}`));
+          return;
+        }
+        this.handleExit(handle, output.trim().split('\n').pop() || `kubectl exited with code ${code}`);
+      });
+    });
+  }
+
+  private handleExit(handle: ForwardHandle, message: string): void {
+    const { id } = handle.info;
+    if (handle.stopped) {
+      return;
+    }
+
+    if (handle.readyAt && Date.now() - handle.readyAt >= STABLE_FORWARD_MS) {
+      handle.reconnector.reset();
+    }
+    handle.readyAt = 0;
+
+    const delayMs = handle.info.autoRestart
+      ? handle.reconnector.schedule(() => {
+          if (handle.stopped) {
+            return;
+          }
+          this.spawnForward(handle)
+            .then(() => {
+              const reconnected: StreamReconnectedEvent = { id, attempts: handle.reconnector.attempts };
+              emitToRenderer('forward:reconnected', reconnected);
+              // Carries the updated info (the bound port may differ)
+              emitToRenderer('forward:restarted', { ...handle.info });
+            })
+            .catch((error) => this.handleExit(handle, error instanceof Error ? error.message : String(error)));
+        })
+      : null;
+
+    if (delayMs === null) {
+      console.error(`[Forward ${id}] Exited: ${message}`);
+      this.stopForward(id);
+      emitToRenderer('forward:exited', { id, message });
+      return;
+    }
+
+    handle.info.status = 'restarting';
+    const event: StreamReconnectingEvent = {
+      id,
+      attempt: handle.reconnector.attempts,
+      maxAttempts: handle.reconnector.policy.maxAttempts,
+      delayMs,
+      message,
+    };
+    console.warn(
+      `[Forward ${id}] Exited (${message}), restarting in ${delayMs}ms ` +
+        `(${event.attempt}/${event.maxAttempts})`
+    );
+    emitToRenderer('forward:reconnecting', event);
+  }
+}
+
+// Singleton instance
+export const portForwardManager = new PortForwardManager();
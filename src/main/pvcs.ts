@@ -0,0 +1,149 @@
+import type { PvcStatusEvent } from '../common/kubeTypes';
+import { getKubectlJson } from './kube';
+import { emitToRenderer } from './events';
+import { watchManager } from './watch';
+
+// While a claim is Pending, its events are checked this often for the reason
+const PENDING_EVENT_POLL_MS = 5000;
+
+// Event reasons that explain a Pending claim, most useful first
+const PENDING_REASONS = [
+  'ProvisioningFailed',
+  'FailedBinding',
+  'WaitForFirstConsumer',
+  'ExternalProvisioning',
+  'Provisioning',
+];
+
+function describePending(reason: string, message: string): string {
+  const missingClass = message.match(/storageclass\.storage\.k8s\.io "([^"]+)" not found/i);
+  if (missingClass) {
+    return `StorageClass "${missingClass[1]}" does not exist. Fix storageClassName or create the class.`;
+  }
+  if (/no storage class is set/i.test(message)) {
+    return 'No StorageClass is set and no PersistentVolume matches. Set storageClassName or mark a StorageClass as default.';
+  }
+
+  switch (reason) {
+    case 'ProvisioningFailed':
+      return `Provisioning failed: ${message}`;
+    case 'WaitForFirstConsumer':
+      return 'The volume is created once a pod using this claim is scheduled.';
+    case 'ExternalProvisioning':
+    case 'Provisioning':
+      return 'Waiting for the storage provisioner to create the volume.';
+    default:
+      return message;
+  }
+}
+
+async function pendingReason(
+  name: string,
+  namespace: string,
+  context: string
+): Promise<{ reason: string; message: string } | null> {
+  const list = await getKubectlJson(
+    context,
+    ['get', 'events', '--field-selector', `involvedObject.kind=PersistentVolumeClaim,involvedObject.name=${name}`],
+    namespace
+  );
+
+  const events: any[] = (list?.items ?? []).filter((event: any) => PENDING_REASONS.includes(event.reason));
+  if (events.length === 0) {
+    return null;
+  }
+
+  const time = (event: any) => Date.parse(event.lastTimestamp ?? event.eventTime ?? event.firstTimestamp ?? '') || 0;
+  const latest = events.reduce((newest, event) => (time(event) >= time(newest) ? event : newest));
+  return { reason: latest.reason, message: describePending(latest.reason, latest.message ?? '') };
+}
+
+/**
+ * Watch a PersistentVolumeClaim and emit `pvc:status` as it moves from
+ * Pending to Bound. While Pending, the claim's events are checked so the
+ * UI can say why (missing StorageClass, provisioning failure, waiting for
+ * a consumer). Returns the watch id.
+ */
+export async function watchPvc(name: string, namespace: string, context: string): Promise<string> {
+  // Fail fast with NotFound rather than watching nothing
+  await getKubectlJson(context, ['get', 'pvc', name], namespace);
+
+  let last: PvcStatusEvent | null = null;
+  let lastState = '';
+  let pollTimer: NodeJS.Timeout | null = null;
+
+  const emit = (event: PvcStatusEvent) => {
+    const state = JSON.stringify({ ...event, watchId: undefined });
+    if (state === lastState) {
+      return;
+    }
+    lastState = state;
+    last = event;
+    emitToRenderer('pvc:status', event);
+  };
+
+  const stopPolling = () => {
+    if (pollTimer) {
+      clearInterval(pollTimer);
+      pollTimer = null;
+    }
+  };
+
+  const checkEvents = async () => {
+    if (!watchManager.hasWatch(watchId)) {
+      stopPolling();
+      return;
+    }
+    try {
+      const pending = await pendingReason(name, namespace, context);
+      if (last && last.phase === 'Pending' && watchManager.hasWatch(watchId)) {
+        emit({ ...last, reason: pending?.reason ?? null, message: pending?.message ?? null });
+      }
+    } catch (error) {
+      console.warn(`[PVC ${namespace}/${name}] Could not read events:`, error);
+    }
+  };
+
+  const watchId = watchManager.startWatch(
+    { context, kind: 'pvc', name, namespace },
+    (event) => {
+      if (event.type !== 'ADDED' && event.type !== 'MODIFIED' && event.type !== 'DELETED') {
+        return;
+      }
+
+      const claim = event.object;
+      const phase: string = claim?.status?.phase ?? 'Pending';
+      const next: PvcStatusEvent = {
+        watchId,
+        name,
+        namespace,
+        phase,
+        volumeName: claim?.spec?.volumeName || null,
+        capacity: claim?.status?.capacity?.storage ?? null,
+        storageClass: claim?.spec?.storageClassName ?? null,
+        reason: phase === 'Pending' ? last?.reason ?? null : null,
+        message: phase === 'Pending' ? last?.message ?? null : null,
+        deleted: event.type === 'DELETED',
+      };
+      emit(next);
+
+      if (next.deleted) {
+        stopPolling();
+        watchManager.stopWatch(watchId);
+        return;
+      }
+
+      if (phase === 'Pending') {
+        if (!pollTimer) {
+          pollTimer = setInterval(checkEvents, PENDING_EVENT_POLL_MS);
+          checkEvents();
+        }
+      } else {
+        stopPolling();
+      }
+    },
+    (message) => emitToRenderer('watch:error', watchId, message)
+  );
+
+  return watchId;
+}
@@ -0,0 +1,63 @@
+const BINARY_SUFFIXES: Record<string, number> = {
+  Ki: 1024,
+  Mi: 1024 ** 2,
+  Gi: 1024 ** 3,
+  Ti: 1024 ** 4,
+  Pi: 1024 ** 5,
+  Ei: 1024 ** 6,
+};
+
+const DECIMAL_SUFFIXES: Record<string, number> = {
+  n: 1e-9,
+  u: 1e-6,
+  m: 1e-3,
+  '': 1,
+  k: 1e3,
+  K: 1e3,
+  M: 1e6,
+  G: 1e9,
+  T: 1e12,
+  P: 1e15,
+  E: 1e18,
+};
+
+/**
+ * Parse a Kubernetes resource quantity ("250m", "1.5", "128Mi", "2G")
+ * into its base unit. Returns null when the value isn't a quantity.
+ */
+export function parseQuantity(value: string | number | undefined | null): number | null {
+  if (typeof value === 'number') {
+    return value;
+  }
+  if (!value) {
+    return null;
+  }
+
+  const match = /^([+-]?\d+(?:\.\d+)?)(?:e([+-]?\d+))?([a-zA-Z]*)$/.exec(value.trim());
+  if (!match) {
+    return null;
+  }
+
+  const [, numberPart, exponent, suffix] = match;
+  let result = parseFloat(numberPart) * (exponent ? 10 ** parseInt(exponent, 10) : 1);
+
+  if (Object.prototype.hasOwnProperty.call(BINARY_SUFFIXES, suffix)) {
+    result *= BINARY_SUFFIXES[suffix];
+  } else if (Object.prototype.hasOwnProperty.call(DECIMAL_SUFFIXES, suffix)) {
+    result *= DECIMAL_SUFFIXES[suffix];
+  } else {
+    return null;
+  }
+
+  return result;
+}
+
+export function parseCpuMillicores(value: string | undefined | null): number | null {
+  const cores = parseQuantity(value);
+  return cores === null ? null : Math.round(cores * 1000);
+}
+
+export function parseMemoryBytes(value: string | undefined | null): number | null {
+  const bytes = parseQuantity(value);
+  return bytes === null ? null : Math.round(bytes);
+}
@@ -0,0 +1,31 @@
+import { stubSpawnKubectl } from './testSupport';
+import assert from 'node:assert/strict';
+import { after, describe, it } from 'node:test';
+import { tapRendererEvents } from './events';
+import { RawStreamManager } from './rawApi';
+
+describe('raw streams', () => {
+  const spawned = stubSpawnKubectl();
+  const events: { channel: string; payload: any }[] = [];
+  const untap = tapRendererEvents((channel, [payload]) => events.push({ channel, payload }));
+  after(untap);
+
+  it('keeps multi-byte characters split across chunks whole', async () => {
+    const id = new RawStreamManager().startRawStream({ context: 'dev', path: '/api/v1/namespaces/x/pods/y/log' });
+    const { child } = spawned[spawned.length - 1];
+    const text = Buffer.from('grüße 日本\n', 'utf8');
+    // Cut inside "ü" and inside "日"
+    child.stdout.write(text.subarray(0, 3));
+    child.stdout.write(text.subarray(3, 11));
+    child.stdout.write(text.subarray(11));
+    child.exit(0);
+    await new Promise((resolve) => child.once('close', resolve));
+
+    const data = events
+      .filter((event) => event.channel === 'raw:data' && event.payload.streamId === id)
+      .map((event) => event.payload.data)
+      .join('');
+    assert.equal(data, 'grüße 日本\n');
+    assert.ok(!data.includes('�'));
+  });
+});
@@ -0,0 +1,194 @@
+import { randomUUID } from 'crypto';
+import type { ChildProcessWithoutNullStreams } from 'child_process';
+import { StringDecoder } from 'string_decoder';
+import type { RawApiResponse, RawStreamDataEvent, RawStreamEndedEvent } from '../common/kubeTypes';
+import { runKubectl, spawnKubectl } from './kube';
+import { emitToRenderer } from './events';
+import { JsonObjectStream } from './jsonStream';
+import { operationRegistry } from './operations';
+
+export interface RawStreamSpec {
+  context: string;
+  path: string;
+  view?: string;
+}
+
+// Larger bodies are cut off; list the resource with paging instead
+const MAX_RAW_BODY_CHARS = 8 * 1024 * 1024;
+// "Error from server (NotFound): ..."; kubectl doesn't print the status code itself
+const SERVER_ERROR_PATTERN = /Error from server \((\w+)\)/;
+// metav1.StatusReason -> HTTP status
+const REASON_STATUS: Record<string, number> = {
+  BadRequest: 400,
+  Unauthorized: 401,
+  Forbidden: 403,
+  NotFound: 404,
+  MethodNotAllowed: 405,
+  NotAcceptable: 406,
+  AlreadyExists: 409,
+  Conflict: 409,
+  Gone: 410,
+  Expired: 410,
+  UnsupportedMediaType: 415,
+  Invalid: 422,
+  TooManyRequests: 429,
+  InternalError: 500,
+  ServiceUnavailable: 503,
+  Timeout: 504,
+};
+
+function validatePath(apiPath: string): void {
+  if (typeof apiPath !== 'string' || !apiPath.startsWith('/')) {
+    throw new Error('API path must start with "/", e.g. /apis/metrics.k8s.io/v1beta1/nodes');
+  }
+  if (/\s/.test(apiPath)) {
+    throw new Error('API path must not contain whitespace; URL-encode it');
+  }
+}
+
+function statusOf(code: number | null, stderr: string): number | null {
+  if (code === 0) {
+    return 200;
+  }
+  const reason = SERVER_ERROR_PATTERN.exec(stderr)?.[1];
+  return reason ? REASON_STATUS[reason] ?? null : null;
+}
+
+function parseJson(body: string): { json: unknown; isJson: boolean } {
+  const trimmed = body.trim();
+  if (!trimmed.startsWith('{') && !trimmed.startsWith('[')) {
+    return { json: null, isJson: false };
+  }
+  try {
+    return { json: JSON.parse(trimmed), isJson: true };
+  } catch {
+    return { json: null, isJson: false };
+  }
+}
+
+/**
+ * `kubectl get --raw PATH`, for API endpoints without a dedicated command
+ * (aggregated APIs, /metrics, /healthz?verbose, /openapi/v2). The body is
+ * returned as text, plus parsed when it is JSON; Prometheus metrics and
+ * health checks are plain text. A failed request is returned rather than
+ * thrown, with the HTTP status inferred from kubectl's error when it names
+ * a reason.
+ */
+export async function getRaw(apiPath: string, context: string, view?: string): Promise<RawApiResponse> {
+  validatePath(apiPath);
+  const result = await runKubectl(context, ['get', '--raw', apiPath], undefined, { view });
+  const ok = result.code === 0;
+  const body = ok ? result.stdout : result.stdout || result.stderr;
+  const truncated = body.length > MAX_RAW_BODY_CHARS;
+  const { json, isJson } = truncated ? { json: null, isJson: false } : parseJson(body);
+
+  return {
+    path: apiPath,
+    ok,
+    status: statusOf(result.code, result.stderr),
+    body: truncated ? body.slice(0, MAX_RAW_BODY_CHARS) : body,
+    truncated,
+    isJson,
+    json,
+    error: ok ? null : result.stderr.trim() || `kubectl exited with code ${result.code}`,
+  };
+}
+
+interface RawStream {
+  process: ChildProcessWithoutNullStreams;
+  // Set once the output turns out to be JSON (e.g. a ?watch=true path)
+  objects: JsonObjectStream | null;
+  sawOutput: boolean;
+  stderr: string;
+  stopped: boolean;
+}
+
+/**
+ * Streams `kubectl get --raw` output as it arrives, for endpoints that
+ * keep the response open: watches (`?watch=true`), log and proxy paths.
+ * JSON output is also split into objects.
+ */
+export class RawStreamManager {
+  private streams: Map<string, RawStream> = new Map();
+
+  startRawStream(spec: RawStreamSpec): string {
+    validatePath(spec.path);
+    const id = randomUUID();
+    const child = spawnKubectl(spec.context, ['get', '--raw', spec.path]);
+    const stream: RawStream = { process: child, objects: null, sawOutput: false, stderr: '', stopped: false };
+    this.streams.set(id, stream);
+    operationRegistry.register(id, 'kubectl', { context: spec.context, view: spec.view }, () =>
+      this.stopRawStream(id)
+    );
+
+    // A multi-byte character can be split across chunks
+    const decoder = new StringDecoder('utf8');
+    const emitData = (data: string) => {
+      if (!data) {
+        return;
+      }
+      if (!stream.sawOutput && data.trim()) {
+        stream.sawOutput = true;
+        const first = data.trimStart()[0];
+        stream.objects = first === '{' || first === '[' ? new JsonObjectStream() : null;
+      }
+      const event: RawStreamDataEvent = {
+        streamId: id,
+        data,
+        objects: stream.objects ? stream.objects.push(data) : null,
+      };
+      emitToRenderer('raw:data', event);
+    };
+
+    child.stdout.on('data', (chunk) => emitData(decoder.write(chunk)));
+
+    child.stderr.on('data', (chunk) => {
+      stream.stderr = (stream.stderr + chunk.toString()).slice(-64 * 1024);
+    });
+
+    child.once('error', (error) => {
+      console.error(`[Raw ${id}] Failed to start kubectl:`, error);
+    });
+
+    child.once('close', (code) => {
+      emitData(decoder.end());
+      this.streams.delete(id);
+      operationRegistry.unregister(id);
+      const event: RawStreamEndedEvent = {
+        streamId: id,
+        code,
+        status: stream.stopped ? null : statusOf(code, stream.stderr),
+        error: stream.stopped || code === 0 ? null : stream.stderr.trim() || `kubectl exited with code ${code}`,
+        stopped: stream.stopped,
+      };
+      emitToRenderer('raw:ended', event);
+    });
+
+    console.log(`[Raw ${id}] Streaming ${spec.path}`);
+    return id;
+  }
+
+  stopRawStream(id: string): boolean {
+    const stream = this.streams.get(id);
+    if (!stream) {
+      return false;
+    }
+
+    stream.stopped = true;
+    try {
+      stream.process.kill();
+    } catch (error) {
+      console.error(`[Raw ${id}] Error killing kubectl:`, error);
+    }
+    return true;
+  }
+
+  stopAllRawStreams(): void {
+    for (const id of Array.from(this.streams.keys())) {
+      this.stopRawStream(id);
+    }
+  }
+}
+
+// Singleton instance
+export const rawStreamManager = new RawStreamManager();
@@ -0,0 +1,246 @@
+import type {
+  EffectivePermissions,
+  NonResourcePermission,
+  ResourcePermission,
+  UserIdentity,
+} from '../common/kubeTypes';
+import { runKubectl, kubectlFailure, getKubeconfigIdentity, KubeError } from './kube';
+import { parseKubectlTable } from './table';
+
+interface ParsedRules {
+  resourceRules: ResourcePermission[];
+  nonResourceRules: NonResourcePermission[];
+  incomplete: boolean;
+}
+
+const PERMISSIONS_TTL_MS = 5 * 60 * 1000;
+
+const permissionsCache: Map<string, EffectivePermissions> = new Map();
+
+function cacheKey(context: string, namespace: string): string {
+  return `${context}\u0000${namespace}`;
+}
+
+function parseList(value: string | undefined): string[] {
+  // Table cells look like "[get list watch]"
+  return (value ?? '')
+    .replace(/^\[|\]$/g, '')
+    .split(/[\s,]+/)
+    .filter(Boolean);
+}
+
+function splitResource(value: string): { resource: string; group: string } {
+  // "deployments.apps" -> deployments in group apps; "*.*" -> everything
+  const dot = value.indexOf('.');
+  if (dot === -1) {
+    return { resource: value, group: '' };
+  }
+  return { resource: value.slice(0, dot), group: value.slice(dot + 1) };
+}
+
+function parseJsonRules(output: string): ParsedRules | null {
+  let parsed: any;
+  try {
+    parsed = JSON.parse(output);
+  } catch {
+    return null;
+  }
+
+  // Either a SelfSubjectRulesReview or just its status
+  const status = parsed?.status ?? parsed;
+  if (!status || (!Array.isArray(status.resourceRules) && !Array.isArray(status.nonResourceRules))) {
+    return null;
+  }
+
+  const resourceRules: ResourcePermission[] = [];
+  for (const rule of status.resourceRules ?? []) {
+    const groups: string[] = rule.apiGroups?.length ? rule.apiGroups : [''];
+    for (const group of groups) {
+      for (const resource of rule.resources ?? []) {
+        resourceRules.push({
+          resource,
+          group,
+          resourceNames: rule.resourceNames ?? [],
+          verbs: rule.verbs ?? [],
+        });
+      }
+    }
+  }
+
+  const nonResourceRules: NonResourcePermission[] = (status.nonResourceRules ?? []).map((rule: any) => ({
+    urls: rule.nonResourceURLs ?? [],
+    verbs: rule.verbs ?? [],
+  }));
+
+  return { resourceRules, nonResourceRules, incomplete: status.incomplete === true };
+}
+
+function parseTableRules(output: string): ParsedRules {
+  // Multi-word headers would split into separate columns; dashes keep the offsets
+  const normalized = output
+    .replace('Non-Resource URLs', 'Non-Resource-URLs')
+    .replace('Resource Names', 'Resource-Names');
+
+  const resourceRules: ResourcePermission[] = [];
+  const nonResourceRules: NonResourcePermission[] = [];
+
+  for (const row of parseKubectlTable(normalized)) {
+    const verbs = parseList(row.Verbs);
+    if (row.Resources) {
+      resourceRules.push({
+        ...splitResource(row.Resources),
+        resourceNames: parseList(row['Resource-Names']),
+        verbs,
+      });
+    } else if (row['Non-Resource-URLs']) {
+      nonResourceRules.push({ urls: parseList(row['Non-Resource-URLs']), verbs });
+    }
+  }
+
+  return { resourceRules, nonResourceRules, incomplete: false };
+}
+
+/**
+ * Merge rules granted by different bindings into one entry per
+ * resource/group/resourceNames with the union of their verbs
+ */
+function mergeRules(rules: ResourcePermission[]): ResourcePermission[] {
+  const merged: Map<string, ResourcePermission> = new Map();
+
+  for (const rule of rules) {
+    const names = [...rule.resourceNames].sort();
+    const key = `${rule.group}/${rule.resource}/${names.join(',')}`;
+    const existing = merged.get(key);
+
+    if (existing) {
+      existing.verbs = Array.from(new Set([...existing.verbs, ...rule.verbs]));
+    } else {
+      merged.set(key, { ...rule, resourceNames: names, verbs: Array.from(new Set(rule.verbs)) });
+    }
+  }
+
+  return Array.from(merged.values()).sort(
+    (a, b) => a.group.localeCompare(b.group) || a.resource.localeCompare(b.resource)
+  );
+}
+
+function forbidden(): KubeError {
+  return new KubeError(
+    'Forbidden',
+    'This cluster does not allow reviewing your own permissions (SelfSubjectRulesReview)'
+  );
+}
+
+/**
+ * What the current user may do in a namespace, from `kubectl auth can-i --list`
+ */
+export async function whoCanI(namespace: string, context: string, refresh = false): Promise<EffectivePermissions> {
+  const key = cacheKey(context, namespace);
+  const cached = permissionsCache.get(key);
+  if (!refresh && cached && Date.now() - cached.fetchedAt < PERMISSIONS_TTL_MS) {
+    return cached;
+  }
+
+  let parsed: ParsedRules | null = null;
+  const jsonResult = await runKubectl(context, ['auth', 'can-i', '--list', '-o', 'json'], namespace);
+
+  if (jsonResult.code === 0) {
+    parsed = parseJsonRules(jsonResult.stdout);
+  } else {
+    const failure = kubectlFailure(jsonResult, 'Failed to list permissions');
+    if (failure.code === 'Forbidden') {
+      throw forbidden();
+    }
+    if (failure.code === 'Unauthorized' || failure.code === 'Unreachable') {
+      throw failure;
+    }
+  }
+
+  // Older kubectl has no -o json for can-i, so fall back to the table
+  if (!parsed) {
+    const tableResult = await runKubectl(context, ['auth', 'can-i', '--list'], namespace);
+    if (tableResult.code !== 0) {
+      const failure = kubectlFailure(tableResult, 'Failed to list permissions');
+      throw failure.code === 'Forbidden' ? forbidden() : failure;
+    }
+    parsed = parseTableRules(tableResult.stdout);
+  }
+
+  const permissions: EffectivePermissions = {
+    context,
+    namespace,
+    resourceRules: mergeRules(parsed.resourceRules),
+    nonResourceRules: parsed.nonResourceRules,
+    incomplete: parsed.incomplete,
+    fetchedAt: Date.now(),
+  };
+
+  permissionsCache.set(key, permissions);
+  return permissions;
+}
+
+// kubectl before 1.26 has no `auth whoami`; clusters before 1.27 (1.26 with
+// the feature gate) don't serve SelfSubjectReview
+const NO_WHOAMI_PATTERN = /unknown command "whoami"|could not find the requested resource|doesn't have a resource type/i;
+
+/**
+ * Who the server says the current user is, from `kubectl auth whoami`
+ * (a SelfSubjectReview), for "logged in as" and for reading RBAC results.
+ * Where that API is missing or not allowed, falls back to what the
+ * kubeconfig says, marked unverified.
+ */
+export async function whoAmI(context: string): Promise<UserIdentity> {
+  const result = await runKubectl(context, ['auth', 'whoami', '-o', 'json']);
+  if (result.code === 0) {
+    const userInfo = JSON.parse(result.stdout || '{}')?.status?.userInfo ?? {};
+    const { kubeconfigUser } = await getKubeconfigIdentity(context).catch(() => ({ kubeconfigUser: null }));
+    return {
+      context,
+      username: userInfo.username ?? null,
+      uid: userInfo.uid ?? null,
+      groups: Array.isArray(userInfo.groups) ? userInfo.groups : [],
+      extra: userInfo.extra ?? {},
+      kubeconfigUser,
+      verified: true,
+      note: null,
+    };
+  }
+
+  const failure = kubectlFailure(result, 'Failed to read user identity');
+  let note: string;
+  if (failure.code === 'Forbidden') {
+    note = 'This cluster does not allow reviewing your own identity (SelfSubjectReview)';
+  } else if (NO_WHOAMI_PATTERN.test(failure.message)) {
+    note = 'This cluster or kubectl does not support `kubectl auth whoami`';
+  } else {
+    throw failure;
+  }
+
+  const identity = await getKubeconfigIdentity(context);
+  return {
+    context,
+    username: identity.username,
+    uid: null,
+    groups: identity.groups,
+    extra: {},
+    kubeconfigUser: identity.kubeconfigUser,
+    verified: false,
+    note: `${note}; showing the identity from the kubeconfig`,
+  };
+}
+
+/**
+ * Drop cached permissions, e.g. after switching context or kubeconfig
+ */
+export function clearPermissionsCache(context?: string): void {
+  if (!context) {
+    permissionsCache.clear();
+    return;
+  }
+
+  for (const key of Array.from(permissionsCache.keys())) {
+    if (key.startsWith(`${context}\u0000`)) {
+      permissionsCache.delete(key);
+    }
+  }
+}
@@ -0,0 +1,61 @@
+import { stubSpawnKubectl, waitForSpawn } from './testSupport';
+import assert from 'node:assert/strict';
+import { afterEach, describe, it, mock } from 'node:test';
+import { getAppConfig } from './appConfig';
+import { HEALTHY_RUN_MS, Reconnector } from './reconnect';
+import { watchManager } from './watch';
+
+const policy = { maxAttempts: 3, baseDelayMs: 1, maxDelayMs: 1, jitter: false };
+
+describe('Reconnector', () => {
+  afterEach(() => mock.timers.reset());
+
+  it('backs off further with each drop and gives up at the limit', () => {
+    const reconnector = new Reconnector(policy);
+    for (let attempt = 1; attempt <= 3; attempt++) {
+      reconnector.connected();
+      assert.notEqual(reconnector.schedule(() => undefined), null);
+      assert.equal(reconnector.attempts, attempt);
+    }
+    assert.equal(reconnector.schedule(() => undefined), null);
+    reconnector.cancel();
+  });
+
+  it('starts over after a run that stayed up for the healthy interval', () => {
+    mock.timers.enable({ apis: ['Date', 'setTimeout'] });
+    const reconnector = new Reconnector(policy);
+    reconnector.connected();
+    reconnector.schedule(() => undefined);
+    reconnector.connected();
+    reconnector.schedule(() => undefined);
+    assert.equal(reconnector.attempts, 2);
+
+    reconnector.connected();
+    mock.timers.tick(HEALTHY_RUN_MS);
+    reconnector.schedule(() => undefined);
+    assert.equal(reconnector.attempts, 1);
+    reconnector.cancel();
+  });
+});
+
+describe('watch reconnects', () => {
+  it('resets the backoff on the first event after reopening', async () => {
+    getAppConfig().reconnect = { ...policy };
+    const spawned = stubSpawnKubectl();
+    const id = watchManager.startWatch({ context: 'dev', kind: 'pods' }, () => undefined);
+
+    for (let drop = 1; drop <= 2; drop++) {
+      await waitForSpawn(spawned, drop);
+      spawned[drop - 1].child.exit(1);
+    }
+    await waitForSpawn(spawned, 3);
+    const { child } = spawned[2];
+    const reconnector = (watchManager as any).watches.get(id).reconnector;
+    assert.equal(reconnector.attempts, 2);
+
+    child.stdout.write(JSON.stringify({ type: 'ADDED', object: { metadata: { name: 'web-0' } } }));
+    await new Promise((resolve) => setImmediate(resolve));
+    assert.equal(reconnector.attempts, 0);
+    watchManager.stopWatch(id);
+  });
+});
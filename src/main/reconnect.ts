@@ -0,0 +1,79 @@
+import type { RetryPolicy } from '../common/kubeTypes';
+import { getAppConfig } from './appConfig';
+
+// A process that stays up this long was healthy, even if it printed nothing
+export const HEALTHY_RUN_MS = 30 * 1000;
+
+/**
+ * Exponential backoff delay for the given 1-based attempt
+ */
+export function backoffDelay(policy: RetryPolicy, attempt: number): number {
+  const delay = Math.min(policy.baseDelayMs * 2 ** (attempt - 1), policy.maxDelayMs);
+  // Full jitter: anywhere between half and the whole backoff
+  return policy.jitter ? delay / 2 + Math.random() * (delay / 2) : delay;
+}
+
+/**
+ * Backoff state for one long-running kubectl process (watch, log follow,
+ * port-forward) that should transparently reopen after a transient drop.
+ * Owners call `connected` when the process starts, `schedule` when it dies
+ * unexpectedly and `reset` once the reopened process has proven healthy
+ * (a run of HEALTHY_RUN_MS counts too, for quiet streams).
+ */
+export class Reconnector {
+  private attempt = 0;
+  private timer: NodeJS.Timeout | null = null;
+  private connectedAt: number | null = null;
+
+  constructor(readonly policy: RetryPolicy = getAppConfig().reconnect) {}
+
+  /** Reconnect attempts since the last healthy run */
+  get attempts(): number {
+    return this.attempt;
+  }
+
+  /** Note that the process just started */
+  connected(): void {
+    this.connectedAt = Date.now();
+  }
+
+  /**
+   * Run `reopen` after the next backoff delay. Returns the delay, or null
+   * when the attempts are used up and the owner should give up.
+   */
+  schedule(reopen: () => void): number | null {
+    if (this.connectedAt !== null && Date.now() - this.connectedAt >= HEALTHY_RUN_MS) {
+      this.attempt = 0;
+    }
+    this.connectedAt = null;
+    if (this.attempt >= this.policy.maxAttempts) {
+      return null;
+    }
+
+    this.attempt += 1;
+    const delay = Math.round(backoffDelay(this.policy, this.attempt));
+    this.cancel();
+    this.timer = setTimeout(() => {
+      this.timer = null;
+      reopen();
+    }, delay);
+    return delay;
+  }
+
+  /**
+   * Mark the stream healthy again. Returns how many attempts the
+   * reconnect took (0 when none was in progress).
+   */
+  reset(): number {
+    const attempts = this.attempt;
+    this.attempt = 0;
+    return attempts;
+  }
+
+  cancel(): void {
+    if (this.timer) {
+      clearTimeout(this.timer);
+      this.timer = null;
+    }
+  }
+}
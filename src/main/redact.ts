@@ -0,0 +1,113 @@
+import os from 'os';
+import path from 'path';
+
+export const REDACTED = '[REDACTED]';
+
+// Environment variables whose values are safe to share as-is
+const SAFE_ENV_KEYS = new Set(['KUBECONFIG', 'KUBECTL_NAMESPACE', 'TERM', 'LANG', 'LC_ALL', 'SHELL']);
+
+const SECRET_PATTERNS: Array<[RegExp, string]> = [
+  // key: value / key=value pairs with secret-looking keys
+  [
+    /(\b(?:token|password|passwd|secret|api[-_]?key|client-key-data|access[-_]?key)\b["']?\s*[:=]\s*)(["']?)[^\s"',]+\2/gi,
+    `$1$2${REDACTED}$2`,
+  ],
+  // HTTP authorization headers
+  [/\b(Bearer|Basic)\s+[A-Za-z0-9._~+/=-]{8,}/g, `$1 ${REDACTED}`],
+  // JSON web tokens (service account tokens, OIDC id tokens)
+  [/\beyJ[A-Za-z0-9_-]{8,}\.[A-Za-z0-9_-]{8,}\.[A-Za-z0-9_-]*/g, REDACTED],
+  // AWS access key ids
+  [/\bAKIA[0-9A-Z]{16}\b/g, REDACTED],
+];
+
+/**
+ * Mask secret-looking substrings (tokens, passwords, keys) in free text
+ */
+export function redactSecrets(text: string): string {
+  return SECRET_PATTERNS.reduce((result, [pattern, replacement]) => result.replace(pattern, replacement), text);
+}
+
+/**
+ * Replace environment values with a placeholder, keeping only known-safe ones
+ */
+export function redactEnv(env: Record<string, string>): Record<string, string> {
+  const result: Record<string, string> = {};
+  for (const [key, value] of Object.entries(env)) {
+    result[key] = SAFE_ENV_KEYS.has(key) ? value : REDACTED;
+  }
+  return result;
+}
+
+/**
+ * Mask a path's home directory (which names the user) and anything
+ * secret-looking in it
+ */
+export function redactPath(filePath: string): string {
+  const home = os.homedir();
+  const relative = path.relative(home, filePath);
+  const inHome = home !== '' && !relative.startsWith('..') && !path.isAbsolute(relative);
+  return redactSecrets(inHome ? path.join('~', relative) : filePath);
+}
+
+// Kubeconfig fields holding credentials; `*File` fields are paths and kept
+const SECRET_FIELD_PATTERN = /token|password|passwd|secret|key-data|api[-_]?key|credential/i;
+// Flags whose value is a credential: `--token X` or `--token=X`
+const SECRET_FLAG_PATTERN = /^--?[\w-]*(?:token|password|secret|api[-_]?key)[\w-]*$/i;
+
+/**
+ * Mask command-line arguments that are credentials: the value of a
+ * secret-looking flag (`--token X`, `--password=X`) and secret-looking text
+ */
+export function redactArgs(args: any[]): any[] {
+  return args.map((arg, index) => {
+    if (typeof arg !== 'string') {
+      return arg;
+    }
+    const previous = args[index - 1];
+    if (typeof previous === 'string' && SECRET_FLAG_PATTERN.test(previous)) {
+      return REDACTED;
+    }
+    const separator = arg.indexOf('=');
+    if (separator > 0 && SECRET_FLAG_PATTERN.test(arg.slice(0, separator))) {
+      return `${arg.slice(0, separator + 1)}${REDACTED}`;
+    }
+    return redactSecrets(arg);
+  });
+}
+
+function redactValue(value: any, key: string | null): any {
+  if (Array.isArray(value)) {
+    return key === 'args' ? redactArgs(value) : value.map((item) => redactValue(item, null));
+  }
+  if (value && typeof value === 'object') {
+    const result: Record<string, any> = {};
+    for (const [childKey, childValue] of Object.entries(value)) {
+      result[childKey] = redactValue(childValue, childKey);
+    }
+    return result;
+  }
+  if (typeof value === 'string') {
+    if (key && SECRET_FIELD_PATTERN.test(key) && !/file$/i.test(key)) {
+      return REDACTED;
+    }
+    return redactSecrets(value);
+  }
+  return value;
+}
+
+/**
+ * Copy of a parsed kubeconfig with credentials masked: tokens, passwords,
+ * client keys, auth-provider secrets, exec plugin env values and
+ * secret-looking exec arguments. Certificates and CA data are public and kept.
+ */
+export function redactKubeconfig(config: any): any {
+  const redacted = redactValue(config, null);
+  for (const entry of Array.isArray(redacted?.users) ? redacted.users : []) {
+    // Exec plugin env entries are { name, value }; any value may be a credential
+    const env = entry?.user?.exec?.env;
+    if (Array.isArray(env)) {
+      entry.user.exec.env = env.map((variable: any) => ({ ...variable, value: REDACTED }));
+    }
+  }
+  return redacted;
+}
@@ -0,0 +1,99 @@
+import './testSupport';
+import assert from 'node:assert/strict';
+import { afterEach, beforeEach, describe, it } from 'node:test';
+import { Reconnector } from './reconnect';
+import { ResilientExecManager } from './resilientExec';
+import { terminalManager } from './terminal';
+
+function runningPod(name: string, overrides: { uid?: string; restarts?: number; phase?: string } = {}) {
+  return {
+    metadata: { name, uid: overrides.uid ?? `${name}-uid`, creationTimestamp: '2026-01-01T00:00:00Z' },
+    status: {
+      phase: overrides.phase ?? 'Running',
+      conditions: [{ type: 'Ready', status: 'True' }],
+      containerStatuses: [{ name: 'app', restartCount: overrides.restarts ?? 0, state: { running: {} } }],
+    },
+  };
+}
+
+describe('resilient exec exits', () => {
+  let manager: ResilientExecManager;
+  let session: any;
+  let abandoned: number[];
+
+  beforeEach(() => {
+    manager = new ResilientExecManager();
+    abandoned = [];
+    (terminalManager as any).writeNotice = () => undefined;
+    (terminalManager as any).abandonRespawn = (_id: string, exitCode: number) => abandoned.push(exitCode);
+    session = {
+      id: 't1',
+      options: { context: 'dev', namespace: 'shop', podSelector: 'app=web' },
+      pod: 'web-0',
+      podUid: null,
+      podRestarts: 0,
+      pods: new Map([['web-0', runningPod('web-0')]]),
+      watchId: null,
+      reconnector: new Reconnector({ maxAttempts: 3, baseDelayMs: 60 * 1000, maxDelayMs: 60 * 1000, jitter: false }),
+      connectedAt: Date.now(),
+      waiting: false,
+      respawning: false,
+      pendingExit: null,
+      stopped: false,
+    };
+    (manager as any).markConnected(session, 'web-0');
+  });
+
+  afterEach(() => {
+    (manager as any).stop(session);
+  });
+
+  const exit = (code: number): boolean => (manager as any).handleExit(session, code);
+  const podEvent = (type: string, object: any) => (manager as any).handlePodEvent(session, { type, object });
+
+  it('ends the terminal when the user leaves the shell', () => {
+    assert.equal(exit(0), false);
+    assert.equal(session.reconnector.attempts, 0);
+  });
+
+  it('does not reconnect a failed exec while its pod keeps running', () => {
+    assert.equal(exit(1), true);
+    assert.ok(session.pendingExit);
+    podEvent('MODIFIED', runningPod('web-0'));
+    assert.equal(session.reconnector.attempts, 0);
+    assert.deepEqual(abandoned, []);
+  });
+
+  it('reconnects when the watch already shows the pod deleted', () => {
+    podEvent('DELETED', runningPod('web-0'));
+    assert.equal(exit(137), true);
+    assert.equal(session.reconnector.attempts, 1);
+    assert.equal(session.pendingExit, null);
+  });
+
+  it('reconnects when the watch catches up with a container restart', () => {
+    assert.equal(exit(137), true);
+    podEvent('MODIFIED', runningPod('web-0', { restarts: 1 }));
+    assert.equal(session.reconnector.attempts, 1);
+    assert.equal(session.pendingExit, null);
+  });
+
+  it('treats a pod recreated under the same name as ended', () => {
+    assert.equal(exit(1), true);
+    podEvent('MODIFIED', runningPod('web-0', { uid: 'recreated' }));
+    assert.equal(session.reconnector.attempts, 1);
+  });
+
+  it('reconnects once the pod stops running', () => {
+    assert.equal(exit(1), true);
+    podEvent('MODIFIED', runningPod('web-0', { phase: 'Failed' }));
+    assert.equal(session.reconnector.attempts, 1);
+  });
+
+  it('ends the terminal when the reconnect attempts are used up', () => {
+    session.reconnector = new Reconnector({ maxAttempts: 0, baseDelayMs: 1, maxDelayMs: 1, jitter: false });
+    assert.equal(exit(1), true);
+    podEvent('DELETED', runningPod('web-0'));
+    assert.deepEqual(abandoned, [1]);
+  });
+});
@@ -0,0 +1,322 @@
+import type {
+  ResilientExecOptions,
+  ResilientExecState,
+  ResilientExecStatusEvent,
+  TerminalReconnectedEvent,
+} from '../common/terminalTypes';
+import { getCloudAuthEnv } from './cloudAuth';
+import { emitToRenderer } from './events';
+import {
+  buildKubectlArgs,
+  getKubectlBinary,
+  getKubectlEnv,
+  getKubectlJson,
+  resolveKubeconfigPath,
+} from './kube';
+import { Reconnector } from './reconnect';
+import { terminalManager } from './terminal';
+import { watchManager, type WatchEvent } from './watch';
+
+// Prefer bash, fall back to sh (alpine, distro images without bash)
+const CONTAINER_SHELL_SCRIPT = 'if command -v bash >/dev/null 2>&1; then exec bash; else exec sh; fi';
+// A session that lasted this long was healthy; the next drop starts the backoff over
+const HEALTHY_SESSION_MS = 10 * 1000;
+// How long a failed exec waits for the pod watch to show why before ending the terminal
+const EXIT_CONFIRM_MS = 5 * 1000;
+
+interface ResilientSession {
+  id: string;
+  options: ResilientExecOptions;
+  pod: string;
+  /** uid and container restarts of the pod when the exec started, to tell a restart */
+  podUid: string | null;
+  podRestarts: number;
+  /** Matching pods as last seen by the watch */
+  pods: Map<string, any>;
+  watchId: string | null;
+  reconnector: Reconnector;
+  connectedAt: number;
+  /** No usable pod; the next pod event retries */
+  waiting: boolean;
+  /** A respawn is in flight */
+  respawning: boolean;
+  /** The exec failed while its pod still looked fine; waiting for the watch to say otherwise */
+  pendingExit: { exitCode: number; timer: NodeJS.Timeout } | null;
+  stopped: boolean;
+}
+
+function isUsable(pod: any, container?: string): boolean {
+  if (pod?.status?.phase !== 'Running' || pod?.metadata?.deletionTimestamp) {
+    return false;
+  }
+  if (container) {
+    const status = (pod.status.containerStatuses ?? []).find((entry: any) => entry?.name === container);
+    return Boolean(status?.state?.running);
+  }
+  const conditions: any[] = pod.status.conditions ?? [];
+  return conditions.some((condition) => condition?.type === 'Ready' && condition?.status === 'True');
+}
+
+function containerRestarts(pod: any, container?: string): number {
+  return (pod?.status?.containerStatuses ?? [])
+    .filter((status: any) => !container || status?.name === container)
+    .reduce((total: number, status: any) => total + (Number(status?.restartCount) || 0), 0);
+}
+
+// Newest first, so a rolling update moves the shell onto the new version
+function pickPod(pods: Iterable<any>, container?: string): string | null {
+  const usable = Array.from(pods)
+    .filter((pod) => isUsable(pod, container))
+    .sort(
+      (a, b) =>
+        (Date.parse(b.metadata?.creationTimestamp ?? '') || 0) - (Date.parse(a.metadata?.creationTimestamp ?? '') || 0)
+    );
+  return usable[0]?.metadata?.name ?? null;
+}
+
+export class ResilientExecManager {
+  private sessions: Map<string, ResilientSession> = new Map();
+
+  /**
+   * Open a terminal running `kubectl exec` in a pod matching the selector
+   * that follows the workload: when the exec ends because its pod went
+   * away or restarted, the terminal's id and scrollback are kept and a new
+   * exec starts in another matching pod, announced by
+   * `terminal:reconnected`. While no matching pod is running, the terminal
+   * waits (reported through `terminal:reconnect-status`) and input is
+   * refused. An exec that ends while the pod watch shows its pod still
+   * running (leaving the shell, `exit 1`, a failed command) ends the
+   * terminal as usual; repeated quick failures give up after the reconnect
+   * policy's attempts.
+   */
+  async createResilientExec(id: string, options: ResilientExecOptions): Promise<{ id: string; pod: string }> {
+    const list = await getKubectlJson(options.context, ['get', 'pods', '-l', options.podSelector], options.namespace);
+    const items: any[] = list?.items ?? [];
+    const pod = pickPod(items, options.container);
+    if (!pod) {
+      throw new Error(`No running pod matches ${options.podSelector} in ${options.namespace}`);
+    }
+
+    const session: ResilientSession = {
+      id,
+      options,
+      pod,
+      podUid: null,
+      podRestarts: 0,
+      pods: new Map(items.map((item) => [item.metadata?.name, item])),
+      watchId: null,
+      reconnector: new Reconnector(),
+      connectedAt: Date.now(),
+      waiting: false,
+      respawning: false,
+      pendingExit: null,
+      stopped: false,
+    };
+    this.markConnected(session, pod);
+
+    await terminalManager.createProcessTerminal(id, getKubectlBinary(options.context), this.execArgs(options, pod), {
+      env: { ...getKubectlEnv(), ...getCloudAuthEnv(options.context, resolveKubeconfigPath()) },
+      lifecycle: {
+        onExit: (exitCode) => this.handleExit(session, exitCode),
+        onRelease: () => this.stop(session),
+      },
+    });
+    if (session.stopped) {
+      // The exec failed straight away and the terminal has already ended
+      return { id, pod };
+    }
+
+    this.sessions.set(id, session);
+    session.watchId = watchManager.startWatch(
+      { context: options.context, kind: 'pods', namespace: options.namespace, labelSelector: options.podSelector },
+      (event) => this.handlePodEvent(session, event),
+      (message) => console.warn(`[ResilientExec ${id}] Pod watch error: ${message}`)
+    );
+
+    terminalManager.writeNotice(
+      id,
+      `Connected to pod ${pod}. If it goes away, the shell moves to another pod matching ${options.podSelector}.`
+    );
+    this.emitStatus(session, 'connected', pod, `Connected to ${pod}`);
+    console.log(`[ResilientExec ${id}] Following ${options.namespace}/${options.podSelector}, starting on ${pod}`);
+    return { id, pod };
+  }
+
+  stopAllResilientExecs(): void {
+    for (const session of Array.from(this.sessions.values())) {
+      this.stop(session);
+    }
+  }
+
+  private execArgs(options: ResilientExecOptions, pod: string): string[] {
+    const args = ['exec', '-it', pod];
+    if (options.container) {
+      args.push('-c', options.container);
+    }
+    const command =
+      options.command && options.command.length > 0 ? options.command : ['sh', '-c', CONTAINER_SHELL_SCRIPT];
+    return buildKubectlArgs(options.context, [...args, '--', ...command], options.namespace);
+  }
+
+  private handlePodEvent(session: ResilientSession, event: WatchEvent): void {
+    const name: string | undefined = event.object?.metadata?.name;
+    if (session.stopped || !name) {
+      return;
+    }
+
+    if (event.type === 'DELETED') {
+      session.pods.delete(name);
+    } else if (event.type === 'ADDED' || event.type === 'MODIFIED') {
+      session.pods.set(name, event.object);
+    } else {
+      return;
+    }
+    if (session.pendingExit && this.podEnded(session)) {
+      const { exitCode, timer } = session.pendingExit;
+      clearTimeout(timer);
+      session.pendingExit = null;
+      if (!this.scheduleReconnect(session, exitCode)) {
+        terminalManager.abandonRespawn(session.id, exitCode);
+      }
+    } else if (session.waiting) {
+      void this.reconnect(session);
+    }
+  }
+
+  /** Whether the watch shows the exec's pod deleted, replaced, restarted or no longer running */
+  private podEnded(session: ResilientSession): boolean {
+    const pod = session.pods.get(session.pod);
+    if (!pod || pod.status?.phase !== 'Running' || pod.metadata?.deletionTimestamp) {
+      return true;
+    }
+    const uid: string | undefined = pod.metadata?.uid;
+    if (session.podUid && uid && uid !== session.podUid) {
+      return true;
+    }
+    return containerRestarts(pod, session.options.container) > session.podRestarts;
+  }
+
+  private markConnected(session: ResilientSession, pod: string): void {
+    const current = session.pods.get(pod);
+    session.pod = pod;
+    session.podUid = current?.metadata?.uid ?? null;
+    session.podRestarts = containerRestarts(current, session.options.container);
+    session.connectedAt = Date.now();
+  }
+
+  /**
+   * Decides, as the exec exits, whether the terminal stays open for a
+   * reconnect. Runs synchronously inside the PTY's exit handler, so when
+   * the watch hasn't caught up with the pod yet the terminal is held open
+   * for EXIT_CONFIRM_MS and a pod event decides.
+   */
+  private handleExit(session: ResilientSession, exitCode: number): boolean {
+    // Status 0 is the user leaving the shell
+    if (session.stopped || exitCode === 0) {
+      return false;
+    }
+    if (this.podEnded(session)) {
+      return this.scheduleReconnect(session, exitCode);
+    }
+
+    const timer = setTimeout(() => {
+      session.pendingExit = null;
+      terminalManager.abandonRespawn(session.id, exitCode);
+    }, EXIT_CONFIRM_MS);
+    session.pendingExit = { exitCode, timer };
+    return true;
+  }
+
+  private scheduleReconnect(session: ResilientSession, exitCode: number): boolean {
+    if (Date.now() - session.connectedAt >= HEALTHY_SESSION_MS) {
+      session.reconnector.reset();
+    }
+    const delay = session.reconnector.schedule(() => void this.reconnect(session));
+    if (delay === null) {
+      console.warn(`[ResilientExec ${session.id}] Giving up after ${session.reconnector.attempts} attempts`);
+      return false;
+    }
+
+    const message = `Exec into ${session.pod} ended with exit code ${exitCode}; reconnecting`;
+    console.log(`[ResilientExec ${session.id}] ${message} in ${delay}ms`);
+    terminalManager.writeNotice(session.id, message);
+    this.emitStatus(session, 'reconnecting', session.pod, message);
+    return true;
+  }
+
+  private async reconnect(session: ResilientSession): Promise<void> {
+    if (session.stopped || session.respawning) {
+      return;
+    }
+
+    const { options } = session;
+    const pod = pickPod(session.pods.values(), options.container);
+    if (!pod) {
+      if (!session.waiting) {
+        session.waiting = true;
+        const message = `No pod matching ${options.podSelector} is running; waiting for one`;
+        terminalManager.writeNotice(session.id, message);
+        this.emitStatus(session, 'waiting', session.pod, message);
+      }
+      return;
+    }
+
+    session.waiting = false;
+    session.respawning = true;
+    try {
+      await terminalManager.respawnProcess(session.id, getKubectlBinary(options.context), this.execArgs(options, pod));
+    } catch (error) {
+      session.respawning = false;
+      if (session.stopped) {
+        return;
+      }
+      console.warn(`[ResilientExec ${session.id}] Respawn failed:`, error instanceof Error ? error.message : error);
+      if (session.reconnector.schedule(() => void this.reconnect(session)) === null) {
+        terminalManager.abandonRespawn(session.id, -1);
+      }
+      return;
+    }
+    session.respawning = false;
+
+    const previousPod = session.pod;
+    this.markConnected(session, pod);
+    terminalManager.writeNotice(
+      session.id,
+      pod === previousPod ? `Reconnected to pod ${pod}.` : `Reconnected to pod ${pod} (was ${previousPod}).`
+    );
+    const payload: TerminalReconnectedEvent = {
+      id: session.id,
+      pod,
+      previousPod,
+      attempts: session.reconnector.attempts,
+    };
+    emitToRenderer('terminal:reconnected', payload);
+    this.emitStatus(session, 'connected', pod, `Connected to ${pod}`);
+  }
+
+  private emitStatus(session: ResilientSession, state: ResilientExecState, pod: string | null, message: string): void {
+    const payload: ResilientExecStatusEvent = { id: session.id, state, pod, message };
+    emitToRenderer('terminal:reconnect-status', payload);
+  }
+
+  private stop(session: ResilientSession): void {
+    if (session.stopped) {
+      return;
+    }
+    session.stopped = true;
+    session.reconnector.cancel();
+    if (session.pendingExit) {
+      clearTimeout(session.pendingExit.timer);
+      session.pendingExit = null;
+    }
+    if (session.watchId) {
+      watchManager.stopWatch(session.watchId);
+    }
+    if (this.sessions.get(session.id) === session) {
+      this.sessions.delete(session.id);
+    }
+  }
+}
+
+// Singleton instance
+export const resilientExecManager = new ResilientExecManager();
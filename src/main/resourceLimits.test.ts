@@ -0,0 +1,58 @@
+import './testSupport';
+import assert from 'node:assert/strict';
+import { describe, it } from 'node:test';
+import { spawnSync } from 'child_process';
+import os from 'os';
+import { validateResourceLimits, withResourceLimits } from './resourceLimits';
+
+const run = (file: string, args: string[]) => spawnSync(file, args, { encoding: 'utf8', timeout: 10 * 1000 });
+
+describe('terminal resource limits', { skip: os.platform() === 'win32' }, () => {
+  it('starts the command under the limits', { skip: os.platform() !== 'linux' }, () => {
+    const limited = withResourceLimits('/bin/sh', ['-c', 'echo "$(ulimit -t) $(ulimit -v)"'], {
+      cpuSeconds: 30,
+      memoryMb: 512,
+    });
+    assert.equal(limited.file, '/bin/sh');
+    assert.equal(run(limited.file, limited.args).stdout, `30 ${512 * 1024}\n`);
+  });
+
+  it('stops a process that uses up its CPU time', () => {
+    const limited = withResourceLimits('/bin/sh', ['-c', 'while :; do :; done'], { cpuSeconds: 1 });
+    const result = run(limited.file, limited.args);
+    assert.ok(result.signal === 'SIGXCPU' || result.signal === 'SIGKILL', `ended with ${result.signal}`);
+  });
+
+  it('passes the command and its arguments through untouched', () => {
+    const limited = withResourceLimits('printf', ['%s|', '$HOME', 'a b', '"; id'], { cpuSeconds: 5 });
+    assert.equal(run(limited.file, limited.args).stdout, '$HOME|a b|"; id|');
+  });
+
+  it('leaves the command alone without limits', () => {
+    assert.deepEqual(withResourceLimits('/bin/bash', ['-l'], undefined), {
+      file: '/bin/bash',
+      args: ['-l'],
+      unsupported: [],
+    });
+  });
+
+  it('reports limits the platform cannot apply', () => {
+    const { platform } = os;
+    (os as any).platform = () => 'darwin';
+    try {
+      const limited = withResourceLimits('/bin/zsh', [], { cpuSeconds: 60, memoryMb: 256 });
+      assert.deepEqual(limited.unsupported, ['memory']);
+      assert.match(limited.args[1], /ulimit -t 60/);
+      assert.doesNotMatch(limited.args[1], /ulimit -v/);
+    } finally {
+      (os as any).platform = platform;
+    }
+  });
+
+  it('refuses limits that are not positive whole numbers', () => {
+    for (const limits of [{ cpuSeconds: 0 }, { cpuSeconds: 1.5 }, { memoryMb: -1 }, { memoryMb: 2 ** 22 }]) {
+      assert.throws(() => validateResourceLimits(limits), /Invalid (CPU time|memory) limit/);
+    }
+    validateResourceLimits({ cpuSeconds: 10, memoryMb: 1024 });
+  });
+});
@@ -0,0 +1,75 @@
+import os from 'os';
+import type { TerminalResourceLimits } from '../common/terminalTypes';
+
+// Largest value ulimit takes everywhere without overflowing rlim_t
+const MAX_LIMIT = 2 ** 31 - 1;
+
+interface LimitSpec {
+  key: keyof TerminalResourceLimits;
+  /** ulimit flag and the factor from the option's unit to ulimit's */
+  flag: string;
+  scale: number;
+  platforms: NodeJS.Platform[];
+  label: string;
+}
+
+// RLIMIT_AS can't be set on macOS (setrlimit returns EINVAL), so memory is Linux only
+const LIMIT_SPECS: LimitSpec[] = [
+  { key: 'cpuSeconds', flag: '-t', scale: 1, platforms: ['linux', 'darwin', 'freebsd'], label: 'CPU time' },
+  { key: 'memoryMb', flag: '-v', scale: 1024, platforms: ['linux', 'freebsd'], label: 'memory' },
+];
+
+export interface LimitedCommand {
+  file: string;
+  args: string[];
+  /** Limits that were requested but can't be applied on this platform */
+  unsupported: string[];
+}
+
+export function validateResourceLimits(limits: TerminalResourceLimits): void {
+  for (const spec of LIMIT_SPECS) {
+    const value = limits[spec.key];
+    if (value !== undefined && (!Number.isInteger(value) || value <= 0 || value * spec.scale > MAX_LIMIT)) {
+      throw new Error(`Invalid ${spec.label} limit: ${value}`);
+    }
+  }
+}
+
+/**
+ * Wrap a command so it starts under the given limits. node-pty can't run
+ * code between fork and exec, so `/bin/sh` sets the limits with ulimit and
+ * then execs the command in place: the PID stays the same, and the limits
+ * are inherited by everything started from it. Limits only apply per
+ * process (a shell running many commands isn't capped in total).
+ */
+export function withResourceLimits(
+  file: string,
+  args: string[],
+  limits: TerminalResourceLimits | undefined
+): LimitedCommand {
+  const platform = os.platform();
+  const commands: string[] = [];
+  const unsupported: string[] = [];
+
+  for (const spec of LIMIT_SPECS) {
+    const value = limits?.[spec.key];
+    if (value === undefined) {
+      continue;
+    }
+    if (!spec.platforms.includes(platform)) {
+      unsupported.push(spec.label);
+      continue;
+    }
+    // Sets the hard limit too, so it can't be raised again from inside the terminal. A
+    // refusal (e.g. above an existing hard limit) is reported but doesn't stop the command.
+    commands.push(`ulimit ${spec.flag} ${value * spec.scale} || echo 'Could not apply the ${spec.label} limit' >&2`);
+  }
+
+  if (commands.length === 0) {
+    return { file, args, unsupported };
+  }
+
+  // The command and its arguments are passed as $0 and $@, never parsed by sh
+  const script = `${commands.join('; ')}; exec "$0" "$@"`;
+  return { file: '/bin/sh', args: ['-c', script, file, ...args], unsupported };
+}
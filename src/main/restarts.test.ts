@@ -0,0 +1,68 @@
+import './testSupport';
+import assert from 'node:assert/strict';
+import { beforeEach, describe, it } from 'node:test';
+import { RestartLeaderboardManager } from './restarts';
+import { watchManager } from './watch';
+
+function pod(name: string, uid: string, restarts: number) {
+  return {
+    metadata: { namespace: 'db', name, uid },
+    status: { containerStatuses: [{ name: 'postgres', restartCount: restarts }] },
+  };
+}
+
+describe('restart leaderboard', () => {
+  let manager: RestartLeaderboardManager;
+  let leaderboard: any;
+
+  beforeEach(() => {
+    manager = new RestartLeaderboardManager();
+    leaderboard = { watchId: 'w1', limit: 10, pods: new Map(), changed: false, timer: null };
+  });
+
+  const update = (object: any) => (manager as any).update(leaderboard, object);
+  const remove = (object: any) => (manager as any).remove(leaderboard, object);
+  const snapshot = () => (manager as any).snapshot('r1', leaderboard);
+
+  it('counts restarts since the watch started', () => {
+    update(pod('web', 'a', 4));
+    update(pod('web', 'a', 6));
+    assert.deepEqual(
+      snapshot().entries.map((entry: any) => [entry.pod, entry.restarts, entry.sessionRestarts]),
+      [['web', 6, 2]]
+    );
+  });
+
+  it('carries restarts over to a StatefulSet pod recreated under the same name', () => {
+    update(pod('db-0', 'first', 1));
+    update(pod('db-0', 'first', 3));
+    remove(pod('db-0', 'first', 3));
+    assert.deepEqual(snapshot().entries, []);
+    assert.equal(snapshot().trackedPods, 0);
+
+    update(pod('db-0', 'second', 0));
+    update(pod('db-0', 'second', 1));
+    const [entry] = snapshot().entries;
+    assert.equal(entry.pod, 'db-0');
+    assert.equal(entry.restarts, 1);
+    // Two restarts of the first pod during the session, one of the new pod
+    assert.equal(entry.sessionRestarts, 3);
+  });
+
+  it('ignores a late delete of the pod a name used to belong to', () => {
+    update(pod('db-0', 'second', 2));
+    remove(pod('db-0', 'first', 2));
+    assert.equal(snapshot().trackedPods, 1);
+  });
+
+  it('forgets deleted pods that do not come back', () => {
+    update(pod('web-abc', 'a', 2));
+    remove(pod('web-abc', 'a', 2));
+    leaderboard.pods.get('db/web-abc').deletedAt -= 60 * 60 * 1000;
+
+    (watchManager as any).hasWatch = () => true;
+    (manager as any).leaderboards.set('r1', leaderboard);
+    (manager as any).emit('r1');
+    assert.equal(leaderboard.pods.size, 0);
+  });
+});
@@ -0,0 +1,260 @@
+import { randomUUID } from 'crypto';
+import type { RestartLeaderboard, RestartLeaderboardEntry } from '../common/kubeTypes';
+import { emitToRenderer } from './events';
+import { watchManager } from './watch';
+
+export interface RestartLeaderboardSpec {
+  context: string;
+  /** Omit for all namespaces */
+  namespace?: string;
+  /** Pods listed; defaults to 10 */
+  limit?: number;
+  view?: string;
+}
+
+interface PodRestarts {
+  namespace: string;
+  pod: string;
+  uid: string;
+  /** Restart count when the watch first saw this pod */
+  baseline: number;
+  restarts: number;
+  /** Restarts of earlier pods of the same name (StatefulSet pods come back with it) */
+  carried: number;
+  container: string | null;
+  reason: string | null;
+  lastRestartAt: string | null;
+  /** When the pod was deleted; the entry stays a while in case the name comes back */
+  deletedAt: number | null;
+}
+
+interface Leaderboard {
+  watchId: string;
+  limit: number;
+  pods: Map<string, PodRestarts>;
+  changed: boolean;
+  timer: NodeJS.Timeout;
+}
+
+const DEFAULT_LIMIT = 10;
+const MAX_LIMIT = 100;
+const EMIT_INTERVAL_MS = 2000;
+// Pod status changes often (probes, conditions); collapse bursts per pod
+const COALESCE_MS = 1000;
+// How long a deleted pod's restarts are kept for a StatefulSet pod recreated under its name
+const TOMBSTONE_MS = 10 * 60 * 1000;
+
+function containerStatuses(pod: any): any[] {
+  return [...(pod?.status?.initContainerStatuses ?? []), ...(pod?.status?.containerStatuses ?? [])];
+}
+
+function readRestarts(pod: any): Pick<PodRestarts, 'restarts' | 'container' | 'reason' | 'lastRestartAt'> {
+  let restarts = 0;
+  let top: any = null;
+  let lastRestartAt: string | null = null;
+
+  for (const status of containerStatuses(pod)) {
+    const count = typeof status?.restartCount === 'number' ? status.restartCount : 0;
+    restarts += count;
+    if (count > 0 && (!top || count > top.restartCount)) {
+      top = status;
+    }
+    const finishedAt: string | undefined = status?.lastState?.terminated?.finishedAt;
+    if (finishedAt && (!lastRestartAt || Date.parse(finishedAt) > Date.parse(lastRestartAt))) {
+      lastRestartAt = finishedAt;
+    }
+  }
+
+  return {
+    restarts,
+    container: top?.name ?? null,
+    // CrashLoopBackOff while waiting says more than the last exit's reason
+    reason: top ? top.state?.waiting?.reason ?? top.lastState?.terminated?.reason ?? null : null,
+    lastRestartAt,
+  };
+}
+
+function sessionRestarts(entry: PodRestarts): number {
+  return entry.carried + entry.restarts - entry.baseline;
+}
+
+/**
+ * Watches pods and keeps a running "most restarting pods" list, emitted as
+ * `restarts:update` when it changes (at most every couple of seconds), to
+ * spot flapping workloads. Pods are ranked by restarts since the watch
+ * started, then by their total restart count.
+ */
+export class RestartLeaderboardManager {
+  private leaderboards: Map<string, Leaderboard> = new Map();
+
+  startRestartLeaderboard(spec: RestartLeaderboardSpec): string {
+    const limit = spec.limit ?? DEFAULT_LIMIT;
+    if (!Number.isInteger(limit) || limit < 1 || limit > MAX_LIMIT) {
+      throw new Error(`limit must be between 1 and ${MAX_LIMIT}`);
+    }
+
+    const id = randomUUID();
+    const watchId = watchManager.startWatch(
+      {
+        context: spec.context,
+        kind: 'pods',
+        namespace: spec.namespace,
+        allNamespaces: !spec.namespace,
+        view: spec.view,
+        coalesceMs: COALESCE_MS,
+      },
+      (event) => {
+        const leaderboard = this.leaderboards.get(id);
+        if (!leaderboard) {
+          return;
+        }
+        if (event.type === 'ADDED' || event.type === 'MODIFIED') {
+          this.update(leaderboard, event.object);
+        } else if (event.type === 'DELETED') {
+          this.remove(leaderboard, event.object);
+        }
+      },
+      (message) => emitToRenderer('watch:error', id, message)
+    );
+
+    this.leaderboards.set(id, {
+      watchId,
+      limit,
+      pods: new Map(),
+      changed: false,
+      timer: setInterval(() => this.emit(id), EMIT_INTERVAL_MS),
+    });
+
+    console.log(`[Restarts ${id}] Tracking pod restarts in ${spec.namespace ?? 'all namespaces'}`);
+    return id;
+  }
+
+  getRestartLeaderboard(id: string): RestartLeaderboard {
+    const leaderboard = this.leaderboards.get(id);
+    if (!leaderboard) {
+      throw new Error(`Restart leaderboard ${id} not found`);
+    }
+    return this.snapshot(id, leaderboard);
+  }
+
+  stopRestartLeaderboard(id: string): boolean {
+    const leaderboard = this.leaderboards.get(id);
+    if (!leaderboard) {
+      return false;
+    }
+
+    clearInterval(leaderboard.timer);
+    watchManager.stopWatch(leaderboard.watchId);
+    this.leaderboards.delete(id);
+    console.log(`[Restarts ${id}] Stopped`);
+    return true;
+  }
+
+  stopAllRestartLeaderboards(): void {
+    for (const id of Array.from(this.leaderboards.keys())) {
+      this.stopRestartLeaderboard(id);
+    }
+  }
+
+  private update(leaderboard: Leaderboard, pod: any): void {
+    const namespace: string = pod?.metadata?.namespace ?? '';
+    const name: string | undefined = pod?.metadata?.name;
+    if (!name) {
+      return;
+    }
+
+    const key = `${namespace}/${name}`;
+    const uid: string = pod.metadata.uid ?? '';
+    const current = readRestarts(pod);
+    const existing = leaderboard.pods.get(key);
+
+    if (!existing) {
+      leaderboard.pods.set(key, {
+        namespace,
+        pod: name,
+        uid,
+        baseline: current.restarts,
+        carried: 0,
+        ...current,
+        deletedAt: null,
+      });
+      if (current.restarts > 0) {
+        leaderboard.changed = true;
+      }
+      return;
+    }
+
+    if (existing.deletedAt !== null) {
+      existing.deletedAt = null;
+      leaderboard.changed = true;
+    }
+    // Recreated under the same name: its restarts all happened during the session
+    if (existing.uid !== uid) {
+      existing.carried = sessionRestarts(existing);
+      existing.baseline = 0;
+      existing.uid = uid;
+    }
+    if (existing.restarts !== current.restarts || existing.reason !== current.reason) {
+      leaderboard.changed = true;
+    }
+    Object.assign(existing, current);
+  }
+
+  /**
+   * Hide a deleted pod but keep its entry: a StatefulSet pod comes back
+   * under the same name and carries the restarts over
+   */
+  private remove(leaderboard: Leaderboard, pod: any): void {
+    const entry = leaderboard.pods.get(`${pod?.metadata?.namespace ?? ''}/${pod?.metadata?.name ?? ''}`);
+    if (entry && entry.deletedAt === null && entry.uid === (pod?.metadata?.uid ?? '')) {
+      entry.deletedAt = Date.now();
+      leaderboard.changed = true;
+    }
+  }
+
+  private snapshot(id: string, leaderboard: Leaderboard): RestartLeaderboard {
+    const live = Array.from(leaderboard.pods.values()).filter((entry) => entry.deletedAt === null);
+    const entries: RestartLeaderboardEntry[] = live
+      .filter((entry) => entry.restarts > 0 || sessionRestarts(entry) > 0)
+      .sort((a, b) => sessionRestarts(b) - sessionRestarts(a) || b.restarts - a.restarts)
+      .slice(0, leaderboard.limit)
+      .map((entry) => ({
+        namespace: entry.namespace,
+        pod: entry.pod,
+        restarts: entry.restarts,
+        sessionRestarts: sessionRestarts(entry),
+        container: entry.container,
+        reason: entry.reason,
+        lastRestartAt: entry.lastRestartAt,
+      }));
+
+    return { leaderboardId: id, trackedPods: live.length, entries, timestamp: Date.now() };
+  }
+
+  private emit(id: string): void {
+    const leaderboard = this.leaderboards.get(id);
+    if (!leaderboard) {
+      return;
+    }
+    // The watch was cancelled elsewhere, e.g. by a context switch
+    if (!watchManager.hasWatch(leaderboard.watchId)) {
+      this.stopRestartLeaderboard(id);
+      return;
+    }
+    const now = Date.now();
+    for (const [key, entry] of Array.from(leaderboard.pods)) {
+      if (entry.deletedAt !== null && now - entry.deletedAt >= TOMBSTONE_MS) {
+        leaderboard.pods.delete(key);
+      }
+    }
+    if (!leaderboard.changed) {
+      return;
+    }
+
+    leaderboard.changed = false;
+    emitToRenderer('restarts:update', this.snapshot(id, leaderboard));
+  }
+}
+
+// Singleton instance
+export const restartLeaderboardManager = new RestartLeaderboardManager();
@@ -0,0 +1,66 @@
+import { stubSpawnKubectl } from './testSupport';
+import assert from 'node:assert/strict';
+import { describe, it } from 'node:test';
+import * as kube from './kube';
+import { rolloutPause, rolloutResume, rolloutUndo } from './rollouts';
+
+describe('rolloutUndo', () => {
+  it('reads the revision once the controller has observed the rollback', async () => {
+    stubSpawnKubectl();
+    (kube as any).runKubectl = async () => ({ code: 0, stdout: 'deployment.apps/web rolled back\n', stderr: '' });
+    const reads: any[] = [
+      // Right after the undo the controller hasn't caught up and the annotation is stale
+      { metadata: { generation: 5, annotations: { 'deployment.kubernetes.io/revision': '3' } },
+        status: { observedGeneration: 4 } },
+      { metadata: { generation: 5, annotations: { 'deployment.kubernetes.io/revision': '6' } },
+        status: { observedGeneration: 5 } },
+    ];
+    (kube as any).getKubectlJson = async (_context: string, args: string[]) => {
+      if (args[1] === 'replicasets') {
+        return { items: [] };
+      }
+      return reads.length > 1 ? reads.shift() : reads[0];
+    };
+
+    const result = await rolloutUndo({ kind: 'deployment', name: 'web', namespace: 'shop', context: 'dev' });
+    assert.equal(result.revision, 6);
+    assert.equal(result.message, 'deployment.apps/web rolled back');
+  });
+});
+
+describe('rollout pause and resume', () => {
+  it('refuses workloads that cannot be paused without running kubectl', async () => {
+    const calls: string[][] = [];
+    (kube as any).runKubectl = async (_context: string, args: string[]) => {
+      calls.push(args);
+      return { code: 0, stdout: '', stderr: '' };
+    };
+    for (const kind of ['statefulset', 'daemonset']) {
+      await assert.rejects(rolloutPause(kind, 'db', 'shop', 'dev'), { code: 'RolloutUnsupported' });
+      await assert.rejects(rolloutResume(kind, 'db', 'shop', 'dev'), /only deployments support/);
+    }
+    assert.deepEqual(calls, []);
+  });
+
+  it('returns the paused state read back, even when it was already paused', async () => {
+    const calls: string[][] = [];
+    (kube as any).runKubectl = async (_context: string, args: string[]) => {
+      calls.push(args);
+      return { code: 1, stdout: '', stderr: 'error: deployments.apps "web" is already paused' };
+    };
+    (kube as any).getKubectlJson = async () => ({ spec: { paused: true } });
+
+    const state = await rolloutPause('deployment', 'web', 'shop', 'dev');
+    assert.deepEqual(state, { kind: 'deployment', name: 'web', namespace: 'shop', paused: true });
+    assert.deepEqual(calls, [['rollout', 'pause', 'deployment/web']]);
+  });
+
+  it('fails on other kubectl errors', async () => {
+    (kube as any).runKubectl = async () => ({
+      code: 1,
+      stdout: '',
+      stderr: 'Error from server (NotFound): deployments.apps "web" not found',
+    });
+    await assert.rejects(rolloutResume('deployment', 'web', 'shop', 'dev'), /not found/);
+  });
+});
@@ -0,0 +1,380 @@
+import { randomUUID } from 'crypto';
+import type { ChildProcessWithoutNullStreams } from 'child_process';
+import YAML from 'yaml';
+import type {
+  RevisionManifest,
+  RolloutHistory,
+  RolloutKind,
+  RolloutPauseState,
+  RolloutProgressEvent,
+  RolloutRevision,
+} from '../common/kubeTypes';
+import { getKubectlJson, KubeError, kubectlFailure, runKubectl, spawnKubectl } from './kube';
+import { emitToRenderer } from './events';
+import { neatObject } from './neatYaml';
+import { operationRegistry } from './operations';
+
+const ROLLOUT_KINDS: Record<string, RolloutKind> = {
+  deployment: 'deployment',
+  deployments: 'deployment',
+  deploy: 'deployment',
+  statefulset: 'statefulset',
+  statefulsets: 'statefulset',
+  sts: 'statefulset',
+  daemonset: 'daemonset',
+  daemonsets: 'daemonset',
+  ds: 'daemonset',
+};
+
+const REVISION_ANNOTATION = 'deployment.kubernetes.io/revision';
+const CHANGE_CAUSE_ANNOTATION = 'kubernetes.io/change-cause';
+const ROLLOUT_STATUS_TIMEOUT = '5m';
+// Added by the deployment controller to each ReplicaSet's template
+const POD_TEMPLATE_HASH_LABEL = 'pod-template-hash';
+// How long rolloutUndo waits for the controller to act on the rolled-back template
+const OBSERVED_TIMEOUT_MS = 10 * 1000;
+const OBSERVED_POLL_MS = 500;
+
+export interface RolloutUndoSpec {
+  kind: string;
+  name: string;
+  namespace: string;
+  context: string;
+  /** Omit to go back one revision */
+  toRevision?: number;
+  view?: string;
+}
+
+function toRolloutKind(kind: string): RolloutKind {
+  const rolloutKind = ROLLOUT_KINDS[kind.toLowerCase().split('.')[0]];
+  if (!rolloutKind) {
+    throw new KubeError(
+      'RolloutUnsupported',
+      `${kind} has no rollout history; only deployments, statefulsets and daemonsets do`
+    );
+  }
+  return rolloutKind;
+}
+
+function labelSelector(workload: any): string | undefined {
+  const labels: Record<string, string> = workload?.spec?.selector?.matchLabels ?? {};
+  const selector = Object.entries(labels).map(([key, value]) => `${key}=${value}`).join(',');
+  return selector || undefined;
+}
+
+function imagesOf(template: any): string[] {
+  const containers: any[] = template?.spec?.containers ?? [];
+  return containers.map((container) => container.image).filter(Boolean);
+}
+
+function isOwnedBy(object: any, owner: any): boolean {
+  const references: any[] = object?.metadata?.ownerReferences ?? [];
+  return references.some((reference) => reference?.uid === owner?.metadata?.uid);
+}
+
+/**
+ * Objects owned by a workload, narrowed with its selector when it has
+ * matchLabels, then checked by owner uid
+ */
+async function listOwned(resource: string, workload: any, namespace: string, context: string): Promise<any[]> {
+  const args = ['get', resource];
+  const selector = labelSelector(workload);
+  if (selector) {
+    args.push('-l', selector);
+  }
+  const list = await getKubectlJson(context, args, namespace);
+  return (list?.items ?? []).filter((item: any) => isOwnedBy(item, workload));
+}
+
+/**
+ * Revision history of a deployment (from its ReplicaSets) or a statefulset
+ * or daemonset (from its ControllerRevisions). Reading the objects rather
+ * than `kubectl rollout history` output gives images and timestamps too.
+ */
+export async function getRolloutHistory(
+  kind: string,
+  name: string,
+  namespace: string,
+  context: string
+): Promise<RolloutHistory> {
+  const rolloutKind = toRolloutKind(kind);
+  const workload = await getKubectlJson(context, ['get', rolloutKind, name], namespace);
+
+  let revisions: RolloutRevision[];
+  let currentRevision: number | null = null;
+
+  if (rolloutKind === 'deployment') {
+    const replicaSets = await listOwned('replicasets', workload, namespace, context);
+    revisions = replicaSets
+      .map((replicaSet) => ({
+        revision: Number(replicaSet.metadata?.annotations?.[REVISION_ANNOTATION]),
+        changeCause: replicaSet.metadata?.annotations?.[CHANGE_CAUSE_ANNOTATION] ?? null,
+        createdAt: replicaSet.metadata?.creationTimestamp ?? null,
+        images: imagesOf(replicaSet.spec?.template),
+        source: replicaSet.metadata?.name ?? '',
+      }))
+      .filter((revision) => Number.isInteger(revision.revision));
+
+    const annotated = Number(workload?.metadata?.annotations?.[REVISION_ANNOTATION]);
+    currentRevision = Number.isInteger(annotated) ? annotated : null;
+  } else {
+    const controllerRevisions = await listOwned('controllerrevisions', workload, namespace, context);
+    revisions = controllerRevisions
+      .map((controllerRevision) => ({
+        revision: Number(controllerRevision.revision),
+        changeCause: controllerRevision.metadata?.annotations?.[CHANGE_CAUSE_ANNOTATION] ?? null,
+        createdAt: controllerRevision.metadata?.creationTimestamp ?? null,
+        images: imagesOf(controllerRevision.data?.spec?.template),
+        source: controllerRevision.metadata?.name ?? '',
+      }))
+      .filter((revision) => Number.isInteger(revision.revision));
+
+    // Statefulsets name their target revision; daemonsets roll to the newest
+    const updateRevision: string | undefined = workload?.status?.updateRevision;
+    const current = updateRevision ? revisions.find((revision) => revision.source === updateRevision) : undefined;
+    currentRevision = current
+      ? current.revision
+      : revisions.reduce<number | null>((max, revision) => Math.max(max ?? 0, revision.revision), null);
+  }
+
+  revisions.sort((a, b) => a.revision - b.revision);
+
+  return {
+    kind: rolloutKind,
+    name,
+    namespace,
+    currentRevision,
+    revisions,
+  };
+}
+
+/**
+ * Pod template deployed at one revision: found through the workload's
+ * owned ReplicaSets (deployments) or ControllerRevisions (statefulsets,
+ * daemonsets). Throws NotFound if no owned object holds that revision.
+ */
+export async function getRevisionManifest(
+  kind: string,
+  name: string,
+  namespace: string,
+  revision: number,
+  context: string
+): Promise<RevisionManifest> {
+  const rolloutKind = toRolloutKind(kind);
+  const workload = await getKubectlJson(context, ['get', rolloutKind, name], namespace);
+
+  let source: any;
+  let template: any;
+
+  if (rolloutKind === 'deployment') {
+    const replicaSets = await listOwned('replicasets', workload, namespace, context);
+    source = replicaSets.find(
+      (replicaSet) => Number(replicaSet.metadata?.annotations?.[REVISION_ANNOTATION]) === revision
+    );
+    template = JSON.parse(JSON.stringify(source?.spec?.template ?? null));
+    // Not part of what the user deployed
+    delete template?.metadata?.labels?.[POD_TEMPLATE_HASH_LABEL];
+  } else {
+    const controllerRevisions = await listOwned('controllerrevisions', workload, namespace, context);
+    source = controllerRevisions.find((controllerRevision) => Number(controllerRevision.revision) === revision);
+    // The data is a patch over the workload spec that replaces the template
+    template = JSON.parse(JSON.stringify(source?.data?.spec?.template ?? null));
+    delete template?.$patch;
+  }
+
+  if (!source || !template) {
+    throw new KubeError('NotFound', `${rolloutKind}/${name} has no revision ${revision}`);
+  }
+
+  const neat = neatObject(template);
+  return {
+    kind: rolloutKind,
+    name,
+    namespace,
+    revision,
+    source: source.metadata?.name ?? '',
+    sourceKind: rolloutKind === 'deployment' ? 'ReplicaSet' : 'ControllerRevision',
+    template: neat,
+    yaml: YAML.stringify(neat),
+  };
+}
+
+function emitProgress(event: RolloutProgressEvent): void {
+  emitToRenderer('rollout:progress', event);
+}
+
+/**
+ * Follow `kubectl rollout status` and emit each line as `rollout:progress`,
+ * ending with a `done` event
+ */
+function followRolloutStatus(rolloutId: string, kind: RolloutKind, spec: RolloutUndoSpec): void {
+  let child: ChildProcessWithoutNullStreams;
+  try {
+    child = spawnKubectl(
+      spec.context,
+      ['rollout', 'status', `${kind}/${spec.name}`, '--watch', `--timeout=${ROLLOUT_STATUS_TIMEOUT}`],
+      spec.namespace
+    );
+  } catch (error) {
+    // The rollback itself went through; only following it failed. Sent once the caller has the id
+    const message = error instanceof Error ? error.message : String(error);
+    setImmediate(() => emitProgress({ rolloutId, message, done: true, ok: false }));
+    return;
+  }
+  let buffer = '';
+  let lastLine = '';
+  let finished = false;
+
+  const finish = (ok: boolean, message: string) => {
+    if (finished) {
+      return;
+    }
+    finished = true;
+    operationRegistry.unregister(rolloutId);
+    emitProgress({ rolloutId, message, done: true, ok });
+  };
+
+  operationRegistry.register(
+    rolloutId,
+    'watches',
+    { context: spec.context, namespace: spec.namespace, view: spec.view },
+    () => {
+      child.kill();
+      finish(false, 'Stopped following the rollout');
+    }
+  );
+
+  const onOutput = (chunk: Buffer) => {
+    buffer += chunk.toString();
+    const lines = buffer.split('\n');
+    buffer = lines.pop() ?? '';
+    for (const line of lines) {
+      if (line.trim()) {
+        lastLine = line.trim();
+        emitProgress({ rolloutId, message: lastLine, done: false });
+      }
+    }
+  };
+
+  child.stdout.on('data', onOutput);
+  child.stderr.on('data', onOutput);
+
+  child.once('error', (error) => finish(false, error.message));
+  child.once('close', (code) => {
+    const rest = buffer.trim();
+    if (rest) {
+      lastLine = rest;
+      emitProgress({ rolloutId, message: rest, done: false });
+    }
+    finish(code === 0, lastLine || `kubectl rollout status exited with code ${code}`);
+  });
+}
+
+/**
+ * Whether the workload's controller has acted on its latest spec (status
+ * caught up with metadata.generation), polling for up to OBSERVED_TIMEOUT_MS.
+ * Until then the revision annotation still names the old revision.
+ */
+async function waitForObservedGeneration(
+  kind: RolloutKind,
+  name: string,
+  namespace: string,
+  context: string
+): Promise<boolean> {
+  const deadline = Date.now() + OBSERVED_TIMEOUT_MS;
+  for (;;) {
+    const workload = await getKubectlJson(context, ['get', kind, name], namespace);
+    const generation = Number(workload?.metadata?.generation);
+    const observed = Number(workload?.status?.observedGeneration);
+    if (!Number.isFinite(generation) || observed >= generation) {
+      return true;
+    }
+    if (Date.now() >= deadline) {
+      return false;
+    }
+    await new Promise((resolve) => setTimeout(resolve, OBSERVED_POLL_MS));
+  }
+}
+
+/**
+ * Roll a workload back with `kubectl rollout undo` and confirm which
+ * revision is now active, once the controller has picked up the rollback;
+ * the revision is null if it hadn't within OBSERVED_TIMEOUT_MS. Progress of
+ * the resulting rollout is streamed as `rollout:progress` events under the
+ * returned id.
+ */
+export async function rolloutUndo(
+  spec: RolloutUndoSpec
+): Promise<{ rolloutId: string; revision: number | null; message: string }> {
+  const kind = toRolloutKind(spec.kind);
+  const args = ['rollout', 'undo', `${kind}/${spec.name}`];
+  if (spec.toRevision !== undefined) {
+    args.push(`--to-revision=${spec.toRevision}`);
+  }
+
+  const result = await runKubectl(spec.context, args, spec.namespace, { view: spec.view });
+  if (result.code !== 0) {
+    throw kubectlFailure(result, `Failed to roll back ${kind}/${spec.name}`);
+  }
+
+  const rolloutId = randomUUID();
+  followRolloutStatus(rolloutId, kind, spec);
+
+  const observed = await waitForObservedGeneration(kind, spec.name, spec.namespace, spec.context);
+  const revision = observed
+    ? (await getRolloutHistory(kind, spec.name, spec.namespace, spec.context)).currentRevision
+    : null;
+  console.log(`[Rollout ${rolloutId}] ${kind}/${spec.name} rolled back, now at revision ${revision ?? 'unknown'}`);
+  return { rolloutId, revision, message: result.stdout.trim() };
+}
+
+/**
+ * Pause or resume a deployment's rollout and return the paused state read
+ * back from the object. Only deployments can be paused.
+ */
+async function setRolloutPaused(
+  kind: string,
+  name: string,
+  namespace: string,
+  context: string,
+  paused: boolean
+): Promise<RolloutPauseState> {
+  const rolloutKind = toRolloutKind(kind);
+  if (rolloutKind !== 'deployment') {
+    throw new KubeError(
+      'RolloutUnsupported',
+      `${rolloutKind}s can't be paused; only deployments support rollout pause and resume`
+    );
+  }
+
+  const result = await runKubectl(
+    context,
+    ['rollout', paused ? 'pause' : 'resume', `${rolloutKind}/${name}`],
+    namespace
+  );
+  // Pausing a paused deployment (or resuming a running one) is not a failure
+  if (result.code !== 0 && !/is already paused|is not paused/i.test(result.stderr)) {
+    throw kubectlFailure(result, `Failed to ${paused ? 'pause' : 'resume'} ${rolloutKind}/${name}`);
+  }
+
+  const workload = await getKubectlJson(context, ['get', rolloutKind, name], namespace);
+  return { kind: rolloutKind, name, namespace, paused: workload?.spec?.paused === true };
+}
+
+export function rolloutPause(
+  kind: string,
+  name: string,
+  namespace: string,
+  context: string
+): Promise<RolloutPauseState> {
+  return setRolloutPaused(kind, name, namespace, context, true);
+}
+
+export function rolloutResume(
+  kind: string,
+  name: string,
+  namespace: string,
+  context: string
+): Promise<RolloutPauseState> {
+  return setRolloutPaused(kind, name, namespace, context, false);
+}
@@ -0,0 +1,78 @@
+import { createWriteStream, existsSync, mkdirSync, renameSync, statSync, type WriteStream } from 'fs';
+import path from 'path';
+
+/**
+ * Append-only file that rolls over at a size cap: NAME becomes NAME.1,
+ * NAME.1 becomes NAME.2 and so on, dropping the oldest. A write error
+ * closes the file and is reported once through onError; callers decide
+ * whether to carry on without it.
+ */
+export class RotatingFile {
+  private stream: WriteStream | null = null;
+  private bytesWritten = 0;
+
+  constructor(
+    readonly filePath: string,
+    private readonly maxBytes: number,
+    private readonly maxRotated: number,
+    private readonly onError: (message: string, error: unknown) => void
+  ) {
+    this.open();
+  }
+
+  get isOpen(): boolean {
+    return this.stream !== null;
+  }
+
+  write(text: string): void {
+    if (!this.stream) {
+      return;
+    }
+
+    this.stream.write(text);
+    this.bytesWritten += Buffer.byteLength(text);
+    if (this.bytesWritten > this.maxBytes) {
+      this.rotate();
+    }
+  }
+
+  close(): void {
+    this.stream?.end();
+    this.stream = null;
+  }
+
+  private open(): void {
+    mkdirSync(path.dirname(this.filePath), { recursive: true });
+    this.bytesWritten = existsSync(this.filePath) ? statSync(this.filePath).size : 0;
+    const stream = createWriteStream(this.filePath, { flags: 'a' });
+    stream.on('error', (error) => {
+      if (this.stream === stream) {
+        this.stream = null;
+        this.onError(`Write to ${this.filePath} failed`, error);
+      }
+    });
+    this.stream = stream;
+  }
+
+  private rotate(): void {
+    this.close();
+
+    try {
+      for (let index = this.maxRotated - 1; index >= 1; index -= 1) {
+        const from = `${this.filePath}.${index}`;
+        if (existsSync(from)) {
+          renameSync(from, `${this.filePath}.${index + 1}`);
+        }
+      }
+      renameSync(this.filePath, `${this.filePath}.1`);
+    } catch (error) {
+      this.onError(`Failed to rotate ${this.filePath}`, error);
+    }
+
+    try {
+      this.open();
+    } catch (error) {
+      this.onError(`Failed to reopen ${this.filePath}`, error);
+    }
+  }
+}
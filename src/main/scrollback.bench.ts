@@ -0,0 +1,87 @@
+/**
+ * Memory used by ScrollbackBuffer for a large log stream, compared with
+ * keeping the same output as a plain string. Run with `npm run bench`.
+ */
+import assert from 'node:assert/strict';
+import { ScrollbackBuffer } from './scrollback';
+
+const STREAM_CHARS = 64 * 1024 * 1024;
+const READS = 200;
+const READ_CHARS = 96 * 1024;
+const LEVELS = ['INFO', 'INFO', 'INFO', 'DEBUG', 'WARN', 'ERROR'];
+const PATHS = ['/api/v1/orders', '/api/v1/users', '/healthz', '/metrics', '/api/v1/cart'];
+
+// Roughly what a busy web pod prints: timestamps, levels, request ids, latencies
+function logLine(index: number): string {
+  const timestamp = new Date(Date.UTC(2026, 0, 1) + index * 7).toISOString();
+  const level = LEVELS[index % LEVELS.length];
+  const requestId = ((index * 2654435761) >>> 0).toString(16).padStart(8, '0');
+  const path = PATHS[(index >>> 3) % PATHS.length];
+  const status = index % 97 === 0 ? 500 : 200;
+  return `${timestamp} ${level} [req ${requestId}] GET ${path} ${status} ${((index * 37) % 900) + 3}ms\r\n`;
+}
+
+// PTY reads arrive a few lines at a time; the same stream every run
+function forEachChunk(visit: (chunk: string) => void): void {
+  let chars = 0;
+  for (let index = 0; chars < STREAM_CHARS; index += 8) {
+    let chunk = '';
+    for (let line = 0; line < 8; line++) {
+      chunk += logLine(index + line);
+    }
+    chars += chunk.length;
+    visit(chunk);
+  }
+}
+
+function heapUsed(): number {
+  global.gc?.();
+  const { heapUsed, external } = process.memoryUsage();
+  return heapUsed + external;
+}
+
+function megabytes(bytes: number): string {
+  return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+}
+
+function elapsedMs(startedAt: bigint): number {
+  return Number(process.hrtime.bigint() - startedAt) / 1e6;
+}
+
+if (!global.gc) {
+  console.warn('Run node with --expose-gc for steadier heap figures');
+}
+
+let baseline = heapUsed();
+const appendStartedAt = process.hrtime.bigint();
+// Keep everything so the comparison covers the whole stream
+const buffer = new ScrollbackBuffer({ maxCompressedBytes: Number.MAX_SAFE_INTEGER });
+forEachChunk((chunk) => buffer.append(chunk));
+const appendMs = elapsedMs(appendStartedAt);
+const bufferHeap = heapUsed() - baseline;
+
+baseline = heapUsed();
+const parts: string[] = [];
+forEachChunk((chunk) => parts.push(chunk));
+const plain = parts.join('');
+parts.length = 0;
+// Flatten the joined string so it's measured as stored
+assert.equal(plain.charCodeAt(plain.length - 1), 10);
+const plainHeap = heapUsed() - baseline;
+
+// Range reads spanning segment boundaries must match the plain text
+const readStartedAt = process.hrtime.bigint();
+for (let read = 0; read < READS; read++) {
+  const start = Math.floor((plain.length / READS) * read) + (read % 7) * 1000;
+  const end = Math.min(start + READ_CHARS, plain.length);
+  assert.equal(buffer.read(start, end), plain.slice(start, end));
+}
+const readMs = elapsedMs(readStartedAt);
+
+const ratio = (plainHeap / bufferHeap).toFixed(1);
+console.log(`Log stream:        ${plain.length} chars`);
+console.log(`As a string:       ${megabytes(plainHeap)} of heap`);
+console.log(`ScrollbackBuffer:  ${megabytes(bufferHeap)} of heap (${ratio}x less)`);
+console.log(`  byteSize:        ${megabytes(buffer.byteSize)}`);
+console.log(`Append:            ${appendMs.toFixed(0)} ms`);
+console.log(`Range reads:       ${(readMs / READS).toFixed(2)} ms per ${READ_CHARS / 1024}K chars across segments`);
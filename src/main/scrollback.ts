@@ -0,0 +1,121 @@
+import { deflateRawSync, inflateRawSync } from 'zlib';
+
+export interface ScrollbackOptions {
+  /** Size of the uncompressed tail before it's sealed into a segment */
+  segmentChars?: number;
+  /** Compressed history beyond this is dropped, oldest first */
+  maxCompressedBytes?: number;
+}
+
+interface Segment {
+  /** Offset of the segment's first character in the whole stream */
+  start: number;
+  length: number;
+  data: Buffer;
+}
+
+const DEFAULT_SEGMENT_CHARS = 64 * 1024;
+// Terminal output compresses ~10x, so this holds tens of MB of history
+const DEFAULT_MAX_COMPRESSED_BYTES = 4 * 1024 * 1024;
+
+function isHighSurrogate(code: number): boolean {
+  return code >= 0xd800 && code <= 0xdbff;
+}
+
+/**
+ * Append-only terminal history. Older output is kept in deflate-compressed
+ * segments and only the most recent segment stays a plain string, so
+ * appends are cheap and reads only inflate the segments they touch.
+ * Offsets count UTF-16 code units from the start of the stream.
+ */
+export class ScrollbackBuffer {
+  private segments: Segment[] = [];
+  private tail = '';
+  private tailStart = 0;
+  private compressedBytes = 0;
+  private readonly segmentChars: number;
+  private readonly maxCompressedBytes: number;
+
+  constructor(options: ScrollbackOptions = {}) {
+    this.segmentChars = options.segmentChars ?? DEFAULT_SEGMENT_CHARS;
+    this.maxCompressedBytes = options.maxCompressedBytes ?? DEFAULT_MAX_COMPRESSED_BYTES;
+  }
+
+  /** Offset of the oldest character still retained */
+  get start(): number {
+    return this.segments.length > 0 ? this.segments[0].start : this.tailStart;
+  }
+
+  /** Offset just past the newest character */
+  get end(): number {
+    return this.tailStart + this.tail.length;
+  }
+
+  get byteSize(): number {
+    return this.compressedBytes + this.tail.length * 2;
+  }
+
+  append(data: string): void {
+    this.tail += data;
+    while (this.tail.length >= this.segmentChars) {
+      this.seal();
+    }
+  }
+
+  /**
+   * Text in [start, end), clamped to what is still retained
+   */
+  read(start: number, end: number = this.end): string {
+    const from = Math.max(start, this.start);
+    const to = Math.min(end, this.end);
+    if (from >= to) {
+      return '';
+    }
+
+    const parts: string[] = [];
+    for (const segment of this.segments) {
+      const segmentEnd = segment.start + segment.length;
+      if (segmentEnd <= from || segment.start >= to) {
+        continue;
+      }
+      const text = inflateRawSync(segment.data).toString('utf8');
+      parts.push(text.slice(Math.max(from - segment.start, 0), Math.min(to, segmentEnd) - segment.start));
+    }
+
+    if (to > this.tailStart) {
+      parts.push(this.tail.slice(Math.max(from - this.tailStart, 0), to - this.tailStart));
+    }
+
+    return parts.join('');
+  }
+
+  /**
+   * The last `chars` characters of output
+   */
+  readTail(chars: number): string {
+    return this.read(this.end - chars);
+  }
+
+  private seal(): void {
+    let cut = this.segmentChars;
+    // Never split a surrogate pair: the halves wouldn't survive UTF-8
+    if (isHighSurrogate(this.tail.charCodeAt(cut - 1))) {
+      cut -= 1;
+    }
+
+    const text = this.tail.slice(0, cut);
+    const data = deflateRawSync(Buffer.from(text, 'utf8'));
+
+    this.segments.push({ start: this.tailStart, length: text.length, data });
+    this.compressedBytes += data.length;
+    this.tail = this.tail.slice(cut);
+    this.tailStart += text.length;
+
+    while (this.compressedBytes > this.maxCompressedBytes && this.segments.length > 0) {
+      const dropped = this.segments.shift();
+      if (dropped) {
+        this.compressedBytes -= dropped.data.length;
+      }
+    }
+  }
+}
@@ -0,0 +1,262 @@
+import { execFile } from 'child_process';
+import { accessSync, constants, statSync } from 'fs';
+import { mkdtemp, readFile, rm, writeFile } from 'fs/promises';
+import os from 'os';
+import path from 'path';
+import { promisify } from 'util';
+import type { SelfTestCheck, SelfTestCheckId, SelfTestReport } from '../common/appTypes';
+import type { KubeconfigValidation } from '../common/kubeTypes';
+import { detectCloudAuth } from './cloudAuth';
+import { classifyKubectlError, getKubectlBinary, loadKubeConfig, resolveKubeconfigPath, runKubectl } from './kube';
+import { validateKubeconfig } from './kubeconfigRepair';
+
+const execFileAsync = promisify(execFile);
+
+const CHECK_TIMEOUT_MS = 10 * 1000;
+// The server check talks to the cluster, possibly through an exec plugin login
+const CONTEXT_TIMEOUT_MS = 15 * 1000;
+
+type CheckOutcome = Pick<SelfTestCheck, 'status' | 'message' | 'hint'>;
+
+const CHECK_LABELS: Record<SelfTestCheckId, string> = {
+  kubectl: 'kubectl installed',
+  kubeconfig: 'Kubeconfig readable and valid',
+  context: 'Current context reachable',
+  'exec-plugin': 'Auth plugin found on PATH',
+  'temp-dir': 'Temp directory writable',
+};
+
+function pass(message: string): CheckOutcome {
+  return { status: 'pass', message, hint: null };
+}
+
+function warn(message: string, hint: string): CheckOutcome {
+  return { status: 'warn', message, hint };
+}
+
+function fail(message: string, hint: string): CheckOutcome {
+  return { status: 'fail', message, hint };
+}
+
+function skip(message: string): CheckOutcome {
+  return { status: 'skipped', message, hint: null };
+}
+
+function messageOf(error: unknown): string {
+  return error instanceof Error ? error.message : String(error);
+}
+
+async function runCheck(
+  id: SelfTestCheckId,
+  timeoutMs: number,
+  check: () => Promise<CheckOutcome>
+): Promise<SelfTestCheck> {
+  const startedAt = Date.now();
+  let timer: NodeJS.Timeout | undefined;
+  const timeout = new Promise<CheckOutcome>((resolve) => {
+    timer = setTimeout(
+      () => resolve(fail(`Timed out after ${timeoutMs / 1000}s`, 'Something is hanging; see the app log for details')),
+      timeoutMs
+    );
+  });
+
+  let outcome: CheckOutcome;
+  try {
+    outcome = await Promise.race([check(), timeout]);
+  } catch (error) {
+    outcome = fail(messageOf(error), 'See the app log for details');
+  } finally {
+    clearTimeout(timer);
+  }
+  return { id, label: CHECK_LABELS[id], ...outcome, durationMs: Date.now() - startedAt };
+}
+
+async function checkKubectl(): Promise<CheckOutcome> {
+  const binary = getKubectlBinary();
+  try {
+    const { stdout } = await execFileAsync(binary, ['version', '--client', '-o', 'json'], {
+      timeout: CHECK_TIMEOUT_MS,
+    });
+    const version: string | undefined = JSON.parse(stdout || '{}')?.clientVersion?.gitVersion;
+    return pass(version ? `kubectl ${version}` : 'kubectl found');
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException)?.code === 'ENOENT') {
+      return fail(
+        'kubectl was not found on PATH',
+        'Install kubectl (https://kubernetes.io/docs/tasks/tools/) or add its directory to PATH'
+      );
+    }
+    return fail(`kubectl did not run: ${messageOf(error)}`, 'Reinstall kubectl; the binary may be corrupt');
+  }
+}
+
+async function checkKubeconfig(validation: Promise<KubeconfigValidation>): Promise<CheckOutcome> {
+  const kubeconfigPath = resolveKubeconfigPath();
+  let contexts: number;
+  try {
+    contexts = (await loadKubeConfig()).contexts.length;
+  } catch (error) {
+    const code = (error as NodeJS.ErrnoException)?.code;
+    if (code === 'ENOENT') {
+      return fail(
+        `${kubeconfigPath} does not exist`,
+        "Create one with your cloud provider's CLI (e.g. `aws eks update-kubeconfig`) or pick a file in the app"
+      );
+    }
+    if (code === 'EACCES') {
+      return fail(`${kubeconfigPath} is not readable`, `Fix its permissions, e.g. \`chmod 600 ${kubeconfigPath}\``);
+    }
+    return fail(`${kubeconfigPath} could not be parsed: ${messageOf(error)}`, 'Fix the YAML syntax in the file');
+  }
+
+  if (contexts === 0) {
+    return fail(`${kubeconfigPath} defines no contexts`, 'Add a cluster with your provider CLI or pick another file');
+  }
+  const { problems } = await validation;
+  if (problems.length > 0) {
+    return warn(
+      `${problems.length} problem(s): ${problems.map((problem) => problem.message).join('; ')}`,
+      'Run the kubeconfig repair to remove broken entries'
+    );
+  }
+  return pass(`${contexts} context(s) in ${kubeconfigPath}`);
+}
+
+async function checkContext(context: string | null): Promise<CheckOutcome> {
+  if (!context) {
+    return skip('No current context is set');
+  }
+
+  const result = await runKubectl(context, ['version', '-o', 'json'], undefined, { timeoutMs: CONTEXT_TIMEOUT_MS });
+  let serverVersion: string | undefined;
+  try {
+    serverVersion = JSON.parse(result.stdout || '{}')?.serverVersion?.gitVersion;
+  } catch {
+    serverVersion = undefined;
+  }
+  if (serverVersion) {
+    return pass(`${context}: server ${serverVersion}`);
+  }
+
+  const output = (result.stderr || result.stdout || 'kubectl version failed').trim();
+  switch (classifyKubectlError(output)) {
+    case 'Unreachable':
+      return fail(`${context}: ${output}`, 'Check the VPN or network, and that the cluster is running');
+    case 'Unauthorized':
+      return fail(`${context}: ${output}`, "The credentials expired or were rejected; log in with the provider's CLI");
+    case 'Forbidden':
+      // Reaching RBAC means the cluster and the credentials both work
+      return warn(`${context}: ${output}`, 'The cluster is reachable, but your user may not read its version');
+    default:
+      return fail(`${context}: ${output}`, 'Run `kubectl version` in a terminal to see the full error');
+  }
+}
+
+function isExecutable(file: string): boolean {
+  try {
+    accessSync(file, constants.X_OK);
+    return statSync(file).isFile();
+  } catch {
+    return false;
+  }
+}
+
+function findOnPath(command: string, searchPath: string): string | null {
+  if (path.isAbsolute(command)) {
+    return isExecutable(command) ? command : null;
+  }
+  const extensions =
+    process.platform === 'win32' ? ['', ...(process.env.PATHEXT || '.EXE;.CMD;.BAT').split(';')] : [''];
+  for (const dir of searchPath.split(path.delimiter).filter(Boolean)) {
+    for (const ext of extensions) {
+      const candidate = path.join(dir, command + ext);
+      if (isExecutable(candidate)) {
+        return candidate;
+      }
+    }
+  }
+  return null;
+}
+
+/**
+ * The exec plugin (aws, gke-gcloud-auth-plugin, kubelogin...) must be found
+ * on the PATH kubectl gets, which includes the directories the app adds
+ * for the provider. An app started from the dock lacks the shell's PATH.
+ */
+async function checkExecPlugin(context: string | null): Promise<CheckOutcome> {
+  if (!context) {
+    return skip('No current context is set');
+  }
+
+  const detection = detectCloudAuth(context, resolveKubeconfigPath());
+  if (!detection.execCommand) {
+    return pass(`${context} does not use an exec plugin`);
+  }
+
+  const searchPath = detection.env.PATH ?? process.env.PATH ?? '';
+  const found = findOnPath(detection.execCommand, searchPath);
+  const added = detection.addedPath.length > 0 ? ` (PATH extended with ${detection.addedPath.join(', ')})` : '';
+  if (found) {
+    return pass(`${detection.execCommand} found at ${found}${added}`);
+  }
+  return fail(
+    `${detection.execCommand} was not found on PATH${added}`,
+    detection.provider
+      ? `Install the ${detection.provider} auth plugin, or add its directory to a cloud auth profile's path`
+      : 'Install the exec plugin, or use an absolute path for it in the kubeconfig'
+  );
+}
+
+async function checkTempDir(): Promise<CheckOutcome> {
+  const base = os.tmpdir();
+  let dir: string | null = null;
+  try {
+    dir = await mkdtemp(path.join(base, 'kubecli-selftest-'));
+    const file = path.join(dir, 'probe');
+    await writeFile(file, 'ok', 'utf8');
+    if ((await readFile(file, 'utf8')) !== 'ok') {
+      return fail(`Files written to ${base} read back differently`, 'Check the disk for errors');
+    }
+    return pass(`${base} is writable`);
+  } catch (error) {
+    return fail(
+      `Cannot write to ${base}: ${messageOf(error)}`,
+      'Free up disk space, or point TMPDIR (TEMP on Windows) at a writable directory'
+    );
+  } finally {
+    if (dir) {
+      await rm(dir, { recursive: true, force: true }).catch(() => undefined);
+    }
+  }
+}
+
+/**
+ * Check everything the app depends on in one go, for an onboarding or
+ * diagnostics screen: kubectl, the kubeconfig, the current context's
+ * server, its exec plugin and the temp directory. Checks run concurrently,
+ * each with a timeout, and every failure comes with a hint on fixing it.
+ * `ok` is false when any check failed; warnings don't count.
+ */
+export async function runSelfTest(): Promise<SelfTestReport> {
+  const startedAt = Date.now();
+  const validation = validateKubeconfig();
+  const context = await validation.then(
+    (result) => result.currentContext,
+    () => null
+  );
+
+  const checks = await Promise.all([
+    runCheck('kubectl', CHECK_TIMEOUT_MS, checkKubectl),
+    runCheck('kubeconfig', CHECK_TIMEOUT_MS, () => checkKubeconfig(validation)),
+    runCheck('context', CONTEXT_TIMEOUT_MS + 1000, () => checkContext(context)),
+    runCheck('exec-plugin', CHECK_TIMEOUT_MS, () => checkExecPlugin(context)),
+    runCheck('temp-dir', CHECK_TIMEOUT_MS, checkTempDir),
+  ]);
+
+  const failed = checks.filter((check) => check.status === 'fail');
+  console.log(
+    `[SelfTest] ${checks.length - failed.length}/${checks.length} checks passed` +
+      (failed.length > 0 ? `; failed: ${failed.map((check) => check.id).join(', ')}` : '')
+  );
+  return { ok: failed.length === 0, context, checks, durationMs: Date.now() - startedAt };
+}
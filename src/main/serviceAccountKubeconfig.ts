@@ -0,0 +1,143 @@
+import { app } from 'electron';
+import { chmod, mkdir, writeFile } from 'fs/promises';
+import path from 'path';
+import YAML from 'yaml';
+import type { ServiceAccountKubeconfig } from '../common/kubeTypes';
+import { getClusterCa, getKubectlJson, jwtExpiry, KubeError, kubectlFailure, resolveContext, runKubectl } from './kube';
+
+export interface ServiceAccountKubeconfigSpec {
+  serviceAccount: string;
+  namespace: string;
+  context: string;
+  /** Defaults to a file under the app's data directory */
+  outputPath?: string;
+  /** Lifetime asked for the token; the API server may shorten it */
+  durationSeconds?: number;
+}
+
+const DEFAULT_TOKEN_DURATION_SECONDS = 8 * 60 * 60;
+// TokenRequest's own minimum
+const MIN_TOKEN_DURATION_SECONDS = 10 * 60;
+// kubectl before 1.24, or an API server without the TokenRequest API
+const NO_TOKEN_REQUEST_PATTERN = /unknown command "token"|the server could not find the requested resource/i;
+const SERVICE_ACCOUNT_TOKEN_TYPE = 'kubernetes.io/service-account-token';
+
+async function requestToken(spec: ServiceAccountKubeconfigSpec, durationSeconds: number): Promise<string | null> {
+  const result = await runKubectl(
+    spec.context,
+    ['create', 'token', spec.serviceAccount, `--duration=${durationSeconds}s`],
+    spec.namespace
+  );
+  if (result.code !== 0) {
+    if (NO_TOKEN_REQUEST_PATTERN.test(result.stderr)) {
+      return null;
+    }
+    throw kubectlFailure(result, `Failed to create a token for ${spec.serviceAccount}`);
+  }
+  return result.stdout.trim() || null;
+}
+
+/**
+ * The long-lived token of a legacy service-account-token Secret. Clusters
+ * before 1.24 create one per service account; later ones only have it if
+ * someone created it by hand.
+ */
+async function legacySecretToken(spec: ServiceAccountKubeconfigSpec, serviceAccount: any): Promise<string> {
+  const list = await getKubectlJson(
+    spec.context,
+    ['get', 'secrets', '--field-selector', `type=${SERVICE_ACCOUNT_TOKEN_TYPE}`],
+    spec.namespace
+  );
+  const referenced = new Set((serviceAccount?.secrets ?? []).map((secret: any) => secret?.name));
+  const secret = (list?.items ?? []).find(
+    (item: any) =>
+      item?.metadata?.annotations?.['kubernetes.io/service-account.name'] === spec.serviceAccount ||
+      referenced.has(item?.metadata?.name)
+  );
+  const token = secret?.data?.token;
+  if (typeof token !== 'string' || !token) {
+    throw new KubeError(
+      'NotFound',
+      `ServiceAccount ${spec.namespace}/${spec.serviceAccount} has no token Secret, ` +
+        'and this cluster or kubectl does not support `kubectl create token`'
+    );
+  }
+  return Buffer.from(token, 'base64').toString('utf8');
+}
+
+/**
+ * Write a standalone kubeconfig that authenticates as a service account,
+ * for handing someone access scoped by that account's RBAC. The token is a
+ * short-lived TokenRequest token (`kubectl create token`) where supported,
+ * falling back to the account's legacy token Secret, which doesn't expire.
+ * The cluster's server and CA come from `context`. The file is written
+ * with mode 0600; the token is never logged.
+ */
+export async function generateServiceAccountKubeconfig(
+  spec: ServiceAccountKubeconfigSpec
+): Promise<ServiceAccountKubeconfig> {
+  const duration = spec.durationSeconds ?? DEFAULT_TOKEN_DURATION_SECONDS;
+  if (!Number.isInteger(duration) || duration < MIN_TOKEN_DURATION_SECONDS) {
+    throw new Error(`durationSeconds must be a whole number of at least ${MIN_TOKEN_DURATION_SECONDS}`);
+  }
+
+  const serviceAccount = await getKubectlJson(
+    spec.context,
+    ['get', 'serviceaccount', spec.serviceAccount],
+    spec.namespace
+  );
+  const resolved = await resolveContext(spec.context);
+  const ca = await getClusterCa(spec.context);
+
+  let tokenSource: ServiceAccountKubeconfig['tokenSource'] = 'token-request';
+  let token = await requestToken(spec, duration);
+  if (token === null) {
+    tokenSource = 'secret';
+    token = await legacySecretToken(spec, serviceAccount);
+  }
+
+  const user = `${spec.serviceAccount}@${spec.namespace}`;
+  const contextName = `${user}@${resolved.cluster}`;
+  const cluster: Record<string, any> = { server: resolved.server };
+  if (ca) {
+    cluster['certificate-authority-data'] = ca.toString('base64');
+  } else if (resolved.insecureSkipTlsVerify) {
+    cluster['insecure-skip-tls-verify'] = true;
+  }
+  if (resolved.tlsServerName) {
+    cluster['tls-server-name'] = resolved.tlsServerName;
+  }
+  if (resolved.proxyUrl) {
+    cluster['proxy-url'] = resolved.proxyUrl;
+  }
+
+  const kubeconfig = {
+    apiVersion: 'v1',
+    kind: 'Config',
+    clusters: [{ name: resolved.cluster, cluster }],
+    users: [{ name: user, user: { token } }],
+    contexts: [{ name: contextName, context: { cluster: resolved.cluster, user, namespace: spec.namespace } }],
+    'current-context': contextName,
+  };
+
+  // EKS cluster names are ARNs, with ":" and "/"
+  const fileName = `${resolved.cluster}-${spec.namespace}-${spec.serviceAccount}`.replace(/[^\w.-]+/g, '_');
+  const outputPath = spec.outputPath
+    ? path.resolve(spec.outputPath)
+    : path.join(app.getPath('userData'), 'kubeconfigs', `${fileName}.yaml`);
+  await mkdir(path.dirname(outputPath), { recursive: true });
+  await writeFile(outputPath, YAML.stringify(kubeconfig), { encoding: 'utf8', mode: 0o600 });
+  // The mode only applies to a new file
+  await chmod(outputPath, 0o600);
+
+  const expiry = tokenSource === 'token-request' ? jwtExpiry(token) : null;
+  console.log(`[ServiceAccount ${spec.namespace}/${spec.serviceAccount}] Wrote kubeconfig to ${outputPath}`);
+  return {
+    path: outputPath,
+    context: contextName,
+    serviceAccount: spec.serviceAccount,
+    namespace: spec.namespace,
+    tokenSource,
+    expiresAt: expiry ? expiry.toISOString() : null,
+  };
+}
@@ -0,0 +1,149 @@
+import type { EndpointAddress, EndpointPort, ServiceEndpoints, ServicePort } from '../common/kubeTypes';
+import { getKubectlJson, KubeError } from './kube';
+
+function slicePorts(ports: any[] | undefined): EndpointPort[] {
+  return (ports ?? []).map((port) => ({
+    name: port?.name || null,
+    port: typeof port?.port === 'number' ? port.port : null,
+    protocol: port?.protocol ?? 'TCP',
+  }));
+}
+
+function fromSlices(slices: any[]): EndpointAddress[] {
+  const addresses: EndpointAddress[] = [];
+  for (const slice of slices) {
+    const ports = slicePorts(slice?.ports);
+    for (const endpoint of slice?.endpoints ?? []) {
+      const conditions = endpoint?.conditions ?? {};
+      for (const ip of endpoint?.addresses ?? []) {
+        addresses.push({
+          ip,
+          // Unset ready means ready, per the EndpointSlice API
+          ready: conditions.ready !== false,
+          terminating: conditions.terminating === true,
+          targetKind: endpoint.targetRef?.kind ?? null,
+          targetName: endpoint.targetRef?.name ?? null,
+          nodeName: endpoint.nodeName ?? null,
+          zone: endpoint.zone ?? null,
+          ports,
+        });
+      }
+    }
+  }
+  return addresses;
+}
+
+// The core/v1 Endpoints object, for clusters without EndpointSlices
+function fromEndpoints(endpoints: any): EndpointAddress[] {
+  const addresses: EndpointAddress[] = [];
+  for (const subset of endpoints?.subsets ?? []) {
+    const ports = slicePorts(subset?.ports);
+    const add = (address: any, ready: boolean) =>
+      addresses.push({
+        ip: address?.ip ?? '',
+        ready,
+        terminating: false,
+        targetKind: address?.targetRef?.kind ?? null,
+        targetName: address?.targetRef?.name ?? null,
+        nodeName: address?.nodeName ?? null,
+        zone: null,
+        ports,
+      });
+    (subset?.addresses ?? []).forEach((address: any) => add(address, true));
+    (subset?.notReadyAddresses ?? []).forEach((address: any) => add(address, false));
+  }
+  return addresses;
+}
+
+async function readEndpoints(
+  name: string,
+  namespace: string,
+  context: string
+): Promise<{ source: ServiceEndpoints['source']; endpoints: EndpointAddress[] }> {
+  try {
+    const list = await getKubectlJson(
+      context,
+      ['get', 'endpointslices.discovery.k8s.io', '-l', `kubernetes.io/service-name=${name}`],
+      namespace
+    );
+    return { source: 'EndpointSlice', endpoints: fromSlices(list?.items ?? []) };
+  } catch (error) {
+    if (error instanceof KubeError && (error.code === 'Unreachable' || error.code === 'Unauthorized')) {
+      throw error;
+    }
+    console.warn(`[Service ${namespace}/${name}] EndpointSlices unavailable, reading Endpoints:`, error);
+  }
+
+  try {
+    const endpoints = await getKubectlJson(context, ['get', 'endpoints', name], namespace);
+    return { source: 'Endpoints', endpoints: fromEndpoints(endpoints) };
+  } catch (error) {
+    // Not created yet, e.g. for a selector-less Service nobody has filled in
+    if (error instanceof KubeError && error.code === 'NotFound') {
+      return { source: 'Endpoints', endpoints: [] };
+    }
+    throw error;
+  }
+}
+
+function describeProblem(service: any, endpoints: EndpointAddress[]): string | null {
+  const selector = service?.spec?.selector;
+  if (service?.spec?.type === 'ExternalName') {
+    return null;
+  }
+  if (endpoints.length === 0) {
+    return selector && Object.keys(selector).length > 0
+      ? 'No pods match the selector'
+      : 'The Service has no selector and nothing has added endpoints for it';
+  }
+  const notReady = endpoints.filter((endpoint) => !endpoint.ready).length;
+  return notReady === endpoints.length ? `None of the ${notReady} endpoint(s) are ready` : null;
+}
+
+/**
+ * A Service's cluster IP and ports together with the addresses behind it
+ * and whether each is ready, for "why does this Service have no
+ * endpoints". Reads EndpointSlices, falling back to Endpoints on older
+ * clusters. Headless Services have no cluster IP; ExternalName Services
+ * only report their external name. Throws NotFound for a missing Service.
+ */
+export async function getServiceEndpoints(
+  name: string,
+  namespace: string,
+  context: string
+): Promise<ServiceEndpoints> {
+  const service = await getKubectlJson(context, ['get', 'service', name], namespace);
+  const spec = service?.spec ?? {};
+  const type: string = spec.type ?? 'ClusterIP';
+
+  const ports: ServicePort[] = (spec.ports ?? []).map((port: any) => ({
+    name: port?.name || null,
+    protocol: port?.protocol ?? 'TCP',
+    port: port?.port,
+    targetPort: port?.targetPort ?? null,
+    nodePort: port?.nodePort ?? null,
+  }));
+
+  // ExternalName Services are a DNS alias with nothing behind them
+  const { source, endpoints } =
+    type === 'ExternalName'
+      ? { source: 'Endpoints' as const, endpoints: [] }
+      : await readEndpoints(name, namespace, context);
+  const ready = endpoints.filter((endpoint) => endpoint.ready).length;
+
+  return {
+    name,
+    namespace,
+    type,
+    clusterIP: spec.clusterIP && spec.clusterIP !== 'None' ? spec.clusterIP : null,
+    headless: spec.clusterIP === 'None',
+    externalName: spec.externalName ?? null,
+    selector: spec.selector ?? null,
+    ports,
+    source,
+    endpoints,
+    readyCount: ready,
+    notReadyCount: endpoints.length - ready,
+    problem: describeProblem(service, endpoints),
+  };
+}
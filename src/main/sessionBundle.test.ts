@@ -0,0 +1,81 @@
+import { userDataDir } from './testSupport';
+import assert from 'node:assert/strict';
+import { afterEach, describe, it, mock } from 'node:test';
+import { mkdirSync, mkdtempSync, readFileSync, writeFileSync } from 'fs';
+import os from 'os';
+import path from 'path';
+import { setKubeconfigPath } from './kube';
+import { REDACTED } from './redact';
+import { exportSessionBundle } from './sessionBundle';
+import { terminalManager } from './terminal';
+
+describe('exportSessionBundle', () => {
+  it('redacts terminal arguments and working directories like their env', async () => {
+    const info = {
+      id: 't1',
+      shell: 'kubectl',
+      args: ['exec', '-it', 'web-0', '--token', 'abc123', '--password=hunter2', '--', 'sh'],
+      cwd: path.join(os.homedir(), 'clients', 'acme'),
+      env: { TERM: 'xterm-256color', AWS_SECRET_ACCESS_KEY: 'xyz' },
+    };
+    (terminalManager as any).getTerminalIds = () => ['t1'];
+    (terminalManager as any).getTerminalInfo = () => info;
+    (terminalManager as any).getScrollback = () => '';
+
+    const outputPath = path.join(userDataDir, 'session-bundle.json');
+    await exportSessionBundle(outputPath);
+    const [terminal] = JSON.parse(readFileSync(outputPath, 'utf8')).terminals;
+    assert.deepEqual(
+      terminal.args,
+      ['exec', '-it', 'web-0', '--token', REDACTED, `--password=${REDACTED}`, '--', 'sh']
+    );
+    assert.equal(terminal.cwd, path.join('~', 'clients', 'acme'));
+    assert.deepEqual(terminal.env, { TERM: 'xterm-256color', AWS_SECRET_ACCESS_KEY: REDACTED });
+  });
+
+  describe('kubeconfig', () => {
+    const home = mkdtempSync(path.join(userDataDir, 'home-'));
+    const outputPath = path.join(userDataDir, 'kubeconfig-bundle.json');
+
+    const exportWithKubeconfig = async (kubeconfigPath: string) => {
+      mock.method(os, 'homedir', () => home);
+      setKubeconfigPath(kubeconfigPath);
+      (terminalManager as any).getTerminalIds = () => [];
+      await exportSessionBundle(outputPath);
+      return readFileSync(outputPath, 'utf8');
+    };
+
+    afterEach(() => {
+      mock.restoreAll();
+      setKubeconfigPath('');
+    });
+
+    it('keeps the home directory out of the kubeconfig path', async () => {
+      mkdirSync(path.join(home, '.kube'), { recursive: true });
+      const config = { 'current-context': 'dev', contexts: [{ name: 'dev', context: { cluster: 'dev' } }] };
+      writeFileSync(path.join(home, '.kube', 'config'), JSON.stringify(config));
+
+      const contents = await exportWithKubeconfig(path.join(home, '.kube', 'config'));
+      assert.deepEqual(JSON.parse(contents).kubeconfig, {
+        path: path.join('~', '.kube', 'config'),
+        currentContext: 'dev',
+        contexts: ['dev'],
+      });
+      assert.equal(contents.includes(home), false);
+    });
+
+    it('reports a kubeconfig that cannot be read, without the home directory', async () => {
+      writeFileSync(path.join(home, 'not-a-directory'), '');
+      const contents = await exportWithKubeconfig(path.join(home, 'not-a-directory', 'config'));
+      const { kubeconfig } = JSON.parse(contents);
+      assert.equal(kubeconfig.path, path.join('~', 'not-a-directory', 'config'));
+      assert.match(kubeconfig.error, /ENOTDIR/);
+      assert.equal(contents.includes(home), false);
+    });
+
+    it('leaves out a kubeconfig that does not exist', async () => {
+      const contents = await exportWithKubeconfig(path.join(home, 'missing', 'config'));
+      assert.equal(JSON.parse(contents).kubeconfig, null);
+    });
+  });
+});
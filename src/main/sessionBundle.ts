@@ -0,0 +1,91 @@
+import { access, constants, writeFile } from 'fs/promises';
+import path from 'path';
+import type { SessionBundleSummary } from '../common/terminalTypes';
+import { loadKubeConfig, resolveKubeconfigPath } from './kube';
+import { terminalManager } from './terminal';
+import { watchManager } from './watch';
+import { portForwardManager } from './portForwards';
+import { redactArgs, redactEnv, redactPath, redactSecrets } from './redact';
+
+const BUNDLE_VERSION = 1;
+// Only the tail of each terminal is useful for reproducing an issue
+const SCROLLBACK_TAIL_CHARS = 64 * 1024;
+const MAX_BUNDLE_BYTES = 16 * 1024 * 1024;
+
+async function collectKubeconfig() {
+  const kubeconfigPath = resolveKubeconfigPath();
+  try {
+    const summary = await loadKubeConfig();
+    return {
+      path: redactPath(summary.kubeconfigPath),
+      currentContext: summary.currentContext,
+      contexts: summary.contexts.map((ctx) => ctx.name),
+    };
+  } catch (error) {
+    // A missing kubeconfig shouldn't prevent exporting terminals
+    if ((error as NodeJS.ErrnoException)?.code === 'ENOENT') {
+      return null;
+    }
+    // One that can't be read or parsed is worth knowing about when reproducing an issue
+    const message = error instanceof Error ? error.message : String(error);
+    return {
+      path: redactPath(kubeconfigPath),
+      error: redactSecrets(message.split(kubeconfigPath).join(redactPath(kubeconfigPath))),
+    };
+  }
+}
+
+/**
+ * Write a sanitized JSON snapshot of the current session (terminals,
+ * background watches, forwards, kubeconfig context names) to `outputPath`
+ */
+export async function exportSessionBundle(outputPath: string): Promise<SessionBundleSummary> {
+  const resolvedPath = path.resolve(outputPath);
+  const directory = path.dirname(resolvedPath);
+
+  try {
+    await access(directory, constants.W_OK);
+  } catch {
+    throw new Error(`Cannot write session bundle: ${directory} is not writable`);
+  }
+
+  const terminals = terminalManager.getTerminalIds().flatMap((id) => {
+    const info = terminalManager.getTerminalInfo(id);
+    if (!info) {
+      return [];
+    }
+
+    return [
+      {
+        ...info,
+        args: redactArgs(info.args),
+        cwd: redactPath(info.cwd),
+        env: redactEnv(info.env),
+        scrollback: redactSecrets(terminalManager.getScrollback(id, SCROLLBACK_TAIL_CHARS)),
+      },
+    ];
+  });
+
+  const bundle = {
+    version: BUNDLE_VERSION,
+    createdAt: new Date().toISOString(),
+    kubeconfig: await collectKubeconfig(),
+    terminals,
+    watches: watchManager.getWatchIds(),
+    forwards: portForwardManager.listForwards(),
+  };
+
+  const contents = JSON.stringify(bundle, null, 2);
+  const bytes = Buffer.byteLength(contents, 'utf8');
+
+  if (bytes > MAX_BUNDLE_BYTES) {
+    throw new Error(
+      `Session bundle is too large (${bytes} bytes, limit ${MAX_BUNDLE_BYTES}). Close unused terminals and retry.`
+    );
+  }
+
+  await writeFile(resolvedPath, contents, 'utf8');
+  console.log(`[Session] Exported bundle with ${terminals.length} terminal(s) to ${resolvedPath}`);
+
+  return { path: resolvedPath, bytes, terminals: terminals.length };
+}
@@ -0,0 +1,78 @@
+import './testSupport';
+import assert from 'node:assert/strict';
+import { describe, it } from 'node:test';
+import { spawnSync } from 'child_process';
+import {
+  formatEnvExports,
+  parseShellProbe,
+  SHELL_PROBE_COMMAND,
+  SHELL_PROBE_MARKER,
+  shellFamilyFor,
+} from './shellExport';
+
+// What a terminal shows for the probe: the echoed command line, the reply, the next prompt
+function probeOutput(reply: string, prompt = '$ '): string {
+  return ` ${SHELL_PROBE_COMMAND}\r\n${reply}\r\n${prompt}`;
+}
+
+describe('parseShellProbe', () => {
+  it('passes the probe as a single argument', () => {
+    assert.match(SHELL_PROBE_COMMAND, /^echo "[^"]+"$/);
+  });
+
+  it('detects bash', () => {
+    const reply = `${SHELL_PROBE_MARKER} b=5.2.21(1)-release z= f= p=`;
+    assert.equal(parseShellProbe(probeOutput(reply)), 'bash');
+  });
+
+  it('detects zsh', () => {
+    assert.equal(parseShellProbe(probeOutput(`${SHELL_PROBE_MARKER} b= z=5.9 f= p=`, '% ')), 'zsh');
+  });
+
+  it('detects fish', () => {
+    assert.equal(parseShellProbe(probeOutput(`${SHELL_PROBE_MARKER} b= z= f=3.7.0 p=`, '> ')), 'fish');
+  });
+
+  it('detects PowerShell, which expands the version table inside the string', () => {
+    const reply = `${SHELL_PROBE_MARKER} b= z= f= p=System.Management.Automation.PSVersionHashTable`;
+    assert.equal(parseShellProbe(probeOutput(reply, 'PS /home/dev> ')), 'pwsh');
+  });
+
+  it('falls back to sh for other POSIX shells', () => {
+    assert.equal(parseShellProbe(probeOutput(`${SHELL_PROBE_MARKER} b= z= f= p=`)), 'sh');
+  });
+
+  it('waits for the reply while only the echoed command has arrived', () => {
+    assert.equal(parseShellProbe(` ${SHELL_PROBE_COMMAND}\r\n`), null);
+    assert.equal(parseShellProbe(` ${SHELL_PROBE_COMMAND}\r\n${SHELL_PROBE_MARKER} b=5.2`), null);
+  });
+});
+
+describe('formatEnvExports', () => {
+  it('uses each family\'s syntax', () => {
+    assert.equal(formatEnvExports('/bin/bash', { A: "it's" }), `export A='it'\\''s'`);
+    assert.equal(formatEnvExports('/usr/bin/fish', { A: 'x' }), `set -gx A 'x'`);
+    assert.equal(formatEnvExports('pwsh.exe', { A: null }), 'Remove-Item Env:A -ErrorAction SilentlyContinue');
+  });
+
+  it('quotes values so sh sets them verbatim, without running anything', () => {
+    const value = `it's "$HOME" \`id\` $(id); a\\b`;
+    const script = `${formatEnvExports('/bin/sh', { A: value })}; printf %s "$A"`;
+    assert.equal(spawnSync('sh', ['-c', script], { encoding: 'utf8' }).stdout, value);
+  });
+
+  it('escapes quotes and backslashes for fish and PowerShell', () => {
+    assert.equal(formatEnvExports('fish', { A: "a'b\\c" }), `set -gx A 'a\\'b\\\\c'`);
+    assert.equal(formatEnvExports('fish', { A: null }), 'set -e A');
+    assert.equal(formatEnvExports('pwsh', { A: "it's" }), `$env:A = 'it''s'`);
+  });
+
+  it('refuses names and values that could break out of the command', () => {
+    assert.throws(() => formatEnvExports('/bin/bash', { 'A;rm -rf ~': 'x' }), /Invalid environment variable name/);
+    assert.throws(() => formatEnvExports('/bin/bash', { A: 'x\ny' }), /control characters/);
+  });
+
+  it('looks through an env wrapper', () => {
+    assert.equal(shellFamilyFor('/usr/bin/env', ['-i', 'fish']), 'fish');
+  });
+});
@@ -0,0 +1,140 @@
+import path from 'path';
+import type { ShellFamily } from '../common/terminalTypes';
+
+export type ShellSyntax = 'posix' | 'fish' | 'powershell';
+
+const ENV_NAME_PATTERN = /^[A-Za-z_][A-Za-z0-9_]*$/;
+// Control characters could end the command line and start another one
+const CONTROL_CHARS = /[\x00-\x1f\x7f]/;
+
+function programName(file: string): string {
+  return path.basename(file).toLowerCase().replace(/\.exe$/, '');
+}
+
+/**
+ * The shell family a spawn command runs, looking through an `env`
+ * wrapper ("/usr/bin/env fish"). Anything unrecognised is "unknown" and
+ * treated as POSIX.
+ */
+export function shellFamilyFor(shell: string, args: string[] = []): ShellFamily {
+  let name = programName(shell);
+  if (name === 'env') {
+    const program = args.find((arg) => !arg.startsWith('-') && !arg.includes('='));
+    name = program ? programName(program) : name;
+  }
+
+  switch (name) {
+    case 'bash':
+    case 'zsh':
+    case 'fish':
+    case 'cmd':
+      return name;
+    case 'pwsh':
+    case 'powershell':
+      return 'pwsh';
+    case 'sh':
+    case 'dash':
+    case 'ash':
+    case 'ksh':
+    case 'mksh':
+      return 'sh';
+    default:
+      return 'unknown';
+  }
+}
+
+export function syntaxForFamily(family: ShellFamily): ShellSyntax {
+  if (family === 'fish') {
+    return 'fish';
+  }
+  if (family === 'pwsh') {
+    return 'powershell';
+  }
+  return 'posix';
+}
+
+export function shellSyntaxFor(shell: string): ShellSyntax {
+  return syntaxForFamily(shellFamilyFor(shell));
+}
+
+// Echoes each shell's version variable; valid syntax in POSIX shells, fish and PowerShell. One
+// double-quoted argument, since PowerShell's echo puts each argument on its own line
+export const SHELL_PROBE_MARKER = '__kubecli_shell__';
+export const SHELL_PROBE_COMMAND =
+  `echo "${SHELL_PROBE_MARKER} b=$BASH_VERSION z=$ZSH_VERSION f=$FISH_VERSION p=$PSVersionTable"`;
+const PROBE_REPLY_PATTERN = new RegExp(`${SHELL_PROBE_MARKER} b=(\\S*) z=(\\S*) f=(\\S*) p=(\\S*)`);
+
+/**
+ * The family named by the probe's reply in a terminal's output, or null
+ * if it hasn't fully arrived. The echoed command line itself is skipped: its
+ * values are still unexpanded "$NAME"s.
+ */
+export function parseShellProbe(output: string): ShellFamily | null {
+  // The last piece may be a line still arriving
+  for (const line of output.split(/\r?\n|\r/).slice(0, -1)) {
+    const match = PROBE_REPLY_PATTERN.exec(line);
+    if (!match || match.slice(1).some((value) => value.startsWith('$'))) {
+      continue;
+    }
+    const [, bash, zsh, fish, powershell] = match;
+    if (bash) {
+      return 'bash';
+    }
+    if (zsh) {
+      return 'zsh';
+    }
+    if (fish) {
+      return 'fish';
+    }
+    return powershell ? 'pwsh' : 'sh';
+  }
+  return null;
+}
+
+function quote(value: string, syntax: ShellSyntax): string {
+  switch (syntax) {
+    case 'fish':
+      return `'${value.replace(/[\\']/g, (char) => `\\${char}`)}'`;
+    case 'powershell':
+      return `'${value.replace(/'/g, "''")}'`;
+    default:
+      return `'${value.replace(/'/g, `'\\''`)}'`;
+  }
+}
+
+function formatOne(name: string, value: string | null, syntax: ShellSyntax): string {
+  if (syntax === 'fish') {
+    return value === null ? `set -e ${name}` : `set -gx ${name} ${quote(value, syntax)}`;
+  }
+  if (syntax === 'powershell') {
+    return value === null
+      ? `Remove-Item Env:${name} -ErrorAction SilentlyContinue`
+      : `$env:${name} = ${quote(value, syntax)}`;
+  }
+  return value === null ? `unset ${name}` : `export ${name}=${quote(value, syntax)}`;
+}
+
+/**
+ * Build one command line that sets (or, for null values, unsets) the given
+ * variables in a running shell. Names are validated and values are quoted
+ * so they can't inject additional commands.
+ */
+export function formatEnvExports(
+  shell: string,
+  vars: Record<string, string | null>,
+  syntax: ShellSyntax = shellSyntaxFor(shell)
+): string {
+  const commands: string[] = [];
+
+  for (const [name, value] of Object.entries(vars)) {
+    if (!ENV_NAME_PATTERN.test(name)) {
+      throw new Error(`Invalid environment variable name: ${name}`);
+    }
+    if (value !== null && CONTROL_CHARS.test(value)) {
+      throw new Error(`Value for ${name} contains control characters`);
+    }
+    commands.push(formatOne(name, value, syntax));
+  }
+
+  return commands.join('; ');
+}
@@ -0,0 +1,49 @@
+import type { StorageClassInfo, StorageClassList } from '../common/kubeTypes';
+import { getKubectlJson } from './kube';
+
+const DEFAULT_CLASS_ANNOTATIONS = [
+  'storageclass.kubernetes.io/is-default-class',
+  'storageclass.beta.kubernetes.io/is-default-class',
+];
+
+function isDefaultClass(storageClass: any): boolean {
+  const annotations: Record<string, string> = storageClass?.metadata?.annotations ?? {};
+  return DEFAULT_CLASS_ANNOTATIONS.some((key) => annotations[key] === 'true');
+}
+
+function toStorageClass(storageClass: any): StorageClassInfo {
+  return {
+    name: storageClass?.metadata?.name ?? '',
+    provisioner: storageClass?.provisioner ?? '',
+    // The API server fills these in; older objects may predate the defaults
+    reclaimPolicy: storageClass?.reclaimPolicy ?? 'Delete',
+    volumeBindingMode: storageClass?.volumeBindingMode ?? 'Immediate',
+    allowVolumeExpansion: storageClass?.allowVolumeExpansion === true,
+    isDefault: isDefaultClass(storageClass),
+    parameters: storageClass?.parameters ?? {},
+    createdAt: storageClass?.metadata?.creationTimestamp ?? null,
+  };
+}
+
+/**
+ * The cluster's StorageClasses and which one a PVC without
+ * storageClassName gets. With none marked default, such a PVC stays
+ * Pending unless a PersistentVolume matches it. With several, the newest
+ * one is used (Kubernetes 1.26 and later; older API servers reject the
+ * claim), and `multipleDefaults` is set.
+ */
+export async function listStorageClasses(context: string): Promise<StorageClassList> {
+  const list = await getKubectlJson(context, ['get', 'storageclasses']);
+  const classes: StorageClassInfo[] = (list?.items ?? [])
+    .map(toStorageClass)
+    .sort((a: StorageClassInfo, b: StorageClassInfo) => a.name.localeCompare(b.name));
+
+  const defaults = classes
+    .filter((storageClass) => storageClass.isDefault)
+    .sort((a, b) => (Date.parse(b.createdAt ?? '') || 0) - (Date.parse(a.createdAt ?? '') || 0));
+  return {
+    classes,
+    defaultClass: defaults[0]?.name ?? null,
+    multipleDefaults: defaults.length > 1,
+  };
+}
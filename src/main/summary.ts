@@ -0,0 +1,59 @@
+import type { ResourceSummary } from '../common/kubeTypes';
+import { runKubectl } from './kube';
+
+// Each count gives up on its own so one slow kind doesn't hold the rest
+const COUNT_TIMEOUT = '10s';
+
+async function listColumn(context: string, kind: string, column: string): Promise<string[] | null> {
+  try {
+    const result = await runKubectl(context, [
+      'get',
+      kind,
+      '--all-namespaces',
+      '--no-headers',
+      `--request-timeout=${COUNT_TIMEOUT}`,
+      '-o',
+      `custom-columns=VALUE:${column}`,
+    ]);
+    if (result.code !== 0) {
+      console.warn(`[Summary] Could not count ${kind}: ${result.stderr.trim()}`);
+      return null;
+    }
+    return result.stdout.split('\n').filter((line) => line.trim().length > 0).map((line) => line.trim());
+  } catch (error) {
+    console.warn(`[Summary] Could not count ${kind}:`, error);
+    return null;
+  }
+}
+
+/**
+ * Count pods (by phase), deployments, services and ready nodes in parallel.
+ * Only one column per object is fetched, not the full objects.
+ */
+export async function getResourceSummary(context: string): Promise<ResourceSummary> {
+  const [podPhases, deployments, services, nodeReady] = await Promise.all([
+    listColumn(context, 'pods', '.status.phase'),
+    listColumn(context, 'deployments', '.metadata.name'),
+    listColumn(context, 'services', '.metadata.name'),
+    listColumn(context, 'nodes', '.status.conditions[?(@.type=="Ready")].status'),
+  ]);
+
+  let pods: ResourceSummary['pods'] = null;
+  if (podPhases) {
+    const byPhase: Record<string, number> = {};
+    for (const phase of podPhases) {
+      byPhase[phase] = (byPhase[phase] ?? 0) + 1;
+    }
+    pods = { total: podPhases.length, byPhase };
+  }
+
+  return {
+    context,
+    pods,
+    deployments: deployments ? deployments.length : null,
+    services: services ? services.length : null,
+    nodes: nodeReady
+      ? { ready: nodeReady.filter((status) => status === 'True').length, total: nodeReady.length }
+      : null,
+  };
+}
@@ -0,0 +1,28 @@
+/**
+ * Parse kubectl's column-aligned table output using the header's column
+ * offsets, so empty cells (e.g. missing SHORTNAMES) don't shift columns
+ */
+export function parseKubectlTable(output: string): Array<Record<string, string>> {
+  const lines = output.split('\n').filter((line) => line.trim().length > 0);
+  if (lines.length === 0) {
+    return [];
+  }
+
+  const header = lines[0];
+  const columns: Array<{ name: string; start: number }> = [];
+  const headerPattern = /\S+/g;
+  let match: RegExpExecArray | null;
+
+  while ((match = headerPattern.exec(header)) !== null) {
+    columns.push({ name: match[0], start: match.index });
+  }
+
+  return lines.slice(1).map((line) => {
+    const row: Record<string, string> = {};
+    columns.forEach((column, index) => {
+      const end = index + 1 < columns.length ? columns[index + 1].start : undefined;
+      row[column.name] = line.slice(column.start, end).trim();
+    });
+    return row;
+  });
+}
@@ -0,0 +1,120 @@
+import './testSupport';
+import assert from 'node:assert/strict';
+import { beforeEach, describe, it } from 'node:test';
+import YAML from 'yaml';
+import * as kube from './kube';
+import { applyTemplate, renderTemplate } from './templates';
+
+const DEBUG_POD = [
+  'apiVersion: v1',
+  'kind: Pod',
+  'metadata:',
+  '  name: debug-${USER}',
+  'spec:',
+  '  containers:',
+  '    - name: debug',
+  '      image: ${IMAGE}',
+  '',
+].join('\n');
+
+describe('renderTemplate', () => {
+  it('fills placeholders inside strings', () => {
+    const rendered = YAML.parse(renderTemplate(DEBUG_POD, { USER: 'ana', IMAGE: 'busybox:1.36' }));
+    assert.equal(rendered.metadata.name, 'debug-ana');
+    assert.equal(rendered.spec.containers[0].image, 'busybox:1.36');
+  });
+
+  it('keeps the type of a number or boolean filling a whole unquoted value', () => {
+    const template = 'replicas: ${REPLICAS}\npaused: ${PAUSED}\nversion: "${REPLICAS}"\nname: ${REPLICAS}x\n';
+    const rendered = YAML.parse(renderTemplate(template, { REPLICAS: '3', PAUSED: 'false' }));
+    assert.deepEqual(rendered, { replicas: 3, paused: false, version: '3', name: '3x' });
+  });
+
+  it('lists every placeholder without a value', () => {
+    assert.throws(
+      () => renderTemplate(`${DEBUG_POD}  labels: { tag: '\${TAG}' }\n`, { USER: 'ana' }),
+      /No value for \$\{IMAGE\}, \$\{TAG\}/
+    );
+  });
+
+  it('keeps values from adding YAML structure', () => {
+    const injections = [
+      'busybox\nkind: Secret',
+      'busybox\n      command: [sh, -c, "cat /var/run/secrets/token"]',
+      '{ privileged: true }',
+      '*alias',
+      'busybox" ]\nkind: "Secret',
+      '# comment',
+    ];
+    for (const image of injections) {
+      const rendered = YAML.parse(renderTemplate(DEBUG_POD, { USER: 'ana', IMAGE: image }));
+      assert.equal(rendered.kind, 'Pod');
+      assert.deepEqual(rendered.spec.containers, [{ name: 'debug', image }]);
+    }
+  });
+
+  it('keeps other values YAML would read as numbers strings', () => {
+    const rendered = YAML.parse(renderTemplate('name: ${NAME}\n', { NAME: '1e3' }));
+    assert.equal(rendered.name, '1e3');
+  });
+
+  it('leaves $${NAME} as a literal placeholder', () => {
+    const rendered = YAML.parse(renderTemplate('command: echo $${HOME} ${MESSAGE}\n', { MESSAGE: 'hi' }));
+    assert.equal(rendered.command, 'echo ${HOME} hi');
+  });
+
+  it('renders every document of a multi-document template', () => {
+    const template = 'kind: ConfigMap\nmetadata:\n  name: ${NAME}\n---\nkind: Service\nmetadata:\n  name: ${NAME}\n';
+    const documents = YAML.parseAllDocuments(renderTemplate(template, { NAME: 'web' })).map((doc) => doc.toJS());
+    assert.deepEqual(
+      documents.map((doc) => [doc.kind, doc.metadata.name]),
+      [['ConfigMap', 'web'], ['Service', 'web']]
+    );
+  });
+
+  it('refuses invalid names and templates', () => {
+    assert.throws(() => renderTemplate(DEBUG_POD, { 'IMAGE-TAG': 'x' }), /Invalid variable name/);
+    assert.throws(() => renderTemplate('image: ${1IMAGE}\n', {}), /Invalid placeholder/);
+    assert.throws(() => renderTemplate('image: [unclosed\n', {}), /not valid YAML/);
+  });
+});
+
+describe('applyTemplate', () => {
+  let calls: { args: string[]; namespace?: string; options: any }[];
+
+  beforeEach(() => {
+    calls = [];
+    (kube as any).runKubectl = async (_context: string, args: string[], namespace: string, options: any) => {
+      calls.push({ args, namespace, options });
+      return {
+        code: 0,
+        stdout: 'pod/debug-ana created (server dry run)\n',
+        stderr: 'Warning: would violate PodSecurity "restricted:latest": allowPrivilegeEscalation != false\n',
+      };
+    };
+  });
+
+  it('applies the rendered manifest through stdin and returns it on a dry run', async () => {
+    const spec = { template: DEBUG_POD, variables: { USER: 'ana', IMAGE: 'busybox:1.36' }, context: 'dev' };
+    const result = await applyTemplate({ ...spec, namespace: 'shop', dryRun: true });
+    assert.deepEqual(calls[0].args, ['apply', '-f', '-', '--dry-run=server']);
+    assert.equal(calls[0].namespace, 'shop');
+    assert.equal(calls[0].options.stdin, result.rendered);
+    assert.equal(YAML.parse(result.rendered ?? '').metadata.name, 'debug-ana');
+    assert.deepEqual(result.applied, ['pod/debug-ana created (server dry run)']);
+    assert.deepEqual(result.warnings, [
+      'would violate PodSecurity "restricted:latest": allowPrivilegeEscalation != false',
+    ]);
+  });
+
+  it('does not return the manifest when applying for real, or run kubectl for a bad template', async () => {
+    const variables = { USER: 'ana', IMAGE: 'busybox:1.36' };
+    const result = await applyTemplate({ template: DEBUG_POD, variables, context: 'dev' });
+    assert.deepEqual([result.dryRun, result.rendered], [false, null]);
+    assert.equal(calls[0].args.includes('--dry-run=server'), false);
+
+    const missingImage = { template: DEBUG_POD, variables: { USER: 'ana' }, context: 'dev' };
+    await assert.rejects(applyTemplate(missingImage), /No value/);
+    assert.equal(calls.length, 1);
+  });
+});
@@ -0,0 +1,115 @@
+import YAML, { type Scalar } from 'yaml';
+import type { TemplateApplyResult } from '../common/kubeTypes';
+import { runKubectl, kubectlFailure } from './kube';
+
+export interface TemplateApplySpec {
+  template: string;
+  variables: Record<string, string>;
+  context: string;
+  namespace?: string;
+  dryRun?: boolean;
+  view?: string;
+}
+
+const VARIABLE_NAME_PATTERN = /^[A-Za-z_][A-Za-z0-9_]*$/;
+// ${NAME}, or $${NAME} for a literal "${NAME}"
+const PLACEHOLDER_PATTERN = /\$(\$?)\{([^}]*)\}/g;
+const WHOLE_PLACEHOLDER_PATTERN = /^\$\{([A-Za-z_][A-Za-z0-9_]*)\}$/;
+// Values that may keep their YAML type when they are a whole unquoted scalar
+const TYPED_VALUE_PATTERN = /^(?:-?\d+(?:\.\d+)?|true|false)$/;
+
+function substitute(text: string, variables: Record<string, string>, unresolved: Set<string>): string {
+  return text.replace(PLACEHOLDER_PATTERN, (placeholder, escaped: string, name: string) => {
+    if (escaped) {
+      return placeholder.slice(1);
+    }
+    if (!VARIABLE_NAME_PATTERN.test(name)) {
+      throw new Error(`Invalid placeholder "${placeholder}"`);
+    }
+    if (!Object.prototype.hasOwnProperty.call(variables, name)) {
+      unresolved.add(name);
+      return placeholder;
+    }
+    return variables[name];
+  });
+}
+
+/**
+ * Fill `${NAME}` placeholders in a manifest template. The template is parsed
+ * first and only string scalars are substituted, so a value can't inject
+ * YAML structure (a value of "x\nkind: Secret" stays one string). A whole
+ * unquoted placeholder such as `replicas: ${REPLICAS}` keeps a numeric or
+ * boolean value's type. Throws listing every placeholder without a value.
+ */
+export function renderTemplate(template: string, variables: Record<string, string>): string {
+  for (const name of Object.keys(variables)) {
+    if (!VARIABLE_NAME_PATTERN.test(name)) {
+      throw new Error(`Invalid variable name "${name}": use letters, digits and "_"`);
+    }
+  }
+
+  const unresolved: Set<string> = new Set();
+  const documents = YAML.parseAllDocuments(template);
+
+  documents.forEach((document, index) => {
+    if (document.errors.length > 0) {
+      throw new Error(`Document ${index + 1} is not valid YAML: ${document.errors[0].message}`);
+    }
+
+    YAML.visit(document, {
+      Scalar(_key, node: Scalar) {
+        if (typeof node.value !== 'string' || !node.value.includes('${')) {
+          return;
+        }
+
+        const whole = node.type === 'PLAIN' ? node.value.match(WHOLE_PLACEHOLDER_PATTERN) : null;
+        const value = substitute(node.value, variables, unresolved);
+        if (whole && TYPED_VALUE_PATTERN.test(value)) {
+          node.value = YAML.parse(value);
+        } else {
+          node.value = value;
+        }
+      },
+    });
+  });
+
+  if (unresolved.size > 0) {
+    throw new Error(`No value for ${Array.from(unresolved).map((name) => `\${${name}}`).join(', ')}`);
+  }
+
+  return documents.map((document) => document.toString()).join('');
+}
+
+/**
+ * Render a template and `kubectl apply` it, for parameterized manifests such
+ * as "debug pod with this image". A dry run goes through the server
+ * (admission included) and returns the rendered manifest for review.
+ */
+export async function applyTemplate(spec: TemplateApplySpec): Promise<TemplateApplyResult> {
+  const rendered = renderTemplate(spec.template, spec.variables ?? {});
+  if (!rendered.trim()) {
+    throw new Error('Template is empty');
+  }
+
+  const args = ['apply', '-f', '-'];
+  if (spec.dryRun) {
+    args.push('--dry-run=server');
+  }
+  const result = await runKubectl(spec.context, args, spec.namespace, { view: spec.view, stdin: rendered });
+  if (result.code !== 0) {
+    throw kubectlFailure(result, 'Failed to apply template');
+  }
+
+  return {
+    dryRun: spec.dryRun === true,
+    applied: result.stdout
+      .split('\n')
+      .map((line) => line.trim())
+      .filter((line) => line.length > 0),
+    warnings: result.stderr
+      .split('\n')
+      .filter((line) => /^Warning:/i.test(line.trim()))
+      .map((line) => line.trim().replace(/^Warning:\s*/i, '')),
+    rendered: spec.dryRun ? rendered : null,
+  };
+}
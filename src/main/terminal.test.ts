@@ -0,0 +1,852 @@
+import { processPty, ttyPty, ttyPtyAvailable, userDataDir } from './testSupport';
+import assert from 'node:assert/strict';
+import { afterEach, beforeEach, describe, it, mock } from 'node:test';
+import { spawn, spawnSync } from 'child_process';
+import { mkdirSync, writeFileSync } from 'fs';
+import path from 'path';
+import { getAppConfig } from './appConfig';
+import { setKubeconfigPath } from './kube';
+import { ScrollbackBuffer } from './scrollback';
+import { SHELL_PROBE_MARKER } from './shellExport';
+import { TerminalManager, TerminalSpawnError } from './terminal';
+
+type Reply = (input: string, handle: any) => void;
+
+/** A PTY without a process; the test emits its output and exit */
+class FakePty {
+  killed = false;
+  readonly signals: string[] = [];
+  private dataListeners: ((chunk: string) => void)[] = [];
+  private exitListeners: ((event: { exitCode: number; signal?: number }) => void)[] = [];
+
+  constructor(readonly pid: number) {}
+
+  onData(listener: (chunk: string) => void) {
+    this.dataListeners.push(listener);
+    return { dispose: () => (this.dataListeners = this.dataListeners.filter((other) => other !== listener)) };
+  }
+
+  onExit(listener: (event: { exitCode: number; signal?: number }) => void) {
+    this.exitListeners.push(listener);
+  }
+
+  emitData(chunk: string): void {
+    this.dataListeners.forEach((listener) => listener(chunk));
+  }
+
+  emitExit(exitCode: number): void {
+    this.exitListeners.forEach((listener) => listener({ exitCode }));
+  }
+
+  write(): void {}
+
+  resize(): void {}
+
+  kill(signal?: string): void {
+    this.killed = true;
+    this.signals.push(signal ?? 'SIGHUP');
+  }
+}
+
+
+/**
+ * A shell terminal without a process: writes are recorded and `reply` can
+ * answer them the way the shell would
+ */
+function addFakeShell(manager: TerminalManager, overrides: Record<string, unknown> = {}, reply?: Reply) {
+  const written: string[] = [];
+  const handle: any = {
+    pty: {
+      pid: 1,
+      write: (data: string) => {
+        written.push(data);
+        if (reply) {
+          setImmediate(() => reply(data, handle));
+        }
+      },
+    },
+    isShell: true,
+    altScreen: false,
+    awaitingRespawn: false,
+    shellFamily: { family: 'bash', source: 'command' },
+    shellProbe: null,
+    latencyProbe: null,
+    commandCapture: null,
+    bytesWritten: 0,
+    ...overrides,
+  };
+  (manager as any).terminals.set('t1', handle);
+  return { handle, written };
+}
+
+describe('measureTerminalLatency', () => {
+  let manager: TerminalManager;
+  let atPrompt: boolean | null;
+
+  // Answers only the printf probe, as a shell with echo off would
+  const printfReply: Reply = (input, handle) => {
+    const token = input.match(/^ printf '__kl_%s__\\n' (\w+)\r$/)?.[1];
+    if (token) {
+      (manager as any).checkLatencyProbe(handle, `__kl_${token}__\r\n`);
+    }
+  };
+
+  beforeEach(() => {
+    manager = new TerminalManager();
+    atPrompt = true;
+    (manager as any).isAtShellPrompt = () => atPrompt;
+  });
+
+  it('does not type a printf into an unconfirmed shell', async () => {
+    const { written } = addFakeShell(manager, {}, printfReply);
+    const result = await manager.measureTerminalLatency('t1', undefined, 100);
+    assert.deepEqual([result.method, result.roundTripMs], ['echo', null]);
+    assert.ok(written.every((input) => !input.includes('printf')));
+  });
+
+  it('does not type a printf while another program owns the terminal', async () => {
+    const { written } = addFakeShell(manager, { shellFamily: { family: 'bash', source: 'probe' } }, printfReply);
+    atPrompt = false;
+    const result = await manager.measureTerminalLatency('t1', undefined, 100);
+    assert.equal(result.roundTripMs, null);
+    assert.ok(written.every((input) => !input.includes('printf')));
+  });
+
+  it('falls back to printf at a confirmed shell prompt', async () => {
+    addFakeShell(manager, { shellFamily: { family: 'bash', source: 'probe' } }, printfReply);
+    const result = await manager.measureTerminalLatency('t1', undefined, 100);
+    assert.equal(result.method, 'printf');
+    assert.equal(typeof result.roundTripMs, 'number');
+  });
+
+  it('uses printf straight away when asked to', async () => {
+    addFakeShell(manager, {}, printfReply);
+    const result = await manager.measureTerminalLatency('t1', 'printf', 100);
+    assert.equal(result.method, 'printf');
+    assert.equal(typeof result.roundTripMs, 'number');
+  });
+});
+
+describe('detectTerminalShell', () => {
+  let manager: TerminalManager;
+
+  beforeEach(() => {
+    manager = new TerminalManager();
+  });
+
+  it('confirms the family from the probe\'s reply', async () => {
+    const { handle } = addFakeShell(manager, {}, (input, target) => {
+      if (input.includes(SHELL_PROBE_MARKER)) {
+        const reply = `${SHELL_PROBE_MARKER} b= z= f= p=System.Management.Automation.PSVersionHashTable`;
+        (manager as any).checkShellProbe(target, `${input}\n${reply}\r\nPS> `);
+      }
+    });
+    assert.deepEqual(await manager.detectTerminalShell('t1', true), { family: 'pwsh', source: 'probe' });
+    assert.equal(handle.shellProbe, null);
+  });
+
+  it('refuses to probe while a latency probe or sequenced commands are reading the output', async () => {
+    const { handle, written } = addFakeShell(manager);
+    handle.latencyProbe = { marker: 'x', output: '', resolve: () => undefined };
+    await assert.rejects(manager.detectTerminalShell('t1', true), /already running/);
+    handle.latencyProbe = null;
+    handle.commandCapture = { output: '', truncated: false, onOutput: () => undefined };
+    await assert.rejects(manager.detectTerminalShell('t1', true), /already running/);
+    assert.deepEqual(written, []);
+  });
+});
+
+describe('reconcileTerminals', () => {
+  let manager: TerminalManager;
+  let exitListener: ((event: { exitCode: number; signal?: number }) => void) | null;
+  let disposed: boolean;
+
+  // A terminal whose process has exited but whose onExit hasn't run yet
+  function addExitedTerminal() {
+    const handle: any = {
+      pty: {
+        pid: spawnSync('true').pid,
+        onData: () => ({ dispose: () => (disposed = true) }),
+        onExit: (listener: typeof exitListener) => (exitListener = listener),
+      },
+      awaitingRespawn: false,
+      goneSince: null,
+      scrollback: { readTail: () => 'last words\r\n' },
+    };
+    (manager as any).terminals.set('t1', handle);
+    (manager as any).listenToPty('t1', handle);
+    (manager as any).readers.set(handle, 't1');
+    return handle;
+  }
+
+  beforeEach(() => {
+    manager = new TerminalManager();
+    exitListener = null;
+    disposed = false;
+  });
+
+  it('leaves a process that has just gone to its own exit handler', () => {
+    addExitedTerminal();
+    const report = manager.reconcileTerminals();
+    assert.deepEqual(report.reaped, []);
+    assert.equal(report.pendingExits, 1);
+    assert.equal(disposed, false);
+
+    exitListener?.({ exitCode: 3 });
+    assert.equal(manager.getLastOutput('t1').exitCode, 3);
+    assert.equal(manager.getLastOutput('t1').output, 'last words\r\n');
+    assert.equal(manager.reconcileTerminals().checked, 0);
+  });
+
+  it('reaps a terminal whose exit never arrived once the grace period is over', () => {
+    const handle = addExitedTerminal();
+    manager.reconcileTerminals();
+    handle.goneSince -= 60 * 1000;
+
+    const report = manager.reconcileTerminals();
+    assert.deepEqual(report.reaped, [{ id: 't1', pid: handle.pty.pid }]);
+    assert.equal(disposed, true);
+    assert.equal(manager.getLastOutput('t1').exitCode, -1);
+
+    // A late exit is not reported a second time
+    exitListener?.({ exitCode: 3 });
+    assert.equal(manager.getLastOutput('t1').exitCode, -1);
+  });
+
+  it('leaves running processes alone', () => {
+    const handle: any = { pty: { pid: process.pid }, awaitingRespawn: false, goneSince: null };
+    (manager as any).terminals.set('t1', handle);
+    const report = manager.reconcileTerminals();
+    assert.deepEqual([report.reaped.length, report.pendingExits], [0, 0]);
+  });
+});
+
+describe('runCommandsSequenced', () => {
+  let manager: TerminalManager;
+
+  // Runs what was typed with a real sh, answering with its echo and output
+  const shReply: Reply = (input, handle) => {
+    const script = input.replace(/\r/g, '\n');
+    const result = spawnSync('sh', ['-c', script], { encoding: 'utf8' });
+    const echo = input.replace(/\r/g, '\r\n');
+    (manager as any).checkCommandCapture(handle, `${echo}${result.stdout.replace(/\n/g, '\r\n')}`);
+  };
+
+  beforeEach(() => {
+    manager = new TerminalManager();
+  });
+
+  it('keeps the end marker out of reach of a trailing comment', async () => {
+    const { written } = addFakeShell(manager, {}, shReply);
+    const results = await manager.runCommandsSequenced('t1', ['echo one # say one', 'echo two; false'], 2000);
+    assert.deepEqual(
+      results.map((result) => [result.output, result.exitCode, result.timedOut]),
+      [
+        ['one\n', 0, false],
+        ['two\n', 1, false],
+      ]
+    );
+    for (const line of written) {
+      const commandLine = line.split('\r').find((part) => part.includes('#'));
+      assert.ok(!commandLine?.includes('__kle_'));
+    }
+  });
+
+  it('refuses a command that would continue onto the next line', async () => {
+    addFakeShell(manager, {}, shReply);
+    await assert.rejects(manager.runCommandsSequenced('t1', ['echo a \\']), /single non-empty line/);
+  });
+});
+
+describe('interruptTerminal', () => {
+  let manager: TerminalManager;
+
+  beforeEach(() => {
+    manager = new TerminalManager();
+  });
+
+  it('types ^C into a kubectl process terminal', async () => {
+    const { written } = addFakeShell(manager, { isShell: false });
+    const result = await manager.interruptTerminal('t1');
+    assert.deepEqual(result, { interrupted: true, method: 'control-char', processGroup: null });
+    assert.deepEqual(written, ['\x03']);
+  });
+
+  it('leaves a shell sitting at its prompt alone', async () => {
+    const { written } = addFakeShell(manager);
+    (manager as any).getForegroundProcessGroup = async () => 1;
+    assert.equal((await manager.interruptTerminal('t1')).interrupted, false);
+    assert.deepEqual(written, []);
+  });
+
+  it('signals the foreground process group', { skip: process.platform === 'win32' }, async () => {
+    const job = spawn('sleep', ['5'], { detached: true, stdio: 'ignore' });
+    const exited = new Promise((resolve) => job.once('exit', (_code, signal) => resolve(signal)));
+    const { written } = addFakeShell(manager);
+    (manager as any).getForegroundProcessGroup = async () => job.pid;
+
+    const result = await manager.interruptTerminal('t1');
+    assert.deepEqual(result, { interrupted: true, method: 'signal', processGroup: job.pid });
+    assert.equal(await exited, 'SIGINT');
+    assert.deepEqual(written, []);
+  });
+});
+
+describe('exportToTerminal', () => {
+  let manager: TerminalManager;
+  let atPrompt: boolean | null;
+
+  beforeEach(() => {
+    manager = new TerminalManager();
+    atPrompt = true;
+    (manager as any).isAtShellPrompt = () => atPrompt;
+  });
+
+  it('types the exports out of history and tracks the shell\'s env', () => {
+    const { handle, written } = addFakeShell(manager, { shell: '/bin/bash', env: { OLD: '1' } });
+    manager.exportToTerminal('t1', { KUBECONFIG: '/tmp/dev', OLD: null });
+    assert.equal(written.length, 1);
+    assert.match(written[0], /^ export KUBECONFIG='\/tmp\/dev'.*unset OLD\r$/);
+    assert.deepEqual(handle.env, { KUBECONFIG: '/tmp/dev' });
+  });
+
+  it('refuses terminals that are not running a shell', () => {
+    const { written } = addFakeShell(manager, { isShell: false, shell: 'kubectl', env: {} });
+    assert.throws(() => manager.exportToTerminal('t1', { A: '1' }), /not running a shell/);
+    assert.deepEqual(written, []);
+  });
+
+  it('refuses while a full-screen program or another command owns the terminal', () => {
+    const { handle, written } = addFakeShell(manager, { shell: '/bin/bash', env: {} });
+    handle.altScreen = true;
+    assert.throws(() => manager.exportToTerminal('t1', { A: '1' }), /full-screen program/);
+    handle.altScreen = false;
+    atPrompt = false;
+    assert.throws(() => manager.exportToTerminal('t1', { A: '1' }), /command is running/);
+    assert.deepEqual([written, handle.env], [[], {}]);
+  });
+});
+
+describe('real shells on a PTY', { skip: !ttyPtyAvailable }, () => {
+  const nodePty = require('node-pty');
+  const { spawn: spawnPty } = nodePty;
+  let manager: TerminalManager;
+
+  const output = () => manager.exportTerminalText('t1', true).text;
+
+  const waitFor = async (done: () => boolean) => {
+    for (let turn = 0; turn < 1000 && !done(); turn++) {
+      await new Promise((resolve) => setTimeout(resolve, 5));
+    }
+  };
+
+  const startShell = async (shell: string, args: string[]) => {
+    await manager.createTerminal('t1', { shell, args });
+    await waitFor(() => /[$#>] $/.test(output()));
+  };
+
+  beforeEach(() => {
+    nodePty.spawn = ttyPty;
+    manager = new TerminalManager();
+  });
+
+  afterEach(() => {
+    if (manager.hasTerminal('t1')) {
+      manager.closeTerminal('t1');
+    }
+    nodePty.spawn = spawnPty;
+  });
+
+  const exportsInto = (shell: string, args: string[]) => async () => {
+    await startShell(shell, args);
+    manager.exportToTerminal('t1', { KL_EXPORTED: "it's exported" });
+    manager.writeToTerminal('t1', 'echo "$KL_EXPORTED"\r');
+    await waitFor(() => /^it's exported$/m.test(output()));
+    assert.match(output(), /^it's exported$/m);
+  };
+
+  it('exports a variable into bash', exportsInto('bash', ['--norc', '--noprofile']));
+
+  it('refuses to export while a command runs', async () => {
+    await startShell('bash', ['--norc', '--noprofile']);
+    manager.writeToTerminal('t1', 'sleep 30\r');
+    const handle = (manager as any).terminals.get('t1');
+    await waitFor(() => (manager as any).isAtShellPrompt(handle) === false);
+    assert.throws(() => manager.exportToTerminal('t1', { KL_EXPORTED: '1' }), /command is running/);
+  });
+
+  it('interrupts the running command and leaves the shell usable', async () => {
+    await startShell('bash', ['--norc', '--noprofile']);
+    manager.writeToTerminal('t1', 'sleep 30\r');
+    const handle = (manager as any).terminals.get('t1');
+    await waitFor(() => (manager as any).isAtShellPrompt(handle) === false);
+
+    const result = await manager.interruptTerminal('t1');
+    assert.deepEqual([result.interrupted, result.method], [true, 'signal']);
+    manager.writeToTerminal('t1', 'echo ok-after-interrupt\r');
+    await waitFor(() => /^ok-after-interrupt$/m.test(output()));
+    assert.match(output(), /^ok-after-interrupt$/m);
+    // Throws if the shell went down with the command
+    process.kill(handle.pty.pid, 0);
+  });
+
+  const hasFish = spawnSync('fish', ['--version'], { stdio: 'ignore' }).status === 0;
+  it('exports a variable into fish', { skip: !hasFish }, exportsInto('fish', ['--no-config']));
+});
+
+describe('context kubectl in terminals', () => {
+  const withContextKubectl = (context: string) =>
+    (new TerminalManager() as any).withContextKubectl({ context, env: { PATH: '/usr/bin' } });
+
+  afterEach(() => {
+    getAppConfig().kubectlPaths = {};
+  });
+
+  it('puts the context\'s pinned kubectl first on PATH', () => {
+    getAppConfig().kubectlPaths = { old: '/opt/kubectl-1.24/kubectl' };
+    assert.ok(withContextKubectl('old').PATH.startsWith(`/opt/kubectl-1.24${path.delimiter}`));
+  });
+
+  it('refuses a pinned binary the shell could not run as kubectl', () => {
+    getAppConfig().kubectlPaths = { old: '/opt/bin/kubectl-1.24' };
+    assert.throws(() => withContextKubectl('old'), { code: 'KubectlMismatch' });
+  });
+});
+
+describe('PTY spawn retries', () => {
+  const nodePty = require('node-pty');
+  const { spawn } = nodePty;
+  const { terminalSpawn } = getAppConfig();
+  let failures: string[];
+
+  beforeEach(() => {
+    getAppConfig().terminalSpawn = { maxAttempts: 3, timeoutMs: 5000 };
+    nodePty.spawn = () => {
+      const failure = failures.shift();
+      if (failure) {
+        throw new Error(failure);
+      }
+      return { pid: 42 };
+    };
+  });
+
+  afterEach(() => {
+    nodePty.spawn = spawn;
+    getAppConfig().terminalSpawn = terminalSpawn;
+  });
+
+  const spawnPty = () => (new TerminalManager() as any).spawnPty('t1', '/bin/sh', [], {});
+
+  it('retries while the system is out of PTYs', async () => {
+    failures = ['forkpty(3) failed.', 'Resource temporarily unavailable'];
+    assert.equal((await spawnPty()).pid, 42);
+    assert.deepEqual(failures, []);
+  });
+
+  it('stops at the configured number of attempts', async () => {
+    failures = ['forkpty(3) failed.', 'forkpty(3) failed.', 'forkpty(3) failed.', 'forkpty(3) failed.'];
+    await assert.rejects(spawnPty(), (error: unknown) => {
+      assert.ok(error instanceof TerminalSpawnError);
+      assert.equal(error.attempts, 3);
+      assert.match(error.message, /after 3 attempts/);
+      return true;
+    });
+  });
+
+  it('stops retrying once the next wait would pass the timeout', async () => {
+    getAppConfig().terminalSpawn = { maxAttempts: 10, timeoutMs: 150 };
+    failures = Array(10).fill('forkpty(3) failed.');
+    await assert.rejects(spawnPty(), { code: 'SpawnFailed', attempts: 2 });
+  });
+
+  it('does not retry a missing shell', async () => {
+    failures = ['File not found: /bin/nope', 'unused'];
+    await assert.rejects(spawnPty(), { code: 'SpawnFailed', attempts: 1 });
+    assert.deepEqual(failures, ['unused']);
+  });
+});
+
+describe('exportTerminalText', () => {
+  it('returns the whole scrollback, raw or as plain text', () => {
+    const manager = new TerminalManager();
+    const scrollback = new ScrollbackBuffer({ segmentChars: 16 });
+    scrollback.append('$ \x1b[31mkubectl get pods\x1b[0m\r\nNAME    READY\r\nweb-0   1/1\r\n');
+    addFakeShell(manager, { scrollback });
+
+    const plain = manager.exportTerminalText('t1', true);
+    assert.equal(plain.text, '$ kubectl get pods\nNAME    READY\nweb-0   1/1\n');
+    assert.deepEqual([plain.truncated, plain.stripped], [false, true]);
+    assert.equal(manager.exportTerminalText('t1', false).text, scrollback.read(0));
+  });
+
+  it('reads what an exited terminal left behind', () => {
+    const manager = new TerminalManager();
+    (manager as any).exited.set('t2', { output: 'error: \x1b[1mno such pod\x1b[0m\r\n' });
+    assert.equal(manager.exportTerminalText('t2', true).text, 'error: no such pod\n');
+    assert.throws(() => manager.exportTerminalText('t3', true), /not found/);
+  });
+});
+
+describe('terminal TERM and locale', () => {
+  const nodePty = require('node-pty');
+  const { spawn: spawnPty } = nodePty;
+
+  beforeEach(() => {
+    nodePty.spawn = processPty;
+  });
+
+  afterEach(() => {
+    nodePty.spawn = spawnPty;
+  });
+
+  const childSees = async (options: Record<string, unknown>) => {
+    const manager = new TerminalManager();
+    const script = 'printf "%s|%s|%s" "$TERM" "$LANG" "$LC_ALL"';
+    await manager.createTerminal('t1', { shell: '/bin/sh', args: ['-c', script], ...options });
+    for (let turn = 0; turn < 1000 && !(manager as any).exited.has('t1'); turn++) {
+      await new Promise((resolve) => setTimeout(resolve, 5));
+    }
+    return manager.getLastOutput('t1').output;
+  };
+
+  it('gives the child the configured TERM, LANG and LC_ALL', async () => {
+    assert.equal(await childSees({ term: 'screen-256color', lang: 'C.UTF-8' }), 'screen-256color|C.UTF-8|C.UTF-8');
+  });
+
+  it('defaults to xterm-256color and the inherited locale', async () => {
+    const saved = { ...process.env };
+    process.env.LANG = 'en_GB.UTF-8';
+    delete process.env.LC_ALL;
+    try {
+      assert.equal(await childSees({}), 'xterm-256color|en_GB.UTF-8|');
+    } finally {
+      process.env = saved;
+    }
+  });
+
+  it('refuses values that are not terminfo or locale names', async () => {
+    const manager = new TerminalManager();
+    await assert.rejects(manager.createTerminal('t1', { term: 'xterm\nEVIL=1' }), /Invalid TERM value/);
+    await assert.rejects(manager.createTerminal('t1', { lang: 'C.UTF-8; id' }), /Invalid locale/);
+  });
+});
+
+describe('terminal layout snapshots', () => {
+  const nodePty = require('node-pty');
+  const { spawn: spawnPty } = nodePty;
+  const home = path.join(userDataDir, 'snapshot-home');
+  let spawned: { file: string; args: string[]; options: any }[];
+
+  beforeEach(() => {
+    mkdirSync(home, { recursive: true });
+    const kubeconfig = path.join(userDataDir, 'snapshot-kubeconfig');
+    // JSON is YAML
+    writeFileSync(kubeconfig, JSON.stringify({ contexts: [{ name: 'dev', context: { cluster: 'dev' } }] }));
+    setKubeconfigPath(kubeconfig);
+    spawned = [];
+    nodePty.spawn = (file: string, args: string[], options: any) => {
+      spawned.push({ file, args, options });
+      return new FakePty(100 + spawned.length);
+    };
+  });
+
+  afterEach(() => {
+    nodePty.spawn = spawnPty;
+  });
+
+  it('recreates the layout with fresh shells under new ids', async () => {
+    const manager = new TerminalManager();
+    await manager.createTerminal('a', { shell: '/bin/bash', args: ['-l'], cwd: home, context: 'dev', label: 'api' });
+    manager.resizeTerminal('a', 120, 40);
+    await manager.createTerminal('b', { shell: '/bin/sh', cwd: home });
+    const snapshot = JSON.parse(JSON.stringify(manager.snapshotTerminals()));
+    assert.deepEqual(
+      snapshot.terminals.map((entry: any) => [entry.id, entry.label, entry.context, entry.cols, entry.rows]),
+      [['a', 'api', 'dev', 120, 40], ['b', null, null, 80, 30]]
+    );
+
+    spawned = [];
+    const restored = new TerminalManager();
+    const result = await restored.restoreFromSnapshot(snapshot);
+    assert.deepEqual(Object.keys(result.ids), ['a', 'b']);
+    assert.deepEqual([result.degraded, result.failed], [[], []]);
+    assert.deepEqual(spawned.map(({ file, args, options }) => [file, args, options.cwd]), [
+      ['/bin/bash', ['-l'], home],
+      ['/bin/sh', [], home],
+    ]);
+    const info = restored.getTerminalInfo(result.ids.a);
+    assert.deepEqual([info?.label, info?.context, info?.cols, info?.rows], ['api', 'dev', 120, 40]);
+  });
+
+  it('falls back for a missing cwd or context and says so', async () => {
+    const entry = { label: null, shell: '/bin/sh', args: [], cols: 80, rows: 30 };
+    const manager = new TerminalManager();
+    const result = await manager.restoreFromSnapshot({
+      version: 1,
+      takenAt: 0,
+      terminals: [
+        { ...entry, id: 'gone-cwd', cwd: path.join(home, 'deleted'), context: 'dev' },
+        { ...entry, id: 'gone-context', cwd: home, context: 'old-cluster' },
+      ],
+    });
+    assert.deepEqual(Object.keys(result.ids), ['gone-cwd', 'gone-context']);
+    assert.deepEqual(result.degraded.map(({ id }) => id), ['gone-cwd', 'gone-context']);
+    assert.match(result.degraded[0].reasons[0], /no longer exists/);
+    assert.match(result.degraded[1].reasons[0], /old-cluster is not in the kubeconfig/);
+    assert.notEqual(spawned[0].options.cwd, path.join(home, 'deleted'));
+    assert.equal(manager.getTerminalInfo(result.ids['gone-context'])?.context, null);
+  });
+
+  it('refuses snapshots it does not understand', async () => {
+    await assert.rejects(new TerminalManager().restoreFromSnapshot({ version: 2 } as any), /Unsupported/);
+  });
+});
+
+describe('output processing failures', () => {
+  const nodePty = require('node-pty');
+  const { spawn: spawnPty } = nodePty;
+  let ptys: FakePty[];
+  let sent: unknown[][];
+  let manager: TerminalManager;
+
+  beforeEach(() => {
+    ptys = [];
+    nodePty.spawn = () => {
+      ptys.push(new FakePty(200 + ptys.length));
+      return ptys[ptys.length - 1];
+    };
+    sent = [];
+    manager = new TerminalManager();
+    const send = (...args: unknown[]) => sent.push(args);
+    (manager as any).window = { isDestroyed: () => false, webContents: { send } };
+  });
+
+  afterEach(() => {
+    nodePty.spawn = spawnPty;
+  });
+
+  it('reports the error and releases the terminal instead of going quiet', async () => {
+    await manager.createTerminal('t1', { shell: '/bin/sh' });
+    const [fake] = ptys;
+    fake.emitData('$ kubectl get pods\r\n');
+    (manager as any).detectEditMode = () => {
+      throw new Error('index out of range');
+    };
+    fake.emitData('NAME    READY\r\n');
+
+    assert.deepEqual(sent.filter(([channel]) => channel === 'terminal:error'), [
+      ['terminal:error', 't1', 'index out of range'],
+    ]);
+    assert.equal(fake.killed, true);
+    assert.equal(manager.hasTerminal('t1'), false);
+    assert.equal(manager.getLastOutput('t1').output, '$ kubectl get pods\r\n');
+
+    // Later output and the PTY dying afterwards are not reported
+    fake.emitData('web-0   1/1\r\n');
+    fake.emitExit(0);
+    assert.deepEqual(sent.map(([channel]) => channel), ['terminal:data', 'terminal:error']);
+  });
+
+  it('leaves a new terminal under the same id alone', async () => {
+    await manager.createTerminal('t1', { shell: '/bin/sh' });
+    (manager as any).appendScrollback = () => {
+      throw new Error('scrollback full');
+    };
+    ptys[0].emitData('x');
+    delete (manager as any).appendScrollback;
+
+    await manager.createTerminal('t1', { shell: '/bin/sh' });
+    ptys[1].emitData('still here');
+    assert.equal(manager.hasTerminal('t1'), true);
+    assert.equal(ptys[1].killed, false);
+  });
+});
+
+describe('terminal notify patterns', () => {
+  const nodePty = require('node-pty');
+  const { spawn: spawnPty } = nodePty;
+
+  afterEach(() => {
+    nodePty.spawn = spawnPty;
+  });
+
+  it('emits terminal:notify when streamed output matches, until cleared', async () => {
+    const fake = new FakePty(300);
+    nodePty.spawn = () => fake;
+    const manager = new TerminalManager();
+    const notified: unknown[][] = [];
+    const send = (channel: string, ...args: unknown[]) => channel === 'terminal:notify' && notified.push(args);
+    (manager as any).window = { isDestroyed: () => false, webContents: { send } };
+    await manager.createTerminal('t1', { shell: '/bin/sh' });
+
+    manager.setNotifyPattern('t1', 'rolled out|error');
+    fake.emitData('Waiting for deployment "web" rollout to finish: 1 of 3 updated replicas...\r\n');
+    fake.emitData('deployment "web" successfully rol');
+    fake.emitData('led out\r\n');
+    assert.deepEqual(notified, [
+      ['t1', { pattern: 'rolled out|error', match: 'rolled out', line: 'deployment "web" successfully rolled out' }],
+    ]);
+
+    manager.setNotifyPattern('t1', '');
+    fake.emitData('error: unable to upgrade connection\r\n');
+    assert.equal(notified.length, 1);
+    assert.throws(() => manager.setNotifyPattern('t1', '['), /Invalid pattern/);
+    assert.throws(() => manager.setNotifyPattern('t2', 'x'), /not found/);
+  });
+});
+
+describe('pasteToTerminal', () => {
+  const { terminalPaste } = getAppConfig();
+
+  afterEach(() => {
+    getAppConfig().terminalPaste = terminalPaste;
+  });
+
+  it('holds back a large paste until it is confirmed', () => {
+    getAppConfig().terminalPaste = { maxBytes: 0, maxLines: 2 };
+    const manager = new TerminalManager();
+    addFakeShell(manager);
+    const written: string[] = [];
+    (manager as any).writeToTerminal = (_id: string, data: string) => written.push(data);
+
+    manager.pasteToTerminal('t1', 'echo 1\recho 2\r');
+    assert.throws(() => manager.pasteToTerminal('t1', 'echo 1\recho 2\recho 3\r'), { code: 'PasteTooLarge' });
+    manager.pasteToTerminal('t1', 'echo 1\recho 2\recho 3\r', true);
+    assert.deepEqual(written, ['echo 1\recho 2\r', 'echo 1\recho 2\recho 3\r']);
+    assert.throws(() => manager.pasteToTerminal('t2', 'x'), /not found/);
+  });
+});
+
+describe('PTY readers of closed terminals', () => {
+  const nodePty = require('node-pty');
+  const { spawn: spawnPty } = nodePty;
+
+  afterEach(() => {
+    nodePty.spawn = spawnPty;
+    mock.timers.reset();
+  });
+
+  const waitFor = async (done: () => boolean) => {
+    for (let turn = 0; turn < 1000 && !done(); turn++) {
+      await new Promise((resolve) => setTimeout(resolve, 5));
+    }
+  };
+
+  it('stops reading on close, and the reader ends with the process', async () => {
+    nodePty.spawn = processPty;
+    const manager = new TerminalManager();
+    await manager.createTerminal('t1', { shell: '/bin/sh', args: ['-c', 'exec sleep 30'] });
+    assert.deepEqual(manager.getReaderStats(), { terminals: 1, readers: 1, lingering: [] });
+
+    manager.closeTerminal('t1');
+    await waitFor(() => manager.getReaderStats().readers === 0);
+    assert.deepEqual(manager.getReaderStats(), { terminals: 0, readers: 0, lingering: [] });
+  });
+
+  it('reports a process that ignores SIGHUP and then kills it', async () => {
+    const fake = new FakePty(400);
+    nodePty.spawn = () => fake;
+    const manager = new TerminalManager();
+    const sent: unknown[][] = [];
+    const send = (...args: unknown[]) => sent.push(args);
+    (manager as any).window = { isDestroyed: () => false, webContents: { send } };
+    await manager.createTerminal('t1', { shell: '/bin/sh' });
+
+    mock.timers.enable({ apis: ['setTimeout', 'Date'], now: 1000 });
+    manager.closeTerminal('t1');
+    fake.emitData('still running\r\n');
+    assert.deepEqual(sent, []);
+    mock.timers.tick(1000);
+    assert.deepEqual(manager.getReaderStats(), {
+      terminals: 0,
+      readers: 1,
+      lingering: [{ id: 't1', pid: 400, closedForMs: 1000, forceKilled: false }],
+    });
+
+    mock.timers.tick(5000);
+    assert.deepEqual(fake.signals, ['SIGHUP', 'SIGKILL']);
+    assert.equal(manager.getReaderStats().lingering[0].forceKilled, true);
+    fake.emitExit(137);
+    assert.deepEqual(manager.getReaderStats(), { terminals: 0, readers: 0, lingering: [] });
+  });
+});
+
+describe('duplicate terminal ids', () => {
+  const nodePty = require('node-pty');
+  const { spawn: spawnPty } = nodePty;
+  let ptys: FakePty[];
+  let manager: TerminalManager;
+  let sent: unknown[][];
+
+  beforeEach(() => {
+    ptys = [];
+    // Our own pid, so the terminal's process counts as alive
+    nodePty.spawn = () => {
+      ptys.push(new FakePty(process.pid));
+      return ptys[ptys.length - 1];
+    };
+    manager = new TerminalManager();
+    sent = [];
+    const send = (...args: unknown[]) => sent.push(args);
+    (manager as any).window = { isDestroyed: () => false, webContents: { send } };
+  });
+
+  afterEach(() => {
+    nodePty.spawn = spawnPty;
+  });
+
+  it('rejects an id that is in use and leaves that terminal alone', async () => {
+    await manager.createTerminal('t1', { shell: '/bin/sh', label: 'first' });
+    ptys[0].emitData('$ ');
+
+    await assert.rejects(manager.createTerminal('t1', { shell: '/bin/bash' }), { code: 'DuplicateId' });
+    assert.equal(ptys.length, 1);
+    assert.equal(ptys[0].killed, false);
+    assert.equal(manager.getTerminalInfo('t1')?.label, 'first');
+    assert.equal(manager.exportTerminalText('t1', false).text, '$ ');
+  });
+
+  it('rejects an id whose terminal is still starting', async () => {
+    const first = manager.createTerminal('t1', { shell: '/bin/sh' });
+    await assert.rejects(manager.createTerminal('t1', { shell: '/bin/sh' }), { code: 'DuplicateId' });
+    await first;
+    assert.equal(ptys.length, 1);
+  });
+
+  it('closes the old terminal first when replacing', async () => {
+    await manager.createTerminal('t1', { shell: '/bin/sh', label: 'first' });
+    await manager.createTerminal('t1', { shell: '/bin/sh', label: 'second', replace: true });
+    assert.equal(ptys[0].killed, true);
+    assert.equal(manager.getTerminalInfo('t1')?.label, 'second');
+
+    // The old process exiting late is not taken for the new terminal's exit
+    ptys[0].emitExit(0);
+    assert.equal(manager.hasTerminal('t1'), true);
+    assert.deepEqual(sent.filter(([channel]) => channel === 'terminal:exit'), []);
+  });
+
+  it('reuses the id of a terminal whose process is gone', async () => {
+    nodePty.spawn = () => new FakePty(2 ** 22 + 1);
+    await manager.createTerminal('t1', { shell: '/bin/sh', label: 'dead' });
+    nodePty.spawn = () => new FakePty(process.pid);
+    await manager.createTerminal('t1', { shell: '/bin/sh', label: 'fresh' });
+    assert.equal(manager.getTerminalInfo('t1')?.label, 'fresh');
+  });
+
+  it('cleans up a gone terminal whose id is reused before its grace period is over', async () => {
+    const dead = new FakePty(spawnSync('true').pid);
+    nodePty.spawn = () => dead;
+    await manager.createTerminal('t1', { shell: '/bin/sh', label: 'dead' });
+    nodePty.spawn = () => new FakePty(process.pid);
+    await manager.createTerminal('t1', { shell: '/bin/sh', label: 'fresh' });
+
+    assert.equal((manager as any).readers.size, 1);
+    assert.deepEqual(sent.filter(([channel]) => channel === 'terminal:exit'), [['terminal:exit', 't1', -1]]);
+    // Late output and exit from the old process don't reach the new terminal
+    dead.emitData('stale output');
+    dead.emitExit(0);
+    assert.equal(manager.exportTerminalText('t1', false).text, '');
+    assert.equal(manager.hasTerminal('t1'), true);
+    assert.equal(sent.filter(([channel]) => channel === 'terminal:exit').length, 1);
+  });
+});
@@ -1,118 +1,1819 @@
 import * as pty from 'node-pty';
+import { execFile, execFileSync } from 'child_process';
+import { randomUUID } from 'crypto';
+import { readFileSync } from 'fs';
+import { readFile, stat } from 'fs/promises';
 import os from 'os';
+import path from 'path';
+import { promisify } from 'util';
 import { BrowserWindow } from 'electron';
+import type {
+  ForegroundProcessInfo,
+  InterruptResult,
+  LastTerminalOutput,
+  OutputPipeStatus,
+  PtyReaderStats,
+  SequencedCommandResult,
+  ShellDetection,
+  TerminalBufferRange,
+  TerminalCapabilities,
+  TerminalCreateOptions,
+  TerminalDiagnostics,
+  TerminalInfo,
+  TerminalLatencyMethod,
+  TerminalLatencyResult,
+  TerminalNotifyEvent,
+  TerminalReconcileReport,
+  TerminalResourceLimits,
+  TerminalRestoreResult,
+  TerminalSnapshot,
+  TerminalTextExport,
+  TerminalWebSocketStatus,
+} from '../common/terminalTypes';
+import { AltScreenParser, answerTerminalQueries, Osc52Parser, stripAnsi } from './terminalSequences';
+import { getKubectlForContext, KubeError, loadKubeConfig, resolveKubeconfigPath } from './kube';
+import { getCloudAuthEnv } from './cloudAuth';
+import { getAppConfig, normalizeCapabilities } from './appConfig';
+import { ScrollbackBuffer } from './scrollback';
+import {
+  formatEnvExports,
+  parseShellProbe,
+  SHELL_PROBE_COMMAND,
+  shellFamilyFor,
+  syntaxForFamily,
+} from './shellExport';
+import { applyProfile } from './terminalProfiles';
+import { isAlive, trackPtyProcess, untrackPtyProcess } from './orphans';
+import { assertCommandAllowed, getCommandPolicy, TerminalInputFilter } from './commandPolicy';
+import { OutputPipe } from './outputPipe';
+import { OutputNotifier } from './outputNotifier';
+import { isInputHistoryEnabled, LineAssembler, recordInput } from './inputHistory';
+import { TerminalWebSocketServer } from './terminalWebSocket';
+import { validateResourceLimits, withResourceLimits } from './resourceLimits';
+import { checkPasteSize } from './pasteGuard';
+import { resolveTerminalCwd } from './cwdPolicy';
 
-export interface TerminalOptions {
-  cwd?: string;
-  env?: Record<string, string>;
+const execFileAsync = promisify(execFile);
+
+export type TerminalOptions = TerminalCreateOptions;
+export type { TerminalInfo };
+
+/**
+ * Output pattern that triggers a `terminal:warning` event with a
+ * friendlier explanation (e.g. kubectl's "did not allocate a TTY")
+ */
+export interface OutputHint {
+  pattern: RegExp;
+  message: string;
+}
+
+/**
+ * Lets the owner of a process terminal start a new process in it when the
+ * old one exits, keeping the terminal's id and scrollback
+ */
+export interface ProcessLifecycle {
+  /** The process exited; return true to keep the terminal open for respawnProcess */
+  onExit(exitCode: number, signal?: number): boolean;
+  /** The terminal was closed or ended for good */
+  onRelease(): void;
+}
+
+export interface ProcessTerminalOptions extends TerminalOptions {
+  outputHints?: OutputHint[];
+  lifecycle?: ProcessLifecycle;
+}
+
+interface TerminalHandle {
+  pty: pty.IPty;
+  shell: string;
+  args: string[];
+  cwd: string;
+  env: Record<string, string>;
+  createdAt: number;
+  cols: number;
+  rows: number;
+  pixelWidth: number;
+  pixelHeight: number;
+  scrollback: ScrollbackBuffer;
+  outputHints: OutputHint[];
+  clipboard: Osc52Parser | null;
+  altScreenParser: AltScreenParser;
+  altScreen: boolean;
+  /** Spawned as the user's shell (not a program like `kubectl attach`) */
+  isShell: boolean;
+  lastOutputAt: number;
+  inputFilter: TerminalInputFilter;
+  outputPipe: OutputPipe | null;
+  webSocket: TerminalWebSocketServer | null;
+  capabilities: TerminalCapabilities;
+  label: string | null;
+  context: string | null;
+  bytesRead: number;
+  bytesWritten: number;
+  profile: string | null;
+  /** Typed lines, for input history; shells only */
+  inputLines: LineAssembler | null;
+  /** Set when output processing threw; the terminal is dead */
+  failure: string | null;
+  notifier: OutputNotifier | null;
+  dataListener: pty.IDisposable | null;
+  /** When closeTerminal released it; the PTY may still be running */
+  closedAt: number | null;
+  forceKilled: boolean;
+  /** When reconcileTerminals first found the process gone; its onExit may still be on the way */
+  goneSince: number | null;
+  /** Cached; confirmed by detectTerminalShell's probe when asked */
+  shellFamily: ShellDetection;
+  /** Output collected while a shell probe waits for its reply */
+  shellProbe: { output: string; resolve: (output: string | null) => void } | null;
+  /** Output searched for the marker while a latency probe waits */
+  latencyProbe: { marker: string; output: string; resolve: (arrived: boolean) => void } | null;
+  /** Output collected while runCommandsSequenced waits for a command's end marker */
+  commandCapture: { output: string; truncated: boolean; onOutput: () => void } | null;
+  lifecycle: ProcessLifecycle | null;
+  /** The process exited and the lifecycle owner is starting another */
+  awaitingRespawn: boolean;
+  /** What the PTY was spawned with, for respawnProcess */
+  spawnEnv: Record<string, string>;
+  limits: TerminalResourceLimits | undefined;
+}
+
+interface ExitedTerminal extends LastTerminalOutput {
+  timer: NodeJS.Timeout;
+}
+
+/**
+ * A PTY could not be started, even after retrying transient failures
+ */
+export class TerminalSpawnError extends Error {
+  readonly code = 'SpawnFailed';
+
+  constructor(message: string, readonly attempts: number) {
+    super(message);
+    this.name = 'TerminalSpawnError';
+  }
+}
+
+/**
+ * A terminal with the requested id is already running (or starting)
+ */
+export class TerminalIdInUseError extends Error {
+  readonly code = 'DuplicateId';
+
+  constructor(readonly id: string) {
+    super(`Terminal with id ${id} already exists; close it first or pass replace: true`);
+    this.name = 'TerminalIdInUseError';
+  }
 }
 
-export class TerminalManager {
-  private terminals: Map<string, pty.IPty> = new Map();
-  private window: BrowserWindow | null = null;
-  private editModeStatus: Map<string, boolean> = new Map();
+// forkpty/openpty fail transiently when the system is briefly out of PTYs or processes
+const TRANSIENT_SPAWN_ERROR = /forkpty|openpty|EAGAIN|temporarily unavailable/i;
+const SPAWN_RETRY_BASE_MS = 100;
+// How often reconcileTerminals runs in the background
+const RECONCILE_INTERVAL_MS = 30 * 1000;
+// node-pty reports an exit only after draining the PTY's last output; reaping waits this long for it
+const EXIT_GRACE_MS = 5 * 1000;
+// How long a shell probe waits for the shell to answer
+const SHELL_PROBE_TIMEOUT_MS = 2000;
+const MAX_SHELL_PROBE_OUTPUT = 4096;
+// `ps` for the foreground process blocks input, so it gets little time
+const PROMPT_CHECK_TIMEOUT_MS = 500;
+const DEFAULT_LATENCY_TIMEOUT_MS = 2000;
+const MAX_LATENCY_TIMEOUT_MS = 10000;
+const DEFAULT_SEQUENCED_TIMEOUT_MS = 30 * 1000;
+const MAX_SEQUENCED_TIMEOUT_MS = 10 * 60 * 1000;
+const MAX_SEQUENCED_COMMANDS = 50;
+// Per command; longer output keeps its start and the last SEQUENCED_OUTPUT_TAIL chars
+const MAX_SEQUENCED_OUTPUT_CHARS = 256 * 1024;
+const SEQUENCED_OUTPUT_TAIL = 4096;
+// A closed terminal's process gets this long after SIGHUP before SIGKILL
+const CLOSE_KILL_TIMEOUT_MS = 3000;
+
+// Exited terminals only keep enough to show the final error
+const MAX_LAST_OUTPUT_CHARS = 256 * 1024;
+// Largest range a single getTerminalBuffer call returns
+const MAX_BUFFER_READ_CHARS = 1024 * 1024;
+// Largest text returned by exportTerminalText, before stripping
+const MAX_EXPORT_TEXT_CHARS = 8 * 1024 * 1024;
+// How long an exited terminal's output stays readable via getLastOutput
+const EXITED_OUTPUT_GRACE_MS = 5 * 60 * 1000;
+const DEFAULT_TERM = 'xterm-256color';
+// terminfo names ("xterm-256color", "screen.xterm") and locales ("C.UTF-8", "de_DE.UTF-8@euro")
+const TERM_PATTERN = /^[\w.+-]+$/;
+const LOCALE_PATTERN = /^[\w.@-]+$/;
+
+export class TerminalManager {
+  private terminals: Map<string, TerminalHandle> = new Map();
+  private window: BrowserWindow | null = null;
+  private editModeStatus: Map<string, boolean> = new Map();
+  private exited: Map<string, ExitedTerminal> = new Map();
+  // Ids whose PTY is being spawned (possibly waiting to retry)
+  private spawning: Set<string> = new Set();
+  // Every PTY whose process hasn't exited, including closed terminals'
+  private readers: Map<TerminalHandle, string> = new Map();
+  // Since launch, including terminals that have exited
+  private totalBytesRead = 0;
+  private totalBytesWritten = 0;
+  private reconcileTimer: NodeJS.Timeout | null = null;
+
+  setWindow(window: BrowserWindow): void {
+    this.window = window;
+  }
+
+  async createTerminal(id: string, requested: TerminalOptions = {}): Promise<void> {
+    const options = applyProfile(requested);
+    if (options.initialCommand) {
+      assertCommandAllowed('terminal', options.initialCommand);
+    }
+    // Detect shell based on platform
+    const shell = options.shell || this.getDefaultShell();
+    const loginArgs = options.loginShell && os.platform() !== 'win32' ? ['-l'] : [];
+    const args = [...loginArgs, ...(options.args ?? [])];
+
+    await this.spawnTerminal(
+      id,
+      shell,
+      args,
+      {
+        ...options,
+        env: this.withContextKubectl(options),
+      },
+      true
+    );
+
+    if (options.initialCommand) {
+      const terminal = this.terminals.get(id);
+      if (terminal) {
+        this.writePty(terminal, `${options.initialCommand}\r`);
+      }
+    }
+  }
+
+  /**
+   * Run an arbitrary program (e.g. `kubectl attach`) inside a PTY. It gets
+   * the same data/exit events as a shell terminal.
+   */
+  async createProcessTerminal(
+    id: string,
+    file: string,
+    args: string[],
+    options: ProcessTerminalOptions = {}
+  ): Promise<void> {
+    await this.spawnTerminal(id, file, args, options);
+  }
+
+  /**
+   * Stop the command running in a shell without closing the shell. The
+   * PTY's foreground process group gets SIGINT directly, which works even
+   * when a program has turned off ISIG or ignores typed ^C. Programs run
+   * via kubectl (exec/attach) and Windows get a ^C character instead.
+   */
+  async interruptTerminal(id: string): Promise<InterruptResult> {
+    const terminal = this.terminals.get(id);
+    if (!terminal) {
+      throw new Error(`Terminal with id ${id} not found`);
+    }
+
+    const shellPid = terminal.pty.pid;
+    const foreground =
+      terminal.isShell && os.platform() !== 'win32' ? await this.getForegroundProcessGroup(shellPid) : null;
+
+    if (foreground === null) {
+      this.writePty(terminal, '\x03');
+      return { interrupted: true, method: 'control-char', processGroup: null };
+    }
+
+    // The shell itself owns the terminal: it's sitting at the prompt
+    if (foreground === shellPid) {
+      return { interrupted: false, method: null, processGroup: null };
+    }
+
+    try {
+      process.kill(-foreground, 'SIGINT');
+    } catch (error) {
+      console.warn(`[Terminal ${id}] SIGINT to process group ${foreground} failed, sending ^C:`, error);
+      this.writePty(terminal, '\x03');
+      return { interrupted: true, method: 'control-char', processGroup: null };
+    }
+
+    console.log(`[Terminal ${id}] Sent SIGINT to foreground process group ${foreground}`);
+    return { interrupted: true, method: 'signal', processGroup: foreground };
+  }
+
+  /**
+   * What is running in the foreground of a terminal, so closing it can ask
+   * first when a command (say a port-forward) would be killed. On unix this
+   * is the PTY's foreground process group: the shell's own when it sits at
+   * the prompt. Windows can't tell, and reports "unknown". A process
+   * terminal (kubectl exec/attach) is always busy with its program.
+   */
+  async getForegroundProcess(id: string): Promise<ForegroundProcessInfo> {
+    const terminal = this.terminals.get(id);
+    if (!terminal) {
+      throw new Error(`Terminal with id ${id} not found`);
+    }
+
+    const shellPid = terminal.pty.pid;
+    const shellName = path.basename(terminal.shell);
+    if (!terminal.isShell) {
+      const command = [terminal.shell, ...terminal.args].join(' ');
+      return { id, state: 'busy', pid: shellPid, name: shellName, command };
+    }
+
+    const foreground = os.platform() !== 'win32' ? await this.getForegroundProcessGroup(shellPid) : null;
+    if (foreground === null) {
+      return { id, state: 'unknown', pid: null, name: null, command: null };
+    }
+    if (foreground === shellPid) {
+      return { id, state: 'idle', pid: shellPid, name: shellName, command: null };
+    }
+
+    const described = await this.describeProcess(foreground);
+    return {
+      id,
+      state: 'busy',
+      pid: foreground,
+      // The group's leader may have exited while the rest of the job runs; node-pty still knows the name
+      name: described?.name ?? (terminal.pty.process || null),
+      command: described?.command ?? null,
+    };
+  }
+
+  /**
+   * Mirror the terminal's output into a named pipe for external tools.
+   * Replaces any pipe already attached.
+   */
+  async attachOutputPipe(id: string, pipePath: string): Promise<OutputPipeStatus> {
+    if (!this.terminals.has(id)) {
+      throw new Error(`Terminal with id ${id} not found`);
+    }
+
+    const pipe = await OutputPipe.create(pipePath);
+    const terminal = this.terminals.get(id);
+    if (!terminal) {
+      // Exited while the pipe was opening
+      pipe.close();
+      throw new Error(`Terminal with id ${id} not found`);
+    }
+
+    terminal.outputPipe?.close();
+    terminal.outputPipe = pipe;
+    console.log(`[Terminal ${id}] Mirroring output to ${pipePath}`);
+    return pipe.status();
+  }
+
+  detachOutputPipe(id: string): OutputPipeStatus | null {
+    const terminal = this.terminals.get(id);
+    if (!terminal) {
+      throw new Error(`Terminal with id ${id} not found`);
+    }
+
+    const pipe = terminal.outputPipe;
+    if (!pipe) {
+      return null;
+    }
+    pipe.close();
+    terminal.outputPipe = null;
+    return pipe.status();
+  }
+
+  /**
+   * Serve a terminal over a token-protected WebSocket on 127.0.0.1 so
+   * another tool or browser tab can watch and type into it. Input goes
+   * through the same path as the app's own (including the command policy).
+   * Replaces any server already running for the terminal.
+   */
+  async startTerminalWebSocket(id: string, port?: number): Promise<TerminalWebSocketStatus> {
+    if (!this.terminals.has(id)) {
+      throw new Error(`Terminal with id ${id} not found`);
+    }
+
+    const server = await TerminalWebSocketServer.start(port, (data) => {
+      try {
+        this.writeToTerminal(id, data);
+      } catch (error) {
+        console.warn(`[Terminal ${id}] WebSocket input rejected:`, error instanceof Error ? error.message : error);
+      }
+    });
+    const terminal = this.terminals.get(id);
+    if (!terminal) {
+      // Exited while the server was starting
+      server.close();
+      throw new Error(`Terminal with id ${id} not found`);
+    }
+
+    terminal.webSocket?.close();
+    terminal.webSocket = server;
+    console.log(`[Terminal ${id}] Serving over WebSocket on 127.0.0.1:${server.port}`);
+    return server.status();
+  }
+
+  stopTerminalWebSocket(id: string): boolean {
+    const terminal = this.terminals.get(id);
+    if (!terminal) {
+      throw new Error(`Terminal with id ${id} not found`);
+    }
+
+    if (!terminal.webSocket) {
+      return false;
+    }
+    terminal.webSocket.close();
+    terminal.webSocket = null;
+    return true;
+  }
+
+  /**
+   * Set (or unset, for null values) environment variables in a running
+   * shell by typing the matching export commands into it. The leading
+   * space keeps the line out of history with HISTCONTROL=ignorespace.
+   * Only done at the shell's prompt: typed into vim or a running program
+   * the line would be keystrokes or input, not an export.
+   */
+  exportToTerminal(id: string, vars: Record<string, string | null>): void {
+    const terminal = this.terminals.get(id);
+    if (!terminal) {
+      throw new Error(`Terminal with id ${id} not found`);
+    }
+    if (!terminal.isShell) {
+      throw new Error(`Terminal ${id} is not running a shell`);
+    }
+    if (Object.keys(vars).length === 0) {
+      return;
+    }
+    if (terminal.altScreen) {
+      throw new Error('A full-screen program is running; try again at the shell prompt');
+    }
+    if (this.isAtShellPrompt(terminal) === false) {
+      throw new Error('A command is running in this terminal; try again at the shell prompt');
+    }
+
+    const syntax = syntaxForFamily(terminal.shellFamily.family);
+    this.writePty(terminal, ` ${formatEnvExports(terminal.shell, vars, syntax)}\r`);
+
+    for (const [name, value] of Object.entries(vars)) {
+      if (value === null) {
+        delete terminal.env[name];
+      } else {
+        terminal.env[name] = value;
+      }
+    }
+  }
+
+  /**
+   * Which shell family a terminal runs, so shell-specific syntax (fish's
+   * `set -gx`, PowerShell's `$env:`) is generated correctly. Inferred from
+   * the spawned command; with `probe`, confirmed by echoing each shell's
+   * version variable into the terminal (visible to the user). The answer
+   * is cached on the terminal.
+   */
+  async detectTerminalShell(id: string, probe = false): Promise<ShellDetection> {
+    const terminal = this.terminals.get(id);
+    if (!terminal) {
+      throw new Error(`Terminal with id ${id} not found`);
+    }
+    if (!probe || !terminal.isShell || terminal.shellFamily.source === 'probe') {
+      return { ...terminal.shellFamily };
+    }
+    if (terminal.altScreen) {
+      throw new Error('A full-screen program is running; try again at the shell prompt');
+    }
+    if (terminal.shellProbe || terminal.latencyProbe || terminal.commandCapture) {
+      throw new Error('Another probe is already running in this terminal');
+    }
+
+    const output = await new Promise<string | null>((resolve) => {
+      const timer = setTimeout(() => finish(null), SHELL_PROBE_TIMEOUT_MS);
+      const finish = (result: string | null) => {
+        clearTimeout(timer);
+        terminal.shellProbe = null;
+        resolve(result);
+      };
+      terminal.shellProbe = { output: '', resolve: finish };
+      // Leading space keeps it out of history with HISTCONTROL=ignorespace
+      this.writePty(terminal, ` ${SHELL_PROBE_COMMAND}\r`);
+    });
+
+    const family = output === null ? null : parseShellProbe(output);
+    if (family === null) {
+      console.warn(`[Terminal ${id}] Shell probe got no reply, keeping ${terminal.shellFamily.family}`);
+      return { ...terminal.shellFamily };
+    }
+    terminal.shellFamily = { family, source: 'probe' };
+    return { ...terminal.shellFamily };
+  }
+
+  /**
+   * Round-trip time through a terminal, to tell a slow connection (an exec
+   * terminal's cluster, a remote host) from slow rendering. "echo" types a
+   * short marker, waits for the PTY to echo it and erases it again; it
+   * needs nothing from the shell but fails when echo is off (password
+   * prompts, `stty -echo`). "printf" runs a printf whose output holds the
+   * marker, so it works without echo but only at a shell prompt. Without a
+   * method, echo is tried first and printf used if nothing comes back, but
+   * only in a shell confirmed by detectTerminalShell's probe that still owns
+   * the terminal: at a password prompt the printf line would be submitted
+   * as the password. Otherwise the failed echo result is returned.
+   */
+  async measureTerminalLatency(
+    id: string,
+    method?: TerminalLatencyMethod,
+    timeoutMs = DEFAULT_LATENCY_TIMEOUT_MS
+  ): Promise<TerminalLatencyResult> {
+    const terminal = this.terminals.get(id);
+    if (!terminal) {
+      throw new Error(`Terminal with id ${id} not found`);
+    }
+    if (terminal.altScreen) {
+      throw new Error('A full-screen program is running; try again at the shell prompt');
+    }
+    if (terminal.latencyProbe || terminal.shellProbe || terminal.commandCapture) {
+      throw new Error('Another probe is already running in this terminal');
+    }
+    const timeout = Math.min(Math.max(timeoutMs, 100), MAX_LATENCY_TIMEOUT_MS);
+    const token = randomUUID().replace(/-/g, '').slice(0, 8);
+
+    if (method !== 'printf') {
+      const marker = `kl${token}`;
+      const roundTripMs = await this.timeRoundTrip(terminal, marker, marker, timeout);
+      // Erase the typed marker, which is also needed when echo is off
+      this.writePty(terminal, '\x7f'.repeat(marker.length));
+      if (roundTripMs !== null || method === 'echo') {
+        return { id, method: 'echo', roundTripMs };
+      }
+      if (terminal.shellFamily.source !== 'probe' || this.isAtShellPrompt(terminal) === false) {
+        return { id, method: 'echo', roundTripMs: null };
+      }
+    }
+
+    if (!terminal.isShell || terminal.shellFamily.family === 'pwsh' || terminal.shellFamily.family === 'cmd') {
+      throw new Error('The printf probe needs a POSIX shell or fish at its prompt');
+    }
+    // The command line only holds "%s", so its echo doesn't match
+    const roundTripMs = await this.timeRoundTrip(
+      terminal,
+      ` printf '__kl_%s__\\n' ${token}\r`,
+      `__kl_${token}__`,
+      timeout
+    );
+    return { id, method: 'printf', roundTripMs };
+  }
+
+  /**
+   * Run commands one after another in a shell, as typed, and capture each
+   * one's output and exit code, for running a short playbook with the
+   * user's aliases and environment. Each command is wrapped in printf
+   * markers that carry a random token and `$?`, so the output between
+   * them and the exit code can be picked out of the stream; OSC 133 marks
+   * from shell integration would only report the whole line's status. The
+   * command sits in a group closed on the next line, so a trailing comment
+   * can't swallow the end marker; the shell reads both lines before
+   * running anything, so the second never reaches the command's stdin. A
+   * command that hasn't finished in time (e.g. one waiting on stdin) is
+   * interrupted with ^C, and the rest are skipped.
+   */
+  async runCommandsSequenced(
+    id: string,
+    commands: string[],
+    timeoutMs = DEFAULT_SEQUENCED_TIMEOUT_MS
+  ): Promise<SequencedCommandResult[]> {
+    const terminal = this.terminals.get(id);
+    if (!terminal) {
+      throw new Error(`Terminal with id ${id} not found`);
+    }
+    if (!terminal.isShell) {
+      throw new Error(`Terminal ${id} is not running a shell`);
+    }
+    const family = terminal.shellFamily.family;
+    if (family === 'pwsh' || family === 'cmd') {
+      throw new Error('Sequenced commands need a POSIX shell or fish');
+    }
+    if (terminal.altScreen) {
+      throw new Error('A full-screen program is running; try again at the shell prompt');
+    }
+    if (terminal.commandCapture || terminal.latencyProbe || terminal.shellProbe) {
+      throw new Error('Commands or a probe are already running in this terminal');
+    }
+    if (commands.length === 0 || commands.length > MAX_SEQUENCED_COMMANDS) {
+      throw new Error(`Between 1 and ${MAX_SEQUENCED_COMMANDS} commands are required`);
+    }
+    for (const command of commands) {
+      // A trailing backslash would pull the group's closing line into the command
+      if (!command.trim() || /[\r\n]|\\$/.test(command)) {
+        throw new Error('Each command must be a single non-empty line');
+      }
+      assertCommandAllowed('terminal', command);
+    }
+    const timeout = Math.min(Math.max(timeoutMs, 100), MAX_SEQUENCED_TIMEOUT_MS);
+    const status = family === 'fish' ? '$status' : '$?';
+    const [open, close] = family === 'fish' ? ['begin;', 'end'] : ['{', '}'];
+
+    const results: SequencedCommandResult[] = [];
+    for (const command of commands) {
+      if (results.some((result) => result.timedOut) || !this.terminals.has(id)) {
+        results.push({
+          command,
+          output: '',
+          exitCode: null,
+          timedOut: false,
+          skipped: true,
+          truncated: false,
+          durationMs: 0,
+        });
+        continue;
+      }
+
+      const token = randomUUID().replace(/-/g, '').slice(0, 12);
+      const start = `__kls_${token}__`;
+      const end = new RegExp(`__kle_${token}_(\\d+)__`);
+      const started = Date.now();
+      // The echoed line only holds "%s", so only the printed markers match
+      const line =
+        ` printf '__kls_%s__\\n' ${token}; ${open} ${command}\r` +
+        `${close}; printf '\\n__kle_%s_%s__\\n' ${token} ${status}\r`;
+
+      const capture = await new Promise<{ output: string; truncated: boolean; exitCode: number | null }>((resolve) => {
+        const timer = setTimeout(() => finish(null), timeout);
+        const finish = (exitCode: number | null) => {
+          clearTimeout(timer);
+          const { output, truncated } = terminal.commandCapture ?? { output: '', truncated: false };
+          terminal.commandCapture = null;
+          resolve({ output, truncated, exitCode });
+        };
+        terminal.commandCapture = {
+          output: '',
+          truncated: false,
+          onOutput: () => {
+            const match = end.exec(terminal.commandCapture?.output ?? '');
+            if (match) {
+              finish(Number(match[1]));
+            }
+          },
+        };
+        this.writePty(terminal, line);
+      });
+
+      const timedOut = capture.exitCode === null;
+      if (timedOut && this.terminals.has(id)) {
+        console.warn(`[Terminal ${id}] Sequenced command timed out after ${timeout}ms, interrupting`);
+        this.writePty(terminal, '\x03');
+      }
+      results.push({
+        command,
+        output: this.sequencedOutput(capture.output, start, end),
+        exitCode: capture.exitCode,
+        timedOut,
+        skipped: false,
+        truncated: capture.truncated,
+        durationMs: Date.now() - started,
+      });
+    }
+    return results;
+  }
+
+  writeToTerminal(id: string, data: string): void {
+    const terminal = this.terminals.get(id);
+    if (!terminal) {
+      throw new Error(`Terminal with id ${id} not found`);
+    }
+    if (terminal.awaitingRespawn) {
+      throw new Error(`Terminal ${id} is reconnecting; input was not sent`);
+    }
+
+    // History sees exactly what reaches the shell, so a blocked line (cleared with ^U) isn't kept
+    const send = (safe: string) => {
+      this.recordInputHistory(terminal, safe);
+      this.writePty(terminal, safe);
+    };
+    if (getCommandPolicy().enabled) {
+      terminal.inputFilter.filter(data, send);
+    } else {
+      send(data);
+    }
+  }
+
+  /**
+   * Write pasted text, refusing with PasteTooLargeError when it's over the
+   * configured size unless the user confirmed it. Keeps a stray clipboard
+   * from flooding the PTY or running hundreds of lines as commands.
+   */
+  pasteToTerminal(id: string, data: string, confirmed = false): void {
+    if (!this.terminals.has(id)) {
+      throw new Error(`Terminal with id ${id} not found`);
+    }
+    if (!confirmed) {
+      checkPasteSize(data);
+    }
+    this.writeToTerminal(id, data);
+  }
+
+  resizeTerminal(id: string, cols: number, rows: number, pixelWidth?: number, pixelHeight?: number): void {
+    const terminal = this.terminals.get(id);
+    if (!terminal) {
+      throw new Error(`Terminal with id ${id} not found`);
+    }
+    // A respawned process starts at the stored size
+    if (!terminal.awaitingRespawn) {
+      terminal.pty.resize(cols, rows);
+    }
+    terminal.cols = cols;
+    terminal.rows = rows;
+    // Keep the previous pixel size when the frontend doesn't send one
+    if (pixelWidth !== undefined && pixelHeight !== undefined) {
+      terminal.pixelWidth = pixelWidth;
+      terminal.pixelHeight = pixelHeight;
+    }
+  }
+
+  closeTerminal(id: string): void {
+    this.releaseLastOutput(id);
+
+    const terminal = this.terminals.get(id);
+    if (!terminal) {
+      return;
+    }
+
+    try {
+      terminal.pty.kill();
+    } catch (error) {
+      console.error(`[Terminal ${id}] Error killing terminal:`, error);
+    }
+    // Stop reading now rather than when the process gets round to exiting
+    terminal.dataListener?.dispose();
+    terminal.dataListener = null;
+    terminal.closedAt = Date.now();
+    this.killIfLingering(id, terminal);
+    untrackPtyProcess(terminal.pty.pid);
+    terminal.outputPipe?.close();
+    terminal.webSocket?.close();
+
+    this.terminals.delete(id);
+    this.editModeStatus.delete(id);
+    terminal.lifecycle?.onRelease();
+    console.log(`[Terminal ${id}] Closed`);
+  }
+
+  /**
+   * A process that ignores SIGHUP would keep its PTY, and our reader on
+   * it, open after the terminal is gone. Escalate to SIGKILL if it hasn't
+   * exited in time. Windows has no signals; kill() already terminates it.
+   */
+  private killIfLingering(id: string, terminal: TerminalHandle): void {
+    if (process.platform === 'win32') {
+      return;
+    }
+
+    const timer = setTimeout(() => {
+      if (!this.readers.has(terminal)) {
+        return;
+      }
+      console.warn(`[Terminal ${id}] Process ${terminal.pty.pid} ignored SIGHUP, sending SIGKILL`);
+      terminal.forceKilled = true;
+      try {
+        terminal.pty.kill('SIGKILL');
+      } catch (error) {
+        console.error(`[Terminal ${id}] Error killing terminal:`, error);
+      }
+    }, CLOSE_KILL_TIMEOUT_MS);
+    timer.unref();
+  }
+
+  /**
+   * Safety net for missed cleanup: release terminals whose process has
+   * exited without onExit having run (reporting `terminal:exit` for each),
+   * and drop readers and edit-mode flags that outlived their terminal. A
+   * process that has only just gone is left to its onExit, which delivers
+   * the last output and the real exit code; it is reaped on a later run
+   * once EXIT_GRACE_MS has passed without one.
+   */
+  reconcileTerminals(): TerminalReconcileReport {
+    const report: TerminalReconcileReport = {
+      checked: 0,
+      reaped: [],
+      pendingExits: 0,
+      staleReaders: 0,
+      staleEditModes: 0,
+    };
+    const now = Date.now();
+
+    for (const [id, handle] of Array.from(this.terminals)) {
+      report.checked++;
+      if (handle.awaitingRespawn) {
+        continue;
+      }
+      if (!this.exitOverdue(handle, now)) {
+        if (handle.goneSince !== null) {
+          report.pendingExits++;
+        }
+        continue;
+      }
+
+      this.reap(id, handle);
+      report.reaped.push({ id, pid: handle.pty.pid });
+    }
+
+    for (const handle of Array.from(this.readers.keys())) {
+      if (!this.isOpen(handle) && this.exitOverdue(handle, now)) {
+        this.readers.delete(handle);
+        report.staleReaders++;
+      }
+    }
+
+    for (const id of Array.from(this.editModeStatus.keys())) {
+      if (!this.terminals.has(id)) {
+        this.editModeStatus.delete(id);
+        report.staleEditModes++;
+      }
+    }
+
+    if (report.reaped.length > 0 || report.staleReaders > 0 || report.staleEditModes > 0) {
+      console.warn('[Terminal] Reconciled state:', report);
+    }
+    return report;
+  }
+
+  /**
+   * Release a terminal whose process exited without onExit having run and
+   * report it as exited; its onExit, should it still come, does nothing
+   */
+  private reap(id: string, handle: TerminalHandle): void {
+    console.warn(`[Terminal ${id}] Process ${handle.pty.pid} exited without cleanup, reaping`);
+    this.readers.delete(handle);
+    handle.dataListener?.dispose();
+    handle.dataListener = null;
+    untrackPtyProcess(handle.pty.pid);
+    handle.outputPipe?.close();
+    handle.webSocket?.close();
+    this.terminals.delete(id);
+    this.editModeStatus.delete(id);
+    handle.lifecycle?.onRelease();
+    this.retainLastOutput(id, handle, -1);
+
+    if (this.window && !this.window.isDestroyed()) {
+      this.window.webContents.send('terminal:exit', id, -1);
+    }
+  }
+
+  /** Whether the process has been gone for EXIT_GRACE_MS; notes when it was first seen gone */
+  private exitOverdue(handle: TerminalHandle, now: number): boolean {
+    if (isAlive(handle.pty.pid)) {
+      handle.goneSince = null;
+      return false;
+    }
+    if (handle.goneSince === null) {
+      handle.goneSince = now;
+    }
+    return now - handle.goneSince >= EXIT_GRACE_MS;
+  }
+
+  startReconciler(): void {
+    if (this.reconcileTimer) {
+      return;
+    }
+    this.reconcileTimer = setInterval(() => {
+      try {
+        this.reconcileTerminals();
+      } catch (error) {
+        console.error('[Terminal] Reconcile failed:', error);
+      }
+    }, RECONCILE_INTERVAL_MS);
+    this.reconcileTimer.unref();
+  }
+
+  stopReconciler(): void {
+    if (this.reconcileTimer) {
+      clearInterval(this.reconcileTimer);
+      this.reconcileTimer = null;
+    }
+  }
+
+  private isOpen(handle: TerminalHandle): boolean {
+    return Array.from(this.terminals.values()).includes(handle);
+  }
+
+  /**
+   * Live PTY readers against open terminals. More readers than terminals
+   * (plus any still spawning) means closed terminals' processes are still
+   * running; `lingering` lists them.
+   */
+  getReaderStats(): PtyReaderStats {
+    const now = Date.now();
+    const lingering = Array.from(this.readers)
+      .filter(([handle]) => handle.closedAt !== null)
+      .map(([handle, id]) => ({
+        id,
+        pid: handle.pty.pid,
+        closedForMs: now - (handle.closedAt ?? now),
+        forceKilled: handle.forceKilled,
+      }));
+
+    return { terminals: this.terminals.size, readers: this.readers.size, lingering };
+  }
+
+  closeAllTerminals(): void {
+    for (const id of this.terminals.keys()) {
+      this.closeTerminal(id);
+    }
+  }
+
+  hasTerminal(id: string): boolean {
+    return this.terminals.has(id);
+  }
+
+  getTerminalIds(): string[] {
+    return Array.from(this.terminals.keys());
+  }
+
+  /**
+   * OSC 52 lets programs in the terminal write the clipboard, which is a
+   * known exfiltration vector, so it's off unless enabled per terminal
+   */
+  setClipboardAccess(id: string, enabled: boolean): void {
+    const terminal = this.terminals.get(id);
+    if (!terminal) {
+      throw new Error(`Terminal with id ${id} not found`);
+    }
+    if (enabled && !terminal.clipboard) {
+      terminal.clipboard = new Osc52Parser();
+    } else if (!enabled) {
+      terminal.clipboard = null;
+    }
+  }
+
+  /**
+   * Emit `terminal:notify` when the terminal's output matches the pattern,
+   * so the UI can raise a desktop notification. Repeated matches within a
+   * few seconds notify once. An empty pattern stops watching.
+   */
+  setNotifyPattern(id: string, pattern: string): void {
+    const terminal = this.terminals.get(id);
+    if (!terminal) {
+      throw new Error(`Terminal with id ${id} not found`);
+    }
+    terminal.notifier = pattern ? OutputNotifier.compile(pattern) : null;
+  }
+
+  getTerminalInfo(id: string): TerminalInfo | undefined {
+    const terminal = this.terminals.get(id);
+    if (!terminal) {
+      return undefined;
+    }
+
+    return {
+      id,
+      shell: terminal.shell,
+      args: [...terminal.args],
+      cwd: terminal.cwd,
+      env: { ...terminal.env },
+      createdAt: terminal.createdAt,
+      cols: terminal.cols,
+      rows: terminal.rows,
+      pixelWidth: terminal.pixelWidth,
+      pixelHeight: terminal.pixelHeight,
+      altScreen: terminal.altScreen,
+      label: terminal.label,
+      context: terminal.context,
+    };
+  }
 
-  setWindow(window: BrowserWindow): void {
-    this.window = window;
+  listTerminals(): TerminalInfo[] {
+    return this.getTerminalIds().flatMap((id) => {
+      const info = this.getTerminalInfo(id);
+      return info ? [info] : [];
+    });
+  }
+
+  /**
+   * Counts and sizes across the terminal subsystem, for spotting leaks and
+   * attaching to bug reports. PTY output is read on the event loop rather
+   * than by threads; see getReaderStats for readers left open.
+   */
+  getTerminalDiagnostics(): TerminalDiagnostics {
+    const now = Date.now();
+    const terminals = Array.from(this.terminals.entries()).map(([id, terminal]) => ({
+      id,
+      isShell: terminal.isShell,
+      ageMs: now - terminal.createdAt,
+      idleMs: now - terminal.lastOutputAt,
+      bytesRead: terminal.bytesRead,
+      bytesWritten: terminal.bytesWritten,
+      scrollbackBytes: terminal.scrollback.byteSize,
+    }));
+    const largest = terminals.reduce<(typeof terminals)[number] | null>(
+      (max, terminal) => (!max || terminal.scrollbackBytes > max.scrollbackBytes ? terminal : max),
+      null
+    );
+
+    return {
+      live: terminals.length,
+      spawning: this.spawning.size,
+      exitedRetained: this.exited.size,
+      bytesRead: this.totalBytesRead,
+      bytesWritten: this.totalBytesWritten,
+      largestScrollback: largest ? { id: largest.id, bytes: largest.scrollbackBytes } : null,
+      outputPipes: Array.from(this.terminals.values()).filter((terminal) => terminal.outputPipe).length,
+      webSockets: Array.from(this.terminals.values()).filter((terminal) => terminal.webSocket).length,
+      terminals,
+    };
+  }
+
+  /**
+   * Describe the open shell terminals so the layout can be recreated with
+   * restoreFromSnapshot. Program terminals (attach, exec) are left out.
+   */
+  snapshotTerminals(): TerminalSnapshot {
+    const terminals = Array.from(this.terminals.entries())
+      .filter(([, terminal]) => terminal.isShell)
+      .map(([id, terminal]) => ({
+        id,
+        label: terminal.label,
+        shell: terminal.shell,
+        args: [...terminal.args],
+        cwd: terminal.cwd,
+        context: terminal.context,
+        cols: terminal.cols,
+        rows: terminal.rows,
+      }));
+    return { version: 1, takenAt: Date.now(), terminals };
   }
 
-  createTerminal(id: string, options: TerminalOptions = {}): void {
-    if (this.terminals.has(id)) {
-      throw new Error(`Terminal with id ${id} already exists`);
+  /**
+   * Start a fresh shell for each snapshot entry under a new id. A cwd that
+   * no longer exists falls back to the home dir and a context missing from
+   * the kubeconfig is dropped; those entries are listed as degraded.
+   */
+  async restoreFromSnapshot(snapshot: TerminalSnapshot): Promise<TerminalRestoreResult> {
+    if (snapshot?.version !== 1 || !Array.isArray(snapshot.terminals)) {
+      throw new Error('Unsupported terminal snapshot');
+    }
+
+    let contexts: Set<string> | null = null;
+    try {
+      contexts = new Set((await loadKubeConfig()).contexts.map((context) => context.name));
+    } catch {
+      // Can't tell which contexts exist; keep them as they were
+    }
+
+    const result: TerminalRestoreResult = { ids: {}, degraded: [], failed: [] };
+    for (const entry of snapshot.terminals) {
+      const reasons: string[] = [];
+      const options: TerminalOptions = { shell: entry.shell, args: entry.args, label: entry.label ?? undefined };
+
+      try {
+        if (entry.cwd && (await stat(entry.cwd)).isDirectory()) {
+          options.cwd = entry.cwd;
+        }
+      } catch {
+        // Checked below
+      }
+      if (entry.cwd && !options.cwd) {
+        reasons.push(`${entry.cwd} no longer exists; started in the home directory`);
+      }
+
+      if (entry.context) {
+        if (!contexts || contexts.has(entry.context)) {
+          options.context = entry.context;
+        } else {
+          reasons.push(`Context ${entry.context} is not in the kubeconfig`);
+        }
+      }
+
+      const id = randomUUID();
+      try {
+        await this.createTerminal(id, options);
+        if (entry.cols > 0 && entry.rows > 0) {
+          this.resizeTerminal(id, entry.cols, entry.rows);
+        }
+      } catch (error) {
+        result.failed.push({ id: entry.id, error: error instanceof Error ? error.message : String(error) });
+        continue;
+      }
+
+      result.ids[entry.id] = id;
+      if (reasons.length > 0) {
+        result.degraded.push({ id: entry.id, reasons });
+      }
+    }
+
+    console.log(
+      `[Terminal] Restored ${Object.keys(result.ids).length}/${snapshot.terminals.length} terminals from snapshot`
+    );
+    return result;
+  }
+
+  /**
+   * The last `chars` characters of a terminal's output
+   */
+  getScrollback(id: string, chars = MAX_LAST_OUTPUT_CHARS): string {
+    const terminal = this.terminals.get(id);
+    if (!terminal) {
+      throw new Error(`Terminal with id ${id} not found`);
+    }
+    return terminal.scrollback.readTail(chars);
+  }
+
+  /**
+   * Read a range of a terminal's history by stream offset. Without a start
+   * offset the most recent `length` characters are returned.
+   */
+  getTerminalBuffer(id: string, start?: number, length = MAX_BUFFER_READ_CHARS): TerminalBufferRange {
+    const terminal = this.terminals.get(id);
+    if (!terminal) {
+      throw new Error(`Terminal with id ${id} not found`);
+    }
+
+    const { scrollback } = terminal;
+    const size = Math.min(Math.max(length, 0), MAX_BUFFER_READ_CHARS);
+    const from = Math.max(start ?? scrollback.end - size, scrollback.start);
+    const to = Math.min(from + size, scrollback.end);
+
+    return {
+      data: scrollback.read(from, to),
+      start: from,
+      end: Math.max(from, to),
+      available: scrollback.start,
+    };
+  }
+
+  /**
+   * A terminal's whole retained output as one string for "copy all" or
+   * "save to file", optionally as plain text without escape sequences.
+   * Works for a recently exited terminal too.
+   */
+  exportTerminalText(id: string, strip: boolean): TerminalTextExport {
+    const terminal = this.terminals.get(id);
+    let raw: string;
+    let truncated: boolean;
+
+    if (terminal) {
+      const { scrollback } = terminal;
+      raw = scrollback.readTail(MAX_EXPORT_TEXT_CHARS);
+      truncated = scrollback.start > 0 || scrollback.end - scrollback.start > MAX_EXPORT_TEXT_CHARS;
+    } else {
+      const exited = this.exited.get(id);
+      if (!exited) {
+        throw new Error(`Terminal with id ${id} not found`);
+      }
+      // Retained output of an exited terminal is already a bounded tail
+      raw = exited.output;
+      truncated = raw.length >= MAX_LAST_OUTPUT_CHARS;
+    }
+
+    return { text: strip ? stripAnsi(raw) : raw, truncated, stripped: strip };
+  }
+
+  /**
+   * Output of a terminal whose process already exited, kept for a short
+   * grace period so the UI can show why it died
+   */
+  getLastOutput(id: string): LastTerminalOutput {
+    const exited = this.exited.get(id);
+    if (!exited) {
+      throw new Error(`No output retained for terminal ${id}`);
+    }
+
+    return {
+      output: exited.output,
+      exitCode: exited.exitCode,
+      signal: exited.signal,
+      exitedAt: exited.exitedAt,
+    };
+  }
+
+  isShellTerminal(id: string): boolean {
+    return this.terminals.get(id)?.isShell ?? false;
+  }
+
+  /**
+   * When the terminal last produced output, used to tell when a command
+   * has finished and the shell is waiting for input again
+   */
+  getLastOutputAt(id: string): number | undefined {
+    return this.terminals.get(id)?.lastOutputAt;
+  }
+
+  isInEditMode(id: string): boolean {
+    return this.editModeStatus.get(id) || false;
+  }
+
+  /**
+   * Free `id` for a new terminal. A live terminal keeps it unless `replace`
+   * is set, in which case it is closed first; one whose process died
+   * without cleanup is reaped. An id still being spawned can't be replaced.
+   */
+  private claimId(id: string, replace: boolean): void {
+    if (this.spawning.has(id)) {
+      throw new TerminalIdInUseError(id);
+    }
+    const existing = this.terminals.get(id);
+    if (!existing) {
+      return;
+    }
+    if (!existing.awaitingRespawn && !isAlive(existing.pty.pid)) {
+      // Without waiting out EXIT_GRACE_MS: the id is needed now
+      this.reap(id, existing);
+    } else if (replace) {
+      console.log(`[Terminal ${id}] Replacing running terminal`);
+      this.closeTerminal(id);
+    } else {
+      throw new TerminalIdInUseError(id);
+    }
+  }
+
+  private async spawnTerminal(
+    id: string,
+    file: string,
+    args: string[],
+    options: ProcessTerminalOptions,
+    isShell = false
+  ): Promise<void> {
+    this.claimId(id, options.replace === true);
+    // Reusing the id of an exited terminal replaces its retained output
+    this.releaseLastOutput(id);
+
+    if (options.term !== undefined && !TERM_PATTERN.test(options.term)) {
+      throw new Error(`Invalid TERM value: ${options.term}`);
+    }
+    if (options.lang !== undefined && !LOCALE_PATTERN.test(options.lang)) {
+      throw new Error(`Invalid locale: ${options.lang}`);
+    }
+    if (options.limits) {
+      validateResourceLimits(options.limits);
     }
 
-    // Detect shell based on platform
-    const shell = this.getDefaultShell();
-    
     // Set up environment
+    const term = options.term || DEFAULT_TERM;
     const env = {
       ...process.env,
       ...options.env,
-      TERM: 'xterm-256color',
+      TERM: term,
     } as any;
+    if (options.lang) {
+      env.LANG = options.lang;
+      env.LC_ALL = options.lang;
+    }
+
+    const limited = withResourceLimits(file, args, options.limits);
+
+    // Create PTY process; nothing is registered until it succeeds, but the
+    // id is held from the first await so a concurrent create can't take it
+    this.spawning.add(id);
+    let cwd: string;
+    let ptyProcess: pty.IPty;
+    try {
+      cwd = await resolveTerminalCwd(options.cwd, process.env.HOME || process.cwd());
+      ptyProcess = await this.spawnPty(id, limited.file, limited.args, {
+        name: term,
+        cols: 80,
+        rows: 30,
+        cwd,
+        env,
+      });
+    } finally {
+      this.spawning.delete(id);
+    }
 
-    // Create PTY process
-    const ptyProcess = pty.spawn(shell, [], {
-      name: 'xterm-256color',
+    const handle: TerminalHandle = {
+      pty: ptyProcess,
+      shell: file,
+      args,
+      cwd,
+      env: { ...options.env },
+      createdAt: Date.now(),
       cols: 80,
       rows: 30,
-      cwd: options.cwd || process.env.HOME || process.cwd(),
-      env,
-    });
+      pixelWidth: options.pixelWidth || 0,
+      pixelHeight: options.pixelHeight || 0,
+      scrollback: new ScrollbackBuffer(),
+      outputHints: options.outputHints || [],
+      clipboard: options.allowClipboard ? new Osc52Parser() : null,
+      altScreenParser: new AltScreenParser(),
+      altScreen: false,
+      isShell,
+      lastOutputAt: Date.now(),
+      inputFilter: new TerminalInputFilter(),
+      outputPipe: null,
+      webSocket: null,
+      capabilities: normalizeCapabilities(options.capabilities, getAppConfig().terminalCapabilities),
+      label: options.label ?? null,
+      context: options.context ?? null,
+      bytesRead: 0,
+      bytesWritten: 0,
+      profile: options.profile ?? null,
+      inputLines: isShell ? new LineAssembler() : null,
+      failure: null,
+      notifier: null,
+      dataListener: null,
+      closedAt: null,
+      forceKilled: false,
+      goneSince: null,
+      shellFamily: { family: shellFamilyFor(file, args), source: 'command' },
+      shellProbe: null,
+      latencyProbe: null,
+      commandCapture: null,
+      lifecycle: options.lifecycle ?? null,
+      awaitingRespawn: false,
+      spawnEnv: env,
+      limits: options.limits,
+    };
 
-    // Handle data from terminal
-    ptyProcess.onData((data: string) => {
-      // Detect edit mode by looking for editor indicators
-      this.detectEditMode(id, data);
-      
+    this.listenToPty(id, handle);
+    this.terminals.set(id, handle);
+    this.readers.set(handle, id);
+    // Recorded so a crashed run's children can be found on next launch
+    trackPtyProcess(ptyProcess.pid, id, [file, ...args].join(' '));
+    console.log(`[Terminal ${id}] Created with shell: ${file}`);
+
+    if (limited.unsupported.length > 0) {
+      const message = `Resource limits not supported on ${os.platform()}: ${limited.unsupported.join(', ')}`;
       if (this.window && !this.window.isDestroyed()) {
-        this.window.webContents.send('terminal:data', id, data);
+        this.window.webContents.send('terminal:warning', id, message);
+      }
+      console.warn(`[Terminal ${id}] ${message}`);
+    }
+  }
+
+  /**
+   * Start a new process in a terminal whose previous one exited while its
+   * lifecycle kept it open. Output continues in the same scrollback and
+   * events keep the terminal's id; the size and environment carry over.
+   */
+  async respawnProcess(id: string, file: string, args: string[]): Promise<void> {
+    const handle = this.terminals.get(id);
+    if (!handle) {
+      throw new Error(`Terminal with id ${id} not found`);
+    }
+    if (!handle.awaitingRespawn || this.spawning.has(id)) {
+      throw new Error(`Terminal ${id} is still running`);
+    }
+
+    const limited = withResourceLimits(file, args, handle.limits);
+    this.spawning.add(id);
+    let ptyProcess: pty.IPty;
+    try {
+      ptyProcess = await this.spawnPty(id, limited.file, limited.args, {
+        name: handle.spawnEnv.TERM || DEFAULT_TERM,
+        cols: handle.cols,
+        rows: handle.rows,
+        cwd: handle.cwd,
+        env: handle.spawnEnv,
+      });
+    } finally {
+      this.spawning.delete(id);
+    }
+    if (this.terminals.get(id) !== handle) {
+      // Closed while spawning
+      ptyProcess.kill();
+      throw new Error(`Terminal with id ${id} not found`);
+    }
+
+    handle.pty = ptyProcess;
+    handle.shell = file;
+    handle.args = args;
+    handle.awaitingRespawn = false;
+    handle.goneSince = null;
+    // The old process may have exited inside a full-screen program
+    handle.altScreenParser = new AltScreenParser();
+    handle.altScreen = false;
+    this.listenToPty(id, handle);
+    this.readers.set(handle, id);
+    trackPtyProcess(ptyProcess.pid, id, [file, ...args].join(' '));
+    console.log(`[Terminal ${id}] Respawned with: ${file}`);
+  }
+
+  /**
+   * Give up on a terminal kept open for respawnProcess, ending it as if its
+   * process had just exited with `exitCode`: `terminal:exit` is sent and the
+   * output stays readable through getLastOutput.
+   */
+  abandonRespawn(id: string, exitCode: number): void {
+    const handle = this.terminals.get(id);
+    if (!handle?.awaitingRespawn) {
+      return;
+    }
+
+    handle.outputPipe?.close();
+    handle.webSocket?.close();
+    this.terminals.delete(id);
+    this.editModeStatus.delete(id);
+    handle.lifecycle?.onRelease();
+    this.retainLastOutput(id, handle, exitCode);
+
+    if (this.window && !this.window.isDestroyed()) {
+      this.window.webContents.send('terminal:exit', id, exitCode);
+    }
+  }
+
+  private listenToPty(id: string, handle: TerminalHandle): void {
+    const ptyProcess = handle.pty;
+
+    // Handle data from terminal
+    handle.dataListener = ptyProcess.onData((chunk: string) => {
+      if (handle.failure) {
+        return;
+      }
+      try {
+        this.processOutput(id, handle, chunk);
+      } catch (error) {
+        this.failTerminal(id, handle, error);
       }
     });
 
     // Handle terminal exit
     ptyProcess.onExit(({ exitCode, signal }) => {
       console.log(`[Terminal ${id}] Exited with code ${exitCode}, signal ${signal}`);
-      this.terminals.delete(id);
+      // Not a reader any more once reconcileTerminals reaped it
+      const reaped = !this.readers.delete(handle);
+      handle.dataListener?.dispose();
+      handle.dataListener = null;
+      // Already cleaned up and reported by failTerminal or reconcileTerminals
+      if (handle.failure || reaped) {
+        return;
+      }
+      untrackPtyProcess(ptyProcess.pid);
+      // The id may already belong to a new terminal if this one was closed
+      const current = this.terminals.get(id);
+      if (current === handle && handle.lifecycle?.onExit(exitCode, signal)) {
+        handle.awaitingRespawn = true;
+        return;
+      }
+      handle.outputPipe?.close();
+      handle.webSocket?.close();
+      if (current === handle) {
+        this.terminals.delete(id);
+        handle.lifecycle?.onRelease();
+      } else if (current) {
+        // Replaced: the exit event and retained output would be taken for the new terminal's
+        return;
+      }
+      this.retainLastOutput(id, handle, exitCode, signal);
       
       if (this.window && !this.window.isDestroyed()) {
         this.window.webContents.send('terminal:exit', id, exitCode, signal);
       }
     });
+  }
 
-    this.terminals.set(id, ptyProcess);
-    console.log(`[Terminal ${id}] Created with shell: ${shell}`);
+  private processOutput(id: string, handle: TerminalHandle, chunk: string): void {
+    handle.lastOutputAt = Date.now();
+    const bytes = Buffer.byteLength(chunk, 'utf8');
+    handle.bytesRead += bytes;
+    this.totalBytesRead += bytes;
+    const data = this.answerTerminalQueries(handle, chunk);
+    // Detect edit mode by looking for editor indicators
+    this.detectEditMode(id, data);
+    this.answerPixelSizeQueries(handle, data);
+    this.checkOutputHints(id, handle, data);
+    this.checkNotifyPattern(id, handle, data);
+    this.forwardClipboardWrites(id, handle, data);
+    this.trackAltScreen(id, handle, data);
+    this.appendScrollback(handle, data);
+    handle.inputLines?.observeOutput(data);
+    this.checkShellProbe(handle, data);
+    this.checkLatencyProbe(handle, data);
+    this.checkCommandCapture(handle, data);
+    handle.outputPipe?.write(data);
+    handle.webSocket?.broadcast(data);
+
+    if (this.window && !this.window.isDestroyed()) {
+      this.window.webContents.send('terminal:data', id, data);
+    }
   }
 
-  writeToTerminal(id: string, data: string): void {
-    const terminal = this.terminals.get(id);
-    if (!terminal) {
-      throw new Error(`Terminal with id ${id} not found`);
+  /**
+   * A bug in output processing would otherwise leave the terminal silently
+   * frozen. Report it as `terminal:error`, kill the PTY and release the
+   * terminal so the UI can offer to start a new one; the output so far
+   * stays readable through getLastOutput.
+   */
+  private failTerminal(id: string, handle: TerminalHandle, error: unknown): void {
+    const message = error instanceof Error ? error.message : String(error);
+    handle.failure = message;
+    console.error(`[Terminal ${id}] Output processing failed, closing the terminal:`, error);
+
+    try {
+      handle.pty.kill();
+    } catch (killError) {
+      console.error(`[Terminal ${id}] Error killing terminal:`, killError);
+    }
+    untrackPtyProcess(handle.pty.pid);
+    handle.outputPipe?.close();
+    handle.webSocket?.close();
+    if (this.terminals.get(id) === handle) {
+      this.terminals.delete(id);
+      handle.lifecycle?.onRelease();
+    }
+    this.editModeStatus.delete(id);
+    this.retainLastOutput(id, handle, -1);
+
+    if (this.window && !this.window.isDestroyed()) {
+      this.window.webContents.send('terminal:error', id, message);
+    }
+  }
+
+  /**
+   * Keystrokes typed into a full-screen app (vim, less) aren't commands.
+   * Neither are lines typed while another program owns the terminal (sudo,
+   * ssh, a REPL), which may be passwords; this runs before the data is
+   * written, so the shell hasn't started a command from it yet.
+   */
+  private recordInputHistory(handle: TerminalHandle, data: string): void {
+    if (!handle.inputLines || !isInputHistoryEnabled()) {
+      return;
+    }
+    if (handle.altScreen) {
+      handle.inputLines.reset();
+      return;
+    }
+    const lines = handle.inputLines.feed(data);
+    if (lines.length === 0 || this.isAtShellPrompt(handle) === false) {
+      return;
+    }
+    for (const line of lines) {
+      recordInput(handle.profile, line);
+    }
+  }
+
+  /**
+   * Whether the shell itself is the PTY's foreground process, read
+   * synchronously so the answer predates the input being written. Null
+   * where it can't be told (Windows, or the process is gone).
+   */
+  private isAtShellPrompt(handle: TerminalHandle): boolean | null {
+    if (os.platform() === 'win32') {
+      return null;
+    }
+    const shellPid = handle.pty.pid;
+    try {
+      let value: string;
+      if (os.platform() === 'linux') {
+        const stat = readFileSync(`/proc/${shellPid}/stat`, 'utf8');
+        value = stat.slice(stat.lastIndexOf(')') + 2).split(' ')[5];
+      } else {
+        value = execFileSync('ps', ['-o', 'tpgid=', '-p', String(shellPid)], {
+          encoding: 'utf8',
+          timeout: PROMPT_CHECK_TIMEOUT_MS,
+        }).trim();
+      }
+      const tpgid = Number(value);
+      return Number.isInteger(tpgid) && tpgid > 0 ? tpgid === shellPid : null;
+    } catch {
+      return null;
+    }
+  }
+
+  private writePty(handle: TerminalHandle, data: string): void {
+    const bytes = Buffer.byteLength(data, 'utf8');
+    handle.bytesWritten += bytes;
+    this.totalBytesWritten += bytes;
+    handle.pty.write(data);
+  }
+
+  /**
+   * pty.spawn with retries for transient failures, bounded by the
+   * configured attempt count and overall timeout
+   */
+  private async spawnPty(id: string, file: string, args: string[], options: pty.IPtyForkOptions): Promise<pty.IPty> {
+    const { maxAttempts, timeoutMs } = getAppConfig().terminalSpawn;
+    const deadline = Date.now() + timeoutMs;
+
+    for (let attempt = 1; ; attempt++) {
+      try {
+        return pty.spawn(file, args, options);
+      } catch (error) {
+        const message = error instanceof Error ? error.message : String(error);
+        const delay = SPAWN_RETRY_BASE_MS * 2 ** (attempt - 1);
+
+        if (!TRANSIENT_SPAWN_ERROR.test(message) || attempt >= maxAttempts || Date.now() + delay > deadline) {
+          throw new TerminalSpawnError(
+            `Failed to start ${file}: ${message}${attempt > 1 ? ` (after ${attempt} attempts)` : ''}`,
+            attempt
+          );
+        }
+
+        console.warn(`[Terminal ${id}] Spawn failed (${message}), retrying in ${delay}ms`);
+        await new Promise((resolve) => setTimeout(resolve, delay));
+      }
+    }
+  }
+
+  private checkLatencyProbe(handle: TerminalHandle, data: string): void {
+    const probe = handle.latencyProbe;
+    if (!probe) {
+      return;
+    }
+    probe.output = (probe.output + stripAnsi(data)).slice(-MAX_SHELL_PROBE_OUTPUT);
+    if (probe.output.includes(probe.marker)) {
+      probe.resolve(true);
+    }
+  }
+
+  /**
+   * Write `input` and time how long until `marker` shows up in the output,
+   * or null after the timeout
+   */
+  private timeRoundTrip(
+    terminal: TerminalHandle,
+    input: string,
+    marker: string,
+    timeoutMs: number
+  ): Promise<number | null> {
+    return new Promise<number | null>((resolve) => {
+      const started = performance.now();
+      const timer = setTimeout(() => finish(false), timeoutMs);
+      const finish = (arrived: boolean) => {
+        clearTimeout(timer);
+        terminal.latencyProbe = null;
+        resolve(arrived ? Math.round((performance.now() - started) * 10) / 10 : null);
+      };
+      terminal.latencyProbe = { marker, output: '', resolve: finish };
+      this.writePty(terminal, input);
+    });
+  }
+
+  /**
+   * A sequenced command's output: what came between its start and end
+   * markers, with the PTY's CRLFs turned into newlines
+   */
+  private sequencedOutput(captured: string, start: string, end: RegExp): string {
+    const from = captured.indexOf(start);
+    if (from === -1) {
+      return '';
+    }
+    let output = captured.slice(from + start.length);
+    const match = end.exec(output);
+    if (match) {
+      output = output.slice(0, match.index);
+    }
+    return output
+      .replace(/\r+\n/g, '\n')
+      .replace(/^\n/, '')
+      .replace(/\n$/, '');
+  }
+
+  private checkCommandCapture(handle: TerminalHandle, data: string): void {
+    const capture = handle.commandCapture;
+    if (!capture) {
+      return;
+    }
+    capture.output += stripAnsi(data);
+    if (capture.output.length > MAX_SEQUENCED_OUTPUT_CHARS) {
+      capture.output =
+        capture.output.slice(0, MAX_SEQUENCED_OUTPUT_CHARS - SEQUENCED_OUTPUT_TAIL) +
+        capture.output.slice(-SEQUENCED_OUTPUT_TAIL);
+      capture.truncated = true;
+    }
+    capture.onOutput();
+  }
+
+  private checkShellProbe(handle: TerminalHandle, data: string): void {
+    const probe = handle.shellProbe;
+    if (!probe) {
+      return;
+    }
+    probe.output = (probe.output + stripAnsi(data)).slice(-MAX_SHELL_PROBE_OUTPUT);
+    if (parseShellProbe(probe.output) !== null) {
+      probe.resolve(probe.output);
+    }
+  }
+
+  private trackAltScreen(id: string, handle: TerminalHandle, data: string): void {
+    for (const active of handle.altScreenParser.push(data)) {
+      if (active === handle.altScreen) {
+        continue;
+      }
+
+      handle.altScreen = active;
+      if (this.window && !this.window.isDestroyed()) {
+        this.window.webContents.send('terminal:altscreen', id, active);
+      }
+    }
+  }
+
+  private retainLastOutput(id: string, handle: TerminalHandle, exitCode: number, signal?: number): void {
+    this.releaseLastOutput(id);
+
+    const timer = setTimeout(() => this.exited.delete(id), EXITED_OUTPUT_GRACE_MS);
+    // Don't keep the app alive just to expire a buffer
+    timer.unref();
+
+    this.exited.set(id, {
+      output: handle.scrollback.readTail(MAX_LAST_OUTPUT_CHARS),
+      exitCode,
+      signal,
+      exitedAt: Date.now(),
+      timer,
+    });
+  }
+
+  private releaseLastOutput(id: string): void {
+    const exited = this.exited.get(id);
+    if (exited) {
+      clearTimeout(exited.timer);
+      this.exited.delete(id);
     }
-    terminal.write(data);
   }
 
-  resizeTerminal(id: string, cols: number, rows: number): void {
+  /**
+   * Print an informational line into the terminal's output without
+   * sending anything to the underlying process
+   */
+  writeNotice(id: string, message: string): void {
     const terminal = this.terminals.get(id);
     if (!terminal) {
       throw new Error(`Terminal with id ${id} not found`);
     }
-    terminal.resize(cols, rows);
+
+    const data = `\x1b[33m${message}\x1b[0m\r\n`;
+    this.appendScrollback(terminal, data);
+    if (this.window && !this.window.isDestroyed()) {
+      this.window.webContents.send('terminal:data', id, data);
+    }
   }
 
-  closeTerminal(id: string): void {
-    const terminal = this.terminals.get(id);
-    if (!terminal) {
+  private forwardClipboardWrites(id: string, handle: TerminalHandle, data: string): void {
+    if (!handle.clipboard) {
       return;
     }
 
-    try {
-      terminal.kill();
-    } catch (error) {
-      console.error(`[Terminal ${id}] Error killing terminal:`, error);
+    for (const text of handle.clipboard.push(data)) {
+      if (this.window && !this.window.isDestroyed()) {
+        this.window.webContents.send('terminal:clipboard', id, text);
+      }
     }
+  }
 
-    this.terminals.delete(id);
-    this.editModeStatus.delete(id);
-    console.log(`[Terminal ${id}] Closed`);
+  private checkNotifyPattern(id: string, handle: TerminalHandle, data: string): void {
+    const match = handle.notifier?.push(data);
+    if (!match || !handle.notifier) {
+      return;
+    }
+
+    const event: TerminalNotifyEvent = { pattern: handle.notifier.pattern.source, ...match };
+    if (this.window && !this.window.isDestroyed()) {
+      this.window.webContents.send('terminal:notify', id, event);
+    }
   }
 
-  closeAllTerminals(): void {
-    for (const id of this.terminals.keys()) {
-      this.closeTerminal(id);
+  private checkOutputHints(id: string, handle: TerminalHandle, data: string): void {
+    if (handle.outputHints.length === 0) {
+      return;
+    }
+
+    // Each hint fires at most once per terminal
+    const remaining: OutputHint[] = [];
+    for (const hint of handle.outputHints) {
+      if (hint.pattern.test(data)) {
+        if (this.window && !this.window.isDestroyed()) {
+          this.window.webContents.send('terminal:warning', id, hint.message);
+        }
+        console.warn(`[Terminal ${id}] ${hint.message}`);
+      } else {
+        remaining.push(hint);
+      }
     }
+    handle.outputHints = remaining;
   }
 
-  hasTerminal(id: string): boolean {
-    return this.terminals.has(id);
+  private appendScrollback(handle: TerminalHandle, data: string): void {
+    handle.scrollback.append(data);
   }
 
-  getTerminalIds(): string[] {
-    return Array.from(this.terminals.keys());
+  /**
+   * Reply to DA and OSC 10/11 queries so programs waiting on them don't
+   * hang; returns the output without the answered queries
+   */
+  private answerTerminalQueries(handle: TerminalHandle, data: string): string {
+    if (!handle.capabilities.answerQueries) {
+      return data;
+    }
+
+    const { output, replies } = answerTerminalQueries(data, handle.capabilities);
+    for (const reply of replies) {
+      this.writePty(handle, reply);
+    }
+    return output;
   }
 
-  isInEditMode(id: string): boolean {
-    return this.editModeStatus.get(id) || false;
+  /**
+   * node-pty has no way to set ws_xpixel/ws_ypixel, so image-capable tools
+   * (sixel, kitty, iTerm2) fall back to XTWINOPS queries. Answer
+   * CSI 14 t (text area in pixels) and CSI 16 t (cell size in pixels)
+   * when the frontend supplied pixel dimensions.
+   */
+  private answerPixelSizeQueries(handle: TerminalHandle, data: string): void {
+    if (handle.pixelWidth <= 0 || handle.pixelHeight <= 0 || !data.includes('\x1b[')) {
+      return;
+    }
+
+    if (data.includes('\x1b[14t')) {
+      this.writePty(handle, `\x1b[4;${handle.pixelHeight};${handle.pixelWidth}t`);
+    }
+
+    if (data.includes('\x1b[16t')) {
+      const cellHeight = Math.floor(handle.pixelHeight / Math.max(handle.rows, 1));
+      const cellWidth = Math.floor(handle.pixelWidth / Math.max(handle.cols, 1));
+      this.writePty(handle, `\x1b[6;${cellHeight};${cellWidth}t`);
+    }
   }
 
   private detectEditMode(id: string, data: string): void {
@@ -166,6 +1867,89 @@ export class TerminalManager {
     }
   }
 
+  /**
+   * Put a context's pinned kubectl first on PATH so `kubectl` in the shell
+   * matches what the app itself runs for that context, and add what the
+   * context's cloud exec plugin needs. A pinned binary under another name
+   * can't be put on PATH, so the terminal is refused rather than left
+   * running a different kubectl than the app.
+   */
+  private withContextKubectl(options: TerminalOptions): Record<string, string> | undefined {
+    if (!options.context) {
+      return options.env;
+    }
+
+    // Extends the given env; it only fills gaps, so it can go last
+    const env = {
+      ...options.env,
+      ...getCloudAuthEnv(options.context, resolveKubeconfigPath(), { ...process.env, ...options.env }),
+    };
+    const binary = getKubectlForContext(options.context);
+    if (!binary) {
+      return env;
+    }
+
+    if (path.basename(binary, path.extname(binary)) !== 'kubectl') {
+      throw new KubeError(
+        'KubectlMismatch',
+        `kubectl for context ${options.context} is ${binary}, which the shell can't run as "kubectl"; ` +
+          'pin a binary named kubectl (e.g. a symlink) instead'
+      );
+    }
+
+    const basePath = env.PATH ?? process.env.PATH ?? '';
+    return { ...env, PATH: `${path.dirname(binary)}${path.delimiter}${basePath}` };
+  }
+
+  /**
+   * Foreground process group of the PTY the shell controls (tpgid), or
+   * null when it can't be determined
+   */
+  private async getForegroundProcessGroup(shellPid: number): Promise<number | null> {
+    try {
+      let value: string | undefined;
+      if (os.platform() === 'linux') {
+        const stat = await readFile(`/proc/${shellPid}/stat`, 'utf8');
+        // tpgid is field 8 overall, the 6th after the parenthesized command
+        value = stat.slice(stat.lastIndexOf(')') + 2).split(' ')[5];
+      } else {
+        const { stdout } = await execFileAsync('ps', ['-o', 'tpgid=', '-p', String(shellPid)]);
+        value = stdout.trim();
+      }
+
+      const tpgid = Number(value);
+      return Number.isInteger(tpgid) && tpgid > 0 ? tpgid : null;
+    } catch {
+      return null;
+    }
+  }
+
+  /**
+   * Name and command line of a process, or null once it has exited
+   */
+  private async describeProcess(pid: number): Promise<{ name: string; command: string } | null> {
+    try {
+      if (os.platform() === 'linux') {
+        const [comm, cmdline] = await Promise.all([
+          readFile(`/proc/${pid}/comm`, 'utf8'),
+          readFile(`/proc/${pid}/cmdline`, 'utf8'),
+        ]);
+        const name = comm.trim();
+        return { name, command: cmdline.split('\0').filter(Boolean).join(' ') || name };
+      }
+
+      const [comm, args] = await Promise.all([
+        execFileAsync('ps', ['-o', 'comm=', '-p', String(pid)]),
+        execFileAsync('ps', ['-o', 'args=', '-p', String(pid)]),
+      ]);
+      // macOS prints the executable's full path
+      const name = path.basename(comm.stdout.trim());
+      return name ? { name, command: args.stdout.trim() || name } : null;
+    } catch {
+      return null;
+    }
+  }
+
   private getDefaultShell(): string {
     const platform = os.platform();
 
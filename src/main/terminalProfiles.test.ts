@@ -0,0 +1,39 @@
+import { userDataDir } from './testSupport';
+import assert from 'node:assert/strict';
+import { beforeEach, describe, it } from 'node:test';
+import os from 'os';
+import path from 'path';
+import { getAppConfig } from './appConfig';
+import { applyProfile, getProfile, listProfiles, saveProfile } from './terminalProfiles';
+
+describe('terminal profiles', () => {
+  beforeEach(() => {
+    getAppConfig().terminalProfiles = [];
+  });
+
+  it('saves a profile, replacing one with the same name', () => {
+    saveProfile({ name: 'ops', shell: 'sh', cwd: userDataDir });
+    saveProfile({ name: 'ops', shell: '/bin/sh', env: { A: '1' } });
+    assert.deepEqual(listProfiles(), [{ name: 'ops', shell: '/bin/sh', env: { A: '1' } }]);
+  });
+
+  it('refuses a shell that cannot run or a cwd that is not a directory', () => {
+    assert.throws(() => saveProfile({ name: 'x', shell: 'no-such-shell-here' }), /Shell not found/);
+    assert.throws(() => saveProfile({ name: 'x', cwd: path.join(userDataDir, 'missing') }), /does not exist/);
+    assert.throws(() => saveProfile({ name: '' }), /name is required/);
+    assert.equal(getProfile('x'), null);
+  });
+
+  it('fills in options from the profile, letting explicit ones win', () => {
+    saveProfile({ name: 'ops', shell: '/bin/sh', args: ['-l'], env: { A: '1', B: '2' }, cwd: '~' });
+    const options = applyProfile({ profile: 'ops', args: [], env: { B: 'mine' } });
+    assert.equal(options.shell, '/bin/sh');
+    assert.deepEqual(options.args, []);
+    assert.deepEqual(options.env, { A: '1', B: 'mine' });
+    assert.equal(options.cwd, os.homedir());
+  });
+
+  it('fails for an unknown profile', () => {
+    assert.throws(() => applyProfile({ profile: 'gone' }), /profile not found: gone/);
+  });
+});
@@ -0,0 +1,114 @@
+import { accessSync, constants, statSync } from 'fs';
+import os from 'os';
+import path from 'path';
+import type { TerminalCreateOptions, TerminalProfile } from '../common/terminalTypes';
+import { getAppConfig, normalizeProfile, updateAppConfig } from './appConfig';
+
+function expandHome(dir: string): string {
+  if (dir === '~') {
+    return os.homedir();
+  }
+  if (dir.startsWith('~/') || dir.startsWith('~\\')) {
+    return path.join(os.homedir(), dir.slice(2));
+  }
+  return dir;
+}
+
+function isExecutable(file: string): boolean {
+  try {
+    accessSync(file, constants.X_OK);
+    return statSync(file).isFile();
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Whether a shell is runnable, either as a path or a command found on PATH
+ */
+function shellExists(shell: string): boolean {
+  if (shell.includes('/') || shell.includes('\\')) {
+    return isExecutable(expandHome(shell));
+  }
+
+  const extensions =
+    process.platform === 'win32' ? ['', ...(process.env.PATHEXT || '.EXE;.CMD;.BAT').split(';')] : [''];
+  const dirs = (process.env.PATH || '').split(path.delimiter).filter(Boolean);
+  return dirs.some((dir) => extensions.some((ext) => isExecutable(path.join(dir, shell + ext))));
+}
+
+export function listProfiles(): TerminalProfile[] {
+  return getAppConfig().terminalProfiles.map((profile) => ({ ...profile }));
+}
+
+export function getProfile(name: string): TerminalProfile | null {
+  const profile = getAppConfig().terminalProfiles.find((entry) => entry.name === name);
+  return profile ? { ...profile } : null;
+}
+
+/**
+ * Validate and store a profile, replacing any existing one with the same
+ * name. The shell must be runnable and the cwd must be a directory.
+ */
+export function saveProfile(raw: TerminalProfile): TerminalProfile {
+  const profile = normalizeProfile(raw);
+  if (!profile || !profile.name.trim()) {
+    throw new Error('Profile name is required');
+  }
+
+  if (profile.shell && !shellExists(profile.shell)) {
+    throw new Error(`Shell not found or not executable: ${profile.shell}`);
+  }
+
+  if (profile.cwd) {
+    const cwd = expandHome(profile.cwd);
+    let isDirectory = false;
+    try {
+      isDirectory = statSync(cwd).isDirectory();
+    } catch {
+      // Reported below
+    }
+    if (!isDirectory) {
+      throw new Error(`Working directory does not exist: ${profile.cwd}`);
+    }
+  }
+
+  updateAppConfig((config) => {
+    const index = config.terminalProfiles.findIndex((entry) => entry.name === profile.name);
+    if (index >= 0) {
+      config.terminalProfiles[index] = profile;
+    } else {
+      config.terminalProfiles.push(profile);
+    }
+  });
+
+  console.log(`[Profiles] Saved terminal profile "${profile.name}"`);
+  return { ...profile };
+}
+
+/**
+ * Merge a named profile into terminal create options. Explicit options win;
+ * env is merged key by key so a caller can add to the profile's variables.
+ */
+export function applyProfile(options: TerminalCreateOptions): TerminalCreateOptions {
+  if (!options.profile) {
+    return options;
+  }
+
+  const profile = getProfile(options.profile);
+  if (!profile) {
+    throw new Error(`Terminal profile not found: ${options.profile}`);
+  }
+
+  const env = profile.env || options.env ? { ...profile.env, ...options.env } : undefined;
+
+  return {
+    ...options,
+    shell: options.shell ?? profile.shell,
+    args: options.args ?? profile.args,
+    env,
+    cwd: options.cwd ?? (profile.cwd ? expandHome(profile.cwd) : undefined),
+    loginShell: options.loginShell ?? profile.loginShell,
+    initialCommand: options.initialCommand ?? profile.initialCommand,
+  };
+}
@@ -0,0 +1,137 @@
+import { randomUUID } from 'crypto';
+import { readFile, stat } from 'fs/promises';
+import path from 'path';
+import type { ScriptDoneEvent, ScriptProgressEvent } from '../common/terminalTypes';
+import { emitToRenderer } from './events';
+import { terminalManager } from './terminal';
+
+interface ScriptRun {
+  scriptId: string;
+  cancelled: boolean;
+}
+
+const MAX_SCRIPT_BYTES = 256 * 1024;
+// The shell is considered ready once output has been quiet this long
+const IDLE_MS = 400;
+const POLL_MS = 50;
+
+function scriptCommands(contents: string): string[] {
+  // Comments are skipped: zsh rejects them interactively by default
+  return contents
+    .split(/\r?\n/)
+    .filter((line) => line.trim().length > 0 && !line.trim().startsWith('#'));
+}
+
+/**
+ * Feeds a local script into a running shell one line at a time, waiting
+ * for the shell to go quiet between lines so it runs with the user's
+ * aliases and functions, as if typed
+ */
+export class TerminalScriptRunner {
+  private runs: Map<string, ScriptRun> = new Map();
+
+  async runScriptInTerminal(terminalId: string, scriptPath: string): Promise<{ scriptId: string; total: number }> {
+    if (!terminalManager.hasTerminal(terminalId)) {
+      throw new Error(`Terminal with id ${terminalId} not found`);
+    }
+    if (!terminalManager.isShellTerminal(terminalId)) {
+      throw new Error(`Terminal ${terminalId} is not running a shell`);
+    }
+    if (this.runs.has(terminalId)) {
+      throw new Error('A script is already running in this terminal');
+    }
+
+    const resolvedPath = path.resolve(scriptPath);
+    let info;
+    try {
+      info = await stat(resolvedPath);
+    } catch {
+      throw new Error(`Script not found: ${resolvedPath}`);
+    }
+    if (!info.isFile()) {
+      throw new Error(`${resolvedPath} is not a file`);
+    }
+    if (info.size > MAX_SCRIPT_BYTES) {
+      throw new Error(`Script is too large (${info.size} bytes, limit ${MAX_SCRIPT_BYTES})`);
+    }
+
+    const commands = scriptCommands(await readFile(resolvedPath, 'utf8'));
+    const run: ScriptRun = { scriptId: randomUUID(), cancelled: false };
+    this.runs.set(terminalId, run);
+
+    void this.feed(terminalId, run, commands);
+    console.log(`[Terminal ${terminalId}] Running script ${resolvedPath} (${commands.length} commands)`);
+    return { scriptId: run.scriptId, total: commands.length };
+  }
+
+  cancelScript(terminalId: string): boolean {
+    const run = this.runs.get(terminalId);
+    if (!run) {
+      return false;
+    }
+    run.cancelled = true;
+    return true;
+  }
+
+  private async feed(terminalId: string, run: ScriptRun, commands: string[]): Promise<void> {
+    let stepsRun = 0;
+    let stoppedReason: ScriptDoneEvent['stoppedReason'] = null;
+
+    try {
+      for (const command of commands) {
+        const ready = await this.waitUntilIdle(terminalId, run);
+        if (!ready) {
+          stoppedReason = run.cancelled ? 'cancelled' : 'terminal-closed';
+          break;
+        }
+
+        terminalManager.writeToTerminal(terminalId, `${command}\r`);
+        stepsRun += 1;
+
+        const progress: ScriptProgressEvent = {
+          terminalId,
+          scriptId: run.scriptId,
+          step: stepsRun,
+          total: commands.length,
+          command,
+        };
+        emitToRenderer('terminal:script-progress', progress);
+      }
+    } catch (error) {
+      console.error(`[Terminal ${terminalId}] Script stopped:`, error);
+      stoppedReason = 'terminal-closed';
+    } finally {
+      this.runs.delete(terminalId);
+    }
+
+    const done: ScriptDoneEvent = {
+      terminalId,
+      scriptId: run.scriptId,
+      stepsRun,
+      total: commands.length,
+      stoppedReason,
+    };
+    emitToRenderer('terminal:script-done', done);
+  }
+
+  private async waitUntilIdle(terminalId: string, run: ScriptRun): Promise<boolean> {
+    for (;;) {
+      if (run.cancelled) {
+        return false;
+      }
+
+      const lastOutputAt = terminalManager.getLastOutputAt(terminalId);
+      if (lastOutputAt === undefined) {
+        return false;
+      }
+      if (Date.now() - lastOutputAt >= IDLE_MS) {
+        return true;
+      }
+
+      await new Promise((resolve) => setTimeout(resolve, POLL_MS));
+    }
+  }
+}
+
+// Singleton instance
+export const terminalScriptRunner = new TerminalScriptRunner();
@@ -0,0 +1,43 @@
+import './testSupport';
+import assert from 'node:assert/strict';
+import { describe, it } from 'node:test';
+import { Osc52Parser, stripAnsi } from './terminalSequences';
+
+const copy = (text: string) => `\x1b]52;c;${Buffer.from(text).toString('base64')}\x07`;
+
+describe('Osc52Parser', () => {
+  it('reads a clipboard write split anywhere, including inside the prefix', () => {
+    const output = `before${copy('kubectl get pods')}after`;
+    for (let split = 1; split < output.length; split++) {
+      const parser = new Osc52Parser();
+      const results = [...parser.push(output.slice(0, split)), ...parser.push(output.slice(split))];
+      assert.deepEqual(results, ['kubectl get pods'], `split at ${split}`);
+    }
+  });
+
+  it('carries a partial prefix that follows a complete sequence', () => {
+    const parser = new Osc52Parser();
+    assert.deepEqual(parser.push(`${copy('one')}\x1b]5`), ['one']);
+    assert.deepEqual(parser.push(`2;c;${Buffer.from('two').toString('base64')}\x1b\\`), ['two']);
+  });
+
+  it('ignores clipboard read requests', () => {
+    assert.deepEqual(new Osc52Parser().push('\x1b]52;c;?\x07'), []);
+  });
+});
+
+describe('stripAnsi', () => {
+  it('removes colors, titles and other escapes', () => {
+    const output = '\x1b]0;user@host: ~\x07\x1b[1;32mok\x1b[0m \x1b(Bdone\x1b=\r\n';
+    assert.equal(stripAnsi(output), 'ok done\n');
+  });
+
+  it('collapses progress bars and backspaces to what was left on screen', () => {
+    assert.equal(stripAnsi('pulling  10%\rpulling 100%\r\n'), 'pulling 100%\n');
+    assert.equal(stripAnsi('spin |\b/\b-\bdone'), 'spin done');
+  });
+
+  it('drops an escape string cut off at the end', () => {
+    assert.equal(stripAnsi('text\x1b]8;;https://exa'), 'text');
+  });
+});
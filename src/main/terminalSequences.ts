@@ -0,0 +1,197 @@
+/**
+ * Parsers for escape sequences the backend reacts to in PTY output.
+ * Output arrives in arbitrary chunks, so parsers keep a small carry-over
+ * buffer for sequences split across chunks.
+ */
+
+import type { TerminalCapabilities } from '../common/terminalTypes';
+
+// Clipboard payloads larger than this are dropped
+export const MAX_CLIPBOARD_BYTES = 100 * 1024;
+// Base64 inflates by 4/3; leave room for the sequence prefix
+const MAX_OSC52_SEQUENCE_CHARS = Math.ceil((MAX_CLIPBOARD_BYTES * 4) / 3) + 64;
+
+const OSC52_START = '\x1b]52;';
+const BASE64_PATTERN = /^[A-Za-z0-9+/=\r\n]*$/;
+
+/**
+ * Extracts clipboard writes from OSC 52 sequences:
+ * `ESC ] 52 ; <selection> ; <base64> (BEL | ESC \)`
+ */
+export class Osc52Parser {
+  private pending = '';
+
+  push(data: string): string[] {
+    let input = this.pending + data;
+    this.pending = '';
+
+    const results: string[] = [];
+    let start = input.indexOf(OSC52_START);
+
+    while (start !== -1) {
+      const bel = input.indexOf('\x07', start);
+      const st = input.indexOf('\x1b\\', start);
+      const candidates = [bel, st].filter((index) => index !== -1);
+
+      if (candidates.length === 0) {
+        // Unterminated: wait for more output unless it's absurdly long
+        const partial = input.slice(start);
+        if (partial.length <= MAX_OSC52_SEQUENCE_CHARS) {
+          this.pending = partial;
+        }
+        return results;
+      }
+
+      const end = Math.min(...candidates);
+      const body = input.slice(start + OSC52_START.length, end);
+      const decoded = this.decode(body);
+      if (decoded !== null) {
+        results.push(decoded);
+      }
+
+      input = input.slice(end + (end === st ? 2 : 1));
+      start = input.indexOf(OSC52_START);
+    }
+
+    // Keep a possible partial prefix (up to `ESC ] 52 ;`) at the very end
+    const escIndex = input.lastIndexOf('\x1b');
+    if (escIndex !== -1 && OSC52_START.startsWith(input.slice(escIndex))) {
+      this.pending = input.slice(escIndex);
+    }
+    return results;
+  }
+
+  private decode(body: string): string | null {
+    const separator = body.indexOf(';');
+    if (separator === -1) {
+      return null;
+    }
+
+    const payload = body.slice(separator + 1);
+    // "?" is a clipboard read request, which we never honour
+    if (payload === '?' || payload.length === 0 || !BASE64_PATTERN.test(payload)) {
+      return null;
+    }
+
+    const bytes = Buffer.from(payload, 'base64');
+    if (bytes.length > MAX_CLIPBOARD_BYTES) {
+      return null;
+    }
+    return bytes.toString('utf8');
+  }
+}
+
+// DEC private modes that switch to the alternate screen buffer
+const ALT_SCREEN_MODES = new Set(['47', '1047', '1049']);
+const PRIVATE_MODE_PATTERN = /\x1b\[\?([\d;]+)([hl])/g;
+// A possibly incomplete `ESC [ ? <digits>` at the end of a chunk
+const PARTIAL_PRIVATE_MODE_PATTERN = /\x1b(\[(\?[\d;]*)?)?$/;
+
+/**
+ * Tracks alternate-screen switches (`ESC [ ? 1049 h/l` and the older 47 /
+ * 1047 variants) and reports the resulting state for each one, in order
+ */
+export class AltScreenParser {
+  private pending = '';
+
+  push(data: string): boolean[] {
+    const input = this.pending + data;
+    const results: boolean[] = [];
+    this.pending = '';
+
+    for (const match of input.matchAll(PRIVATE_MODE_PATTERN)) {
+      const modes = match[1].split(';');
+      if (modes.some((mode) => ALT_SCREEN_MODES.has(mode))) {
+        results.push(match[2] === 'h');
+      }
+    }
+
+    const partial = input.match(PARTIAL_PRIVATE_MODE_PATTERN);
+    if (partial && partial[0].length <= 16) {
+      this.pending = partial[0];
+    }
+
+    return results;
+  }
+}
+
+// DA1 (`ESC [ c`, `ESC [ 0 c`), DA2 (`ESC [ > c`) and OSC 10/11 color queries
+const TERMINAL_QUERY_PATTERN = /\x1b\[(>?)0?c|\x1b\](1[01]);\?(\x07|\x1b\\)/g;
+// Reported as the secondary device attributes: xterm-like, patch level 0
+const SECONDARY_DEVICE_ATTRIBUTES = '0;276;0';
+
+// `#rrggbb` -> `rgb:rrrr/gggg/bbbb`, the form OSC 10/11 replies use
+function toXColor(hex: string): string {
+  const channels = [1, 3, 5].map((offset) => hex.slice(offset, offset + 2).toLowerCase());
+  return `rgb:${channels.map((channel) => channel + channel).join('/')}`;
+}
+
+/**
+ * Answer capability and color queries found in PTY output. Returns the
+ * output with the answered queries removed, so the renderer doesn't reply a
+ * second time, and the replies to write back to the PTY, in order. A query
+ * split across chunks passes through unanswered and is left to the renderer.
+ */
+export function answerTerminalQueries(
+  data: string,
+  capabilities: TerminalCapabilities
+): { output: string; replies: string[] } {
+  if (!data.includes('\x1b[') && !data.includes('\x1b]1')) {
+    return { output: data, replies: [] };
+  }
+
+  const replies: string[] = [];
+  const output = data.replace(TERMINAL_QUERY_PATTERN, (_match, secondary: string, osc: string, terminator: string) => {
+    if (osc) {
+      const color = osc === '10' ? capabilities.foreground : capabilities.background;
+      replies.push(`\x1b]${osc};${toXColor(color)}${terminator}`);
+    } else if (secondary) {
+      replies.push(`\x1b[>${SECONDARY_DEVICE_ATTRIBUTES}c`);
+    } else {
+      replies.push(`\x1b[?${capabilities.deviceAttributes}c`);
+    }
+    return '';
+  });
+
+  return { output, replies };
+}
+
+// CSI: ESC [ (or 8-bit 0x9b), parameters, intermediates, final byte
+const CSI_PATTERN = /(?:\x1b\[|\x9b)[0-?]*[ -/]*[@-~]/g;
+// OSC, DCS, SOS, PM and APC strings, terminated by BEL or ST (ESC \ or 0x9c).
+// An unterminated string at the end of the text is dropped too.
+const STRING_SEQUENCE_PATTERN = /(?:\x1b[\]PX^_]|[\x90\x98\x9d\x9e\x9f])[\s\S]*?(?:\x07|\x1b\\|\x9c|$)/g;
+// Other escapes: charset selection (ESC ( B), keypad modes (ESC =), etc.
+const OTHER_ESCAPE_PATTERN = /\x1b(?:[ -/]+[0-~]|[0-~])?/g;
+// Control characters other than tab and newline
+const CONTROL_PATTERN = /[\x00-\x08\x0b-\x1f\x7f]/g;
+
+/**
+ * Remove escape sequences (CSI/SGR, OSC, DCS and the like) from terminal
+ * output and apply carriage returns and backspaces, so progress bars and
+ * spinners collapse to their final state. Meant for exporting output as
+ * readable text, not for rendering.
+ */
+export function stripAnsi(text: string): string {
+  const withoutEscapes = text
+    .replace(STRING_SEQUENCE_PATTERN, '')
+    .replace(CSI_PATTERN, '')
+    .replace(OTHER_ESCAPE_PATTERN, '');
+
+  return withoutEscapes
+    .replace(/\r+\n/g, '\n')
+    .split('\n')
+    .map((line) => {
+      // A bare CR returns to column 0; later text overwrites what was there
+      let result = '';
+      for (const segment of line.split('\r')) {
+        result = segment + result.slice(segment.length);
+      }
+      // Backspace moves left one column
+      while (result.includes('\x08')) {
+        result = result.replace(/[^\x08]?\x08/, '');
+      }
+      return result.replace(CONTROL_PATTERN, '');
+    })
+    .join('\n');
+}
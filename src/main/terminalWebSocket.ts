@@ -0,0 +1,268 @@
+import { createHash, randomBytes, timingSafeEqual } from 'crypto';
+import http from 'http';
+import type { Duplex } from 'stream';
+import type { TerminalWebSocketStatus } from '../common/terminalTypes';
+
+// RFC 6455 handshake constant
+const WEBSOCKET_GUID = '258EAFA5-E914-47DA-95CA-C5AB0DC85B11';
+// Larger client messages close the connection (1009)
+const MAX_MESSAGE_BYTES = 1024 * 1024;
+// A viewer this far behind misses output rather than buffering without bound
+const MAX_QUEUED_BYTES = 1024 * 1024;
+
+const OPCODE_CONTINUATION = 0x0;
+const OPCODE_TEXT = 0x1;
+const OPCODE_BINARY = 0x2;
+const OPCODE_CLOSE = 0x8;
+const OPCODE_PING = 0x9;
+const OPCODE_PONG = 0xa;
+
+function encodeFrame(opcode: number, payload: Buffer): Buffer {
+  const length = payload.length;
+  let header: Buffer;
+  if (length < 126) {
+    header = Buffer.from([0x80 | opcode, length]);
+  } else if (length < 0x10000) {
+    header = Buffer.alloc(4);
+    header[0] = 0x80 | opcode;
+    header[1] = 126;
+    header.writeUInt16BE(length, 2);
+  } else {
+    header = Buffer.alloc(10);
+    header[0] = 0x80 | opcode;
+    header[1] = 127;
+    header.writeBigUInt64BE(BigInt(length), 2);
+  }
+  return Buffer.concat([header, payload]);
+}
+
+function closeFrame(code: number): Buffer {
+  const payload = Buffer.alloc(2);
+  payload.writeUInt16BE(code, 0);
+  return encodeFrame(OPCODE_CLOSE, payload);
+}
+
+/**
+ * One connected viewer: decodes the (masked) frames it sends and writes
+ * unmasked frames back
+ */
+class WebSocketClient {
+  private buffer = Buffer.alloc(0);
+  private fragments: Buffer[] = [];
+  private fragmentBytes = 0;
+  private closed = false;
+
+  constructor(
+    private readonly socket: Duplex,
+    private readonly onMessage: (text: string) => void,
+    private readonly onClose: () => void
+  ) {
+    socket.on('data', (chunk: Buffer) => this.receive(chunk));
+    socket.on('close', () => this.finish());
+    socket.on('error', () => this.finish());
+  }
+
+  get queuedBytes(): number {
+    return this.socket.writableLength;
+  }
+
+  send(text: string): void {
+    if (!this.closed) {
+      this.socket.write(encodeFrame(OPCODE_TEXT, Buffer.from(text, 'utf8')));
+    }
+  }
+
+  close(code = 1000): void {
+    if (this.closed) {
+      return;
+    }
+    this.socket.end(closeFrame(code));
+    this.finish();
+  }
+
+  private finish(): void {
+    if (!this.closed) {
+      this.closed = true;
+      this.onClose();
+    }
+  }
+
+  private receive(chunk: Buffer): void {
+    this.buffer = Buffer.concat([this.buffer, chunk]);
+
+    while (!this.closed && this.buffer.length >= 2) {
+      const fin = (this.buffer[0] & 0x80) !== 0;
+      const opcode = this.buffer[0] & 0x0f;
+      const masked = (this.buffer[1] & 0x80) !== 0;
+      let length = this.buffer[1] & 0x7f;
+      let offset = 2;
+
+      if (length === 126) {
+        if (this.buffer.length < 4) {
+          return;
+        }
+        length = this.buffer.readUInt16BE(2);
+        offset = 4;
+      } else if (length === 127) {
+        if (this.buffer.length < 10) {
+          return;
+        }
+        const longLength = this.buffer.readBigUInt64BE(2);
+        if (longLength > BigInt(MAX_MESSAGE_BYTES)) {
+          this.close(1009);
+          return;
+        }
+        length = Number(longLength);
+        offset = 10;
+      }
+
+      // Clients must mask every frame
+      if (!masked) {
+        this.close(1002);
+        return;
+      }
+      if (this.fragmentBytes + length > MAX_MESSAGE_BYTES) {
+        this.close(1009);
+        return;
+      }
+      if (this.buffer.length < offset + 4 + length) {
+        return;
+      }
+
+      const mask = this.buffer.subarray(offset, offset + 4);
+      const payload = Buffer.from(this.buffer.subarray(offset + 4, offset + 4 + length));
+      for (let index = 0; index < payload.length; index++) {
+        payload[index] ^= mask[index % 4];
+      }
+      this.buffer = this.buffer.subarray(offset + 4 + length);
+      this.handleFrame(fin, opcode, payload);
+    }
+  }
+
+  private handleFrame(fin: boolean, opcode: number, payload: Buffer): void {
+    switch (opcode) {
+      case OPCODE_PING:
+        this.socket.write(encodeFrame(OPCODE_PONG, payload));
+        return;
+      case OPCODE_PONG:
+        return;
+      case OPCODE_CLOSE:
+        this.close(1000);
+        return;
+      case OPCODE_TEXT:
+      case OPCODE_BINARY:
+      case OPCODE_CONTINUATION:
+        this.fragments.push(payload);
+        this.fragmentBytes += payload.length;
+        if (fin) {
+          const message = Buffer.concat(this.fragments).toString('utf8');
+          this.fragments = [];
+          this.fragmentBytes = 0;
+          this.onMessage(message);
+        }
+        return;
+      default:
+        this.close(1002);
+    }
+  }
+}
+
+/**
+ * Local WebSocket server (127.0.0.1 only) mirroring one terminal's output
+ * to any number of viewers and forwarding what they send as input.
+ * Connecting requires the per-session token: `ws://127.0.0.1:PORT/?token=...`.
+ */
+export class TerminalWebSocketServer {
+  readonly token = randomBytes(24).toString('hex');
+  private clients = new Set<WebSocketClient>();
+  private droppedBytes = 0;
+  private closed = false;
+
+  private constructor(
+    private readonly server: http.Server,
+    private readonly onInput: (data: string) => void
+  ) {
+    server.on('request', (_request, response) => {
+      response.writeHead(426, { 'Content-Type': 'text/plain' });
+      response.end('WebSocket connections only\n');
+    });
+    server.on('upgrade', (request, socket) => this.upgrade(request, socket));
+  }
+
+  static start(port: number | undefined, onInput: (data: string) => void): Promise<TerminalWebSocketServer> {
+    return new Promise((resolve, reject) => {
+      const server = http.createServer();
+      const instance = new TerminalWebSocketServer(server, onInput);
+      server.once('error', (error: NodeJS.ErrnoException) => {
+        reject(error.code === 'EADDRINUSE' ? new Error(`Port ${port} is already in use`) : error);
+      });
+      server.listen(port ?? 0, '127.0.0.1', () => resolve(instance));
+    });
+  }
+
+  get port(): number {
+    const address = this.server.address();
+    return address && typeof address === 'object' ? address.port : 0;
+  }
+
+  broadcast(data: string): void {
+    for (const client of this.clients) {
+      if (client.queuedBytes > MAX_QUEUED_BYTES) {
+        this.droppedBytes += Buffer.byteLength(data, 'utf8');
+        continue;
+      }
+      client.send(data);
+    }
+  }
+
+  status(): TerminalWebSocketStatus {
+    return {
+      port: this.port,
+      url: `ws://127.0.0.1:${this.port}/?token=${this.token}`,
+      token: this.token,
+      clients: this.clients.size,
+      droppedBytes: this.droppedBytes,
+    };
+  }
+
+  close(): void {
+    if (this.closed) {
+      return;
+    }
+    this.closed = true;
+    for (const client of Array.from(this.clients)) {
+      client.close(1001);
+    }
+    this.server.close();
+  }
+
+  private authorized(request: http.IncomingMessage): boolean {
+    const token = new URL(request.url ?? '/', 'http://127.0.0.1').searchParams.get('token') ?? '';
+    const expected = Buffer.from(this.token);
+    const given = Buffer.from(token);
+    return given.length === expected.length && timingSafeEqual(given, expected);
+  }
+
+  private upgrade(request: http.IncomingMessage, socket: Duplex): void {
+    const key = request.headers['sec-websocket-key'];
+    if (this.closed || typeof key !== 'string' || request.headers.upgrade?.toLowerCase() !== 'websocket') {
+      socket.end('HTTP/1.1 400 Bad Request\r\n\r\n');
+      return;
+    }
+    if (!this.authorized(request)) {
+      socket.end('HTTP/1.1 401 Unauthorized\r\n\r\n');
+      return;
+    }
+
+    const accept = createHash('sha1').update(key + WEBSOCKET_GUID).digest('base64');
+    socket.write(
+      'HTTP/1.1 101 Switching Protocols\r\n' +
+        'Upgrade: websocket\r\n' +
+        'Connection: Upgrade\r\n' +
+        `Sec-WebSocket-Accept: ${accept}\r\n\r\n`
+    );
+
+    const client = new WebSocketClient(socket, this.onInput, () => this.clients.delete(client));
+    this.clients.add(client);
+  }
+}
@@ -0,0 +1,171 @@
+import { spawn, spawnSync } from 'child_process';
+import { EventEmitter } from 'events';
+import { mkdtempSync } from 'fs';
+import os from 'os';
+import path from 'path';
+import { PassThrough } from 'stream';
+
+/**
+ * Test-only stand-ins for the modules that only load inside Electron: under
+ * plain node the `electron` package is just the binary's path, and node-pty
+ * is built for Electron's ABI. Import this before the module under test.
+ */
+export const userDataDir = mkdtempSync(path.join(os.tmpdir(), 'kubecli-test-'));
+
+function stubModule(id: string, exports: unknown): void {
+  const resolved = require.resolve(id);
+  require.cache[resolved] = { id: resolved, filename: resolved, loaded: true, exports } as unknown as NodeModule;
+}
+
+stubModule('electron', {
+  app: {
+    getPath: () => userDataDir,
+    getVersion: () => '0.0.0-test',
+  },
+  BrowserWindow: { getAllWindows: () => [] },
+});
+stubModule('node-pty', {
+  spawn: () => {
+    throw new Error('node-pty is not available in tests');
+  },
+});
+
+/**
+ * A kubectl process driven by the test: write to its stdout and stderr,
+ * then `exit` it
+ */
+export class FakeChild extends EventEmitter {
+  readonly stdout = new PassThrough();
+  readonly stderr = new PassThrough();
+  readonly stdin = new PassThrough();
+  killed = false;
+  private exited = false;
+
+  kill(): boolean {
+    this.killed = true;
+    this.exit(null);
+    return true;
+  }
+
+  exit(code: number | null): void {
+    if (this.exited) {
+      return;
+    }
+    this.exited = true;
+    this.stdout.end();
+    this.stderr.end();
+    setImmediate(() => this.emit('close', code));
+  }
+}
+
+/**
+ * Replace kube's spawnKubectl with one handing out FakeChilds; the returned
+ * list gets each spawn's args and child
+ */
+export function stubSpawnKubectl(): { args: string[]; child: FakeChild }[] {
+  // Loaded late: the stubs above must be in place first
+  const kube = require('./kube');
+  const spawned: { args: string[]; child: FakeChild }[] = [];
+  kube.spawnKubectl = (_context: string, args: string[]) => {
+    const child = new FakeChild();
+    spawned.push({ args, child });
+    return child;
+  };
+  return spawned;
+}
+
+/** Resolves with the spawned list once it holds `count` entries */
+export async function waitForSpawn<T>(spawned: T[], count: number): Promise<T[]> {
+  for (let turn = 0; spawned.length < count; turn++) {
+    if (turn > 1000) {
+      throw new Error(`Expected ${count} spawn(s), saw ${spawned.length}`);
+    }
+    await new Promise((resolve) => setImmediate(resolve));
+  }
+  return spawned.slice(-count);
+}
+
+/**
+ * A plain child process standing in for a PTY, so the process and its
+ * environment are real; install it as node-pty's `spawn`. Output is
+ * stdout only.
+ */
+export function processPty(file: string, args: string[], options: any) {
+  const child = spawn(file, args, { cwd: options.cwd, env: options.env });
+  return {
+    pid: child.pid,
+    onData: (listener: (chunk: string) => void) => {
+      const onChunk = (chunk: Buffer) => listener(chunk.toString());
+      child.stdout.on('data', onChunk);
+      return { dispose: () => child.stdout.off('data', onChunk) };
+    },
+    onExit: (listener: (event: any) => void) => {
+      child.on('close', (exitCode, signal) => listener({ exitCode, signal }));
+    },
+    write: () => undefined,
+    resize: () => undefined,
+    // node-pty's default, as a closed terminal's shell would get from the kernel
+    kill: (signal = 'SIGHUP') => child.kill(signal as NodeJS.Signals),
+  };
+}
+
+// Runs argv[1:] as a session leader on a new PTY, in this process so the pid
+// is the program's; a forked copy relays stdin and stdout to the PTY
+const TTY_RUNNER = `
+import fcntl, os, select, sys, termios
+master, slave = os.openpty()
+if os.fork() == 0:
+    os.close(slave)
+    inputs = [0, master]
+    while True:
+        ready = select.select(inputs, [], [])[0]
+        if master in ready:
+            try:
+                data = os.read(master, 65536)
+            except OSError:
+                break
+            if not data:
+                break
+            os.write(1, data)
+        if 0 in ready:
+            data = os.read(0, 65536)
+            if data:
+                os.write(master, data)
+            else:
+                inputs.remove(0)
+    os._exit(0)
+os.close(master)
+os.setsid()
+fcntl.ioctl(slave, termios.TIOCSCTTY, 0)
+for fd in (0, 1, 2):
+    os.dup2(slave, fd)
+os.close(slave)
+os.execvp(sys.argv[1], sys.argv[1:])
+`;
+
+/** Whether ttyPty can run here: it needs python3 and a unix PTY */
+export const ttyPtyAvailable =
+  process.platform !== 'win32' && spawnSync('python3', ['-c', 'import pty'], { stdio: 'ignore' }).status === 0;
+
+/**
+ * Like processPty, but the process runs on a real pseudo-terminal, so a
+ * shell gets its prompt, line editing and job control, and the PTY's
+ * foreground process group is the kernel's. Writes are typed into it.
+ */
+export function ttyPty(file: string, args: string[], options: any) {
+  const child = spawn('python3', ['-c', TTY_RUNNER, file, ...args], { cwd: options.cwd, env: options.env });
+  return {
+    pid: child.pid,
+    onData: (listener: (chunk: string) => void) => {
+      const onChunk = (chunk: Buffer) => listener(chunk.toString());
+      child.stdout.on('data', onChunk);
+      return { dispose: () => child.stdout.off('data', onChunk) };
+    },
+    onExit: (listener: (event: any) => void) => {
+      child.on('close', (exitCode, signal) => listener({ exitCode, signal }));
+    },
+    write: (data: string) => child.stdin.write(data),
+    resize: () => undefined,
+    kill: (signal = 'SIGHUP') => child.kill(signal as NodeJS.Signals),
+  };
+}
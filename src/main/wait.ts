@@ -0,0 +1,131 @@
+import { randomUUID } from 'crypto';
+import type { WaitProgressEvent, WaitResultEvent } from '../common/kubeTypes';
+import { classifyKubectlError, spawnKubectl } from './kube';
+import { emitToRenderer } from './events';
+import { operationRegistry } from './operations';
+
+export interface WaitSpec {
+  context: string;
+  kind: string;
+  name: string;
+  namespace?: string;
+  /** What `--for` takes: "condition=Ready", "delete", "create" or "jsonpath={.status.phase}=Running" */
+  condition: string;
+  timeoutSeconds?: number;
+  view?: string;
+}
+
+const DEFAULT_TIMEOUT_SECONDS = 300;
+const MAX_TIMEOUT_SECONDS = 24 * 60 * 60;
+const PROGRESS_INTERVAL_MS = 1000;
+const MAX_CAPTURED_OUTPUT_CHARS = 64 * 1024;
+
+const CONDITION_PATTERN = /^(?:delete|create|condition=[^\s=]+(?:=\S+)?|jsonpath=.+)$/;
+// kubectl wait's message when --timeout passes first
+const TIMEOUT_PATTERN = /timed out waiting for the condition/i;
+
+// Wait id -> kill the kubectl process
+const activeWaits: Map<string, () => void> = new Map();
+
+/**
+ * Run `kubectl wait` in the background. `wait:progress` fires every second
+ * with the elapsed time, and `wait:result` once with whether the condition
+ * was met, the wait timed out, failed, or was cancelled. Returns the wait id.
+ */
+export function waitForCondition(spec: WaitSpec): string {
+  if (!CONDITION_PATTERN.test(spec.condition)) {
+    throw new Error(
+      `Invalid condition "${spec.condition}": use condition=NAME, delete, create or jsonpath=EXPR=VALUE`
+    );
+  }
+  const timeoutSeconds = spec.timeoutSeconds ?? DEFAULT_TIMEOUT_SECONDS;
+  if (!Number.isInteger(timeoutSeconds) || timeoutSeconds < 1 || timeoutSeconds > MAX_TIMEOUT_SECONDS) {
+    throw new Error(`Timeout must be between 1 and ${MAX_TIMEOUT_SECONDS} seconds`);
+  }
+
+  const waitId = randomUUID();
+  const startedAt = Date.now();
+  const child = spawnKubectl(
+    spec.context,
+    ['wait', `${spec.kind}/${spec.name}`, `--for=${spec.condition}`, `--timeout=${timeoutSeconds}s`],
+    spec.namespace
+  );
+  let output = '';
+  let cancelled = false;
+  let finished = false;
+
+  const progress = setInterval(() => {
+    const event: WaitProgressEvent = { waitId, elapsedMs: Date.now() - startedAt, timeoutSeconds };
+    emitToRenderer('wait:progress', event);
+  }, PROGRESS_INTERVAL_MS);
+
+  const finish = (event: Omit<WaitResultEvent, 'waitId' | 'kind' | 'name' | 'condition' | 'elapsedMs'>) => {
+    if (finished) {
+      return;
+    }
+    finished = true;
+    clearInterval(progress);
+    activeWaits.delete(waitId);
+    operationRegistry.unregister(waitId);
+
+    const result: WaitResultEvent = {
+      waitId,
+      kind: spec.kind,
+      name: spec.name,
+      condition: spec.condition,
+      elapsedMs: Date.now() - startedAt,
+      ...event,
+    };
+    console.log(`[Wait ${waitId}] ${spec.kind}/${spec.name} ${spec.condition}: ${result.outcome}`);
+    emitToRenderer('wait:result', result);
+  };
+
+  const append = (chunk: Buffer) => {
+    output = `${output}${chunk.toString()}`.slice(-MAX_CAPTURED_OUTPUT_CHARS);
+  };
+  child.stdout.on('data', append);
+  child.stderr.on('data', append);
+
+  child.on('error', (error) => {
+    finish({ outcome: 'error', output, error: `Failed to start kubectl: ${error.message}` });
+  });
+
+  child.on('close', (code) => {
+    if (cancelled) {
+      finish({ outcome: 'cancelled', output });
+    } else if (code === 0) {
+      finish({ outcome: 'met', output });
+    } else if (TIMEOUT_PATTERN.test(output)) {
+      finish({ outcome: 'timed-out', output });
+    } else {
+      const message = output.trim() || `kubectl exited with code ${code}`;
+      finish({ outcome: 'error', output, error: message, errorCode: classifyKubectlError(message) });
+    }
+  });
+
+  const cancel = () => {
+    cancelled = true;
+    child.kill();
+  };
+  activeWaits.set(waitId, cancel);
+  operationRegistry.register(
+    waitId,
+    'kubectl',
+    { context: spec.context, namespace: spec.namespace, view: spec.view },
+    cancel
+  );
+
+  return waitId;
+}
+
+/**
+ * Stop a wait; it still reports `wait:result`, with outcome "cancelled"
+ */
+export function cancelWait(waitId: string): boolean {
+  const cancel = activeWaits.get(waitId);
+  if (!cancel) {
+    return false;
+  }
+  cancel();
+  return true;
+}
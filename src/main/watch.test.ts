@@ -0,0 +1,77 @@
+import { stubSpawnKubectl, waitForSpawn } from './testSupport';
+import assert from 'node:assert/strict';
+import { beforeEach, describe, it } from 'node:test';
+import { watchManager, type WatchEvent } from './watch';
+
+const pod = (name: string, resourceVersion: number) => ({
+  metadata: { namespace: 'shop', name, resourceVersion: String(resourceVersion) },
+});
+
+const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+describe('watch event coalescing', () => {
+  const spawned = stubSpawnKubectl();
+  let events: WatchEvent[];
+
+  beforeEach(() => {
+    spawned.length = 0;
+    events = [];
+  });
+
+  const start = async (coalesceMs?: number) => {
+    const id = watchManager.startWatch({ context: 'dev', kind: 'pods', coalesceMs }, (event) => events.push(event));
+    await waitForSpawn(spawned, 1);
+    const send = async (...sent: WatchEvent[]) => {
+      spawned[0].child.stdout.write(sent.map((event) => JSON.stringify(event)).join('\n'));
+      await new Promise((resolve) => setImmediate(resolve));
+    };
+    return { id, send };
+  };
+
+  const summary = () =>
+    events.map(({ type, object }) => `${type} ${object.metadata.name}@${object.metadata.resourceVersion}`);
+
+  it('collapses rapid updates of one object into the latest', async () => {
+    const { id, send } = await start(30);
+    await send(
+      { type: 'ADDED', object: pod('web-0', 1) },
+      { type: 'MODIFIED', object: pod('web-0', 2) },
+      { type: 'MODIFIED', object: pod('web-1', 3) },
+      { type: 'MODIFIED', object: pod('web-0', 4) },
+      { type: 'MODIFIED', object: pod('web-0', 5) },
+      { type: 'MODIFIED', object: pod('web-1', 6) }
+    );
+    assert.deepEqual(summary(), ['ADDED web-0@1']);
+
+    await sleep(60);
+    assert.deepEqual(summary(), ['ADDED web-0@1', 'MODIFIED web-0@5', 'MODIFIED web-1@6']);
+    watchManager.stopWatch(id);
+  });
+
+  it('delivers a deletion after the update still pending for that object', async () => {
+    const { id, send } = await start(1000);
+    await send(
+      { type: 'MODIFIED', object: pod('web-0', 2) },
+      { type: 'MODIFIED', object: pod('web-1', 3) },
+      { type: 'DELETED', object: pod('web-0', 4) },
+      { type: 'ADDED', object: pod('web-0', 5) }
+    );
+    assert.deepEqual(summary(), ['MODIFIED web-0@2', 'DELETED web-0@4', 'ADDED web-0@5']);
+    watchManager.stopWatch(id);
+  });
+
+  it('passes every event through when coalescing is off', async () => {
+    const { id, send } = await start();
+    await send({ type: 'MODIFIED', object: pod('web-0', 2) }, { type: 'MODIFIED', object: pod('web-0', 3) });
+    assert.deepEqual(summary(), ['MODIFIED web-0@2', 'MODIFIED web-0@3']);
+    watchManager.stopWatch(id);
+  });
+
+  it('drops pending updates when the watch stops', async () => {
+    const { id, send } = await start(20);
+    await send({ type: 'MODIFIED', object: pod('web-0', 2) });
+    watchManager.stopWatch(id);
+    await sleep(40);
+    assert.deepEqual(events, []);
+  });
+});
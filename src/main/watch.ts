@@ -0,0 +1,381 @@
+import { randomUUID } from 'crypto';
+import type { ChildProcessWithoutNullStreams } from 'child_process';
+import type {
+  NodeNotReadyEvent,
+  NodeSummary,
+  NodeWatchEvent,
+  ResourceWatchEvent,
+  StreamReconnectedEvent,
+  StreamReconnectingEvent,
+} from '../common/kubeTypes';
+import { spawnKubectl } from './kube';
+import { readyCondition, summarizeNode } from './nodes';
+import { JsonObjectStream } from './jsonStream';
+import { operationRegistry } from './operations';
+import { emitToRenderer } from './events';
+import { Reconnector } from './reconnect';
+import { KeyedCoalescer } from './coalesce';
+
+export interface WatchSpec {
+  context: string;
+  kind: string;
+  name?: string;
+  namespace?: string;
+  allNamespaces?: boolean;
+  labelSelector?: string;
+  fieldSelector?: string;
+  view?: string;
+  /**
+   * Collapse MODIFIED events for the same object within this many ms into
+   * the latest one; ADDED and DELETED are always delivered. Off when unset.
+   */
+  coalesceMs?: number;
+}
+
+export type WatchEventType = 'ADDED' | 'MODIFIED' | 'DELETED' | 'BOOKMARK' | 'ERROR';
+
+export interface WatchEvent {
+  type: WatchEventType;
+  object: any;
+}
+
+type WatchEventHandler = (event: WatchEvent) => void;
+type WatchErrorHandler = (message: string) => void;
+
+interface WatchHandle {
+  spec: WatchSpec;
+  process: ChildProcessWithoutNullStreams | null;
+  reopenTimer: NodeJS.Timeout | null;
+  reconnector: Reconnector;
+  stopped: boolean;
+  onEvent: WatchEventHandler;
+  onError?: WatchErrorHandler;
+  coalescer: KeyedCoalescer<WatchEvent> | null;
+}
+
+// The API server closes watches periodically; reopen shortly after
+const REOPEN_DELAY_MS = 1000;
+const MAX_COALESCE_MS = 10 * 1000;
+// Kubelets post status every few seconds; their MODIFIED events are collapsed over this window
+const NODE_COALESCE_MS = 1000;
+
+function sameNodeSummary(a: NodeSummary, b: NodeSummary): boolean {
+  return (
+    a.ready === b.ready &&
+    a.readyReason === b.readyReason &&
+    a.kubeletVersion === b.kubeletVersion &&
+    a.unschedulable === b.unschedulable &&
+    a.pressures.join(',') === b.pressures.join(',')
+  );
+}
+
+/**
+ * Owns every `kubectl get --watch` process so they can be cancelled by id
+ */
+export class WatchManager {
+  private watches: Map<string, WatchHandle> = new Map();
+
+  startWatch(spec: WatchSpec, onEvent: WatchEventHandler, onError?: WatchErrorHandler): string {
+    const id = randomUUID();
+    const handle: WatchHandle = {
+      spec,
+      process: null,
+      reopenTimer: null,
+      reconnector: new Reconnector(),
+      stopped: false,
+      onEvent,
+      onError,
+      coalescer: null,
+    };
+
+    if (spec.coalesceMs && spec.coalesceMs > 0) {
+      const coalescer = new KeyedCoalescer<WatchEvent>((_key, event) => {
+        if (!handle.stopped) {
+          onEvent(event);
+        }
+      }, Math.min(spec.coalesceMs, MAX_COALESCE_MS));
+      handle.coalescer = coalescer;
+      handle.onEvent = (event) => {
+        const name: string | undefined = event.object?.metadata?.name;
+        if (!name || (event.type !== 'ADDED' && event.type !== 'MODIFIED' && event.type !== 'DELETED')) {
+          onEvent(event);
+          return;
+        }
+
+        const key = `${event.object?.metadata?.namespace ?? ''}/${name}`;
+        if (event.type === 'MODIFIED') {
+          coalescer.push(key, event);
+          return;
+        }
+        // Keep the object's events in order
+        coalescer.flushKey(key);
+        onEvent(event);
+      };
+    }
+
+    this.watches.set(id, handle);
+    operationRegistry.register(
+      id,
+      'watches',
+      { context: spec.context, namespace: spec.namespace, view: spec.view },
+      () => this.stopWatch(id)
+    );
+    this.open(id, handle);
+    console.log(`[Watch ${id}] Started for ${spec.kind}${spec.name ? `/${spec.name}` : ''}`);
+    return id;
+  }
+
+  /**
+   * Watch only objects matching a label and/or field selector, emitting
+   * `watch:event` keyed by object name. Much cheaper than watching a whole
+   * kind on large clusters.
+   */
+  watchSelected(spec: WatchSpec): string {
+    if (!spec.labelSelector && !spec.fieldSelector) {
+      throw new Error('A label or field selector is required');
+    }
+
+    const watchId = this.startWatch(
+      spec,
+      (event) => {
+        if (event.type !== 'ADDED' && event.type !== 'MODIFIED' && event.type !== 'DELETED') {
+          return;
+        }
+
+        const name: string | undefined = event.object?.metadata?.name;
+        if (!name) {
+          return;
+        }
+
+        const payload: ResourceWatchEvent = {
+          watchId,
+          type: event.type,
+          name,
+          namespace: event.object?.metadata?.namespace,
+          object: event.object,
+        };
+        emitToRenderer('watch:event', payload);
+      },
+      (message) => emitToRenderer('watch:error', watchId, message)
+    );
+
+    return watchId;
+  }
+
+  /**
+   * Watch the cluster's nodes for a live nodes view, emitting `node:event`
+   * upserts and removals only when something shown changes (readiness,
+   * kubelet version, pressure, cordon), so heartbeat-only status updates
+   * stay quiet. A node leaving Ready also emits `node:notready`. A field
+   * or label selector narrows the watch server-side, e.g. to one pool.
+   */
+  watchNodes(options: { context: string; labelSelector?: string; fieldSelector?: string; view?: string }): string {
+    const known: Map<string, NodeSummary> = new Map();
+
+    const apply = (node: any) => {
+      const summary = summarizeNode(node);
+      const previous = known.get(summary.name);
+      if (previous && sameNodeSummary(previous, summary)) {
+        return;
+      }
+      known.set(summary.name, summary);
+
+      const payload: NodeWatchEvent = { watchId, type: 'upsert', node: summary };
+      emitToRenderer('node:event', payload);
+
+      if (previous?.ready === true && summary.ready !== true) {
+        const condition = readyCondition(node);
+        const notReady: NodeNotReadyEvent = {
+          watchId,
+          name: summary.name,
+          ready: summary.ready,
+          reason: condition?.reason ?? null,
+          message: condition?.message ?? null,
+          since: condition?.lastTransitionTime ?? null,
+        };
+        console.warn(`[Watch ${watchId}] Node ${summary.name} is no longer Ready`);
+        emitToRenderer('node:notready', notReady);
+      }
+    };
+
+    const watchId = this.startWatch(
+      { ...options, kind: 'nodes', coalesceMs: NODE_COALESCE_MS },
+      (event) => {
+        const name: string | undefined = event.object?.metadata?.name;
+        if (!name) {
+          return;
+        }
+
+        if (event.type === 'DELETED') {
+          const summary = known.get(name) ?? summarizeNode(event.object);
+          known.delete(name);
+          const payload: NodeWatchEvent = { watchId, type: 'remove', node: summary };
+          emitToRenderer('node:event', payload);
+        } else if (event.type === 'ADDED' || event.type === 'MODIFIED') {
+          // ADDED is also sent for every node when the watch reopens; unchanged ones are dropped
+          apply(event.object);
+        }
+      },
+      (message) => emitToRenderer('watch:error', watchId, message)
+    );
+
+    return watchId;
+  }
+
+  stopWatch(id: string): boolean {
+    const handle = this.watches.get(id);
+    if (!handle) {
+      return false;
+    }
+
+    handle.stopped = true;
+    handle.reconnector.cancel();
+    handle.coalescer?.dispose();
+    if (handle.reopenTimer) {
+      clearTimeout(handle.reopenTimer);
+      handle.reopenTimer = null;
+    }
+    if (handle.process) {
+      try {
+        handle.process.kill();
+      } catch (error) {
+        console.error(`[Watch ${id}] Error killing kubectl:`, error);
+      }
+      handle.process = null;
+    }
+
+    this.watches.delete(id);
+    operationRegistry.unregister(id);
+    console.log(`[Watch ${id}] Stopped`);
+    return true;
+  }
+
+  stopAllWatches(): void {
+    for (const id of Array.from(this.watches.keys())) {
+      this.stopWatch(id);
+    }
+  }
+
+  hasWatch(id: string): boolean {
+    return this.watches.has(id);
+  }
+
+  getWatchIds(): string[] {
+    return Array.from(this.watches.keys());
+  }
+
+  private open(id: string, handle: WatchHandle): void {
+    const { spec } = handle;
+    const args = ['get', spec.kind];
+
+    if (spec.name) {
+      args.push(spec.name);
+    }
+    if (spec.allNamespaces) {
+      args.push('--all-namespaces');
+    }
+    if (spec.labelSelector) {
+      args.push('-l', spec.labelSelector);
+    }
+    if (spec.fieldSelector) {
+      args.push('--field-selector', spec.fieldSelector);
+    }
+    args.push('--watch', '--output-watch-events', '-o', 'json');
+
+    let child: ChildProcessWithoutNullStreams;
+    try {
+      child = spawnKubectl(spec.context, args, spec.allNamespaces ? undefined : spec.namespace);
+    } catch (error) {
+      // Reported like a failed spawn: later, so startWatch's caller has the id first
+      const message = error instanceof Error ? error.message : String(error);
+      setImmediate(() => this.fail(id, handle, message));
+      return;
+    }
+    const parser = new JsonObjectStream();
+    let stderr = '';
+
+    handle.process = child;
+    handle.reconnector.connected();
+
+    child.stdout.on('data', (chunk) => {
+      for (const value of parser.push(chunk.toString())) {
+        const event = value as WatchEvent;
+        if (!event || typeof event.type !== 'string') {
+          continue;
+        }
+        const attempts = handle.reconnector.reset();
+        if (attempts > 0) {
+          console.log(`[Watch ${id}] Reconnected after ${attempts} attempt(s)`);
+          const reconnected: StreamReconnectedEvent = { id, attempts };
+          emitToRenderer('watch:reconnected', reconnected);
+        }
+        handle.onEvent(event);
+      }
+    });
+
+    child.stderr.on('data', (chunk) => {
+      stderr += chunk.toString();
+    });
+
+    child.once('error', (error: NodeJS.ErrnoException) => {
+      const message =
+        error?.code === 'ENOENT'
+          ? 'kubectl executable not found. Install kubectl or add it to your PATH.'
+          : error.message;
+      this.fail(id, handle, message);
+    });
+
+    child.once('close', (code) => {
+      handle.process = null;
+      if (handle.stopped) {
+        return;
+      }
+
+      // A clean exit is the server ending the watch; reopen right away
+      if (code === 0) {
+        handle.reopenTimer = setTimeout(() => {
+          handle.reopenTimer = null;
+          if (!handle.stopped) {
+            console.log(`[Watch ${id}] Reopening`);
+            this.open(id, handle);
+          }
+        }, REOPEN_DELAY_MS);
+        return;
+      }
+
+      // Dropped: back off, and give up once the attempts are used up
+      const message = stderr.trim() || `kubectl watch exited with code ${code}`;
+      const delayMs = handle.reconnector.schedule(() => {
+        if (!handle.stopped) {
+          this.open(id, handle);
+        }
+      });
+      if (delayMs === null) {
+        this.fail(id, handle, message);
+        return;
+      }
+
+      const event: StreamReconnectingEvent = {
+        id,
+        attempt: handle.reconnector.attempts,
+        maxAttempts: handle.reconnector.policy.maxAttempts,
+        delayMs,
+        message,
+      };
+      console.warn(`[Watch ${id}] Dropped (${message}), reconnecting in ${delayMs}ms`);
+      emitToRenderer('watch:reconnecting', event);
+    });
+  }
+
+  private fail(id: string, handle: WatchHandle, message: string): void {
+    if (handle.stopped) {
+      return;
+    }
+    console.error(`[Watch ${id}] Failed: ${message}`);
+    this.stopWatch(id);
+    handle.onError?.(message);
+  }
+}
+
+// Singleton instance
+export const watchManager = new WatchManager();
@@ -0,0 +1,130 @@
+import type { WorkloadHealth, WorkloadHealthReport, WorkloadKind } from '../common/kubeTypes';
+import { getKubectlJson } from './kube';
+
+const WORKLOAD_KINDS: WorkloadKind[] = ['deployment', 'statefulset', 'daemonset'];
+
+interface ReplicaCounts {
+  desired: number;
+  ready: number;
+  available: number;
+  updated: number;
+  rollingOut: boolean;
+}
+
+function count(value: any): number {
+  return typeof value === 'number' ? value : 0;
+}
+
+function replicaCounts(kind: WorkloadKind, item: any): ReplicaCounts {
+  const spec = item?.spec ?? {};
+  const status = item?.status ?? {};
+  // The controller hasn't seen the latest spec yet
+  const stale = count(status.observedGeneration) < count(item?.metadata?.generation);
+
+  if (kind === 'daemonset') {
+    const desired = count(status.desiredNumberScheduled);
+    const updated = count(status.updatedNumberScheduled);
+    return {
+      desired,
+      ready: count(status.numberReady),
+      available: count(status.numberAvailable),
+      updated,
+      rollingOut: stale || updated < desired,
+    };
+  }
+
+  const desired = typeof spec.replicas === 'number' ? spec.replicas : 1;
+  const ready = count(status.readyReplicas);
+  const updated = count(status.updatedReplicas);
+
+  if (kind === 'statefulset') {
+    return {
+      desired,
+      ready,
+      available: typeof status.availableReplicas === 'number' ? status.availableReplicas : ready,
+      updated,
+      rollingOut:
+        stale || (!!status.updateRevision && status.currentRevision !== status.updateRevision) || updated < desired,
+    };
+  }
+
+  return {
+    desired,
+    ready,
+    available: count(status.availableReplicas),
+    updated,
+    // Old replicas still around alongside the new ones
+    rollingOut: stale || updated < desired || count(status.replicas) > updated,
+  };
+}
+
+/**
+ * Pick the condition that explains a problem: Available=False first, then a
+ * stalled rollout, then any other failure condition
+ */
+function failingCondition(item: any): { reason: string | null; message: string | null } | null {
+  const conditions: any[] = item?.status?.conditions ?? [];
+  const failing =
+    conditions.find((condition) => condition.type === 'Available' && condition.status === 'False') ??
+    conditions.find((condition) => condition.type === 'Progressing' && condition.status === 'False') ??
+    conditions.find((condition) => condition.type === 'ReplicaFailure' && condition.status === 'True');
+
+  return failing ? { reason: failing.reason ?? null, message: failing.message ?? null } : null;
+}
+
+function toWorkloadHealth(kind: WorkloadKind, item: any): WorkloadHealth {
+  const counts = replicaCounts(kind, item);
+  const healthy = counts.ready >= counts.desired && counts.available >= counts.desired;
+  const condition = failingCondition(item);
+
+  return {
+    kind,
+    name: item?.metadata?.name ?? '',
+    namespace: item?.metadata?.namespace ?? '',
+    ...counts,
+    paused: kind === 'deployment' && item?.spec?.paused === true,
+    healthy: healthy && !condition,
+    reason: condition?.reason ?? null,
+    message: condition?.message ?? null,
+  };
+}
+
+/**
+ * Desired vs ready/available replicas for deployments, statefulsets and
+ * daemonsets. Kinds are listed concurrently; a kind that fails (e.g.
+ * forbidden) is reported in `errors` without hiding the others.
+ */
+export async function getWorkloadHealth(namespace: string | undefined, context: string): Promise<WorkloadHealthReport> {
+  const report: WorkloadHealthReport = {
+    context,
+    namespace: namespace ?? null,
+    workloads: [],
+    errors: {},
+  };
+
+  const results = await Promise.allSettled(
+    WORKLOAD_KINDS.map((kind) => {
+      const args = ['get', kind];
+      if (!namespace) {
+        args.push('--all-namespaces');
+      }
+      return getKubectlJson(context, args, namespace);
+    })
+  );
+
+  results.forEach((result, index) => {
+    const kind = WORKLOAD_KINDS[index];
+    if (result.status === 'rejected') {
+      const message = result.reason instanceof Error ? result.reason.message : String(result.reason);
+      console.warn(`[Workloads] Could not list ${kind}s: ${message}`);
+      report.errors[kind] = message;
+      return;
+    }
+
+    for (const item of result.value?.items ?? []) {
+      report.workloads.push(toWorkloadHealth(kind, item));
+    }
+  });
+
+  return report;
+}
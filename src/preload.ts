@@ -1,5 +1,165 @@
 import { contextBridge, ipcRenderer } from 'electron';
-import type { KubeConfigSummary, KubectlResult } from './common/kubeTypes';
+import type {
+  AllContainerLogs,
+  ApiResource,
+  ApplyDoneEvent,
+  ApplyObjectEvent,
+  AuthStatus,
+  BackgroundSession,
+  CancelledOperationCounts,
+  CloudAuthDetection,
+  ClusterCertStatus,
+  ClusterDetailEvent,
+  ClusterDetailList,
+  ClusterEvent,
+  ConfigMapContents,
+  ContainerImageStatus,
+  ContainerProbes,
+  ContainerRestartInfo,
+  ContextOverrides,
+  ContextSearchResult,
+  ContextTags,
+  CrdSchema,
+  DeleteItemResult,
+  DeletePreview,
+  DeploymentConditionEvent,
+  DirectoryApplyResult,
+  DiscoveryCacheStatus,
+  EffectivePermissions,
+  EventRateEvent,
+  ExecStdinProgressEvent,
+  ForwardExitedEvent,
+  ForwardInfo,
+  ForwardStatusEvent,
+  HpaStatus,
+  IngressRouting,
+  JobStatusEvent,
+  JsonLogEvent,
+  JsonLogFieldMap,
+  KillPodsResult,
+  KubeconfigImportResult,
+  KubeconfigRepairResult,
+  KubeconfigRepairStrategy,
+  KubeConfigSummary,
+  KubeconfigValidation,
+  KubectlPlugin,
+  KubectlPoolLimits,
+  KubectlPoolStats,
+  KubectlResult,
+  KustomizeApplyResult,
+  LatencySample,
+  LogExportResult,
+  LogGrepResult,
+  LogLevelSettings,
+  LogLinesEvent,
+  LogSink,
+  LogSinkStatus,
+  LogStreamSink,
+  LogStreamEndedEvent,
+  ManifestDiffResult,
+  MergedKubeconfig,
+  MergedLogs,
+  MetricsSample,
+  MetricsTargetKind,
+  MetricsUpdateEvent,
+  NamespaceExportResult,
+  NodeNotReadyEvent,
+  NodeStatus,
+  NodeWatchEvent,
+  OperationScope,
+  OwnerTree,
+  PatchType,
+  PodFileDataEvent,
+  PodFileDownloadEvent,
+  PodFileEndEvent,
+  PodFileErrorEvent,
+  PodKilledEvent,
+  PodPathChangeEvent,
+  PodPhaseEvent,
+  PodResources,
+  PodSpread,
+  PodTableUpdateEvent,
+  PortForwardTargetKind,
+  PvcStatusEvent,
+  RawApiResponse,
+  RawStreamDataEvent,
+  RawStreamEndedEvent,
+  ResolvedContext,
+  ResourceComparison,
+  ResourceDeletedEvent,
+  ResourceDrift,
+  ResourceMetadata,
+  ResourcePage,
+  ResourceRef,
+  ResourceSummary,
+  ResourcesYamlResult,
+  ResourceWatchEvent,
+  RestartLeaderboard,
+  RevisionManifest,
+  RolloutHistory,
+  RolloutPauseState,
+  RolloutProgressEvent,
+  ServerDryRunResult,
+  ServiceAccountKubeconfig,
+  ServiceEndpoints,
+  SessionReattachResult,
+  StorageClassList,
+  StreamReconnectedEvent,
+  StreamReconnectingEvent,
+  TemplateApplyResult,
+  UserIdentity,
+  WaitProgressEvent,
+  WaitResultEvent,
+  WorkloadHealthReport,
+} from './common/kubeTypes';
+import type {
+  ActionLogEntry,
+  ActionLogFilter,
+  CommandPolicy,
+  LogLevel,
+  LogSettings,
+  SelfTestReport,
+} from './common/appTypes';
+import type {
+  AttachToPodOptions,
+  BroadcastGroup,
+  BroadcastWriteResult,
+  CwdCheck,
+  DebugPodOptions,
+  ForegroundProcessInfo,
+  InterruptResult,
+  LastTerminalOutput,
+  LocalCommandExitEvent,
+  LocalCommandSpec,
+  NodeShellOptions,
+  OrphanedProcess,
+  OutputPipeStatus,
+  PtyReaderStats,
+  ReapResult,
+  ResilientExecOptions,
+  ResilientExecStatusEvent,
+  ScriptDoneEvent,
+  ScriptProgressEvent,
+  SequencedCommandResult,
+  SessionBundleSummary,
+  ShellDetection,
+  SshTerminalOptions,
+  TerminalBufferRange,
+  TerminalCreateOptions,
+  TerminalDiagnostics,
+  TerminalInfo,
+  TerminalLatencyMethod,
+  TerminalLatencyResult,
+  TerminalNotifyEvent,
+  TerminalPasteLimits,
+  TerminalProfile,
+  TerminalReconcileReport,
+  TerminalReconnectedEvent,
+  TerminalRestoreResult,
+  TerminalSnapshot,
+  TerminalTextExport,
+  TerminalWebSocketStatus,
+} from './common/terminalTypes';
 
 type SuccessResponse<T> = {
   success: true;
@@ -9,6 +169,8 @@ type SuccessResponse<T> = {
 type ErrorResponse = {
   success: false;
   error: string;
+  code?: string;
+  details?: Record<string, unknown>;
 };
 
 function unwrap<T>(response: SuccessResponse<T> | ErrorResponse): T {
@@ -16,12 +178,19 @@ function unwrap<T>(response: SuccessResponse<T> | ErrorResponse): T {
     return response.data;
   }
 
-  throw new Error(response.error);
+  const error: Error & { code?: string; details?: Record<string, unknown> } = new Error(response.error);
+  if (response.code) {
+    error.code = response.code;
+  }
+  if (response.details) {
+    error.details = response.details;
+  }
+  throw error;
 }
 
 contextBridge.exposeInMainWorld('kube', {
-  getContexts: async (): Promise<KubeConfigSummary> => {
-    const response = await ipcRenderer.invoke('kube:get-contexts');
+  getContexts: async (filter?: string): Promise<KubeConfigSummary> => {
+    const response = await ipcRenderer.invoke('kube:get-contexts', filter);
     return unwrap(response);
   },
   setContext: async (contextName: string): Promise<KubeConfigSummary> => {
@@ -34,34 +203,1122 @@ contextBridge.exposeInMainWorld('kube', {
   },
   runCommand: async (
     context: string,
-    command: string
+    command: string,
+    view?: string
   ): Promise<KubectlResult> => {
     const response = await ipcRenderer.invoke('kube:run-command', {
       context,
       command,
+      view,
     });
 
     return unwrap(response);
   },
+  listApiResources: async (context: string, refresh?: boolean): Promise<ApiResource[]> => {
+    const response = await ipcRenderer.invoke('kube:list-api-resources', { context, refresh });
+    return unwrap(response);
+  },
+  warmCache: async (context: string, refresh?: boolean): Promise<DiscoveryCacheStatus> => {
+    const response = await ipcRenderer.invoke('kube:warm-cache', { context, refresh });
+    return unwrap(response);
+  },
+  clearCache: async (context?: string): Promise<{ cleared: boolean }> => {
+    const response = await ipcRenderer.invoke('kube:clear-cache', context);
+    return unwrap(response);
+  },
+  diffManifest: async (content: string, context: string, namespace?: string): Promise<ManifestDiffResult> => {
+    const response = await ipcRenderer.invoke('kube:diff-manifest', { content, context, namespace });
+    return unwrap(response);
+  },
+  watchPod: async (pod: string, namespace: string, context: string): Promise<{ watchId: string }> => {
+    const response = await ipcRenderer.invoke('kube:watch-pod', { pod, namespace, context });
+    return unwrap(response);
+  },
+  stopWatch: async (watchId: string): Promise<{ stopped: boolean }> => {
+    const response = await ipcRenderer.invoke('kube:stop-watch', watchId);
+    return unwrap(response);
+  },
+  streamLogsForSelector: async (params: {
+    labelSelector: string;
+    namespace: string;
+    container?: string;
+    context: string;
+    tailLines?: number;
+    json?: boolean;
+    jsonFields?: Partial<Record<keyof JsonLogFieldMap, string | string[]>>;
+  }): Promise<{ streamId: string }> => {
+    const response = await ipcRenderer.invoke('kube:stream-logs-for-selector', params);
+    return unwrap(response);
+  },
+  stopLogStream: async (streamId: string): Promise<{ stopped: boolean }> => {
+    const response = await ipcRenderer.invoke('kube:stop-log-stream', streamId);
+    return unwrap(response);
+  },
+  onLogData: (callback: (event: LogLinesEvent) => void) => {
+    const handler = (_event: any, payload: LogLinesEvent) => callback(payload);
+    ipcRenderer.on('logs:data', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('logs:data', handler);
+  },
+  onLogError: (callback: (streamId: string, message: string) => void) => {
+    const handler = (_event: any, streamId: string, message: string) => callback(streamId, message);
+    ipcRenderer.on('logs:error', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('logs:error', handler);
+  },
+  onPodPhase: (callback: (event: PodPhaseEvent) => void) => {
+    const handler = (_event: any, payload: PodPhaseEvent) => callback(payload);
+    ipcRenderer.on('pod:phase', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('pod:phase', handler);
+  },
+  onWatchError: (callback: (watchId: string, message: string) => void) => {
+    const handler = (_event: any, watchId: string, message: string) => callback(watchId, message);
+    ipcRenderer.on('watch:error', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('watch:error', handler);
+  },
+  startMetricsStream: async (params: {
+    context: string;
+    kind: MetricsTargetKind;
+    namespace?: string;
+    intervalMs?: number;
+  }): Promise<{ streamId: string }> => {
+    const response = await ipcRenderer.invoke('kube:start-metrics-stream', params);
+    return unwrap(response);
+  },
+  stopMetricsStream: async (streamId: string): Promise<{ stopped: boolean }> => {
+    const response = await ipcRenderer.invoke('kube:stop-metrics-stream', streamId);
+    return unwrap(response);
+  },
+  getMetricsHistory: async (streamId: string): Promise<Record<string, MetricsSample[]>> => {
+    const response = await ipcRenderer.invoke('kube:get-metrics-history', streamId);
+    return unwrap(response);
+  },
+  onMetricsUpdate: (callback: (event: MetricsUpdateEvent) => void) => {
+    const handler = (_event: any, payload: MetricsUpdateEvent) => callback(payload);
+    ipcRenderer.on('metrics:update', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('metrics:update', handler);
+  },
+  onMetricsError: (callback: (streamId: string, message: string) => void) => {
+    const handler = (_event: any, streamId: string, message: string) => callback(streamId, message);
+    ipcRenderer.on('metrics:error', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('metrics:error', handler);
+  },
+  cancelOperations: async (filter?: OperationScope): Promise<CancelledOperationCounts> => {
+    const response = await ipcRenderer.invoke('kube:cancel-operations', filter);
+    return unwrap(response);
+  },
+  setKubectlForContext: async (context: string, path: string): Promise<{ context: string; path: string | null }> => {
+    const response = await ipcRenderer.invoke('kube:set-kubectl-for-context', { context, path });
+    return unwrap(response);
+  },
+  getKubectlForContext: async (context: string): Promise<{ context: string; path: string | null }> => {
+    const response = await ipcRenderer.invoke('kube:get-kubectl-for-context', context);
+    return unwrap(response);
+  },
+  streamEvents: async (params: {
+    context: string;
+    namespace?: string;
+    warningsOnly?: boolean;
+  }): Promise<{ streamId: string }> => {
+    const response = await ipcRenderer.invoke('kube:stream-events', params);
+    return unwrap(response);
+  },
+  onClusterEvent: (callback: (event: ClusterEvent) => void) => {
+    const handler = (_event: any, payload: ClusterEvent) => callback(payload);
+    ipcRenderer.on('event:new', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('event:new', handler);
+  },
+  startApiProxy: async (params: {
+    context: string;
+    port?: number;
+    autoRestart?: boolean;
+  }): Promise<ForwardInfo> => {
+    const response = await ipcRenderer.invoke('kube:start-api-proxy', params);
+    return unwrap(response);
+  },
+  stopApiProxy: async (id: string): Promise<{ stopped: boolean }> => {
+    const response = await ipcRenderer.invoke('kube:stop-api-proxy', id);
+    return unwrap(response);
+  },
+  listForwards: async (): Promise<ForwardInfo[]> => {
+    const response = await ipcRenderer.invoke('kube:list-forwards');
+    return unwrap(response);
+  },
+  onForwardExited: (callback: (event: ForwardExitedEvent) => void) => {
+    const handler = (_event: any, payload: ForwardExitedEvent) => callback(payload);
+    ipcRenderer.on('forward:exited', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('forward:exited', handler);
+  },
+  onForwardRestarted: (callback: (info: ForwardInfo) => void) => {
+    const handler = (_event: any, info: ForwardInfo) => callback(info);
+    ipcRenderer.on('forward:restarted', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('forward:restarted', handler);
+  },
+  getResourcesYaml: async (params: {
+    refs: ResourceRef[];
+    context: string;
+    stripManagedFields?: boolean;
+    stripStatus?: boolean;
+    neat?: boolean;
+  }): Promise<ResourcesYamlResult> => {
+    const response = await ipcRenderer.invoke('kube:get-resources-yaml', params);
+    return unwrap(response);
+  },
+  runPlugin: async (params: {
+    name: string;
+    args?: string[];
+    context: string;
+    namespace?: string;
+  }): Promise<KubectlResult> => {
+    const response = await ipcRenderer.invoke('kube:run-plugin', params);
+    return unwrap(response);
+  },
+  listPlugins: async (): Promise<KubectlPlugin[]> => {
+    const response = await ipcRenderer.invoke('kube:list-plugins');
+    return unwrap(response);
+  },
+  whoCanI: async (params: {
+    namespace: string;
+    context: string;
+    refresh?: boolean;
+  }): Promise<EffectivePermissions> => {
+    const response = await ipcRenderer.invoke('kube:who-can-i', params);
+    return unwrap(response);
+  },
+  streamPodFile: async (params: {
+    pod: string;
+    namespace: string;
+    container?: string;
+    path: string;
+    context: string;
+    follow?: boolean;
+    tailLines?: number;
+  }): Promise<{ streamId: string }> => {
+    const response = await ipcRenderer.invoke('kube:stream-pod-file', params);
+    return unwrap(response);
+  },
+  stopPodFileStream: async (streamId: string): Promise<{ stopped: boolean }> => {
+    const response = await ipcRenderer.invoke('kube:stop-pod-file-stream', streamId);
+    return unwrap(response);
+  },
+  onFileData: (callback: (event: PodFileDataEvent) => void) => {
+    const handler = (_event: any, payload: PodFileDataEvent) => callback(payload);
+    ipcRenderer.on('file:data', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('file:data', handler);
+  },
+  onFileError: (callback: (event: PodFileErrorEvent) => void) => {
+    const handler = (_event: any, payload: PodFileErrorEvent) => callback(payload);
+    ipcRenderer.on('file:error', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('file:error', handler);
+  },
+  onFileEnd: (callback: (event: PodFileEndEvent) => void) => {
+    const handler = (_event: any, payload: PodFileEndEvent) => callback(payload);
+    ipcRenderer.on('file:end', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('file:end', handler);
+  },
+  getPodImages: async (params: {
+    pod: string;
+    namespace: string;
+    context: string;
+  }): Promise<ContainerImageStatus[]> => {
+    const response = await ipcRenderer.invoke('kube:get-pod-images', params);
+    return unwrap(response);
+  },
+  startPortForward: async (params: {
+    context: string;
+    namespace: string;
+    targetKind: PortForwardTargetKind;
+    name: string;
+    remotePort: number;
+    localPort?: number;
+    autoRestart?: boolean;
+  }): Promise<ForwardInfo> => {
+    const response = await ipcRenderer.invoke('kube:start-port-forward', params);
+    return unwrap(response);
+  },
+  stopPortForward: async (id: string): Promise<{ stopped: boolean }> => {
+    const response = await ipcRenderer.invoke('kube:stop-port-forward', id);
+    return unwrap(response);
+  },
+  onForwardStatus: (callback: (event: ForwardStatusEvent) => void) => {
+    const handler = (_event: any, payload: ForwardStatusEvent) => callback(payload);
+    ipcRenderer.on('forward:status', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('forward:status', handler);
+  },
+  getNodeStatus: async (params: { name: string; context: string }): Promise<NodeStatus> => {
+    const response = await ipcRenderer.invoke('kube:get-node-status', params);
+    return unwrap(response);
+  },
+  getAuthStatus: async (contextName: string): Promise<AuthStatus> => {
+    const response = await ipcRenderer.invoke('kube:get-auth-status', contextName);
+    return unwrap(response);
+  },
+  watchSelected: async (params: {
+    kind: string;
+    labelSelector?: string;
+    fieldSelector?: string;
+    namespace?: string;
+    context: string;
+    coalesceMs?: number;
+  }): Promise<{ watchId: string }> => {
+    const response = await ipcRenderer.invoke('kube:watch-selected', params);
+    return unwrap(response);
+  },
+  onWatchEvent: (callback: (event: ResourceWatchEvent) => void) => {
+    const handler = (_event: any, payload: ResourceWatchEvent) => callback(payload);
+    ipcRenderer.on('watch:event', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('watch:event', handler);
+  },
+  getPodLogs: async (params: {
+    context: string;
+    pod: string;
+    namespace: string;
+    container?: string;
+    previous?: boolean;
+    tailLines?: number;
+  }): Promise<{ logs: string }> => {
+    const response = await ipcRenderer.invoke('kube:get-pod-logs', params);
+    return unwrap(response);
+  },
+  getPreviousLogs: async (params: {
+    context: string;
+    pod: string;
+    namespace: string;
+    container?: string;
+    tailLines?: number;
+  }): Promise<{ logs: string }> => {
+    const response = await ipcRenderer.invoke('kube:get-previous-logs', params);
+    return unwrap(response);
+  },
+  getResourceSummary: async (contextName: string): Promise<ResourceSummary> => {
+    const response = await ipcRenderer.invoke('kube:get-resource-summary', contextName);
+    return unwrap(response);
+  },
+  pauseLogStream: async (params: {
+    streamId: string;
+    paused: boolean;
+  }): Promise<{ paused: boolean; flushedLines: number }> => {
+    const response = await ipcRenderer.invoke('kube:pause-log-stream', params);
+    return unwrap(response);
+  },
+  deleteBySelector: async (params: {
+    kind: string;
+    labelSelector: string;
+    namespace?: string;
+    context: string;
+    confirmToken?: string;
+    gracePeriodSeconds?: number;
+  }): Promise<{ preview?: DeletePreview; results?: DeleteItemResult[] }> => {
+    const response = await ipcRenderer.invoke('kube:delete-by-selector', params);
+    return unwrap(response);
+  },
+  onResourceDeleted: (callback: (event: ResourceDeletedEvent) => void) => {
+    const handler = (_event: any, payload: ResourceDeletedEvent) => callback(payload);
+    ipcRenderer.on('resource:deleted', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('resource:deleted', handler);
+  },
+  getWorkloadHealth: async (params: { namespace?: string; context: string }): Promise<WorkloadHealthReport> => {
+    const response = await ipcRenderer.invoke('kube:get-workload-health', params);
+    return unwrap(response);
+  },
+  watchPodsTable: async (params: {
+    namespace?: string;
+    context: string;
+    node?: string;
+    view?: string;
+  }): Promise<{ watchId: string }> => {
+    const response = await ipcRenderer.invoke('kube:watch-pods-table', params);
+    return unwrap(response);
+  },
+  onPodsTableUpdate: (callback: (event: PodTableUpdateEvent) => void) => {
+    const handler = (_event: any, payload: PodTableUpdateEvent) => callback(payload);
+    ipcRenderer.on('pods-table:update', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('pods-table:update', handler);
+  },
+  serverDryRunApply: async (params: {
+    content: string;
+    context: string;
+    namespace?: string;
+  }): Promise<ServerDryRunResult> => {
+    const response = await ipcRenderer.invoke('kube:server-dry-run-apply', params);
+    return unwrap(response);
+  },
+  getContainerRestarts: async (params: {
+    pod: string;
+    namespace: string;
+    context: string;
+  }): Promise<ContainerRestartInfo[]> => {
+    const response = await ipcRenderer.invoke('kube:get-container-restarts', params);
+    return unwrap(response);
+  },
+  applyManifestStreaming: async (params: {
+    content: string;
+    context: string;
+    namespace?: string;
+    continueOnError?: boolean;
+    parallelism?: number;
+    dryRun?: boolean;
+    view?: string;
+  }): Promise<{ applyId: string; total: number }> => {
+    const response = await ipcRenderer.invoke('kube:apply-manifest-streaming', params);
+    return unwrap(response);
+  },
+  onApplyObject: (callback: (event: ApplyObjectEvent) => void) => {
+    const handler = (_event: any, payload: ApplyObjectEvent) => callback(payload);
+    ipcRenderer.on('apply:object', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('apply:object', handler);
+  },
+  onApplyDone: (callback: (event: ApplyDoneEvent) => void) => {
+    const handler = (_event: any, payload: ApplyDoneEvent) => callback(payload);
+    ipcRenderer.on('apply:done', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('apply:done', handler);
+  },
+  getRolloutHistory: async (params: {
+    kind: string;
+    name: string;
+    namespace: string;
+    context: string;
+  }): Promise<RolloutHistory> => {
+    const response = await ipcRenderer.invoke('kube:get-rollout-history', params);
+    return unwrap(response);
+  },
+  rolloutUndo: async (params: {
+    kind: string;
+    name: string;
+    namespace: string;
+    context: string;
+    toRevision?: number;
+    view?: string;
+  }): Promise<{ rolloutId: string; revision: number | null; message: string }> => {
+    const response = await ipcRenderer.invoke('kube:rollout-undo', params);
+    return unwrap(response);
+  },
+  onRolloutProgress: (callback: (event: RolloutProgressEvent) => void) => {
+    const handler = (_event: any, payload: RolloutProgressEvent) => callback(payload);
+    ipcRenderer.on('rollout:progress', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('rollout:progress', handler);
+  },
+  startLatencyProbe: async (params: {
+    context: string;
+    intervalMs?: number;
+    view?: string;
+  }): Promise<{ probeId: string }> => {
+    const response = await ipcRenderer.invoke('kube:start-latency-probe', params);
+    return unwrap(response);
+  },
+  stopLatencyProbe: async (probeId: string): Promise<{ stopped: boolean }> => {
+    const response = await ipcRenderer.invoke('kube:stop-latency-probe', probeId);
+    return unwrap(response);
+  },
+  getLatencyHistory: async (probeId: string): Promise<LatencySample[]> => {
+    const response = await ipcRenderer.invoke('kube:get-latency-history', probeId);
+    return unwrap(response);
+  },
+  onLatencySample: (callback: (sample: LatencySample) => void) => {
+    const handler = (_event: any, sample: LatencySample) => callback(sample);
+    ipcRenderer.on('latency:sample', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('latency:sample', handler);
+  },
+  resolveContext: async (params: {
+    name: string;
+    kubeconfigPath?: string;
+    overrides?: ContextOverrides;
+  }): Promise<ResolvedContext> => {
+    const response = await ipcRenderer.invoke('kube:resolve-context', params);
+    return unwrap(response);
+  },
+  getAllContainerLogs: async (params: {
+    context: string;
+    pod: string;
+    namespace: string;
+    tailLines?: number;
+    includeInit?: boolean;
+  }): Promise<AllContainerLogs> => {
+    const response = await ipcRenderer.invoke('kube:get-all-container-logs', params);
+    return unwrap(response);
+  },
+  watchPvc: async (name: string, namespace: string, context: string): Promise<{ watchId: string }> => {
+    const response = await ipcRenderer.invoke('kube:watch-pvc', { name, namespace, context });
+    return unwrap(response);
+  },
+  onPvcStatus: (callback: (event: PvcStatusEvent) => void) => {
+    const handler = (_event: any, payload: PvcStatusEvent) => callback(payload);
+    ipcRenderer.on('pvc:status', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('pvc:status', handler);
+  },
+  getRevisionManifest: async (params: {
+    kind: string;
+    name: string;
+    namespace: string;
+    revision: number;
+    context: string;
+  }): Promise<RevisionManifest> => {
+    const response = await ipcRenderer.invoke('kube:get-revision-manifest', params);
+    return unwrap(response);
+  },
+  downloadPodFile: async (params: {
+    context: string;
+    pod: string;
+    namespace: string;
+    container?: string;
+    remotePath: string;
+    localPath: string;
+    view?: string;
+  }): Promise<{ downloadId: string }> => {
+    const response = await ipcRenderer.invoke('kube:download-pod-file', params);
+    return unwrap(response);
+  },
+  onDownloadProgress: (callback: (event: PodFileDownloadEvent) => void) => {
+    const handler = (_event: any, payload: PodFileDownloadEvent) => callback(payload);
+    ipcRenderer.on('download:progress', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('download:progress', handler);
+  },
+  listClustersDetailed: async (kubeconfigPath?: string): Promise<ClusterDetailList> => {
+    const response = await ipcRenderer.invoke('kube:list-clusters-detailed', kubeconfigPath);
+    return unwrap(response);
+  },
+  onClusterDetail: (callback: (event: ClusterDetailEvent) => void) => {
+    const handler = (_event: any, payload: ClusterDetailEvent) => callback(payload);
+    ipcRenderer.on('cluster:detail', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('cluster:detail', handler);
+  },
+  runKubectlStdin: async (params: {
+    args: string[];
+    stdin: string;
+    context: string;
+    namespace?: string;
+    timeoutMs?: number;
+  }): Promise<KubectlResult> => {
+    const response = await ipcRenderer.invoke('kube:run-kubectl-stdin', params);
+    return unwrap(response);
+  },
+  rolloutPause: async (params: {
+    kind: string;
+    name: string;
+    namespace: string;
+    context: string;
+  }): Promise<RolloutPauseState> => {
+    const response = await ipcRenderer.invoke('kube:rollout-pause', params);
+    return unwrap(response);
+  },
+  rolloutResume: async (params: {
+    kind: string;
+    name: string;
+    namespace: string;
+    context: string;
+  }): Promise<RolloutPauseState> => {
+    const response = await ipcRenderer.invoke('kube:rollout-resume', params);
+    return unwrap(response);
+  },
+  onWatchReconnecting: (callback: (event: StreamReconnectingEvent) => void) => {
+    const handler = (_event: any, payload: StreamReconnectingEvent) => callback(payload);
+    ipcRenderer.on('watch:reconnecting', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('watch:reconnecting', handler);
+  },
+  onWatchReconnected: (callback: (event: StreamReconnectedEvent) => void) => {
+    const handler = (_event: any, payload: StreamReconnectedEvent) => callback(payload);
+    ipcRenderer.on('watch:reconnected', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('watch:reconnected', handler);
+  },
+  onLogReconnecting: (callback: (event: StreamReconnectingEvent) => void) => {
+    const handler = (_event: any, payload: StreamReconnectingEvent) => callback(payload);
+    ipcRenderer.on('logs:reconnecting', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('logs:reconnecting', handler);
+  },
+  onLogReconnected: (callback: (event: StreamReconnectedEvent) => void) => {
+    const handler = (_event: any, payload: StreamReconnectedEvent) => callback(payload);
+    ipcRenderer.on('logs:reconnected', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('logs:reconnected', handler);
+  },
+  onForwardReconnecting: (callback: (event: StreamReconnectingEvent) => void) => {
+    const handler = (_event: any, payload: StreamReconnectingEvent) => callback(payload);
+    ipcRenderer.on('forward:reconnecting', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('forward:reconnecting', handler);
+  },
+  onForwardReconnected: (callback: (event: StreamReconnectedEvent) => void) => {
+    const handler = (_event: any, payload: StreamReconnectedEvent) => callback(payload);
+    ipcRenderer.on('forward:reconnected', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('forward:reconnected', handler);
+  },
+  getMergedKubeconfig: async (): Promise<MergedKubeconfig> => {
+    const response = await ipcRenderer.invoke('kube:get-merged-kubeconfig');
+    return unwrap(response);
+  },
+  onLogJson: (callback: (event: JsonLogEvent) => void) => {
+    const handler = (_event: any, payload: JsonLogEvent) => callback(payload);
+    ipcRenderer.on('logs:json', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('logs:json', handler);
+  },
+  getOwnerTree: async (params: {
+    kind: string;
+    name: string;
+    namespace: string;
+    context: string;
+    includeDescendants?: boolean;
+    maxDepth?: number;
+  }): Promise<OwnerTree> => {
+    const response = await ipcRenderer.invoke('kube:get-owner-tree', params);
+    return unwrap(response);
+  },
+  killPods: async (params: {
+    labelSelector: string;
+    namespace: string;
+    context: string;
+    gracePeriodSeconds?: number;
+    confirm?: boolean;
+    dryRun?: boolean;
+  }): Promise<KillPodsResult> => {
+    const response = await ipcRenderer.invoke('kube:kill-pods', params);
+    return unwrap(response);
+  },
+  onPodKilled: (callback: (event: PodKilledEvent) => void) => {
+    const handler = (_event: any, payload: PodKilledEvent) => callback(payload);
+    ipcRenderer.on('pod:killed', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('pod:killed', handler);
+  },
+  getCrdSchema: async (params: {
+    group: string;
+    version: string;
+    kind: string;
+    context: string;
+    refresh?: boolean;
+  }): Promise<CrdSchema> => {
+    const response = await ipcRenderer.invoke('kube:get-crd-schema', params);
+    return unwrap(response);
+  },
+  getMergedLogs: async (params: {
+    context: string;
+    pod: string;
+    namespace: string;
+    tailLines?: number;
+    includeInit?: boolean;
+  }): Promise<MergedLogs> => {
+    const response = await ipcRenderer.invoke('kube:get-merged-logs', params);
+    return unwrap(response);
+  },
+  detectCloudAuth: async (contextName: string): Promise<CloudAuthDetection> => {
+    const response = await ipcRenderer.invoke('kube:detect-cloud-auth', contextName);
+    return unwrap(response);
+  },
+  grepLogs: async (params: {
+    context: string;
+    namespace: string;
+    labelSelector: string;
+    pattern: string;
+    ignoreCase?: boolean;
+    container?: string;
+    tailLines?: number;
+    contextLines?: number;
+  }): Promise<LogGrepResult> => {
+    const response = await ipcRenderer.invoke('kube:grep-logs', params);
+    return unwrap(response);
+  },
+  patchResource: async (params: {
+    kind: string;
+    name: string;
+    namespace?: string;
+    patch: string | object;
+    patchType?: PatchType;
+    context: string;
+  }): Promise<any> => {
+    const response = await ipcRenderer.invoke('kube:patch-resource', params);
+    return unwrap(response);
+  },
+  watchJob: async (name: string, namespace: string, context: string): Promise<{ watchId: string }> => {
+    const response = await ipcRenderer.invoke('kube:watch-job', { name, namespace, context });
+    return unwrap(response);
+  },
+  triggerCronJob: async (
+    name: string,
+    namespace: string,
+    context: string
+  ): Promise<{ jobName: string; watchId: string }> => {
+    const response = await ipcRenderer.invoke('kube:trigger-cronjob', { name, namespace, context });
+    return unwrap(response);
+  },
+  onJobStatus: (callback: (event: JobStatusEvent) => void) => {
+    const handler = (_event: any, payload: JobStatusEvent) => callback(payload);
+    ipcRenderer.on('job:status', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('job:status', handler);
+  },
+  getPodProbes: async (params: { pod: string; namespace: string; context: string }): Promise<ContainerProbes[]> => {
+    const response = await ipcRenderer.invoke('kube:get-pod-probes', params);
+    return unwrap(response);
+  },
+  execWithStdinFile: async (params: {
+    context: string;
+    pod: string;
+    namespace: string;
+    container?: string;
+    command: string[];
+    stdinPath: string;
+    view?: string;
+  }): Promise<{ execId: string; totalBytes: number }> => {
+    const response = await ipcRenderer.invoke('kube:exec-with-stdin-file', params);
+    return unwrap(response);
+  },
+  onExecProgress: (callback: (event: ExecStdinProgressEvent) => void) => {
+    const handler = (_event: any, payload: ExecStdinProgressEvent) => callback(payload);
+    ipcRenderer.on('exec:progress', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('exec:progress', handler);
+  },
+  compareResourceAcross: async (params: {
+    kind: string;
+    name: string;
+    a: { context: string; namespace?: string };
+    b: { context: string; namespace?: string };
+  }): Promise<ResourceComparison> => {
+    const response = await ipcRenderer.invoke('kube:compare-resource-across', params);
+    return unwrap(response);
+  },
+  searchContexts: async (params: {
+    pattern: string;
+    fuzzy?: boolean;
+    limit?: number;
+  }): Promise<ContextSearchResult> => {
+    const response = await ipcRenderer.invoke('kube:search-contexts', params);
+    return unwrap(response);
+  },
+  waitForCondition: async (params: {
+    context: string;
+    kind: string;
+    name: string;
+    namespace?: string;
+    condition: string;
+    timeoutSeconds?: number;
+    view?: string;
+  }): Promise<{ waitId: string }> => {
+    const response = await ipcRenderer.invoke('kube:wait-for-condition', params);
+    return unwrap(response);
+  },
+  cancelWait: async (waitId: string): Promise<{ cancelled: boolean }> => {
+    const response = await ipcRenderer.invoke('kube:cancel-wait', waitId);
+    return unwrap(response);
+  },
+  onWaitProgress: (callback: (event: WaitProgressEvent) => void) => {
+    const handler = (_event: any, payload: WaitProgressEvent) => callback(payload);
+    ipcRenderer.on('wait:progress', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('wait:progress', handler);
+  },
+  onWaitResult: (callback: (event: WaitResultEvent) => void) => {
+    const handler = (_event: any, payload: WaitResultEvent) => callback(payload);
+    ipcRenderer.on('wait:result', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('wait:result', handler);
+  },
+  setLabels: async (params: {
+    kind: string;
+    name: string;
+    namespace?: string;
+    labels: Record<string, string | null>;
+    overwrite?: boolean;
+    context: string;
+  }): Promise<ResourceMetadata> => {
+    const response = await ipcRenderer.invoke('kube:set-labels', params);
+    return unwrap(response);
+  },
+  setAnnotations: async (params: {
+    kind: string;
+    name: string;
+    namespace?: string;
+    annotations: Record<string, string | null>;
+    overwrite?: boolean;
+    context: string;
+  }): Promise<ResourceMetadata> => {
+    const response = await ipcRenderer.invoke('kube:set-annotations', params);
+    return unwrap(response);
+  },
+  getPodResources: async (params: { pod: string; namespace: string; context: string }): Promise<PodResources> => {
+    const response = await ipcRenderer.invoke('kube:get-pod-resources', params);
+    return unwrap(response);
+  },
+  watchPodPath: async (params: {
+    context: string;
+    pod: string;
+    namespace: string;
+    container?: string;
+    path: string;
+    intervalMs?: number;
+    view?: string;
+  }): Promise<{ watchId: string }> => {
+    const response = await ipcRenderer.invoke('kube:watch-pod-path', params);
+    return unwrap(response);
+  },
+  stopPodPathWatch: async (watchId: string): Promise<{ stopped: boolean }> => {
+    const response = await ipcRenderer.invoke('kube:stop-pod-path-watch', watchId);
+    return unwrap(response);
+  },
+  onPodPathChange: (callback: (event: PodPathChangeEvent) => void) => {
+    const handler = (_event: any, payload: PodPathChangeEvent) => callback(payload);
+    ipcRenderer.on('fs:change', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('fs:change', handler);
+  },
+  setLogSink: async (params: { streamId: string; sink: LogSink; filePath?: string }): Promise<LogSinkStatus> => {
+    const response = await ipcRenderer.invoke('kube:set-log-sink', params);
+    return unwrap(response);
+  },
+  listLogSinks: async (): Promise<LogStreamSink[]> => {
+    const response = await ipcRenderer.invoke('kube:list-log-sinks');
+    return unwrap(response);
+  },
+  setContextTags: async (params: { name: string; tags: string[] | null }): Promise<ContextTags> => {
+    const response = await ipcRenderer.invoke('kube:set-context-tags', params);
+    return unwrap(response);
+  },
+  getHpaStatus: async (params: { name: string; namespace: string; context: string }): Promise<HpaStatus> => {
+    const response = await ipcRenderer.invoke('kube:get-hpa-status', params);
+    return unwrap(response);
+  },
+  watchNodes: async (params: {
+    context: string;
+    labelSelector?: string;
+    fieldSelector?: string;
+    view?: string;
+  }): Promise<{ watchId: string }> => {
+    const response = await ipcRenderer.invoke('kube:watch-nodes', params);
+    return unwrap(response);
+  },
+  onNodeEvent: (callback: (event: NodeWatchEvent) => void) => {
+    const handler = (_event: any, payload: NodeWatchEvent) => callback(payload);
+    ipcRenderer.on('node:event', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('node:event', handler);
+  },
+  onNodeNotReady: (callback: (event: NodeNotReadyEvent) => void) => {
+    const handler = (_event: any, payload: NodeNotReadyEvent) => callback(payload);
+    ipcRenderer.on('node:notready', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('node:notready', handler);
+  },
+  applyTemplate: async (params: {
+    template: string;
+    variables: Record<string, string>;
+    context: string;
+    namespace?: string;
+    dryRun?: boolean;
+    view?: string;
+  }): Promise<TemplateApplyResult> => {
+    const response = await ipcRenderer.invoke('kube:apply-template', params);
+    return unwrap(response);
+  },
+  validateKubeconfig: async (): Promise<KubeconfigValidation> => {
+    const response = await ipcRenderer.invoke('kube:validate-kubeconfig');
+    return unwrap(response);
+  },
+  repairKubeconfig: async (strategy: KubeconfigRepairStrategy): Promise<KubeconfigRepairResult> => {
+    const response = await ipcRenderer.invoke('kube:repair-kubeconfig', strategy);
+    return unwrap(response);
+  },
+  streamJobLogs: async (params: {
+    context: string;
+    namespace: string;
+    job: string;
+    container?: string;
+    tailLines?: number;
+    view?: string;
+  }): Promise<{ streamId: string }> => {
+    const response = await ipcRenderer.invoke('kube:stream-job-logs', params);
+    return unwrap(response);
+  },
+  onLogStreamEnded: (callback: (event: LogStreamEndedEvent) => void) => {
+    const handler = (_event: any, payload: LogStreamEndedEvent) => callback(payload);
+    ipcRenderer.on('logs:ended', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('logs:ended', handler);
+  },
+  whoAmI: async (context: string): Promise<UserIdentity> => {
+    const response = await ipcRenderer.invoke('kube:whoami', context);
+    return unwrap(response);
+  },
+  getServiceEndpoints: async (name: string, namespace: string, context: string): Promise<ServiceEndpoints> => {
+    const response = await ipcRenderer.invoke('kube:get-service-endpoints', { name, namespace, context });
+    return unwrap(response);
+  },
+  exportLogs: async (params: {
+    context: string;
+    pod: string;
+    namespace: string;
+    container?: string;
+    since: string;
+    until?: string;
+    outputPath?: string;
+  }): Promise<LogExportResult> => {
+    const response = await ipcRenderer.invoke('kube:export-logs', params);
+    return unwrap(response);
+  },
+  getConfigMap: async (params: { name: string; namespace: string; context: string }): Promise<ConfigMapContents> => {
+    const response = await ipcRenderer.invoke('kube:get-configmap', params);
+    return unwrap(response);
+  },
+  updateConfigMapKey: async (params: {
+    name: string;
+    namespace: string;
+    key: string;
+    value: string;
+    context: string;
+  }): Promise<ConfigMapContents> => {
+    const response = await ipcRenderer.invoke('kube:update-configmap-key', params);
+    return unwrap(response);
+  },
+  startEventRateMonitor: async (params: {
+    context: string;
+    namespace?: string;
+    windowMs?: number;
+    view?: string;
+  }): Promise<{ monitorId: string }> => {
+    const response = await ipcRenderer.invoke('kube:start-event-rate-monitor', params);
+    return unwrap(response);
+  },
+  stopEventRateMonitor: async (monitorId: string): Promise<{ stopped: boolean }> => {
+    const response = await ipcRenderer.invoke('kube:stop-event-rate-monitor', monitorId);
+    return unwrap(response);
+  },
+  onEventRate: (callback: (event: EventRateEvent) => void) => {
+    const handler = (_event: any, payload: EventRateEvent) => callback(payload);
+    ipcRenderer.on('event:rate', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('event:rate', handler);
+  },
+  getIngress: async (params: { name: string; namespace: string; context: string }): Promise<IngressRouting> => {
+    const response = await ipcRenderer.invoke('kube:get-ingress', params);
+    return unwrap(response);
+  },
+  formatAges: async (timestamps: (string | null)[]): Promise<string[]> => {
+    const response = await ipcRenderer.invoke('kube:format-ages', timestamps);
+    return unwrap(response);
+  },
+  exportNamespace: async (params: {
+    namespace: string;
+    context: string;
+    outputDir: string;
+    kinds?: string[];
+  }): Promise<NamespaceExportResult> => {
+    const response = await ipcRenderer.invoke('kube:export-namespace', params);
+    return unwrap(response);
+  },
+  applyDirectory: async (params: {
+    dir: string;
+    context: string;
+    namespace?: string;
+  }): Promise<DirectoryApplyResult> => {
+    const response = await ipcRenderer.invoke('kube:apply-directory', params);
+    return unwrap(response);
+  },
+  getKubectlPoolStats: async (): Promise<KubectlPoolStats> => {
+    const response = await ipcRenderer.invoke('kube:get-kubectl-pool-stats');
+    return unwrap(response);
+  },
+  setKubectlPoolLimits: async (limits: Partial<KubectlPoolLimits>): Promise<KubectlPoolStats> => {
+    const response = await ipcRenderer.invoke('kube:set-kubectl-pool-limits', limits);
+    return unwrap(response);
+  },
+  checkClusterCert: async (contextName: string): Promise<ClusterCertStatus> => {
+    const response = await ipcRenderer.invoke('kube:check-cluster-cert', contextName);
+    return unwrap(response);
+  },
+  getDrift: async (params: {
+    kind: string;
+    name: string;
+    namespace?: string;
+    context: string;
+  }): Promise<ResourceDrift> => {
+    const response = await ipcRenderer.invoke('kube:get-drift', params);
+    return unwrap(response);
+  },
+  getLogLevelSettings: async (): Promise<LogLevelSettings> => {
+    const response = await ipcRenderer.invoke('kube:get-log-level-settings');
+    return unwrap(response);
+  },
+  setLogLevelSettings: async (settings: Partial<LogLevelSettings>): Promise<LogLevelSettings> => {
+    const response = await ipcRenderer.invoke('kube:set-log-level-settings', settings);
+    return unwrap(response);
+  },
+  promoteToBackground: async (params: { id: string; label?: string; view?: string }): Promise<BackgroundSession> => {
+    const response = await ipcRenderer.invoke('kube:promote-to-background', params);
+    return unwrap(response);
+  },
+  listBackgroundSessions: async (): Promise<BackgroundSession[]> => {
+    const response = await ipcRenderer.invoke('kube:list-background-sessions');
+    return unwrap(response);
+  },
+  reattachSession: async (id: string, view?: string): Promise<SessionReattachResult> => {
+    const response = await ipcRenderer.invoke('kube:reattach-session', id, view);
+    return unwrap(response);
+  },
+  closeBackgroundSession: async (id: string): Promise<{ closed: boolean }> => {
+    const response = await ipcRenderer.invoke('kube:close-background-session', id);
+    return unwrap(response);
+  },
+  listResourcesPaged: async (spec: {
+    context: string;
+    kind: string;
+    namespace?: string;
+    limit?: number;
+    continueToken?: string;
+    labelSelector?: string;
+    fieldSelector?: string;
+    view?: string;
+  }): Promise<ResourcePage> => {
+    const response = await ipcRenderer.invoke('kube:list-resources-paged', spec);
+    return unwrap(response);
+  },
+  generateServiceAccountKubeconfig: async (spec: {
+    serviceAccount: string;
+    namespace: string;
+    context: string;
+    outputPath?: string;
+    durationSeconds?: number;
+  }): Promise<ServiceAccountKubeconfig> => {
+    const response = await ipcRenderer.invoke('kube:generate-sa-kubeconfig', spec);
+    return unwrap(response);
+  },
+  startRestartLeaderboard: async (params: {
+    context: string;
+    namespace?: string;
+    limit?: number;
+    view?: string;
+  }): Promise<{ leaderboardId: string }> => {
+    const response = await ipcRenderer.invoke('kube:start-restart-leaderboard', params);
+    return unwrap(response);
+  },
+  getRestartLeaderboard: async (leaderboardId: string): Promise<RestartLeaderboard> => {
+    const response = await ipcRenderer.invoke('kube:get-restart-leaderboard', leaderboardId);
+    return unwrap(response);
+  },
+  stopRestartLeaderboard: async (leaderboardId: string): Promise<{ stopped: boolean }> => {
+    const response = await ipcRenderer.invoke('kube:stop-restart-leaderboard', leaderboardId);
+    return unwrap(response);
+  },
+  onRestartsUpdate: (callback: (event: RestartLeaderboard) => void) => {
+    const handler = (_event: any, payload: RestartLeaderboard) => callback(payload);
+    ipcRenderer.on('restarts:update', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('restarts:update', handler);
+  },
+  getRaw: async (params: { path: string; context: string; view?: string }): Promise<RawApiResponse> => {
+    const response = await ipcRenderer.invoke('kube:get-raw', params);
+    return unwrap(response);
+  },
+  streamRaw: async (spec: { path: string; context: string; view?: string }): Promise<{ streamId: string }> => {
+    const response = await ipcRenderer.invoke('kube:stream-raw', spec);
+    return unwrap(response);
+  },
+  stopRawStream: async (streamId: string): Promise<{ stopped: boolean }> => {
+    const response = await ipcRenderer.invoke('kube:stop-raw-stream', streamId);
+    return unwrap(response);
+  },
+  onRawData: (callback: (event: RawStreamDataEvent) => void) => {
+    const handler = (_event: any, payload: RawStreamDataEvent) => callback(payload);
+    ipcRenderer.on('raw:data', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('raw:data', handler);
+  },
+  onRawEnded: (callback: (event: RawStreamEndedEvent) => void) => {
+    const handler = (_event: any, payload: RawStreamEndedEvent) => callback(payload);
+    ipcRenderer.on('raw:ended', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('raw:ended', handler);
+  },
+  getWorkloadPodSpread: async (params: {
+    kind: string;
+    name: string;
+    namespace: string;
+    context: string;
+  }): Promise<PodSpread> => {
+    const response = await ipcRenderer.invoke('kube:get-workload-pod-spread', params);
+    return unwrap(response);
+  },
+  watchDeployment: async (name: string, namespace: string, context: string): Promise<{ watchId: string }> => {
+    const response = await ipcRenderer.invoke('kube:watch-deployment', { name, namespace, context });
+    return unwrap(response);
+  },
+  onDeploymentCondition: (callback: (event: DeploymentConditionEvent) => void) => {
+    const handler = (_event: any, payload: DeploymentConditionEvent) => callback(payload);
+    ipcRenderer.on('deployment:condition', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('deployment:condition', handler);
+  },
+  listStorageClasses: async (context: string): Promise<StorageClassList> => {
+    const response = await ipcRenderer.invoke('kube:list-storage-classes', context);
+    return unwrap(response);
+  },
+  applyKustomize: async (params: {
+    directory: string;
+    context: string;
+    namespace?: string;
+    buildOnly?: boolean;
+    dryRun?: boolean;
+    continueOnError?: boolean;
+    parallelism?: number;
+    view?: string;
+  }): Promise<KustomizeApplyResult> => {
+    const response = await ipcRenderer.invoke('kube:apply-kustomize', params);
+    return unwrap(response);
+  },
+  importKubeconfig: async (params: { sourcePath: string; merge?: boolean }): Promise<KubeconfigImportResult> => {
+    const response = await ipcRenderer.invoke('kube:import-kubeconfig', params);
+    return unwrap(response);
+  },
 });
 
 contextBridge.exposeInMainWorld('terminal', {
-  create: async (id: string, options?: { cwd?: string; env?: Record<string, string> }): Promise<{ id: string }> => {
+  create: async (id: string, options?: TerminalCreateOptions): Promise<{ id: string }> => {
     const response = await ipcRenderer.invoke('terminal:create', id, options);
     return unwrap(response);
   },
+  attachToPod: async (id: string, options: AttachToPodOptions): Promise<{ id: string }> => {
+    const response = await ipcRenderer.invoke('terminal:attach-to-pod', id, options);
+    return unwrap(response);
+  },
   write: async (id: string, data: string): Promise<void> => {
     const response = await ipcRenderer.invoke('terminal:write', id, data);
     unwrap(response);
   },
-  resize: async (id: string, cols: number, rows: number): Promise<void> => {
-    const response = await ipcRenderer.invoke('terminal:resize', id, cols, rows);
+  resize: async (id: string, cols: number, rows: number, pixelWidth?: number, pixelHeight?: number): Promise<void> => {
+    const response = await ipcRenderer.invoke('terminal:resize', id, cols, rows, pixelWidth, pixelHeight);
     unwrap(response);
   },
   close: async (id: string): Promise<void> => {
     const response = await ipcRenderer.invoke('terminal:close', id);
     unwrap(response);
   },
+  exportSessionBundle: async (outputPath: string): Promise<SessionBundleSummary> => {
+    const response = await ipcRenderer.invoke('terminal:export-session-bundle', outputPath);
+    return unwrap(response);
+  },
   onData: (callback: (id: string, data: string) => void) => {
     const handler = (_event: any, id: string, data: string) => callback(id, data);
     ipcRenderer.on('terminal:data', handler);
@@ -74,10 +1331,291 @@ contextBridge.exposeInMainWorld('terminal', {
     // Return cleanup function
     return () => ipcRenderer.removeListener('terminal:exit', handler);
   },
+  onWarning: (callback: (id: string, message: string) => void) => {
+    const handler = (_event: any, id: string, message: string) => callback(id, message);
+    ipcRenderer.on('terminal:warning', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('terminal:warning', handler);
+  },
   onEditMode: (callback: (id: string, isEditMode: boolean) => void) => {
     const handler = (_event: any, id: string, isEditMode: boolean) => callback(id, isEditMode);
     ipcRenderer.on('terminal:edit-mode', handler);
     // Return cleanup function
     return () => ipcRenderer.removeListener('terminal:edit-mode', handler);
   },
+  setClipboardAccess: async (id: string, enabled: boolean): Promise<void> => {
+    const response = await ipcRenderer.invoke('terminal:set-clipboard-access', id, enabled);
+    unwrap(response);
+  },
+  onClipboard: (callback: (id: string, text: string) => void) => {
+    const handler = (_event: any, id: string, text: string) => callback(id, text);
+    ipcRenderer.on('terminal:clipboard', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('terminal:clipboard', handler);
+  },
+  setNotifyPattern: async (id: string, pattern: string): Promise<void> => {
+    const response = await ipcRenderer.invoke('terminal:set-notify-pattern', id, pattern);
+    unwrap(response);
+  },
+  onNotify: (callback: (id: string, event: TerminalNotifyEvent) => void) => {
+    const handler = (_event: any, id: string, payload: TerminalNotifyEvent) => callback(id, payload);
+    ipcRenderer.on('terminal:notify', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('terminal:notify', handler);
+  },
+  paste: async (id: string, data: string, confirmed?: boolean): Promise<void> => {
+    const response = await ipcRenderer.invoke('terminal:paste', id, data, confirmed);
+    unwrap(response);
+  },
+  getPasteLimits: async (): Promise<TerminalPasteLimits> => {
+    const response = await ipcRenderer.invoke('terminal:get-paste-limits');
+    return unwrap(response);
+  },
+  setPasteLimits: async (limits: Partial<TerminalPasteLimits>): Promise<TerminalPasteLimits> => {
+    const response = await ipcRenderer.invoke('terminal:set-paste-limits', limits);
+    return unwrap(response);
+  },
+  getLastOutput: async (id: string): Promise<LastTerminalOutput> => {
+    const response = await ipcRenderer.invoke('terminal:get-last-output', id);
+    return unwrap(response);
+  },
+  list: async (): Promise<TerminalInfo[]> => {
+    const response = await ipcRenderer.invoke('terminal:list');
+    return unwrap(response);
+  },
+  onAltScreen: (callback: (id: string, active: boolean) => void) => {
+    const handler = (_event: any, id: string, active: boolean) => callback(id, active);
+    ipcRenderer.on('terminal:altscreen', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('terminal:altscreen', handler);
+  },
+  getBuffer: async (
+    id: string,
+    range?: { start?: number; length?: number }
+  ): Promise<TerminalBufferRange> => {
+    const response = await ipcRenderer.invoke('terminal:get-buffer', id, range);
+    return unwrap(response);
+  },
+  exportEnv: async (id: string, vars: Record<string, string | null>): Promise<void> => {
+    const response = await ipcRenderer.invoke('terminal:export-env', id, vars);
+    unwrap(response);
+  },
+  debugPod: async (id: string, options: DebugPodOptions): Promise<{ id: string }> => {
+    const response = await ipcRenderer.invoke('terminal:debug-pod', id, options);
+    return unwrap(response);
+  },
+  runScript: async (id: string, scriptPath: string): Promise<{ scriptId: string; total: number }> => {
+    const response = await ipcRenderer.invoke('terminal:run-script', id, scriptPath);
+    return unwrap(response);
+  },
+  cancelScript: async (id: string): Promise<{ cancelled: boolean }> => {
+    const response = await ipcRenderer.invoke('terminal:cancel-script', id);
+    return unwrap(response);
+  },
+  onScriptProgress: (callback: (event: ScriptProgressEvent) => void) => {
+    const handler = (_event: any, payload: ScriptProgressEvent) => callback(payload);
+    ipcRenderer.on('terminal:script-progress', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('terminal:script-progress', handler);
+  },
+  onScriptDone: (callback: (event: ScriptDoneEvent) => void) => {
+    const handler = (_event: any, payload: ScriptDoneEvent) => callback(payload);
+    ipcRenderer.on('terminal:script-done', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('terminal:script-done', handler);
+  },
+  openNodeShell: async (id: string, options: NodeShellOptions): Promise<{ id: string }> => {
+    const response = await ipcRenderer.invoke('terminal:open-node-shell', id, options);
+    return unwrap(response);
+  },
+  openSsh: async (id: string, options: SshTerminalOptions): Promise<{ id: string }> => {
+    const response = await ipcRenderer.invoke('terminal:open-ssh', id, options);
+    return unwrap(response);
+  },
+  listProfiles: async (): Promise<TerminalProfile[]> => {
+    const response = await ipcRenderer.invoke('terminal:list-profiles');
+    return unwrap(response);
+  },
+  saveProfile: async (profile: TerminalProfile): Promise<TerminalProfile> => {
+    const response = await ipcRenderer.invoke('terminal:save-profile', profile);
+    return unwrap(response);
+  },
+  listOrphanedProcesses: async (): Promise<OrphanedProcess[]> => {
+    const response = await ipcRenderer.invoke('terminal:list-orphaned-processes');
+    return unwrap(response);
+  },
+  reapOrphans: async (): Promise<ReapResult[]> => {
+    const response = await ipcRenderer.invoke('terminal:reap-orphans');
+    return unwrap(response);
+  },
+  createBroadcastGroup: async (terminalIds: string[]): Promise<BroadcastGroup> => {
+    const response = await ipcRenderer.invoke('terminal:create-broadcast-group', terminalIds);
+    return unwrap(response);
+  },
+  broadcastWrite: async (groupId: string, data: string): Promise<BroadcastWriteResult[]> => {
+    const response = await ipcRenderer.invoke('terminal:broadcast-write', groupId, data);
+    return unwrap(response);
+  },
+  deleteBroadcastGroup: async (groupId: string): Promise<{ deleted: boolean }> => {
+    const response = await ipcRenderer.invoke('terminal:delete-broadcast-group', groupId);
+    return unwrap(response);
+  },
+  interrupt: async (id: string): Promise<InterruptResult> => {
+    const response = await ipcRenderer.invoke('terminal:interrupt', id);
+    return unwrap(response);
+  },
+  attachOutputPipe: async (id: string, pipePath: string): Promise<OutputPipeStatus> => {
+    const response = await ipcRenderer.invoke('terminal:attach-output-pipe', id, pipePath);
+    return unwrap(response);
+  },
+  detachOutputPipe: async (id: string): Promise<OutputPipeStatus | null> => {
+    const response = await ipcRenderer.invoke('terminal:detach-output-pipe', id);
+    return unwrap(response);
+  },
+  exportText: async (id: string, stripAnsi?: boolean): Promise<TerminalTextExport> => {
+    const response = await ipcRenderer.invoke('terminal:export-text', id, stripAnsi);
+    return unwrap(response);
+  },
+  startWebSocket: async (id: string, port?: number): Promise<TerminalWebSocketStatus> => {
+    const response = await ipcRenderer.invoke('terminal:start-web-socket', id, port);
+    return unwrap(response);
+  },
+  stopWebSocket: async (id: string): Promise<{ stopped: boolean }> => {
+    const response = await ipcRenderer.invoke('terminal:stop-web-socket', id);
+    return unwrap(response);
+  },
+  snapshot: async (): Promise<TerminalSnapshot> => {
+    const response = await ipcRenderer.invoke('terminal:snapshot');
+    return unwrap(response);
+  },
+  restoreSnapshot: async (snapshot: TerminalSnapshot): Promise<TerminalRestoreResult> => {
+    const response = await ipcRenderer.invoke('terminal:restore-snapshot', snapshot);
+    return unwrap(response);
+  },
+  getDiagnostics: async (): Promise<TerminalDiagnostics> => {
+    const response = await ipcRenderer.invoke('terminal:get-diagnostics');
+    return unwrap(response);
+  },
+  getInputHistory: async (profile: string | null): Promise<{ enabled: boolean; lines: string[] }> => {
+    const response = await ipcRenderer.invoke('terminal:get-input-history', profile);
+    return unwrap(response);
+  },
+  setInputHistoryEnabled: async (enabled: boolean): Promise<{ enabled: boolean }> => {
+    const response = await ipcRenderer.invoke('terminal:set-input-history-enabled', enabled);
+    return unwrap(response);
+  },
+  clearInputHistory: async (profile?: string | null): Promise<{ cleared: boolean }> => {
+    const response = await ipcRenderer.invoke('terminal:clear-input-history', profile);
+    return unwrap(response);
+  },
+  onError: (callback: (id: string, message: string) => void) => {
+    const handler = (_event: any, id: string, message: string) => callback(id, message);
+    ipcRenderer.on('terminal:error', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('terminal:error', handler);
+  },
+  getReaderStats: async (): Promise<PtyReaderStats> => {
+    const response = await ipcRenderer.invoke('terminal:get-reader-stats');
+    return unwrap(response);
+  },
+  reconcile: async (): Promise<TerminalReconcileReport> => {
+    const response = await ipcRenderer.invoke('terminal:reconcile');
+    return unwrap(response);
+  },
+  runCommand: async (spec: LocalCommandSpec): Promise<{ commandId: string }> => {
+    const response = await ipcRenderer.invoke('terminal:run-command', spec);
+    return unwrap(response);
+  },
+  stopCommand: async (commandId: string): Promise<{ stopped: boolean }> => {
+    const response = await ipcRenderer.invoke('terminal:stop-command', commandId);
+    return unwrap(response);
+  },
+  onCommandData: (callback: (commandId: string, data: string) => void) => {
+    const handler = (_event: any, commandId: string, data: string) => callback(commandId, data);
+    ipcRenderer.on('command:data', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('command:data', handler);
+  },
+  onCommandExit: (callback: (event: LocalCommandExitEvent) => void) => {
+    const handler = (_event: any, payload: LocalCommandExitEvent) => callback(payload);
+    ipcRenderer.on('command:exit', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('command:exit', handler);
+  },
+  detectShell: async (id: string, probe?: boolean): Promise<ShellDetection> => {
+    const response = await ipcRenderer.invoke('terminal:detect-shell', id, probe);
+    return unwrap(response);
+  },
+  measureLatency: async (
+    id: string,
+    params?: { method?: TerminalLatencyMethod; timeoutMs?: number }
+  ): Promise<TerminalLatencyResult> => {
+    const response = await ipcRenderer.invoke('terminal:measure-latency', id, params);
+    return unwrap(response);
+  },
+  checkCwd: async (directory: string): Promise<CwdCheck> => {
+    const response = await ipcRenderer.invoke('terminal:check-cwd', directory);
+    return unwrap(response);
+  },
+  runCommandsSequenced: async (
+    id: string,
+    commands: string[],
+    params?: { timeoutMs?: number }
+  ): Promise<SequencedCommandResult[]> => {
+    const response = await ipcRenderer.invoke('terminal:run-commands-sequenced', id, commands, params);
+    return unwrap(response);
+  },
+  getForegroundProcess: async (id: string): Promise<ForegroundProcessInfo> => {
+    const response = await ipcRenderer.invoke('terminal:get-foreground-process', id);
+    return unwrap(response);
+  },
+  createResilientExec: async (id: string, options: ResilientExecOptions): Promise<{ id: string; pod: string }> => {
+    const response = await ipcRenderer.invoke('terminal:create-resilient-exec', id, options);
+    return unwrap(response);
+  },
+  onReconnected: (callback: (event: TerminalReconnectedEvent) => void) => {
+    const handler = (_event: any, payload: TerminalReconnectedEvent) => callback(payload);
+    ipcRenderer.on('terminal:reconnected', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('terminal:reconnected', handler);
+  },
+  onReconnectStatus: (callback: (event: ResilientExecStatusEvent) => void) => {
+    const handler = (_event: any, payload: ResilientExecStatusEvent) => callback(payload);
+    ipcRenderer.on('terminal:reconnect-status', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('terminal:reconnect-status', handler);
+  },
+});
+
+contextBridge.exposeInMainWorld('app', {
+  setLogLevel: async (level: string): Promise<{ level: LogLevel }> => {
+    const response = await ipcRenderer.invoke('app:set-log-level', level);
+    return unwrap(response);
+  },
+  getLogSettings: async (): Promise<LogSettings> => {
+    const response = await ipcRenderer.invoke('app:get-log-settings');
+    return unwrap(response);
+  },
+  getLogFilePath: async (): Promise<{ path: string | null }> => {
+    const response = await ipcRenderer.invoke('app:get-log-file-path');
+    return unwrap(response);
+  },
+  getCommandPolicy: async (): Promise<CommandPolicy> => {
+    const response = await ipcRenderer.invoke('app:get-command-policy');
+    return unwrap(response);
+  },
+  selfTest: async (): Promise<SelfTestReport> => {
+    const response = await ipcRenderer.invoke('app:self-test');
+    return unwrap(response);
+  },
+  getActionLog: async (filter?: ActionLogFilter): Promise<ActionLogEntry[]> => {
+    const response = await ipcRenderer.invoke('app:get-action-log', filter);
+    return unwrap(response);
+  },
+  onActionLogEntry: (callback: (entry: ActionLogEntry) => void) => {
+    const handler = (_event: any, entry: ActionLogEntry) => callback(entry);
+    ipcRenderer.on('action-log:entry', handler);
+    // Return cleanup function
+    return () => ipcRenderer.removeListener('action-log:entry', handler);
+  },
 });
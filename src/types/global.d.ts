@@ -1,21 +1,752 @@
-import type { KubeConfigSummary, KubectlResult } from '../common/kubeTypes';
+import type {
+  AllContainerLogs,
+  ApiResource,
+  ApplyDoneEvent,
+  ApplyObjectEvent,
+  AuthStatus,
+  BackgroundSession,
+  CancelledOperationCounts,
+  CloudAuthDetection,
+  ClusterCertStatus,
+  ClusterDetailEvent,
+  ClusterDetailList,
+  ClusterEvent,
+  ConfigMapContents,
+  ContainerImageStatus,
+  ContainerProbes,
+  ContainerRestartInfo,
+  ContextOverrides,
+  ContextSearchResult,
+  ContextTags,
+  CrdSchema,
+  DeleteItemResult,
+  DeletePreview,
+  DeploymentConditionEvent,
+  DirectoryApplyResult,
+  DiscoveryCacheStatus,
+  EffectivePermissions,
+  EventRateEvent,
+  ExecStdinProgressEvent,
+  ForwardExitedEvent,
+  ForwardInfo,
+  ForwardStatusEvent,
+  HpaStatus,
+  IngressRouting,
+  JobStatusEvent,
+  JsonLogEvent,
+  JsonLogFieldMap,
+  KillPodsResult,
+  KubeconfigImportResult,
+  KubeconfigRepairResult,
+  KubeconfigRepairStrategy,
+  KubeConfigSummary,
+  KubeconfigValidation,
+  KubectlPlugin,
+  KubectlPoolLimits,
+  KubectlPoolStats,
+  KubectlResult,
+  KustomizeApplyResult,
+  LatencySample,
+  LogExportResult,
+  LogGrepResult,
+  LogLevelSettings,
+  LogLinesEvent,
+  LogSink,
+  LogSinkStatus,
+  LogStreamSink,
+  LogStreamEndedEvent,
+  ManifestDiffResult,
+  MergedKubeconfig,
+  MergedLogs,
+  MetricsSample,
+  MetricsTargetKind,
+  MetricsUpdateEvent,
+  NamespaceExportResult,
+  NodeNotReadyEvent,
+  NodeStatus,
+  NodeWatchEvent,
+  OperationScope,
+  OwnerTree,
+  PatchType,
+  PodFileDataEvent,
+  PodFileDownloadEvent,
+  PodFileEndEvent,
+  PodFileErrorEvent,
+  PodKilledEvent,
+  PodPathChangeEvent,
+  PodPhaseEvent,
+  PodResources,
+  PodSpread,
+  PodTableUpdateEvent,
+  PortForwardTargetKind,
+  PvcStatusEvent,
+  RawApiResponse,
+  RawStreamDataEvent,
+  RawStreamEndedEvent,
+  ResolvedContext,
+  ResourceComparison,
+  ResourceDeletedEvent,
+  ResourceDrift,
+  ResourceMetadata,
+  ResourcePage,
+  ResourceRef,
+  ResourceSummary,
+  ResourcesYamlResult,
+  ResourceWatchEvent,
+  RestartLeaderboard,
+  RevisionManifest,
+  RolloutHistory,
+  RolloutPauseState,
+  RolloutProgressEvent,
+  ServerDryRunResult,
+  ServiceAccountKubeconfig,
+  ServiceEndpoints,
+  SessionReattachResult,
+  StorageClassList,
+  StreamReconnectedEvent,
+  StreamReconnectingEvent,
+  TemplateApplyResult,
+  UserIdentity,
+  WaitProgressEvent,
+  WaitResultEvent,
+  WorkloadHealthReport,
+} from '../common/kubeTypes';
+import type {
+  ActionLogEntry,
+  ActionLogFilter,
+  CommandPolicy,
+  LogLevel,
+  LogSettings,
+  SelfTestReport,
+} from '../common/appTypes';
+import type {
+  AttachToPodOptions,
+  BroadcastGroup,
+  BroadcastWriteResult,
+  CwdCheck,
+  DebugPodOptions,
+  ForegroundProcessInfo,
+  InterruptResult,
+  LastTerminalOutput,
+  LocalCommandExitEvent,
+  LocalCommandSpec,
+  NodeShellOptions,
+  OrphanedProcess,
+  OutputPipeStatus,
+  PtyReaderStats,
+  ReapResult,
+  ResilientExecOptions,
+  ResilientExecStatusEvent,
+  ScriptDoneEvent,
+  ScriptProgressEvent,
+  SequencedCommandResult,
+  SessionBundleSummary,
+  ShellDetection,
+  SshTerminalOptions,
+  TerminalBufferRange,
+  TerminalCreateOptions,
+  TerminalDiagnostics,
+  TerminalInfo,
+  TerminalLatencyMethod,
+  TerminalLatencyResult,
+  TerminalNotifyEvent,
+  TerminalPasteLimits,
+  TerminalProfile,
+  TerminalReconcileReport,
+  TerminalReconnectedEvent,
+  TerminalRestoreResult,
+  TerminalSnapshot,
+  TerminalTextExport,
+  TerminalWebSocketStatus,
+} from '../common/terminalTypes';
 
 declare global {
   interface Window {
     kube?: {
-      getContexts: () => Promise<KubeConfigSummary>;
+      getContexts: (filter?: string) => Promise<KubeConfigSummary>;
       setContext: (contextName: string) => Promise<KubeConfigSummary>;
       setConfig: (configPath: string) => Promise<KubeConfigSummary>;
-      runCommand: (context: string, command: string) => Promise<KubectlResult>;
+      runCommand: (context: string, command: string, view?: string) => Promise<KubectlResult>;
+      listApiResources: (context: string, refresh?: boolean) => Promise<ApiResource[]>;
+      warmCache: (context: string, refresh?: boolean) => Promise<DiscoveryCacheStatus>;
+      clearCache: (context?: string) => Promise<{ cleared: boolean }>;
+      diffManifest: (content: string, context: string, namespace?: string) => Promise<ManifestDiffResult>;
+      watchPod: (pod: string, namespace: string, context: string) => Promise<{ watchId: string }>;
+      stopWatch: (watchId: string) => Promise<{ stopped: boolean }>;
+      streamLogsForSelector: (params: {
+        labelSelector: string;
+        namespace: string;
+        container?: string;
+        context: string;
+        tailLines?: number;
+        json?: boolean;
+        jsonFields?: Partial<Record<keyof JsonLogFieldMap, string | string[]>>;
+      }) => Promise<{ streamId: string }>;
+      stopLogStream: (streamId: string) => Promise<{ stopped: boolean }>;
+      onLogData: (callback: (event: LogLinesEvent) => void) => (() => void);
+      onLogError: (callback: (streamId: string, message: string) => void) => (() => void);
+      onPodPhase: (callback: (event: PodPhaseEvent) => void) => (() => void);
+      onWatchError: (callback: (watchId: string, message: string) => void) => (() => void);
+      startMetricsStream: (params: {
+        context: string;
+        kind: MetricsTargetKind;
+        namespace?: string;
+        intervalMs?: number;
+      }) => Promise<{ streamId: string }>;
+      stopMetricsStream: (streamId: string) => Promise<{ stopped: boolean }>;
+      getMetricsHistory: (streamId: string) => Promise<Record<string, MetricsSample[]>>;
+      onMetricsUpdate: (callback: (event: MetricsUpdateEvent) => void) => (() => void);
+      onMetricsError: (callback: (streamId: string, message: string) => void) => (() => void);
+      cancelOperations: (filter?: OperationScope) => Promise<CancelledOperationCounts>;
+      setKubectlForContext: (context: string, path: string) => Promise<{ context: string; path: string | null }>;
+      getKubectlForContext: (context: string) => Promise<{ context: string; path: string | null }>;
+      streamEvents: (params: {
+        context: string;
+        namespace?: string;
+        warningsOnly?: boolean;
+      }) => Promise<{ streamId: string }>;
+      onClusterEvent: (callback: (event: ClusterEvent) => void) => (() => void);
+      startApiProxy: (params: {
+        context: string;
+        port?: number;
+        autoRestart?: boolean;
+      }) => Promise<ForwardInfo>;
+      stopApiProxy: (id: string) => Promise<{ stopped: boolean }>;
+      listForwards: () => Promise<ForwardInfo[]>;
+      onForwardExited: (callback: (event: ForwardExitedEvent) => void) => (() => void);
+      onForwardRestarted: (callback: (info: ForwardInfo) => void) => (() => void);
+      getResourcesYaml: (params: {
+        refs: ResourceRef[];
+        context: string;
+        stripManagedFields?: boolean;
+        stripStatus?: boolean;
+        neat?: boolean;
+      }) => Promise<ResourcesYamlResult>;
+      runPlugin: (params: {
+        name: string;
+        args?: string[];
+        context: string;
+        namespace?: string;
+      }) => Promise<KubectlResult>;
+      listPlugins: () => Promise<KubectlPlugin[]>;
+      whoCanI: (params: {
+        namespace: string;
+        context: string;
+        refresh?: boolean;
+      }) => Promise<EffectivePermissions>;
+      streamPodFile: (params: {
+        pod: string;
+        namespace: string;
+        container?: string;
+        path: string;
+        context: string;
+        follow?: boolean;
+        tailLines?: number;
+      }) => Promise<{ streamId: string }>;
+      stopPodFileStream: (streamId: string) => Promise<{ stopped: boolean }>;
+      onFileData: (callback: (event: PodFileDataEvent) => void) => (() => void);
+      onFileError: (callback: (event: PodFileErrorEvent) => void) => (() => void);
+      onFileEnd: (callback: (event: PodFileEndEvent) => void) => (() => void);
+      getPodImages: (params: {
+        pod: string;
+        namespace: string;
+        context: string;
+      }) => Promise<ContainerImageStatus[]>;
+      startPortForward: (params: {
+        context: string;
+        namespace: string;
+        targetKind: PortForwardTargetKind;
+        name: string;
+        remotePort: number;
+        localPort?: number;
+        autoRestart?: boolean;
+      }) => Promise<ForwardInfo>;
+      stopPortForward: (id: string) => Promise<{ stopped: boolean }>;
+      onForwardStatus: (callback: (event: ForwardStatusEvent) => void) => (() => void);
+      getNodeStatus: (params: { name: string; context: string }) => Promise<NodeStatus>;
+      getAuthStatus: (contextName: string) => Promise<AuthStatus>;
+      watchSelected: (params: {
+        kind: string;
+        labelSelector?: string;
+        fieldSelector?: string;
+        namespace?: string;
+        context: string;
+        coalesceMs?: number;
+      }) => Promise<{ watchId: string }>;
+      onWatchEvent: (callback: (event: ResourceWatchEvent) => void) => (() => void);
+      getPodLogs: (params: {
+        context: string;
+        pod: string;
+        namespace: string;
+        container?: string;
+        previous?: boolean;
+        tailLines?: number;
+      }) => Promise<{ logs: string }>;
+      getPreviousLogs: (params: {
+        context: string;
+        pod: string;
+        namespace: string;
+        container?: string;
+        tailLines?: number;
+      }) => Promise<{ logs: string }>;
+      getResourceSummary: (contextName: string) => Promise<ResourceSummary>;
+      pauseLogStream: (params: {
+        streamId: string;
+        paused: boolean;
+      }) => Promise<{ paused: boolean; flushedLines: number }>;
+      deleteBySelector: (params: {
+        kind: string;
+        labelSelector: string;
+        namespace?: string;
+        context: string;
+        confirmToken?: string;
+        gracePeriodSeconds?: number;
+      }) => Promise<{ preview?: DeletePreview; results?: DeleteItemResult[] }>;
+      onResourceDeleted: (callback: (event: ResourceDeletedEvent) => void) => (() => void);
+      getWorkloadHealth: (params: { namespace?: string; context: string }) => Promise<WorkloadHealthReport>;
+      watchPodsTable: (params: {
+        namespace?: string;
+        context: string;
+        node?: string;
+        view?: string;
+      }) => Promise<{ watchId: string }>;
+      onPodsTableUpdate: (callback: (event: PodTableUpdateEvent) => void) => (() => void);
+      serverDryRunApply: (params: {
+        content: string;
+        context: string;
+        namespace?: string;
+      }) => Promise<ServerDryRunResult>;
+      getContainerRestarts: (params: {
+        pod: string;
+        namespace: string;
+        context: string;
+      }) => Promise<ContainerRestartInfo[]>;
+      applyManifestStreaming: (params: {
+        content: string;
+        context: string;
+        namespace?: string;
+        continueOnError?: boolean;
+        parallelism?: number;
+        dryRun?: boolean;
+        view?: string;
+      }) => Promise<{ applyId: string; total: number }>;
+      onApplyObject: (callback: (event: ApplyObjectEvent) => void) => (() => void);
+      onApplyDone: (callback: (event: ApplyDoneEvent) => void) => (() => void);
+      getRolloutHistory: (params: {
+        kind: string;
+        name: string;
+        namespace: string;
+        context: string;
+      }) => Promise<RolloutHistory>;
+      rolloutUndo: (params: {
+        kind: string;
+        name: string;
+        namespace: string;
+        context: string;
+        toRevision?: number;
+        view?: string;
+      }) => Promise<{ rolloutId: string; revision: number | null; message: string }>;
+      onRolloutProgress: (callback: (event: RolloutProgressEvent) => void) => (() => void);
+      startLatencyProbe: (params: {
+        context: string;
+        intervalMs?: number;
+        view?: string;
+      }) => Promise<{ probeId: string }>;
+      stopLatencyProbe: (probeId: string) => Promise<{ stopped: boolean }>;
+      getLatencyHistory: (probeId: string) => Promise<LatencySample[]>;
+      onLatencySample: (callback: (sample: LatencySample) => void) => (() => void);
+      resolveContext: (params: {
+        name: string;
+        kubeconfigPath?: string;
+        overrides?: ContextOverrides;
+      }) => Promise<ResolvedContext>;
+      getAllContainerLogs: (params: {
+        context: string;
+        pod: string;
+        namespace: string;
+        tailLines?: number;
+        includeInit?: boolean;
+      }) => Promise<AllContainerLogs>;
+      watchPvc: (name: string, namespace: string, context: string) => Promise<{ watchId: string }>;
+      onPvcStatus: (callback: (event: PvcStatusEvent) => void) => (() => void);
+      getRevisionManifest: (params: {
+        kind: string;
+        name: string;
+        namespace: string;
+        revision: number;
+        context: string;
+      }) => Promise<RevisionManifest>;
+      downloadPodFile: (params: {
+        context: string;
+        pod: string;
+        namespace: string;
+        container?: string;
+        remotePath: string;
+        localPath: string;
+        view?: string;
+      }) => Promise<{ downloadId: string }>;
+      onDownloadProgress: (callback: (event: PodFileDownloadEvent) => void) => (() => void);
+      listClustersDetailed: (kubeconfigPath?: string) => Promise<ClusterDetailList>;
+      onClusterDetail: (callback: (event: ClusterDetailEvent) => void) => (() => void);
+      runKubectlStdin: (params: {
+        args: string[];
+        stdin: string;
+        context: string;
+        namespace?: string;
+        timeoutMs?: number;
+      }) => Promise<KubectlResult>;
+      rolloutPause: (params: {
+        kind: string;
+        name: string;
+        namespace: string;
+        context: string;
+      }) => Promise<RolloutPauseState>;
+      rolloutResume: (params: {
+        kind: string;
+        name: string;
+        namespace: string;
+        context: string;
+      }) => Promise<RolloutPauseState>;
+      onWatchReconnecting: (callback: (event: StreamReconnectingEvent) => void) => (() => void);
+      onWatchReconnected: (callback: (event: StreamReconnectedEvent) => void) => (() => void);
+      onLogReconnecting: (callback: (event: StreamReconnectingEvent) => void) => (() => void);
+      onLogReconnected: (callback: (event: StreamReconnectedEvent) => void) => (() => void);
+      onForwardReconnecting: (callback: (event: StreamReconnectingEvent) => void) => (() => void);
+      onForwardReconnected: (callback: (event: StreamReconnectedEvent) => void) => (() => void);
+      getMergedKubeconfig: () => Promise<MergedKubeconfig>;
+      onLogJson: (callback: (event: JsonLogEvent) => void) => (() => void);
+      getOwnerTree: (params: {
+        kind: string;
+        name: string;
+        namespace: string;
+        context: string;
+        includeDescendants?: boolean;
+        maxDepth?: number;
+      }) => Promise<OwnerTree>;
+      killPods: (params: {
+        labelSelector: string;
+        namespace: string;
+        context: string;
+        gracePeriodSeconds?: number;
+        confirm?: boolean;
+        dryRun?: boolean;
+      }) => Promise<KillPodsResult>;
+      onPodKilled: (callback: (event: PodKilledEvent) => void) => (() => void);
+      getCrdSchema: (params: {
+        group: string;
+        version: string;
+        kind: string;
+        context: string;
+        refresh?: boolean;
+      }) => Promise<CrdSchema>;
+      getMergedLogs: (params: {
+        context: string;
+        pod: string;
+        namespace: string;
+        tailLines?: number;
+        includeInit?: boolean;
+      }) => Promise<MergedLogs>;
+      detectCloudAuth: (contextName: string) => Promise<CloudAuthDetection>;
+      grepLogs: (params: {
+        context: string;
+        namespace: string;
+        labelSelector: string;
+        pattern: string;
+        ignoreCase?: boolean;
+        container?: string;
+        tailLines?: number;
+        contextLines?: number;
+      }) => Promise<LogGrepResult>;
+      patchResource: (params: {
+        kind: string;
+        name: string;
+        namespace?: string;
+        patch: string | object;
+        patchType?: PatchType;
+        context: string;
+      }) => Promise<any>;
+      watchJob: (name: string, namespace: string, context: string) => Promise<{ watchId: string }>;
+      triggerCronJob: (
+        name: string,
+        namespace: string,
+        context: string
+      ) => Promise<{ jobName: string; watchId: string }>;
+      onJobStatus: (callback: (event: JobStatusEvent) => void) => (() => void);
+      getPodProbes: (params: { pod: string; namespace: string; context: string }) => Promise<ContainerProbes[]>;
+      execWithStdinFile: (params: {
+        context: string;
+        pod: string;
+        namespace: string;
+        container?: string;
+        command: string[];
+        stdinPath: string;
+        view?: string;
+      }) => Promise<{ execId: string; totalBytes: number }>;
+      onExecProgress: (callback: (event: ExecStdinProgressEvent) => void) => (() => void);
+      compareResourceAcross: (params: {
+        kind: string;
+        name: string;
+        a: { context: string; namespace?: string };
+        b: { context: string; namespace?: string };
+      }) => Promise<ResourceComparison>;
+      searchContexts: (params: { pattern: string; fuzzy?: boolean; limit?: number }) => Promise<ContextSearchResult>;
+      waitForCondition: (params: {
+        context: string;
+        kind: string;
+        name: string;
+        namespace?: string;
+        condition: string;
+        timeoutSeconds?: number;
+        view?: string;
+      }) => Promise<{ waitId: string }>;
+      cancelWait: (waitId: string) => Promise<{ cancelled: boolean }>;
+      onWaitProgress: (callback: (event: WaitProgressEvent) => void) => (() => void);
+      onWaitResult: (callback: (event: WaitResultEvent) => void) => (() => void);
+      setLabels: (params: {
+        kind: string;
+        name: string;
+        namespace?: string;
+        labels: Record<string, string | null>;
+        overwrite?: boolean;
+        context: string;
+      }) => Promise<ResourceMetadata>;
+      setAnnotations: (params: {
+        kind: string;
+        name: string;
+        namespace?: string;
+        annotations: Record<string, string | null>;
+        overwrite?: boolean;
+        context: string;
+      }) => Promise<ResourceMetadata>;
+      getPodResources: (params: { pod: string; namespace: string; context: string }) => Promise<PodResources>;
+      watchPodPath: (params: {
+        context: string;
+        pod: string;
+        namespace: string;
+        container?: string;
+        path: string;
+        intervalMs?: number;
+        view?: string;
+      }) => Promise<{ watchId: string }>;
+      stopPodPathWatch: (watchId: string) => Promise<{ stopped: boolean }>;
+      onPodPathChange: (callback: (event: PodPathChangeEvent) => void) => (() => void);
+      setLogSink: (params: { streamId: string; sink: LogSink; filePath?: string }) => Promise<LogSinkStatus>;
+      listLogSinks: () => Promise<LogStreamSink[]>;
+      setContextTags: (params: { name: string; tags: string[] | null }) => Promise<ContextTags>;
+      getHpaStatus: (params: { name: string; namespace: string; context: string }) => Promise<HpaStatus>;
+      watchNodes: (params: {
+        context: string;
+        labelSelector?: string;
+        fieldSelector?: string;
+        view?: string;
+      }) => Promise<{ watchId: string }>;
+      onNodeEvent: (callback: (event: NodeWatchEvent) => void) => (() => void);
+      onNodeNotReady: (callback: (event: NodeNotReadyEvent) => void) => (() => void);
+      applyTemplate: (params: {
+        template: string;
+        variables: Record<string, string>;
+        context: string;
+        namespace?: string;
+        dryRun?: boolean;
+        view?: string;
+      }) => Promise<TemplateApplyResult>;
+      validateKubeconfig: () => Promise<KubeconfigValidation>;
+      repairKubeconfig: (strategy: KubeconfigRepairStrategy) => Promise<KubeconfigRepairResult>;
+      streamJobLogs: (params: {
+        context: string;
+        namespace: string;
+        job: string;
+        container?: string;
+        tailLines?: number;
+        view?: string;
+      }) => Promise<{ streamId: string }>;
+      onLogStreamEnded: (callback: (event: LogStreamEndedEvent) => void) => (() => void);
+      whoAmI: (context: string) => Promise<UserIdentity>;
+      getServiceEndpoints: (name: string, namespace: string, context: string) => Promise<ServiceEndpoints>;
+      exportLogs: (params: {
+        context: string;
+        pod: string;
+        namespace: string;
+        container?: string;
+        since: string;
+        until?: string;
+        outputPath?: string;
+      }) => Promise<LogExportResult>;
+      getConfigMap: (params: { name: string; namespace: string; context: string }) => Promise<ConfigMapContents>;
+      updateConfigMapKey: (params: {
+        name: string;
+        namespace: string;
+        key: string;
+        value: string;
+        context: string;
+      }) => Promise<ConfigMapContents>;
+      startEventRateMonitor: (params: {
+        context: string;
+        namespace?: string;
+        windowMs?: number;
+        view?: string;
+      }) => Promise<{ monitorId: string }>;
+      stopEventRateMonitor: (monitorId: string) => Promise<{ stopped: boolean }>;
+      onEventRate: (callback: (event: EventRateEvent) => void) => (() => void);
+      getIngress: (params: { name: string; namespace: string; context: string }) => Promise<IngressRouting>;
+      formatAges: (timestamps: (string | null)[]) => Promise<string[]>;
+      exportNamespace: (params: {
+        namespace: string;
+        context: string;
+        outputDir: string;
+        kinds?: string[];
+      }) => Promise<NamespaceExportResult>;
+      applyDirectory: (params: { dir: string; context: string; namespace?: string }) => Promise<DirectoryApplyResult>;
+      getKubectlPoolStats: () => Promise<KubectlPoolStats>;
+      setKubectlPoolLimits: (limits: Partial<KubectlPoolLimits>) => Promise<KubectlPoolStats>;
+      checkClusterCert: (contextName: string) => Promise<ClusterCertStatus>;
+      getDrift: (params: {
+        kind: string;
+        name: string;
+        namespace?: string;
+        context: string;
+      }) => Promise<ResourceDrift>;
+      getLogLevelSettings: () => Promise<LogLevelSettings>;
+      setLogLevelSettings: (settings: Partial<LogLevelSettings>) => Promise<LogLevelSettings>;
+      promoteToBackground: (params: { id: string; label?: string; view?: string }) => Promise<BackgroundSession>;
+      listBackgroundSessions: () => Promise<BackgroundSession[]>;
+      reattachSession: (id: string, view?: string) => Promise<SessionReattachResult>;
+      closeBackgroundSession: (id: string) => Promise<{ closed: boolean }>;
+      listResourcesPaged: (spec: {
+        context: string;
+        kind: string;
+        namespace?: string;
+        limit?: number;
+        continueToken?: string;
+        labelSelector?: string;
+        fieldSelector?: string;
+        view?: string;
+      }) => Promise<ResourcePage>;
+      generateServiceAccountKubeconfig: (spec: {
+        serviceAccount: string;
+        namespace: string;
+        context: string;
+        outputPath?: string;
+        durationSeconds?: number;
+      }) => Promise<ServiceAccountKubeconfig>;
+      startRestartLeaderboard: (params: {
+        context: string;
+        namespace?: string;
+        limit?: number;
+        view?: string;
+      }) => Promise<{ leaderboardId: string }>;
+      getRestartLeaderboard: (leaderboardId: string) => Promise<RestartLeaderboard>;
+      stopRestartLeaderboard: (leaderboardId: string) => Promise<{ stopped: boolean }>;
+      onRestartsUpdate: (callback: (event: RestartLeaderboard) => void) => (() => void);
+      getRaw: (params: { path: string; context: string; view?: string }) => Promise<RawApiResponse>;
+      streamRaw: (spec: { path: string; context: string; view?: string }) => Promise<{ streamId: string }>;
+      stopRawStream: (streamId: string) => Promise<{ stopped: boolean }>;
+      onRawData: (callback: (event: RawStreamDataEvent) => void) => (() => void);
+      onRawEnded: (callback: (event: RawStreamEndedEvent) => void) => (() => void);
+      getWorkloadPodSpread: (params: {
+        kind: string;
+        name: string;
+        namespace: string;
+        context: string;
+      }) => Promise<PodSpread>;
+      watchDeployment: (name: string, namespace: string, context: string) => Promise<{ watchId: string }>;
+      onDeploymentCondition: (callback: (event: DeploymentConditionEvent) => void) => (() => void);
+      listStorageClasses: (context: string) => Promise<StorageClassList>;
+      applyKustomize: (params: {
+        directory: string;
+        context: string;
+        namespace?: string;
+        buildOnly?: boolean;
+        dryRun?: boolean;
+        continueOnError?: boolean;
+        parallelism?: number;
+        view?: string;
+      }) => Promise<KustomizeApplyResult>;
+      importKubeconfig: (params: { sourcePath: string; merge?: boolean }) => Promise<KubeconfigImportResult>;
     };
     terminal?: {
-      create: (id: string, options?: { cwd?: string; env?: Record<string, string> }) => Promise<{ id: string }>;
+      create: (id: string, options?: TerminalCreateOptions) => Promise<{ id: string }>;
+      attachToPod: (id: string, options: AttachToPodOptions) => Promise<{ id: string }>;
       write: (id: string, data: string) => Promise<void>;
-      resize: (id: string, cols: number, rows: number) => Promise<void>;
+      resize: (id: string, cols: number, rows: number, pixelWidth?: number, pixelHeight?: number) => Promise<void>;
       close: (id: string) => Promise<void>;
+      exportSessionBundle: (outputPath: string) => Promise<SessionBundleSummary>;
       onData: (callback: (id: string, data: string) => void) => (() => void);
       onExit: (callback: (id: string, exitCode: number, signal?: number) => void) => (() => void);
+      onWarning: (callback: (id: string, message: string) => void) => (() => void);
       onEditMode: (callback: (id: string, isEditMode: boolean) => void) => (() => void);
+      setClipboardAccess: (id: string, enabled: boolean) => Promise<void>;
+      setNotifyPattern: (id: string, pattern: string) => Promise<void>;
+      onNotify: (callback: (id: string, event: TerminalNotifyEvent) => void) => (() => void);
+      onClipboard: (callback: (id: string, text: string) => void) => (() => void);
+      paste: (id: string, data: string, confirmed?: boolean) => Promise<void>;
+      getPasteLimits: () => Promise<TerminalPasteLimits>;
+      setPasteLimits: (limits: Partial<TerminalPasteLimits>) => Promise<TerminalPasteLimits>;
+      getLastOutput: (id: string) => Promise<LastTerminalOutput>;
+      list: () => Promise<TerminalInfo[]>;
+      onAltScreen: (callback: (id: string, active: boolean) => void) => (() => void);
+      getBuffer: (
+        id: string,
+        range?: { start?: number; length?: number }
+      ) => Promise<TerminalBufferRange>;
+      exportEnv: (id: string, vars: Record<string, string | null>) => Promise<void>;
+      debugPod: (id: string, options: DebugPodOptions) => Promise<{ id: string }>;
+      runScript: (id: string, scriptPath: string) => Promise<{ scriptId: string; total: number }>;
+      cancelScript: (id: string) => Promise<{ cancelled: boolean }>;
+      onScriptProgress: (callback: (event: ScriptProgressEvent) => void) => (() => void);
+      onScriptDone: (callback: (event: ScriptDoneEvent) => void) => (() => void);
+      openNodeShell: (id: string, options: NodeShellOptions) => Promise<{ id: string }>;
+      openSsh: (id: string, options: SshTerminalOptions) => Promise<{ id: string }>;
+      listProfiles: () => Promise<TerminalProfile[]>;
+      saveProfile: (profile: TerminalProfile) => Promise<TerminalProfile>;
+      listOrphanedProcesses: () => Promise<OrphanedProcess[]>;
+      reapOrphans: () => Promise<ReapResult[]>;
+      createBroadcastGroup: (terminalIds: string[]) => Promise<BroadcastGroup>;
+      broadcastWrite: (groupId: string, data: string) => Promise<BroadcastWriteResult[]>;
+      deleteBroadcastGroup: (groupId: string) => Promise<{ deleted: boolean }>;
+      interrupt: (id: string) => Promise<InterruptResult>;
+      attachOutputPipe: (id: string, pipePath: string) => Promise<OutputPipeStatus>;
+      detachOutputPipe: (id: string) => Promise<OutputPipeStatus | null>;
+      exportText: (id: string, stripAnsi?: boolean) => Promise<TerminalTextExport>;
+      startWebSocket: (id: string, port?: number) => Promise<TerminalWebSocketStatus>;
+      stopWebSocket: (id: string) => Promise<{ stopped: boolean }>;
+      snapshot: () => Promise<TerminalSnapshot>;
+      restoreSnapshot: (snapshot: TerminalSnapshot) => Promise<TerminalRestoreResult>;
+      getDiagnostics: () => Promise<TerminalDiagnostics>;
+      getInputHistory: (profile: string | null) => Promise<{ enabled: boolean; lines: string[] }>;
+      setInputHistoryEnabled: (enabled: boolean) => Promise<{ enabled: boolean }>;
+      clearInputHistory: (profile?: string | null) => Promise<{ cleared: boolean }>;
+      onError: (callback: (id: string, message: string) => void) => (() => void);
+      getReaderStats: () => Promise<PtyReaderStats>;
+      reconcile: () => Promise<TerminalReconcileReport>;
+      runCommand: (spec: LocalCommandSpec) => Promise<{ commandId: string }>;
+      stopCommand: (commandId: string) => Promise<{ stopped: boolean }>;
+      onCommandData: (callback: (commandId: string, data: string) => void) => (() => void);
+      onCommandExit: (callback: (event: LocalCommandExitEvent) => void) => (() => void);
+      detectShell: (id: string, probe?: boolean) => Promise<ShellDetection>;
+      measureLatency: (
+        id: string,
+        params?: { method?: TerminalLatencyMethod; timeoutMs?: number }
+      ) => Promise<TerminalLatencyResult>;
+      checkCwd: (directory: string) => Promise<CwdCheck>;
+      runCommandsSequenced: (
+        id: string,
+        commands: string[],
+        params?: { timeoutMs?: number }
+      ) => Promise<SequencedCommandResult[]>;
+      getForegroundProcess: (id: string) => Promise<ForegroundProcessInfo>;
+      createResilientExec: (id: string, options: ResilientExecOptions) => Promise<{ id: string; pod: string }>;
+      onReconnected: (callback: (event: TerminalReconnectedEvent) => void) => (() => void);
+      onReconnectStatus: (callback: (event: ResilientExecStatusEvent) => void) => (() => void);
+    };
+    app?: {
+      setLogLevel: (level: string) => Promise<{ level: LogLevel }>;
+      getLogSettings: () => Promise<LogSettings>;
+      getLogFilePath: () => Promise<{ path: string | null }>;
+      getCommandPolicy: () => Promise<CommandPolicy>;
+      selfTest: () => Promise<SelfTestReport>;
+      getActionLog: (filter?: ActionLogFilter) => Promise<ActionLogEntry[]>;
+      onActionLogEntry: (callback: (entry: ActionLogEntry) => void) => (() => void);
     };
   }
 }